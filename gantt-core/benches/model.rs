@@ -0,0 +1,171 @@
+//! Benchmarks for the model operations that dominate frame time and file I/O
+//! on large projects. Run with `cargo bench`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use chrono::Duration;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use gantt_core::synthetic::synthetic_project;
+use gantt_core::task::{task_matches, TaskPriority};
+use gantt_core::{Project, TimelineViewport, UndoHistory};
+
+const TASK_COUNT: usize = 5_000;
+
+/// The system allocator, counting allocations so the idle-frame benchmark
+/// can report how many a frame makes.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Allocations `f` makes over `frames` calls.
+fn allocations_per(frames: usize, mut f: impl FnMut()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for _ in 0..frames {
+        f();
+    }
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn serialization(c: &mut Criterion) {
+    let project = synthetic_project(TASK_COUNT);
+    let json = serde_json::to_string_pretty(&project).unwrap();
+
+    c.bench_function("serialize 5k tasks", |b| {
+        b.iter(|| serde_json::to_string_pretty(black_box(&project)).unwrap())
+    });
+    c.bench_function("load 5k tasks", |b| {
+        b.iter(|| {
+            let mut loaded: Project = serde_json::from_str(black_box(&json)).unwrap();
+            loaded.rebuild_index();
+            loaded
+        })
+    });
+}
+
+fn hierarchy(c: &mut Criterion) {
+    let project = synthetic_project(TASK_COUNT);
+
+    c.bench_function("recalculate_parent_dates 5k", |b| {
+        b.iter_batched_ref(
+            || project.clone(),
+            |p| p.recalculate_parent_dates(),
+            BatchSize::LargeInput,
+        )
+    });
+    c.bench_function("sort_tasks_grouped 5k", |b| {
+        b.iter_batched_ref(
+            || {
+                // Reversed, so every parent comes after its children.
+                let mut p = project.clone();
+                let mut tasks = p.tasks.clone();
+                tasks.reverse();
+                p.set_tasks(tasks);
+                p
+            },
+            |p| p.sort_tasks_grouped(),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn filtering(c: &mut Criterion) {
+    let project = synthetic_project(TASK_COUNT);
+
+    c.bench_function("task_matches 5k", |b| {
+        b.iter(|| {
+            project
+                .tasks
+                .iter()
+                .filter(|t| {
+                    task_matches(&t.name, &t.description, t.priority, black_box("task 4"), Some(TaskPriority::High))
+                })
+                .count()
+        })
+    });
+}
+
+fn undo(c: &mut Criterion) {
+    let project = synthetic_project(TASK_COUNT);
+
+    c.bench_function("undo push + undo 5k", |b| {
+        let mut history = UndoHistory::new();
+        b.iter(|| {
+            history.push("Edit task", &project.tasks, &project.dependencies);
+            black_box(history.undo(&project.tasks, &project.dependencies))
+        })
+    });
+}
+
+/// What the app asks of the model on a frame where nothing changed: whether
+/// the generation moved since its caches and undo snapshot were taken, the
+/// cached dependency graph and broken links. Returns whether anything
+/// needs redoing.
+fn idle_frame(project: &Project, seen: &mut u64) -> bool {
+    let changed = project.generation() != *seen || project.has_untouched_changes();
+    *seen = project.generation();
+    black_box(project.dependency_graph());
+    black_box(project.dependency_violations());
+    changed
+}
+
+fn idle(c: &mut Criterion) {
+    let mut project = synthetic_project(TASK_COUNT);
+    project.touch();
+    let mut seen = project.generation();
+    // Warm the caches, as the first frame after a change does.
+    idle_frame(&project, &mut seen);
+
+    let idle = allocations_per(1_000, || assert!(!idle_frame(&project, &mut seen)));
+    let snapshot = allocations_per(10, || {
+        black_box((project.tasks.clone(), project.dependencies.clone()));
+    });
+    println!("idle frame 5k: {} allocations per frame", idle as f64 / 1_000.0);
+    println!("snapshot per frame 5k: {} allocations per frame", snapshot as f64 / 10.0);
+    assert_eq!(idle, 0, "idle frames should not allocate");
+
+    c.bench_function("idle frame 5k", |b| b.iter(|| idle_frame(black_box(&project), &mut seen)));
+    c.bench_function("snapshot per frame 5k", |b| {
+        b.iter(|| (project.tasks.clone(), project.dependencies.clone()))
+    });
+}
+
+fn timeline(c: &mut Criterion) {
+    let project = synthetic_project(TASK_COUNT);
+    let start = project.tasks.iter().map(|t| t.start).min().unwrap();
+    let end = project.tasks.iter().map(|t| t.end).max().unwrap();
+    let viewport = TimelineViewport::new(start - Duration::days(7), end + Duration::days(7));
+
+    c.bench_function("datetime_to_x 10k", |b| {
+        b.iter(|| {
+            project
+                .tasks
+                .iter()
+                .map(|t| viewport.datetime_to_x(t.start) + viewport.datetime_to_x(t.end))
+                .sum::<f32>()
+        })
+    });
+}
+
+criterion_group!(benches, serialization, hierarchy, filtering, undo, idle, timeline);
+criterion_main!(benches);
@@ -0,0 +1,71 @@
+//! Vacations and other days people are away, kept with the project so the
+//! resource view and the schedule checks can see who is out when.
+//!
+//! Absences are whole days and name people the same way tasks do, by the
+//! trimmed assignee name.
+
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::task::Task;
+
+/// Days one person is away, first and last day included.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Absence {
+    pub assignee: String,
+    pub first_day: NaiveDate,
+    pub last_day: NaiveDate,
+    /// What the absence is, e.g. "Vacation".
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+}
+
+impl Absence {
+    pub fn new(assignee: impl Into<String>, first_day: NaiveDate, last_day: NaiveDate) -> Self {
+        Self { assignee: assignee.into(), first_day, last_day, note: String::new() }
+    }
+
+    /// From the start of the first day to the end of the last.
+    pub fn span(&self) -> (NaiveDateTime, NaiveDateTime) {
+        let start = self.first_day.and_time(chrono::NaiveTime::MIN);
+        let end = self.last_day.max(self.first_day).and_time(chrono::NaiveTime::MIN) + Duration::days(1);
+        (start, end)
+    }
+
+    pub fn is_for(&self, assignee: &str) -> bool {
+        self.assignee.trim() == assignee
+    }
+
+    /// True if `task` is assigned to this person and runs into the absence.
+    /// A milestone counts when it falls on one of the days.
+    pub fn overlaps(&self, task: &Task) -> bool {
+        if !task.assignee_name().is_some_and(|a| self.is_for(a)) {
+            return false;
+        }
+        let (start, end) = self.span();
+        if task.is_milestone {
+            (start..end).contains(&task.start)
+        } else {
+            task.start < end && start < task.end
+        }
+    }
+
+    /// `Vacation, 2024-03-11 – 2024-03-15`, or just the dates without a note.
+    pub fn describe(&self) -> String {
+        let days = if self.first_day >= self.last_day {
+            self.first_day.format("%Y-%m-%d").to_string()
+        } else {
+            format!("{} – {}", self.first_day.format("%Y-%m-%d"), self.last_day.format("%Y-%m-%d"))
+        };
+        if self.note.trim().is_empty() {
+            days
+        } else {
+            format!("{}, {}", self.note.trim(), days)
+        }
+    }
+}
+
+/// The absences `task` runs into.
+pub fn absences_overlapping<'a>(task: &'a Task, absences: &'a [Absence]) -> impl Iterator<Item = &'a Absence> + 'a {
+    absences.iter().filter(move |a| a.overlaps(task))
+}
@@ -0,0 +1,212 @@
+//! Change log of edits to task fields and dependencies.
+//!
+//! Events are found by comparing two states of the project with [`diff`]:
+//! the app compares the undo snapshot pushed before each edit with the
+//! state once the edit settles, so anything undoable is covered however it
+//! was made — editor, chart drags, commands, undo and redo alike.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::task::{Dependency, Task};
+
+/// Consecutive changes to the same field closer together than this are
+/// merged into one event, so typing a name logs one rename.
+const COALESCE_SECS: i64 = 10;
+
+/// Which part of a task changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeField {
+    Name,
+    Start,
+    End,
+    Progress,
+    Priority,
+    Dependency,
+}
+
+impl ChangeField {
+    pub fn label(self) -> &'static str {
+        match self {
+            ChangeField::Name => "Name",
+            ChangeField::Start => "Start",
+            ChangeField::End => "End",
+            ChangeField::Progress => "Progress",
+            ChangeField::Priority => "Priority",
+            ChangeField::Dependency => "Dependency",
+        }
+    }
+}
+
+/// One field of one task going from `old` to `new`. Values are stored as
+/// display text; an empty `old` or `new` means the value was added or removed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    pub task_id: Uuid,
+    /// The task's name when the change happened, for tasks deleted since.
+    pub task_name: String,
+    /// For dependency changes, the task at the other end of the link.
+    #[serde(default)]
+    pub related: Option<Uuid>,
+    pub field: ChangeField,
+    pub old: String,
+    pub new: String,
+    pub at: NaiveDateTime,
+}
+
+impl ChangeEvent {
+    /// True if the event concerns `id`, as the task or the other end of a link.
+    pub fn involves(&self, id: Uuid) -> bool {
+        self.task_id == id || self.related == Some(id)
+    }
+
+    /// `old → new`, or what was added / removed.
+    pub fn summary(&self) -> String {
+        match (self.old.is_empty(), self.new.is_empty()) {
+            (true, _) => format!("added {}", self.new),
+            (_, true) => format!("removed {}", self.old),
+            _ => format!("{} → {}", self.old, self.new),
+        }
+    }
+}
+
+/// Recorded change events, oldest first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ActivityLog {
+    events: Vec<ChangeEvent>,
+}
+
+impl ActivityLog {
+    pub fn events(&self) -> &[ChangeEvent] {
+        &self.events
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    /// Events involving `id`, newest first.
+    pub fn for_task(&self, id: Uuid) -> impl Iterator<Item = &ChangeEvent> {
+        self.events.iter().rev().filter(move |e| e.involves(id))
+    }
+
+    /// Append `event`, merging it into the newest event when that changed
+    /// the same field moments ago, then drop the oldest events beyond `limit`.
+    pub fn record(&mut self, event: ChangeEvent, limit: usize) {
+        let merge = self.events.last().is_some_and(|last| {
+            last.task_id == event.task_id
+                && last.related == event.related
+                && last.field == event.field
+                && last.new == event.old
+                && (event.at - last.at).num_seconds() < COALESCE_SECS
+        });
+        if merge {
+            let last = self.events.last_mut().unwrap();
+            last.new = event.new;
+            last.task_name = event.task_name;
+            last.at = event.at;
+            if last.old == last.new {
+                self.events.pop();
+            }
+        } else {
+            self.events.push(event);
+        }
+        self.truncate(limit);
+    }
+
+    /// Keep only the newest `limit` events.
+    pub fn truncate(&mut self, limit: usize) {
+        let excess = self.events.len().saturating_sub(limit);
+        self.events.drain(..excess);
+    }
+}
+
+pub(crate) fn format_date(at: NaiveDateTime) -> String {
+    at.format("%Y-%m-%d %H:%M").to_string()
+}
+
+pub(crate) fn format_progress(progress: f32) -> String {
+    format!("{:.0}%", progress * 100.0)
+}
+
+/// `Design → Build (FS +2d)`.
+pub(crate) fn describe_link(dep: &Dependency, names: &HashMap<Uuid, &str>) -> String {
+    let name = |id| names.get(&id).copied().unwrap_or("?");
+    let lag = if dep.lag_days != 0 { format!(" {:+}d", dep.lag_days) } else { String::new() };
+    format!("{} → {} ({}{})", name(dep.from_task), name(dep.to_task), dep.kind.short_label(), lag)
+}
+
+/// Changes from `before` to `after`, stamped `at`. Tasks that were added or
+/// deleted produce no events; their links do.
+pub fn diff(
+    before_tasks: &[Task],
+    before_deps: &[Dependency],
+    after_tasks: &[Task],
+    after_deps: &[Dependency],
+    at: NaiveDateTime,
+) -> Vec<ChangeEvent> {
+    let mut events = Vec::new();
+    let old_tasks: HashMap<Uuid, &Task> = before_tasks.iter().map(|t| (t.id, t)).collect();
+    for task in after_tasks {
+        let Some(old) = old_tasks.get(&task.id) else {
+            continue;
+        };
+        let mut change = |field, old: String, new: String| {
+            if old != new {
+                events.push(ChangeEvent {
+                    task_id: task.id,
+                    task_name: task.name.clone(),
+                    related: None,
+                    field,
+                    old,
+                    new,
+                    at,
+                });
+            }
+        };
+        change(ChangeField::Name, old.name.clone(), task.name.clone());
+        change(ChangeField::Start, format_date(old.start), format_date(task.start));
+        change(ChangeField::End, format_date(old.end), format_date(task.end));
+        change(ChangeField::Progress, format_progress(old.progress), format_progress(task.progress));
+        change(ChangeField::Priority, old.priority.label().to_string(), task.priority.label().to_string());
+    }
+
+    // Deleted tasks' names are still needed to describe their removed links.
+    let mut names: HashMap<Uuid, &str> = before_tasks.iter().map(|t| (t.id, t.name.as_str())).collect();
+    names.extend(after_tasks.iter().map(|t| (t.id, t.name.as_str())));
+    let key = |d: &Dependency| (d.from_task, d.to_task);
+    let old_links: HashMap<(Uuid, Uuid), &Dependency> = before_deps.iter().map(|d| (key(d), d)).collect();
+    let new_links: HashMap<(Uuid, Uuid), &Dependency> = after_deps.iter().map(|d| (key(d), d)).collect();
+    let mut link_change = |dep: &Dependency, old: String, new: String| {
+        events.push(ChangeEvent {
+            task_id: dep.to_task,
+            task_name: names.get(&dep.to_task).copied().unwrap_or("?").to_string(),
+            related: Some(dep.from_task),
+            field: ChangeField::Dependency,
+            old,
+            new,
+            at,
+        });
+    };
+    for dep in after_deps {
+        match old_links.get(&key(dep)) {
+            None => link_change(dep, String::new(), describe_link(dep, &names)),
+            Some(old) if old.kind != dep.kind || old.lag_days != dep.lag_days => {
+                link_change(dep, describe_link(old, &names), describe_link(dep, &names))
+            }
+            Some(_) => {}
+        }
+    }
+    for dep in before_deps.iter().filter(|d| !new_links.contains_key(&key(d))) {
+        link_change(dep, describe_link(dep, &names), String::new());
+    }
+    events
+}
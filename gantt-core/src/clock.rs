@@ -0,0 +1,82 @@
+//! How a project's task times map to real instants.
+//!
+//! Task times are stored as [`NaiveDateTime`]s. Without a time zone they are
+//! *floating*: "09:00" means nine in the morning wherever the file is opened,
+//! which is how every project file before time zones behaves. With a
+//! [`Project::time_zone`](crate::Project::time_zone) they are wall-clock
+//! times in that zone, and the UI converts them to and from the viewer's
+//! local time.
+
+use chrono::{Local, LocalResult, NaiveDateTime, TimeZone};
+pub use chrono_tz::{Tz, TZ_VARIANTS};
+
+/// Converts between a project's stored times and the viewer's local time.
+///
+/// ```
+/// use gantt_core::clock::ProjectClock;
+/// use chrono::NaiveDate;
+///
+/// let nine = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap().and_hms_opt(9, 0, 0).unwrap();
+/// // Floating projects show their times unchanged.
+/// assert_eq!(ProjectClock::floating().to_local(nine), nine);
+///
+/// // Zoned ones round-trip through local time.
+/// let berlin = ProjectClock::new("Europe/Berlin".parse().ok());
+/// assert_eq!(berlin.from_local(berlin.to_local(nine)), nine);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ProjectClock {
+    zone: Option<Tz>,
+}
+
+/// The instant `at` names in `zone`. Times skipped by a DST change are read
+/// as if the clocks had not changed yet; repeated ones as the earlier.
+fn resolve<Z: TimeZone>(zone: &Z, at: NaiveDateTime) -> Option<chrono::DateTime<Z>> {
+    match zone.from_local_datetime(&at) {
+        LocalResult::Single(t) => Some(t),
+        LocalResult::Ambiguous(earlier, _) => Some(earlier),
+        LocalResult::None => zone
+            .from_local_datetime(&(at + chrono::Duration::hours(1)))
+            .earliest()
+            .map(|t| t - chrono::Duration::hours(1)),
+    }
+}
+
+impl ProjectClock {
+    pub fn new(zone: Option<Tz>) -> Self {
+        Self { zone }
+    }
+
+    /// A clock for floating times, which never converts.
+    pub fn floating() -> Self {
+        Self { zone: None }
+    }
+
+    pub fn zone(self) -> Option<Tz> {
+        self.zone
+    }
+
+    /// A stored time as the viewer's local time.
+    pub fn to_local(self, at: NaiveDateTime) -> NaiveDateTime {
+        match self.zone {
+            None => at,
+            Some(zone) => resolve(&zone, at).map_or(at, |t| t.with_timezone(&Local).naive_local()),
+        }
+    }
+
+    /// A time the viewer entered in local time, as stored in the project.
+    pub fn from_local(self, at: NaiveDateTime) -> NaiveDateTime {
+        match self.zone {
+            None => at,
+            Some(zone) => resolve(&Local, at).map_or(at, |t| t.with_timezone(&zone).naive_local()),
+        }
+    }
+
+    /// The current time as the project stores times.
+    pub fn now(self) -> NaiveDateTime {
+        match self.zone {
+            None => Local::now().naive_local(),
+            Some(zone) => chrono::Utc::now().with_timezone(&zone).naive_local(),
+        }
+    }
+}
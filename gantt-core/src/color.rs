@@ -0,0 +1,96 @@
+//! Colours stored in projects, independent of any UI toolkit.
+
+use serde::{Deserialize, Serialize};
+
+/// An 8-bit RGBA colour, saved as a `[r, g, b, a]` array.
+///
+/// The channels are stored with alpha premultiplied, the way the desktop
+/// app's UI toolkit keeps them, and saved and loaded unchanged, so every
+/// colour — translucent ones included — reads back exactly as written.
+/// Files from before this crate stored the same premultiplied channels.
+///
+/// ```
+/// use gantt_core::color::Rgba;
+///
+/// let steel_blue = Rgba::from_rgb(70, 130, 180);
+/// assert_eq!(steel_blue.to_array(), [70, 130, 180, 255]);
+/// assert_eq!(steel_blue.to_hex(), "#4682B4");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(from = "[u8; 4]", into = "[u8; 4]")]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+    /// An opaque colour.
+    pub const fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 255 }
+    }
+
+    /// A colour from channels that already have alpha premultiplied.
+    pub const fn from_rgba_premultiplied(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub const fn to_array(self) -> [u8; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+
+    /// The channels with alpha divided back out, as `[r, g, b, a]` — the
+    /// colour a picker shows for a translucent colour.
+    pub fn to_unmultiplied(self) -> [u8; 4] {
+        match self.a {
+            0 => [0, 0, 0, 0],
+            255 => self.to_array(),
+            a => {
+                // Premultiplied in linear light, as the UI toolkit does.
+                let alpha = a as f32 / 255.0;
+                let un = |c: u8| gamma_from_linear((linear_from_gamma(c) / alpha).min(1.0));
+                [un(self.r), un(self.g), un(self.b), a]
+            }
+        }
+    }
+
+    /// `#RRGGBB` of the unmultiplied colour, ignoring alpha.
+    ///
+    /// ```
+    /// use gantt_core::color::Rgba;
+    ///
+    /// // Half-transparent white keeps its hue in exports.
+    /// assert_eq!(Rgba::from_rgba_premultiplied(188, 188, 188, 128).to_hex(), "#FFFFFF");
+    /// ```
+    pub fn to_hex(self) -> String {
+        let [r, g, b, _] = self.to_unmultiplied();
+        format!("#{:02X}{:02X}{:02X}", r, g, b)
+    }
+}
+
+fn linear_from_gamma(c: u8) -> f32 {
+    let c = c as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn gamma_from_linear(l: f32) -> u8 {
+    let c = if l <= 0.003_130_8 { l * 12.92 } else { 1.055 * l.powf(1.0 / 2.4) - 0.055 };
+    (c * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+impl From<[u8; 4]> for Rgba {
+    fn from([r, g, b, a]: [u8; 4]) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+impl From<Rgba> for [u8; 4] {
+    fn from(color: Rgba) -> Self {
+        color.to_array()
+    }
+}
@@ -0,0 +1,155 @@
+//! What changed between two versions of a project, e.g. the open project and
+//! a colleague's updated copy of the file.
+//!
+//! Tasks are matched by id, so a renamed or moved task shows up as modified
+//! rather than as one task removed and another added.
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::activity::{describe_link, format_date, format_progress};
+use crate::project::Project;
+use crate::task::{Dependency, Task};
+
+/// Which field of a task differs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffField {
+    Name,
+    Start,
+    End,
+    Progress,
+    Parent,
+}
+
+impl DiffField {
+    pub fn label(self) -> &'static str {
+        match self {
+            DiffField::Name => "Name",
+            DiffField::Start => "Start",
+            DiffField::End => "End",
+            DiffField::Progress => "Progress",
+            DiffField::Parent => "Parent",
+        }
+    }
+}
+
+/// One field going from `old` to `new`, as display text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    pub field: DiffField,
+    pub old: String,
+    pub new: String,
+}
+
+/// A task that was added, removed or modified. `changes` is empty for added
+/// and removed tasks.
+#[derive(Debug, Clone)]
+pub struct TaskDiff {
+    pub id: Uuid,
+    /// The task's name in the version that has it; the newer one if both do.
+    pub name: String,
+    pub changes: Vec<FieldDiff>,
+}
+
+/// A dependency present in only one of the two versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkDiff {
+    pub from_task: Uuid,
+    pub to_task: Uuid,
+    /// `Design → Build (FS +2d)`.
+    pub description: String,
+}
+
+/// Differences from an old version of a project to a new one. Each list
+/// follows the task order of the version its entries come from.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectDiff {
+    /// Tasks only in the new version.
+    pub added: Vec<TaskDiff>,
+    /// Tasks only in the old version.
+    pub removed: Vec<TaskDiff>,
+    /// Tasks in both whose compared fields differ.
+    pub modified: Vec<TaskDiff>,
+    pub links_added: Vec<LinkDiff>,
+    /// A link whose kind or lag changed is listed as removed and added.
+    pub links_removed: Vec<LinkDiff>,
+}
+
+impl ProjectDiff {
+    /// True if the two versions have the same tasks, compared fields and links.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of added, removed and modified tasks and links.
+    pub fn len(&self) -> usize {
+        self.added.len()
+            + self.removed.len()
+            + self.modified.len()
+            + self.links_added.len()
+            + self.links_removed.len()
+    }
+}
+
+fn parent_name(task: &Task, project: &Project) -> String {
+    match task.parent_id {
+        None => "(top level)".to_string(),
+        Some(id) => project.task(id).map_or_else(|| "?".to_string(), |p| p.name.clone()),
+    }
+}
+
+fn field_changes(old: &Task, old_project: &Project, new: &Task, new_project: &Project) -> Vec<FieldDiff> {
+    let mut changes = Vec::new();
+    let mut change = |field, old: String, new: String| {
+        if old != new {
+            changes.push(FieldDiff { field, old, new });
+        }
+    };
+    change(DiffField::Name, old.name.clone(), new.name.clone());
+    change(DiffField::Start, format_date(old.start), format_date(new.start));
+    change(DiffField::End, format_date(old.end), format_date(new.end));
+    change(DiffField::Progress, format_progress(old.progress), format_progress(new.progress));
+    if old.parent_id != new.parent_id {
+        change(DiffField::Parent, parent_name(old, old_project), parent_name(new, new_project));
+    }
+    changes
+}
+
+/// Links of `deps` missing from `other`, described with `names`.
+fn links_missing(deps: &[Dependency], other: &[Dependency], names: &HashMap<Uuid, &str>) -> Vec<LinkDiff> {
+    let key = |d: &Dependency| (d.from_task, d.to_task, d.kind, d.lag_days);
+    deps.iter()
+        .filter(|d| !other.iter().any(|o| key(o) == key(d)))
+        .map(|d| LinkDiff { from_task: d.from_task, to_task: d.to_task, description: describe_link(d, names) })
+        .collect()
+}
+
+/// Differences from `old` to `new`.
+pub fn compare(old: &Project, new: &Project) -> ProjectDiff {
+    let mut diff = ProjectDiff::default();
+    for task in &new.tasks {
+        match old.task(task.id) {
+            None => diff.added.push(TaskDiff { id: task.id, name: task.name.clone(), changes: Vec::new() }),
+            Some(before) => {
+                let changes = field_changes(before, old, task, new);
+                if !changes.is_empty() {
+                    diff.modified.push(TaskDiff { id: task.id, name: task.name.clone(), changes });
+                }
+            }
+        }
+    }
+    diff.removed = old
+        .tasks
+        .iter()
+        .filter(|t| new.task(t.id).is_none())
+        .map(|t| TaskDiff { id: t.id, name: t.name.clone(), changes: Vec::new() })
+        .collect();
+
+    // Removed tasks' names are still needed to describe their links.
+    let mut names: HashMap<Uuid, &str> = old.tasks.iter().map(|t| (t.id, t.name.as_str())).collect();
+    names.extend(new.tasks.iter().map(|t| (t.id, t.name.as_str())));
+    diff.links_added = links_missing(&new.dependencies, &old.dependencies, &names);
+    diff.links_removed = links_missing(&old.dependencies, &new.dependencies, &names);
+    diff
+}
@@ -0,0 +1,190 @@
+//! Quick date presets offered beside the editor's date fields.
+//!
+//! A preset is a day counted from somewhere: today, next Monday, the date
+//! being set, or the task's start. The project keeps one list for starts and
+//! one for ends, so a team with fixed sprint lengths can offer just those.
+//! Written out, a preset reads `today`, `tomorrow`, `monday`, `+1w` or
+//! `start+3d`, and lists are those separated by commas.
+
+use std::fmt;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Largest offset a written preset may have, about ten years either way.
+pub const MAX_PRESET_DAYS: i64 = 3650;
+
+/// Where a preset counts its days from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PresetAnchor {
+    Today,
+    /// The first Monday after today.
+    NextMonday,
+    /// The date the preset sets, as it is now.
+    Current,
+    /// The task's start, for setting its end.
+    Start,
+}
+
+/// One preset: `days` after `anchor`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatePreset {
+    pub anchor: PresetAnchor,
+    pub days: i64,
+}
+
+impl DatePreset {
+    pub const fn new(anchor: PresetAnchor, days: i64) -> Self {
+        Self { anchor, days }
+    }
+
+    /// The offset alone, `+1w` or `-3d`; empty for none.
+    pub fn offset(&self) -> String {
+        match self.days {
+            0 => String::new(),
+            days if days % 7 == 0 => format!("{:+}w", days / 7),
+            days => format!("{:+}d", days),
+        }
+    }
+
+    /// The day this preset picks, given today, the date it would replace
+    /// and the task's start, or `None` past the last date there is. With
+    /// `skip_weekends`, a Saturday or Sunday moves on to the Monday after.
+    pub fn date(
+        &self,
+        today: NaiveDate,
+        current: NaiveDate,
+        start: NaiveDate,
+        skip_weekends: bool,
+    ) -> Option<NaiveDate> {
+        let from = match self.anchor {
+            PresetAnchor::Today => today,
+            PresetAnchor::NextMonday => {
+                today.checked_add_signed(Duration::days(7 - today.weekday().num_days_from_monday() as i64))?
+            }
+            PresetAnchor::Current => current,
+            PresetAnchor::Start => start,
+        };
+        let date = from.checked_add_signed(Duration::try_days(self.days)?)?;
+        match date.weekday() {
+            Weekday::Sat if skip_weekends => date.checked_add_signed(Duration::days(2)),
+            Weekday::Sun if skip_weekends => date.checked_add_signed(Duration::days(1)),
+            _ => Some(date),
+        }
+    }
+
+    /// A preset as written by its `Display`: an anchor (`today`,
+    /// `tomorrow`, `monday`, `start`, or none for the current date)
+    /// followed by an optional offset in days or weeks, of at most
+    /// [`MAX_PRESET_DAYS`].
+    ///
+    /// ```
+    /// use gantt_core::date_preset::{DatePreset, PresetAnchor};
+    ///
+    /// assert_eq!(DatePreset::parse("Tomorrow"), Some(DatePreset::new(PresetAnchor::Today, 1)));
+    /// assert_eq!(DatePreset::parse("start + 2w"), Some(DatePreset::new(PresetAnchor::Start, 14)));
+    /// assert_eq!(DatePreset::parse("-3d"), Some(DatePreset::new(PresetAnchor::Current, -3)));
+    /// assert_eq!(DatePreset::parse("start+3").unwrap().to_string(), "start+3d");
+    /// assert_eq!(DatePreset::parse("next week"), None);
+    /// assert_eq!(DatePreset::parse("today+99999999d"), None);
+    /// assert_eq!(DatePreset::parse("-99999999999999999w"), None);
+    /// assert_eq!(DatePreset::parse(""), None);
+    /// ```
+    pub fn parse(text: &str) -> Option<DatePreset> {
+        let text: String = text.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_lowercase();
+        let (anchor, extra, rest) = [
+            ("today", PresetAnchor::Today, 0),
+            ("tomorrow", PresetAnchor::Today, 1),
+            ("monday", PresetAnchor::NextMonday, 0),
+            ("start", PresetAnchor::Start, 0),
+        ]
+        .into_iter()
+        .find_map(|(word, anchor, extra)| text.strip_prefix(word).map(|rest| (anchor, extra, rest)))
+        .unwrap_or((PresetAnchor::Current, 0, text.as_str()));
+        if rest.is_empty() {
+            return (anchor != PresetAnchor::Current).then_some(DatePreset::new(anchor, extra));
+        }
+        let sign = match rest.as_bytes()[0] {
+            b'+' => 1,
+            b'-' => -1,
+            _ => return None,
+        };
+        let rest = &rest[1..];
+        let (number, unit) = match rest.strip_suffix('w') {
+            Some(number) => (number, 7),
+            None => (rest.strip_suffix('d').unwrap_or(rest), 1),
+        };
+        let count: i64 = number.parse().ok()?;
+        let days = count.checked_mul(sign * unit)?.checked_add(extra)?;
+        (days.abs() <= MAX_PRESET_DAYS).then_some(DatePreset::new(anchor, days))
+    }
+}
+
+impl fmt::Display for DatePreset {
+    /// `today`, `tomorrow`, `monday+1d`, `+1w` or `start+3d`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.anchor, self.days) {
+            (PresetAnchor::Today, 1) => write!(f, "tomorrow"),
+            (PresetAnchor::Today, _) => write!(f, "today{}", self.offset()),
+            (PresetAnchor::NextMonday, _) => write!(f, "monday{}", self.offset()),
+            (PresetAnchor::Current, 0) => write!(f, "+0d"),
+            (PresetAnchor::Current, _) => write!(f, "{}", self.offset()),
+            (PresetAnchor::Start, _) => write!(f, "start{}", self.offset()),
+        }
+    }
+}
+
+/// The presets a project offers, saved with it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatePresets {
+    /// Offered for a task's start, and a milestone's date.
+    pub start: Vec<DatePreset>,
+    /// Offered for a task's end.
+    pub end: Vec<DatePreset>,
+    /// Move presets landing on a weekend on to the Monday after.
+    pub skip_weekends: bool,
+}
+
+impl Default for DatePresets {
+    fn default() -> Self {
+        Self {
+            start: vec![
+                DatePreset::new(PresetAnchor::Today, 0),
+                DatePreset::new(PresetAnchor::Today, 1),
+                DatePreset::new(PresetAnchor::NextMonday, 0),
+                DatePreset::new(PresetAnchor::Current, 7),
+            ],
+            end: [1, 3, 7, 14].map(|days| DatePreset::new(PresetAnchor::Start, days)).to_vec(),
+            skip_weekends: true,
+        }
+    }
+}
+
+impl DatePresets {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// `presets` written out as a comma-separated list.
+pub fn format_presets(presets: &[DatePreset]) -> String {
+    presets.iter().map(DatePreset::to_string).collect::<Vec<_>>().join(", ")
+}
+
+/// A comma-separated list of presets, or the first entry that isn't one.
+///
+/// ```
+/// use gantt_core::date_preset::{format_presets, parse_presets};
+///
+/// let sprint = parse_presets("start+2w, start + 4w,").unwrap();
+/// assert_eq!(format_presets(&sprint), "start+2w, start+4w");
+/// assert_eq!(parse_presets("today, soon"), Err("soon".to_string()));
+/// ```
+pub fn parse_presets(text: &str) -> Result<Vec<DatePreset>, String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| DatePreset::parse(part).ok_or_else(|| part.to_string()))
+        .collect()
+}
@@ -0,0 +1,84 @@
+//! Earned value: how much of the budgeted work was planned, done and paid
+//! for by a status date.
+//!
+//! A task's budget is its estimate in hours and its cost the hours tracked
+//! against it, so all three values are in hours. The plan is the tasks'
+//! current dates; projects keep no separate baseline.
+
+use std::collections::HashSet;
+
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+use crate::project::Project;
+use crate::task::Task;
+
+/// Planned value, earned value and actual cost of a project at a status
+/// date, in hours.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct EarnedValue {
+    /// Budget of the work scheduled to be done by the status date (PV).
+    pub planned: f64,
+    /// Budget of the work actually done (EV).
+    pub earned: f64,
+    /// Hours tracked up to the status date (AC).
+    pub actual: f64,
+    /// Leaf tasks left out because they have no estimate.
+    pub excluded: usize,
+}
+
+impl EarnedValue {
+    /// Schedule performance index, EV / PV; below 1 means behind schedule.
+    /// `None` before any work was planned.
+    pub fn spi(&self) -> Option<f64> {
+        (self.planned > 0.0).then(|| self.earned / self.planned)
+    }
+
+    /// Cost performance index, EV / AC; below 1 means over budget. `None`
+    /// before any time was tracked.
+    pub fn cpi(&self) -> Option<f64> {
+        (self.actual > 0.0).then(|| self.earned / self.actual)
+    }
+
+    /// `SPI 0.92 · CPI 1.10`, with a dash for an index that can't be worked
+    /// out yet.
+    pub fn summary(&self) -> String {
+        let index = |i: Option<f64>| i.map_or_else(|| "—".to_string(), |i| format!("{:.2}", i));
+        format!("SPI {} · CPI {}", index(self.spi()), index(self.cpi()))
+    }
+}
+
+/// Hours tracked on `task` before `at`.
+fn tracked_before(task: &Task, at: NaiveDateTime) -> f64 {
+    let total: chrono::Duration = task
+        .time_entries
+        .iter()
+        .map(|e| (e.end.unwrap_or(at).min(at) - e.start).max(chrono::Duration::zero()))
+        .sum();
+    total.num_seconds() as f64 / 3600.0
+}
+
+impl Project {
+    /// Earned value of the leaf tasks as of `status_date`. Parents and
+    /// milestones carry no budget of their own; tasks without an estimate
+    /// are counted in [`EarnedValue::excluded`]. Progress is each task's
+    /// logged progress on the status date when the project logs task
+    /// progress (see [`crate::task_progress`]), and its current progress
+    /// otherwise.
+    pub fn earned_value(&self, status_date: NaiveDateTime) -> EarnedValue {
+        let parents: HashSet<Uuid> = self.tasks.iter().filter_map(|t| t.parent_id).collect();
+        let mut value = EarnedValue::default();
+        for task in self.tasks.iter().filter(|t| !t.is_milestone && !parents.contains(&t.id)) {
+            let Some(budget) = task.estimate_hours.filter(|h| *h > 0.0) else {
+                value.excluded += 1;
+                continue;
+            };
+            let budget = budget as f64;
+            value.planned += budget * task.expected_progress(status_date) as f64;
+            let progress = self.task_progress.progress_on(task.id, status_date.date()).unwrap_or(task.progress);
+            value.earned += budget * progress.clamp(0.0, 1.0) as f64;
+            value.actual += tracked_before(task, status_date);
+        }
+        value
+    }
+}
@@ -0,0 +1,277 @@
+use super::{ExportExtra, ExportOptions, Exporter};
+use crate::project::Project;
+use crate::task::Task;
+use chrono::NaiveDateTime;
+use std::io::Write;
+
+/// First field of the row that starts the earned-value summary under the
+/// tasks; importers stop reading tasks there.
+pub const EARNED_VALUE_HEADING: &str = "Earned Value";
+
+/// Columns in the task rows, which the summary rows are padded to.
+const TASK_COLUMNS: usize = 11;
+
+/// Map progress float back to a human-readable status string.
+fn progress_to_status(progress: f32) -> &'static str {
+    if progress >= 1.0 {
+        "Finished"
+    } else if progress >= 0.5 {
+        "In Progress"
+    } else if progress >= 0.25 {
+        "Released"
+    } else {
+        "Not Started"
+    }
+}
+
+/// Write tasks as CSV separated by `delimiter` (`;` by default).
+///
+/// Columns: Task Label ; Start Date ; End Date ; Status ; Priority ; Description ; Parent ; Variance ; Tentative ; Risk ; Slug
+/// Dates are formatted as DD/MM/YYYY HH:MM. Variance is actual minus expected
+/// progress at export time, in percentage points (blank for milestones).
+/// Tentative is "Yes" for tentative tasks and blank otherwise, and Risk
+/// and Slug are blank for tasks without one.
+/// Returns the number of tasks written.
+pub fn export_csv(tasks: &[Task], delimiter: u8, out: &mut dyn Write) -> Result<usize, String> {
+    let mut wtr = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .from_writer(out);
+
+    // Write header
+    wtr.write_record(["Task Label", "Start Date", "End Date", "Status", "Priority", "Description", "Parent", "Variance", "Tentative", "Risk", "Slug"])
+        .map_err(|e| format!("Failed to write header: {}", e))?;
+
+    // Write each task
+    let now = chrono::Local::now().naive_local();
+    for task in tasks {
+        let parent_name = task.parent_id
+            .and_then(|pid| tasks.iter().find(|t| t.id == pid))
+            .map(|t| t.name.as_str())
+            .unwrap_or("");
+        let variance = if task.is_milestone {
+            String::new()
+        } else {
+            format!("{:+.0}", task.progress_variance(now) * 100.0)
+        };
+        let tentative = if task.tentative { "Yes" } else { "" };
+        let risk = task.risk.map_or("", |r| r.label());
+        wtr.write_record([
+            &task.name,
+            &task.start.format("%d/%m/%Y %H:%M").to_string(),
+            &task.end.format("%d/%m/%Y %H:%M").to_string(),
+            progress_to_status(task.progress),
+            task.priority.label(),
+            &task.description,
+            parent_name,
+            &variance,
+            tentative,
+            risk,
+            task.slug.as_deref().unwrap_or(""),
+        ])
+        .map_err(|e| format!("Failed to write task '{}': {}", task.name, e))?;
+    }
+
+    wtr.flush().map_err(|e| format!("Failed to flush CSV: {}", e))?;
+    Ok(tasks.len())
+}
+
+/// Write the project's earned value at `status_date` (see
+/// [`Project::earned_value`]) as CSV separated by `delimiter`: a heading row
+/// with the status date, then one row each for planned value, earned value
+/// and actual cost in hours, SPI, CPI and the tasks left out. Rows are
+/// padded to the task rows' width so the block can follow them. Writes
+/// nothing when no task has an estimate; returns whether it wrote.
+pub fn export_earned_value_csv(
+    project: &Project,
+    status_date: NaiveDateTime,
+    delimiter: u8,
+    out: &mut dyn Write,
+) -> Result<bool, String> {
+    let ev = project.earned_value(status_date);
+    if ev.planned <= 0.0 && ev.earned <= 0.0 && ev.actual <= 0.0 {
+        return Ok(false);
+    }
+    let mut wtr = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .from_writer(out);
+
+    let index = |i: Option<f64>| i.map_or_else(String::new, |i| format!("{:.2}", i));
+    let rows = [
+        (EARNED_VALUE_HEADING, status_date.format("%d/%m/%Y %H:%M").to_string()),
+        ("Planned Value (h)", format!("{:.1}", ev.planned)),
+        ("Earned Value (h)", format!("{:.1}", ev.earned)),
+        ("Actual Cost (h)", format!("{:.1}", ev.actual)),
+        ("SPI", index(ev.spi())),
+        ("CPI", index(ev.cpi())),
+        ("Tasks Without Estimate", ev.excluded.to_string()),
+    ];
+    for (label, value) in rows {
+        let mut record = vec![label.to_string(), value];
+        record.resize(TASK_COLUMNS, String::new());
+        wtr.write_record(&record).map_err(|e| format!("Failed to write '{}': {}", label, e))?;
+    }
+
+    wtr.flush().map_err(|e| format!("Failed to flush CSV: {}", e))?;
+    Ok(true)
+}
+
+/// Write every finished time entry as CSV separated by `delimiter`, one
+/// row per entry: Task ; Date ; Hours. Running timers are left out.
+/// Returns the number of entries written.
+pub fn export_time_entries_csv(tasks: &[Task], delimiter: u8, out: &mut dyn Write) -> Result<usize, String> {
+    let mut wtr = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .from_writer(out);
+
+    wtr.write_record(["Task", "Date", "Hours"])
+        .map_err(|e| format!("Failed to write header: {}", e))?;
+
+    let mut count = 0;
+    for task in tasks {
+        for entry in &task.time_entries {
+            let Some(end) = entry.end else {
+                continue;
+            };
+            let hours = (end - entry.start).num_minutes() as f64 / 60.0;
+            wtr.write_record([
+                task.name.as_str(),
+                &entry.start.format("%d/%m/%Y").to_string(),
+                &format!("{:.2}", hours),
+            ])
+            .map_err(|e| format!("Failed to write entry for '{}': {}", task.name, e))?;
+            count += 1;
+        }
+    }
+
+    wtr.flush().map_err(|e| format!("Failed to flush CSV: {}", e))?;
+    Ok(count)
+}
+
+/// Write the project's logged task progress as CSV separated by
+/// `delimiter`, one row per reading: Task ; Date ; Progress (whole
+/// percent). Readings of tasks no longer in the project are left out.
+/// Returns the number of readings written.
+pub fn export_progress_history_csv(project: &Project, delimiter: u8, out: &mut dyn Write) -> Result<usize, String> {
+    let mut wtr = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .from_writer(out);
+
+    wtr.write_record(["Task", "Date", "Progress"])
+        .map_err(|e| format!("Failed to write header: {}", e))?;
+
+    let mut count = 0;
+    for sample in project.task_progress.samples() {
+        let Some(task) = project.task(sample.task) else {
+            continue;
+        };
+        wtr.write_record([
+            task.name.as_str(),
+            &sample.date.format("%d/%m/%Y").to_string(),
+            &format!("{:.0}", sample.progress * 100.0),
+        ])
+        .map_err(|e| format!("Failed to write reading for '{}': {}", task.name, e))?;
+        count += 1;
+    }
+
+    wtr.flush().map_err(|e| format!("Failed to flush CSV: {}", e))?;
+    Ok(count)
+}
+
+/// The options' delimiter as the single byte the CSV writer takes.
+fn delimiter_byte(options: &ExportOptions) -> Result<u8, String> {
+    u8::try_from(options.delimiter)
+        .ok()
+        .filter(|b| b.is_ascii() && !matches!(b, b'"' | b'\n' | b'\r'))
+        .ok_or_else(|| format!("'{}' can't be used as a CSV delimiter", options.delimiter))
+}
+
+pub struct CsvExporter;
+
+impl Exporter for CsvExporter {
+    fn name(&self) -> &'static str {
+        "CSV"
+    }
+
+    fn extension(&self) -> &'static str {
+        "csv"
+    }
+
+    /// The tasks, followed by the project's earned value as of now when
+    /// tasks have estimates.
+    fn export(&self, project: &Project, options: &ExportOptions, out: &mut dyn Write) -> Result<usize, String> {
+        let delimiter = delimiter_byte(options)?;
+        let count = export_csv(&project.tasks, delimiter, out)?;
+        export_earned_value_csv(project, project.clock().now(), delimiter, out)?;
+        Ok(count)
+    }
+
+    fn extras(&self) -> &'static [ExportExtra] {
+        &[ExportExtra::Delimiter]
+    }
+}
+
+pub struct TimeEntriesExporter;
+
+impl Exporter for TimeEntriesExporter {
+    fn name(&self) -> &'static str {
+        "Time Entries (CSV)"
+    }
+
+    fn extension(&self) -> &'static str {
+        "csv"
+    }
+
+    fn export(&self, project: &Project, options: &ExportOptions, out: &mut dyn Write) -> Result<usize, String> {
+        if !project.tasks.iter().any(|t| t.time_entries.iter().any(|e| e.end.is_some())) {
+            return Err("no time has been tracked".to_string());
+        }
+        export_time_entries_csv(&project.tasks, delimiter_byte(options)?, out)
+    }
+
+    fn extras(&self) -> &'static [ExportExtra] {
+        &[ExportExtra::Delimiter]
+    }
+
+    fn item_label(&self) -> &'static str {
+        "time entries"
+    }
+
+    fn file_name(&self, project_name: &str) -> String {
+        format!("{} time.csv", project_name)
+    }
+}
+
+pub struct ProgressHistoryExporter;
+
+impl Exporter for ProgressHistoryExporter {
+    fn name(&self) -> &'static str {
+        "Progress History (CSV)"
+    }
+
+    fn extension(&self) -> &'static str {
+        "csv"
+    }
+
+    fn export(&self, project: &Project, options: &ExportOptions, out: &mut dyn Write) -> Result<usize, String> {
+        if !project.task_progress.samples().iter().any(|s| project.task(s.task).is_some()) {
+            return Err("no task progress has been logged; turn on Edit → Log Task Progress".to_string());
+        }
+        export_progress_history_csv(project, delimiter_byte(options)?, out)
+    }
+
+    fn extras(&self) -> &'static [ExportExtra] {
+        &[ExportExtra::Delimiter]
+    }
+
+    fn item_label(&self) -> &'static str {
+        "progress readings"
+    }
+
+    fn file_name(&self, project_name: &str) -> String {
+        format!("{} progress.csv", project_name)
+    }
+}
@@ -0,0 +1,547 @@
+use crate::task::{Dependency, DependencyKind};
+use super::{ExportExtra, ExportOptions, Exporter};
+use crate::project::Project;
+use crate::task::Task;
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Weekday};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::io::Write;
+use uuid::Uuid;
+
+const LABEL_WIDTH: f64 = 240.0;
+const HEADER_HEIGHT: f64 = 36.0;
+const ROW_HEIGHT: f64 = 26.0;
+const BAR_INSET: f64 = 6.0;
+/// Width the timeline aims for; the day width is clamped to a readable range.
+const TIMELINE_WIDTH: f64 = 1000.0;
+const INDENT: f64 = 14.0;
+/// Height of the milestone lane between the header and the rows.
+const LANE_HEIGHT: f64 = 26.0;
+/// Depth of the zigzag on a bar cut at the edge of a cropped export.
+const TOOTH: f64 = 3.0;
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Per-task data inlined for the script: positions for relayout and the
+/// text shown in tooltips.
+#[derive(Serialize)]
+struct TaskData<'a> {
+    id: Uuid,
+    parent: Option<Uuid>,
+    name: &'a str,
+    x0: f64,
+    x1: f64,
+    collapsed: bool,
+    dates: String,
+    progress: u32,
+    assignee: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct LinkData {
+    from: Uuid,
+    to: Uuid,
+    kind: &'static str,
+    /// `stroke-dasharray` of the kind's line style; empty when solid.
+    dash: String,
+}
+
+#[derive(Serialize)]
+struct ChartData<'a> {
+    header: f64,
+    row: f64,
+    tasks: Vec<TaskData<'a>>,
+    links: Vec<LinkData>,
+}
+
+/// Start and end of the arrow for `dep`, at the anchors its kind links.
+fn link_anchors(kind: DependencyKind, from: (f64, f64), to: (f64, f64)) -> (f64, f64) {
+    let x1 = match kind {
+        DependencyKind::StartToStart | DependencyKind::StartToFinish => from.0,
+        DependencyKind::FinishToStart | DependencyKind::FinishToFinish => from.1,
+    };
+    let x2 = match kind {
+        DependencyKind::FinishToFinish | DependencyKind::StartToFinish => to.1,
+        DependencyKind::FinishToStart | DependencyKind::StartToStart => to.0,
+    };
+    (x1, x2)
+}
+
+/// `stroke-dasharray` value for a link of `kind`, as drawn in the app.
+fn dash_array(kind: DependencyKind) -> String {
+    let pattern: Vec<String> = kind.line_style().dash_pattern().iter().map(|l| l.to_string()).collect();
+    pattern.join(" ")
+}
+
+/// Elbow path from one row's anchor to another's, dashed by `dash`. Kept
+/// in step with `arrow()` in the embedded script.
+fn link_path(x1: f64, y1: f64, x2: f64, y2: f64, dash: &str) -> String {
+    let dash = if dash.is_empty() { String::new() } else { format!(r#" stroke-dasharray="{}""#, dash) };
+    format!(r#"<path d="M{x1:.1} {y1:.1} H{:.1} V{y2:.1} H{x2:.1}"{dash} marker-end="url(#arrow)"/>"#, x1 + 8.0)
+}
+
+/// ` · SPI 0.92 · CPI 1.10` for the header, or nothing when no task has an
+/// estimate to measure against.
+fn earned_value(project: &Project, now: NaiveDateTime) -> String {
+    let ev = project.earned_value(now);
+    if ev.planned <= 0.0 && ev.earned <= 0.0 && ev.actual <= 0.0 {
+        return String::new();
+    }
+    let mut text = format!(" · {}", ev.summary());
+    if ev.excluded > 0 {
+        let _ = write!(text, " ({} tasks without an estimate left out)", ev.excluded);
+    }
+    escape(&text)
+}
+
+/// Outline of a bar from `x0` to `x1` and `top` down by `height`, with a
+/// zigzag on each side `torn` marks (left, right): the bar carries on past
+/// the edge of a cropped export.
+fn torn_bar_path(x0: f64, x1: f64, top: f64, height: f64, torn: (bool, bool)) -> String {
+    let bottom = top + height;
+    // An even count, so both corners on a torn side stay square.
+    let teeth = (height / 8.0).round().max(1.0) as usize * 2;
+    let step = height / teeth as f64;
+    let mut d = format!("M{x0:.1} {top:.1} H{x1:.1}");
+    for i in 1..=teeth {
+        let x = if torn.1 && i % 2 == 1 { x1 - TOOTH } else { x1 };
+        let _ = write!(d, " L{x:.1} {:.1}", top + i as f64 * step);
+    }
+    let _ = write!(d, " H{x0:.1}");
+    for i in 1..teeth {
+        let x = if torn.0 && i % 2 == 1 { x0 + TOOTH } else { x0 };
+        let _ = write!(d, " L{x:.1} {:.1}", bottom - i as f64 * step);
+    }
+    d.push_str(" Z");
+    d
+}
+
+fn task_dates(task: &Task) -> String {
+    if task.is_milestone {
+        task.start.format("%a %b %d %Y, %H:%M").to_string()
+    } else {
+        format!("{} → {}", task.start.format("%a %b %d %Y, %H:%M"), task.end.format("%a %b %d %Y, %H:%M"))
+    }
+}
+
+/// Export the project as one self-contained HTML page: the chart as inline
+/// SVG, with a small script for hover tooltips and collapsing phases and a
+/// print stylesheet. Nothing is loaded from the network.
+/// With `milestone_lane` set, a lane of milestones (only those tagged "key"
+/// for `Some(true)`) runs under the header, as in the app. With `crop`, the
+/// timeline covers just those days and bars running past them are cut at
+/// its edges with a zigzag; the tasks are expected to be scoped to it
+/// already (see [`ExportOptions::scope`]).
+/// Returns the number of tasks written.
+pub fn export_html(
+    project: &Project,
+    milestone_lane: Option<bool>,
+    crop: Option<(NaiveDate, NaiveDate)>,
+    out: &mut dyn Write,
+) -> Result<usize, String> {
+    let tasks = &project.tasks;
+    let first = tasks.iter().map(|t| t.start).min().ok_or_else(|| "Project has no tasks".to_string())?;
+    let last = tasks.iter().map(|t| t.end).max().unwrap_or(first);
+    let (first_day, end_day) = match crop {
+        Some((from, to)) => (from, to + chrono::Duration::days(1)),
+        None => (first.date() - chrono::Duration::days(1), last.date() + chrono::Duration::days(2)),
+    };
+    let range_start = first_day.and_hms_opt(0, 0, 0).unwrap();
+    let range_end = end_day.and_hms_opt(0, 0, 0).unwrap();
+    let days = (range_end - range_start).num_days().max(1) as f64;
+    let day_width = (TIMELINE_WIDTH / days).clamp(4.0, 40.0);
+    let x_of = |at: NaiveDateTime| LABEL_WIDTH + (at - range_start).num_minutes() as f64 / 1440.0 * day_width;
+    // Only a crop cuts anything: otherwise the range has room to spare.
+    let clip = |x: f64| x.clamp(LABEL_WIDTH, x_of(range_end));
+    let width = LABEL_WIDTH + days * day_width + 20.0;
+
+    let by_id: HashMap<Uuid, &Task> = tasks.iter().map(|t| (t.id, t)).collect();
+    let parents: HashSet<Uuid> = tasks.iter().filter_map(|t| t.parent_id).collect();
+    let ancestors = |task: &Task| {
+        std::iter::successors(task.parent_id.and_then(|p| by_id.get(&p).copied()), |t| {
+            t.parent_id.and_then(|p| by_id.get(&p).copied())
+        })
+    };
+
+    // Rows under a collapsed phase start hidden, as in the app.
+    let rows_top = HEADER_HEIGHT + if milestone_lane.is_some() { LANE_HEIGHT } else { 0.0 };
+    let mut row_y: HashMap<Uuid, f64> = HashMap::new();
+    let mut rows = 0;
+    for task in tasks {
+        if !ancestors(task).any(|a| a.collapsed) {
+            row_y.insert(task.id, rows_top + rows as f64 * ROW_HEIGHT);
+            rows += 1;
+        }
+    }
+    let height = rows_top + rows as f64 * ROW_HEIGHT;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg id="chart" xmlns="http://www.w3.org/2000/svg" width="{w:.0}" height="{h:.0}" font-family="sans-serif" font-size="12">"#,
+        w = width,
+        h = height
+    );
+    let _ = writeln!(
+        svg,
+        r##"<defs><marker id="arrow" viewBox="0 0 8 8" refX="8" refY="4" markerWidth="7" markerHeight="7" orient="auto"><path d="M0 0 L8 4 L0 8 z" fill="#6A7080"/></marker></defs>"##
+    );
+
+    // Timeline header and grid: weeks for short projects, months for long ones.
+    let weekly = days <= 120.0;
+    let _ = writeln!(svg, r##"<g class="grid" stroke="#E2E4E8">"##);
+    let mut day = range_start.date();
+    while day < range_end.date() {
+        let tick = if weekly { day.weekday() == Weekday::Mon } else { day.day() == 1 };
+        if tick {
+            let x = x_of(day.and_hms_opt(0, 0, 0).unwrap());
+            let label = if weekly { day.format("%b %d") } else { day.format("%b %Y") };
+            let _ = writeln!(
+                svg,
+                r##"<line x1="{x:.1}" y1="{top}" x2="{x:.1}" y2="100%"/><text x="{tx:.1}" y="22" stroke="none" fill="#505560">{label}</text>"##,
+                top = HEADER_HEIGHT - 8.0,
+                tx = x + 3.0,
+            );
+        }
+        day = day.succ_opt().unwrap_or(day);
+    }
+    let _ = writeln!(svg, "</g>");
+    let now = chrono::Local::now().naive_local();
+    if now > range_start && now < range_end {
+        let _ = writeln!(
+            svg,
+            r##"<line class="today" x1="{x:.1}" y1="{top}" x2="{x:.1}" y2="100%" stroke="#DC3C3C" stroke-dasharray="4 3"/>"##,
+            x = x_of(now),
+            top = HEADER_HEIGHT - 8.0,
+        );
+    }
+
+    if let Some(key_only) = milestone_lane {
+        let mut milestones: Vec<&Task> =
+            tasks.iter().filter(|t| t.is_milestone && (!key_only || t.is_key())).collect();
+        milestones.sort_by_key(|t| t.start);
+        let _ = writeln!(svg, r#"<g class="lane" transform="translate(0,{HEADER_HEIGHT})">"#);
+        let _ = writeln!(
+            svg,
+            r##"<rect width="100%" height="{LANE_HEIGHT}" fill="#F4F5F7" stroke="#E2E4E8"/><text x="8" y="17" fill="#707070">Milestones</text>"##
+        );
+        let mid = LANE_HEIGHT / 2.0;
+        for task in milestones {
+            let x = x_of(task.start);
+            let _ = writeln!(
+                svg,
+                r##"<polygon points="{x:.1},{t} {r:.1},{mid} {x:.1},{b} {l:.1},{mid}" fill="{color}" stroke="#303030" stroke-width="0.5"><title>{name} · {date}</title></polygon><text x="{tx:.1}" y="17" fill="#303030">{name}</text>"##,
+                t = mid - 6.0,
+                b = mid + 6.0,
+                l = x - 6.0,
+                r = x + 6.0,
+                tx = x + 9.0,
+                color = task.color.to_hex(),
+                name = escape(&task.name),
+                date = task.start.format("%Y-%m-%d"),
+            );
+        }
+        let _ = writeln!(svg, "</g>");
+    }
+
+    for task in tasks {
+        let (transform, display, odd) = match row_y.get(&task.id) {
+            Some(y) => (format!("translate(0,{:.1})", y), "", ((y - rows_top) / ROW_HEIGHT) as usize % 2 == 1),
+            None => ("translate(0,0)".to_string(), r#" style="display:none""#, false),
+        };
+        let _ = writeln!(svg, r#"<g class="row" data-id="{}" transform="{}"{}>"#, task.id, transform, display);
+        // Translucent so the grid shows through; still catches the pointer.
+        let stripe = if odd { 0.5 } else { 0.0 };
+        let _ = writeln!(
+            svg,
+            r##"<rect class="stripe" width="100%" height="{ROW_HEIGHT}" fill="#E8EAEE" fill-opacity="{stripe}"/>"##
+        );
+
+        let depth = ancestors(task).count() as f64;
+        let label_x = 8.0 + depth * INDENT;
+        let is_parent = parents.contains(&task.id);
+        if is_parent {
+            let _ = writeln!(
+                svg,
+                r##"<text class="toggle" x="{label_x:.1}" y="17" fill="#505560">{}</text>"##,
+                if task.collapsed { "▸" } else { "▾" }
+            );
+        }
+        let weight = if is_parent { r#" font-weight="bold""# } else { "" };
+        let _ = writeln!(
+            svg,
+            r##"<text x="{:.1}" y="17" fill="#202020"{weight}>{}</text>"##,
+            label_x + 12.0,
+            escape(&task.name)
+        );
+
+        let (full0, full1) = (x_of(task.start), x_of(task.end));
+        let (x0, x1) = (clip(full0), clip(full1));
+        let torn = (full0 < x0, full1 > x1);
+        let color = task.color.to_hex();
+        let mid = ROW_HEIGHT / 2.0;
+        if task.is_milestone {
+            let _ = writeln!(
+                svg,
+                r##"<polygon class="bar" points="{x0:.1},{t} {r:.1},{mid} {x0:.1},{b} {l:.1},{mid}" fill="{color}" stroke="#303030" stroke-width="0.5"/>"##,
+                t = mid - 7.0,
+                b = mid + 7.0,
+                l = x0 - 7.0,
+                r = x0 + 7.0,
+            );
+        } else if is_parent && (torn.0 || torn.1) {
+            let _ = writeln!(
+                svg,
+                r##"<path class="bar" d="{}" fill="#404652"/>"##,
+                torn_bar_path(x0, x1, mid - 3.0, 6.0, torn)
+            );
+        } else if is_parent {
+            let _ = writeln!(
+                svg,
+                r##"<rect class="bar" x="{x0:.1}" y="{y:.1}" width="{w:.1}" height="6" fill="#404652"/>"##,
+                y = mid - 3.0,
+                w = (x1 - x0).max(2.0),
+            );
+        } else if torn.0 || torn.1 {
+            let h = ROW_HEIGHT - 2.0 * BAR_INSET;
+            let tentative = if task.tentative {
+                format!(r#" fill-opacity="0.35" stroke="{color}" stroke-dasharray="4 3""#)
+            } else {
+                String::new()
+            };
+            let _ = writeln!(
+                svg,
+                r##"<path class="bar" d="{}" fill="{color}"{tentative}/>"##,
+                torn_bar_path(x0, x1, BAR_INSET, h, torn)
+            );
+            // Progress is measured along the whole bar, then cut like it.
+            let done = clip(full0 + (full1 - full0) * task.progress.clamp(0.0, 1.0) as f64);
+            if task.progress > 0.0 && done > x0 {
+                let _ = writeln!(
+                    svg,
+                    r##"<path d="{}" fill="#000000" fill-opacity="0.3"/>"##,
+                    torn_bar_path(x0, done, BAR_INSET, h, (torn.0, torn.1 && done >= x1))
+                );
+            }
+        } else {
+            let w = (x1 - x0).max(4.0);
+            // Tentative bars are faded with a dashed outline, as in the app.
+            let tentative = if task.tentative {
+                format!(r#" fill-opacity="0.35" stroke="{color}" stroke-dasharray="4 3""#)
+            } else {
+                String::new()
+            };
+            let _ = writeln!(
+                svg,
+                r##"<rect class="bar" x="{x0:.1}" y="{BAR_INSET}" width="{w:.1}" height="{h}" rx="3" fill="{color}"{tentative}/>"##,
+                h = ROW_HEIGHT - 2.0 * BAR_INSET,
+            );
+            if task.progress > 0.0 {
+                let _ = writeln!(
+                    svg,
+                    r##"<rect x="{x0:.1}" y="{BAR_INSET}" width="{pw:.1}" height="{h}" rx="3" fill="#000000" fill-opacity="0.3"/>"##,
+                    pw = w * task.progress.clamp(0.0, 1.0) as f64,
+                    h = ROW_HEIGHT - 2.0 * BAR_INSET,
+                );
+            }
+        }
+        let _ = writeln!(svg, "</g>");
+    }
+    // Links go on top so the row backgrounds don't hide them.
+    let _ = writeln!(svg, r##"<g id="links" fill="none" stroke="#6A7080" stroke-width="1.2">"##);
+    for dep in &project.dependencies {
+        let (Some(from), Some(to)) = (by_id.get(&dep.from_task), by_id.get(&dep.to_task)) else {
+            continue;
+        };
+        let (Some(y1), Some(y2)) = (row_y.get(&dep.from_task), row_y.get(&dep.to_task)) else {
+            continue;
+        };
+        let (x1, x2) = link_anchors(
+            dep.kind,
+            (clip(x_of(from.start)), clip(x_of(from.end))),
+            (clip(x_of(to.start)), clip(x_of(to.end))),
+        );
+        let _ = writeln!(svg, "{}", link_path(x1, y1 + ROW_HEIGHT / 2.0, x2, y2 + ROW_HEIGHT / 2.0, &dash_array(dep.kind)));
+    }
+    let _ = writeln!(svg, "</g>");
+    svg.push_str("</svg>\n");
+
+    let data = ChartData {
+        header: rows_top,
+        row: ROW_HEIGHT,
+        tasks: tasks
+            .iter()
+            .map(|t| TaskData {
+                id: t.id,
+                parent: t.parent_id,
+                name: &t.name,
+                x0: clip(x_of(t.start)),
+                x1: clip(x_of(t.end)),
+                collapsed: t.collapsed,
+                dates: task_dates(t),
+                progress: (t.progress * 100.0).round() as u32,
+                assignee: t.assignee_name(),
+            })
+            .collect(),
+        links: project.dependencies.iter().map(link_data).collect(),
+    };
+    // `</` would end the script element early.
+    let json = serde_json::to_string(&data).map_err(|e| e.to_string())?.replace("</", "<\\/");
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>{STYLE}</style>
+</head>
+<body>
+<header><h1>{title}</h1><p>Exported {date} · {count} tasks{cropped}{earned}</p></header>
+<main>
+{svg}</main>
+<div id="tip"></div>
+<script type="application/json" id="gantt-data">{json}</script>
+<script>{SCRIPT}</script>
+</body>
+</html>
+"#,
+        title = escape(&project.name),
+        date = now.format("%a %b %d %Y, %H:%M"),
+        count = tasks.len(),
+        cropped = crop.map_or_else(String::new, |(from, to)| {
+            format!(" · {} – {}", from.format("%b %d %Y"), to.format("%b %d %Y"))
+        }),
+        earned = earned_value(project, now),
+    );
+
+    out.write_all(html.as_bytes()).map_err(|e| format!("Failed to write HTML: {}", e))?;
+    Ok(tasks.len())
+}
+
+pub struct HtmlExporter;
+
+impl Exporter for HtmlExporter {
+    fn name(&self) -> &'static str {
+        "HTML"
+    }
+
+    fn extension(&self) -> &'static str {
+        "html"
+    }
+
+    fn export(&self, project: &Project, options: &ExportOptions, out: &mut dyn Write) -> Result<usize, String> {
+        export_html(project, options.milestone_lane, options.crop(), out)
+    }
+
+    fn extras(&self) -> &'static [ExportExtra] {
+        &[ExportExtra::CropToRange]
+    }
+}
+
+fn link_data(dep: &Dependency) -> LinkData {
+    LinkData { from: dep.from_task, to: dep.to_task, kind: dep.kind.short_label(), dash: dash_array(dep.kind) }
+}
+
+const STYLE: &str = r#"
+body { font-family: sans-serif; margin: 16px; color: #202020; background: #FFFFFF; }
+header h1 { font-size: 20px; margin: 0; }
+header p { margin: 4px 0 12px; color: #707070; font-size: 12px; }
+main { overflow-x: auto; border: 1px solid #E2E4E8; }
+#chart .toggle { cursor: pointer; user-select: none; }
+#tip { position: fixed; display: none; pointer-events: none; background: #FFFFFF; border: 1px solid #C8CBD2;
+       border-radius: 4px; padding: 6px 8px; font-size: 12px; box-shadow: 0 2px 6px rgba(0,0,0,0.15); }
+#tip b { display: block; margin-bottom: 2px; }
+@media print {
+  @page { size: landscape; margin: 10mm; }
+  body { margin: 0; }
+  main { overflow: visible; border: none; }
+  #chart { max-width: 100%; height: auto; }
+  #chart .toggle, #tip { display: none; }
+  #chart .row, #chart .bar { -webkit-print-color-adjust: exact; print-color-adjust: exact; }
+}
+"#;
+
+/// Tooltips and collapsing. Collapsing re-stacks the visible rows and
+/// redraws the links, mirroring the layout done when exporting.
+const SCRIPT: &str = r#"
+(function () {
+  const data = JSON.parse(document.getElementById('gantt-data').textContent);
+  const svg = document.getElementById('chart');
+  const links = document.getElementById('links');
+  const tip = document.getElementById('tip');
+  const byId = new Map(data.tasks.map(t => [t.id, t]));
+  const collapsed = new Set(data.tasks.filter(t => t.collapsed).map(t => t.id));
+  const rowOf = id => svg.querySelector('g.row[data-id="' + id + '"]');
+
+  function hidden(task) {
+    for (let p = task.parent; p; p = byId.has(p) ? byId.get(p).parent : null) {
+      if (collapsed.has(p)) return true;
+    }
+    return false;
+  }
+
+  function arrow(link, ys) {
+    const a = byId.get(link.from), b = byId.get(link.to);
+    const x1 = (link.kind === 'SS' || link.kind === 'SF') ? a.x0 : a.x1;
+    const x2 = (link.kind === 'FF' || link.kind === 'SF') ? b.x1 : b.x0;
+    const y1 = ys.get(link.from) + data.row / 2, y2 = ys.get(link.to) + data.row / 2;
+    const dash = link.dash ? ' stroke-dasharray="' + link.dash + '"' : '';
+    return '<path d="M' + x1.toFixed(1) + ' ' + y1.toFixed(1) + ' H' + (x1 + 8).toFixed(1) +
+      ' V' + y2.toFixed(1) + ' H' + x2.toFixed(1) + '"' + dash + ' marker-end="url(#arrow)"/>';
+  }
+
+  function layout() {
+    const ys = new Map();
+    let row = 0;
+    for (const task of data.tasks) {
+      const g = rowOf(task.id);
+      if (hidden(task)) { g.style.display = 'none'; continue; }
+      const y = data.header + row * data.row;
+      g.style.display = '';
+      g.setAttribute('transform', 'translate(0,' + y + ')');
+      g.querySelector('.stripe').setAttribute('fill-opacity', row % 2 === 0 ? '0' : '0.5');
+      const toggle = g.querySelector('.toggle');
+      if (toggle) toggle.textContent = collapsed.has(task.id) ? '▸' : '▾';
+      ys.set(task.id, y);
+      row++;
+    }
+    svg.setAttribute('height', data.header + row * data.row);
+    links.innerHTML = data.links.filter(l => ys.has(l.from) && ys.has(l.to)).map(l => arrow(l, ys)).join('');
+  }
+
+  svg.addEventListener('click', e => {
+    const toggle = e.target.closest('.toggle');
+    if (!toggle) return;
+    const id = toggle.closest('g.row').dataset.id;
+    if (collapsed.has(id)) collapsed.delete(id); else collapsed.add(id);
+    layout();
+  });
+
+  svg.addEventListener('mousemove', e => {
+    const g = e.target.closest('g.row');
+    const task = g && byId.get(g.dataset.id);
+    if (!task) { tip.style.display = 'none'; return; }
+    tip.textContent = '';
+    const name = document.createElement('b');
+    name.textContent = task.name;
+    tip.appendChild(name);
+    const lines = [task.dates, 'Progress: ' + task.progress + '%'];
+    if (task.assignee) lines.push('Assignee: ' + task.assignee);
+    for (const line of lines) {
+      const div = document.createElement('div');
+      div.textContent = line;
+      tip.appendChild(div);
+    }
+    tip.style.left = (e.clientX + 14) + 'px';
+    tip.style.top = (e.clientY + 14) + 'px';
+    tip.style.display = 'block';
+  });
+  svg.addEventListener('mouseleave', () => { tip.style.display = 'none'; });
+})();
+"#;
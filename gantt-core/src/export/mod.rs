@@ -0,0 +1,227 @@
+//! Export formats behind one trait.
+//!
+//! Every format implements [`Exporter`] and is registered in an
+//! [`ExporterRegistry`]. Front ends build their export menus from the
+//! registry, so adding a format means implementing the trait and
+//! registering it: here in [`ExporterRegistry::with_builtins`], or on top of
+//! it for a format only one front end can draw.
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::project::Project;
+use crate::task::Task;
+
+pub mod csv_export;
+pub mod html_export;
+pub mod org_export;
+pub mod plantuml_export;
+
+/// Which tasks an export covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ExportScope {
+    #[default]
+    Project,
+    /// Tasks matching the view's search and priority filter.
+    Filter,
+    /// The selected tasks and their subtasks.
+    Selection,
+    /// Tasks overlapping [`ExportOptions::date_range`].
+    DateRange,
+}
+
+/// A choice only some formats offer in the export dialog; formats list
+/// theirs in [`Exporter::extras`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportExtra {
+    /// [`ExportOptions::delimiter`].
+    Delimiter,
+    /// [`ExportOptions::crop_to_range`], for formats that draw the timeline.
+    CropToRange,
+}
+
+/// Choices shared by every export format, set in the export dialog and
+/// remembered per format in the settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    pub scope: ExportScope,
+    /// Days kept by [`ExportScope::DateRange`] (inclusive). Kept when
+    /// another scope is picked so switching back finds them again.
+    pub date_range: Option<(NaiveDate, NaiveDate)>,
+    /// Keep descriptions and comments; without them only the schedule is
+    /// exported.
+    pub include_notes: bool,
+    /// Keep finished tasks. Phases whose tasks are all finished go too.
+    pub include_completed: bool,
+    /// Field separator, for formats offering [`ExportExtra::Delimiter`].
+    pub delimiter: char,
+    /// Draw only [`Self::date_range`] of the timeline, for formats offering
+    /// [`ExportExtra::CropToRange`]. Tasks wholly outside it are left out,
+    /// and bars running past its edges are cut there.
+    pub crop_to_range: bool,
+    /// Put the chart's milestone lane above the rows, in formats that draw
+    /// the chart; `Some(true)` for key milestones only. Follows the view.
+    #[serde(skip)]
+    pub milestone_lane: Option<bool>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            scope: ExportScope::Project,
+            date_range: None,
+            include_notes: true,
+            include_completed: true,
+            delimiter: ';',
+            crop_to_range: false,
+            milestone_lane: None,
+        }
+    }
+}
+
+impl ExportOptions {
+    /// The days the drawn timeline is cut to, when cropping is on.
+    pub fn crop(&self) -> Option<(NaiveDate, NaiveDate)> {
+        self.date_range.filter(|_| self.crop_to_range)
+    }
+
+    /// A copy of `project` cut down to what the options ask for. `matches`
+    /// is the view's current filter and `selection` the selected tasks.
+    /// Parents of kept tasks are kept too, so phases still frame their
+    /// tasks, and links survive only when both ends do. A [`Self::crop`]
+    /// leaves out tasks outside it whatever the scope.
+    pub fn scope(&self, project: &Project, matches: impl Fn(&Task) -> bool, selection: &[Uuid]) -> Project {
+        let selected: HashSet<Uuid> = match self.scope {
+            ExportScope::Selection => {
+                selection.iter().flat_map(|&id| std::iter::once(id).chain(project.descendant_ids(id))).collect()
+            }
+            _ => HashSet::new(),
+        };
+        let overlaps = |t: &Task, (from, to): (NaiveDate, NaiveDate)| t.start.date() <= to && t.end.date() >= from;
+        let wanted = |t: &Task| {
+            let in_scope = match self.scope {
+                ExportScope::Project => true,
+                ExportScope::Filter => matches(t),
+                ExportScope::Selection => selected.contains(&t.id),
+                ExportScope::DateRange => self.date_range.is_none_or(|range| overlaps(t, range)),
+            };
+            in_scope
+                && self.crop().is_none_or(|range| overlaps(t, range))
+                && (self.include_completed || !t.is_complete())
+        };
+        let mut keep: HashSet<Uuid> = HashSet::new();
+        for task in project.tasks.iter().filter(|t| wanted(t)) {
+            keep.insert(task.id);
+            keep.extend(project.ancestor_ids(task.id));
+        }
+
+        let mut scoped = project.clone();
+        let tasks = project
+            .tasks
+            .iter()
+            .filter(|t| keep.contains(&t.id))
+            .cloned()
+            .map(|mut t| {
+                if !self.include_notes {
+                    t.description.clear();
+                    t.comments.clear();
+                }
+                t
+            })
+            .collect();
+        scoped.set_tasks(tasks);
+        scoped.set_dependencies(
+            project
+                .dependencies
+                .iter()
+                .filter(|d| keep.contains(&d.from_task) && keep.contains(&d.to_task))
+                .cloned()
+                .collect(),
+        );
+        scoped
+    }
+}
+
+/// One export format.
+pub trait Exporter {
+    /// Name shown in menus and used to look the format up, e.g. `"CSV"`.
+    fn name(&self) -> &'static str;
+
+    /// File extension without the dot.
+    fn extension(&self) -> &'static str;
+
+    /// Write `project` to `out`. Returns how many items were written.
+    fn export(&self, project: &Project, options: &ExportOptions, out: &mut dyn Write) -> Result<usize, String>;
+
+    /// What the count returned by [`Self::export`] counts.
+    fn item_label(&self) -> &'static str {
+        "tasks"
+    }
+
+    /// Suggested file name for a project called `project_name`.
+    fn file_name(&self, project_name: &str) -> String {
+        format!("{}.{}", project_name, self.extension())
+    }
+
+    /// Also put the output on the clipboard, for text formats that are
+    /// usually pasted somewhere.
+    fn copies_to_clipboard(&self) -> bool {
+        false
+    }
+
+    /// Format-specific choices the export dialog shows beside the shared
+    /// ones.
+    fn extras(&self) -> &'static [ExportExtra] {
+        &[]
+    }
+}
+
+/// Every export format, in menu order.
+#[derive(Default)]
+pub struct ExporterRegistry {
+    exporters: Vec<Arc<dyn Exporter>>,
+}
+
+impl ExporterRegistry {
+    /// Registry holding the built-in formats.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::default();
+        registry.register(csv_export::CsvExporter);
+        registry.register(html_export::HtmlExporter);
+        registry.register(plantuml_export::PlantUmlExporter);
+        registry.register(org_export::OrgExporter);
+        registry.register(csv_export::TimeEntriesExporter);
+        registry.register(csv_export::ProgressHistoryExporter);
+        registry
+    }
+
+    /// Add a format, replacing any registered under the same name.
+    pub fn register(&mut self, exporter: impl Exporter + 'static) {
+        let exporter: Arc<dyn Exporter> = Arc::new(exporter);
+        match self.exporters.iter_mut().find(|e| e.name() == exporter.name()) {
+            Some(existing) => *existing = exporter,
+            None => self.exporters.push(exporter),
+        }
+    }
+
+    /// The format called `name` (ignoring case), or failing that the first
+    /// one writing files with that extension.
+    pub fn find(&self, name: &str) -> Option<Arc<dyn Exporter>> {
+        let name = name.trim().trim_start_matches('.');
+        self.exporters
+            .iter()
+            .find(|e| e.name().eq_ignore_ascii_case(name))
+            .or_else(|| self.exporters.iter().find(|e| e.extension().eq_ignore_ascii_case(name)))
+            .cloned()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<dyn Exporter>> {
+        self.exporters.iter()
+    }
+}
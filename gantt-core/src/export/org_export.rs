@@ -0,0 +1,124 @@
+use super::{ExportOptions, Exporter};
+use crate::task::TaskPriority;
+use crate::project::Project;
+use crate::task::Task;
+use chrono::NaiveDateTime;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::io::Write;
+use uuid::Uuid;
+
+/// An active org timestamp, `<2024-03-04 Mon 09:00>`.
+fn timestamp(at: NaiveDateTime) -> String {
+    at.format("<%Y-%m-%d %a %H:%M>").to_string()
+}
+
+/// Org's priority cookies run from `A` (highest) to `D` here, declared by
+/// `#+PRIORITIES` in the file header; tasks without a priority get none.
+fn priority_cookie(priority: TaskPriority) -> &'static str {
+    match priority {
+        TaskPriority::None => "",
+        TaskPriority::Critical => " [#A]",
+        TaskPriority::High => " [#B]",
+        TaskPriority::Medium => " [#C]",
+        TaskPriority::Low => " [#D]",
+    }
+}
+
+/// Org tags may only hold letters, digits, `_`, `@`, `#` and `%`.
+fn org_tag(tag: &str) -> String {
+    tag.trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '_' | '@' | '#' | '%') { c } else { '_' })
+        .collect()
+}
+
+fn is_done(task: &Task, tasks: &[Task]) -> bool {
+    let children = task.children_ids(tasks);
+    if children.is_empty() {
+        task.progress >= 1.0
+    } else {
+        children.iter().all(|c| is_done(c, tasks))
+    }
+}
+
+fn write_heading(out: &mut String, task: &Task, tasks: &[Task], level: usize, visited: &mut HashSet<Uuid>) {
+    if !visited.insert(task.id) {
+        return;
+    }
+    let children = task.children_ids(tasks);
+    let keyword = if is_done(task, tasks) { "DONE" } else { "TODO" };
+    let name = task.name.split_whitespace().collect::<Vec<_>>().join(" ");
+    let _ = write!(out, "{} {}{} {}", "*".repeat(level), keyword, priority_cookie(task.priority), name);
+    if !children.is_empty() {
+        // Org keeps the cookie up to date itself; fill in the current value
+        // so it reads right before the first refresh.
+        let done = children.iter().filter(|c| is_done(c, tasks)).count();
+        let _ = write!(out, " [{}%]", done * 100 / children.len());
+    }
+    let tags: Vec<String> = task.tags.iter().map(|t| org_tag(t)).filter(|t| !t.is_empty()).collect();
+    if !tags.is_empty() {
+        let _ = write!(out, " :{}:", tags.join(":"));
+    }
+    out.push('\n');
+
+    // The planning line has to come straight after the heading.
+    let indent = " ".repeat(level + 1);
+    if task.is_milestone {
+        let _ = writeln!(out, "{}DEADLINE: {}", indent, timestamp(task.start));
+    } else {
+        let _ = writeln!(out, "{}SCHEDULED: {} DEADLINE: {}", indent, timestamp(task.start), timestamp(task.end));
+    }
+    let _ = writeln!(out, "{}:PROPERTIES:", indent);
+    let _ = writeln!(out, "{}:ID:       {}", indent, task.id);
+    if !task.is_milestone && children.is_empty() {
+        let _ = writeln!(out, "{}:PROGRESS: {:.0}%", indent, task.progress * 100.0);
+    }
+    let _ = writeln!(out, "{}:END:", indent);
+    // Indenting the description keeps lines starting with `*` from
+    // being read as headings.
+    for line in task.description.lines() {
+        let _ = writeln!(out, "{}{}", indent, line);
+    }
+
+    for child in children {
+        write_heading(out, child, tasks, level + 1, visited);
+    }
+}
+
+/// The project as an org-mode outline: one heading per task, nested by the
+/// task hierarchy, with `SCHEDULED`/`DEADLINE` timestamps org-agenda reads.
+pub fn to_org(project: &Project) -> String {
+    let tasks = &project.tasks;
+    let ids: HashSet<Uuid> = tasks.iter().map(|t| t.id).collect();
+    let mut out = String::new();
+    let _ = writeln!(out, "#+TITLE: {}", project.name.replace('\n', " "));
+    let _ = writeln!(out, "#+TODO: TODO | DONE");
+    let _ = writeln!(out, "#+PRIORITIES: A D C");
+    out.push('\n');
+
+    let mut visited = HashSet::new();
+    // Tasks whose parent is missing are written at the top level.
+    for task in tasks.iter().filter(|t| t.parent_id.is_none_or(|p| !ids.contains(&p))) {
+        write_heading(&mut out, task, tasks, 1, &mut visited);
+    }
+    out
+}
+
+pub struct OrgExporter;
+
+impl Exporter for OrgExporter {
+    fn name(&self) -> &'static str {
+        "Org"
+    }
+
+    fn extension(&self) -> &'static str {
+        "org"
+    }
+
+    fn export(&self, project: &Project, _: &ExportOptions, out: &mut dyn Write) -> Result<usize, String> {
+        out.write_all(to_org(project).as_bytes())
+            .map_err(|e| format!("Failed to write org file: {}", e))?;
+        Ok(project.tasks.len())
+    }
+}
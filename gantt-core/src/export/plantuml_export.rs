@@ -0,0 +1,163 @@
+use super::{ExportOptions, Exporter};
+use crate::task::{Dependency, DependencyKind};
+use crate::project::Project;
+use crate::task::Task;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::io::Write;
+use uuid::Uuid;
+
+/// A task name PlantUML can read between `[` and `]`: brackets become
+/// parentheses and runs of whitespace (including newlines) one space.
+fn plantuml_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| match c {
+            '[' => '(',
+            ']' => ')',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    let cleaned = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+    if cleaned.is_empty() { "Task".to_string() } else { cleaned }
+}
+
+/// A unique PlantUML name for every task. PlantUML tells tasks apart by
+/// name, so names that clash (ignoring case) after cleaning get ` (2)`,
+/// ` (3)`… appended.
+fn unique_names(tasks: &[Task]) -> HashMap<Uuid, String> {
+    let mut taken: HashSet<String> = HashSet::new();
+    let mut names = HashMap::new();
+    for task in tasks {
+        let base = plantuml_name(&task.name);
+        let mut name = base.clone();
+        let mut n = 2;
+        while !taken.insert(name.to_lowercase()) {
+            name = format!("{} ({})", base, n);
+            n += 1;
+        }
+        names.insert(task.id, name);
+    }
+    names
+}
+
+/// The finish-to-start link a task's start can be written against: the
+/// first one from a leaf task, without lead time.
+fn fs_predecessor<'a>(task: &Task, deps: &'a [Dependency], parents: &HashSet<Uuid>) -> Option<&'a Dependency> {
+    deps.iter().find(|d| {
+        d.to_task == task.id
+            && d.kind == DependencyKind::FinishToStart
+            && d.lag_days >= 0
+            && !parents.contains(&d.from_task)
+    })
+}
+
+/// The project in PlantUML's `@startgantt` syntax. Phases (parent tasks)
+/// become separators, milestones `happens`, and a task with a plain
+/// finish-to-start predecessor starts at that task's end; everything else
+/// keeps its own dates. Tasks with a slug are given it as their alias, so
+/// the file refers to them by it.
+pub fn to_plantuml(project: &Project) -> String {
+    let tasks = &project.tasks;
+    let names = unique_names(tasks);
+    let parents: HashSet<Uuid> = tasks.iter().filter_map(|t| t.parent_id).collect();
+    let name = |id: Uuid| names.get(&id).map(String::as_str).unwrap_or("Task");
+    let slug = |id: Uuid| project.task(id).and_then(|t| t.slug.as_deref());
+    // A task is named in brackets until the line that gives it its alias;
+    // from then on it goes by the alias.
+    let mut aliased: HashSet<Uuid> = HashSet::new();
+    let refer = |id: Uuid, aliased: &HashSet<Uuid>| match slug(id).filter(|_| aliased.contains(&id)) {
+        Some(slug) => format!("[{}]", slug),
+        None => format!("[{}]", name(id)),
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(out, "@startgantt");
+    let _ = writeln!(out, "title {}", project.name.replace('\n', " "));
+    if let Some(start) = tasks.iter().map(|t| t.start).min() {
+        let _ = writeln!(out, "Project starts {}", start.format("%Y-%m-%d"));
+    }
+
+    for task in tasks {
+        if parents.contains(&task.id) {
+            let _ = writeln!(out, "-- {} --", name(task.id));
+            continue;
+        }
+        let this = match slug(task.id) {
+            Some(slug) if aliased.insert(task.id) => format!("[{}] as [{}]", name(task.id), slug),
+            _ => refer(task.id, &aliased),
+        };
+        let link = fs_predecessor(task, &project.dependencies, &parents);
+        if task.is_milestone {
+            match link.filter(|d| d.lag_days == 0) {
+                Some(d) => {
+                    let _ = writeln!(out, "{} happens at {}'s end", this, refer(d.from_task, &aliased));
+                }
+                None => {
+                    let _ = writeln!(out, "{} happens {}", this, task.start.format("%Y-%m-%d"));
+                }
+            }
+        } else {
+            match link {
+                Some(d) if d.lag_days == 0 => {
+                    let _ = writeln!(
+                        out,
+                        "{} starts at {}'s end and lasts {} days",
+                        this,
+                        refer(d.from_task, &aliased),
+                        task.duration_days()
+                    );
+                }
+                Some(d) => {
+                    let _ = writeln!(
+                        out,
+                        "{} starts {} days after {}'s end and lasts {} days",
+                        this,
+                        d.lag_days,
+                        refer(d.from_task, &aliased),
+                        task.duration_days()
+                    );
+                }
+                None => {
+                    let _ = writeln!(
+                        out,
+                        "{} starts {} and ends {}",
+                        this,
+                        task.start.format("%Y-%m-%d"),
+                        task.end.format("%Y-%m-%d")
+                    );
+                }
+            }
+            if task.progress > 0.0 {
+                let _ = writeln!(out, "{} is {:.0}% completed", refer(task.id, &aliased), task.progress * 100.0);
+            }
+        }
+        let _ = writeln!(out, "{} is colored in {}", refer(task.id, &aliased), task.color.to_hex());
+    }
+
+    let _ = writeln!(out, "@endgantt");
+    out
+}
+
+pub struct PlantUmlExporter;
+
+impl Exporter for PlantUmlExporter {
+    fn name(&self) -> &'static str {
+        "PlantUML"
+    }
+
+    fn extension(&self) -> &'static str {
+        "puml"
+    }
+
+    fn export(&self, project: &Project, _: &ExportOptions, out: &mut dyn Write) -> Result<usize, String> {
+        out.write_all(to_plantuml(project).as_bytes())
+            .map_err(|e| format!("Failed to write PlantUML: {}", e))?;
+        Ok(project.tasks.len())
+    }
+
+    fn copies_to_clipboard(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,104 @@
+use crate::Project;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Why a save failed.
+#[derive(Debug, Clone)]
+pub struct SaveError {
+    /// The IO or serialization error, as reported by the system.
+    pub message: String,
+    /// A complete copy of the project that could not be moved over the
+    /// target, left in place for manual recovery.
+    pub temp_file: Option<PathBuf>,
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.temp_file {
+            Some(tmp) => write!(f, "{} (a copy was kept at {})", self.message, tmp.display()),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+/// Somewhere [`save_project_with`] can write the temporary copy.
+pub trait SaveSink: Write {
+    /// Make everything written so far durable.
+    fn sync(&mut self) -> io::Result<()>;
+}
+
+impl SaveSink for File {
+    fn sync(&mut self) -> io::Result<()> {
+        self.sync_all()
+    }
+}
+
+/// The temporary file a save of `path` writes first: `path` with `.tmp`
+/// appended, in the same directory so the rename stays on one filesystem.
+pub fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+/// Save a project to a JSON file.
+///
+/// The JSON goes to [`temp_path`], is synced to disk and then renamed over
+/// `path`, so a failed or interrupted save leaves the old file intact.
+pub fn save_project(project: &Project, path: &Path) -> Result<(), SaveError> {
+    save_project_with(project, path, |tmp| File::create(tmp))
+}
+
+/// [`save_project`] writing the temporary copy to whatever `create` opens
+/// for its path.
+///
+/// A copy that was only partly written is deleted; one that was complete
+/// but couldn't be renamed is kept and named in the error.
+pub fn save_project_with<W: SaveSink>(
+    project: &Project,
+    path: &Path,
+    create: impl FnOnce(&Path) -> io::Result<W>,
+) -> Result<(), SaveError> {
+    let tmp = temp_path(path);
+    let written = create(&tmp).and_then(|mut sink| {
+        let mut out = BufWriter::new(&mut sink);
+        serde_json::to_writer_pretty(&mut out, project)?;
+        out.flush()?;
+        drop(out);
+        sink.sync()
+    });
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(SaveError { message: e.to_string(), temp_file: None });
+    }
+    if let Err(e) = std::fs::rename(&tmp, path) {
+        return Err(SaveError { message: e.to_string(), temp_file: Some(tmp) });
+    }
+    sync_parent(path);
+    Ok(())
+}
+
+/// Make the rename itself durable. Best effort: the new file is complete
+/// either way, and not every platform can sync a directory.
+fn sync_parent(path: &Path) {
+    #[cfg(unix)]
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        let _ = File::open(dir).and_then(|d| d.sync_all());
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+}
+
+/// Load a project from a JSON file.
+pub fn load_project(path: &PathBuf) -> Result<Project, String> {
+    let json = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut project: Project = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+    project.rebuild_index();
+    project.repair_dates();
+    project.recalculate_parent_dates();
+    Ok(project)
+}
@@ -0,0 +1,67 @@
+//! How long the project runs and when it will likely finish at the pace
+//! kept so far.
+//!
+//! The forecast is a straight line: if the first half of the elapsed time
+//! got a quarter of the work done, the whole takes four times as long. It
+//! is only as good as the progress entered, and says nothing until some
+//! work is done.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Weekday};
+
+use crate::project::Project;
+
+/// The span of a project's tasks and its forecast finish.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Forecast {
+    /// Earliest task start.
+    pub start: NaiveDateTime,
+    /// Latest task end.
+    pub end: NaiveDateTime,
+    /// Monday-to-Friday days from the first day to the last, both counted.
+    pub working_days: i64,
+    /// When the work will be done at the pace so far: the planned end once
+    /// everything is complete, `None` before the start or before any
+    /// progress.
+    pub finish: Option<NaiveDateTime>,
+}
+
+impl Forecast {
+    /// Days the forecast finish falls after `target`, if it does.
+    pub fn days_late(&self, target: NaiveDate) -> Option<i64> {
+        let late = (self.finish?.date() - target).num_days();
+        (late > 0).then_some(late)
+    }
+}
+
+/// Monday-to-Friday days from `first` to `last`, both counted.
+pub fn working_days(first: NaiveDate, last: NaiveDate) -> i64 {
+    let days = (last - first).num_days() + 1;
+    if days <= 0 {
+        return 0;
+    }
+    let weekdays_in = |n: i64| {
+        (0..n)
+            .filter(|i| !matches!((first + Duration::days(*i)).weekday(), Weekday::Sat | Weekday::Sun))
+            .count() as i64
+    };
+    days / 7 * 5 + weekdays_in(days % 7)
+}
+
+impl Project {
+    /// Span and forecast finish of the tasks as of `now`; `None` without
+    /// tasks. Progress is [`Project::completion`], so long tasks weigh more.
+    pub fn forecast(&self, now: NaiveDateTime) -> Option<Forecast> {
+        let start = self.tasks.iter().map(|t| t.start).min()?;
+        let end = self.tasks.iter().map(|t| t.end).max()?;
+        let done = self.completion() as f64;
+        let finish = if done >= 1.0 {
+            Some(end)
+        } else if done > 0.0 && now > start {
+            let total = (now - start).num_seconds() as f64 / done;
+            Duration::try_seconds(total as i64).and_then(|total| start.checked_add_signed(total))
+        } else {
+            None
+        };
+        Some(Forecast { start, end, working_days: working_days(start.date(), end.date()), finish })
+    }
+}
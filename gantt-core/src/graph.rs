@@ -0,0 +1,280 @@
+//! Adjacency view of a project's dependency links.
+//!
+//! Built once from the flat dependency list and cached on the [`Project`]
+//! until the links change, so scheduling, validation and the editor can
+//! look up a task's predecessors and successors without scanning every link.
+//!
+//! [`Project`]: super::Project
+
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+
+use super::task::Dependency;
+
+/// ```
+/// use gantt_core::graph::DependencyGraph;
+/// use gantt_core::task::{Dependency, DependencyKind};
+/// use uuid::Uuid;
+///
+/// let (design, build) = (Uuid::new_v4(), Uuid::new_v4());
+/// let link = Dependency { from_task: design, to_task: build, kind: DependencyKind::FinishToStart, lag_days: 0 };
+/// let graph = DependencyGraph::new(&[link]);
+/// assert_eq!(graph.predecessors(build)[0].from_task, design);
+/// assert!(graph.would_create_cycle(build, design));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    successors: HashMap<Uuid, Vec<Dependency>>,
+    predecessors: HashMap<Uuid, Vec<Dependency>>,
+    /// Tasks on a cycle, grouped: tasks in the same cycle share an index.
+    cycles: HashMap<Uuid, usize>,
+    /// Linked tasks, predecessors first, ignoring links that close a cycle.
+    topo: Vec<Uuid>,
+    edge_count: usize,
+}
+
+impl DependencyGraph {
+    pub fn new(dependencies: &[Dependency]) -> Self {
+        let mut graph = Self { edge_count: dependencies.len(), ..Default::default() };
+        // Tasks in order of first appearance, so the topological order is stable.
+        let mut nodes: Vec<Uuid> = Vec::new();
+        let mut seen = HashSet::new();
+        for d in dependencies {
+            graph.successors.entry(d.from_task).or_default().push(d.clone());
+            graph.predecessors.entry(d.to_task).or_default().push(d.clone());
+            for id in [d.from_task, d.to_task] {
+                if seen.insert(id) {
+                    nodes.push(id);
+                }
+            }
+        }
+        graph.cycles = graph.find_cycles(&nodes);
+        graph.topo = graph.topological_order(&nodes);
+        graph
+    }
+
+    /// Number of links the graph was built from.
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Links into `id`.
+    pub fn predecessors(&self, id: Uuid) -> &[Dependency] {
+        self.predecessors.get(&id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Links out of `id`.
+    pub fn successors(&self, id: Uuid) -> &[Dependency] {
+        self.successors.get(&id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Every link into or out of `id`, incoming first.
+    pub fn links_of(&self, id: Uuid) -> impl Iterator<Item = &Dependency> {
+        self.predecessors(id).iter().chain(self.successors(id))
+    }
+
+    /// True if there is already a link `from → to`.
+    pub fn has_link(&self, from: Uuid, to: Uuid) -> bool {
+        self.successors(from).iter().any(|d| d.to_task == to)
+    }
+
+    /// True if `id` has any link.
+    pub fn is_linked(&self, id: Uuid) -> bool {
+        self.predecessors.contains_key(&id) || self.successors.contains_key(&id)
+    }
+
+    /// Linked tasks in dependency order (predecessors first). Links that
+    /// close a cycle are ignored.
+    pub fn topo_order(&self) -> &[Uuid] {
+        &self.topo
+    }
+
+    /// True if any links form a cycle.
+    pub fn has_cycle(&self) -> bool {
+        !self.cycles.is_empty()
+    }
+
+    /// Tasks on a cycle; tasks in the same cycle share an index.
+    pub fn cycle_groups(&self) -> &HashMap<Uuid, usize> {
+        &self.cycles
+    }
+
+    /// True if `dep` is part of a cycle.
+    pub fn is_cyclic_edge(&self, dep: &Dependency) -> bool {
+        match (self.cycles.get(&dep.from_task), self.cycles.get(&dep.to_task)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// True if adding a link `from → to` would close a cycle (including a
+    /// task linked to itself).
+    pub fn would_create_cycle(&self, from: Uuid, to: Uuid) -> bool {
+        // Walk successors from `to`; reaching `from` means `from` already follows `to`.
+        let mut stack = vec![to];
+        let mut seen = HashSet::new();
+        while let Some(id) = stack.pop() {
+            if id == from {
+                return true;
+            }
+            if seen.insert(id) {
+                stack.extend(self.successors(id).iter().map(|d| d.to_task));
+            }
+        }
+        false
+    }
+
+    /// Every task `id` depends on, directly or through other tasks.
+    pub fn upstream(&self, id: Uuid) -> HashSet<Uuid> {
+        self.reachable(id, |id| self.predecessors(id).iter().map(|d| d.from_task).collect())
+    }
+
+    /// Every task that depends on `id`, directly or through other tasks.
+    pub fn downstream(&self, id: Uuid) -> HashSet<Uuid> {
+        self.reachable(id, |id| self.successors(id).iter().map(|d| d.to_task).collect())
+    }
+
+    /// Tasks reachable from `root` through `next`, not counting `root`
+    /// itself unless a cycle leads back to it.
+    fn reachable(&self, root: Uuid, next: impl Fn(Uuid) -> Vec<Uuid>) -> HashSet<Uuid> {
+        let mut seen = HashSet::new();
+        let mut stack = next(root);
+        while let Some(id) = stack.pop() {
+            if seen.insert(id) {
+                stack.extend(next(id));
+            }
+        }
+        seen
+    }
+
+    /// Strongly connected components with more than one task (or a self
+    /// link), via iterative Kosaraju: finish order on the graph, then
+    /// components on the reversed graph.
+    fn find_cycles(&self, nodes: &[Uuid]) -> HashMap<Uuid, usize> {
+        let mut visited = HashSet::new();
+        let mut order = Vec::with_capacity(nodes.len());
+        for &root in nodes {
+            if !visited.insert(root) {
+                continue;
+            }
+            let mut stack = vec![(root, 0usize)];
+            while let Some((id, next)) = stack.pop() {
+                if let Some(child) = self.successors(id).get(next).map(|d| d.to_task) {
+                    stack.push((id, next + 1));
+                    if visited.insert(child) {
+                        stack.push((child, 0));
+                    }
+                } else {
+                    order.push(id);
+                }
+            }
+        }
+
+        let mut component: HashMap<Uuid, usize> = HashMap::new();
+        let mut groups = HashMap::new();
+        let mut count = 0;
+        for &root in order.iter().rev() {
+            if component.contains_key(&root) {
+                continue;
+            }
+            let mut members = vec![root];
+            let mut stack = vec![root];
+            component.insert(root, count);
+            while let Some(id) = stack.pop() {
+                for p in self.predecessors(id).iter().map(|d| d.from_task) {
+                    if let std::collections::hash_map::Entry::Vacant(e) = component.entry(p) {
+                        e.insert(count);
+                        members.push(p);
+                        stack.push(p);
+                    }
+                }
+            }
+            let self_loop = self.successors(root).iter().any(|d| d.to_task == root);
+            if members.len() > 1 || self_loop {
+                for id in members {
+                    groups.insert(id, count);
+                }
+            }
+            count += 1;
+        }
+        groups
+    }
+
+    /// Kahn's algorithm over `nodes`, skipping cyclic links. Needs `cycles`.
+    fn topological_order(&self, nodes: &[Uuid]) -> Vec<Uuid> {
+        let mut indegree: HashMap<Uuid, usize> = nodes
+            .iter()
+            .map(|&id| (id, self.predecessors(id).iter().filter(|d| !self.is_cyclic_edge(d)).count()))
+            .collect();
+        let mut ready: Vec<Uuid> = nodes.iter().rev().copied().filter(|id| indegree[id] == 0).collect();
+        let mut order = Vec::with_capacity(nodes.len());
+        while let Some(id) = ready.pop() {
+            order.push(id);
+            for d in self.successors(id).iter().filter(|d| !self.is_cyclic_edge(d)) {
+                if let Some(n) = indegree.get_mut(&d.to_task) {
+                    *n -= 1;
+                    if *n == 0 {
+                        ready.push(d.to_task);
+                    }
+                }
+            }
+        }
+        order
+    }
+}
+
+/// Which side of a traced task a task or link is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceSide {
+    /// Something the traced task waits for.
+    Upstream,
+    /// Something waiting for the traced task.
+    Downstream,
+}
+
+/// The full predecessor and successor chains of one task, computed once
+/// and reused every frame until the task or the links change.
+#[derive(Debug, Clone)]
+pub struct DependencyTrace {
+    pub root: Uuid,
+    pub upstream: HashSet<Uuid>,
+    pub downstream: HashSet<Uuid>,
+}
+
+impl DependencyTrace {
+    pub fn new(graph: &DependencyGraph, root: Uuid) -> Self {
+        Self { root, upstream: graph.upstream(root), downstream: graph.downstream(root) }
+    }
+
+    /// True if `id` is the traced task or on either chain.
+    pub fn contains(&self, id: Uuid) -> bool {
+        id == self.root || self.upstream.contains(&id) || self.downstream.contains(&id)
+    }
+
+    /// The side of a task on one of the chains; the root is on neither.
+    pub fn side_of(&self, id: Uuid) -> Option<TraceSide> {
+        if id == self.root {
+            None
+        } else if self.upstream.contains(&id) {
+            Some(TraceSide::Upstream)
+        } else if self.downstream.contains(&id) {
+            Some(TraceSide::Downstream)
+        } else {
+            None
+        }
+    }
+
+    /// The chain a link belongs to, if it connects tasks along one.
+    pub fn link_side(&self, dep: &Dependency) -> Option<TraceSide> {
+        let upstream_end = |id| id == self.root || self.upstream.contains(&id);
+        let downstream_end = |id| id == self.root || self.downstream.contains(&id);
+        if self.upstream.contains(&dep.from_task) && upstream_end(dep.to_task) {
+            Some(TraceSide::Upstream)
+        } else if downstream_end(dep.from_task) && self.downstream.contains(&dep.to_task) {
+            Some(TraceSide::Downstream)
+        } else {
+            None
+        }
+    }
+}
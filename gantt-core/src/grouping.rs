@@ -0,0 +1,144 @@
+//! Regrouping tasks by a field for triage: by priority, assignee, tag or
+//! status instead of the parent hierarchy.
+//!
+//! Groups are worked out from the tasks each time and never stored; the
+//! only way they change a task is [`GroupKey::apply`], which sets the
+//! grouped field so the task belongs to another group.
+
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use crate::task::{Task, TaskPriority};
+
+/// How the task list is organised.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GroupBy {
+    /// Parents and their subtasks, as stored.
+    #[default]
+    Hierarchy,
+    Priority,
+    Assignee,
+    /// By each task's first tag.
+    Tag,
+    Status,
+}
+
+impl GroupBy {
+    pub const ALL: [GroupBy; 5] =
+        [GroupBy::Hierarchy, GroupBy::Priority, GroupBy::Assignee, GroupBy::Tag, GroupBy::Status];
+}
+
+/// How far along a task is, as grouped by [`GroupBy::Status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Status {
+    NotStarted,
+    InProgress,
+    Done,
+}
+
+impl Status {
+    pub fn of(task: &Task) -> Self {
+        if task.is_complete() {
+            Status::Done
+        } else if task.progress > 0.0 {
+            Status::InProgress
+        } else {
+            Status::NotStarted
+        }
+    }
+}
+
+/// The value a group collects tasks by.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GroupKey {
+    Priority(TaskPriority),
+    /// `None` for unassigned tasks.
+    Assignee(Option<String>),
+    /// `None` for tasks without tags.
+    Tag(Option<String>),
+    Status(Status),
+}
+
+impl GroupKey {
+    /// The group `task` falls in under `by`; `None` for the hierarchy.
+    pub fn of(task: &Task, by: GroupBy) -> Option<GroupKey> {
+        match by {
+            GroupBy::Hierarchy => None,
+            GroupBy::Priority => Some(GroupKey::Priority(task.priority)),
+            GroupBy::Assignee => Some(GroupKey::Assignee(task.assignee_name().map(str::to_string))),
+            GroupBy::Tag => Some(GroupKey::Tag(task.tags.first().cloned())),
+            GroupBy::Status => Some(GroupKey::Status(Status::of(task))),
+        }
+    }
+
+    /// Change `task` so it falls in this group. A new tag replaces the first
+    /// one and "no tag" clears them all; "in progress" keeps partial
+    /// progress and otherwise sets it to half.
+    pub fn apply(&self, task: &mut Task) {
+        match self {
+            GroupKey::Priority(priority) => task.priority = *priority,
+            GroupKey::Assignee(assignee) => task.assignee = assignee.clone(),
+            GroupKey::Tag(None) => task.tags.clear(),
+            GroupKey::Tag(Some(tag)) => {
+                task.tags.retain(|t| t != tag);
+                if task.tags.is_empty() {
+                    task.tags.push(tag.clone());
+                } else {
+                    task.tags[0] = tag.clone();
+                }
+            }
+            GroupKey::Status(Status::NotStarted) => task.progress = 0.0,
+            GroupKey::Status(Status::InProgress) => {
+                if Status::of(task) != Status::InProgress {
+                    task.progress = 0.5;
+                }
+            }
+            GroupKey::Status(Status::Done) => task.progress = 1.0,
+        }
+    }
+
+    /// Position of the group in the list: most urgent priority and earliest
+    /// status first, names alphabetically with the empty group last.
+    fn order(&self) -> (u8, String) {
+        match self {
+            GroupKey::Priority(p) => {
+                let rank = TaskPriority::all().iter().position(|q| q == p).unwrap_or(0);
+                (4 - rank as u8, String::new())
+            }
+            GroupKey::Status(s) => (*s as u8, String::new()),
+            GroupKey::Assignee(name) | GroupKey::Tag(name) => match name {
+                Some(name) => (0, name.to_lowercase()),
+                None => (1, String::new()),
+            },
+        }
+    }
+}
+
+/// The tasks of one group, as indices into the task list in list order.
+#[derive(Debug, Clone)]
+pub struct TaskGroup {
+    pub key: GroupKey,
+    pub tasks: Vec<usize>,
+}
+
+/// Tasks grouped by `by`, in group order; empty for the hierarchy. Parents
+/// are left out, since their dates and progress come from their subtasks.
+pub fn group_tasks(tasks: &[Task], by: GroupBy) -> Vec<TaskGroup> {
+    let parents: HashSet<Uuid> = tasks.iter().filter_map(|t| t.parent_id).collect();
+    let mut groups: Vec<TaskGroup> = Vec::new();
+    for (i, task) in tasks.iter().enumerate() {
+        if parents.contains(&task.id) {
+            continue;
+        }
+        let Some(key) = GroupKey::of(task, by) else {
+            continue;
+        };
+        match groups.iter_mut().find(|g| g.key == key) {
+            Some(group) => group.tasks.push(i),
+            None => groups.push(TaskGroup { key, tasks: vec![i] }),
+        }
+    }
+    groups.sort_by_key(|g| g.key.order());
+    groups
+}
@@ -0,0 +1,192 @@
+//! Project rules that mark tasks needing attention.
+//!
+//! Each rule is a switch plus its thresholds, saved with the project. The
+//! rules are checked against the tasks and the current time whenever the
+//! chart or the task list is drawn, so a task is marked as soon as it
+//! crosses a threshold and unmarked once it is done.
+//!
+//! What counts as late or due soon is decided once, by [`due_state`], for
+//! the highlights, the list's due badges and filter, and deadline
+//! reminders alike.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::color::Rgba;
+use crate::project::Project;
+use crate::task::{hidden_where, Task};
+
+/// How a rule marks a task. A task gets at most one; overdue wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    /// Unfinished past its end: the bar is tinted red.
+    Overdue,
+    /// Ending soon with too little done: an amber badge on the bar.
+    AtRisk,
+}
+
+impl Highlight {
+    pub fn label(self) -> &'static str {
+        match self {
+            Highlight::Overdue => "Overdue",
+            Highlight::AtRisk => "At risk",
+        }
+    }
+
+    pub fn color(self) -> Rgba {
+        match self {
+            Highlight::Overdue => Rgba::from_rgb(220, 60, 60),
+            Highlight::AtRisk => Rgba::from_rgb(230, 160, 40),
+        }
+    }
+}
+
+/// Where a task stands against the time it is due.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DueState {
+    /// Unfinished and past due.
+    Late,
+    /// Unfinished and due within the window.
+    DueSoon,
+    /// Complete.
+    Done,
+}
+
+impl DueState {
+    pub fn label(self) -> &'static str {
+        match self {
+            DueState::Late => "Late",
+            DueState::DueSoon => "Due soon",
+            DueState::Done => "Done",
+        }
+    }
+
+    pub fn all() -> &'static [DueState] {
+        &[DueState::Late, DueState::DueSoon, DueState::Done]
+    }
+
+    pub fn color(self) -> Rgba {
+        match self {
+            DueState::Late => Rgba::from_rgb(220, 60, 60),
+            DueState::DueSoon => Rgba::from_rgb(230, 160, 40),
+            DueState::Done => Rgba::from_rgb(80, 180, 100),
+        }
+    }
+}
+
+/// When `task` is due: its end, or a milestone's date.
+pub fn due_time(task: &Task) -> NaiveDateTime {
+    if task.is_milestone { task.start } else { task.end }
+}
+
+/// Where `task` stands at `now`, counting it due soon from `window` before
+/// it is due. `None` for unfinished work due later than that.
+pub fn due_state(task: &Task, now: NaiveDateTime, window: Duration) -> Option<DueState> {
+    let due = due_time(task);
+    if task.progress >= 1.0 {
+        Some(DueState::Done)
+    } else if due < now {
+        Some(DueState::Late)
+    } else {
+        (due - now <= window).then_some(DueState::DueSoon)
+    }
+}
+
+/// `d` in its largest whole unit: `3d`, `5h` or `12m`; `None` under a
+/// minute.
+pub fn short_span(d: Duration) -> Option<String> {
+    if d.num_days() >= 1 {
+        Some(format!("{}d", d.num_days()))
+    } else if d.num_hours() >= 1 {
+        Some(format!("{}h", d.num_hours()))
+    } else if d.num_minutes() >= 1 {
+        Some(format!("{}m", d.num_minutes()))
+    } else {
+        None
+    }
+}
+
+/// Ids of the tasks to leave out when only those in `only` are shown: every
+/// other task with no such task below it, so a phase still frames its
+/// matching work. Nothing is left out for `None`.
+pub fn hidden_by_due(tasks: &[Task], only: Option<DueState>, states: &HashMap<Uuid, DueState>) -> HashSet<Uuid> {
+    let Some(only) = only else {
+        return HashSet::new();
+    };
+    hidden_where(tasks, |t| states.get(&t.id) != Some(&only))
+}
+
+/// The project's highlight rules. Milestones are left out; they show
+/// whether they were hit on their own.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HighlightRules {
+    /// Mark unfinished tasks whose end has passed.
+    pub overdue: bool,
+    /// Mark tasks ending within `due_soon_days` that are below
+    /// `due_soon_progress`.
+    pub due_soon: bool,
+    /// Days before the end a task starts counting as due soon.
+    pub due_soon_days: i64,
+    /// Progress, 0 to 1, a due-soon task has to reach to stay unmarked.
+    pub due_soon_progress: f32,
+}
+
+impl Default for HighlightRules {
+    fn default() -> Self {
+        Self { overdue: true, due_soon: true, due_soon_days: 3, due_soon_progress: 0.5 }
+    }
+}
+
+impl HighlightRules {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// How long before it is due a task counts as due soon.
+    pub fn due_window(&self) -> Duration {
+        Duration::days(self.due_soon_days.max(0))
+    }
+
+    /// How the rules mark `task` at `now`, if at all.
+    pub fn highlight(&self, task: &Task, now: NaiveDateTime) -> Option<Highlight> {
+        if task.is_milestone {
+            return None;
+        }
+        match due_state(task, now, self.due_window())? {
+            DueState::Late => self.overdue.then_some(Highlight::Overdue),
+            DueState::DueSoon => {
+                (self.due_soon && task.progress < self.due_soon_progress).then_some(Highlight::AtRisk)
+            }
+            DueState::Done => None,
+        }
+    }
+
+    /// The marked tasks among `tasks` at `now`.
+    pub fn evaluate(&self, tasks: &[Task], now: NaiveDateTime) -> HashMap<Uuid, Highlight> {
+        tasks
+            .iter()
+            .filter_map(|task| Some((task.id, self.highlight(task, now)?)))
+            .collect()
+    }
+}
+
+impl Project {
+    /// The tasks the project's rules mark at the current time.
+    pub fn highlights(&self) -> HashMap<Uuid, Highlight> {
+        self.highlight_rules.evaluate(&self.tasks, self.clock().now())
+    }
+
+    /// Where each task that is late, due soon or done stands at the
+    /// current time, by the window in the highlight rules.
+    pub fn due_states(&self) -> HashMap<Uuid, DueState> {
+        let (now, window) = (self.clock().now(), self.highlight_rules.due_window());
+        self.tasks
+            .iter()
+            .filter_map(|task| Some((task.id, due_state(task, now, window)?)))
+            .collect()
+    }
+}
@@ -0,0 +1,191 @@
+use super::task::{Dependency, ProgressPolicy, Task};
+use std::time::{Duration, Instant};
+
+const MAX_HISTORY: usize = 50;
+
+/// A snapshot of the mutable project data (tasks + dependencies).
+#[derive(Clone)]
+pub struct ProjectSnapshot {
+    /// Human-readable name of the action this snapshot undoes (e.g. "Rename task").
+    pub label: String,
+    pub tasks: Vec<Task>,
+    pub dependencies: Vec<Dependency>,
+    /// For a change of progress policy, the policy before and after it, so
+    /// undo and redo can put the right one back along with the tasks.
+    pub progress_policy: Option<(ProgressPolicy, ProgressPolicy)>,
+}
+
+/// Undo/redo stack for project mutations.
+///
+/// Push a snapshot before each change; undo hands back the state to restore
+/// and keeps the current one for redo.
+///
+/// ```
+/// use chrono::{Duration, NaiveDate};
+/// use gantt_core::{Task, UndoHistory};
+///
+/// let monday = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap().and_hms_opt(9, 0, 0).unwrap();
+/// let mut tasks = vec![Task::new("Design", monday, monday + Duration::days(4))];
+/// let mut history = UndoHistory::new();
+/// history.push("Rename task", &tasks, &[]);
+/// tasks[0].name = "Sketch".to_string();
+/// let restored = history.undo(&tasks, &[]).unwrap();
+/// assert_eq!(restored.tasks[0].name, "Design");
+/// ```
+pub struct UndoHistory {
+    past: Vec<ProjectSnapshot>,
+    future: Vec<ProjectSnapshot>,
+    /// When the newest `past` entry was pushed (or last coalesced into).
+    last_push: Option<Instant>,
+}
+
+impl Default for UndoHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UndoHistory {
+    pub fn new() -> Self {
+        Self {
+            past: Vec::new(),
+            future: Vec::new(),
+            last_push: None,
+        }
+    }
+
+    /// Push a snapshot of the current state before a mutation is applied.
+    /// `label` names the mutation, for display in undo/redo UI.
+    pub fn push(&mut self, label: impl Into<String>, tasks: &[Task], dependencies: &[Dependency]) {
+        if self.past.len() >= MAX_HISTORY {
+            self.past.remove(0);
+        }
+        self.past.push(ProjectSnapshot {
+            label: label.into(),
+            tasks: tasks.to_vec(),
+            dependencies: dependencies.to_vec(),
+            progress_policy: None,
+        });
+        // Any new action clears the redo stack.
+        self.future.clear();
+        self.last_push = Some(Instant::now());
+    }
+
+    /// Like [`push`](Self::push), for a change of the project's progress
+    /// policy from `from` to `to`.
+    pub fn push_policy_change(
+        &mut self,
+        label: impl Into<String>,
+        tasks: &[Task],
+        dependencies: &[Dependency],
+        from: ProgressPolicy,
+        to: ProgressPolicy,
+    ) {
+        self.push(label, tasks, dependencies);
+        if let Some(snapshot) = self.past.last_mut() {
+            snapshot.progress_policy = Some((from, to));
+        }
+    }
+
+    /// Undo: returns the previous snapshot (state to restore), saving the current state for redo.
+    pub fn undo(
+        &mut self,
+        current_tasks: &[Task],
+        current_deps: &[Dependency],
+    ) -> Option<ProjectSnapshot> {
+        let snapshot = self.past.pop()?;
+        self.last_push = None;
+        self.future.push(ProjectSnapshot {
+            label: snapshot.label.clone(),
+            tasks: current_tasks.to_vec(),
+            dependencies: current_deps.to_vec(),
+            progress_policy: snapshot.progress_policy,
+        });
+        Some(snapshot)
+    }
+
+    /// Redo: returns the next snapshot, saving current state back to undo stack.
+    pub fn redo(
+        &mut self,
+        current_tasks: &[Task],
+        current_deps: &[Dependency],
+    ) -> Option<ProjectSnapshot> {
+        let snapshot = self.future.pop()?;
+        self.last_push = None;
+        self.past.push(ProjectSnapshot {
+            label: snapshot.label.clone(),
+            tasks: current_tasks.to_vec(),
+            dependencies: current_deps.to_vec(),
+            progress_policy: snapshot.progress_policy,
+        });
+        Some(snapshot)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.past.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.future.is_empty()
+    }
+
+    /// Like [`push`](Self::push), but folds into the previous entry when it has
+    /// the same label and was pushed less than `window` ago, so a burst of
+    /// small edits (e.g. keyboard nudges) undoes in one step. Returns whether
+    /// it pushed a new snapshot.
+    pub fn push_coalescing(
+        &mut self,
+        label: impl Into<String>,
+        window: Duration,
+        tasks: &[Task],
+        dependencies: &[Dependency],
+    ) -> bool {
+        let label = label.into();
+        let recent = self.last_push.is_some_and(|t| t.elapsed() < window);
+        if recent && self.future.is_empty() && self.undo_label() == Some(label.as_str()) {
+            self.last_push = Some(Instant::now());
+            return false;
+        }
+        self.push(label, tasks, dependencies);
+        true
+    }
+
+    /// Rename the action the next undo would revert, for an action that
+    /// reuses the newest snapshot because nothing changed since it was taken.
+    pub fn relabel(&mut self, label: impl Into<String>) {
+        if let Some(snapshot) = self.past.last_mut() {
+            snapshot.label = label.into();
+        }
+    }
+
+    /// The snapshot the next undo would restore.
+    pub fn undo_snapshot(&self) -> Option<&ProjectSnapshot> {
+        self.past.last()
+    }
+
+    /// Label of the action the next undo would revert.
+    pub fn undo_label(&self) -> Option<&str> {
+        self.past.last().map(|s| s.label.as_str())
+    }
+
+    /// Label of the action the next redo would re-apply.
+    pub fn redo_label(&self) -> Option<&str> {
+        self.future.last().map(|s| s.label.as_str())
+    }
+
+    /// Labels of the actions undo would revert, next first.
+    pub fn undo_labels(&self) -> impl Iterator<Item = &str> {
+        self.past.iter().rev().map(|s| s.label.as_str())
+    }
+
+    /// Labels of the actions redo would re-apply, next first.
+    pub fn redo_labels(&self) -> impl Iterator<Item = &str> {
+        self.future.iter().rev().map(|s| s.label.as_str())
+    }
+
+    pub fn clear(&mut self) {
+        self.past.clear();
+        self.future.clear();
+        self.last_push = None;
+    }
+}
@@ -0,0 +1,51 @@
+//! The Gantt project model, without any UI: tasks, dependencies, scheduling,
+//! validation, undo history and reading and writing `.gantt` files.
+//!
+//! The desktop app is built on this crate; scripts and other front ends can
+//! use it on its own.
+//!
+//! ```
+//! use chrono::{Duration, NaiveDate};
+//! use gantt_core::{Project, Task};
+//!
+//! let monday = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap().and_hms_opt(9, 0, 0).unwrap();
+//! let mut project = Project::new("Launch");
+//! project.add_task(Task::new("Design", monday, monday + Duration::days(4)));
+//!
+//! let json = serde_json::to_string(&project).unwrap();
+//! let loaded: Project = serde_json::from_str(&json).unwrap();
+//! assert_eq!(loaded.tasks[0].name, "Design");
+//! ```
+
+pub mod absence;
+pub mod activity;
+pub mod clock;
+pub mod color;
+pub mod compare;
+pub mod date_preset;
+pub mod earned_value;
+pub mod export;
+pub mod file;
+pub mod forecast;
+pub mod graph;
+pub mod grouping;
+pub mod highlight;
+pub mod history;
+pub mod project;
+pub mod quick_add;
+pub mod reminders;
+pub mod risk;
+pub mod schedule;
+pub mod slug;
+pub mod synthetic;
+pub mod task;
+pub mod task_progress;
+pub mod timeline;
+pub mod validation;
+
+pub use color::Rgba;
+pub use file::{load_project, save_project};
+pub use history::UndoHistory;
+pub use project::Project;
+pub use task::Task;
+pub use timeline::{TimelineScale, TimelineViewport};
@@ -0,0 +1,897 @@
+use std::cell::{OnceCell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::absence::Absence;
+use super::activity::ActivityLog;
+use super::clock::{ProjectClock, Tz};
+use super::color::Rgba;
+use super::date_preset::DatePresets;
+use super::graph::DependencyGraph;
+use super::highlight::HighlightRules;
+use super::slug::dedupe_slugs;
+use super::task::{CompletedTasks, Dependency, DependencyKind, ProgressPolicy, ProgressSource, ShiftAmount, Task};
+use super::task_progress::TaskProgressLog;
+use super::validation::{subtask_spans, DependencyViolation};
+
+/// A Gantt project containing tasks, dependencies, and metadata.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Project {
+    /// Schema version — used to detect old files and migrate defaults. v1 = original, v2 = priority/description/parent, v3 = NaiveDateTime.
+    #[serde(default = "default_version")]
+    pub version: u32,
+    pub name: String,
+    pub tasks: Vec<Task>,
+    pub dependencies: Vec<Dependency>,
+    /// Custom task colours used recently in this project, most recent first.
+    #[serde(default)]
+    pub recent_colors: Vec<Rgba>,
+    /// Dated overall-completion readings, oldest first, for the progress chart.
+    #[serde(default)]
+    pub progress_history: Vec<ProgressSample>,
+    /// Keep a reading of each task's progress per day in `task_progress`.
+    #[serde(default)]
+    pub log_task_progress: bool,
+    /// Per-task progress by day, while `log_task_progress` is on.
+    #[serde(default, skip_serializing_if = "TaskProgressLog::is_empty")]
+    pub task_progress: TaskProgressLog,
+    /// Recorded changes to task fields and links; see [`ActivityLog`].
+    #[serde(default, skip_serializing_if = "ActivityLog::is_empty")]
+    pub activity: ActivityLog,
+    /// Shell command run after each save of this file, with `{file}`
+    /// standing for its path. Overrides the app-wide command.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_save_command: Option<String>,
+    /// Zone task times are wall-clock times in, stored by name. `None`
+    /// keeps them floating, as in files from before zones existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<Tz>,
+    #[serde(default)]
+    pub task_defaults: TaskDefaults,
+    /// Whether finished tasks are shown, struck through or hidden. Saved
+    /// with the file like each task's collapsed state.
+    #[serde(default, skip_serializing_if = "CompletedTasks::is_default")]
+    pub completed_tasks: CompletedTasks,
+    /// Where task progress comes from: by hand, checklists or subtasks.
+    #[serde(default, skip_serializing_if = "ProgressPolicy::is_default")]
+    pub progress_policy: ProgressPolicy,
+    /// Rules marking overdue and at-risk tasks on the chart and the list.
+    #[serde(default, skip_serializing_if = "HighlightRules::is_default")]
+    pub highlight_rules: HighlightRules,
+    /// Days people are away, edited in the Resources dialog.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub absences: Vec<Absence>,
+    /// Day the project has to be done by; the header warns when the
+    /// [`Project::forecast`] finish runs past it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_end: Option<NaiveDate>,
+    /// Quick dates offered beside the editor's date fields.
+    #[serde(default, skip_serializing_if = "DatePresets::is_default")]
+    pub date_presets: DatePresets,
+    pub created: DateTime<Utc>,
+    pub modified: DateTime<Utc>,
+    /// Position of every task in `tasks`, kept in step by the task mutation
+    /// methods below. Call [`Self::rebuild_index`] after replacing or
+    /// reordering `tasks` directly.
+    #[serde(skip)]
+    index: HashMap<Uuid, usize>,
+    /// Adjacency view of `dependencies`, built on first use and dropped by
+    /// the dependency mutation methods below.
+    #[serde(skip)]
+    graph: OnceCell<Arc<DependencyGraph>>,
+    /// [`Self::dependency_violations`] and the generation it was worked out at.
+    #[serde(skip)]
+    violations: RefCell<Option<(u64, Arc<[DependencyViolation]>)>>,
+    /// Changes whenever the project does; see [`Self::generation`].
+    #[serde(skip, default = "next_generation")]
+    generation: u64,
+    /// Tasks changed since the last [`Self::touch`], and the parents they
+    /// had, for it to roll up; `None` when any task may have changed.
+    #[serde(skip)]
+    changed: Option<HashSet<Uuid>>,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+/// Generations come from one process-wide counter, so two different
+/// projects (or a reloaded one) never share a value.
+static GENERATION: AtomicU64 = AtomicU64::new(1);
+
+fn next_generation() -> u64 {
+    GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// What converting a task into a milestone would discard, so the UI can ask
+/// before doing it. See [`Project::milestone_impact`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MilestoneImpact {
+    /// Calendar days the task spans; anything over 1 is collapsed to the start.
+    pub duration_days: i64,
+    /// Direct children, which are promoted to the top level.
+    pub children: usize,
+    /// Incoming FF/SF links, which are converted to FS/SS.
+    pub finish_links: usize,
+}
+
+impl MilestoneImpact {
+    /// True if the conversion loses nothing worth confirming.
+    pub fn is_harmless(&self) -> bool {
+        self.duration_days <= 1 && self.children == 0 && self.finish_links == 0
+    }
+}
+
+/// Starting values for tasks created in this project.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskDefaults {
+    /// Length of a new task in calendar days, counting its first and last.
+    pub duration_days: i64,
+    /// Time of day new tasks start, and milestones fall, on their first day.
+    pub start_time: NaiveTime,
+    /// Time of day new tasks end on their last day.
+    pub end_time: NaiveTime,
+    /// Kind first offered for links drawn on the chart or added in the
+    /// editor.
+    pub dependency_kind: DependencyKind,
+}
+
+impl Default for TaskDefaults {
+    fn default() -> Self {
+        Self {
+            duration_days: 7,
+            start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            end_time: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+            dependency_kind: DependencyKind::FinishToStart,
+        }
+    }
+}
+
+impl TaskDefaults {
+    /// Start and end of a task over `days` calendar days (at least one) from
+    /// `first_day`: the start time on the first day to the end time on the
+    /// last. An end time at or before the start time still leaves a one-day
+    /// task an hour long, so it never draws with zero width. A span running
+    /// past the last date there is ends on that date.
+    ///
+    /// ```
+    /// use chrono::{Duration, NaiveDate};
+    /// use gantt_core::project::TaskDefaults;
+    ///
+    /// let monday = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+    /// let (start, end) = TaskDefaults::default().span(monday, 1);
+    /// assert_eq!(start.date(), monday);
+    /// assert_eq!(end - start, Duration::hours(8));
+    /// ```
+    pub fn span(&self, first_day: NaiveDate, days: i64) -> (NaiveDateTime, NaiveDateTime) {
+        let start = first_day.and_time(self.start_time);
+        let last_day = chrono::Duration::try_days(days.max(1) - 1)
+            .and_then(|length| first_day.checked_add_signed(length))
+            .unwrap_or(NaiveDate::MAX);
+        let end = last_day.and_time(self.end_time);
+        (start, if end > start { end } else { start + chrono::Duration::hours(1) })
+    }
+
+    /// A task called `name` over `days` calendar days from `first_day`; see
+    /// [`Self::span`].
+    pub fn task(&self, name: impl Into<String>, first_day: NaiveDate, days: i64) -> Task {
+        let (start, end) = self.span(first_day, days);
+        Task::new(name, start, end)
+    }
+
+    /// A milestone called `name` at the start time on `day`.
+    pub fn milestone(&self, name: impl Into<String>, day: NaiveDate) -> Task {
+        Task::new_milestone(name, day.and_time(self.start_time))
+    }
+}
+
+/// Overall completion of the project on a given day.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ProgressSample {
+    pub date: NaiveDate,
+    /// 0.0–1.0, weighted by task duration (see [`Project::completion`]).
+    pub completion: f32,
+}
+
+/// How many days of [`ProgressSample`]s are kept.
+const MAX_PROGRESS_SAMPLES: usize = 730;
+
+/// How many custom colours [`Project::remember_color`] keeps.
+const MAX_RECENT_COLORS: usize = 8;
+
+impl Default for Project {
+    fn default() -> Self {
+        Self {
+            version: 3,
+            name: "Untitled Project".to_string(),
+            tasks: Vec::new(),
+            dependencies: Vec::new(),
+            recent_colors: Vec::new(),
+            progress_history: Vec::new(),
+            log_task_progress: false,
+            task_progress: TaskProgressLog::default(),
+            activity: ActivityLog::default(),
+            post_save_command: None,
+            time_zone: None,
+            task_defaults: TaskDefaults::default(),
+            completed_tasks: CompletedTasks::default(),
+            progress_policy: ProgressPolicy::default(),
+            highlight_rules: HighlightRules::default(),
+            absences: Vec::new(),
+            target_end: None,
+            date_presets: DatePresets::default(),
+            created: Utc::now(),
+            modified: Utc::now(),
+            index: HashMap::new(),
+            graph: OnceCell::new(),
+            violations: RefCell::new(None),
+            generation: next_generation(),
+            changed: None,
+        }
+    }
+}
+
+impl Project {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// How to read this project's task times; see [`ProjectClock`].
+    pub fn clock(&self) -> ProjectClock {
+        ProjectClock::new(self.time_zone)
+    }
+
+    /// Bring parents back over their subtasks, touch the modified timestamp
+    /// and advance the generation. Call after every mutation: it is what
+    /// keeps parents rolled up, whatever the change was.
+    ///
+    /// Only the parents of tasks changed since the last touch are rolled
+    /// up: those changed through the methods here, [`Self::task_mut`]
+    /// included, or reported with [`Self::task_changed`]. After
+    /// [`Self::set_tasks`] or [`Self::tasks_changed`], as on undo and
+    /// import, every parent is.
+    pub fn touch(&mut self) {
+        match self.changed.take() {
+            Some(changed) => self.roll_up_changed(&changed),
+            None => self.recalculate_parent_dates(),
+        }
+        self.changed = Some(HashSet::new());
+        debug_assert!(
+            self.tasks.iter().all(Task::has_valid_dates),
+            "task dates out of order after a change: {:?}",
+            self.tasks.iter().filter(|t| !t.has_valid_dates()).map(|t| &t.name).collect::<Vec<_>>()
+        );
+        self.modified = Utc::now();
+        self.generation = next_generation();
+    }
+
+    /// Put right any task whose dates don't hold together, as a file edited
+    /// by hand can have: a milestone keeps its start, and a task ending
+    /// before it starts ends when it starts. Returns how many were changed.
+    pub fn repair_dates(&mut self) -> usize {
+        let mut repaired = 0;
+        for task in self.tasks.iter_mut().filter(|t| !t.has_valid_dates()) {
+            task.set_single_date(task.start);
+            repaired += 1;
+        }
+        repaired
+    }
+
+    /// An id for the current state of the project: it changes on every
+    /// mutation made through the methods here or followed by [`Self::touch`].
+    /// Compare it against a remembered value to tell whether cached derived
+    /// data is still valid, instead of comparing or cloning tasks.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// True if tasks were noted as changed since the last [`Self::touch`],
+    /// so [`Self::generation`] doesn't account for them yet.
+    pub fn has_untouched_changes(&self) -> bool {
+        self.changed.as_ref().is_none_or(|changed| !changed.is_empty())
+    }
+
+    /// Note that task `id` is about to change, or just did, other than
+    /// through the methods here, so the next [`Self::touch`] rolls up its
+    /// parents. Call it before the change if the task may move to another
+    /// parent, so the one it leaves is rolled up as well.
+    pub fn task_changed(&mut self, id: Uuid) {
+        let parent = self.task(id).and_then(|t| t.parent_id);
+        if let Some(changed) = &mut self.changed {
+            changed.insert(id);
+            changed.extend(parent);
+        }
+    }
+
+    /// Note that any task may have changed, so the next [`Self::touch`]
+    /// rolls up every parent.
+    pub fn tasks_changed(&mut self) {
+        self.changed = None;
+    }
+
+    /// Drop the cached dependency graph after the links changed.
+    fn links_changed(&mut self) {
+        self.graph.take();
+        self.generation = next_generation();
+    }
+
+    /// Rebuild the id → position index from `tasks`.
+    pub fn rebuild_index(&mut self) {
+        self.index = self.tasks.iter().enumerate().map(|(i, t)| (t.id, i)).collect();
+    }
+
+    /// Position of task `id` in `tasks`.
+    pub fn task_index(&self, id: Uuid) -> Option<usize> {
+        let i = self.index.get(&id).copied();
+        debug_assert!(
+            self.index.len() == self.tasks.len() && i.map_or(id, |i| self.tasks[i].id) == id,
+            "task index out of date"
+        );
+        i
+    }
+
+    /// Look up a task by id.
+    pub fn task(&self, id: Uuid) -> Option<&Task> {
+        self.task_index(id).map(|i| &self.tasks[i])
+    }
+
+    /// Look up a task by id for editing. Don't change its `id` through this.
+    /// The next [`Self::touch`] rolls up its parents, old and new.
+    pub fn task_mut(&mut self, id: Uuid) -> Option<&mut Task> {
+        self.task_changed(id);
+        self.task_index(id).map(|i| &mut self.tasks[i])
+    }
+
+    /// Name of task `id`, or an empty string if there is none.
+    pub fn task_name(&self, id: Uuid) -> String {
+        self.task(id).map(|t| t.name.clone()).unwrap_or_default()
+    }
+
+    /// Replace every task. Of any tasks sharing a slug, the later ones get
+    /// a suffixed one.
+    pub fn set_tasks(&mut self, mut tasks: Vec<Task>) {
+        dedupe_slugs(&mut tasks);
+        self.tasks = tasks;
+        self.rebuild_index();
+        self.tasks_changed();
+        self.generation = next_generation();
+    }
+
+    /// Append a task, suffixing its slug if another task has it.
+    pub fn add_task(&mut self, mut task: Task) {
+        self.free_slug(&mut task);
+        self.index.insert(task.id, self.tasks.len());
+        let id = task.id;
+        self.tasks.push(task);
+        self.task_changed(id);
+        self.generation = next_generation();
+    }
+
+    /// Insert a task at `pos`, shifting the ones after it, suffixing its
+    /// slug if another task has it.
+    pub fn insert_task(&mut self, pos: usize, mut task: Task) {
+        self.free_slug(&mut task);
+        let id = task.id;
+        self.tasks.insert(pos, task);
+        for (i, t) in self.tasks.iter().enumerate().skip(pos) {
+            self.index.insert(t.id, i);
+        }
+        self.task_changed(id);
+        self.generation = next_generation();
+    }
+
+    /// Position just past task `id` and everything nested under it, where a
+    /// new sibling below it goes. Relies on the order from
+    /// [`Self::sort_tasks_grouped`], which keeps subtasks under their parent.
+    pub fn subtree_end(&self, id: Uuid) -> Option<usize> {
+        let start = self.task_index(id)?;
+        let mut inside = vec![id];
+        let mut end = start + 1;
+        while let Some(parent) = self.tasks.get(end).and_then(|t| t.parent_id) {
+            if !inside.contains(&parent) {
+                break;
+            }
+            inside.push(self.tasks[end].id);
+            end += 1;
+        }
+        Some(end)
+    }
+
+    /// Remove every task matching `remove`.
+    pub fn remove_tasks(&mut self, remove: impl Fn(&Task) -> bool) {
+        let parents: Vec<Uuid> = self.tasks.iter().filter(|t| remove(t)).filter_map(|t| t.parent_id).collect();
+        self.tasks.retain(|t| !remove(t));
+        self.rebuild_index();
+        if let Some(changed) = &mut self.changed {
+            changed.extend(parents);
+        }
+        self.generation = next_generation();
+    }
+
+    /// The dependency graph, rebuilt if the links changed since last use.
+    pub fn dependency_graph(&self) -> Arc<DependencyGraph> {
+        let graph = self.graph.get_or_init(|| Arc::new(DependencyGraph::new(&self.dependencies)));
+        debug_assert_eq!(graph.edge_count(), self.dependencies.len(), "dependency graph out of date");
+        Arc::clone(graph)
+    }
+
+    /// Links the tasks' current dates break, in link order. Worked out again
+    /// only after the project changed, so it is cheap to ask every frame.
+    pub fn dependency_violations(&self) -> Arc<[DependencyViolation]> {
+        if let Some((generation, cached)) = &*self.violations.borrow() {
+            if *generation == self.generation {
+                return Arc::clone(cached);
+            }
+        }
+        let found: Arc<[DependencyViolation]> = self
+            .dependencies
+            .iter()
+            .filter_map(|dep| DependencyViolation::check(dep, self.task(dep.from_task)?, self.task(dep.to_task)?))
+            .collect();
+        *self.violations.borrow_mut() = Some((self.generation, Arc::clone(&found)));
+        found
+    }
+
+    /// Replace every dependency.
+    pub fn set_dependencies(&mut self, dependencies: Vec<Dependency>) {
+        self.dependencies = dependencies;
+        self.links_changed();
+    }
+
+    /// Add a dependency.
+    pub fn add_dependency(&mut self, dep: Dependency) {
+        self.dependencies.push(dep);
+        self.links_changed();
+    }
+
+    /// Remove every dependency matching `remove`.
+    pub fn remove_dependencies(&mut self, remove: impl Fn(&Dependency) -> bool) {
+        self.dependencies.retain(|d| !remove(d));
+        self.links_changed();
+    }
+
+    /// The link `from → to`, for editing its kind or lag.
+    pub fn dependency_mut(&mut self, from: Uuid, to: Uuid) -> Option<&mut Dependency> {
+        self.links_changed();
+        self.dependencies.iter_mut().find(|d| d.from_task == from && d.to_task == to)
+    }
+
+    /// Record a custom colour at the front of `recent_colors`.
+    pub fn remember_color(&mut self, color: Rgba) {
+        self.recent_colors.retain(|c| *c != color);
+        self.recent_colors.insert(0, color);
+        self.recent_colors.truncate(MAX_RECENT_COLORS);
+    }
+
+    /// Leaf tasks with a duration, each with its length in hours as weight.
+    pub(crate) fn weighted_leaves(&self) -> impl Iterator<Item = (&Task, f64)> {
+        let parents: HashSet<Uuid> = self.tasks.iter().filter_map(|t| t.parent_id).collect();
+        self.tasks
+            .iter()
+            .filter(move |t| !t.is_milestone && !parents.contains(&t.id))
+            .map(|t| (t, (t.end - t.start).num_minutes().max(1) as f64 / 60.0))
+    }
+
+    /// The task whose timer is running. Only one runs at a time.
+    pub fn running_timer(&self) -> Option<&Task> {
+        self.tasks.iter().find(|t| t.running_entry().is_some())
+    }
+
+    /// Actual completion: progress of every leaf task, weighted by duration.
+    pub fn completion(&self) -> f32 {
+        let (done, total) = self
+            .weighted_leaves()
+            .fold((0.0, 0.0), |(done, total), (t, w)| (done + t.progress as f64 * w, total + w));
+        if total > 0.0 { (done / total) as f32 } else { 0.0 }
+    }
+
+    /// Planned completion at `at`: the share of all scheduled task time that
+    /// falls before it.
+    pub fn planned_completion(&self, at: NaiveDateTime) -> f32 {
+        let (done, total) = self
+            .weighted_leaves()
+            .fold((0.0, 0.0), |(done, total), (t, w)| (done + t.expected_progress(at) as f64 * w, total + w));
+        if total > 0.0 { (done / total) as f32 } else { 0.0 }
+    }
+
+    /// Record today's completion in `progress_history`, replacing any earlier
+    /// reading from the same day, and with `log_task_progress` on each
+    /// task's progress in `task_progress`. Call after progress changes.
+    pub fn record_progress(&mut self, today: NaiveDate) {
+        let sample = ProgressSample { date: today, completion: self.completion() };
+        match self.progress_history.last_mut() {
+            Some(last) if last.date == today => *last = sample,
+            Some(last) if last.completion == sample.completion => {}
+            _ => self.progress_history.push(sample),
+        }
+        let excess = self.progress_history.len().saturating_sub(MAX_PROGRESS_SAMPLES);
+        self.progress_history.drain(..excess);
+        if self.log_task_progress {
+            self.task_progress.record_tasks(&self.tasks, today);
+        }
+    }
+
+    /// Start or stop logging each task's progress. Stopping drops the
+    /// readings so far; starting takes a first reading on `today`.
+    pub fn set_log_task_progress(&mut self, on: bool, today: NaiveDate) {
+        self.log_task_progress = on;
+        if on {
+            self.record_progress(today);
+        } else {
+            self.task_progress.clear();
+        }
+    }
+
+    /// Ids of every task below `id` in the hierarchy (children, grandchildren, …).
+    pub fn descendant_ids(&self, id: Uuid) -> Vec<Uuid> {
+        let mut result = Vec::new();
+        let mut stack = vec![id];
+        while let Some(current) = stack.pop() {
+            for child in self.tasks.iter().filter(|t| t.parent_id == Some(current)) {
+                if !result.contains(&child.id) && child.id != id {
+                    result.push(child.id);
+                    stack.push(child.id);
+                }
+            }
+        }
+        result
+    }
+
+    /// Tasks whose own dates move when shifting `ids`: the selected tasks and
+    /// everything below them, plus with `with_successors` every task reached
+    /// by following dependency links forward. Parents are left out — their
+    /// dates roll up from their children — and so are locked tasks.
+    pub fn shift_targets(&self, ids: &[Uuid], with_successors: bool) -> Vec<Uuid> {
+        let graph = self.dependency_graph();
+        let mut seen: HashSet<Uuid> = HashSet::new();
+        let mut stack: Vec<Uuid> = ids.to_vec();
+        while let Some(id) = stack.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+            stack.extend(self.descendant_ids(id));
+            if with_successors {
+                stack.extend(graph.successors(id).iter().map(|d| d.to_task));
+            }
+        }
+        let parents: HashSet<Uuid> = self.tasks.iter().filter_map(|t| t.parent_id).collect();
+        self.tasks
+            .iter()
+            .filter(|t| seen.contains(&t.id) && !parents.contains(&t.id) && !t.locked)
+            .map(|t| t.id)
+            .collect()
+    }
+
+    /// Move every task in `ids` by `amount` and roll the change up to their
+    /// parents. Use [`Self::shift_targets`] to pick `ids`.
+    pub fn shift_tasks(&mut self, ids: &[Uuid], amount: ShiftAmount) {
+        let ids: HashSet<Uuid> = ids.iter().copied().collect();
+        for task in self.tasks.iter_mut().filter(|t| ids.contains(&t.id)) {
+            task.shift(amount);
+        }
+        self.roll_up_changed(&ids);
+    }
+
+    /// Describe what [`Self::set_milestone`] would change when turning `id`
+    /// into a milestone.
+    pub fn milestone_impact(&self, id: Uuid) -> MilestoneImpact {
+        MilestoneImpact {
+            duration_days: self.task(id).map_or(0, |t| t.duration_days()),
+            children: self.tasks.iter().filter(|t| t.parent_id == Some(id)).count(),
+            finish_links: self
+                .dependency_graph()
+                .predecessors(id)
+                .iter()
+                .filter(|d| finish_constrained(d.kind))
+                .count(),
+        }
+    }
+
+    /// Turn a task into a milestone or back into a regular task.
+    ///
+    /// A milestone has no duration, so its end collapses onto its start, its
+    /// children move to the top level, and links constraining its finish
+    /// (FF/SF) are rewritten to constrain its start instead (FS/SS).
+    /// Turning a milestone back gives it a one-day span.
+    pub fn set_milestone(&mut self, id: Uuid, milestone: bool) {
+        if milestone {
+            for child in self.tasks.iter_mut().filter(|t| t.parent_id == Some(id)) {
+                child.parent_id = None;
+            }
+            self.links_changed();
+            for dep in self.dependencies.iter_mut().filter(|d| d.to_task == id) {
+                dep.kind = match dep.kind {
+                    DependencyKind::FinishToFinish => DependencyKind::FinishToStart,
+                    DependencyKind::StartToFinish => DependencyKind::StartToStart,
+                    kind => kind,
+                };
+            }
+        }
+        if let Some(task) = self.task_mut(id) {
+            task.is_milestone = milestone;
+            if milestone {
+                task.set_single_date(task.start);
+            } else if task.end <= task.start {
+                task.reset_to_one_day();
+            }
+        }
+    }
+
+    /// Ancestors of `id`, nearest first. Stops early if the parent links loop.
+    pub fn ancestor_ids(&self, id: Uuid) -> Vec<Uuid> {
+        let mut result = Vec::new();
+        let mut current = self.task(id).and_then(|t| t.parent_id);
+        while let Some(pid) = current {
+            if pid == id || result.contains(&pid) {
+                break;
+            }
+            result.push(pid);
+            current = self.task(pid).and_then(|t| t.parent_id);
+        }
+        result
+    }
+
+    /// True if `ancestor` sits somewhere above `id` in the hierarchy.
+    pub fn is_ancestor_of(&self, ancestor: Uuid, id: Uuid) -> bool {
+        self.ancestor_ids(id).contains(&ancestor)
+    }
+
+    /// True if `parent` can become the parent of `id` without a loop: it
+    /// exists, isn't `id` itself and isn't nested under it.
+    pub fn can_set_parent(&self, id: Uuid, parent: Uuid) -> bool {
+        parent != id && self.task(parent).is_some() && !self.is_ancestor_of(id, parent)
+    }
+
+    /// Cut every loop in the parent links, such as a task that is its own
+    /// parent or A under B under A. Walking up from each task in list order,
+    /// the link that closes a loop is cleared, which makes that task
+    /// top-level. Returns the tasks whose parent was cleared. Use after
+    /// loading or importing, where the hierarchy wasn't built through
+    /// [`Self::can_set_parent`].
+    pub fn break_parent_cycles(&mut self) -> Vec<Uuid> {
+        let mut settled: HashSet<Uuid> = HashSet::new();
+        let mut broken = Vec::new();
+        for i in 0..self.tasks.len() {
+            let mut path: Vec<Uuid> = Vec::new();
+            let mut current = Some(self.tasks[i].id);
+            while let Some(id) = current {
+                if settled.contains(&id) {
+                    break;
+                }
+                if path.contains(&id) {
+                    let last = path[path.len() - 1];
+                    if let Some(task) = self.task_mut(last) {
+                        task.parent_id = None;
+                    }
+                    broken.push(last);
+                    break;
+                }
+                path.push(id);
+                current = self.task(id).and_then(|t| t.parent_id);
+            }
+            settled.extend(path);
+        }
+        if !broken.is_empty() {
+            self.generation = next_generation();
+        }
+        broken
+    }
+
+    /// Where `id`'s progress comes from under the project's policy.
+    pub fn progress_source(&self, id: Uuid) -> ProgressSource {
+        match self.task(id) {
+            Some(task) => self.progress_policy.source(task, task.has_children(&self.tasks)),
+            None => ProgressSource::Manual,
+        }
+    }
+
+    /// Bring every task's progress in line with the project's policy and
+    /// return how many values changed. Call after changing the policy.
+    pub fn apply_progress_policy(&mut self) -> usize {
+        let before: HashMap<Uuid, f32> = self.tasks.iter().map(|t| (t.id, t.progress)).collect();
+        self.recalculate_parent_dates();
+        self.tasks.iter().filter(|t| before.get(&t.id) != Some(&t.progress)).count()
+    }
+
+    /// Set a parent's dates to span its children, which sit at `children` in
+    /// `tasks`, and, when the policy rolls its progress up, its progress to
+    /// their average. Milestones widen the span but only count towards
+    /// progress when every child is one, since a single unreached checkpoint
+    /// would otherwise weigh as much as a whole task.
+    fn roll_up(&mut self, pid: Uuid, children: &[usize]) {
+        let mut span: Option<(NaiveDateTime, NaiveDateTime)> = None;
+        let (mut progress, mut count) = (0.0, 0);
+        let (mut milestone_progress, mut milestones) = (0.0, 0);
+        for child in children.iter().map(|&i| &self.tasks[i]) {
+            span = Some(match span {
+                Some((start, end)) => (start.min(child.start), end.max(child.end)),
+                None => (child.start, child.end),
+            });
+            if child.is_milestone {
+                milestone_progress += child.progress;
+                milestones += 1;
+            } else {
+                progress += child.progress;
+                count += 1;
+            }
+        }
+        let Some((start, end)) = span else {
+            return;
+        };
+        if count == 0 {
+            (progress, count) = (milestone_progress, milestones);
+        }
+        let policy = self.progress_policy;
+        if let Some(parent) = self.task_index(pid).map(|i| &mut self.tasks[i]) {
+            let rolls_up = policy.source(parent, true) == ProgressSource::Subtasks;
+            parent.start = start;
+            parent.end = end;
+            if rolls_up {
+                parent.progress = progress / count as f32;
+            }
+        }
+    }
+
+    /// Recalculate every parent task's start/end/progress from its children,
+    /// deepest parents first, after setting checklist progress where the
+    /// policy uses it. [`Self::touch`] runs this when any task may have
+    /// changed, as after [`Self::set_tasks`]; code that changes tasks without
+    /// touching, such as loading or building a project, calls it itself. A
+    /// parent loop, which has no deepest end to start from, is
+    /// gone round until every parent exactly spans its children.
+    pub fn recalculate_parent_dates(&mut self) {
+        let mut children: HashMap<Uuid, Vec<usize>> = HashMap::new();
+        for (i, task) in self.tasks.iter().enumerate() {
+            if let Some(pid) = task.parent_id {
+                children.entry(pid).or_default().push(i);
+            }
+        }
+        if self.progress_policy == ProgressPolicy::Checklist {
+            for task in self.tasks.iter_mut().filter(|t| !t.checklist.is_empty()) {
+                if ProgressPolicy::Checklist.source(task, children.contains_key(&task.id)) == ProgressSource::Checklist {
+                    task.progress = task.checklist_progress().unwrap_or(task.progress);
+                }
+            }
+        }
+        let mut parent_ids: Vec<Uuid> = children.keys().copied().collect();
+        parent_ids.sort_by_cached_key(|pid| std::cmp::Reverse(self.ancestor_ids(*pid).len()));
+        for &pid in &parent_ids {
+            self.roll_up(pid, &children[&pid]);
+        }
+
+        for _ in 0..self.tasks.len() {
+            let stale = self.uncontained_parents();
+            if stale.is_empty() {
+                break;
+            }
+            for pid in stale {
+                self.roll_up(pid, &children[&pid]);
+            }
+        }
+        debug_assert!(self.uncontained_parents().is_empty(), "parents still off their subtasks after a rollup");
+        self.changed = Some(HashSet::new());
+    }
+
+    /// Parents whose dates aren't exactly the span of their subtasks, as
+    /// data imported or pasted without a rollup can leave them.
+    pub fn uncontained_parents(&self) -> Vec<Uuid> {
+        let spans = subtask_spans(&self.tasks);
+        self.tasks
+            .iter()
+            .filter(|t| spans.get(&t.id).is_some_and(|&span| span != (t.start, t.end)))
+            .map(|t| t.id)
+            .collect()
+    }
+
+    /// Recalculate only what a change to `changed` can affect: the task itself
+    /// (if it is a parent) and its ancestors, bottom-up. Gives the same result
+    /// as [`Self::recalculate_parent_dates`] when nothing else changed.
+    pub fn recalculate_parents_of(&mut self, changed: Uuid) {
+        self.roll_up_changed(&HashSet::from([changed]));
+    }
+
+    /// [`Self::recalculate_parents_of`] for every task in `changed` at once,
+    /// rolling each affected parent up once, deepest first.
+    fn roll_up_changed(&mut self, changed: &HashSet<Uuid>) {
+        let mut parents: HashSet<Uuid> = HashSet::new();
+        for &id in changed {
+            parents.insert(id);
+            parents.extend(self.ancestor_ids(id));
+        }
+        let mut children: HashMap<Uuid, Vec<usize>> = HashMap::new();
+        for (i, task) in self.tasks.iter().enumerate() {
+            if let Some(pid) = task.parent_id.filter(|pid| parents.contains(pid)) {
+                children.entry(pid).or_default().push(i);
+            }
+        }
+        if self.progress_policy == ProgressPolicy::Checklist {
+            for &id in changed {
+                let is_parent = children.contains_key(&id);
+                let Some(task) = self.task_index(id).map(|i| &mut self.tasks[i]) else {
+                    continue;
+                };
+                if ProgressPolicy::Checklist.source(task, is_parent) == ProgressSource::Checklist {
+                    task.progress = task.checklist_progress().unwrap_or(task.progress);
+                }
+            }
+        }
+        let mut parent_ids: Vec<Uuid> = children.keys().copied().collect();
+        parent_ids.sort_by_cached_key(|pid| std::cmp::Reverse(self.ancestor_ids(*pid).len()));
+        for pid in parent_ids {
+            self.roll_up(pid, &children[&pid]);
+        }
+    }
+
+    /// Re-order tasks so every parent is immediately followed by its whole
+    /// subtree, at any depth. Siblings keep their relative order. Tasks whose
+    /// parent is missing are treated as top-level; tasks caught in a parent
+    /// loop are placed from the first one in list order. Tasks are moved,
+    /// never cloned.
+    pub fn sort_tasks_grouped(&mut self) {
+        let n = self.tasks.len();
+        let position: HashMap<Uuid, usize> = self.tasks.iter().enumerate().map(|(i, t)| (t.id, i)).collect();
+        let parent_of = |i: usize| {
+            self.tasks[i]
+                .parent_id
+                .and_then(|pid| position.get(&pid).copied())
+                .filter(|&p| p != i)
+        };
+
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); n];
+        for i in 0..n {
+            if let Some(p) = parent_of(i) {
+                children[p].push(i);
+            }
+        }
+
+        // Depth-first from each root in list order; anything left unvisited
+        // afterwards sits on a parent loop and starts its own walk.
+        let mut order = Vec::with_capacity(n);
+        let mut visited = vec![false; n];
+        let mut stack = Vec::new();
+        let roots = (0..n).filter(|&i| parent_of(i).is_none());
+        for root in roots.chain(0..n) {
+            stack.push(root);
+            while let Some(i) = stack.pop() {
+                if visited[i] {
+                    continue;
+                }
+                visited[i] = true;
+                order.push(i);
+                stack.extend(children[i].iter().rev());
+            }
+        }
+
+        // Apply the permutation in place: `at[k]` is the original index of
+        // the task now in slot k, `slot[o]` is where original task o sits.
+        let mut at: Vec<usize> = (0..n).collect();
+        let mut slot: Vec<usize> = (0..n).collect();
+        for (k, &want) in order.iter().enumerate() {
+            let j = slot[want];
+            if j != k {
+                self.tasks.swap(k, j);
+                let displaced = at[k];
+                at[k] = want;
+                at[j] = displaced;
+                slot[displaced] = j;
+                slot[want] = k;
+            }
+        }
+
+        self.rebuild_index();
+    }
+}
+
+/// True for link kinds that constrain the successor's finish.
+fn finish_constrained(kind: DependencyKind) -> bool {
+    matches!(kind, DependencyKind::FinishToFinish | DependencyKind::StartToFinish)
+}
@@ -0,0 +1,287 @@
+//! Parsing for the quick-add box, which turns one line such as
+//! `Design review tue for 3d p:high #frontend /Phase 1` into a task.
+//!
+//! Recognised pieces may appear anywhere in the line; every word that isn't
+//! recognised is kept, in order, as the task name.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Weekday};
+use uuid::Uuid;
+
+use super::task::{Task, TaskPriority};
+
+/// Length in days when the entry doesn't say.
+pub const DEFAULT_DURATION_DAYS: i64 = 1;
+
+/// Longest length a duration may give, about a century. Anything longer is
+/// taken for part of the name.
+pub const MAX_DURATION_DAYS: i64 = 36_525;
+
+/// Everything a quick-add line describes.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct QuickAdd {
+    /// Words left over once dates, durations, priority, tags and parent are
+    /// taken out. May be empty.
+    pub name: String,
+    pub start: Option<NaiveDate>,
+    /// Length in calendar days, from `for Nd` / `for Nw`.
+    pub duration_days: Option<i64>,
+    pub priority: Option<TaskPriority>,
+    pub tags: Vec<String>,
+    /// Task chosen with `/name`, and its full name.
+    pub parent: Option<(Uuid, String)>,
+}
+
+impl QuickAdd {
+    /// The task name, or a placeholder when none was typed.
+    pub fn display_name(&self) -> &str {
+        if self.name.is_empty() {
+            "New Task"
+        } else {
+            &self.name
+        }
+    }
+
+    /// First and last day the task covers. A length running past the last
+    /// date there is ends on the start day.
+    pub fn date_range(&self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let start = self.start.unwrap_or(today);
+        let days = self.duration_days.unwrap_or(DEFAULT_DURATION_DAYS).max(1);
+        (start, start.checked_add_signed(Duration::days(days - 1)).unwrap_or(start))
+    }
+
+    /// Build the task. Colour is left at the default for the caller to pick.
+    pub fn to_task(&self, today: NaiveDate, start_time: NaiveTime, end_time: NaiveTime) -> Task {
+        let (first, last) = self.date_range(today);
+        let mut task = Task::new(self.display_name(), first.and_time(start_time), last.and_time(end_time));
+        if task.end <= task.start {
+            task.end = task.start + Duration::hours(1);
+        }
+        task.priority = self.priority.unwrap_or_default();
+        task.tags = self.tags.clone();
+        task.parent_id = self.parent.as_ref().map(|(id, _)| *id);
+        task
+    }
+}
+
+/// Parse a quick-add line. `today` anchors relative dates; `tasks` are the
+/// candidates for `/parent`.
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use gantt_core::quick_add::parse_quick_add;
+///
+/// // Wednesday 6 March.
+/// let today = NaiveDate::from_ymd_opt(2024, 3, 6).unwrap();
+/// let entry = parse_quick_add("Design review fri for 2w #ui", today, &[]);
+/// assert_eq!(entry.name, "Design review");
+/// assert_eq!(entry.start, NaiveDate::from_ymd_opt(2024, 3, 8));
+/// assert_eq!(entry.duration_days, Some(14));
+/// assert_eq!(entry.tags, ["ui"]);
+/// ```
+pub fn parse_quick_add(input: &str, today: NaiveDate, tasks: &[Task]) -> QuickAdd {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let mut result = QuickAdd::default();
+    let mut name_words: Vec<&str> = Vec::new();
+    let mut i = 0;
+
+    while i < words.len() {
+        let word = words[i];
+        let lower = word.to_lowercase();
+        let next = words.get(i + 1).map(|w| w.to_lowercase());
+
+        // Two-word forms first: "next mon", "for 3d", "12 aug", "aug 12".
+        if let Some(next) = next.as_deref() {
+            let pair = match lower.as_str() {
+                "next" if next == "week" => Some(Piece::Start(next_weekday(today, Weekday::Mon, false))),
+                "next" => parse_weekday(next).map(|wd| Piece::Start(next_weekday(today, wd, false))),
+                "for" => parse_duration(next).map(Piece::Duration),
+                _ => day_month(&lower, next, today).map(Piece::Start),
+            };
+            if let Some(piece) = pair {
+                piece.apply(&mut result);
+                i += 2;
+                continue;
+            }
+        }
+
+        if let Some(date) = parse_single_date(&lower, today) {
+            result.start = Some(date);
+        } else if let Some(priority) = lower.strip_prefix("p:").and_then(parse_priority) {
+            result.priority = Some(priority);
+        } else if let Some(tag) = word.strip_prefix('#').filter(|t| !t.is_empty()) {
+            if !result.tags.iter().any(|t| t == tag) {
+                result.tags.push(tag.to_string());
+            }
+        } else if let Some((used, id, name)) = word
+            .strip_prefix('/')
+            .filter(|q| !q.is_empty())
+            .and_then(|_| match_parent(&words[i..], tasks))
+        {
+            result.parent = Some((id, name));
+            i += used;
+            continue;
+        } else {
+            name_words.push(word);
+        }
+        i += 1;
+    }
+
+    result.name = name_words.join(" ");
+    result
+}
+
+/// A recognised two-word piece.
+enum Piece {
+    Start(NaiveDate),
+    Duration(i64),
+}
+
+impl Piece {
+    fn apply(self, result: &mut QuickAdd) {
+        match self {
+            Piece::Start(date) => result.start = Some(date),
+            Piece::Duration(days) => result.duration_days = Some(days),
+        }
+    }
+}
+
+/// `today`, `tomorrow`, a weekday name or an ISO date.
+fn parse_single_date(word: &str, today: NaiveDate) -> Option<NaiveDate> {
+    match word {
+        "today" | "tod" => Some(today),
+        "tomorrow" | "tmr" | "tmrw" => Some(today + Duration::days(1)),
+        "yesterday" => Some(today - Duration::days(1)),
+        _ => parse_weekday(word)
+            .map(|wd| next_weekday(today, wd, true))
+            .or_else(|| NaiveDate::parse_from_str(word, "%Y-%m-%d").ok()),
+    }
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    let weekday = match word {
+        "mon" | "monday" => Weekday::Mon,
+        "tue" | "tues" | "tuesday" => Weekday::Tue,
+        "wed" | "wednesday" => Weekday::Wed,
+        "thu" | "thur" | "thurs" | "thursday" => Weekday::Thu,
+        "fri" | "friday" => Weekday::Fri,
+        "sat" | "saturday" => Weekday::Sat,
+        "sun" | "sunday" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+/// The next `weekday` after `today`, or `today` itself when it matches and
+/// `include_today` is set.
+fn next_weekday(today: NaiveDate, weekday: Weekday, include_today: bool) -> NaiveDate {
+    let ahead = (weekday.num_days_from_monday() as i64 - today.weekday().num_days_from_monday() as i64)
+        .rem_euclid(7);
+    let ahead = if ahead == 0 && !include_today { 7 } else { ahead };
+    today + Duration::days(ahead)
+}
+
+fn parse_month(word: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+    if word.len() < 3 {
+        return None;
+    }
+    MONTHS
+        .iter()
+        .position(|m| word.starts_with(m) && (word.len() == 3 || full_month_name(m).starts_with(word)))
+        .map(|i| i as u32 + 1)
+}
+
+fn full_month_name(abbrev: &str) -> &'static str {
+    match abbrev {
+        "jan" => "january",
+        "feb" => "february",
+        "mar" => "march",
+        "apr" => "april",
+        "may" => "may",
+        "jun" => "june",
+        "jul" => "july",
+        "aug" => "august",
+        "sep" => "september",
+        "oct" => "october",
+        "nov" => "november",
+        _ => "december",
+    }
+}
+
+/// `12 aug` or `aug 12`, in the current year unless that day has passed.
+fn day_month(a: &str, b: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let (day, month) = match (a.parse::<u32>(), b.parse::<u32>()) {
+        (Ok(day), Err(_)) => (day, parse_month(b)?),
+        (Err(_), Ok(day)) => (day, parse_month(a)?),
+        _ => return None,
+    };
+    let this_year = NaiveDate::from_ymd_opt(today.year(), month, day);
+    match this_year {
+        Some(date) if date >= today => Some(date),
+        _ => NaiveDate::from_ymd_opt(today.year() + 1, month, day),
+    }
+}
+
+/// `3d`, `3days`, `2w`, `2wk`, `2weeks` → length in days, up to
+/// [`MAX_DURATION_DAYS`].
+///
+/// ```
+/// use gantt_core::quick_add::parse_duration;
+///
+/// assert_eq!(parse_duration("3days"), Some(3));
+/// assert_eq!(parse_duration("2wk"), Some(14));
+/// assert_eq!(parse_duration("0d"), None);
+/// assert_eq!(parse_duration("3h"), None);
+/// assert_eq!(parse_duration("99999999d"), None);
+/// assert_eq!(parse_duration("9999999999999999999w"), None);
+/// ```
+pub fn parse_duration(word: &str) -> Option<i64> {
+    let split = word.find(|c: char| !c.is_ascii_digit())?;
+    let (count, unit) = word.split_at(split);
+    let count: i64 = count.parse().ok()?;
+    let per_unit = match unit {
+        "d" | "day" | "days" => 1,
+        "w" | "wk" | "wks" | "week" | "weeks" => 7,
+        _ => return None,
+    };
+    count.checked_mul(per_unit).filter(|days| (1..=MAX_DURATION_DAYS).contains(days))
+}
+
+/// `high`, `h`, `3`, … → priority. Expects lowercase.
+pub fn parse_priority(word: &str) -> Option<TaskPriority> {
+    let priority = match word {
+        "none" | "0" => TaskPriority::None,
+        "low" | "l" | "1" => TaskPriority::Low,
+        "medium" | "med" | "m" | "2" => TaskPriority::Medium,
+        "high" | "h" | "3" => TaskPriority::High,
+        "critical" | "crit" | "c" | "4" => TaskPriority::Critical,
+        _ => return None,
+    };
+    Some(priority)
+}
+
+/// Match `/name…` against task names by case-insensitive prefix, taking as
+/// many following words as still match. An exact name match wins over a
+/// longer name sharing the prefix. Returns the words used and the task.
+fn match_parent(words: &[&str], tasks: &[Task]) -> Option<(usize, Uuid, String)> {
+    let candidates: Vec<(&Task, String)> = tasks
+        .iter()
+        .filter(|t| !t.is_milestone)
+        .map(|t| (t, t.name.to_lowercase()))
+        .collect();
+    let mut query = words[0].trim_start_matches('/').to_lowercase();
+    let mut best = None;
+    for used in 1..=words.len() {
+        if used > 1 {
+            query.push(' ');
+            query.push_str(&words[used - 1].to_lowercase());
+        }
+        let mut matches = candidates.iter().filter(|(_, name)| name.starts_with(&query)).peekable();
+        let Some(first) = matches.peek().copied() else {
+            break;
+        };
+        let chosen = candidates.iter().find(|(_, name)| *name == query).unwrap_or(first);
+        best = Some((used, chosen.0.id, chosen.0.name.clone()));
+    }
+    best
+}
@@ -0,0 +1,73 @@
+//! Deadline reminders: which unfinished tasks are coming due, and which of
+//! them have already been announced or snoozed today.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use uuid::Uuid;
+
+use super::highlight::{due_state, due_time, short_span, DueState};
+use super::task::{Task, TaskPriority};
+
+/// An unfinished task due within the lead time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reminder {
+    pub task_id: Uuid,
+    pub name: String,
+    /// End date, or the date of a milestone.
+    pub due: NaiveDateTime,
+}
+
+impl Reminder {
+    /// "in 3h", "in 2d", "now".
+    pub fn due_in(&self, now: NaiveDateTime) -> String {
+        short_span(self.due - now).map_or_else(|| "now".to_string(), |span| format!("in {}", span))
+    }
+}
+
+/// Leaf tasks and milestones [`DueState::DueSoon`] by `lead` at `now`,
+/// soonest first. Only the listed priorities count.
+pub fn upcoming_reminders(
+    tasks: &[Task],
+    now: NaiveDateTime,
+    lead: Duration,
+    priorities: &[TaskPriority],
+) -> Vec<Reminder> {
+    let parents: HashSet<Uuid> = tasks.iter().filter_map(|t| t.parent_id).collect();
+    let mut reminders: Vec<Reminder> = tasks
+        .iter()
+        .filter(|t| priorities.contains(&t.priority) && !parents.contains(&t.id))
+        .filter(|t| due_state(t, now, lead) == Some(DueState::DueSoon))
+        .map(|t| Reminder { task_id: t.id, name: t.name.clone(), due: due_time(t) })
+        .collect();
+    reminders.sort_by_key(|r| r.due);
+    reminders
+}
+
+/// Per-task record of the last day a reminder was sent or snoozed, so each
+/// task notifies at most once a day.
+#[derive(Debug, Clone, Default)]
+pub struct ReminderLog {
+    notified: HashMap<Uuid, NaiveDate>,
+    snoozed: HashMap<Uuid, NaiveDate>,
+}
+
+impl ReminderLog {
+    /// True if `id` hasn't been notified or snoozed on `today`.
+    pub fn should_notify(&self, id: Uuid, today: NaiveDate) -> bool {
+        self.notified.get(&id) != Some(&today) && !self.is_snoozed(id, today)
+    }
+
+    pub fn mark_notified(&mut self, id: Uuid, today: NaiveDate) {
+        self.notified.insert(id, today);
+    }
+
+    /// Silence `id` for the rest of `today`.
+    pub fn snooze(&mut self, id: Uuid, today: NaiveDate) {
+        self.snoozed.insert(id, today);
+    }
+
+    pub fn is_snoozed(&self, id: Uuid, today: NaiveDate) -> bool {
+        self.snoozed.get(&id) == Some(&today)
+    }
+}
@@ -0,0 +1,105 @@
+//! Risk ratings on tasks, and how the work still to do splits across them.
+//!
+//! A rating is a planner's judgement of how likely a task is to slip or go
+//! wrong; tasks start unrated. High-risk tasks on the critical path are
+//! flagged by validation, since any slip there moves the project's end.
+
+use serde::{Deserialize, Serialize};
+
+use crate::color::Rgba;
+use crate::project::Project;
+
+/// How risky a task is judged to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum RiskLevel {
+    Low,
+    Medium,
+    High,
+}
+
+impl RiskLevel {
+    pub fn label(self) -> &'static str {
+        match self {
+            RiskLevel::Low => "Low",
+            RiskLevel::Medium => "Medium",
+            RiskLevel::High => "High",
+        }
+    }
+
+    pub fn all() -> &'static [RiskLevel] {
+        &[RiskLevel::Low, RiskLevel::Medium, RiskLevel::High]
+    }
+
+    /// A level as written in a file, in any case: the label or its first
+    /// letter, or `med`.
+    pub fn parse(text: &str) -> Option<RiskLevel> {
+        match text.trim().to_lowercase().as_str() {
+            "low" | "l" => Some(RiskLevel::Low),
+            "medium" | "med" | "m" => Some(RiskLevel::Medium),
+            "high" | "h" => Some(RiskLevel::High),
+            _ => None,
+        }
+    }
+
+    pub fn color(self) -> Rgba {
+        match self {
+            RiskLevel::Low => Rgba::from_rgb(80, 180, 100),
+            RiskLevel::Medium => Rgba::from_rgb(230, 160, 40),
+            RiskLevel::High => Rgba::from_rgb(220, 60, 60),
+        }
+    }
+}
+
+/// Work left on unfinished leaf tasks, split by risk. Work is scheduled
+/// hours times the share not yet done, as in [`Project::completion`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RiskBreakdown {
+    /// Remaining hours for each level, Low to High.
+    pub remaining: [f64; 3],
+    /// Remaining hours on unrated tasks.
+    pub unrated: f64,
+    /// Unfinished tasks at each level, Low to High.
+    pub open: [usize; 3],
+}
+
+impl RiskBreakdown {
+    /// Remaining hours at `level`, or on unrated tasks for `None`.
+    pub fn hours(&self, level: Option<RiskLevel>) -> f64 {
+        level.map_or(self.unrated, |l| self.remaining[l as usize])
+    }
+
+    /// Remaining hours over every level and unrated tasks.
+    pub fn total(&self) -> f64 {
+        self.remaining.iter().sum::<f64>() + self.unrated
+    }
+
+    /// Share, 0 to 1, of the remaining work at `level`.
+    pub fn share(&self, level: Option<RiskLevel>) -> f64 {
+        let total = self.total();
+        if total > 0.0 { self.hours(level) / total } else { 0.0 }
+    }
+
+    /// Whether any unfinished task is rated.
+    pub fn is_rated(&self) -> bool {
+        self.open.iter().any(|&n| n > 0)
+    }
+}
+
+impl Project {
+    /// How the remaining work splits across risk levels. Parents and
+    /// milestones carry no work of their own.
+    pub fn risk_breakdown(&self) -> RiskBreakdown {
+        let mut breakdown = RiskBreakdown::default();
+        for (task, hours) in self.weighted_leaves().filter(|(t, _)| t.progress < 1.0) {
+            let left = hours * (1.0 - task.progress.clamp(0.0, 1.0) as f64);
+            match task.risk {
+                Some(level) => {
+                    breakdown.remaining[level as usize] += left;
+                    breakdown.open[level as usize] += 1;
+                }
+                None => breakdown.unrated += left,
+            }
+        }
+        breakdown
+    }
+}
@@ -0,0 +1,258 @@
+//! Schedule analysis over the dependency graph: slack, the critical path,
+//! rescheduling to clear violated links, and laying a phase's children out
+//! back to back.
+//!
+//! Slack is measured against the tasks' current dates: a task's late finish
+//! is the latest it could end, with its successors slipping as far as they
+//! can too, without moving the project's last end date. Tasks with no slack
+//! form the critical path.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use chrono::{Datelike, Duration, NaiveDateTime, Weekday};
+use uuid::Uuid;
+
+use super::graph::DependencyGraph;
+use super::project::Project;
+use super::task::{Dependency, DependencyKind, FixedEdge, ShiftAmount, Task};
+use super::validation::{dependency_bound, dependency_satisfied};
+
+/// Total slack (float) of every task against the current schedule.
+/// Links on a cycle are ignored.
+pub fn slack(tasks: &[Task], graph: &DependencyGraph) -> HashMap<Uuid, Duration> {
+    let Some(project_end) = tasks.iter().map(|t| t.end).max() else {
+        return HashMap::new();
+    };
+    let by_id: HashMap<Uuid, &Task> = tasks.iter().map(|t| (t.id, t)).collect();
+    let mut late_finish: HashMap<Uuid, NaiveDateTime> = HashMap::new();
+
+    // Unlinked tasks can slip to the project end; linked ones are walked
+    // successors first.
+    for task in tasks.iter().filter(|t| !graph.is_linked(t.id)) {
+        late_finish.insert(task.id, project_end);
+    }
+    for &id in graph.topo_order().iter().rev() {
+        let Some(task) = by_id.get(&id) else {
+            continue;
+        };
+        let duration = task.end - task.start;
+        let mut lf = project_end;
+        for d in graph.successors(id).iter().filter(|d| !graph.is_cyclic_edge(d)) {
+            let Some(succ) = by_id.get(&d.to_task) else {
+                continue;
+            };
+            let succ_lf = late_finish.get(&d.to_task).copied().unwrap_or(project_end);
+            let succ_ls = succ_lf - (succ.end - succ.start);
+            let lag = Duration::days(d.lag_days);
+            let bound = match d.kind {
+                DependencyKind::FinishToStart => succ_ls - lag,
+                DependencyKind::StartToStart => succ_ls - lag + duration,
+                DependencyKind::FinishToFinish => succ_lf - lag,
+                DependencyKind::StartToFinish => succ_lf - lag + duration,
+            };
+            lf = lf.min(bound);
+        }
+        late_finish.insert(id, lf);
+    }
+
+    late_finish
+        .into_iter()
+        .filter_map(|(id, lf)| by_id.get(&id).map(|t| (id, lf - t.end)))
+        .collect()
+}
+
+/// Tasks with no slack left.
+pub fn critical_tasks(tasks: &[Task], graph: &DependencyGraph) -> HashSet<Uuid> {
+    slack(tasks, graph)
+        .into_iter()
+        .filter(|(_, s)| *s <= Duration::zero())
+        .map(|(id, _)| id)
+        .collect()
+}
+
+/// One task move proposed by [`resolve_violations`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProposedMove {
+    pub task_id: Uuid,
+    pub old_start: NaiveDateTime,
+    pub old_end: NaiveDateTime,
+    pub new_start: NaiveDateTime,
+    pub new_end: NaiveDateTime,
+}
+
+impl ProposedMove {
+    pub fn delta(&self) -> Duration {
+        self.new_start - self.old_start
+    }
+}
+
+/// Result of [`resolve_violations`].
+#[derive(Debug, Clone, Default)]
+pub struct Reschedule {
+    /// Leaf tasks to move, in dependency order.
+    pub moves: Vec<ProposedMove>,
+    /// Tasks on each dependency cycle. Cyclic links can't all be satisfied,
+    /// so they are left alone.
+    pub cycles: Vec<Vec<Uuid>>,
+    /// Violated links the moves don't clear: into skipped or locked tasks,
+    /// or into parents whose children aren't otherwise constrained.
+    pub unresolved: usize,
+}
+
+/// Rounds of moving tasks and re-rolling parent dates before giving up; a
+/// moved child can push its parent, which can push the parent's successors.
+const MAX_PASSES: usize = 32;
+
+/// Forward-only moves that satisfy every violated dependency, keeping
+/// durations. Each task moves just far enough for its strictest
+/// predecessor, and moves cascade down the chain. Tasks in `skip` keep
+/// their dates, as do locked tasks, parents (their dates roll up from
+/// their children) and tasks on a cycle.
+pub fn resolve_violations(project: &Project, skip: &HashSet<Uuid>) -> Reschedule {
+    let graph = project.dependency_graph();
+    let parents: HashSet<Uuid> = project.tasks.iter().filter_map(|t| t.parent_id).collect();
+    let mut work = project.clone();
+
+    let mut moved: Vec<Uuid> = Vec::new();
+    for _ in 0..MAX_PASSES {
+        let mut changed = false;
+        for &id in graph.topo_order() {
+            if skip.contains(&id) || parents.contains(&id) || graph.cycle_groups().contains_key(&id) {
+                continue;
+            }
+            let Some(task) = work.task(id).filter(|t| !t.locked) else {
+                continue;
+            };
+            let mut delta = Duration::zero();
+            for dep in graph.predecessors(id).iter().filter(|d| !graph.is_cyclic_edge(d)) {
+                let Some(pred) = work.task(dep.from_task) else {
+                    continue;
+                };
+                let (constrains_start, earliest) = dependency_bound(dep, pred);
+                let current = if constrains_start { task.start } else { task.end };
+                delta = delta.max(earliest - current);
+            }
+            if delta > Duration::zero() {
+                if let Some(task) = work.task_mut(id) {
+                    task.set_dates(task.start + delta, task.end + delta, Duration::zero(), FixedEdge::Start);
+                }
+                work.recalculate_parents_of(id);
+                if !moved.contains(&id) {
+                    moved.push(id);
+                }
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let moves = moved
+        .iter()
+        .filter_map(|id| {
+            let (old, new) = (project.task(*id)?, work.task(*id)?);
+            Some(ProposedMove {
+                task_id: *id,
+                old_start: old.start,
+                old_end: old.end,
+                new_start: new.start,
+                new_end: new.end,
+            })
+        })
+        .collect();
+
+    let mut cycles: Vec<Vec<Uuid>> = Vec::new();
+    let mut groups: HashMap<usize, usize> = HashMap::new();
+    // Walk tasks in list order so each cycle is listed the way the chart shows it.
+    for task in &project.tasks {
+        if let Some(group) = graph.cycle_groups().get(&task.id) {
+            let index = *groups.entry(*group).or_insert_with(|| {
+                cycles.push(Vec::new());
+                cycles.len() - 1
+            });
+            cycles[index].push(task.id);
+        }
+    }
+
+    let unresolved = project
+        .dependencies
+        .iter()
+        .filter(|d| !graph.is_cyclic_edge(d))
+        .filter(|d| match (work.task(d.from_task), work.task(d.to_task)) {
+            (Some(pred), Some(succ)) => !dependency_satisfied(d, pred, succ),
+            _ => false,
+        })
+        .count();
+
+    Reschedule { moves, cycles, unresolved }
+}
+
+/// Result of [`chain_children`].
+#[derive(Debug, Clone, Default)]
+pub struct ChildChain {
+    /// Children whose dates change, in list order. A child that is itself a
+    /// parent moves with everything below it.
+    pub moves: Vec<ProposedMove>,
+    /// Finish-to-start links between consecutive children that aren't there
+    /// yet. Links that would close a cycle are left out.
+    pub links: Vec<Dependency>,
+    /// Locked children, which keep their dates.
+    pub locked: Vec<Uuid>,
+}
+
+/// Lay `parent`'s direct children out back to back in list order, keeping
+/// their durations: each child starts, at its own time of day, on the first
+/// working day after the one above it ends, plus `gap_days` working days.
+/// The first child stays where it is, and so do locked children, with the
+/// chain carrying on after them.
+pub fn chain_children(project: &Project, parent: Uuid, gap_days: i64) -> ChildChain {
+    let children: Vec<&Task> = project.tasks.iter().filter(|t| t.parent_id == Some(parent)).collect();
+    let mut chain = ChildChain::default();
+
+    let mut previous_end: Option<NaiveDateTime> = None;
+    for child in &children {
+        let (start, end) = match previous_end {
+            Some(after) if !child.locked => {
+                let mut start = after.date().and_time(child.start.time());
+                if start < after {
+                    start += Duration::days(1);
+                }
+                while matches!(start.weekday(), Weekday::Sat | Weekday::Sun) {
+                    start += Duration::days(1);
+                }
+                let start = ShiftAmount::WorkingDays(gap_days.max(0)).apply(start);
+                (start, start + (child.end - child.start))
+            }
+            _ => (child.start, child.end),
+        };
+        if child.locked {
+            chain.locked.push(child.id);
+        }
+        if start != child.start {
+            chain.moves.push(ProposedMove {
+                task_id: child.id,
+                old_start: child.start,
+                old_end: child.end,
+                new_start: start,
+                new_end: end,
+            });
+        }
+        previous_end = Some(end);
+    }
+
+    let mut dependencies = project.dependencies.clone();
+    let mut graph = project.dependency_graph();
+    for pair in children.windows(2) {
+        let (from, to) = (pair[0].id, pair[1].id);
+        if graph.has_link(from, to) || graph.would_create_cycle(from, to) {
+            continue;
+        }
+        let link = Dependency { from_task: from, to_task: to, kind: DependencyKind::FinishToStart, lag_days: 0 };
+        dependencies.push(link.clone());
+        chain.links.push(link);
+        graph = Arc::new(DependencyGraph::new(&dependencies));
+    }
+    chain
+}
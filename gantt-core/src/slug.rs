@@ -0,0 +1,121 @@
+//! Slugs: short, human-readable task identifiers such as `api-design`.
+//!
+//! A task's id changes when it is recreated, e.g. deleted and imported
+//! again; its slug is whatever the user set, so scripts, links and exports
+//! can refer to it across files. Slugs are optional and unique within a
+//! project: tasks added with a slug already in use get `-2`, `-3`… appended.
+
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use crate::project::Project;
+use crate::task::Task;
+
+/// Longest slug accepted.
+pub const MAX_SLUG_LEN: usize = 64;
+
+/// `name` as a slug: lowercase ASCII letters and digits, with a single
+/// hyphen for each run of anything else. Empty if the name has no letters
+/// or digits.
+///
+/// ```
+/// use gantt_core::slug::slugify;
+///
+/// assert_eq!(slugify("API Design (v2)"), "api-design-v2");
+/// assert_eq!(slugify("  Café / Beta  "), "caf-beta");
+/// assert_eq!(slugify("***"), "");
+/// ```
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let mut slug = slug.trim_end_matches('-').to_string();
+    if slug.len() > MAX_SLUG_LEN {
+        slug.truncate(MAX_SLUG_LEN);
+        slug = slug.trim_end_matches('-').to_string();
+    }
+    slug
+}
+
+/// Why `slug` can't be used as written, if it can't. Slugs are lowercase
+/// letters, digits and single hyphens, starting and ending with a letter
+/// or digit.
+pub fn slug_format_problem(slug: &str) -> Option<&'static str> {
+    if slug.is_empty() {
+        Some("Slug is empty")
+    } else if slug.len() > MAX_SLUG_LEN {
+        Some("Slug is too long")
+    } else if !slug.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') {
+        Some("Use lowercase letters, digits and hyphens only")
+    } else if slug.starts_with('-') || slug.ends_with('-') || slug.contains("--") {
+        Some("Hyphens go between letters or digits")
+    } else {
+        None
+    }
+}
+
+/// `base`, or `base-2`, `base-3`… whichever `taken` says is free first.
+fn first_free(base: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|slug| !taken(slug))
+        .expect("unbounded suffixes")
+}
+
+/// Give every task in `tasks` a slug no earlier task has, suffixing the
+/// later of any two that clash.
+pub(crate) fn dedupe_slugs(tasks: &mut [Task]) {
+    let mut seen: HashSet<String> = HashSet::new();
+    for task in tasks {
+        if let Some(slug) = &task.slug {
+            let free = first_free(slug, |s| seen.contains(s));
+            seen.insert(free.clone());
+            task.slug = Some(free);
+        }
+    }
+}
+
+impl Project {
+    /// The task with slug `slug`, if any.
+    pub fn task_by_slug(&self, slug: &str) -> Option<&Task> {
+        self.tasks.iter().find(|t| t.slug.as_deref() == Some(slug))
+    }
+
+    /// A free slug for task `id` made from `name`, or `None` if the name
+    /// gives nothing to make one from.
+    pub fn suggest_slug(&self, name: &str, id: Uuid) -> Option<String> {
+        let base = slugify(name);
+        (!base.is_empty()).then(|| first_free(&base, |s| self.slug_taken(s, id)))
+    }
+
+    /// Why task `id` can't have slug `slug`: a malformed slug, or one
+    /// another task has.
+    pub fn slug_problem(&self, id: Uuid, slug: &str) -> Option<String> {
+        if let Some(problem) = slug_format_problem(slug) {
+            return Some(problem.to_string());
+        }
+        let other = self.tasks.iter().find(|t| t.id != id && t.slug.as_deref() == Some(slug))?;
+        Some(format!("'{}' already uses this slug", other.name))
+    }
+
+    fn slug_taken(&self, slug: &str, except: Uuid) -> bool {
+        self.tasks.iter().any(|t| t.id != except && t.slug.as_deref() == Some(slug))
+    }
+
+    /// Suffix `task`'s slug, if it has one, until no task in the project
+    /// has it.
+    pub(crate) fn free_slug(&self, task: &mut Task) {
+        if let Some(slug) = &task.slug {
+            task.slug = Some(first_free(slug, |s| self.slug_taken(s, task.id)));
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Generated projects of any size, for benchmarks and tests.
+//!
+//! Output is deterministic: the same task count always gives the same
+//! hierarchy, dates, priorities and links (only the ids differ).
+
+use chrono::{Duration, NaiveDate, NaiveTime};
+
+use super::project::Project;
+use super::task::{Dependency, DependencyKind, Task, TaskPriority};
+
+/// Tasks per top-level phase, including the phase itself.
+const PHASE_SIZE: usize = 25;
+/// Every this many tasks within a phase, one becomes a sub-group parent.
+const GROUP_EVERY: usize = 6;
+
+/// Small linear congruential generator; good enough for varied test data
+/// and avoids a dependency on `rand`.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        self.0 >> 33
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n.max(1)
+    }
+}
+
+/// Build a project with `task_count` tasks: phases of [`PHASE_SIZE`] tasks,
+/// some nested a level deeper, with finish-to-start links between
+/// neighbouring leaves, a sprinkling of milestones and varied priorities,
+/// progress, tags and assignees. Parent dates are already rolled up.
+pub fn synthetic_project(task_count: usize) -> Project {
+    let mut rng = Lcg(0x5EED);
+    let base = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap().and_time(NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+    let people = ["Alex", "Blair", "Casey", "Devon", "Emery"];
+    let tags = ["frontend", "backend", "design", "infra", "docs"];
+
+    let mut tasks: Vec<Task> = Vec::with_capacity(task_count);
+    let mut dependencies = Vec::new();
+    let mut phase_id = None;
+    let mut group_id = None;
+    let mut previous_leaf: Option<uuid::Uuid> = None;
+    let mut cursor = base;
+
+    for i in 0..task_count {
+        let slot = i % PHASE_SIZE;
+        let start = cursor + Duration::days(rng.below(3) as i64);
+        let end = start + Duration::days(1 + rng.below(10) as i64) + Duration::hours(8);
+        let mut task = if slot != 0 && rng.below(20) == 0 {
+            Task::new_milestone(format!("Milestone {}", i), start)
+        } else {
+            Task::new(format!("Task {}", i), start, end)
+        };
+        task.priority = TaskPriority::all()[rng.below(TaskPriority::all().len() as u64) as usize];
+        task.description = format!("Generated task {} of {}", i, task_count);
+
+        if slot == 0 {
+            // A new phase: top-level parent.
+            task.name = format!("Phase {}", i / PHASE_SIZE + 1);
+            phase_id = Some(task.id);
+            group_id = None;
+        } else if slot % GROUP_EVERY == 1 && !task.is_milestone {
+            // A sub-group parent inside the phase.
+            task.name = format!("Group {}", i);
+            task.parent_id = phase_id;
+            group_id = Some(task.id);
+        } else {
+            task.parent_id = group_id.or(phase_id);
+            task.progress = (rng.below(11) as f32) / 10.0;
+            task.tags.push(tags[rng.below(tags.len() as u64) as usize].to_string());
+            task.assignee = Some(people[rng.below(people.len() as u64) as usize].to_string());
+            if let Some(prev) = previous_leaf {
+                if rng.below(3) != 0 {
+                    dependencies.push(Dependency {
+                        from_task: prev,
+                        to_task: task.id,
+                        kind: DependencyKind::FinishToStart,
+                        lag_days: 0,
+                    });
+                }
+            }
+            previous_leaf = Some(task.id);
+            cursor = start;
+        }
+        tasks.push(task);
+    }
+
+    let mut project = Project::new(format!("Synthetic ({} tasks)", task_count));
+    project.set_tasks(tasks);
+    project.set_dependencies(dependencies);
+    project.recalculate_parent_dates();
+    project
+}
@@ -0,0 +1,788 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
+use crate::color::Rgba;
+use crate::risk::RiskLevel;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Task priority level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum TaskPriority {
+    #[default]
+    None,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl TaskPriority {
+    pub fn label(self) -> &'static str {
+        match self {
+            TaskPriority::None => "—",
+            TaskPriority::Low => "Low",
+            TaskPriority::Medium => "Medium",
+            TaskPriority::High => "High",
+            TaskPriority::Critical => "Critical",
+        }
+    }
+
+    pub fn all() -> &'static [TaskPriority] {
+        &[
+            TaskPriority::None,
+            TaskPriority::Low,
+            TaskPriority::Medium,
+            TaskPriority::High,
+            TaskPriority::Critical,
+        ]
+    }
+}
+
+/// Represents the type of dependency between two tasks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum DependencyKind {
+    #[default]
+    FinishToStart,
+    StartToStart,
+    FinishToFinish,
+    StartToFinish,
+}
+
+impl DependencyKind {
+    pub fn short_label(self) -> &'static str {
+        match self {
+            DependencyKind::FinishToStart => "FS",
+            DependencyKind::StartToStart => "SS",
+            DependencyKind::FinishToFinish => "FF",
+            DependencyKind::StartToFinish => "SF",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            DependencyKind::FinishToStart  => "Finish-to-Start (FS): successor can't start until this task finishes",
+            DependencyKind::StartToStart   => "Start-to-Start (SS): successor can't start until this task starts",
+            DependencyKind::FinishToFinish => "Finish-to-Finish (FF): successor can't finish until this task finishes",
+            DependencyKind::StartToFinish  => "Start-to-Finish (SF): successor can't finish until this task starts",
+        }
+    }
+
+    pub fn all() -> &'static [DependencyKind] {
+        &[
+            DependencyKind::FinishToStart,
+            DependencyKind::StartToStart,
+            DependencyKind::FinishToFinish,
+            DependencyKind::StartToFinish,
+        ]
+    }
+
+    /// The line a link of this kind is drawn with, so the kind reads
+    /// without relying on colour.
+    pub fn line_style(self) -> LineStyle {
+        match self {
+            DependencyKind::FinishToStart => LineStyle::Solid,
+            DependencyKind::StartToStart => LineStyle::Dashed,
+            DependencyKind::FinishToFinish => LineStyle::Dotted,
+            DependencyKind::StartToFinish => LineStyle::DashDot,
+        }
+    }
+}
+
+/// Stroke pattern of a dependency arrow, on screen and in exports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineStyle {
+    Solid,
+    Dashed,
+    Dotted,
+    DashDot,
+}
+
+impl LineStyle {
+    pub fn label(self) -> &'static str {
+        match self {
+            LineStyle::Solid => "Solid",
+            LineStyle::Dashed => "Dashed",
+            LineStyle::Dotted => "Dotted",
+            LineStyle::DashDot => "Dash-dot",
+        }
+    }
+
+    /// Alternating dash and gap lengths in pixels, as SVG's
+    /// `stroke-dasharray` takes them; empty for a solid line.
+    pub fn dash_pattern(self) -> &'static [f32] {
+        match self {
+            LineStyle::Solid => &[],
+            LineStyle::Dashed => &[6.0, 4.0],
+            LineStyle::Dotted => &[1.5, 3.0],
+            LineStyle::DashDot => &[7.0, 3.0, 1.5, 3.0],
+        }
+    }
+}
+
+/// Where a milestone stands relative to now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MilestoneStatus {
+    /// Marked complete.
+    Hit,
+    Upcoming,
+    /// Date has passed without being marked complete.
+    Overdue,
+}
+
+impl MilestoneStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            MilestoneStatus::Hit => "Hit",
+            MilestoneStatus::Upcoming => "Upcoming",
+            MilestoneStatus::Overdue => "Overdue",
+        }
+    }
+
+    pub fn color(self) -> Rgba {
+        match self {
+            MilestoneStatus::Hit => Rgba::from_rgb(80, 160, 80),
+            MilestoneStatus::Upcoming => Rgba::from_rgb(230, 160, 40),
+            MilestoneStatus::Overdue => Rgba::from_rgb(220, 60, 60),
+        }
+    }
+}
+
+/// How a task's progress compares with how far through its dates it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleStatus {
+    OnTrack,
+    /// A little behind: up to 20 points short of the expected progress.
+    Slipping,
+    Behind,
+}
+
+impl ScheduleStatus {
+    /// Status for a progress variance (actual minus expected, as fractions).
+    pub fn from_variance(variance: f32) -> Self {
+        if variance >= -0.05 {
+            ScheduleStatus::OnTrack
+        } else if variance >= -0.2 {
+            ScheduleStatus::Slipping
+        } else {
+            ScheduleStatus::Behind
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ScheduleStatus::OnTrack => "On track",
+            ScheduleStatus::Slipping => "Slipping",
+            ScheduleStatus::Behind => "Behind",
+        }
+    }
+
+    pub fn color(self) -> Rgba {
+        match self {
+            ScheduleStatus::OnTrack => Rgba::from_rgb(80, 160, 80),
+            ScheduleStatus::Slipping => Rgba::from_rgb(220, 140, 40),
+            ScheduleStatus::Behind => Rgba::from_rgb(220, 60, 60),
+        }
+    }
+}
+
+/// How far to move a task's dates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShiftAmount {
+    Days(i64),
+    /// Monday–Friday days; start and end each step over weekends.
+    WorkingDays(i64),
+}
+
+impl ShiftAmount {
+    pub fn is_zero(self) -> bool {
+        matches!(self, ShiftAmount::Days(0) | ShiftAmount::WorkingDays(0))
+    }
+
+    /// Move `at` by this amount, keeping its time of day.
+    pub fn apply(self, at: NaiveDateTime) -> NaiveDateTime {
+        match self {
+            ShiftAmount::Days(days) => at + chrono::Duration::days(days),
+            ShiftAmount::WorkingDays(days) => {
+                let step = chrono::Duration::days(days.signum());
+                let mut date = at.date();
+                let mut left = days.abs();
+                while left > 0 {
+                    date += step;
+                    if date.weekday().num_days_from_monday() < 5 {
+                        left -= 1;
+                    }
+                }
+                date.and_time(at.time())
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ShiftAmount {
+    /// `+14d` or `-3 working days`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShiftAmount::Days(days) => write!(f, "{:+}d", days),
+            ShiftAmount::WorkingDays(days) => write!(f, "{:+} working days", days),
+        }
+    }
+}
+
+/// The date [`Task::set_dates`] holds to when the new dates are closer
+/// than the minimum span; the other one moves away from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixedEdge {
+    Start,
+    End,
+}
+
+/// A dependency link between two tasks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dependency {
+    pub from_task: Uuid,
+    pub to_task: Uuid,
+    #[serde(default)]
+    pub kind: DependencyKind,
+    /// Offset in days applied to the constraint; negative values are lead time.
+    #[serde(default)]
+    pub lag_days: i64,
+}
+
+/// A timestamped status update on a task. Comments are only ever added or
+/// deleted, never edited.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: Uuid,
+    pub author: String,
+    #[serde(with = "datetime_serde")]
+    pub created: NaiveDateTime,
+    pub text: String,
+}
+
+impl Comment {
+    pub fn new(author: impl Into<String>, text: impl Into<String>, created: NaiveDateTime) -> Self {
+        Self { id: Uuid::new_v4(), author: author.into(), created, text: text.into() }
+    }
+}
+
+/// One step on a task's checklist.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    pub text: String,
+    #[serde(default)]
+    pub done: bool,
+}
+
+impl ChecklistItem {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into(), done: false }
+    }
+}
+
+/// A stretch of time worked on a task. `end` is `None` while the timer runs.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TimeEntry {
+    pub start: NaiveDateTime,
+    pub end: Option<NaiveDateTime>,
+}
+
+impl TimeEntry {
+    /// Length of the entry, counting a running one up to `now`.
+    pub fn duration(&self, now: NaiveDateTime) -> chrono::Duration {
+        (self.end.unwrap_or(now) - self.start).max(chrono::Duration::zero())
+    }
+}
+
+/// A single task or milestone in the Gantt chart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Task {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(with = "datetime_serde")]
+    pub start: NaiveDateTime,
+    #[serde(with = "datetime_serde")]
+    pub end: NaiveDateTime,
+    /// Progress from 0.0 (not started) to 1.0 (complete).
+    pub progress: f32,
+    /// Optional group/category name (legacy, kept for compat).
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Parent task id for hierarchy/phases.
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+    /// Whether this parent task's children are collapsed.
+    #[serde(default)]
+    pub collapsed: bool,
+    /// Priority level.
+    #[serde(default)]
+    pub priority: TaskPriority,
+    /// Optional description / notes.
+    #[serde(default)]
+    pub description: String,
+    /// Display color for the task bar.
+    pub color: Rgba,
+    /// If true, this is a milestone (rendered as a diamond, zero-duration).
+    pub is_milestone: bool,
+    /// Free-form labels for filtering and grouping.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Person responsible for the task.
+    #[serde(default)]
+    pub assignee: Option<String>,
+    /// Fixed dates: dragging, shifting and rescheduling leave the task
+    /// alone, and the editor only changes its dates once it is unlocked.
+    #[serde(default)]
+    pub locked: bool,
+    /// A placeholder not yet committed to: drawn hatched with a dashed
+    /// outline, and can be hidden from the list and chart.
+    #[serde(default)]
+    pub tentative: bool,
+    /// Status updates, oldest first.
+    #[serde(default)]
+    pub comments: Vec<Comment>,
+    /// Time worked on the task, oldest first.
+    #[serde(default)]
+    pub time_entries: Vec<TimeEntry>,
+    /// Expected effort in hours, to compare the tracked time against.
+    #[serde(default)]
+    pub estimate_hours: Option<f32>,
+    /// Web address the task comes from or refers to, e.g. an issue.
+    #[serde(default)]
+    pub link: Option<String>,
+    /// Steps to tick off; with [`ProgressPolicy::Checklist`] they set the
+    /// task's progress.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checklist: Vec<ChecklistItem>,
+    /// How risky the task is judged to be; `None` until someone rates it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk: Option<RiskLevel>,
+    /// Short name other files refer to the task by, unique in the project.
+    /// See [`crate::slug`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+}
+
+impl Task {
+    /// Create a new task with sensible defaults.
+    pub fn new(name: impl Into<String>, start: NaiveDateTime, end: NaiveDateTime) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            start,
+            end,
+            progress: 0.0,
+            group: None,
+            parent_id: None,
+            collapsed: false,
+            priority: TaskPriority::None,
+            description: String::new(),
+            color: Rgba::from_rgb(70, 130, 180), // Steel blue
+            is_milestone: false,
+            tags: Vec::new(),
+            assignee: None,
+            locked: false,
+            tentative: false,
+            comments: Vec::new(),
+            time_entries: Vec::new(),
+            estimate_hours: None,
+            link: None,
+            checklist: Vec::new(),
+            risk: None,
+            slug: None,
+        }
+    }
+
+    /// Create a new milestone.
+    pub fn new_milestone(name: impl Into<String>, date: NaiveDateTime) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            start: date,
+            end: date,
+            progress: 0.0,
+            group: None,
+            parent_id: None,
+            collapsed: false,
+            priority: TaskPriority::None,
+            description: String::new(),
+            color: Rgba::from_rgb(255, 165, 0), // Orange
+            is_milestone: true,
+            tags: Vec::new(),
+            assignee: None,
+            locked: false,
+            tentative: false,
+            comments: Vec::new(),
+            time_entries: Vec::new(),
+            estimate_hours: None,
+            link: None,
+            checklist: Vec::new(),
+            risk: None,
+            slug: None,
+        }
+    }
+
+    /// Length in calendar days, counting both the start and end day
+    /// (a task from Mon 09:00 to Fri 17:00 lasts 5 days).
+    pub fn duration_days(&self) -> i64 {
+        (self.end.date() - self.start.date()).num_days() + 1
+    }
+
+    /// Number of Monday–Friday days between the start and end day, inclusive.
+    pub fn working_days(&self) -> i64 {
+        let end = self.end.date();
+        self.start
+            .date()
+            .iter_days()
+            .take_while(|d| *d <= end)
+            .filter(|d| d.weekday().num_days_from_monday() < 5)
+            .count() as i64
+    }
+
+    /// Move the end so the task lasts `days` calendar days (clamped to at
+    /// least 1), keeping the start and the end's time of day.
+    pub fn set_duration_days(&mut self, days: i64) {
+        let end_date = self.start.date() + chrono::Duration::days(days.max(1) - 1);
+        self.end = end_date.and_time(self.end.time()).max(self.start);
+    }
+
+    /// Set the task's dates. A milestone keeps a single date, the fixed
+    /// edge's; any other task ends at least `min_span` after it starts,
+    /// the edge that isn't fixed moving to make room. Ends never come
+    /// before starts.
+    ///
+    /// ```
+    /// use chrono::{Duration, NaiveDate};
+    /// use gantt_core::task::FixedEdge;
+    /// use gantt_core::Task;
+    ///
+    /// let monday = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap().and_hms_opt(9, 0, 0).unwrap();
+    /// let mut task = Task::new("Build", monday, monday + Duration::days(4));
+    /// // An end dragged back past the start stops a day after it.
+    /// task.set_dates(monday, monday - Duration::days(2), Duration::days(1), FixedEdge::Start);
+    /// assert_eq!(task.end, monday + Duration::days(1));
+    /// ```
+    pub fn set_dates(&mut self, start: NaiveDateTime, end: NaiveDateTime, min_span: chrono::Duration, fixed: FixedEdge) {
+        if self.is_milestone {
+            self.set_single_date(if fixed == FixedEdge::End { end } else { start });
+            return;
+        }
+        let min_span = min_span.max(chrono::Duration::zero());
+        (self.start, self.end) = match fixed {
+            _ if end - start >= min_span => (start, end),
+            FixedEdge::Start => (start, start + min_span),
+            FixedEdge::End => (end - min_span, end),
+        };
+    }
+
+    /// Put the task on the single date `at`, as milestones are.
+    pub fn set_single_date(&mut self, at: NaiveDateTime) {
+        self.start = at;
+        self.end = at;
+    }
+
+    /// Whether the dates hold together: a milestone's start and end are
+    /// the same, and no task ends before it starts.
+    pub fn has_valid_dates(&self) -> bool {
+        if self.is_milestone {
+            self.start == self.end
+        } else {
+            self.end >= self.start
+        }
+    }
+
+    /// Move start and end by `amount`. Calendar shifts keep the duration
+    /// exactly; working-day shifts keep the number of working days.
+    pub fn shift(&mut self, amount: ShiftAmount) {
+        let start = amount.apply(self.start);
+        self.end = amount.apply(self.end).max(start);
+        self.start = start;
+    }
+
+    /// Give the task a one-day span: end of the working day (17:00) on its
+    /// start date, or an hour after the start if it begins later than that.
+    pub fn reset_to_one_day(&mut self) {
+        let end_of_day = self.start.date().and_time(NaiveTime::from_hms_opt(17, 0, 0).unwrap());
+        self.end = if end_of_day > self.start {
+            end_of_day
+        } else {
+            self.start + chrono::Duration::hours(1)
+        };
+    }
+
+    /// Tagged "key" (in any case), as milestones worth pinning above the
+    /// chart are.
+    pub fn is_key(&self) -> bool {
+        self.tags.iter().any(|t| t.trim().eq_ignore_ascii_case("key"))
+    }
+
+    /// The assignee with surrounding whitespace removed, if any.
+    pub fn assignee_name(&self) -> Option<&str> {
+        self.assignee.as_deref().map(str::trim).filter(|a| !a.is_empty())
+    }
+
+    /// Status of this task as a milestone at `now`.
+    pub fn milestone_status(&self, now: NaiveDateTime) -> MilestoneStatus {
+        if self.progress >= 1.0 {
+            MilestoneStatus::Hit
+        } else if self.start < now {
+            MilestoneStatus::Overdue
+        } else {
+            MilestoneStatus::Upcoming
+        }
+    }
+
+    /// Progress the task should have made by `now` if work went evenly
+    /// across its dates: 0 before the start, 1 after the end.
+    pub fn expected_progress(&self, now: NaiveDateTime) -> f32 {
+        if now <= self.start {
+            0.0
+        } else if now >= self.end {
+            1.0
+        } else {
+            ((now - self.start).num_minutes() as f64 / (self.end - self.start).num_minutes().max(1) as f64) as f32
+        }
+    }
+
+    /// Actual minus expected progress at `now`; negative means behind.
+    pub fn progress_variance(&self, now: NaiveDateTime) -> f32 {
+        self.progress.clamp(0.0, 1.0) - self.expected_progress(now)
+    }
+
+    pub fn schedule_status(&self, now: NaiveDateTime) -> ScheduleStatus {
+        ScheduleStatus::from_variance(self.progress_variance(now))
+    }
+
+    /// The entry whose timer is running, if any.
+    pub fn running_entry(&self) -> Option<&TimeEntry> {
+        self.time_entries.iter().find(|e| e.end.is_none())
+    }
+
+    /// Hours recorded in time entries, counting a running timer up to `now`.
+    pub fn tracked_hours(&self, now: NaiveDateTime) -> f32 {
+        let total: chrono::Duration = self.time_entries.iter().map(|e| e.duration(now)).sum();
+        total.num_seconds() as f32 / 3600.0
+    }
+
+    /// True once the task is fully done.
+    pub fn is_complete(&self) -> bool {
+        self.progress >= 1.0
+    }
+
+    /// Share of the checklist ticked off, `None` without one.
+    pub fn checklist_progress(&self) -> Option<f32> {
+        if self.checklist.is_empty() {
+            return None;
+        }
+        let done = self.checklist.iter().filter(|item| item.done).count();
+        Some(done as f32 / self.checklist.len() as f32)
+    }
+
+    /// Returns true if this task has any children in the given task list.
+    pub fn has_children(&self, tasks: &[Task]) -> bool {
+        tasks.iter().any(|t| t.parent_id == Some(self.id))
+    }
+
+    /// Returns the IDs of all direct children of this task.
+    pub fn children_ids<'a>(&self, tasks: &'a [Task]) -> Vec<&'a Task> {
+        tasks.iter().filter(|t| t.parent_id == Some(self.id)).collect()
+    }
+}
+
+/// Every distinct assignee in `tasks`, sorted case-insensitively.
+pub fn assignee_names(tasks: &[Task]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for name in tasks.iter().filter_map(Task::assignee_name) {
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    names.sort_by_key(|n| n.to_lowercase());
+    names
+}
+
+/// How completed tasks appear in the list and chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CompletedTasks {
+    #[default]
+    Show,
+    /// Names struck through and bars faded.
+    Strike,
+    /// Left out, except for parents that still have unfinished work.
+    Hide,
+}
+
+impl CompletedTasks {
+    pub fn is_default(&self) -> bool {
+        *self == CompletedTasks::Show
+    }
+}
+
+/// Where the project's task progress comes from. Saved with the project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ProgressPolicy {
+    /// Every task's progress is entered by hand, parents' too.
+    Manual,
+    /// Tasks with a checklist are as far along as the share of it ticked
+    /// off; parents without one roll up from their children.
+    Checklist,
+    /// Parents roll up from their children, the rest is entered by hand.
+    #[default]
+    Subtasks,
+}
+
+impl ProgressPolicy {
+    pub fn all() -> &'static [ProgressPolicy] {
+        &[ProgressPolicy::Manual, ProgressPolicy::Checklist, ProgressPolicy::Subtasks]
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == ProgressPolicy::Subtasks
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProgressPolicy::Manual => "Manual",
+            ProgressPolicy::Checklist => "From checklist",
+            ProgressPolicy::Subtasks => "From subtasks",
+        }
+    }
+
+    /// Where `task`'s progress comes from under this policy; `is_parent`
+    /// says whether it has children.
+    pub fn source(self, task: &Task, is_parent: bool) -> ProgressSource {
+        match self {
+            ProgressPolicy::Checklist if !task.checklist.is_empty() => ProgressSource::Checklist,
+            ProgressPolicy::Checklist | ProgressPolicy::Subtasks if is_parent => ProgressSource::Subtasks,
+            _ => ProgressSource::Manual,
+        }
+    }
+}
+
+/// Where one task's progress comes from; see [`ProgressPolicy::source`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressSource {
+    Manual,
+    Checklist,
+    Subtasks,
+}
+
+/// Ids of the completed tasks `mode` leaves out: with [`CompletedTasks::Hide`],
+/// every complete task that has no unfinished task below it.
+pub fn hidden_completed(tasks: &[Task], mode: CompletedTasks) -> HashSet<Uuid> {
+    if mode != CompletedTasks::Hide {
+        return HashSet::new();
+    }
+    hidden_where(tasks, Task::is_complete)
+}
+
+/// Ids of the tentative tasks to leave out when `hide` is set: every
+/// tentative task with no committed task below it, so a tentative phase
+/// still frames its confirmed work.
+pub fn hidden_tentative(tasks: &[Task], hide: bool) -> HashSet<Uuid> {
+    if !hide {
+        return HashSet::new();
+    }
+    hidden_where(tasks, |t| t.tentative)
+}
+
+/// Ids of the tasks to leave out when only those rated `only` are shown:
+/// every other task with no task at that level below it, so a phase still
+/// frames its matching work. Nothing is left out for `None`.
+pub fn hidden_by_risk(tasks: &[Task], only: Option<RiskLevel>) -> HashSet<Uuid> {
+    let Some(level) = only else {
+        return HashSet::new();
+    };
+    hidden_where(tasks, |t| t.risk != Some(level))
+}
+
+/// Tasks matching `hide` that have no task below them which doesn't.
+pub(crate) fn hidden_where(tasks: &[Task], hide: impl Fn(&Task) -> bool) -> HashSet<Uuid> {
+    let parent_of: HashMap<Uuid, Option<Uuid>> = tasks.iter().map(|t| (t.id, t.parent_id)).collect();
+    let mut kept_below: HashSet<Uuid> = HashSet::new();
+    for task in tasks.iter().filter(|t| !hide(t)) {
+        let mut parent = task.parent_id;
+        // `insert` returning false means the rest of the chain is marked already.
+        while let Some(id) = parent.filter(|id| kept_below.insert(*id)) {
+            parent = parent_of.get(&id).copied().flatten();
+        }
+    }
+    tasks
+        .iter()
+        .filter(|t| hide(t) && !kept_below.contains(&t.id))
+        .map(|t| t.id)
+        .collect()
+}
+
+/// Returns true if a task matches the current search/filter.
+pub fn task_matches(
+    name: &str,
+    description: &str,
+    priority: TaskPriority,
+    search: &str,
+    filter_priority: Option<TaskPriority>,
+) -> bool {
+    // Priority filter
+    if let Some(fp) = filter_priority {
+        if priority != fp {
+            return false;
+        }
+    }
+
+    // Text search (case-insensitive)
+    if !search.is_empty() {
+        let query = search.to_lowercase();
+        if !name.to_lowercase().contains(&query) && !description.to_lowercase().contains(&query) {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Serde helper for `NaiveDateTime` with backward compatibility for `NaiveDate`.
+/// Supports migration from date-only strings to datetime strings.
+mod datetime_serde {
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+    use serde::{self, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(dt: &NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let s = dt.format("%Y-%m-%dT%H:%M:%S").to_string();
+        serializer.serialize_str(&s)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<NaiveDateTime, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s: String = Deserialize::deserialize(deserializer)?;
+
+        // Try parsing as full datetime first (ISO 8601 format)
+        if let Ok(dt) = NaiveDateTime::parse_from_str(&s, "%Y-%m-%dT%H:%M:%S") {
+            return Ok(dt);
+        }
+
+        // Try alternative datetime formats
+        if let Ok(dt) = NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S") {
+            return Ok(dt);
+        }
+
+        // Fallback: parse as date-only and append default time
+        // For backward compatibility with v2 files
+        if let Ok(date) = NaiveDate::parse_from_str(&s, "%Y-%m-%d") {
+            // Default to midnight for backward compatibility
+            // Note: We can't distinguish between start/end here, so we default to midnight
+            // The caller should handle setting end times to 23:59:59 if needed
+            let time = NaiveTime::from_hms_opt(0, 0, 0).unwrap();
+            return Ok(NaiveDateTime::new(date, time));
+        }
+
+        Err(serde::de::Error::custom(format!(
+            "Failed to parse datetime or date from: {}",
+            s
+        )))
+    }
+}
@@ -0,0 +1,93 @@
+//! Dated progress readings per task, for the editor's sparkline, earned
+//! value as of past days and the progress history CSV export.
+//!
+//! Readings are taken by [`Project::record_progress`] while the project's
+//! `log_task_progress` setting is on. A task gets at most one reading per
+//! day — a later change the same day replaces it — and a day whose
+//! progress matches the task's previous reading adds nothing.
+//!
+//! [`Project::record_progress`]: crate::Project::record_progress
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::task::Task;
+
+/// Most readings kept across all tasks; the oldest go first.
+pub const MAX_TASK_PROGRESS_SAMPLES: usize = 20_000;
+
+/// One task's progress at the end of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TaskProgressSample {
+    pub task: Uuid,
+    pub date: NaiveDate,
+    /// 0.0–1.0.
+    pub progress: f32,
+}
+
+/// Readings for every task, oldest first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TaskProgressLog {
+    samples: Vec<TaskProgressSample>,
+}
+
+impl TaskProgressLog {
+    pub fn samples(&self) -> &[TaskProgressSample] {
+        &self.samples
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// `task`'s readings, oldest first.
+    pub fn for_task(&self, task: Uuid) -> impl Iterator<Item = &TaskProgressSample> {
+        self.samples.iter().filter(move |s| s.task == task)
+    }
+
+    /// `task`'s progress at the end of `date`: its latest reading on or
+    /// before that day. `None` before the first reading.
+    pub fn progress_on(&self, task: Uuid, date: NaiveDate) -> Option<f32> {
+        self.samples.iter().rev().find(|s| s.task == task && s.date <= date).map(|s| s.progress)
+    }
+
+    /// Note `task` at `progress` on `date`, replacing a reading from the
+    /// same day and skipping one that changes nothing.
+    pub fn record(&mut self, task: Uuid, date: NaiveDate, progress: f32) {
+        let sample = TaskProgressSample { task, date, progress: progress.clamp(0.0, 1.0) };
+        match self.samples.iter().rposition(|s| s.task == task) {
+            Some(i) if self.samples[i].date == date => {
+                // Dropping back to the day before's value undoes the day's reading.
+                let before = self.samples[..i].iter().rfind(|s| s.task == task);
+                if before.is_some_and(|s| s.progress == sample.progress) {
+                    self.samples.remove(i);
+                } else {
+                    self.samples[i] = sample;
+                }
+            }
+            Some(i) if self.samples[i].progress == sample.progress => {}
+            _ => self.samples.push(sample),
+        }
+        let excess = self.samples.len().saturating_sub(MAX_TASK_PROGRESS_SAMPLES);
+        self.samples.drain(..excess);
+    }
+
+    /// [`Self::record`] each of `tasks`, looking only at those whose
+    /// progress differs from their latest reading.
+    pub fn record_tasks(&mut self, tasks: &[Task], date: NaiveDate) {
+        let latest: HashMap<Uuid, f32> = self.samples.iter().map(|s| (s.task, s.progress)).collect();
+        for task in tasks {
+            if latest.get(&task.id) != Some(&task.progress.clamp(0.0, 1.0)) {
+                self.record(task.id, date, task.progress);
+            }
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::task::Task;
+
+/// Shortest range a viewport spans, so widths and zoom maths never divide
+/// by zero or go negative.
+pub const MIN_RANGE_DAYS: i64 = 1;
+
+/// Zoom limits, in pixels per day.
+const MIN_PIXELS_PER_DAY: f32 = 1.0;
+const MAX_PIXELS_PER_DAY: f32 = 120.0;
+
+/// Controls what scale the timeline displays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimelineScale {
+    Hours,
+    Days,
+    Weeks,
+    Months,
+}
+
+impl TimelineScale {
+    pub fn label(self) -> &'static str {
+        match self {
+            TimelineScale::Hours => "Hours",
+            TimelineScale::Days => "Days",
+            TimelineScale::Weeks => "Weeks",
+            TimelineScale::Months => "Months",
+        }
+    }
+}
+
+/// Manages the visible viewport of the timeline.
+///
+/// `end` is kept at least [`MIN_RANGE_DAYS`] after `start` and the zoom
+/// within its limits by the constructor and the methods below; code that
+/// writes the fields directly should go through [`TimelineViewport::set_range`]
+/// instead.
+///
+/// ```
+/// use chrono::{Duration, NaiveDate};
+/// use gantt_core::TimelineViewport;
+///
+/// let start = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+/// let mut viewport = TimelineViewport::new(start, start + Duration::days(30));
+/// viewport.set_pixels_per_day(20.0);
+/// assert_eq!(viewport.datetime_to_x(start + Duration::days(2)), 40.0);
+/// assert_eq!(viewport.x_to_datetime(40.0), start + Duration::days(2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimelineViewport {
+    /// The leftmost visible datetime.
+    pub start: NaiveDateTime,
+    /// The rightmost visible datetime.
+    pub end: NaiveDateTime,
+    /// Current display scale.
+    pub scale: TimelineScale,
+    /// Pixels per day (controls zoom level).
+    pub pixels_per_day: f32,
+    /// Pixels per hour (derived from pixels_per_day).
+    pub pixels_per_hour: f32,
+}
+
+impl TimelineViewport {
+    /// A viewport over `start..end` at the default zoom. An `end` before
+    /// or too close to `start` is moved out to the minimum range.
+    pub fn new(start: NaiveDateTime, end: NaiveDateTime) -> Self {
+        let pixels_per_day = 18.0;
+        let mut viewport = Self {
+            start,
+            end,
+            scale: TimelineScale::Weeks,
+            pixels_per_day,
+            pixels_per_hour: pixels_per_day / 24.0,
+        };
+        viewport.set_range(start, end);
+        viewport
+    }
+
+    /// Show `start..end` at the current zoom, with `end` moved out to at
+    /// least the minimum range after `start`.
+    pub fn set_range(&mut self, start: NaiveDateTime, end: NaiveDateTime) {
+        self.start = start;
+        self.end = end.max(start + Duration::days(MIN_RANGE_DAYS));
+    }
+
+    /// Move the range by `by`, keeping its length.
+    pub fn pan(&mut self, by: Duration) {
+        self.set_range(self.start + by, self.end + by);
+    }
+
+    /// Set the zoom, clamped to its limits; a non-finite value keeps the
+    /// current zoom. The scale is left alone.
+    pub fn set_pixels_per_day(&mut self, pixels_per_day: f32) {
+        if pixels_per_day.is_finite() {
+            self.pixels_per_day = pixels_per_day.clamp(MIN_PIXELS_PER_DAY, MAX_PIXELS_PER_DAY);
+            self.pixels_per_hour = self.pixels_per_day / 24.0;
+        }
+    }
+
+    /// Convert a datetime to an x-pixel offset from the viewport start.
+    pub fn datetime_to_x(&self, dt: NaiveDateTime) -> f32 {
+        match self.scale {
+            TimelineScale::Hours => {
+                let hours = (dt - self.start).num_seconds() as f32 / 3600.0;
+                hours * self.pixels_per_hour
+            }
+            _ => {
+                let total_seconds = (dt - self.start).num_seconds() as f32;
+                let days = total_seconds / 86400.0; // 86400 seconds in a day
+                days * self.pixels_per_day
+            }
+        }
+    }
+
+    /// Legacy method for compatibility - delegates to datetime_to_x.
+    pub fn date_to_x(&self, date: NaiveDateTime) -> f32 {
+        self.datetime_to_x(date)
+    }
+
+    /// Convert an x-pixel offset to a datetime (inverse of datetime_to_x).
+    pub fn x_to_datetime(&self, x: f32) -> NaiveDateTime {
+        match self.scale {
+            TimelineScale::Hours => {
+                let hours = x / self.pixels_per_hour;
+                self.start + chrono::Duration::seconds((hours * 3600.0) as i64)
+            }
+            _ => {
+                let days = x / self.pixels_per_day;
+                self.start + chrono::Duration::seconds((days * 86400.0) as i64)
+            }
+        }
+    }
+
+    /// The step dragging moves dates by at the current scale: an hour on
+    /// the hour scale, a day otherwise.
+    pub fn snap_unit(&self) -> Duration {
+        match self.scale {
+            TimelineScale::Hours => Duration::hours(1),
+            _ => Duration::days(1),
+        }
+    }
+
+    /// Total width in pixels for the visible range; never negative.
+    pub fn total_width(&self) -> f32 {
+        self.datetime_to_x(self.end).max(0.0)
+    }
+
+    /// Zoom in (increase pixels per day), auto-switching scale if needed.
+    pub fn zoom_in(&mut self) {
+        self.pixels_per_day = (self.pixels_per_day * 1.2).min(MAX_PIXELS_PER_DAY);
+        self.pixels_per_hour = self.pixels_per_day / 24.0;
+
+        // Auto-switch timeline scale based on zoom level
+        self.update_scale_for_zoom();
+    }
+
+    /// Zoom out (decrease pixels per day), auto-switching scale if needed.
+    pub fn zoom_out(&mut self) {
+        self.pixels_per_day = (self.pixels_per_day / 1.2).max(MIN_PIXELS_PER_DAY);
+        self.pixels_per_hour = self.pixels_per_day / 24.0;
+
+        // Auto-switch timeline scale based on zoom level
+        self.update_scale_for_zoom();
+    }
+
+    /// Show `start..end` across `width` pixels, picking the zoom and scale to
+    /// match. Within the zoom limits the range may end up a little wider.
+    /// Inverted bounds are swapped, a range under a day long is shown as
+    /// the week around it, and a width that isn't positive counts as one
+    /// pixel.
+    pub fn fit(&mut self, start: NaiveDateTime, end: NaiveDateTime, width: f32) {
+        let (mut start, mut end) = (start.min(end), start.max(end));
+        if end - start < Duration::days(1) {
+            let middle = start + (end - start) / 2;
+            start = middle - Duration::hours(84);
+            end = middle + Duration::hours(84);
+        }
+        let width = if width.is_finite() { width.max(1.0) } else { 1.0 };
+        let days = (end - start).num_seconds() as f32 / 86400.0;
+        self.set_pixels_per_day(width / days);
+        let visible_days = (width / self.pixels_per_day) as i64;
+        self.set_range(start, end.max(start + Duration::days(visible_days)));
+        self.update_scale_for_zoom();
+    }
+
+    /// Frame `start..end` across `width` pixels with about 5% padding on
+    /// either side. A range under a day long, such as a single milestone,
+    /// is shown as the week either side of it instead.
+    pub fn fit_to_range(&mut self, start: NaiveDateTime, end: NaiveDateTime, width: f32) {
+        let (start, end) = (start.min(end), start.max(end));
+        let span = end - start;
+        if span < Duration::days(1) {
+            let middle = start + span / 2;
+            self.fit(middle - Duration::weeks(1), middle + Duration::weeks(1), width);
+        } else {
+            let pad = span / 20;
+            self.fit(start - pad, end + pad, width);
+        }
+    }
+
+    /// Frame all of `tasks` across `width` pixels with a little padding. A
+    /// single milestone gets the week around it; without tasks the week
+    /// from the current start is shown.
+    pub fn fit_to_tasks(&mut self, tasks: &[Task], width: f32) {
+        let start = tasks.iter().map(|t| t.start).min();
+        let end = tasks.iter().map(|t| t.end).max();
+        match (start, end) {
+            (Some(start), Some(end)) if end - start >= Duration::days(1) => {
+                let pad = ((end - start) / 40).max(Duration::days(1));
+                self.fit(start - pad, end + pad, width);
+            }
+            (Some(start), Some(end)) => self.fit(start, end, width),
+            _ => self.fit(self.start, self.start + Duration::weeks(1), width),
+        }
+    }
+
+    /// Update the timeline scale based on current zoom level (pixels per day).
+    fn update_scale_for_zoom(&mut self) {
+        // Scale thresholds:
+        // Hours: > 50 ppd (very zoomed in)
+        // Days: 10-50 ppd (moderate zoom)
+        // Weeks: 3-10 ppd (zoomed out)
+        // Months: < 3 ppd (very zoomed out)
+
+        if self.pixels_per_day > 50.0 {
+            self.scale = TimelineScale::Hours;
+        } else if self.pixels_per_day > 10.0 {
+            self.scale = TimelineScale::Days;
+        } else if self.pixels_per_day > 3.0 {
+            self.scale = TimelineScale::Weeks;
+        } else {
+            self.scale = TimelineScale::Months;
+        }
+    }
+
+}
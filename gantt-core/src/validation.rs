@@ -0,0 +1,252 @@
+//! Schedule checks shared by the editor and anything else that needs to
+//! explain what is wrong with a task.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Duration, NaiveDateTime};
+use uuid::Uuid;
+
+use super::absence::{absences_overlapping, Absence};
+use super::graph::DependencyGraph;
+use super::risk::RiskLevel;
+use super::schedule::critical_tasks;
+use super::task::{Dependency, DependencyKind, FixedEdge, Task};
+
+/// A one-click correction for a [`ValidationIssue`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValidationFix {
+    /// Move the task, keeping its duration, so it starts at this time.
+    MoveStart(NaiveDateTime),
+    /// Move the task, keeping its duration, so it ends at this time.
+    MoveEnd(NaiveDateTime),
+    /// Give the task a one-day span.
+    ResetDuration,
+}
+
+impl ValidationFix {
+    pub fn label(self) -> &'static str {
+        match self {
+            ValidationFix::MoveStart(_) | ValidationFix::MoveEnd(_) => "Move task",
+            ValidationFix::ResetDuration => "Make 1 day",
+        }
+    }
+
+    /// Apply the fix to `task`.
+    pub fn apply(self, task: &mut Task) {
+        match self {
+            ValidationFix::MoveStart(start) => {
+                let end = task.end + (start - task.start);
+                task.set_dates(start, end, Duration::zero(), FixedEdge::Start);
+            }
+            ValidationFix::MoveEnd(end) => {
+                let start = task.start + (end - task.end);
+                task.set_dates(start, end, Duration::zero(), FixedEdge::End);
+            }
+            ValidationFix::ResetDuration => task.reset_to_one_day(),
+        }
+    }
+}
+
+/// A problem found with a single task.
+#[derive(Debug, Clone)]
+pub struct ValidationIssue {
+    pub message: String,
+    /// `None` when there is no safe automatic fix (e.g. parent tasks,
+    /// whose dates come from their children, or locked tasks).
+    pub fix: Option<ValidationFix>,
+}
+
+/// Where a dependency constrains its successor, and the earliest time it allows.
+/// Returns `(constrains_start, earliest)`.
+pub fn dependency_bound(dep: &Dependency, predecessor: &Task) -> (bool, NaiveDateTime) {
+    let lag = Duration::days(dep.lag_days);
+    match dep.kind {
+        DependencyKind::FinishToStart => (true, predecessor.end + lag),
+        DependencyKind::StartToStart => (true, predecessor.start + lag),
+        DependencyKind::FinishToFinish => (false, predecessor.end + lag),
+        DependencyKind::StartToFinish => (false, predecessor.start + lag),
+    }
+}
+
+/// A dependency the successor's current dates break. See
+/// [`crate::Project::dependency_violations`].
+#[derive(Debug, Clone)]
+pub struct DependencyViolation {
+    pub dependency: Dependency,
+    /// Whether the link bounds the successor's start (FS/SS) or its end (FF/SF).
+    pub constrains_start: bool,
+    /// The earliest start or end the link allows.
+    pub earliest: NaiveDateTime,
+    /// How far before `earliest` the successor starts or ends.
+    pub early_by: Duration,
+}
+
+impl DependencyViolation {
+    /// The violation of `dep`, if `successor` breaks it given `predecessor`'s dates.
+    pub fn check(dep: &Dependency, predecessor: &Task, successor: &Task) -> Option<Self> {
+        let (constrains_start, earliest) = dependency_bound(dep, predecessor);
+        let current = if constrains_start { successor.start } else { successor.end };
+        (current < earliest).then(|| DependencyViolation {
+            dependency: dep.clone(),
+            constrains_start,
+            earliest,
+            early_by: earliest - current,
+        })
+    }
+
+    /// `Starts before 'Design' allows (FS +2d, 2024-03-10 09:00)`.
+    pub fn describe(&self, predecessor: &str) -> String {
+        let what = if self.constrains_start { "Starts" } else { "Ends" };
+        let dep = &self.dependency;
+        let lag = if dep.lag_days == 0 { String::new() } else { format!(" {:+}d", dep.lag_days) };
+        format!(
+            "{} before '{}' allows ({}{}, {})",
+            what,
+            predecessor,
+            dep.kind.short_label(),
+            lag,
+            self.earliest.format("%Y-%m-%d %H:%M")
+        )
+    }
+}
+
+/// True if `successor` satisfies `dep` given `predecessor`'s dates.
+pub fn dependency_satisfied(dep: &Dependency, predecessor: &Task, successor: &Task) -> bool {
+    let (constrains_start, earliest) = dependency_bound(dep, predecessor);
+    if constrains_start {
+        successor.start >= earliest
+    } else {
+        successor.end >= earliest
+    }
+}
+
+/// The span of each parent's direct subtasks, from the earliest start to
+/// the latest end, keyed by parent.
+pub fn subtask_spans(tasks: &[Task]) -> HashMap<Uuid, (NaiveDateTime, NaiveDateTime)> {
+    let mut spans: HashMap<Uuid, (NaiveDateTime, NaiveDateTime)> = HashMap::new();
+    for task in tasks {
+        if let Some(pid) = task.parent_id {
+            spans
+                .entry(pid)
+                .and_modify(|(start, end)| {
+                    *start = (*start).min(task.start);
+                    *end = (*end).max(task.end);
+                })
+                .or_insert((task.start, task.end));
+        }
+    }
+    spans
+}
+
+/// Check one task against its own dates, its incoming dependencies and its
+/// assignee's absences, and warn if it is high-risk on the critical path
+/// or a parent that doesn't span its subtasks.
+pub fn validate_task(
+    task: &Task,
+    tasks: &[Task],
+    graph: &DependencyGraph,
+    absences: &[Absence],
+) -> Vec<ValidationIssue> {
+    let critical = is_high_risk(task) && critical_tasks(tasks, graph).contains(&task.id);
+    let subtasks = tasks
+        .iter()
+        .filter(|t| t.parent_id == Some(task.id))
+        .fold(None, |span: Option<(NaiveDateTime, NaiveDateTime)>, t| {
+            Some(span.map_or((t.start, t.end), |(start, end)| (start.min(t.start), end.max(t.end))))
+        });
+    check_task(task, subtasks, critical, |id| tasks.iter().find(|t| t.id == id), graph, absences)
+}
+
+fn is_high_risk(task: &Task) -> bool {
+    task.risk == Some(RiskLevel::High)
+}
+
+/// Number of tasks with at least one issue, in a single pass over the project.
+pub fn count_tasks_with_issues(tasks: &[Task], graph: &DependencyGraph, absences: &[Absence]) -> usize {
+    let spans = subtask_spans(tasks);
+    let by_id: HashMap<Uuid, &Task> = tasks.iter().map(|t| (t.id, t)).collect();
+    // The critical path is only worked out if some task could be flagged on it.
+    let critical = if tasks.iter().any(is_high_risk) { critical_tasks(tasks, graph) } else { HashSet::new() };
+    tasks
+        .iter()
+        .filter(|t| {
+            let on_critical = is_high_risk(t) && critical.contains(&t.id);
+            !check_task(t, spans.get(&t.id).copied(), on_critical, |id| by_id.get(&id).copied(), graph, absences).is_empty()
+        })
+        .count()
+}
+
+/// `subtasks` is the span of the task's subtasks, if it has any.
+fn check_task<'a>(
+    task: &Task,
+    subtasks: Option<(NaiveDateTime, NaiveDateTime)>,
+    high_risk_critical: bool,
+    find: impl Fn(Uuid) -> Option<&'a Task>,
+    graph: &DependencyGraph,
+    absences: &[Absence],
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let is_parent = subtasks.is_some();
+
+    // Parents take their dates from their subtasks; one that doesn't was
+    // changed without a rollup, and the next change made in the app fixes it.
+    if let Some((start, end)) = subtasks.filter(|&span| span != (task.start, task.end)) {
+        issues.push(ValidationIssue {
+            message: format!(
+                "Doesn't span its subtasks ({} – {})",
+                start.format("%Y-%m-%d"),
+                end.format("%Y-%m-%d")
+            ),
+            fix: None,
+        });
+    }
+
+    if !task.is_milestone && !is_parent && task.end <= task.start {
+        issues.push(ValidationIssue {
+            message: if task.end == task.start {
+                "Has no duration".to_string()
+            } else {
+                "Ends before it starts".to_string()
+            },
+            fix: (!task.locked).then_some(ValidationFix::ResetDuration),
+        });
+    }
+
+    for dep in graph.predecessors(task.id) {
+        let Some(pred) = find(dep.from_task) else {
+            continue;
+        };
+        let Some(violation) = DependencyViolation::check(dep, pred, task) else {
+            continue;
+        };
+        issues.push(ValidationIssue {
+            message: violation.describe(&pred.name),
+            fix: (!is_parent && !task.locked).then_some(if violation.constrains_start {
+                ValidationFix::MoveStart(violation.earliest)
+            } else {
+                ValidationFix::MoveEnd(violation.earliest)
+            }),
+        });
+    }
+
+    // Parents have no assignee work of their own; their subtasks are checked.
+    if !is_parent {
+        for absence in absences_overlapping(task, absences) {
+            // The fix starts the task, at its usual time, on the day they're back.
+            let (_, back) = absence.span();
+            issues.push(ValidationIssue {
+                message: format!("Overlaps assignee absence ({})", absence.describe()),
+                fix: (!task.locked).then_some(ValidationFix::MoveStart(back.date().and_time(task.start.time()))),
+            });
+        }
+    }
+
+    if high_risk_critical {
+        issues.push(ValidationIssue {
+            message: "High risk and on the critical path: any slip moves the project end".to_string(),
+            fix: None,
+        });
+    }
+
+    issues
+}
@@ -0,0 +1,21 @@
+//! Dates shared by the integration tests. Every test works in March 2024,
+//! where the 4th is a Monday.
+
+#![allow(dead_code)]
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+/// Day `d` of March 2024.
+pub fn date(d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, d).unwrap()
+}
+
+/// `hour`:00 on day `d` of March 2024.
+pub fn at(d: u32, hour: u32) -> NaiveDateTime {
+    date(d).and_hms_opt(hour, 0, 0).unwrap()
+}
+
+/// 9:00 on day `d` of March 2024.
+pub fn day(d: u32) -> NaiveDateTime {
+    at(d, 9)
+}
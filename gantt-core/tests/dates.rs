@@ -0,0 +1,97 @@
+//! Task dates hold together however they are changed: ends never come
+//! before starts, resizes keep a minimum span and milestones stay a single
+//! point in time.
+
+mod common;
+
+use chrono::Duration;
+use common::day;
+use gantt_core::task::{FixedEdge, ShiftAmount};
+use gantt_core::validation::ValidationFix;
+use gantt_core::{Project, Task};
+
+#[test]
+fn set_dates_takes_a_valid_range_as_given() {
+    let mut task = Task::new("Build", day(4), day(8));
+    task.set_dates(day(5), day(9), Duration::days(1), FixedEdge::Start);
+    assert_eq!((task.start, task.end), (day(5), day(9)));
+}
+
+#[test]
+fn set_dates_stops_an_end_dragged_past_the_start() {
+    let mut task = Task::new("Build", day(5), day(9));
+    task.set_dates(day(5), day(3), Duration::days(1), FixedEdge::Start);
+    assert_eq!((task.start, task.end), (day(5), day(6)));
+}
+
+#[test]
+fn set_dates_stops_a_start_dragged_past_the_end() {
+    let mut task = Task::new("Build", day(5), day(6));
+    task.set_dates(day(7), day(6), Duration::days(1), FixedEdge::End);
+    assert_eq!((task.start, task.end), (day(5), day(6)));
+}
+
+#[test]
+fn set_dates_without_a_minimum_span_lets_the_ends_meet_but_not_cross() {
+    let mut task = Task::new("Build", day(5), day(6));
+    task.set_dates(day(8), day(6), Duration::zero(), FixedEdge::End);
+    assert_eq!((task.start, task.end), (day(6), day(6)));
+    assert!(task.has_valid_dates());
+}
+
+#[test]
+fn set_dates_never_gives_a_milestone_a_duration() {
+    let mut launch = Task::new_milestone("Launch", day(15));
+    launch.set_dates(day(16), day(20), Duration::days(1), FixedEdge::Start);
+    assert_eq!((launch.start, launch.end), (day(16), day(16)));
+    launch.set_dates(day(16), day(20), Duration::days(1), FixedEdge::End);
+    assert_eq!((launch.start, launch.end), (day(20), day(20)));
+    assert!(launch.has_valid_dates());
+}
+
+#[test]
+fn shifting_keeps_the_duration() {
+    let mut task = Task::new("Build", day(4), day(8));
+    task.shift(ShiftAmount::Days(3));
+    assert_eq!((task.start, task.end), (day(7), day(11)));
+    let mut launch = Task::new_milestone("Launch", day(15));
+    launch.shift(ShiftAmount::Days(-1));
+    assert!(launch.has_valid_dates());
+}
+
+#[test]
+fn validation_fixes_keep_milestones_on_one_date() {
+    let mut launch = Task::new_milestone("Launch", day(15));
+    ValidationFix::MoveStart(day(18)).apply(&mut launch);
+    assert_eq!((launch.start, launch.end), (day(18), day(18)));
+    ValidationFix::MoveEnd(day(20)).apply(&mut launch);
+    assert_eq!((launch.start, launch.end), (day(20), day(20)));
+}
+
+#[test]
+fn repair_dates_puts_right_inverted_tasks_and_stretched_milestones() {
+    let mut project = Project::new("Launch");
+    let mut build = Task::new("Build", day(4), day(8));
+    build.end = day(2);
+    let mut launch = Task::new_milestone("Launch", day(15));
+    launch.end = day(16);
+    project.add_task(build);
+    project.add_task(launch);
+
+    assert_eq!(project.repair_dates(), 2);
+    assert_eq!((project.tasks[0].start, project.tasks[0].end), (day(4), day(4)));
+    assert_eq!((project.tasks[1].start, project.tasks[1].end), (day(15), day(15)));
+    assert_eq!(project.repair_dates(), 0);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "task dates out of order")]
+fn touch_catches_a_task_left_ending_before_it_starts() {
+    let mut project = Project::new("Launch");
+    let build = Task::new("Build", day(4), day(8));
+    let id = build.id;
+    project.add_task(build);
+    project.task_mut(id).unwrap().end = day(2);
+    project.touch();
+}
@@ -0,0 +1,110 @@
+//! Projects come back from disk as they were saved, and a failed save never
+//! costs the copy already there.
+
+mod common;
+
+use std::io::{self, Write};
+
+use common::day;
+use gantt_core::color::Rgba;
+use gantt_core::file::{save_project_with, temp_path, SaveSink};
+use gantt_core::task::{Dependency, DependencyKind};
+use gantt_core::{Project, Task};
+
+#[test]
+fn tasks_and_dependencies_survive_a_round_trip() {
+    let mut project = Project::new("Launch");
+    let design = Task::new("Design", day(4), day(8));
+    let build = Task::new("Build", day(11), day(15));
+    project.add_dependency(Dependency {
+        from_task: design.id,
+        to_task: build.id,
+        kind: DependencyKind::FinishToStart,
+        lag_days: 0,
+    });
+    project.add_task(design);
+    project.add_task(build);
+
+    let json = serde_json::to_string(&project).unwrap();
+    let loaded: Project = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.name, "Launch");
+    assert_eq!(loaded.tasks.len(), project.tasks.len());
+    for (loaded, saved) in loaded.tasks.iter().zip(&project.tasks) {
+        assert_eq!(
+            (loaded.id, &loaded.name, loaded.start, loaded.end, loaded.progress, loaded.parent_id),
+            (saved.id, &saved.name, saved.start, saved.end, saved.progress, saved.parent_id),
+        );
+        assert_eq!(
+            (loaded.color, loaded.priority, loaded.is_milestone),
+            (saved.color, saved.priority, saved.is_milestone),
+        );
+    }
+    let (loaded, saved) = (&loaded.dependencies[0], &project.dependencies[0]);
+    assert_eq!(
+        (loaded.from_task, loaded.to_task, loaded.kind, loaded.lag_days),
+        (saved.from_task, saved.to_task, saved.kind, saved.lag_days),
+    );
+}
+
+#[test]
+fn any_colour_survives_repeated_saves() {
+    let mut seed = 0x2545_f491_u32;
+    for _ in 0..10_000 {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        let color = Rgba::from(seed.to_le_bytes());
+        let mut json = serde_json::to_string(&color).unwrap();
+        for _ in 0..3 {
+            let loaded: Rgba = serde_json::from_str(&json).unwrap();
+            assert_eq!(loaded, color);
+            json = serde_json::to_string(&loaded).unwrap();
+        }
+    }
+}
+
+/// Takes a few bytes, then reports a full disk.
+struct FullDisk(usize);
+
+impl Write for FullDisk {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.0 == 0 {
+            return Err(io::Error::other("no space left on device"));
+        }
+        let n = buf.len().min(self.0);
+        self.0 -= n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl SaveSink for FullDisk {
+    fn sync(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn a_failed_save_leaves_the_original_file() {
+    let dir = std::env::temp_dir().join(format!("gantt-save-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("launch.gantt.json");
+    std::fs::write(&path, "original").unwrap();
+
+    let project = Project::new("Launch");
+    let err = save_project_with(&project, &path, |_| Ok(FullDisk(64))).unwrap_err();
+    assert!(err.message.contains("no space left"));
+    assert!(err.temp_file.is_none());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "original");
+
+    // A complete copy that can't replace the target (here a directory) is kept.
+    let blocked = dir.join("blocked");
+    std::fs::create_dir_all(&blocked).unwrap();
+    let err = gantt_core::save_project(&project, &blocked).unwrap_err();
+    assert_eq!(err.temp_file, Some(temp_path(&blocked)));
+    assert!(gantt_core::load_project(&temp_path(&blocked)).is_ok());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,128 @@
+//! Which tasks are hidden, grouped, flagged or clashing with an absence.
+
+mod common;
+
+use chrono::Duration;
+use common::{at, date, day};
+use gantt_core::absence::{absences_overlapping, Absence};
+use gantt_core::grouping::{group_tasks, GroupBy, GroupKey, Status};
+use gantt_core::highlight::{due_state, DueState, Highlight, HighlightRules};
+use gantt_core::risk::RiskLevel;
+use gantt_core::task::{hidden_by_risk, hidden_completed, hidden_tentative, CompletedTasks};
+use gantt_core::Task;
+
+/// A phase over Spike (4th–8th) and Build (11th–15th).
+fn phase() -> [Task; 3] {
+    let phase = Task::new("Phase", day(4), day(15));
+    let mut spike = Task::new("Spike", day(4), day(8));
+    let mut build = Task::new("Build", day(11), day(15));
+    spike.parent_id = Some(phase.id);
+    build.parent_id = Some(phase.id);
+    [phase, spike, build]
+}
+
+#[test]
+fn hiding_completed_keeps_a_parent_with_unfinished_children() {
+    let mut tasks = phase();
+    tasks[1].progress = 1.0;
+    // A phase marked done by hand stays while a child is unfinished.
+    tasks[0].progress = 1.0;
+
+    let hidden = hidden_completed(&tasks, CompletedTasks::Hide);
+    assert!(hidden.contains(&tasks[1].id));
+    assert_eq!(hidden.len(), 1);
+    assert!(hidden_completed(&tasks, CompletedTasks::Strike).is_empty());
+}
+
+#[test]
+fn hiding_tentative_keeps_a_parent_with_confirmed_children() {
+    let mut tasks = phase();
+    tasks[0].tentative = true;
+    tasks[1].tentative = true;
+
+    let hidden = hidden_tentative(&tasks, true);
+    assert_eq!(hidden.len(), 1);
+    assert!(hidden.contains(&tasks[1].id));
+    assert!(hidden_tentative(&tasks, false).is_empty());
+}
+
+#[test]
+fn filtering_by_risk_keeps_the_parents_of_matching_tasks() {
+    let mut tasks = phase();
+    tasks[1].risk = Some(RiskLevel::High);
+
+    let hidden = hidden_by_risk(&tasks, Some(RiskLevel::High));
+    assert_eq!(hidden.len(), 1);
+    assert!(hidden.contains(&tasks[2].id));
+    assert!(hidden_by_risk(&tasks, None).is_empty());
+}
+
+#[test]
+fn grouping_by_status_and_moving_between_groups() {
+    let mut tasks = vec![
+        Task::new("Design", day(4), day(8)),
+        Task::new("Build", day(11), day(15)),
+        Task::new("Test", day(18), day(20)),
+    ];
+    tasks[0].progress = 1.0;
+    tasks[1].progress = 0.3;
+
+    let groups = group_tasks(&tasks, GroupBy::Status);
+    let keys: Vec<_> = groups.iter().map(|g| g.key.clone()).collect();
+    assert_eq!(
+        keys,
+        [GroupKey::Status(Status::NotStarted), GroupKey::Status(Status::InProgress), GroupKey::Status(Status::Done)]
+    );
+
+    // Moving "Test" into the done group finishes it; the hierarchy is untouched.
+    GroupKey::Status(Status::Done).apply(&mut tasks[2]);
+    assert_eq!(tasks[2].progress, 1.0);
+    assert_eq!(group_tasks(&tasks, GroupBy::Status)[1].tasks, [0, 2]);
+}
+
+#[test]
+fn due_state_runs_from_due_soon_to_late() {
+    let mut task = Task::new("Build", day(4), day(8));
+    let window = Duration::days(2);
+    assert_eq!(due_state(&task, day(5), window), None);
+    assert_eq!(due_state(&task, day(6), window), Some(DueState::DueSoon));
+    assert_eq!(due_state(&task, day(8), window), Some(DueState::DueSoon));
+    assert_eq!(due_state(&task, day(9), window), Some(DueState::Late));
+    task.progress = 1.0;
+    assert_eq!(due_state(&task, day(9), window), Some(DueState::Done));
+}
+
+#[test]
+fn highlight_flags_tasks_behind_or_overdue() {
+    let rules = HighlightRules::default();
+    let mut task = Task::new("Build", day(4), day(8));
+    task.progress = 0.25;
+
+    assert_eq!(rules.highlight(&task, day(4)), None);
+    assert_eq!(rules.highlight(&task, day(6)), Some(Highlight::AtRisk));
+    assert_eq!(rules.highlight(&task, day(9)), Some(Highlight::Overdue));
+
+    // Far enough along, or switched off, it is left alone.
+    task.progress = 0.5;
+    assert_eq!(rules.highlight(&task, day(6)), None);
+    let rules = HighlightRules { overdue: false, ..rules };
+    assert_eq!(rules.highlight(&task, day(9)), None);
+}
+
+#[test]
+fn absences_clash_only_with_their_assignee() {
+    let mut away = Absence::new("Ana", date(11), date(15));
+    away.note = "Vacation".to_string();
+    let absences = vec![away, Absence::new("Ben", date(4), date(8))];
+
+    let mut build = Task::new("Build", day(7), day(12));
+    build.assignee = Some(" Ana ".to_string());
+    let hits: Vec<_> = absences_overlapping(&build, &absences).collect();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].describe(), "Vacation, 2024-03-11 – 2024-03-15");
+
+    // Ending the morning the absence starts is fine; unassigned tasks never clash.
+    let design = Task { assignee: Some("Ana".to_string()), ..Task::new("Design", day(4), at(11, 0)) };
+    assert_eq!(absences_overlapping(&design, &absences).count(), 0);
+    assert_eq!(absences_overlapping(&Task::new("Test", day(4), day(20)), &absences).count(), 0);
+}
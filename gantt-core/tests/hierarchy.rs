@@ -0,0 +1,298 @@
+//! Parents span their subtasks exactly, whichever way the tasks were
+//! changed, and the hierarchy survives loops, orphans and deep nesting.
+
+mod common;
+
+use common::day;
+use gantt_core::task::{ChecklistItem, ProgressPolicy, ShiftAmount};
+use gantt_core::{Project, Task, UndoHistory};
+use uuid::Uuid;
+
+fn span(project: &Project, id: Uuid) -> (chrono::NaiveDateTime, chrono::NaiveDateTime) {
+    project.task(id).map(|t| (t.start, t.end)).unwrap()
+}
+
+/// Phase over Design (4th–8th) and Build (11th–15th), with an empty
+/// Wrap-up beside it; both parents start out with stale dates.
+struct Plan {
+    project: Project,
+    phase: Uuid,
+    wrap_up: Uuid,
+    design: Uuid,
+    build: Uuid,
+}
+
+fn plan() -> Plan {
+    let phase = Task::new("Phase", day(1), day(2));
+    let wrap_up = Task::new("Wrap-up", day(1), day(2));
+    let mut design = Task::new("Design", day(4), day(8));
+    let mut build = Task::new("Build", day(11), day(15));
+    design.parent_id = Some(phase.id);
+    build.parent_id = Some(phase.id);
+    let ids = (phase.id, wrap_up.id, design.id, build.id);
+    let mut project = Project::new("Launch");
+    for task in [phase, wrap_up, design, build] {
+        project.add_task(task);
+    }
+    Plan { project, phase: ids.0, wrap_up: ids.1, design: ids.2, build: ids.3 }
+}
+
+#[test]
+fn import_rolls_up_every_parent() {
+    let Plan { mut project, phase, .. } = plan();
+    project.tasks_changed();
+    project.touch();
+    assert!(project.uncontained_parents().is_empty());
+    assert_eq!(span(&project, phase), (day(4), day(15)));
+}
+
+#[test]
+fn editor_reparenting_rolls_up_both_parents() {
+    let Plan { mut project, phase, wrap_up, build, .. } = plan();
+    project.touch();
+    let mut edited = project.task(build).unwrap().clone();
+    edited.parent_id = Some(wrap_up);
+    *project.task_mut(build).unwrap() = edited;
+    project.touch();
+    assert!(project.uncontained_parents().is_empty());
+    assert_eq!(span(&project, phase), (day(4), day(8)));
+    assert_eq!(span(&project, wrap_up), (day(11), day(15)));
+}
+
+#[test]
+fn drag_reported_with_task_changed_rolls_up() {
+    let Plan { mut project, phase, design, .. } = plan();
+    project.touch();
+    let i = project.task_index(design).unwrap();
+    project.tasks[i].start = day(5);
+    project.task_changed(design);
+    project.touch();
+    assert!(project.uncontained_parents().is_empty());
+    assert_eq!(span(&project, phase), (day(5), day(15)));
+}
+
+#[test]
+fn undo_restore_rolls_up_every_parent() {
+    let Plan { mut project, phase, design, .. } = plan();
+    project.touch();
+    let mut history = UndoHistory::new();
+    history.push("Move task", &project.tasks, &project.dependencies);
+    project.task_mut(design).unwrap().start = day(6);
+    project.touch();
+    assert_eq!(span(&project, phase), (day(6), day(15)));
+
+    let snapshot = history.undo(&project.tasks, &project.dependencies).unwrap();
+    project.set_tasks(snapshot.tasks);
+    project.touch();
+    assert!(project.uncontained_parents().is_empty());
+    assert_eq!(span(&project, phase), (day(4), day(15)));
+}
+
+#[test]
+fn shifts_through_the_project_roll_up_as_they_go() {
+    let Plan { mut project, phase, design, .. } = plan();
+    project.touch();
+    project.shift_tasks(&[design], ShiftAmount::Days(-2));
+    assert_eq!(span(&project, phase).0, day(2));
+    assert!(project.uncontained_parents().is_empty());
+}
+
+#[test]
+fn uncontained_parents_reports_stale_parents_until_touched() {
+    let Plan { mut project, phase, build, .. } = plan();
+    project.touch();
+    assert!(project.uncontained_parents().is_empty());
+
+    // Pasted data can leave a subtask outside its parent...
+    project.task_mut(build).unwrap().end = day(20);
+    assert_eq!(project.uncontained_parents(), vec![phase]);
+    // ...until the change is recorded.
+    project.touch();
+    assert!(project.uncontained_parents().is_empty());
+    assert_eq!(span(&project, phase).1, day(20));
+
+    // A parent left wider than its subtasks is pulled in as well.
+    project.task_mut(phase).unwrap().start = day(1);
+    project.touch();
+    assert_eq!(span(&project, phase).0, day(4));
+}
+
+#[test]
+fn milestones_widen_a_parent_but_only_count_when_all_children_are_milestones() {
+    let mut project = Project::new("Launch");
+    let phase = Task::new("Phase", day(1), day(2));
+    let mut design = Task::new("Design", day(4), day(8));
+    let mut build = Task::new("Build", day(11), day(15));
+    let mut review = Task::new_milestone("Review", day(18));
+    let mut gates = Task::new("Gates", day(1), day(2));
+    let mut alpha = Task::new_milestone("Alpha", day(20));
+    let mut beta = Task::new_milestone("Beta", day(22));
+    design.progress = 1.0;
+    build.progress = 0.5;
+    alpha.progress = 1.0;
+    for child in [&mut design, &mut build, &mut review, &mut gates] {
+        child.parent_id = Some(phase.id);
+    }
+    alpha.parent_id = Some(gates.id);
+    beta.parent_id = Some(gates.id);
+    let (phase_id, gates_id) = (phase.id, gates.id);
+    for task in [phase, design, build, review, gates, alpha, beta] {
+        project.add_task(task);
+    }
+
+    project.recalculate_parent_dates();
+    let gates = project.task(gates_id).unwrap();
+    assert_eq!(gates.progress, 0.5);
+    assert_eq!((gates.start, gates.end), (day(20), day(22)));
+    let phase = project.task(phase_id).unwrap();
+    assert_eq!(phase.progress, (1.0 + 0.5 + 0.5) / 3.0);
+    assert_eq!((phase.start, phase.end), (day(4), day(22)));
+}
+
+#[test]
+fn recalculating_one_branch_matches_recalculating_every_parent() {
+    // Release > Phase > Stage > (Design, Build), and Docs under Release.
+    let mut project = Project::new("Launch");
+    let release = Task::new("Release", day(1), day(2));
+    let mut phase = Task::new("Phase", day(1), day(2));
+    let mut stage = Task::new("Stage", day(1), day(2));
+    let mut design = Task::new("Design", day(4), day(8));
+    let mut build = Task::new("Build", day(9), day(12));
+    let mut docs = Task::new("Docs", day(10), day(14));
+    phase.parent_id = Some(release.id);
+    stage.parent_id = Some(phase.id);
+    design.parent_id = Some(stage.id);
+    build.parent_id = Some(stage.id);
+    docs.parent_id = Some(release.id);
+    design.progress = 1.0;
+    let (release_id, build_id) = (release.id, build.id);
+    for task in [release, phase, stage, design, build, docs] {
+        project.add_task(task);
+    }
+    project.recalculate_parent_dates();
+
+    let build = project.task_mut(build_id).unwrap();
+    build.end = day(20);
+    build.progress = 0.5;
+    let mut full = project.clone();
+    project.recalculate_parents_of(build_id);
+    full.recalculate_parent_dates();
+
+    assert_eq!(span(&project, release_id).1, day(20));
+    let rolled = |p: &Project| p.tasks.iter().map(|t| (t.id, t.start, t.end, t.progress)).collect::<Vec<_>>();
+    assert_eq!(rolled(&project), rolled(&full));
+}
+
+#[test]
+fn progress_policy_takes_checklists_and_rolls_them_up() {
+    let mut project = Project::new("Launch");
+    let mut phase = Task::new("Phase", day(4), day(15));
+    let mut design = Task::new("Design", day(4), day(8));
+    let mut build = Task::new("Build", day(11), day(15));
+    phase.progress = 0.9;
+    design.progress = 0.5;
+    design.parent_id = Some(phase.id);
+    build.parent_id = Some(phase.id);
+    build.checklist = vec![ChecklistItem::new("Frame"), ChecklistItem::new("Roof")];
+    build.checklist[0].done = true;
+    let (phase_id, build_id) = (phase.id, build.id);
+    for task in [phase, design, build] {
+        project.add_task(task);
+    }
+
+    // By hand, the phase keeps what was entered.
+    project.progress_policy = ProgressPolicy::Manual;
+    assert_eq!(project.apply_progress_policy(), 0);
+    assert_eq!(project.task(phase_id).unwrap().progress, 0.9);
+
+    // Build takes half its checklist, the phase the average below it.
+    project.progress_policy = ProgressPolicy::Checklist;
+    assert_eq!(project.apply_progress_policy(), 2);
+    assert_eq!(project.task(build_id).unwrap().progress, 0.5);
+    assert_eq!(project.task(phase_id).unwrap().progress, 0.5);
+}
+
+/// A project of `n` unrelated tasks named by their index, and their ids.
+fn flat(n: usize) -> (Project, Vec<Uuid>) {
+    let tasks: Vec<Task> = (0..n).map(|i| Task::new(format!("Task {}", i), day(4), day(8))).collect();
+    let ids = tasks.iter().map(|t| t.id).collect();
+    let mut project = Project::new("Launch");
+    project.set_tasks(tasks);
+    (project, ids)
+}
+
+fn parent(project: &Project, i: usize) -> Option<Uuid> {
+    project.tasks[i].parent_id
+}
+
+#[test]
+fn breaking_cycles_clears_a_task_parented_to_itself() {
+    let (mut project, id) = flat(2);
+    project.tasks[0].parent_id = Some(id[0]);
+    assert!(!project.can_set_parent(id[0], id[0]));
+    assert_eq!(project.break_parent_cycles(), [id[0]]);
+    assert_eq!(parent(&project, 0), None);
+}
+
+#[test]
+fn breaking_cycles_cuts_a_two_task_loop_once() {
+    let (mut project, id) = flat(2);
+    project.tasks[0].parent_id = Some(id[1]);
+    project.tasks[1].parent_id = Some(id[0]);
+    assert_eq!(project.break_parent_cycles(), [id[1]]);
+    assert_eq!((parent(&project, 0), parent(&project, 1)), (Some(id[1]), None));
+    assert!(project.break_parent_cycles().is_empty());
+}
+
+#[test]
+fn breaking_cycles_cuts_a_longer_loop_and_keeps_the_rest_of_the_chain() {
+    let (mut project, id) = flat(4);
+    // 0 under 1 under 2 under 0, and 3 hanging off the loop.
+    for (child, p) in [(0, 1), (1, 2), (2, 0), (3, 0)] {
+        project.tasks[child].parent_id = Some(id[p]);
+    }
+    assert!(project.is_ancestor_of(id[2], id[0]));
+
+    assert_eq!(project.break_parent_cycles(), [id[2]]);
+    assert_eq!(project.ancestor_ids(id[3]), [id[0], id[1], id[2]]);
+    // With the loop gone, closing it again is refused.
+    assert!(!project.can_set_parent(id[2], id[0]));
+    assert!(project.can_set_parent(id[3], id[2]));
+}
+
+#[test]
+fn sorting_nests_deep_chains_and_places_orphans_and_loops() {
+    let task = |name| Task::new(name, day(4), day(8));
+    // A > B > C > D listed deepest first, Orphan under a task that is
+    // gone, and X and Y each other's parent.
+    let (a, mut b, mut c, mut d) = (task("A"), task("B"), task("C"), task("D"));
+    let (mut orphan, mut x, mut y) = (task("Orphan"), task("X"), task("Y"));
+    b.parent_id = Some(a.id);
+    c.parent_id = Some(b.id);
+    d.parent_id = Some(c.id);
+    orphan.parent_id = Some(Uuid::new_v4());
+    x.parent_id = Some(y.id);
+    y.parent_id = Some(x.id);
+    let mut project = Project::new("Launch");
+    for task in [d, orphan, c, x, b, y, a] {
+        project.add_task(task);
+    }
+    let ids = |p: &Project| p.tasks.iter().map(|t| t.id).collect::<Vec<_>>();
+    let names = |p: &Project| p.tasks.iter().map(|t| t.name.clone()).collect::<Vec<_>>();
+    let mut before = ids(&project);
+
+    project.sort_tasks_grouped();
+    // Roots in list order with their subtrees, then the loop from X.
+    assert_eq!(names(&project), ["Orphan", "A", "B", "C", "D", "X", "Y"]);
+    // The same tasks, each once, and still found by id.
+    let mut after = ids(&project);
+    before.sort();
+    after.sort();
+    assert_eq!(before, after);
+    let sorted = ids(&project);
+    assert!(sorted.iter().all(|&id| project.task(id).is_some_and(|t| t.id == id)));
+
+    // Sorting again changes nothing.
+    project.sort_tasks_grouped();
+    assert_eq!(ids(&project), sorted);
+}
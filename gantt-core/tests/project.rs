@@ -0,0 +1,192 @@
+//! Project-wide bookkeeping: undo, comparison, slugs, reports and entry.
+
+mod common;
+
+use chrono::{Duration, NaiveDate};
+use common::{date, day};
+use gantt_core::compare::{compare, DiffField};
+use gantt_core::date_preset::{DatePreset, PresetAnchor};
+use gantt_core::quick_add::parse_quick_add;
+use gantt_core::risk::RiskLevel;
+use gantt_core::task::{TaskPriority, TimeEntry};
+use gantt_core::task_progress::TaskProgressLog;
+use gantt_core::{Project, Task, UndoHistory};
+use uuid::Uuid;
+
+#[test]
+fn undo_and_redo_walk_the_history() {
+    let mut history = UndoHistory::new();
+    let mut tasks = vec![Task::new("Design", day(4), day(8))];
+
+    history.push("Rename task", &tasks, &[]);
+    tasks[0].name = "Sketch".to_string();
+    assert_eq!(history.undo_label(), Some("Rename task"));
+
+    let before = history.undo(&tasks, &[]).unwrap();
+    assert_eq!(before.tasks[0].name, "Design");
+    assert_eq!(history.redo_label(), Some("Rename task"));
+    let after = history.redo(&before.tasks, &[]).unwrap();
+    assert_eq!(after.tasks[0].name, "Sketch");
+
+    // A new change clears what could be redone.
+    history.undo(&after.tasks, &[]);
+    history.push("Delete task", &before.tasks, &[]);
+    assert!(!history.can_redo());
+    assert_eq!(history.undo_labels().collect::<Vec<_>>(), ["Delete task"]);
+
+    // A burst of the same change undoes in one step.
+    let window = std::time::Duration::from_secs(60);
+    assert!(history.push_coalescing("Nudge", window, &tasks, &[]));
+    assert!(!history.push_coalescing("Nudge", window, &tasks, &[]));
+    assert_eq!(history.undo_labels().collect::<Vec<_>>(), ["Nudge", "Delete task"]);
+}
+
+#[test]
+fn compare_lists_added_removed_and_modified_tasks() {
+    let mut old = Project::new("Launch");
+    let design = Task::new("Design", day(4), day(8));
+    let build = Task::new("Build", day(11), day(15));
+    let (design_id, build_id) = (design.id, build.id);
+    old.add_task(design);
+    old.add_task(build);
+
+    let mut new = old.clone();
+    new.task_mut(design_id).unwrap().progress = 0.5;
+    new.remove_tasks(|t| t.id == build_id);
+    new.add_task(Task::new("Test", day(18), day(20)));
+
+    let diff = compare(&old, &new);
+    assert_eq!(diff.added[0].name, "Test");
+    assert_eq!(diff.removed[0].id, build_id);
+    let progress = &diff.modified[0].changes[0];
+    assert_eq!((progress.field, progress.old.as_str(), progress.new.as_str()), (DiffField::Progress, "0%", "50%"));
+    assert_eq!(diff.len(), 3);
+}
+
+#[test]
+fn slugs_stay_unique_within_a_project() {
+    let mut project = Project::new("Launch");
+    let mut design = Task::new("API Design", day(4), day(8));
+    design.slug = Some("api-design".to_string());
+    project.add_task(design.clone());
+
+    let review = Task::new("API design", day(11), day(15));
+    assert_eq!(project.suggest_slug(&review.name, review.id).as_deref(), Some("api-design-2"));
+    // A task's own slug doesn't count as taken.
+    assert_eq!(project.suggest_slug(&design.name, design.id).as_deref(), Some("api-design"));
+
+    // Added with a slug in use, a task gets the next free one.
+    let mut copy = Task::new("API Design", day(4), day(8));
+    copy.slug = Some("api-design".to_string());
+    project.add_task(copy);
+    assert!(project.task_by_slug("api-design-2").is_some());
+}
+
+#[test]
+fn earned_value_compares_plan_progress_and_time_spent() {
+    let mut project = Project::new("Launch");
+    let mut design = Task::new("Design", day(4), day(8));
+    design.estimate_hours = Some(20.0);
+    design.progress = 1.0;
+    design.time_entries.push(TimeEntry { start: day(4), end: Some(day(4) + Duration::hours(25)) });
+    let mut build = Task::new("Build", day(11), day(15));
+    build.estimate_hours = Some(40.0);
+    build.progress = 0.25;
+    project.add_task(design);
+    project.add_task(build);
+    project.add_task(Task::new("Test", day(18), day(20)));
+
+    // Halfway through "Build": 20 + 20 hours planned, 20 + 10 earned.
+    let ev = project.earned_value(day(13));
+    assert_eq!((ev.planned, ev.earned, ev.actual), (40.0, 30.0, 25.0));
+    assert_eq!(ev.spi(), Some(0.75));
+    assert_eq!(ev.cpi(), Some(1.2));
+    assert_eq!(ev.excluded, 1);
+    assert_eq!(ev.summary(), "SPI 0.75 · CPI 1.20");
+}
+
+#[test]
+fn risk_breakdown_weighs_hours_and_counts_open_tasks() {
+    let mut project = Project::new("Launch");
+    let mut design = Task::new("Design", day(4), day(5));
+    design.risk = Some(RiskLevel::High);
+    design.progress = 0.5;
+    let mut build = Task::new("Build", day(5), day(6));
+    build.risk = Some(RiskLevel::Low);
+    project.add_task(design);
+    project.add_task(build);
+    project.add_task(Task::new("Test", day(6), day(7)));
+
+    let risk = project.risk_breakdown();
+    assert_eq!(risk.hours(Some(RiskLevel::High)), 12.0);
+    assert_eq!(risk.hours(Some(RiskLevel::Low)), 24.0);
+    assert_eq!(risk.hours(None), 24.0);
+    assert_eq!(risk.share(Some(RiskLevel::High)), 0.2);
+    assert_eq!(risk.open, [1, 0, 1]);
+}
+
+#[test]
+fn progress_log_keeps_one_sample_per_change() {
+    let task = Uuid::new_v4();
+    let mut log = TaskProgressLog::default();
+    log.record(task, date(4), 0.25);
+    log.record(task, date(4), 0.5); // same day: replaces
+    log.record(task, date(5), 0.5); // unchanged: skipped
+    log.record(task, date(8), 0.8);
+
+    let progress: Vec<f32> = log.for_task(task).map(|s| s.progress).collect();
+    assert_eq!(progress, [0.5, 0.8]);
+    assert_eq!(log.progress_on(task, date(6)), Some(0.5));
+    assert_eq!(log.progress_on(task, date(3)), None);
+}
+
+#[test]
+fn date_presets_count_from_their_anchor() {
+    // Friday 8 March.
+    let (today, current, start) = (date(8), date(12), date(4));
+    let pick = |anchor, days, skip| DatePreset::new(anchor, days).date(today, current, start, skip);
+    assert_eq!(pick(PresetAnchor::Today, 1, false), Some(date(9)));
+    assert_eq!(pick(PresetAnchor::Today, 1, true), Some(date(11)));
+    assert_eq!(pick(PresetAnchor::NextMonday, 0, true), Some(date(11)));
+    assert_eq!(pick(PresetAnchor::Current, 7, true), Some(date(19)));
+    assert_eq!(pick(PresetAnchor::Start, 5, false), Some(date(9)));
+    assert_eq!(pick(PresetAnchor::Start, 5, true), Some(date(11)));
+    // As a project file could have it.
+    assert_eq!(pick(PresetAnchor::Today, 99_999_999, false), None);
+}
+
+#[test]
+fn quick_add_reads_dates_durations_and_tags() {
+    let ymd = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    // Wednesday 6 March.
+    let today = date(6);
+    let phase = Task::new("Phase 1", day(4), day(8));
+    let tasks = [phase.clone()];
+    let parse = |line| parse_quick_add(line, today, &tasks);
+
+    // Absolute dates, weekdays (today counts), `next …` and day-month.
+    assert_eq!(parse("Ship 2024-05-02").start, Some(ymd(2024, 5, 2)));
+    assert_eq!(parse("Ship fri").start, Some(date(8)));
+    assert_eq!(parse("Ship wed").start, Some(date(6)));
+    assert_eq!(parse("Ship next wed").start, Some(date(13)));
+    assert_eq!(parse("Ship next week").start, Some(date(11)));
+    assert_eq!(parse("Ship 12 aug").start, Some(ymd(2024, 8, 12)));
+    assert_eq!(parse("Ship feb 1").start, Some(ymd(2025, 2, 1)));
+
+    // Durations, priority, tags and the parent.
+    let entry = parse("Design review tue for 2w p:high #ui #ui /phase 1 now");
+    assert_eq!(entry.start, Some(date(12)));
+    assert_eq!(entry.duration_days, Some(14));
+    assert_eq!(entry.priority, Some(TaskPriority::High));
+    assert_eq!(entry.tags, vec!["ui".to_string()]);
+    assert_eq!(entry.parent, Some((phase.id, "Phase 1".to_string())));
+    // Everything else, in order, is the name.
+    assert_eq!(entry.name, "Design review now");
+    assert_eq!(entry.date_range(today), (date(12), date(25)));
+
+    // A length too long to be one is part of the name.
+    let entry = parse("x for 99999999d");
+    assert_eq!(entry.duration_days, None);
+    assert_eq!(entry.name, "x for 99999999d");
+    assert_eq!(parse("p:urgent #").name, "p:urgent #");
+}
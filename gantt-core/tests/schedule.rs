@@ -0,0 +1,145 @@
+//! Dependencies, their violations and the scheduling built on them.
+
+mod common;
+
+use chrono::Duration;
+use common::{at, date, day};
+use gantt_core::graph::DependencyGraph;
+use gantt_core::schedule::chain_children;
+use gantt_core::task::{Dependency, DependencyKind};
+use gantt_core::validation::{count_tasks_with_issues, validate_task, ValidationFix};
+use gantt_core::{Project, Task};
+use uuid::Uuid;
+
+fn link(from_task: Uuid, to_task: Uuid, lag_days: i64) -> Dependency {
+    Dependency { from_task, to_task, kind: DependencyKind::FinishToStart, lag_days }
+}
+
+#[test]
+fn graph_orders_and_walks_the_links() {
+    let [design, build, test] = ["Design", "Build", "Test"].map(|name| Task::new(name, day(4), day(8)).id);
+    let graph = DependencyGraph::new(&[link(build, test, 0), link(design, build, 0)]);
+
+    assert_eq!(graph.edge_count(), 2);
+    assert_eq!(graph.predecessors(build)[0].from_task, design);
+    assert_eq!(graph.successors(build)[0].to_task, test);
+    assert!(graph.has_link(design, build) && !graph.has_link(build, design));
+    assert_eq!(graph.topo_order(), [design, build, test]);
+    assert_eq!(graph.upstream(test).len(), 2);
+    assert!(graph.downstream(test).is_empty());
+}
+
+#[test]
+fn graph_finds_cycles_before_and_after_they_close() {
+    let [design, build, test] = ["Design", "Build", "Test"].map(|name| Task::new(name, day(4), day(8)).id);
+    let graph = DependencyGraph::new(&[link(design, build, 0), link(build, test, 0)]);
+    assert!(graph.would_create_cycle(test, design));
+    assert!(!graph.has_cycle());
+
+    let cyclic = DependencyGraph::new(&[link(design, build, 0), link(build, test, 0), link(test, design, 0)]);
+    assert!(cyclic.has_cycle());
+    assert_eq!(cyclic.cycle_groups().len(), 3);
+    assert!(cyclic.is_cyclic_edge(&link(test, design, 0)));
+}
+
+#[test]
+fn dependency_violations_follow_recorded_changes() {
+    let mut project = Project::new("Launch");
+    let design = Task::new("Design", day(4), day(8));
+    let build = Task::new("Build", day(7), day(12));
+    let test = Task::new("Test", day(12), day(14));
+    let (design_id, build_id, test_id) = (design.id, build.id, test.id);
+    project.set_tasks(vec![design, build, test]);
+    project.set_dependencies(vec![link(design_id, build_id, 0), link(build_id, test_id, 1)]);
+
+    // Build starts a day before Design ends; Test needs a day's gap after Build.
+    let violations = project.dependency_violations();
+    assert_eq!(violations.len(), 2);
+    assert_eq!(violations[0].early_by, Duration::days(1));
+    assert_eq!(violations[1].describe("Build"), "Starts before 'Build' allows (FS +1d, 2024-03-13 09:00)");
+
+    // Moving Test is picked up once the change is recorded.
+    project.task_mut(test_id).unwrap().start = day(13);
+    project.touch();
+    assert_eq!(project.dependency_violations().len(), 1);
+}
+
+#[test]
+fn validation_offers_a_fix_that_clears_the_issue() {
+    let design = Task::new("Design", day(4), day(8));
+    let mut build = Task::new("Build", day(6), day(12));
+    let graph = DependencyGraph::new(&[link(design.id, build.id, 0)]);
+    let mut tasks = vec![design, build.clone()];
+
+    // Build starts before Design finishes; the fix moves it to start then.
+    let issues = validate_task(&build, &tasks, &graph, &[]);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].message, "Starts before 'Design' allows (FS, 2024-03-08 09:00)");
+    assert_eq!(issues[0].fix, Some(ValidationFix::MoveStart(day(8))));
+    assert_eq!(count_tasks_with_issues(&tasks, &graph, &[]), 1);
+
+    issues[0].fix.unwrap().apply(&mut build);
+    assert_eq!((build.start, build.end), (day(8), day(14)));
+    tasks[1] = build.clone();
+    assert!(validate_task(&build, &tasks, &graph, &[]).is_empty());
+}
+
+#[test]
+fn validation_offers_no_fix_for_a_locked_task() {
+    let mut build = Task::new("Build", day(6), day(12));
+    let tasks = vec![build.clone()];
+    let graph = DependencyGraph::new(&[]);
+
+    // A task ending before it starts is offered a one-day span, unless locked.
+    build.end = day(1);
+    assert_eq!(validate_task(&build, &tasks, &graph, &[])[0].message, "Ends before it starts");
+    build.locked = true;
+    assert_eq!(validate_task(&build, &tasks, &graph, &[])[0].fix, None);
+}
+
+#[test]
+fn chaining_children_runs_them_back_to_back_over_working_days() {
+    let mut project = Project::new("Launch");
+    let phase = Task::new("Phase", at(4, 9), at(14, 17));
+    let mut design = Task::new("Design", at(4, 9), at(6, 17));
+    let mut build = Task::new("Build", at(4, 9), at(5, 17));
+    let mut test = Task::new("Test", at(13, 9), at(14, 17));
+    for child in [&mut design, &mut build, &mut test] {
+        child.parent_id = Some(phase.id);
+    }
+    let (phase_id, design_id, build_id) = (phase.id, design.id, build.id);
+    for task in [phase, design, build, test] {
+        project.add_task(task);
+    }
+
+    // Build follows Design on Thursday; Test follows Build over the weekend.
+    let chain = chain_children(&project, phase_id, 0);
+    let dates: Vec<_> = chain.moves.iter().map(|m| (m.new_start, m.new_end)).collect();
+    assert_eq!(dates, [(at(7, 9), at(8, 17)), (at(11, 9), at(12, 17))]);
+    assert_eq!(chain.links.len(), 2);
+    assert_eq!((chain.links[0].from_task, chain.links[0].to_task), (design_id, build_id));
+
+    // A day's gap between each.
+    let chain = chain_children(&project, phase_id, 1);
+    assert_eq!(chain.moves[0].new_start, at(8, 9));
+}
+
+#[test]
+fn forecast_projects_the_finish_from_progress_so_far() {
+    let mut project = Project::new("Launch");
+    let mut design = Task::new("Design", day(4), day(9));
+    design.progress = 0.5;
+    project.add_task(design);
+    project.add_task(Task::new("Build", day(9), day(14)));
+
+    // Mon 4th to Thu 14th: two weeks less the last Friday.
+    let forecast = project.forecast(day(4)).unwrap();
+    assert_eq!((forecast.start, forecast.end, forecast.working_days), (day(4), day(14), 9));
+    assert_eq!(forecast.finish, None);
+
+    // A quarter done after five days: twenty days in all, four behind plan.
+    let forecast = project.forecast(day(9)).unwrap();
+    assert_eq!(forecast.finish, Some(day(24)));
+    assert_eq!(forecast.days_late(date(20)), Some(4));
+    assert_eq!(forecast.days_late(date(31)), None);
+}
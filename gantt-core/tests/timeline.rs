@@ -0,0 +1,108 @@
+//! The timeline maps times to pixels sensibly at every zoom and for every
+//! range it is asked to fit.
+
+mod common;
+
+use chrono::{Duration, NaiveTime};
+use common::{at, date, day};
+use gantt_core::project::TaskDefaults;
+use gantt_core::{Task, TimelineScale, TimelineViewport};
+
+#[test]
+fn pixels_and_times_convert_both_ways() {
+    let mut viewport = TimelineViewport::new(at(1, 0), at(31, 0));
+    viewport.set_pixels_per_day(20.0);
+    assert_eq!(viewport.datetime_to_x(at(3, 0)), 40.0);
+    assert_eq!(viewport.x_to_datetime(40.0), at(3, 0));
+    assert_eq!(viewport.total_width(), 600.0);
+
+    // Panning keeps the range's length.
+    viewport.pan(Duration::days(7));
+    assert_eq!((viewport.start, viewport.end), (at(8, 0), at(31, 0) + Duration::days(7)));
+}
+
+#[test]
+fn zoom_switches_scale_and_stops_at_the_limits() {
+    let mut viewport = TimelineViewport::new(at(1, 0), at(31, 0));
+    for _ in 0..20 {
+        viewport.zoom_in();
+    }
+    assert_eq!(viewport.scale, TimelineScale::Hours);
+    assert_eq!(viewport.snap_unit(), Duration::hours(1));
+    viewport.set_pixels_per_day(f32::NAN);
+    assert!(viewport.pixels_per_day.is_finite());
+    for _ in 0..40 {
+        viewport.zoom_out();
+    }
+    assert_eq!(viewport.scale, TimelineScale::Months);
+    assert!(viewport.pixels_per_day >= 1.0);
+}
+
+#[test]
+fn a_backwards_range_becomes_a_single_day() {
+    let viewport = TimelineViewport::new(day(10), day(5));
+    assert_eq!((viewport.start, viewport.end), (day(10), day(11)));
+    assert!(viewport.total_width() > 0.0);
+}
+
+#[test]
+fn fitting_a_range_pads_it_and_survives_bad_input() {
+    let mut viewport = TimelineViewport::new(day(1), day(31));
+    viewport.fit_to_range(day(10), day(30), 1000.0);
+    assert_eq!(viewport.start, day(9));
+
+    // A milestone gets a two-week window around it.
+    viewport.fit_to_range(day(15), day(15), 1000.0);
+    assert_eq!(viewport.start, day(8));
+    assert!(viewport.end >= day(22));
+
+    // Bounds given the wrong way round, or no room to draw in, still
+    // leave a usable range.
+    viewport.fit_to_range(day(30), day(10), 1000.0);
+    assert_eq!(viewport.start, day(9));
+    viewport.fit_to_range(day(10), day(30), 0.0);
+    assert!(viewport.end > viewport.start);
+    assert!(viewport.datetime_to_x(day(20)).is_finite());
+}
+
+#[test]
+fn fitting_no_tasks_or_a_lone_milestone_leaves_room_to_work() {
+    let mut viewport = TimelineViewport::new(day(1), day(31));
+    viewport.fit_to_tasks(&[], 800.0);
+    assert_eq!(viewport.start, day(1));
+    assert!(viewport.end >= day(8));
+
+    let launch = Task::new_milestone("Launch", day(15));
+    viewport.fit_to_tasks(&[launch], 800.0);
+    assert!(viewport.start <= day(12) && viewport.end >= day(18));
+    assert!(viewport.total_width() > 0.0);
+}
+
+#[test]
+fn default_tasks_fill_the_working_day() {
+    let monday = date(4);
+    let defaults = TaskDefaults::default();
+
+    // A one-day task is the working day, and counts as one day.
+    let task = defaults.task("Review", monday, 1);
+    assert_eq!((task.start, task.end), (at(4, 9), at(4, 17)));
+    assert_eq!(task.duration_days(), 1);
+
+    // Eight hours wide at the Hours scale, not a sliver at midnight.
+    let mut viewport = TimelineViewport::new(at(4, 0), at(11, 0));
+    viewport.scale = TimelineScale::Hours;
+    let width = viewport.date_to_x(task.end) - viewport.date_to_x(task.start);
+    assert_eq!(width, 8.0 * viewport.pixels_per_hour);
+
+    // Multi-day tasks end on their last day, so a week is Monday to Friday.
+    let week = defaults.task("Build", monday, 5);
+    assert_eq!(week.end, at(8, 17));
+    assert_eq!(week.duration_days(), 5);
+
+    // Milestones fall at the start time, and follow a changed default.
+    let late = TaskDefaults { start_time: NaiveTime::from_hms_opt(10, 30, 0).unwrap(), ..defaults };
+    let launch = late.milestone("Launch", monday);
+    assert!(launch.is_milestone);
+    assert_eq!(launch.start, monday.and_hms_opt(10, 30, 0).unwrap());
+    assert_eq!(launch.end, launch.start);
+}
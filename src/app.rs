@@ -1,11 +1,49 @@
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use std::collections::HashSet;
 use std::path::PathBuf;
+use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::model::{Project, Task, TimelineViewport, UndoHistory};
-use crate::model::task::TaskPriority;
+use crate::commands::CommandRegistry;
+use crate::i18n::format_datetime;
+use crate::keymap::Keymap;
+use crate::model::absence::Absence;
+use crate::model::graph::{DependencyGraph, DependencyTrace};
+use crate::model::grouping::{group_tasks, GroupBy, GroupKey};
+use crate::model::highlight::{DueState, HighlightRules};
+use crate::model::risk::RiskLevel;
+use crate::model::{Project, Rgba, Task, TimelineViewport, UndoHistory};
+use crate::model::task::{CompletedTasks, Dependency, DependencyKind, FixedEdge, ProgressPolicy, ProgressSource, ShiftAmount, TaskPriority};
+use crate::model::quick_add::QuickAdd;
+use crate::model::schedule::{ChildChain, ProposedMove};
+use crate::model::reminders::{Reminder, ReminderLog};
+use crate::settings::{AppSettings, RowDensity, Session, SessionViewport, UI_SCALE_RANGE};
 use crate::ui;
-use crate::ui::theme_manager::ThemeManager;
+use crate::ui::model_ext::ToRgba;
+use crate::ui::onboarding::{CoachTarget, EmptyStateAction};
+use crate::ui::theme_manager::{ThemeManager, ThemePreset};
+
+/// Where the newest edit started from, until the activity log catches up
+/// with it.
+enum ActivityBaseline {
+    /// The newest undo snapshot, pushed just before the edit.
+    UndoTop,
+    /// A copy of the state before an edit that was folded into an earlier
+    /// undo step.
+    Copy { tasks: Vec<Task>, dependencies: Vec<Dependency> },
+}
+
+/// Keyboard date nudges folded into one undo step, for the running total
+/// the status bar reports.
+struct DateNudge {
+    ids: Vec<Uuid>,
+    end_only: bool,
+    days: i64,
+    at: std::time::Instant,
+}
+
+/// Presses closer together than this undo as one step.
+const NUDGE_COALESCE: std::time::Duration = std::time::Duration::from_secs(1);
 
 /// Main application state.
 pub struct GanttApp {
@@ -13,10 +51,45 @@ pub struct GanttApp {
     pub viewport: TimelineViewport,
     pub file_path: Option<PathBuf>,
     pub selected_task: Option<Uuid>,
+    /// Every selected task, for bulk edits. Contains `selected_task` (the
+    /// primary selection) whenever that is set; see [`Self::sync_selection`].
+    pub selection: Vec<Uuid>,
+    /// View shown in the chart area.
+    pub main_view: ui::view_tabs::MainView,
 
     // Dialog state
     pub show_add_task: bool,
     pub show_about: bool,
+    pub show_command_palette: bool,
+    /// While set, the command palette picks a successor for this task
+    /// instead (L).
+    pub palette_link_from: Option<Uuid>,
+    pub show_shortcuts: bool,
+    /// Show the "Shift Dates" dialog for the selection.
+    pub show_shift_dates: bool,
+    /// Show the preview of moves that clear violated dependencies.
+    pub show_reschedule: bool,
+    /// Show the list of links the tasks' dates break.
+    pub show_violations: bool,
+    /// Show the project-wide activity log.
+    pub show_activity: bool,
+    /// Show the project-wide Dependencies panel.
+    pub show_dependencies: bool,
+    /// Link picked in the Dependencies panel to stand out on the chart.
+    pub highlighted_link: Option<(Uuid, Uuid)>,
+    /// Show the "Import from GitHub" dialog.
+    pub show_github_import: bool,
+    /// Access token for private repositories. Kept for the session only,
+    /// never written to the settings file.
+    pub github_token: String,
+    /// Issue fetch in progress, if any.
+    pub github_fetch: Option<crate::io::github_import::GithubFetch>,
+    /// Jira export read from disk, shown for preview until it is added or
+    /// dismissed.
+    pub pending_jira_import: Option<crate::io::jira_import::JiraImport>,
+    /// Markdown outline read from disk, shown for preview until it is added
+    /// or dismissed.
+    pub pending_markdown_import: Option<crate::io::markdown_import::MarkdownImport>,
     pub new_task_name: String,
     pub new_task_start: String,
     pub new_task_end: String,
@@ -30,24 +103,123 @@ pub struct GanttApp {
     // Theme engine
     pub theme_manager: ThemeManager,
 
+    // Persisted user settings
+    pub settings: AppSettings,
+
+    // Width of the task list / editor panel (the chart takes the rest)
+    pub task_panel_width: f32,
+
     // Undo / redo
     pub undo_history: UndoHistory,
 
     // Filter / search
     pub search_query: String,
     pub filter_priority: Option<TaskPriority>,
+    /// Show only tasks at this risk level, and the phases framing them.
+    pub filter_risk: Option<RiskLevel>,
+    /// Show only late, due-soon or done tasks, and the phases framing them.
+    pub filter_due: Option<DueState>,
+    /// Leave tentative tasks out of the list and chart.
+    pub hide_tentative: bool,
 
     // Pending actions from nested UI closures
     pub pending_add_subtask: Option<Uuid>,
     pub pending_add_dependency: Option<crate::model::task::Dependency>,
+    /// Task waiting for the user to confirm its conversion to a milestone.
+    pub pending_milestone: Option<Uuid>,
+    /// Parent whose children the "Arrange Children" preview is showing.
+    pub pending_arrange: Option<Uuid>,
+
+    /// Unfinished tasks coming due, not snoozed; refreshed every minute.
+    pub reminders: Vec<Reminder>,
+    pub reminder_log: ReminderLog,
+    /// When reminders were last checked; `None` forces a check next frame.
+    pub last_reminder_check: Option<std::time::Instant>,
+
+    /// Dependency chains of the selected task, kept until the selection or
+    /// the links change.
+    trace_cache: Option<(Arc<DependencyGraph>, Arc<DependencyTrace>)>,
+    /// Task whose trace was dismissed with Escape.
+    trace_dismissed: Option<Uuid>,
+
+    /// Start of the edit the activity log hasn't recorded yet; `None` once
+    /// it is up to date and after loading or replacing the project.
+    activity_baseline: Option<ActivityBaseline>,
+    /// Project generation the newest undo snapshot was taken at, while it
+    /// still matches the project.
+    undo_generation: Option<u64>,
+    /// The last run of keyboard date nudges.
+    date_nudge: Option<DateNudge>,
+
+    /// Time under the pointer on the chart last frame, for the status bar.
+    pub chart_hover_time: Option<NaiveDateTime>,
+
+    /// Formats offered under File → Export.
+    pub exporters: crate::export::ExporterRegistry,
+    /// Format whose export dialog is open.
+    pub pending_export: Option<&'static str>,
+    /// Commands offered by the command palette and bound to keys.
+    pub commands: CommandRegistry,
+    pub keymap: Keymap,
+
+    // Saving
+    pub saver: crate::io::background_save::BackgroundSaver,
+    /// [`Project::generation`] last written to disk (or loaded); the project
+    /// has unsaved changes while its generation differs.
+    pub saved_generation: u64,
+    /// Post-save commands running in the background.
+    pub save_hooks: crate::io::save_hook::SaveHooks,
+    /// Finished post-save command runs, oldest first.
+    pub hook_log: Vec<crate::io::save_hook::HookRun>,
+    /// Project command the user agreed to run for the open file. A command
+    /// that arrives with an opened file is confirmed once before it runs.
+    pub trusted_hook: Option<String>,
+    /// Project command waiting for that confirmation, with the saved path.
+    pub pending_hook: Option<(String, PathBuf)>,
+    /// Show the post-save command settings and log.
+    pub show_save_hook: bool,
+    /// Show the project's time zone setting.
+    pub show_time_zone: bool,
+    /// Show the project's target end date setting.
+    pub show_target_end: bool,
+    /// Show the rules marking overdue and at-risk tasks.
+    pub show_highlight_rules: bool,
+    /// Show the project's progress policy.
+    pub show_progress_policy: bool,
+    pub show_resources: bool,
+    /// The last save that failed, with the file it was meant for, until the
+    /// error dialog is dismissed.
+    pub save_error: Option<(PathBuf, crate::model::file::SaveError)>,
+    /// How the task list is organised; the chart follows its order.
+    pub group_by: GroupBy,
+    /// Groups folded shut in the grouped task list.
+    pub collapsed_groups: HashSet<GroupKey>,
+    /// Another version of the project file being compared with the open one.
+    pub comparison: Option<ui::compare_panel::Comparison>,
+    pub toasts: ui::toasts::Toasts,
+    /// A running Edit → Copy Chart as Image.
+    pub chart_capture: ui::chart_capture::ChartCapture,
 }
 
 impl GanttApp {
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
         // Register Phosphor icon font as a fallback so icons render inline with text
         let mut fonts = egui::FontDefinitions::default();
         egui_phosphor::add_to_fonts(&mut fonts, egui_phosphor::Variant::Regular);
-        _cc.egui_ctx.set_fonts(fonts);
+        cc.egui_ctx.set_fonts(fonts);
+
+        let settings = AppSettings::load();
+        let commands = CommandRegistry::with_builtins();
+        let keymap = Keymap::new(&commands, &settings.key_bindings);
+        cc.egui_ctx.set_zoom_factor(settings.ui_scale);
+        let accent = settings
+            .accent_color
+            .as_deref()
+            .and_then(|hex| ui::theme_def::hex_color::parse_hex_color(hex).ok());
+        let theme_manager = ThemeManager::new(&settings.active_theme, accent);
+        let task_panel_width = settings
+            .task_panel_width
+            .unwrap_or_else(|| theme_manager.active().sizing.side_panel_default_width);
 
         let project = Self::sample_project();
         let start = project
@@ -70,32 +242,167 @@ impl GanttApp {
         let default_end = (today + chrono::Duration::days(7))
             .format("%Y-%m-%d %H:%M")
             .to_string();
+        let defaults = project.task_defaults;
+        let (new_task_start_date, new_task_end_date) = defaults.span(today.date(), defaults.duration_days);
 
-        // Default times: 09:00 for start, 17:00 for end
-        let start_time = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
-        let end_time = NaiveTime::from_hms_opt(17, 0, 0).unwrap();
-
-        Self {
+        let saved_generation = project.generation();
+        let mut app = Self {
             project,
             viewport: TimelineViewport::new(start, end),
             file_path: None,
             selected_task: None,
+            selection: Vec::new(),
+            main_view: ui::view_tabs::MainView::default(),
             show_add_task: false,
             show_about: false,
+            show_command_palette: false,
+            palette_link_from: None,
+            show_shortcuts: false,
+            show_shift_dates: false,
+            show_reschedule: false,
+            show_violations: false,
+            show_activity: false,
+            show_dependencies: false,
+            highlighted_link: None,
+            show_github_import: false,
+            github_token: std::env::var("GITHUB_TOKEN").unwrap_or_default(),
+            github_fetch: None,
+            pending_jira_import: None,
+            pending_markdown_import: None,
             new_task_name: String::new(),
             new_task_start: default_start.clone(),
             new_task_end: default_end.clone(),
-            new_task_start_date: today.date().and_time(start_time),
-            new_task_end_date: (today + chrono::Duration::days(7)).date().and_time(end_time),
+            new_task_start_date,
+            new_task_end_date,
             new_task_is_milestone: false,
-            status_message: "Ready".to_string(),
-            theme_manager: ThemeManager::new(),
+            status_message: theme_manager
+                .override_warnings()
+                .first()
+                .cloned()
+                .unwrap_or_else(|| "Ready".to_string()),
+            theme_manager,
+            settings,
+            task_panel_width,
             undo_history: UndoHistory::new(),
             search_query: String::new(),
             filter_priority: None,
+            filter_risk: None,
+            filter_due: None,
+            hide_tentative: false,
             pending_add_subtask: None,
             pending_add_dependency: None,
+            pending_milestone: None,
+            pending_arrange: None,
+            keymap,
+            chart_hover_time: None,
+            trace_cache: None,
+            trace_dismissed: None,
+            activity_baseline: None,
+            undo_generation: None,
+            date_nudge: None,
+            reminders: Vec::new(),
+            reminder_log: ReminderLog::default(),
+            last_reminder_check: None,
+            exporters: crate::export::registry(),
+            pending_export: None,
+            commands,
+            saver: crate::io::background_save::BackgroundSaver::new(cc.egui_ctx.clone()),
+            saved_generation,
+            save_hooks: crate::io::save_hook::SaveHooks::new(cc.egui_ctx.clone()),
+            hook_log: Vec::new(),
+            trusted_hook: None,
+            pending_hook: None,
+            show_save_hook: false,
+            show_time_zone: false,
+            show_target_end: false,
+            show_highlight_rules: false,
+            show_progress_policy: false,
+            show_resources: false,
+            save_error: None,
+            group_by: GroupBy::Hierarchy,
+            collapsed_groups: HashSet::new(),
+            comparison: None,
+            toasts: ui::toasts::Toasts::default(),
+            chart_capture: ui::chart_capture::ChartCapture::default(),
+        };
+        app.restore_session();
+        app
+    }
+
+    /// Where this run is now, for the next one to pick up.
+    fn session(&self) -> Session {
+        Session {
+            file: self.file_path.clone(),
+            viewport: Some(SessionViewport {
+                start: self.viewport.start,
+                end: self.viewport.end,
+                scale: self.viewport.scale,
+                pixels_per_day: self.viewport.pixels_per_day,
+            }),
+            main_view: self.main_view,
+            selected_task: self.selected_task,
+            selection: self.selection.clone(),
+            search_query: self.search_query.clone(),
+            filter_priority: self.filter_priority,
+            filter_risk: self.filter_risk,
+            filter_due: self.filter_due,
+            hide_tentative: self.hide_tentative,
+        }
+    }
+
+    /// Reopen the file the last run had open, with its view, selection and
+    /// filters. A file that is gone or won't load leaves an empty project
+    /// and a toast saying why.
+    fn restore_session(&mut self) {
+        if !self.settings.restore_session {
+            return;
         }
+        let session = std::mem::take(&mut self.settings.session);
+        // Forget the file until it has loaded, so one that brings the app
+        // down isn't retried on every launch.
+        self.settings.save();
+        if let Some(path) = &session.file {
+            let name = path.display();
+            if !path.exists() {
+                self.new_project();
+                self.toasts.error(format!("{} no longer exists; started with an empty project", name));
+                return;
+            }
+            match crate::io::load_project(path) {
+                Ok(mut project) => {
+                    let broken = project.break_parent_cycles();
+                    self.project = project;
+                    self.saved_generation = self.project.generation();
+                    self.file_path = Some(path.clone());
+                    self.recalculate_viewport();
+                    self.status_message = match self.parent_loop_note(&broken) {
+                        Some(note) => format!("Reopened {}; {}", name, note),
+                        None => format!("Reopened {}", name),
+                    };
+                }
+                Err(e) => {
+                    self.new_project();
+                    self.toasts.error(format!("Couldn't reopen {}: {}. Started with an empty project", name, e));
+                    return;
+                }
+            }
+        }
+
+        if let Some(saved) = session.viewport {
+            self.viewport.set_range(saved.start, saved.end);
+            self.viewport.set_pixels_per_day(saved.pixels_per_day);
+            self.viewport.scale = saved.scale;
+        }
+        self.main_view = session.main_view;
+        self.selected_task = session.selected_task.filter(|id| self.project.task(*id).is_some());
+        self.selection = session.selection.iter().copied().filter(|id| self.project.task(*id).is_some()).collect();
+        self.search_query = session.search_query.clone();
+        self.filter_priority = session.filter_priority;
+        self.filter_risk = session.filter_risk;
+        self.filter_due = session.filter_due;
+        self.hide_tentative = session.hide_tentative;
+        self.settings.session = session;
+        self.settings.save();
     }
 
     /// Generate a sample project for demonstration.
@@ -113,7 +420,7 @@ impl GanttApp {
             (today - chrono::Duration::days(5)).date().and_time(start_time),
             (today + chrono::Duration::days(8)).date().and_time(end_time),
         );
-        phase1.color = egui::Color32::from_rgb(70, 120, 180);
+        phase1.color = Rgba::from_rgb(70, 120, 180);
 
         let mut t1 = Task::new(
             "Project Kickoff",
@@ -121,7 +428,7 @@ impl GanttApp {
             (today - chrono::Duration::days(2)).date().and_time(end_time),
         );
         t1.progress = 1.0;
-        t1.color = egui::Color32::from_rgb(70, 130, 180);
+        t1.color = Rgba::from_rgb(70, 130, 180);
         t1.parent_id = Some(phase1.id);
 
         let mut t2 = Task::new(
@@ -130,7 +437,7 @@ impl GanttApp {
             (today + chrono::Duration::days(5)).date().and_time(end_time),
         );
         t2.progress = 0.6;
-        t2.color = egui::Color32::from_rgb(60, 179, 113);
+        t2.color = Rgba::from_rgb(60, 179, 113);
         t2.parent_id = Some(phase1.id);
 
         let mut m1 = Task::new_milestone("Planning Complete", (today + chrono::Duration::days(8)).date().and_time(start_time));
@@ -142,7 +449,7 @@ impl GanttApp {
             (today + chrono::Duration::days(6)).date().and_time(start_time),
             (today + chrono::Duration::days(30)).date().and_time(end_time),
         );
-        phase2.color = egui::Color32::from_rgb(180, 100, 50);
+        phase2.color = Rgba::from_rgb(180, 100, 50);
 
         let mut t3 = Task::new(
             "UI Design",
@@ -150,7 +457,7 @@ impl GanttApp {
             (today + chrono::Duration::days(18)).date().and_time(end_time),
         );
         t3.progress = 0.0;
-        t3.color = egui::Color32::from_rgb(218, 112, 214);
+        t3.color = Rgba::from_rgb(218, 112, 214);
         t3.parent_id = Some(phase2.id);
 
         let mut t4 = Task::new(
@@ -159,7 +466,7 @@ impl GanttApp {
             (today + chrono::Duration::days(28)).date().and_time(end_time),
         );
         t4.progress = 0.0;
-        t4.color = egui::Color32::from_rgb(106, 90, 205);
+        t4.color = Rgba::from_rgb(106, 90, 205);
         t4.parent_id = Some(phase2.id);
 
         let mut t5 = Task::new(
@@ -168,7 +475,7 @@ impl GanttApp {
             (today + chrono::Duration::days(30)).date().and_time(end_time),
         );
         t5.progress = 0.0;
-        t5.color = egui::Color32::from_rgb(220, 20, 60);
+        t5.color = Rgba::from_rgb(220, 20, 60);
         t5.parent_id = Some(phase2.id);
 
         let m2 = Task::new_milestone("Launch", (today + chrono::Duration::days(32)).date().and_time(start_time));
@@ -179,32 +486,37 @@ impl GanttApp {
                 from_task: t1.id,
                 to_task: t2.id,
                 kind: crate::model::task::DependencyKind::FinishToStart,
+                lag_days: 0,
             },
             crate::model::task::Dependency {
                 from_task: t2.id,
                 to_task: t3.id,
                 kind: crate::model::task::DependencyKind::FinishToStart,
+                lag_days: 0,
             },
             crate::model::task::Dependency {
                 from_task: t3.id,
                 to_task: t4.id,
                 kind: crate::model::task::DependencyKind::StartToStart,
+                lag_days: 0,
             },
             crate::model::task::Dependency {
                 from_task: t4.id,
                 to_task: t5.id,
                 kind: crate::model::task::DependencyKind::FinishToStart,
+                lag_days: 0,
             },
             crate::model::task::Dependency {
                 from_task: t5.id,
                 to_task: m2.id,
                 kind: crate::model::task::DependencyKind::FinishToStart,
+                lag_days: 0,
             },
         ];
 
         // Order: parent, then children in sequence
-        project.tasks = vec![phase1, t1, t2, m1, phase2, t3, t4, t5, m2];
-        project.dependencies = deps;
+        project.set_tasks(vec![phase1, t1, t2, m1, phase2, t3, t4, t5, m2]);
+        project.set_dependencies(deps);
         // Auto-calculate parent dates from children
         project.recalculate_parent_dates();
         project
@@ -214,24 +526,51 @@ impl GanttApp {
 
     pub fn new_project(&mut self) {
         self.project = Project::default();
+        self.saved_generation = self.project.generation();
         self.file_path = None;
         self.selected_task = None;
         self.undo_history.clear();
+        self.activity_baseline = None;
+        self.comparison = None;
         self.status_message = "New project created".to_string();
     }
 
+    /// Swap the project for the example one shown at first start.
+    pub fn load_example_project(&mut self) {
+        self.new_project();
+        self.project = Self::sample_project();
+        self.saved_generation = self.project.generation();
+        self.recalculate_viewport();
+        self.status_message = "Loaded the example project".to_string();
+    }
+
+    /// Run the first-run tour again from its first step.
+    pub fn restart_tour(&mut self, ctx: &egui::Context) {
+        self.settings.tour_done = false;
+        self.settings.save();
+        ui::onboarding::restart_tour(ctx);
+    }
+
     pub fn open_project(&mut self) {
         if let Some(path) = rfd::FileDialog::new()
             .add_filter("Gantt Project", &["gantt.json", "json"])
             .pick_file()
         {
             match crate::io::load_project(&path) {
-                Ok(project) => {
+                Ok(mut project) => {
+                    let broken = project.break_parent_cycles();
                     self.project = project;
+                    self.saved_generation = self.project.generation();
                     self.file_path = Some(path);
                     self.recalculate_viewport();
                     self.undo_history.clear();
-                    self.status_message = "Project loaded".to_string();
+                    self.activity_baseline = None;
+                    self.trusted_hook = None;
+                    self.comparison = None;
+                    self.status_message = match self.parent_loop_note(&broken) {
+                        Some(note) => format!("Project loaded; {}", note),
+                        None => "Project loaded".to_string(),
+                    };
                 }
                 Err(e) => {
                     self.status_message = format!("Error loading: {}", e);
@@ -241,12 +580,8 @@ impl GanttApp {
     }
 
     pub fn save_project(&mut self) {
-        if let Some(ref path) = self.file_path.clone() {
-            self.project.touch();
-            match crate::io::save_project(&self.project, path) {
-                Ok(()) => self.status_message = "Project saved".to_string(),
-                Err(e) => self.status_message = format!("Error saving: {}", e),
-            }
+        if let Some(path) = self.file_path.clone() {
+            self.start_save(path);
         } else {
             self.save_project_as();
         }
@@ -259,12 +594,89 @@ impl GanttApp {
             .save_file()
         {
             self.file_path = Some(path.clone());
-            self.project.touch();
-            match crate::io::save_project(&self.project, &path) {
-                Ok(()) => self.status_message = "Project saved".to_string(),
-                Err(e) => self.status_message = format!("Error saving: {}", e),
+            self.start_save(path);
+        }
+    }
+
+    /// Queue a save of the current project on the background saver. The
+    /// result is picked up by [`Self::poll_saves`].
+    fn start_save(&mut self, path: PathBuf) {
+        // Stamp the file without advancing the generation: saving isn't an edit.
+        self.project.modified = chrono::Utc::now();
+        let mut project = self.project.clone();
+        if !self.settings.save_activity {
+            project.activity.clear();
+        }
+        match self.saver.save(project, path) {
+            Ok(()) => self.status_message = "Saving…".to_string(),
+            Err(e) => self.status_message = format!("Error saving: {}", e),
+        }
+    }
+
+    /// Apply finished background saves.
+    fn poll_saves(&mut self) {
+        for outcome in self.saver.poll() {
+            match outcome.result {
+                Ok(()) => {
+                    self.saved_generation = outcome.generation;
+                    self.status_message = format!("Saved {}", outcome.path.display());
+                    self.run_post_save_hook(outcome.path);
+                }
+                Err(e) => {
+                    self.status_message = format!("Error saving: {}", e.message);
+                    self.save_error = Some((outcome.path, e));
+                }
+            }
+        }
+    }
+
+    /// Start the post-save command for the file just written to `path`:
+    /// the project's own, or else the app-wide one. A project's command
+    /// waits for confirmation until the user has agreed to it.
+    fn run_post_save_hook(&mut self, path: PathBuf) {
+        let own = self.project.post_save_command.as_deref().map(str::trim).filter(|c| !c.is_empty());
+        match own {
+            Some(command) if self.trusted_hook.as_deref() != Some(command) => {
+                self.pending_hook = Some((command.to_string(), path));
             }
+            Some(command) => self.save_hooks.run(command, path),
+            None => {
+                let command = self.settings.post_save_command.trim();
+                if !command.is_empty() {
+                    self.save_hooks.run(command, path);
+                }
+            }
+        }
+    }
+
+    /// Run the confirmed project command from `pending_hook` and trust it
+    /// for the rest of the session.
+    pub fn confirm_pending_hook(&mut self) {
+        if let Some((command, path)) = self.pending_hook.take() {
+            self.save_hooks.run(&command, path);
+            self.trusted_hook = Some(command);
+        }
+    }
+
+    /// Log finished post-save commands; failures also get a toast.
+    fn poll_save_hooks(&mut self) {
+        for run in self.save_hooks.poll() {
+            if !run.succeeded() {
+                let reason = match &run.result {
+                    Ok(code) => format!("exited with code {}", code),
+                    Err(e) => e.clone(),
+                };
+                self.toasts.error(format!("Post-save command {}. See File → After-Save Command for its output.", reason));
+            }
+            self.hook_log.push(run);
         }
+        let excess = self.hook_log.len().saturating_sub(crate::io::save_hook::LOG_LIMIT);
+        self.hook_log.drain(..excess);
+    }
+
+    /// True if the project changed since it was last saved or loaded.
+    pub fn is_dirty(&self) -> bool {
+        self.project.generation() != self.saved_generation
     }
 
     pub fn import_csv(&mut self) {
@@ -295,9 +707,10 @@ impl GanttApp {
 
                     let count = tasks.len();
                     self.project = crate::model::Project::new(proj_name);
-                    self.project.tasks = tasks;
+                    self.project.set_tasks(tasks);
                     self.file_path = None;
                     self.selected_task = None;
+                    self.activity_baseline = None;
                     self.recalculate_viewport();
 
                     if skipped > 0 {
@@ -316,25 +729,230 @@ impl GanttApp {
         }
     }
 
-    pub fn export_csv(&mut self) {
+    /// Pick another version of the project file and show how it differs
+    /// from the open project.
+    pub fn compare_with_file(&mut self) {
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("Gantt Project", &["gantt.json", "json"])
+            .pick_file()
+        {
+            match crate::io::load_project(&path) {
+                Ok(mut other) => {
+                    other.break_parent_cycles();
+                    let label = path.file_name().map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into_owned());
+                    let comparison = ui::compare_panel::Comparison::new(label, other, &self.project);
+                    self.status_message = format!("{} differences", comparison.diff.len());
+                    self.comparison = Some(comparison);
+                }
+                Err(e) => self.status_message = format!("Error loading: {}", e),
+            }
+        }
+    }
+
+    /// Read a Jira CSV export and open its preview.
+    pub fn import_jira_csv(&mut self) {
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("CSV Files", &["csv"])
+            .pick_file()
+        {
+            match crate::io::jira_import::import_jira_csv(&path) {
+                Ok(import) => self.pending_jira_import = Some(import),
+                Err(e) => self.status_message = format!("Jira import failed: {}", e),
+            }
+        }
+    }
+
+    /// Add the previewed Jira issues to the project as one undoable step.
+    pub fn apply_jira_import(&mut self, import: crate::io::jira_import::JiraImport) {
+        self.push_undo("Import Jira CSV");
+        let count = import.tasks.len();
+        for task in import.tasks {
+            self.project.add_task(task);
+        }
+        let broken = self.project.break_parent_cycles();
+        self.project.sort_tasks_grouped();
+        self.project.tasks_changed();
+        self.project.touch();
+        self.recalculate_viewport();
+        self.status_message = if import.skipped > 0 {
+            format!("Imported {} Jira issues ({} rows skipped)", count, import.skipped)
+        } else {
+            format!("Imported {} Jira issues", count)
+        };
+        if let Some(note) = self.parent_loop_note(&broken) {
+            self.status_message = format!("{}; {}", self.status_message, note);
+        }
+    }
+
+    /// Read a Markdown outline and open its preview, laid out from today.
+    pub fn import_markdown(&mut self) {
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("Markdown", &["md", "markdown", "txt"])
+            .pick_file()
+        {
+            let today = chrono::Local::now().date_naive();
+            match crate::io::markdown_import::import_markdown(&path, today) {
+                Ok(import) => self.pending_markdown_import = Some(import),
+                Err(e) => self.status_message = format!("Markdown import failed: {}", e),
+            }
+        }
+    }
+
+    /// Add the previewed outline to the end of the project as one undoable step.
+    pub fn apply_markdown_import(&mut self, import: crate::io::markdown_import::MarkdownImport) {
+        self.push_undo("Import Markdown outline");
+        let tasks = import.tasks(&self.project.task_defaults);
+        let count = tasks.len();
+        for task in tasks {
+            self.project.add_task(task);
+        }
+        self.project.sort_tasks_grouped();
+        self.project.record_progress(chrono::Local::now().date_naive());
+        self.project.tasks_changed();
+        self.project.touch();
+        self.recalculate_viewport();
+        self.status_message = format!("Imported {} tasks from the Markdown outline", count);
+    }
+
+    /// Status text naming the tasks [`Project::break_parent_cycles`] moved
+    /// to the top level, if any.
+    fn parent_loop_note(&self, broken: &[Uuid]) -> Option<String> {
+        if broken.is_empty() {
+            return None;
+        }
+        let names: Vec<String> = broken.iter().map(|&id| format!("'{}'", self.project.task_name(id))).collect();
+        Some(format!("moved {} out of a parent loop to the top level", names.join(", ")))
+    }
+
+    /// Start fetching the open issues of `settings.github_repo` in the
+    /// background; [`Self::poll_github_import`] merges them when they arrive.
+    pub fn start_github_import(&mut self, ctx: &egui::Context) {
+        let Some(repo) = crate::io::github_import::parse_repo(&self.settings.github_repo) else {
+            self.status_message = "Enter a repository as owner/name".to_string();
+            return;
+        };
+        self.settings.github_repo = repo.clone();
+        self.settings.save();
+        let token = Some(self.github_token.trim().to_string()).filter(|t| !t.is_empty());
+        self.status_message = format!("Fetching issues from {}…", repo);
+        self.github_fetch = Some(crate::io::github_import::GithubFetch::start(ctx, repo, token));
+    }
+
+    fn poll_github_import(&mut self) {
+        let Some(result) = self.github_fetch.as_ref().and_then(|f| f.poll()) else {
+            return;
+        };
+        let repo = self.github_fetch.take().map(|f| f.repo).unwrap_or_default();
+        match result {
+            Ok(issues) => {
+                self.push_undo("Import GitHub issues");
+                let today = chrono::Local::now().date_naive();
+                let summary = crate::io::github_import::merge_issues(&mut self.project, &issues, today);
+                self.project.touch();
+                self.show_github_import = false;
+                self.recalculate_viewport();
+                self.status_message = format!(
+                    "Imported {} issues from {}: {} new, {} updated",
+                    issues.len(),
+                    repo,
+                    summary.added,
+                    summary.updated
+                );
+            }
+            Err(e) => {
+                self.status_message = format!("GitHub import failed: {}", e);
+                rfd::MessageDialog::new()
+                    .set_level(rfd::MessageLevel::Error)
+                    .set_title("GitHub import failed")
+                    .set_description(&e)
+                    .set_buttons(rfd::MessageButtons::Ok)
+                    .show();
+            }
+        }
+    }
+
+    /// Open the export dialog for the format called `name`; exporting
+    /// happens from there with [`Self::export_as`].
+    pub fn begin_export(&mut self, name: &str) {
+        let Some(exporter) = self.exporters.find(name) else {
+            self.status_message = format!("Unknown export format: {}", name);
+            return;
+        };
         if self.project.tasks.is_empty() {
             self.status_message = "Nothing to export — project has no tasks".to_string();
             return;
         }
+        self.pending_export = Some(exporter.name());
+    }
+
+    /// Whether the view hides any tasks, i.e. whether exporting the
+    /// current filter differs from exporting the whole project.
+    pub fn is_filtering(&self) -> bool {
+        self.hide_tentative
+            || !self.search_query.trim().is_empty()
+            || self.filter_priority.is_some()
+            || self.filter_risk.is_some()
+            || self.filter_due.is_some()
+    }
+
+    /// Export the project in the format called `name`, scoped by `options`,
+    /// to a file the user picks. Formats that copy to the clipboard do so
+    /// even if the save dialog is cancelled.
+    pub fn export_as(&mut self, ctx: &egui::Context, name: &str, options: &crate::export::ExportOptions) {
+        let Some(exporter) = self.exporters.find(name) else {
+            self.status_message = format!("Unknown export format: {}", name);
+            return;
+        };
+        let mut options = options.clone();
+        let due_states = self.project.due_states();
+        options.milestone_lane = self.settings.show_milestone_lane.then_some(self.settings.milestone_lane_key_only);
+        let project = options.scope(
+            &self.project,
+            |t| {
+                !(self.hide_tentative && t.tentative)
+                    && self.filter_risk.is_none_or(|risk| t.risk == Some(risk))
+                    && self.filter_due.is_none_or(|due| due_states.get(&t.id) == Some(&due))
+                    && ui::filter_bar::task_matches(&t.name, &t.description, t.priority, &self.search_query, self.filter_priority)
+            },
+            &self.selection,
+        );
+        if project.tasks.is_empty() {
+            self.status_message = "Nothing to export — no tasks match the export options".to_string();
+            return;
+        }
+
+        // Render first, so a format with nothing to write says so before
+        // asking where to save.
+        let mut output = Vec::new();
+        let count = match exporter.export(&project, &options, &mut output) {
+            Ok(count) => count,
+            Err(e) => {
+                self.status_message = format!("{} export failed: {}", exporter.name(), e);
+                return;
+            }
+        };
+        let copied = exporter.copies_to_clipboard();
+        if copied {
+            ctx.copy_text(String::from_utf8_lossy(&output).into_owned());
+            self.status_message = format!("Copied {} to the clipboard", exporter.name());
+        }
 
-        let default_name = format!("{}.csv", self.project.name);
         if let Some(path) = rfd::FileDialog::new()
-            .add_filter("CSV Files", &["csv"])
-            .set_file_name(&default_name)
+            .add_filter(exporter.name(), &[exporter.extension()])
+            .set_file_name(exporter.file_name(&self.project.name))
             .save_file()
         {
-            match crate::io::csv_export::export_csv(&self.project.tasks, &path) {
-                Ok(count) => {
-                    self.status_message = format!("Exported {} tasks to CSV", count);
-                }
-                Err(e) => {
-                    self.status_message = format!("CSV export failed: {}", e);
+            match std::fs::write(&path, &output) {
+                Ok(()) => {
+                    self.status_message = format!(
+                        "Exported {} {} to {}{}",
+                        count,
+                        exporter.item_label(),
+                        exporter.name(),
+                        if copied { " (also copied to the clipboard)" } else { "" }
+                    )
                 }
+                Err(e) => self.status_message = format!("{} export failed: {}", exporter.name(), e),
             }
         }
     }
@@ -342,27 +960,256 @@ impl GanttApp {
     // --- Task operations ---
 
     pub fn undo(&mut self) {
-        if let Some(snap) = self.undo_history.undo(&self.project.tasks, &self.project.dependencies) {
-            self.project.tasks = snap.tasks;
-            self.project.dependencies = snap.dependencies;
-            self.project.recalculate_parent_dates();
-            self.project.sort_tasks_grouped();
-            self.project.touch();
-            self.selected_task = None;
-            self.status_message = "Undo".to_string();
-        }
+        self.undo_steps(1);
     }
 
     pub fn redo(&mut self) {
-        if let Some(snap) = self.undo_history.redo(&self.project.tasks, &self.project.dependencies) {
-            self.project.tasks = snap.tasks;
-            self.project.dependencies = snap.dependencies;
-            self.project.recalculate_parent_dates();
-            self.project.sort_tasks_grouped();
-            self.project.touch();
-            self.selected_task = None;
-            self.status_message = "Redo".to_string();
+        self.redo_steps(1);
+    }
+
+    /// Undo the last `steps` actions one at a time, so each can still be
+    /// redone on its own.
+    pub fn undo_steps(&mut self, steps: usize) {
+        let mut undone = 0;
+        let mut last = String::new();
+        self.flush_activity();
+        self.undo_generation = None;
+        for _ in 0..steps {
+            let Some(snap) = self.undo_history.undo(&self.project.tasks, &self.project.dependencies) else {
+                break;
+            };
+            undone += 1;
+            last = snap.label.clone();
+            if let Some((before, _)) = snap.progress_policy {
+                self.project.progress_policy = before;
+            }
+            self.log_restore(&snap);
+            self.restore_snapshot(snap);
+        }
+        match undone {
+            0 => {}
+            1 => self.status_message = format!("Undo: {}", last),
+            n => self.status_message = format!("Undid {} steps, back to before '{}'", n, last),
+        }
+    }
+
+    /// Redo the next `steps` undone actions one at a time.
+    pub fn redo_steps(&mut self, steps: usize) {
+        let mut redone = 0;
+        let mut last = String::new();
+        self.flush_activity();
+        self.undo_generation = None;
+        for _ in 0..steps {
+            let Some(snap) = self.undo_history.redo(&self.project.tasks, &self.project.dependencies) else {
+                break;
+            };
+            redone += 1;
+            last = snap.label.clone();
+            if let Some((_, after)) = snap.progress_policy {
+                self.project.progress_policy = after;
+            }
+            self.log_restore(&snap);
+            self.restore_snapshot(snap);
+        }
+        match redone {
+            0 => {}
+            1 => self.status_message = format!("Redo: {}", last),
+            n => self.status_message = format!("Redid {} steps, up to '{}'", n, last),
+        }
+    }
+
+    /// Replace tasks and links with an undo or redo snapshot. Selected tasks
+    /// that still exist stay selected; if the primary one is gone, its
+    /// nearest sibling (or else its parent) takes over.
+    fn restore_snapshot(&mut self, snap: crate::model::history::ProjectSnapshot) {
+        let fallbacks = self.selected_task.map(|id| self.selection_fallbacks(id)).unwrap_or_default();
+        self.project.set_tasks(snap.tasks);
+        self.project.set_dependencies(snap.dependencies);
+        self.project.sort_tasks_grouped();
+        self.project.record_progress(chrono::Local::now().date_naive());
+        self.project.touch();
+
+        let project = &self.project;
+        self.selection.retain(|&id| project.task(id).is_some());
+        if self.selected_task.is_some_and(|id| project.task(id).is_none()) {
+            self.selected_task = fallbacks.into_iter().find(|&f| project.task(f).is_some());
+            if let Some(fallback) = self.selected_task.filter(|f| !self.selection.contains(f)) {
+                self.selection.push(fallback);
+            }
+        }
+    }
+
+    /// Tasks to select instead of `id` if it disappears: its siblings, nearest
+    /// first and the following one before the preceding, then its parent.
+    fn selection_fallbacks(&self, id: Uuid) -> Vec<Uuid> {
+        let Some(task) = self.project.task(id) else {
+            return Vec::new();
+        };
+        let siblings: Vec<Uuid> =
+            self.project.tasks.iter().filter(|t| t.parent_id == task.parent_id).map(|t| t.id).collect();
+        let pos = siblings.iter().position(|&s| s == id).unwrap_or(0);
+        let mut fallbacks = Vec::with_capacity(siblings.len());
+        for distance in 1..siblings.len() {
+            fallbacks.extend(siblings.get(pos + distance));
+            fallbacks.extend(pos.checked_sub(distance).and_then(|i| siblings.get(i)));
+        }
+        fallbacks.extend(task.parent_id);
+        fallbacks
+    }
+
+    /// Set the zone the project's times are in, or `None` for floating
+    /// times. Stored times are kept as they are, not converted.
+    pub fn set_time_zone(&mut self, zone: Option<crate::model::clock::Tz>) {
+        if self.project.time_zone == zone {
+            return;
+        }
+        self.project.time_zone = zone;
+        self.project.touch();
+        self.status_message = match zone {
+            Some(zone) => format!("Times are now in {}", zone.name()),
+            None => "Times are now floating".to_string(),
+        };
+    }
+
+    /// Change the kind and lag of the link `from → to`.
+    pub fn update_link(&mut self, from: Uuid, to: Uuid, kind: DependencyKind, lag: i64) {
+        self.push_undo("Edit dependency");
+        if let Some(dep) = self.project.dependency_mut(from, to) {
+            dep.kind = kind;
+            dep.lag_days = lag;
+            self.status_message = if lag == 0 {
+                format!("Dependency changed to {}", kind.short_label())
+            } else {
+                format!("Dependency changed to {} {:+}d", kind.short_label(), lag)
+            };
+        }
+        self.project.touch();
+    }
+
+    /// Remove the links given as `(from, to)` pairs, as one undo step.
+    pub fn remove_links(&mut self, links: &[(Uuid, Uuid)]) {
+        if links.is_empty() {
+            return;
+        }
+        let label = if links.len() == 1 { "Remove dependency" } else { "Remove dependencies" };
+        self.push_undo(label);
+        self.project.remove_dependencies(|d| links.contains(&(d.from_task, d.to_task)));
+        self.project.touch();
+        self.status_message = match links.len() {
+            1 => "Dependency removed".to_string(),
+            n => format!("{} dependencies removed", n),
+        };
+    }
+
+    /// Set the day the project has to be done by, or `None` for no target.
+    pub fn set_target_end(&mut self, target: Option<NaiveDate>) {
+        if self.project.target_end == target {
+            return;
+        }
+        self.project.target_end = target;
+        self.project.touch();
+        self.status_message = match target {
+            Some(target) => format!("Target end set to {}", target.format("%Y-%m-%d")),
+            None => "Target end cleared".to_string(),
+        };
+    }
+
+    /// Replace the rules marking overdue and at-risk tasks.
+    pub fn set_highlight_rules(&mut self, rules: HighlightRules) {
+        if self.project.highlight_rules == rules {
+            return;
+        }
+        self.project.highlight_rules = rules;
+        self.project.touch();
+        self.status_message = "Highlight rules updated".to_string();
+    }
+
+    /// Switch where task progress comes from and recompute what the new
+    /// policy sets, as one undo step that also puts the old policy back.
+    pub fn set_progress_policy(&mut self, policy: ProgressPolicy) {
+        let before = self.project.progress_policy;
+        if before == policy {
+            return;
+        }
+        self.flush_activity();
+        self.undo_history.push_policy_change(
+            "Change progress policy",
+            &self.project.tasks,
+            &self.project.dependencies,
+            before,
+            policy,
+        );
+        self.undo_generation = None;
+        self.activity_baseline = Some(ActivityBaseline::UndoTop);
+        self.project.progress_policy = policy;
+        let changed = self.project.apply_progress_policy();
+        self.project.record_progress(chrono::Local::now().date_naive());
+        self.project.touch();
+        self.status_message = match changed {
+            0 => format!("Progress policy: {}; no values changed", policy.label()),
+            1 => format!("Progress policy: {}; 1 value changed", policy.label()),
+            n => format!("Progress policy: {}; {} values changed", policy.label(), n),
+        };
+    }
+
+    /// Replace the project's absences with those edited in the Resources dialog.
+    pub fn set_absences(&mut self, absences: Vec<Absence>) {
+        if self.project.absences == absences {
+            return;
+        }
+        self.project.absences = absences;
+        self.project.touch();
+        self.status_message = match self.project.absences.len() {
+            0 => "No absences recorded".to_string(),
+            1 => "1 absence recorded".to_string(),
+            n => format!("{} absences recorded", n),
+        };
+    }
+
+    /// Start or stop logging each task's progress by day. Stopping asks
+    /// first when it would drop readings already taken.
+    pub fn toggle_task_progress_log(&mut self) {
+        let on = !self.project.log_task_progress;
+        if !on && !self.project.task_progress.is_empty() {
+            let confirm = rfd::MessageDialog::new()
+                .set_title("Log Task Progress")
+                .set_description("Stop logging task progress and delete the readings taken so far?")
+                .set_buttons(rfd::MessageButtons::YesNo)
+                .show();
+            if confirm != rfd::MessageDialogResult::Yes {
+                return;
+            }
+        }
+        self.project.set_log_task_progress(on, chrono::Local::now().date_naive());
+        self.project.touch();
+        self.status_message = if on {
+            "Logging task progress".to_string()
+        } else {
+            "Stopped logging task progress".to_string()
+        };
+    }
+
+    /// Show, strike through or hide finished tasks. Saved with the project.
+    pub fn set_completed_tasks(&mut self, mode: CompletedTasks) {
+        if self.project.completed_tasks == mode {
+            return;
         }
+        self.project.completed_tasks = mode;
+        self.project.touch();
+        self.status_message = match mode {
+            CompletedTasks::Show => "Showing completed tasks",
+            CompletedTasks::Strike => "Completed tasks struck through",
+            CompletedTasks::Hide => "Completed tasks hidden",
+        }
+        .to_string();
+    }
+
+    /// `task` in the next colour of the theme's task palette, as every task
+    /// created from scratch starts out.
+    fn colored(&self, mut task: Task) -> Task {
+        let palette = ui::theme::task_palette();
+        task.color = ui::theme::task_color(self.project.tasks.len() % palette.len().max(1)).to_rgba();
+        task
     }
 
     pub fn create_task_from_dialog(&mut self) {
@@ -372,71 +1219,175 @@ impl GanttApp {
             self.new_task_name.clone()
         };
 
-        let start = self.new_task_start_date;
-        let end = if self.new_task_end_date >= start {
-            self.new_task_end_date
+        // The dialog's dates are in the viewer's local time.
+        let clock = self.project.clock();
+        let start = clock.from_local(self.new_task_start_date);
+        let end = if self.new_task_end_date >= self.new_task_start_date {
+            clock.from_local(self.new_task_end_date)
         } else {
-            start + chrono::Duration::days(7)
+            let defaults = self.project.task_defaults;
+            clock.from_local(defaults.span(self.new_task_start_date.date(), defaults.duration_days).1)
         };
 
         let task = if self.new_task_is_milestone {
             Task::new_milestone(name, start)
         } else {
-            let palette = ui::theme::task_palette();
-            let color_idx = self.project.tasks.len() % palette.len().max(1);
-            let mut t = Task::new(name, start, end);
-            t.color = ui::theme::task_color(color_idx);
-            t
+            self.colored(Task::new(name, start, end))
         };
 
-        self.undo_history.push(&self.project.tasks, &self.project.dependencies);
-        self.project.tasks.push(task);
+        self.push_undo("Add task");
+        self.project.add_task(task);
         self.project.sort_tasks_grouped();
         self.reset_dialog_fields();
         self.status_message = "Task added".to_string();
     }
 
+    /// Add the task described by a quick-add entry and select it.
+    pub fn quick_add_task(&mut self, entry: &QuickAdd) {
+        let defaults = self.project.task_defaults;
+        let task = self.colored(entry.to_task(chrono::Local::now().date_naive(), defaults.start_time, defaults.end_time));
+        let id = task.id;
+
+        self.push_undo("Add task");
+        self.project.add_task(task);
+        self.project.sort_tasks_grouped();
+        self.project.touch();
+        self.selected_task = Some(id);
+        self.selection = vec![id];
+        self.status_message = match &entry.parent {
+            Some((_, parent)) => format!("Added '{}' under '{}'", entry.display_name(), parent),
+            None => format!("Added '{}'", entry.display_name()),
+        };
+    }
+
+    /// Add a task drawn on the chart, select it and start renaming it there.
+    pub fn create_drawn_task(&mut self, ctx: &egui::Context, drawn: ui::gantt_chart::DrawnTask) {
+        let mut task = if drawn.milestone {
+            Task::new_milestone("New milestone", drawn.start)
+        } else {
+            self.colored(Task::new("New task", drawn.start, drawn.end))
+        };
+        task.parent_id = drawn.parent;
+        let (id, name) = (task.id, task.name.clone());
+
+        let label = if drawn.milestone { "Add milestone" } else { "Draw task" };
+        self.push_undo(label);
+        if let Some(parent) = drawn.parent.and_then(|pid| self.project.task_mut(pid)) {
+            parent.collapsed = false;
+        }
+        self.project.add_task(task);
+        self.project.sort_tasks_grouped();
+        self.project.touch();
+        self.selected_task = Some(id);
+        self.selection = vec![id];
+        ui::gantt_chart::reveal_task(ctx, id);
+        ui::inline_rename::begin(ctx, id, &name, ui::inline_rename::RenameSurface::Chart);
+        self.status_message = match drawn.parent {
+            Some(pid) => format!("Added '{}' under '{}'", name, self.project.task_name(pid)),
+            None => format!("Added '{}'", name),
+        };
+    }
+
+    /// Add a task right above or below `id` (after its subtasks), under the
+    /// same parent, select it and start renaming it on `surface`. It starts
+    /// on the day `id` starts (above) or the day after it ends (below) and
+    /// spans the project's default task days and times.
+    pub fn insert_task_beside(
+        &mut self,
+        ctx: &egui::Context,
+        id: Uuid,
+        side: ui::gantt_chart::InsertSide,
+        surface: ui::inline_rename::RenameSurface,
+    ) {
+        let Some(anchor) = self.project.task(id) else {
+            return;
+        };
+        let (pos, first_day) = match side {
+            ui::gantt_chart::InsertSide::Above => (self.project.task_index(id), anchor.start.date()),
+            ui::gantt_chart::InsertSide::Below => {
+                (self.project.subtree_end(id), anchor.end.date() + chrono::Duration::days(1))
+            }
+        };
+        let Some(pos) = pos else {
+            return;
+        };
+        let defaults = self.project.task_defaults;
+        let mut task = defaults.task("New task", first_day, defaults.duration_days);
+        task.parent_id = anchor.parent_id;
+        task.color = anchor.color;
+        let (new_id, name) = (task.id, task.name.clone());
+
+        self.push_undo("Insert task");
+        self.project.insert_task(pos, task);
+        self.project.touch();
+        self.selected_task = Some(new_id);
+        self.selection = vec![new_id];
+        ui::inline_rename::begin(ctx, new_id, &name, surface);
+        self.status_message = format!("Inserted '{}'", name);
+    }
+
     /// Add a subtask under the given parent. Inserts immediately after the parent's last child.
     pub fn add_subtask(&mut self, parent_id: Uuid) {
-        let parent = match self.project.tasks.iter().find(|t| t.id == parent_id) {
+        let parent = match self.project.task(parent_id) {
             Some(p) => p.clone(),
             None => return,
         };
-        let today = chrono::Local::now().naive_local();
-        let start = if parent.start > today { parent.start } else { today };
-        let end = if parent.end > start + chrono::Duration::days(7) {
-            parent.end
-        } else {
-            start + chrono::Duration::days(7)
-        };
-        let palette = ui::theme::task_palette();
-        let color_idx = self.project.tasks.len() % palette.len().max(1);
-        let mut t = Task::new("New Subtask", start, end);
-        t.color = ui::theme::task_color(color_idx);
+        let first_day = parent.start.date().max(chrono::Local::now().date_naive());
+        let defaults = self.project.task_defaults;
+        let mut t = self.colored(defaults.task("New Subtask", first_day, defaults.duration_days));
+        t.end = t.end.max(parent.end);
         t.parent_id = Some(parent_id);
 
-        self.undo_history.push(&self.project.tasks, &self.project.dependencies);
+        self.push_undo("Add subtask");
 
         // Insert after last child of parent (or right after parent if no children)
         let insert_pos = self.project.tasks.iter().rposition(|t| {
             t.parent_id == Some(parent_id) || t.id == parent_id
         }).map(|p| p + 1).unwrap_or(self.project.tasks.len());
 
-        self.project.tasks.insert(insert_pos, t.clone());
+        self.project.insert_task(insert_pos, t.clone());
         self.selected_task = Some(t.id);
-        self.project.recalculate_parent_dates();
         self.project.touch();
         self.status_message = format!("Added subtask under '{}'", parent.name);
     }
 
+    /// Add `dep`, whichever view it was drawn in: the chart, the task list,
+    /// the link picker or the editor. Links to a missing task or to itself,
+    /// links already there and links that would close a cycle are refused
+    /// with a status message. Returns whether the link was added.
+    pub fn add_dependency(&mut self, dep: crate::model::task::Dependency) -> bool {
+        let (from, to) = (dep.from_task, dep.to_task);
+        let graph = self.project.dependency_graph();
+        let refused = if self.project.task(from).is_none() || self.project.task(to).is_none() {
+            Some("Can't link: that task no longer exists")
+        } else if from == to {
+            Some("Can't link a task to itself")
+        } else if graph.has_link(from, to) {
+            Some("Those tasks are already linked")
+        } else if graph.would_create_cycle(from, to) {
+            Some("Can't link: that would create a dependency cycle")
+        } else {
+            None
+        };
+        if let Some(reason) = refused {
+            self.status_message = reason.to_string();
+            return false;
+        }
+        let from_name = self.project.task_name(from);
+        let to_name = self.project.task_name(to);
+        self.push_undo("Add dependency");
+        self.project.add_dependency(dep);
+        self.project.touch();
+        self.status_message = format!("Linked '{}' → '{}'", from_name, to_name);
+        true
+    }
+
     pub fn delete_task(&mut self, id: Uuid) {
-        self.undo_history.push(&self.project.tasks, &self.project.dependencies);
+        self.push_undo("Delete task");
         // Also delete all children of this task
         let children_ids: Vec<Uuid> = self
             .project
-            .tasks
-            .iter()
-            .find(|t| t.id == id)
+            .task(id)
             .map(|parent| {
                 parent
                     .children_ids(&self.project.tasks)
@@ -445,62 +1396,904 @@ impl GanttApp {
                     .collect()
             })
             .unwrap_or_default();
-        self.project.tasks.retain(|t| t.id != id && t.parent_id != Some(id));
-        self.project.dependencies.retain(|d| {
-            d.from_task != id && d.to_task != id
-            && !children_ids.contains(&d.from_task)
-            && !children_ids.contains(&d.to_task)
+        self.project.remove_tasks(|t| t.id == id || t.parent_id == Some(id));
+        self.project.remove_dependencies(|d| {
+            d.from_task == id || d.to_task == id
+            || children_ids.contains(&d.from_task)
+            || children_ids.contains(&d.to_task)
         });
-        self.project.recalculate_parent_dates();
+        self.project.touch();
         if self.selected_task == Some(id) || children_ids.contains(&self.selected_task.unwrap_or(Uuid::nil())) {
             self.selected_task = None;
         }
         self.status_message = "Task deleted".to_string();
     }
 
+    /// Set the progress of a task the policy leaves to hand entry (clamped
+    /// to 0–1). With `coalesce`, repeated changes to the same task within a
+    /// second share one undo entry.
+    pub fn set_task_progress(&mut self, id: Uuid, progress: f32, coalesce: bool) {
+        let Some(task) = self.project.task(id) else {
+            return;
+        };
+        // Checklist and subtask progress is worked out, not entered.
+        if self.project.progress_source(id) != ProgressSource::Manual {
+            return;
+        }
+        let progress = progress.clamp(0.0, 1.0);
+        if (task.progress - progress).abs() < f32::EPSILON {
+            return;
+        }
+        let label = format!("Set progress of '{}'", task.name);
+        if coalesce {
+            self.push_undo_coalescing(label, std::time::Duration::from_secs(1));
+        } else {
+            self.push_undo(label);
+        }
+        if let Some(task) = self.project.task_mut(id) {
+            task.progress = progress;
+            self.status_message = format!("'{}' at {:.0}%", task.name, progress * 100.0);
+        }
+        self.project.record_progress(chrono::Local::now().date_naive());
+        self.project.touch();
+    }
+
+    /// Recolor a task, optionally together with everything below it, as one undo step.
+    pub fn set_task_color(&mut self, id: Uuid, color: Rgba, include_children: bool) {
+        let mut ids = vec![id];
+        if include_children {
+            ids.extend(self.project.descendant_ids(id));
+        }
+        let label = if include_children { "Recolor task group" } else { "Change color" };
+        self.push_undo(label);
+        for task in self.project.tasks.iter_mut().filter(|t| ids.contains(&t.id)) {
+            task.color = color;
+        }
+        self.project.touch();
+        self.status_message = if include_children {
+            format!("Recolored {} tasks", ids.len())
+        } else {
+            "Color changed".to_string()
+        };
+    }
+
+    /// Apply a bulk edit to every selected task as one undo step.
+    pub fn apply_bulk_edit(&mut self, edit: ui::bulk_editor::BulkEdit) {
+        let ids = self.selection.clone();
+        self.push_undo(format!("Edit {} tasks", ids.len()));
+        let has_children: Vec<Uuid> = ids
+            .iter()
+            .copied()
+            .filter(|id| self.project.tasks.iter().any(|t| t.parent_id == Some(*id)))
+            .collect();
+        let manual: Vec<Uuid> =
+            ids.iter().copied().filter(|&id| self.project.progress_source(id) == ProgressSource::Manual).collect();
+        let nestable: Vec<Uuid> = match edit.parent {
+            Some(Some(parent)) => ids.iter().copied().filter(|&id| self.project.can_set_parent(id, parent)).collect(),
+            _ => ids.clone(),
+        };
+        for &id in &ids {
+            self.project.task_changed(id);
+        }
+        for task in self.project.tasks.iter_mut().filter(|t| ids.contains(&t.id)) {
+            let is_parent = has_children.contains(&task.id);
+            if let Some(priority) = edit.priority {
+                task.priority = priority;
+            }
+            if let Some(color) = edit.color {
+                task.color = color.to_rgba();
+            }
+            // One nesting level: tasks with children stay top-level.
+            if let Some(parent) = edit.parent {
+                if !is_parent && nestable.contains(&task.id) {
+                    task.parent_id = parent;
+                }
+            }
+            // Parent dates are rolled up from children below, and progress
+            // too unless the policy leaves it to hand entry.
+            if !is_parent {
+                if let Some(days) = edit.shift_days.filter(|_| !task.locked) {
+                    task.shift(ShiftAmount::Days(days));
+                }
+            }
+            if let Some(progress) = edit.progress.filter(|_| manual.contains(&task.id)) {
+                task.progress = progress.clamp(0.0, 1.0);
+            }
+            if let Some(tag) = &edit.add_tag {
+                if !task.tags.contains(tag) {
+                    task.tags.push(tag.clone());
+                }
+            }
+            if let Some(tag) = &edit.remove_tag {
+                task.tags.retain(|t| t != tag);
+            }
+        }
+        if edit.parent.is_some() {
+            self.project.sort_tasks_grouped();
+        }
+        self.project.record_progress(chrono::Local::now().date_naive());
+        self.project.touch();
+        self.status_message = format!("Edited {} tasks", ids.len());
+    }
+
+    /// Move `ids` (from [`Project::shift_targets`]) by `amount` as one undo step.
+    pub fn shift_tasks(&mut self, ids: &[Uuid], amount: ShiftAmount) {
+        if ids.is_empty() || amount.is_zero() {
+            return;
+        }
+        let label = format!("Shift {} task{} by {}", ids.len(), if ids.len() == 1 { "" } else { "s" }, amount);
+        self.push_undo(label.clone());
+        self.project.shift_tasks(ids, amount);
+        self.project.touch();
+        self.status_message = label;
+    }
+
+    /// Apply moves from [`crate::model::schedule::resolve_violations`] as one undo step.
+    pub fn apply_reschedule(&mut self, moves: &[ProposedMove]) {
+        if moves.is_empty() {
+            return;
+        }
+        let label = format!("Reschedule {} task{}", moves.len(), if moves.len() == 1 { "" } else { "s" });
+        self.push_undo(label.clone());
+        for m in moves {
+            if let Some(task) = self.project.task_mut(m.task_id) {
+                task.set_dates(m.new_start, m.new_end, chrono::Duration::zero(), FixedEdge::Start);
+            }
+        }
+        self.project.touch();
+        self.status_message = format!("{} to clear dependency violations", label);
+    }
+
+    /// Lay out `parent`'s children as [`crate::model::schedule::chain_children`]
+    /// planned, adding its links too if `link` is set, as one undo step. A
+    /// child that is a parent takes its unlocked subtree along.
+    pub fn arrange_children(&mut self, parent: Uuid, chain: &ChildChain, link: bool) {
+        let links = if link { chain.links.as_slice() } else { &[] };
+        if chain.moves.is_empty() && links.is_empty() {
+            return;
+        }
+        let name = self.project.task_name(parent);
+        let label = format!("Arrange children of '{}'", name);
+        self.push_undo(label.clone());
+        for m in &chain.moves {
+            let delta = m.delta();
+            let mut ids = self.project.descendant_ids(m.task_id);
+            ids.push(m.task_id);
+            for &id in &ids {
+                self.project.task_changed(id);
+            }
+            for task in self.project.tasks.iter_mut().filter(|t| ids.contains(&t.id) && !t.locked) {
+                task.set_dates(task.start + delta, task.end + delta, chrono::Duration::zero(), FixedEdge::Start);
+            }
+        }
+        for dep in links {
+            self.project.add_dependency(dep.clone());
+        }
+        self.project.touch();
+        self.status_message = match (chain.moves.len(), links.len()) {
+            (moved, 0) => format!("Arranged {} task{} under '{}'", moved, if moved == 1 { "" } else { "s" }, name),
+            (moved, linked) => format!(
+                "Arranged {} task{} under '{}' and added {} link{}",
+                moved,
+                if moved == 1 { "" } else { "s" },
+                name,
+                linked,
+                if linked == 1 { "" } else { "s" }
+            ),
+        };
+    }
+
+    /// Reconcile `selection` with `selected_task` after anything that may have
+    /// changed either: a primary selection set elsewhere (chart click, new task)
+    /// collapses the selection to that task, and deleted tasks drop out.
+    fn sync_selection(&mut self) {
+        let project = &self.project;
+        self.selection.retain(|id| project.task(*id).is_some());
+        match self.selected_task {
+            Some(id) if project.task(id).is_none() => {
+                self.selected_task = self.selection.last().copied();
+            }
+            Some(id) if !self.selection.contains(&id) => self.selection = vec![id],
+            None => self.selection.clear(),
+            _ => {}
+        }
+    }
+
+    /// Convert a task to or from a milestone as one undo step.
+    pub fn set_milestone(&mut self, id: Uuid, milestone: bool) {
+        let Some(name) = self.project.task(id).map(|t| t.name.clone()) else {
+            return;
+        };
+        let label = if milestone { "Convert to milestone" } else { "Convert to task" };
+        self.push_undo(label);
+        self.project.set_milestone(id, milestone);
+        self.project.sort_tasks_grouped();
+        self.project.touch();
+        self.status_message = if milestone {
+            format!("'{}' is now a milestone", name)
+        } else {
+            format!("'{}' is now a 1-day task", name)
+        };
+    }
+
+    /// Lock or unlock a task's dates as one undo step.
+    pub fn set_task_locked(&mut self, id: Uuid, locked: bool) {
+        let Some(name) = self.project.task(id).map(|t| t.name.clone()) else {
+            return;
+        };
+        let label = if locked { "Lock task" } else { "Unlock task" };
+        self.push_undo(label);
+        if let Some(task) = self.project.task_mut(id) {
+            task.locked = locked;
+        }
+        self.project.touch();
+        self.status_message = if locked {
+            format!("Locked the dates of '{}'", name)
+        } else {
+            format!("Unlocked '{}'", name)
+        };
+    }
+
+    /// Mark a task tentative or confirmed as one undo step.
+    pub fn set_task_tentative(&mut self, id: Uuid, tentative: bool) {
+        let Some(name) = self.project.task(id).map(|t| t.name.clone()) else {
+            return;
+        };
+        let label = if tentative { "Mark tentative" } else { "Mark confirmed" };
+        self.push_undo(label);
+        if let Some(task) = self.project.task_mut(id) {
+            task.tentative = tentative;
+        }
+        self.project.touch();
+        self.status_message = if tentative {
+            format!("Marked '{}' as tentative", name)
+        } else {
+            format!("Marked '{}' as confirmed", name)
+        };
+    }
+
+    /// Start the timer on `id`, stopping whichever timer was running, or
+    /// stop it if it is the one running. One undo step either way.
+    pub fn toggle_timer(&mut self, id: Uuid) {
+        let Some(name) = self.project.task(id).map(|t| t.name.clone()) else {
+            return;
+        };
+        let now = chrono::Local::now().naive_local();
+        let running = self.project.running_timer().map(|t| t.id);
+        let label = if running == Some(id) { "Stop timer" } else { "Start timer" };
+        self.push_undo(label);
+        if let Some(task) = running.and_then(|r| self.project.task_mut(r)) {
+            for entry in task.time_entries.iter_mut().filter(|e| e.end.is_none()) {
+                entry.end = Some(now);
+            }
+        }
+        if running == Some(id) {
+            self.status_message = format!("Stopped the timer on '{}'", name);
+        } else if let Some(task) = self.project.task_mut(id) {
+            task.time_entries.push(crate::model::task::TimeEntry { start: now, end: None });
+            self.status_message = format!("Timing '{}'", name);
+        }
+        self.project.touch();
+    }
+
+    /// Stop the running timer, if any.
+    pub fn stop_timer(&mut self) {
+        if let Some(id) = self.project.running_timer().map(|t| t.id) {
+            self.toggle_timer(id);
+        }
+    }
+
+    /// Name put on new comments: the one set in the settings, or "Anonymous".
+    pub fn comment_author(&self) -> &str {
+        match self.settings.author_name.trim() {
+            "" => "Anonymous",
+            name => name,
+        }
+    }
+
+    /// Append a comment to a task as one undo step.
+    pub fn add_comment(&mut self, id: Uuid, text: &str) {
+        let text = text.trim();
+        if text.is_empty() || self.project.task(id).is_none() {
+            return;
+        }
+        let comment = crate::model::task::Comment::new(
+            self.comment_author(),
+            text,
+            chrono::Local::now().naive_local(),
+        );
+        self.push_undo("Add comment");
+        if let Some(task) = self.project.task_mut(id) {
+            task.comments.push(comment);
+        }
+        self.project.touch();
+        self.status_message = "Comment added".to_string();
+    }
+
+    /// Remove one comment from a task as one undo step.
+    pub fn delete_comment(&mut self, id: Uuid, comment_id: Uuid) {
+        let exists = self.project.task(id).is_some_and(|t| t.comments.iter().any(|c| c.id == comment_id));
+        if !exists {
+            return;
+        }
+        self.push_undo("Delete comment");
+        if let Some(task) = self.project.task_mut(id) {
+            task.comments.retain(|c| c.id != comment_id);
+        }
+        self.project.touch();
+        self.status_message = "Comment deleted".to_string();
+    }
+
+    /// Nudge the selected task's progress by `delta`, snapped to the 5% grid
+    /// so nudges from odd values land on round numbers.
+    pub fn nudge_selected_progress(&mut self, delta: f32) {
+        let Some(id) = self.selected_task else {
+            return;
+        };
+        if let Some(current) = self.project.task(id).map(|t| t.progress) {
+            let target = ((current + delta) * 20.0).round() / 20.0;
+            self.set_task_progress(id, target, true);
+        }
+    }
+
+    /// Move the selected tasks by `days` from the keyboard, parents with
+    /// their whole subtree, or with `end_only` move just their ends. Days
+    /// are working days while the setting for that is on, and presses in
+    /// quick succession undo as one step.
+    pub fn nudge_selected_dates(&mut self, days: i64, end_only: bool) {
+        if self.selection.is_empty() {
+            return;
+        }
+        let amount = if self.settings.nudge_working_days {
+            ShiftAmount::WorkingDays(days)
+        } else {
+            ShiftAmount::Days(days)
+        };
+        let ids: Vec<Uuid> = if end_only {
+            let parents: HashSet<Uuid> = self.project.tasks.iter().filter_map(|t| t.parent_id).collect();
+            self.selection
+                .iter()
+                .filter_map(|&id| self.project.task(id))
+                .filter(|t| !t.is_milestone && !t.locked && !parents.contains(&t.id))
+                .map(|t| t.id)
+                .collect()
+        } else {
+            self.project.shift_targets(&self.selection, false)
+        };
+        if ids.is_empty() {
+            self.status_message = if self.selection.iter().filter_map(|&id| self.project.task(id)).all(|t| t.locked) {
+                "Locked tasks keep their dates; unlock them to change them".to_string()
+            } else {
+                "Milestones and phases have no end of their own to move".to_string()
+            };
+            return;
+        }
+
+        // Ends stop short of the start as a resize on the chart does, so
+        // a nudge that can't shorten anything changes nothing.
+        let ends: Vec<(Uuid, NaiveDateTime)> = if end_only {
+            ids.iter()
+                .filter_map(|&id| self.project.task(id))
+                .filter_map(|t| {
+                    let mut resized = t.clone();
+                    let min_span = chrono::Duration::days(1).min(t.end - t.start);
+                    resized.set_dates(t.start, amount.apply(t.end), min_span, FixedEdge::Start);
+                    (resized.end != t.end).then_some((t.id, resized.end))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        if end_only && ends.is_empty() {
+            self.status_message = "Already as short as it can be".to_string();
+            return;
+        }
+
+        let label = if end_only { "Nudge end dates" } else { "Nudge dates" };
+        let continues = self.date_nudge.as_ref().is_some_and(|n| {
+            n.ids == ids
+                && n.end_only == end_only
+                && n.at.elapsed() < NUDGE_COALESCE
+                && !self.undo_history.can_redo()
+                && self.undo_history.undo_label() == Some(label)
+        });
+        let total_days = days + if continues { self.date_nudge.as_ref().map_or(0, |n| n.days) } else { 0 };
+        self.push_undo_coalescing(label, NUDGE_COALESCE);
+        if end_only {
+            for &(id, end) in &ends {
+                if let Some(task) = self.project.task_mut(id) {
+                    task.end = end;
+                }
+            }
+        } else {
+            self.project.shift_tasks(&ids, amount);
+        }
+        self.project.touch();
+
+        let total = match amount {
+            ShiftAmount::Days(_) => ShiftAmount::Days(total_days),
+            ShiftAmount::WorkingDays(_) => ShiftAmount::WorkingDays(total_days),
+        };
+        let span = self.project.tasks.iter().filter(|t| ids.contains(&t.id)).fold(None, |span, t| {
+            Some(span.map_or((t.start, t.end), |(s, e): (NaiveDateTime, NaiveDateTime)| (s.min(t.start), e.max(t.end))))
+        });
+        let dates = span.map_or_else(String::new, |(start, end)| {
+            if start == end {
+                format_datetime(start, "date.month_day")
+            } else {
+                format!("{} – {}", format_datetime(start, "date.month_day"), format_datetime(end, "date.month_day"))
+            }
+        });
+        self.status_message = format!("{} {} → {}", if end_only { "Moved end" } else { "Moved" }, total, dates);
+        self.date_nudge = Some(DateNudge { ids, end_only, days: total_days, at: std::time::Instant::now() });
+    }
+
+    /// Move a task to another group of the grouped list by changing the
+    /// field the list is grouped by, as one undo step.
+    pub fn regroup_task(&mut self, id: Uuid, key: GroupKey) {
+        let Some(task) = self.project.task(id) else {
+            return;
+        };
+        if GroupKey::of(task, self.group_by).as_ref() == Some(&key) {
+            return;
+        }
+        let name = task.name.clone();
+        self.push_undo(format!("Regroup '{}'", name));
+        if let Some(task) = self.project.task_mut(id) {
+            key.apply(task);
+        }
+        self.project.touch();
+        self.status_message = format!("Moved '{}' to another group", name);
+    }
+
+    /// Frame the selected tasks, and everything under selected parents, in
+    /// the chart and scroll the first of them into view. With nothing
+    /// selected, fit the whole project instead.
+    pub fn zoom_to_selection(&mut self, ctx: &egui::Context) {
+        self.main_view = ui::view_tabs::MainView::Gantt;
+        let mut ids = self.selection.clone();
+        for id in ids.clone() {
+            ids.extend(self.project.descendant_ids(id));
+        }
+        let selected = self.project.tasks.iter().filter(|t| ids.contains(&t.id));
+        let Some(first) = selected.clone().next() else {
+            ui::gantt_chart::fit_to_window(ctx);
+            return;
+        };
+        let start = selected.clone().map(|t| t.start).min().unwrap_or(first.start);
+        let end = selected.map(|t| t.end).max().unwrap_or(first.end);
+        ui::gantt_chart::zoom_to_range(ctx, start, end, first.id);
+    }
+
+    /// Copy the chart as shown, or with `whole_project` every row of the
+    /// whole timeline fitted to the window's width, to the clipboard as an
+    /// image. The copy finishes a few frames later and leaves the view as it
+    /// was; see [`ui::chart_capture`].
+    pub fn copy_chart_image(&mut self, ctx: &egui::Context, whole_project: bool) {
+        if self.chart_capture.is_busy() {
+            return;
+        }
+        self.main_view = ui::view_tabs::MainView::Gantt;
+        if whole_project {
+            self.chart_capture.request_whole(ctx, &self.viewport);
+        } else {
+            self.chart_capture.request(ctx);
+        }
+    }
+
+    /// Switch to the chart and scroll it to today, widening the viewport
+    /// if today lies outside it.
+    pub fn go_to_today(&mut self, ctx: &egui::Context) {
+        let now = chrono::Local::now().naive_local();
+        if now < self.viewport.start {
+            self.viewport.set_range(now - chrono::Duration::days(7), self.viewport.end);
+        }
+        if now > self.viewport.end {
+            self.viewport.set_range(self.viewport.start, now + chrono::Duration::days(7));
+        }
+        self.main_view = ui::view_tabs::MainView::Gantt;
+        ui::gantt_chart::scroll_to_today(ctx);
+    }
+
+    /// Chains to highlight for the selected task, if tracing is on and it
+    /// has any links. Reachability is only recomputed when the selected
+    /// task or the dependency graph changes.
+    fn dependency_trace(&mut self) -> Option<Arc<DependencyTrace>> {
+        if !self.settings.trace_dependencies || self.selection.len() > 1 {
+            return None;
+        }
+        let root = self.selected_task.filter(|id| self.trace_dismissed != Some(*id))?;
+        let graph = self.project.dependency_graph();
+        if !graph.is_linked(root) {
+            return None;
+        }
+        match &self.trace_cache {
+            Some((cached, trace)) if trace.root == root && Arc::ptr_eq(cached, &graph) => Some(trace.clone()),
+            _ => {
+                let trace = Arc::new(DependencyTrace::new(&graph, root));
+                self.trace_cache = Some((graph, trace.clone()));
+                Some(trace)
+            }
+        }
+    }
+
+    /// Push an undo snapshot before an edit. The edit goes in the activity
+    /// log once it settles, or when the next one starts.
+    pub fn push_undo(&mut self, label: impl Into<String>) {
+        self.flush_activity();
+        let generation = self.project.generation();
+        let unchanged = self.undo_generation == Some(generation)
+            && !self.project.has_untouched_changes()
+            && !self.undo_history.can_redo();
+        if unchanged {
+            // The last action changed nothing, so its snapshot still holds
+            // this state; reuse it rather than copying the project again.
+            self.undo_history.relabel(label);
+        } else {
+            self.undo_history.push(label, &self.project.tasks, &self.project.dependencies);
+        }
+        self.undo_generation = Some(generation);
+        self.activity_baseline = Some(ActivityBaseline::UndoTop);
+    }
+
+    /// Like [`push_undo`](Self::push_undo), folding the edit into the
+    /// previous undo step when that had the same label and was less than
+    /// `window` ago.
+    pub fn push_undo_coalescing(&mut self, label: impl Into<String>, window: std::time::Duration) {
+        self.flush_activity();
+        let pushed = self.undo_history.push_coalescing(label, window, &self.project.tasks, &self.project.dependencies);
+        self.undo_generation = pushed.then(|| self.project.generation());
+        self.activity_baseline = Some(if pushed {
+            ActivityBaseline::UndoTop
+        } else {
+            ActivityBaseline::Copy {
+                tasks: self.project.tasks.clone(),
+                dependencies: self.project.dependencies.clone(),
+            }
+        });
+    }
+
+    /// Log the pending edit once it has settled: not while a drag is in
+    /// progress, so a drag logs only where the task ended up.
+    fn record_activity(&mut self, ctx: &egui::Context) {
+        if self.activity_baseline.is_some() && !ctx.input(|i| i.pointer.any_down()) {
+            self.flush_activity();
+        }
+    }
+
+    /// Log what the pending edit changed.
+    fn flush_activity(&mut self) {
+        let Some(baseline) = self.activity_baseline.take() else {
+            return;
+        };
+        let (tasks, dependencies) = match &baseline {
+            ActivityBaseline::UndoTop => match self.undo_history.undo_snapshot() {
+                Some(snap) => (&snap.tasks, &snap.dependencies),
+                None => return,
+            },
+            ActivityBaseline::Copy { tasks, dependencies } => (tasks, dependencies),
+        };
+        let events = crate::model::activity::diff(
+            tasks,
+            dependencies,
+            &self.project.tasks,
+            &self.project.dependencies,
+            chrono::Local::now().naive_local(),
+        );
+        self.log_activity(events);
+    }
+
+    fn log_activity(&mut self, events: Vec<crate::model::activity::ChangeEvent>) {
+        for event in events {
+            self.project.activity.record(event, self.settings.activity_limit);
+        }
+    }
+
+    /// Log what restoring `snap` changes, as undo and redo do.
+    fn log_restore(&mut self, snap: &crate::model::history::ProjectSnapshot) {
+        let events = crate::model::activity::diff(
+            &self.project.tasks,
+            &self.project.dependencies,
+            &snap.tasks,
+            &snap.dependencies,
+            chrono::Local::now().naive_local(),
+        );
+        self.log_activity(events);
+    }
+
+    /// Refresh the reminder list once a minute and send a desktop
+    /// notification for each task newly coming due (once per task per day).
+    pub fn check_reminders(&mut self, ctx: &egui::Context) {
+        const INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+        if self.last_reminder_check.is_some_and(|t| t.elapsed() < INTERVAL) {
+            return;
+        }
+        self.last_reminder_check = Some(std::time::Instant::now());
+        ctx.request_repaint_after(INTERVAL);
+
+        let prefs = &self.settings.notifications;
+        let now = chrono::Local::now().naive_local();
+        let today = now.date();
+        let upcoming = crate::model::reminders::upcoming_reminders(
+            &self.project.tasks,
+            now,
+            chrono::Duration::hours(prefs.lead_hours as i64),
+            &prefs.priorities,
+        );
+        let fresh: Vec<&Reminder> =
+            upcoming.iter().filter(|r| self.reminder_log.should_notify(r.task_id, today)).collect();
+        if prefs.enabled && !fresh.is_empty() {
+            let (summary, body) = match fresh.as_slice() {
+                [one] => (
+                    format!("'{}' is due {}", one.name, one.due_in(now)),
+                    format!("Due {}", one.due.format("%a %b %d %H:%M")),
+                ),
+                many => (
+                    format!("{} tasks due within {}h", many.len(), prefs.lead_hours),
+                    many.iter().take(5).map(|r| format!("• {} ({})", r.name, r.due_in(now))).collect::<Vec<_>>().join("\n"),
+                ),
+            };
+            crate::io::desktop_notify::notify(summary, body);
+        }
+        for id in fresh.iter().map(|r| r.task_id).collect::<Vec<_>>() {
+            self.reminder_log.mark_notified(id, today);
+        }
+        self.reminders = upcoming
+            .into_iter()
+            .filter(|r| !self.reminder_log.is_snoozed(r.task_id, today))
+            .collect();
+    }
+
+    /// Hide a reminder and stop it notifying until tomorrow.
+    pub fn snooze_reminder(&mut self, id: Uuid) {
+        self.reminder_log.snooze(id, chrono::Local::now().date_naive());
+        self.reminders.retain(|r| r.task_id != id);
+    }
+
+    /// Run the registered command `id`, if it exists and is enabled.
+    pub fn run_command(&mut self, ctx: &egui::Context, id: &str) {
+        let Some(command) = self.commands.get(id) else {
+            return;
+        };
+        if command.is_enabled(self) {
+            let run = command.run;
+            run(self, ctx);
+        }
+    }
+
+    /// Select `id` and scroll the chart to it, expanding collapsed parents
+    /// and widening the viewport if the task lies outside it.
+    pub fn reveal_task(&mut self, ctx: &egui::Context, id: Uuid) {
+        let Some(task) = self.project.task(id) else {
+            return;
+        };
+        let (start, end) = (task.start, task.end);
+        let mut parent = task.parent_id;
+        while let Some(pid) = parent {
+            let Some(p) = self.project.task_mut(pid) else {
+                break;
+            };
+            p.collapsed = false;
+            parent = p.parent_id;
+        }
+        if start < self.viewport.start {
+            self.viewport.set_range(start - chrono::Duration::days(7), self.viewport.end);
+        }
+        if end > self.viewport.end {
+            self.viewport.set_range(self.viewport.start, end + chrono::Duration::days(7));
+        }
+        self.selected_task = Some(id);
+        self.selection = vec![id];
+        ui::gantt_chart::reveal_task(ctx, id);
+    }
+
+    /// Give a task to another person (`None` = unassigned) as one undo step.
+    pub fn reassign_task(&mut self, id: Uuid, assignee: Option<String>) {
+        self.push_undo("Reassign task");
+        if let Some(task) = self.project.task_mut(id) {
+            self.status_message = match &assignee {
+                Some(name) => format!("Assigned '{}' to {}", task.name, name),
+                None => format!("Unassigned '{}'", task.name),
+            };
+            task.assignee = assignee;
+        }
+        self.project.touch();
+    }
+
+    /// Rename a task in place. Blank or unchanged names are ignored.
+    pub fn rename_task(&mut self, id: Uuid, name: String) {
+        let name = name.trim().to_string();
+        let Some(old) = self.project.task(id).map(|t| t.name.clone()) else {
+            return;
+        };
+        if name.is_empty() || name == old {
+            return;
+        }
+        self.push_undo("Rename task");
+        if let Some(task) = self.project.task_mut(id) {
+            task.name = name.clone();
+        }
+        self.project.touch();
+        self.status_message = format!("Renamed '{}' → '{}'", old, name);
+    }
+
     fn reset_dialog_fields(&mut self) {
         let today = chrono::Local::now().naive_local();
-        let start_time = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
-        let end_time = NaiveTime::from_hms_opt(17, 0, 0).unwrap();
+        let defaults = self.project.task_defaults;
 
         self.new_task_name = String::new();
         self.new_task_start = today.format("%Y-%m-%d %H:%M").to_string();
         self.new_task_end = (today + chrono::Duration::days(7))
             .format("%Y-%m-%d %H:%M")
             .to_string();
-        self.new_task_start_date = today.date().and_time(start_time);
-        self.new_task_end_date = (today + chrono::Duration::days(7)).date().and_time(end_time);
+        (self.new_task_start_date, self.new_task_end_date) = defaults.span(today.date(), defaults.duration_days);
         self.new_task_is_milestone = false;
     }
 
+    // --- Settings ---
+
+    /// Switch the active theme and remember the choice.
+    pub fn set_theme(&mut self, index: usize) {
+        self.theme_manager.set_active(index);
+        self.settings.active_theme = self.theme_manager.active().meta.name.clone();
+        self.settings.save();
+    }
+
+    /// Switch to one of the quick appearance presets.
+    pub fn set_theme_preset(&mut self, preset: ThemePreset) {
+        self.theme_manager.set_active_by_name(preset.theme_name());
+        self.settings.active_theme = self.theme_manager.active().meta.name.clone();
+        self.settings.save();
+        self.status_message = format!("Appearance: {}", preset.label());
+    }
+
+    /// Switch to the workspace at `index` in the settings: its panels,
+    /// timeline scale and theme. The project and selection are untouched.
+    pub fn apply_workspace(&mut self, index: usize) {
+        let Some(workspace) = self.settings.workspaces.get(index).cloned() else {
+            return;
+        };
+        self.settings.set_panels(workspace.panels);
+        self.viewport.scale = workspace.scale;
+        if let Some(theme) = &workspace.theme {
+            self.theme_manager.set_active_by_name(theme);
+            self.settings.active_theme = self.theme_manager.active().meta.name.clone();
+        }
+        self.settings.active_workspace = Some(workspace.name.clone());
+        self.settings.save();
+        self.status_message = format!("Workspace: {}", workspace.name);
+    }
+
+    /// Store the current panels, timeline scale and theme in the workspace
+    /// at `index`.
+    pub fn update_workspace(&mut self, index: usize) {
+        let panels = self.settings.panels();
+        let theme = self.settings.active_theme.clone();
+        let Some(workspace) = self.settings.workspaces.get_mut(index) else {
+            return;
+        };
+        workspace.panels = panels;
+        workspace.scale = self.viewport.scale;
+        workspace.theme = Some(theme);
+        self.status_message = format!("Workspace '{}' updated", workspace.name);
+        self.settings.save();
+    }
+
+    /// Put back the built-in workspaces, dropping changes made to them.
+    pub fn reset_workspaces(&mut self) {
+        self.settings.workspaces = crate::settings::Workspace::builtins();
+        self.settings.save();
+        self.status_message = "Workspaces reset".to_string();
+    }
+
+    /// Set (or clear, with `None`) the accent colour override and persist it.
+    pub fn set_accent_color(&mut self, color: Option<egui::Color32>) {
+        self.theme_manager.set_accent(color);
+        self.settings.accent_color = color.map(ui::theme_overrides::to_hex);
+        self.settings.save();
+    }
+
+    /// Open `theme.toml` in the system editor, creating a commented template first.
+    pub fn open_theme_overrides(&mut self) {
+        let path = ui::theme_overrides::default_path();
+        if !path.exists() {
+            let _ = std::fs::write(&path, ui::theme_overrides::TEMPLATE);
+        }
+        if let Err(e) = open::that(&path) {
+            self.status_message = format!("Could not open {}: {}", path.display(), e);
+        }
+    }
+
+    /// Set the UI scale (egui zoom factor) and persist it.
+    pub fn set_ui_scale(&mut self, ctx: &egui::Context, scale: f32) {
+        let scale = scale.clamp(*UI_SCALE_RANGE.start(), *UI_SCALE_RANGE.end());
+        ctx.set_zoom_factor(scale);
+        self.settings.ui_scale = scale;
+        self.settings.save();
+    }
+
+    /// Switch between compact and comfortable task rows and persist it.
+    pub fn set_row_density(&mut self, density: RowDensity) {
+        self.settings.row_density = density;
+        self.settings.save();
+    }
+
+    /// Remember the current task panel width.
+    fn save_task_panel_width(&mut self) {
+        self.settings.task_panel_width = Some(self.task_panel_width);
+        self.settings.save();
+    }
+
     fn recalculate_viewport(&mut self) {
         if let (Some(min), Some(max)) = (
             self.project.tasks.iter().map(|t| t.start).min(),
             self.project.tasks.iter().map(|t| t.end).max(),
         ) {
-            self.viewport.start = min - chrono::Duration::days(7);
-            self.viewport.end = max + chrono::Duration::days(30);
+            self.viewport.set_range(min - chrono::Duration::days(7), max + chrono::Duration::days(30));
         }
     }
 }
 
 impl eframe::App for GanttApp {
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.settings.session = self.session();
+        self.settings.save();
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.poll_saves();
+        match self.chart_capture.poll(ctx, &mut self.viewport) {
+            Some(Ok([width, height])) => {
+                self.toasts.info(format!("Copied a {}×{} px image of the chart", width, height));
+            }
+            Some(Err(e)) => {
+                self.toasts.error(format!("Couldn't copy the chart image: {}", e));
+            }
+            None => {}
+        }
+        self.poll_save_hooks();
+        self.sync_selection();
+        self.check_reminders(ctx);
+        self.record_activity(ctx);
+        self.poll_github_import();
+        if self.project.running_timer().is_some() {
+            // Keep the status bar's timer ticking.
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+        }
+        if self.theme_manager.poll_overrides() {
+            self.status_message = self
+                .theme_manager
+                .override_warnings()
+                .first()
+                .cloned()
+                .unwrap_or_else(|| "Reloaded theme.toml".to_string());
+        }
         ui::theme::set_active(self.theme_manager.active());
+        ui::theme::set_density(self.settings.row_density);
+        ui::theme::set_color_blind_safe(self.settings.color_blind_safe);
+        crate::i18n::set_language(self.settings.language);
+        // Keep the saved scale in step with egui's own Ctrl +/- zoom.
+        if (ctx.zoom_factor() - self.settings.ui_scale).abs() > f32::EPSILON {
+            self.set_ui_scale(ctx, ctx.zoom_factor());
+        }
         ui::theme::apply_theme(ctx);
 
-        // Handle keyboard shortcuts outside closures to avoid borrow issues
-        let should_save = ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::S));
-        let should_undo = ctx.input(|i| i.modifiers.ctrl && !i.modifiers.shift && i.key_pressed(egui::Key::Z));
-        let should_redo = ctx.input(|i| i.modifiers.ctrl && (i.key_pressed(egui::Key::Y) || (i.modifiers.shift && i.key_pressed(egui::Key::Z))));
-        if should_save {
-            self.save_project();
-        }
-        if should_undo {
-            self.undo();
+        // Keyboard shortcuts go through the keymap; handled outside the
+        // panel closures to avoid borrow issues.
+        if !ui::shortcuts_dialog::is_capturing(ctx) {
+            let typing = ctx.wants_keyboard_input();
+            let pressed = ctx.input_mut(|i| self.keymap.pressed(i, typing));
+            for id in pressed {
+                self.run_command(ctx, id);
+            }
         }
-        if should_redo {
-            self.redo();
+        // Escape clears the dependency trace (dialogs close on it too).
+        if self.selected_task.is_some()
+            && !self.show_command_palette
+            && !ctx.wants_keyboard_input()
+            && ctx.input(|i| i.key_pressed(egui::Key::Escape))
+        {
+            self.trace_dismissed = self.selected_task;
         }
 
         // Handle pending subtask additions (from editor inside panel closure)
@@ -508,19 +2301,13 @@ impl eframe::App for GanttApp {
             self.add_subtask(parent_id);
         }
         if let Some(dep) = self.pending_add_dependency.take() {
-            let exists = self.project.dependencies.iter().any(|d| {
-                d.from_task == dep.from_task && d.to_task == dep.to_task
-            });
-            if !exists {
-                let from_name = self.project.tasks.iter().find(|t| t.id == dep.from_task).map(|t| t.name.clone()).unwrap_or_default();
-                let to_name   = self.project.tasks.iter().find(|t| t.id == dep.to_task  ).map(|t| t.name.clone()).unwrap_or_default();
-                self.undo_history.push(&self.project.tasks, &self.project.dependencies);
-                self.project.dependencies.push(dep);
-                self.project.touch();
-                self.status_message = format!("Linked '{}' → '{}'", from_name, to_name);
-            }
+            self.add_dependency(dep);
         }
 
+        // What the panels below draw; anything that changes the project after
+        // they have drawn it asks for another frame at the end.
+        let drawn_generation = self.project.generation();
+
         // Top panel: toolbar
         egui::TopBottomPanel::top("toolbar").show(ctx, |ui| {
             ui::toolbar::show_toolbar(self, ui);
@@ -535,63 +2322,103 @@ impl eframe::App for GanttApp {
                     .inner_margin(egui::Margin::symmetric(10.0, 0.0)),
             )
             .show(ctx, |ui| {
-                ui.horizontal_centered(|ui| {
-                    ui.label(
-                        egui::RichText::new(&self.status_message)
-                            .font(ui::theme::font_status())
-                            .color(ui::theme::text_secondary()),
-                    );
-                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        ui.label(
-                            egui::RichText::new(format!("Tasks: {}", self.project.tasks.len()))
-                                .size(10.5)
-                                .color(ui::theme::text_dim()),
-                        );
-                        ui.label(
-                            egui::RichText::new(" · ")
-                                .size(10.5)
-                                .color(ui::theme::text_dim()),
-                        );
-                        let default_ppd = ui::theme::zoom().default_pixels_per_day;
-                        ui.label(
-                            egui::RichText::new(format!(
-                                "Zoom: {:.0}%",
-                                self.viewport.pixels_per_day / default_ppd * 100.0
-                            ))
-                            .size(10.5)
-                            .color(ui::theme::text_dim()),
-                        );
-                    });
-                });
+                ui::status_bar::show_status_bar(self, ui);
             });
 
-        // Left panel: task table + editor
+        // Left panel: task table + editor.
+        // The width is owned by the app (not egui's panel memory) so the panel
+        // never grows from its own content; the divider below resizes it.
+        let max_panel_width = (ctx.screen_rect().width() - ui::theme::chart_min_width())
+            .max(ui::theme::side_panel_min_width());
+        self.task_panel_width = self
+            .task_panel_width
+            .clamp(ui::theme::side_panel_min_width(), max_panel_width);
+
         let mut task_action = ui::task_table::TaskTableAction::None;
+        // Task changed in the editor, and whether its parent changed too.
         let mut editor_changed = false;
         let mut dep_remove: Option<(Uuid, Uuid)> = None;
-        egui::SidePanel::left("task_panel")
-            .default_width(ui::theme::side_panel_default_width())
-            .min_width(ui::theme::side_panel_min_width())
-            .max_width(ui::theme::side_panel_default_width() * 2.0)
-            .resizable(true)
+        let mut dep_update: Option<(Uuid, Uuid, DependencyKind, i64)> = None;
+        let mut color_change: Option<(Uuid, egui::Color32, bool, bool)> = None;
+        let mut progress_change: Option<(Uuid, f32)> = None;
+        let mut milestone_toggle: Option<(Uuid, bool)> = None;
+        let mut lock_toggle: Option<(Uuid, bool)> = None;
+        let mut tentative_toggle: Option<(Uuid, bool)> = None;
+        let mut comment_add: Option<(Uuid, String)> = None;
+        let mut comment_delete: Option<(Uuid, Uuid)> = None;
+        let mut timer_toggle: Option<Uuid> = None;
+        let mut validation_fix: Option<(Uuid, crate::model::validation::ValidationFix)> = None;
+        let mut bulk_edit: Option<ui::bulk_editor::BulkEdit> = None;
+        // The rows the list and the chart both show, worked out once here so
+        // they list the same tasks even if the list changes them.
+        let groups = group_tasks(&self.project.tasks, self.group_by);
+        let due_states = self.project.due_states();
+        let rows = ui::rows::build_rows(
+            &self.project.tasks,
+            &ui::rows::RowFilter {
+                search_query: &self.search_query,
+                priority: self.filter_priority,
+                completed: self.project.completed_tasks,
+                hide_tentative: self.hide_tentative,
+                risk: self.filter_risk,
+                due: self.filter_due,
+                due_states: &due_states,
+            },
+            &groups,
+            &self.collapsed_groups,
+        );
+        let panel_response = egui::SidePanel::left("task_panel")
+            .exact_width(self.task_panel_width)
+            .resizable(false)
             .frame(
                 egui::Frame::default()
                     .fill(ui::theme::bg_panel())
                     .inner_margin(egui::Margin::same(ui::theme::layout().panel_inner_margin))
                     .stroke(egui::Stroke::new(1.0, ui::theme::border_subtle())),
             )
-            .show(ctx, |ui| {
-                // If a task is selected, show editor at the top
-                if let Some(sel_id) = self.selected_task {
-                    let deps_snapshot: Vec<_> = self.project.dependencies.clone();
-                    let tasks_snapshot: Vec<_> = self.project.tasks.clone();
-                    if let Some(task) = self.project.tasks.iter_mut().find(|t| t.id == sel_id) {
+            .show_animated(ctx, self.settings.show_task_list, |ui| {
+                // Several tasks selected: bulk editor instead of the task editor
+                if self.selection.len() > 1 {
+                    match ui::bulk_editor::show_bulk_editor(&self.project.tasks, &self.selection, ui) {
+                        ui::bulk_editor::BulkEditorAction::Apply(edit) => bulk_edit = Some(edit),
+                        ui::bulk_editor::BulkEditorAction::ClearSelection => {
+                            self.selection.clear();
+                            self.selected_task = None;
+                        }
+                        ui::bulk_editor::BulkEditorAction::None => {}
+                    }
+                    ui.add_space(4.0);
+                    ui.separator();
+                    ui.add_space(2.0);
+                } else if let Some(sel_id) = self.selected_task.filter(|_| self.settings.show_editor) {
+                    // If a task is selected, show editor at the top
+                    let graph = self.project.dependency_graph();
+                    if let Some(i) = self.project.task_index(sel_id) {
+                        // Edit a copy of the one task rather than cloning the whole
+                        // list each frame; the list still holds the pre-edit state
+                        // for the undo snapshot below.
+                        let mut edited = self.project.tasks[i].clone();
                         let result = ui::task_editor::show_task_editor(
-                            task,
-                            &tasks_snapshot,
-                            &deps_snapshot,
+                            &mut edited,
+                            &self.project,
+                            &graph,
+                            self.comment_author(),
                             ui,
                         );
+                        if matches!(result, ui::task_editor::EditorAction::Changed) {
+                            self.push_undo_coalescing("Edit task", std::time::Duration::from_secs(1));
+                        }
+                        let reparented = edited.parent_id != self.project.tasks[i].parent_id;
+                        if reparented && edited.parent_id.is_some_and(|p| !self.project.can_set_parent(sel_id, p)) {
+                            edited.parent_id = self.project.tasks[i].parent_id;
+                            self.toasts.error("A task can't be nested under itself or its subtasks");
+                        }
+                        // Only an edit needs rolling up; an idle editor leaves
+                        // nothing pending for the next touch or undo push.
+                        if reparented || !matches!(result, ui::task_editor::EditorAction::None) {
+                            self.project.task_changed(sel_id);
+                        }
+                        self.project.tasks[i] = edited;
                         match result {
                             ui::task_editor::EditorAction::Changed => {
                                 editor_changed = true;
@@ -605,6 +2432,39 @@ impl eframe::App for GanttApp {
                             ui::task_editor::EditorAction::AddDependency(dep) => {
                                 self.pending_add_dependency = Some(dep);
                             }
+                            ui::task_editor::EditorAction::ArrangeChildren(parent_id) => {
+                                self.pending_arrange = Some(parent_id);
+                            }
+                            ui::task_editor::EditorAction::UpdateDependency { from, to, kind, lag } => {
+                                dep_update = Some((from, to, kind, lag));
+                            }
+                            ui::task_editor::EditorAction::SetProgress(progress) => {
+                                progress_change = Some((sel_id, progress));
+                            }
+                            ui::task_editor::EditorAction::SetColor { color, include_children, custom } => {
+                                color_change = Some((sel_id, color, include_children, custom));
+                            }
+                            ui::task_editor::EditorAction::ApplyFix(fix) => {
+                                validation_fix = Some((sel_id, fix));
+                            }
+                            ui::task_editor::EditorAction::SetMilestone(milestone) => {
+                                milestone_toggle = Some((sel_id, milestone));
+                            }
+                            ui::task_editor::EditorAction::SetLocked(locked) => {
+                                lock_toggle = Some((sel_id, locked));
+                            }
+                            ui::task_editor::EditorAction::SetTentative(tentative) => {
+                                tentative_toggle = Some((sel_id, tentative));
+                            }
+                            ui::task_editor::EditorAction::AddComment(text) => {
+                                comment_add = Some((sel_id, text));
+                            }
+                            ui::task_editor::EditorAction::DeleteComment(comment_id) => {
+                                comment_delete = Some((sel_id, comment_id));
+                            }
+                            ui::task_editor::EditorAction::ToggleTimer => {
+                                timer_toggle = Some(sel_id);
+                            }
                             ui::task_editor::EditorAction::None => {}
                         }
                     }
@@ -613,27 +2473,83 @@ impl eframe::App for GanttApp {
                     ui.add_space(2.0);
                 }
 
-                // Filter bar
-                ui::filter_bar::show_filter_bar(
-                    &mut self.search_query,
-                    &mut self.filter_priority,
-                    ui,
-                );
-                ui.add_space(2.0);
+                // Filter bar; while it's hidden, a reminder that a filter
+                // still applies.
+                if self.settings.show_filter_bar {
+                    let filter_bar = ui.scope(|ui| {
+                        ui::filter_bar::show_filter_bar(
+                            &mut self.search_query,
+                            &mut self.filter_priority,
+                            &mut self.filter_risk,
+                            &mut self.filter_due,
+                            &mut self.hide_tentative,
+                            ui,
+                        )
+                    });
+                    ui::onboarding::mark_target(ctx, CoachTarget::FilterBar, filter_bar.response.rect);
+                    ui.add_space(2.0);
+                } else if self.is_filtering() && ui::filter_bar::show_hidden_filter_note(ui) {
+                    self.search_query.clear();
+                    self.filter_priority = None;
+                    self.filter_risk = None;
+                    self.filter_due = None;
+                    self.hide_tentative = false;
+                }
 
+                let highlights = self.project.highlights();
+                let links = self.project.dependency_graph();
                 task_action = ui::task_table::show_task_table(
                     &self.project.tasks,
                     self.selected_task,
-                    &self.search_query,
-                    self.filter_priority,
+                    &self.selection,
+                    ui::task_table::TableOptions {
+                        completed: self.project.completed_tasks,
+                        due_states: &due_states,
+                        now: self.project.clock().now(),
+                        group_by: self.group_by,
+                        rows: &rows,
+                        collapsed_groups: &self.collapsed_groups,
+                        highlights: &highlights,
+                        wrap_names: self.settings.wrap_task_names,
+                        links: &links,
+                    },
                     ui,
                 );
             });
 
+        if let Some(panel) = &panel_response {
+            // The editor opens at the top of the panel.
+            ui::onboarding::mark_target(ctx, CoachTarget::Editor, panel.response.rect);
+        }
+
+        // Divider between the task panel and the chart: drag to resize,
+        // double-click to fit the widest visible task name.
+        let divider = panel_response.map(|panel| ui::task_table::show_panel_divider(ctx, panel.response.rect));
+        if let Some(divider) = divider {
+            if divider.dragged() {
+                self.task_panel_width = (self.task_panel_width + divider.drag_delta().x)
+                    .clamp(ui::theme::side_panel_min_width(), max_panel_width);
+            }
+            if divider.double_clicked() {
+                let fit = ui::task_table::fit_width(
+                    &self.project.tasks,
+                    &rows.rows,
+                    &self.project.highlights(),
+                    &due_states,
+                    ctx,
+                );
+                self.task_panel_width = fit.clamp(ui::theme::side_panel_min_width(), max_panel_width);
+                self.save_task_panel_width();
+            } else if divider.drag_stopped() {
+                self.save_task_panel_width();
+            }
+        }
+
         // Handle task table actions
         match task_action {
             ui::task_table::TaskTableAction::Select(id) => {
                 self.selected_task = Some(id);
+                self.selection = vec![id];
             }
             ui::task_table::TaskTableAction::Delete(id) => {
                 self.delete_task(id);
@@ -642,47 +2558,282 @@ impl eframe::App for GanttApp {
                 self.show_add_task = true;
             }
             ui::task_table::TaskTableAction::ToggleCollapse(id) => {
-                if let Some(task) = self.project.tasks.iter_mut().find(|t| t.id == id) {
+                if let Some(task) = self.project.task_mut(id) {
                     task.collapsed = !task.collapsed;
                     self.project.touch();
                 }
             }
+            ui::task_table::TaskTableAction::Rename(id, name) => {
+                self.rename_task(id, name);
+            }
+            ui::task_table::TaskTableAction::ToggleSelect(id) => {
+                if let Some(pos) = self.selection.iter().position(|s| *s == id) {
+                    self.selection.remove(pos);
+                    if self.selected_task == Some(id) {
+                        self.selected_task = self.selection.last().copied();
+                    }
+                } else {
+                    self.selection.push(id);
+                    self.selected_task = Some(id);
+                }
+            }
+            ui::task_table::TaskTableAction::SelectRange(ids) => {
+                self.selected_task = ids.last().copied();
+                self.selection = ids;
+            }
+            ui::task_table::TaskTableAction::QuickAdd(entry) => {
+                self.quick_add_task(&entry);
+            }
+            ui::task_table::TaskTableAction::SetGroupBy(by) => {
+                self.group_by = by;
+            }
+            ui::task_table::TaskTableAction::ToggleGroup(key) => {
+                if !self.collapsed_groups.remove(&key) {
+                    self.collapsed_groups.insert(key);
+                }
+            }
+            ui::task_table::TaskTableAction::FilterDue(state) => {
+                self.filter_due = (self.filter_due != Some(state)).then_some(state);
+            }
+            ui::task_table::TaskTableAction::Regroup(id, key) => {
+                self.regroup_task(id, key);
+            }
+            ui::task_table::TaskTableAction::Link { from, to, at } => {
+                let kind = self.project.task_defaults.dependency_kind;
+                ui::gantt_chart::request_link(ctx, from, to, at, kind);
+            }
             ui::task_table::TaskTableAction::None => {}
         }
 
         // If the editor modified the task, mark project dirty
         if editor_changed {
-            self.project.recalculate_parent_dates();
+            self.project.record_progress(chrono::Local::now().date_naive());
             self.project.touch();
             self.status_message = "Task updated".to_string();
         }
         // Handle dependency removal from editor
-        if let Some((from, to)) = dep_remove {
-            self.undo_history.push(&self.project.tasks, &self.project.dependencies);
-            self.project.dependencies.retain(|d| {
-                !(d.from_task == from && d.to_task == to)
-            });
+        if let Some(link) = dep_remove {
+            self.remove_links(&[link]);
+        }
+        if let Some(edit) = bulk_edit {
+            self.apply_bulk_edit(edit);
+        }
+        // Handle quick progress buttons from editor
+        if let Some((id, progress)) = progress_change {
+            self.set_task_progress(id, progress, false);
+        }
+        // Handle colour changes from editor
+        if let Some((id, color, include_children, custom)) = color_change {
+            let color = color.to_rgba();
+            self.set_task_color(id, color, include_children);
+            if custom {
+                self.project.remember_color(color);
+            }
+        }
+        // Handle one-click fixes from the editor's warnings
+        if let Some((id, fix)) = validation_fix {
+            self.push_undo("Fix schedule");
+            if let Some(task) = self.project.task_mut(id) {
+                fix.apply(task);
+                self.status_message = format!(
+                    "Fixed '{}' ({} → {})",
+                    task.name,
+                    task.start.format("%Y-%m-%d"),
+                    task.end.format("%Y-%m-%d")
+                );
+            }
             self.project.touch();
-            self.status_message = "Dependency removed".to_string();
+        }
+        // Handle the milestone checkbox, asking first if the conversion loses data
+        if let Some((id, milestone)) = milestone_toggle {
+            if milestone && !self.project.milestone_impact(id).is_harmless() {
+                self.pending_milestone = Some(id);
+            } else {
+                self.set_milestone(id, milestone);
+            }
+        }
+        if let Some((id, locked)) = lock_toggle {
+            self.set_task_locked(id, locked);
+        }
+        if let Some((id, tentative)) = tentative_toggle {
+            self.set_task_tentative(id, tentative);
+        }
+        if let Some((id, text)) = comment_add {
+            self.add_comment(id, &text);
+        }
+        if let Some((id, comment_id)) = comment_delete {
+            self.delete_comment(id, comment_id);
+        }
+        if let Some(id) = timer_toggle {
+            self.toggle_timer(id);
+        }
+        // Handle dependency kind / lag edits from editor
+        if let Some((from, to, kind, lag)) = dep_update {
+            self.update_link(from, to, kind, lag);
+        }
+
+        // Agenda panel, at the right edge or beside the task list. Its width
+        // is saved once a resize ends.
+        if self.settings.show_agenda {
+            let mut agenda_action = ui::agenda_panel::AgendaAction::None;
+            let panel = if self.settings.agenda_beside_list {
+                egui::SidePanel::left("agenda_panel")
+            } else {
+                egui::SidePanel::right("agenda_panel")
+            };
+            let agenda = panel
+                .default_width(self.settings.agenda_width)
+                .frame(
+                    egui::Frame::default()
+                        .fill(ui::theme::bg_panel())
+                        .inner_margin(egui::Margin::symmetric(8.0, 4.0)),
+                )
+                .show(ctx, |ui| {
+                    agenda_action = ui::agenda_panel::show_agenda_panel(
+                        &self.project.tasks,
+                        self.selected_task,
+                        &self.search_query,
+                        self.filter_priority,
+                        self.settings.agenda_days,
+                        self.project.clock().now(),
+                        self.project.highlight_rules.due_window(),
+                        ui,
+                    );
+                });
+            let width = agenda.response.rect.width();
+            if (width - self.settings.agenda_width).abs() >= 1.0 && !ctx.input(|i| i.pointer.any_down()) {
+                self.settings.agenda_width = width;
+                self.settings.save();
+            }
+            match agenda_action {
+                ui::agenda_panel::AgendaAction::Select(id) => {
+                    self.reveal_task(ctx, id);
+                }
+                ui::agenda_panel::AgendaAction::SetDays(days) => {
+                    self.settings.agenda_days = days;
+                    self.settings.save();
+                }
+                ui::agenda_panel::AgendaAction::None => {}
+            }
         }
 
         // Central panel: Gantt chart
         let chart_frame = egui::Frame::default()
             .fill(ui::theme::bg_dark())
             .inner_margin(egui::Margin::ZERO);
+        self.chart_hover_time = None;
         egui::CentralPanel::default().frame(chart_frame).show(ctx, |ui| {
+            let forecast = self.project.forecast(self.project.clock().now());
+            if ui::view_tabs::show_view_tabs(&mut self.main_view, forecast, self.project.target_end, ui) {
+                self.show_target_end = true;
+            }
+            if self.main_view == ui::view_tabs::MainView::Calendar {
+                match ui::calendar_view::show_calendar_view(
+                    &self.project.tasks,
+                    self.selected_task,
+                    &self.selection,
+                    ui,
+                ) {
+                    ui::calendar_view::CalendarAction::Select(id) => {
+                        self.selected_task = Some(id);
+                        self.selection = vec![id];
+                    }
+                    ui::calendar_view::CalendarAction::Open(id) => {
+                        self.selected_task = Some(id);
+                        self.selection = vec![id];
+                        self.main_view = ui::view_tabs::MainView::Gantt;
+                    }
+                    ui::calendar_view::CalendarAction::None => {}
+                }
+                return;
+            }
+            if self.main_view == ui::view_tabs::MainView::Resources {
+                match ui::resource_view::show_resource_view(
+                    &self.project.tasks,
+                    &self.project.absences,
+                    &mut self.viewport,
+                    self.selected_task,
+                    &self.selection,
+                    self.project.clock(),
+                    ui,
+                ) {
+                    ui::resource_view::ResourceAction::Select(id) => {
+                        self.selected_task = Some(id);
+                        self.selection = vec![id];
+                    }
+                    ui::resource_view::ResourceAction::Reassign(id, assignee) => {
+                        self.reassign_task(id, assignee);
+                    }
+                    ui::resource_view::ResourceAction::None => {}
+                }
+                return;
+            }
+            if self.main_view == ui::view_tabs::MainView::Progress {
+                ui::progress_chart::show_progress_chart(&self.project, ui);
+                return;
+            }
+            if self.main_view == ui::view_tabs::MainView::Network {
+                if let ui::network_view::NetworkAction::Select(id) = ui::network_view::show_network_view(
+                    &self.project.tasks,
+                    &self.project.dependency_graph(),
+                    self.project.generation(),
+                    self.selected_task,
+                    &self.selection,
+                    ui,
+                ) {
+                    self.selected_task = Some(id);
+                    self.selection = vec![id];
+                }
+                return;
+            }
+            if self.settings.show_milestone_strip {
+                if let Some(id) = ui::milestone_strip::show_milestone_strip(&self.project.tasks, self.selected_task, ui) {
+                    self.reveal_task(ui.ctx(), id);
+                }
+            }
+            let chart_rect = ui.available_rect_before_wrap();
+            let trace = self.dependency_trace();
+            let clock = self.project.clock();
+            let violations = self.project.dependency_violations();
+            let highlights = self.project.highlights();
             let chart_interaction = ui::gantt_chart::show_gantt_chart(
                 &mut self.project.tasks,
                 &self.project.dependencies,
                 &mut self.viewport,
                 &mut self.selected_task,
+                &self.selection,
+                ui::gantt_chart::ChartOptions {
+                    trace: trace.as_deref(),
+                    schedule_tint: self.settings.schedule_tint,
+                    clock,
+                    labels: self.settings.bar_labels,
+                    completed: self.project.completed_tasks,
+                    task_defaults: self.project.task_defaults,
+                    absences: &self.project.absences,
+                    milestone_lane: self
+                        .settings
+                        .show_milestone_lane
+                        .then_some(self.settings.milestone_lane_key_only),
+                    rows: &rows,
+                    violations: &violations,
+                    highlights: &highlights,
+                    highlight_link: self.show_dependencies.then_some(self.highlighted_link).flatten(),
+                },
                 ui,
             );
+            self.chart_hover_time = chart_interaction.hover_time;
+            if let Some(scroll) = chart_interaction.scroll {
+                self.chart_capture.set_chart_scroll(scroll);
+            }
             if chart_interaction.changed {
-                self.project.recalculate_parent_dates();
+                // The chart may have reordered rows in place.
+                self.project.rebuild_index();
+                if let Some(id) = chart_interaction.moved_task {
+                    self.project.task_changed(id);
+                }
                 self.project.touch();
                 if let Some(selected) = self.selected_task {
-                    if let Some(task) = self.project.tasks.iter().find(|t| t.id == selected) {
+                    if let Some(task) = self.project.task(selected) {
                         self.status_message = format!(
                             "Updated '{}' ({} → {})",
                             task.name,
@@ -696,36 +2847,14 @@ impl eframe::App for GanttApp {
                     self.status_message = "Timeline updated".to_string();
                 }
             }
-            if let Some(dep) = chart_interaction.new_dependency {
-                // Avoid duplicates
-                let exists = self.project.dependencies.iter().any(|d| {
-                    d.from_task == dep.from_task && d.to_task == dep.to_task
-                });
-                if !exists {
-                    let from_name = self.project.tasks.iter()
-                        .find(|t| t.id == dep.from_task)
-                        .map(|t| t.name.clone())
-                        .unwrap_or_default();
-                    let to_name = self.project.tasks.iter()
-                        .find(|t| t.id == dep.to_task)
-                        .map(|t| t.name.clone())
-                        .unwrap_or_default();
-                    self.undo_history.push(&self.project.tasks, &self.project.dependencies);
-                    self.project.dependencies.push(dep);
-                    self.project.touch();
-                    self.status_message = format!("Linked '{}' → '{}'", from_name, to_name);
-                }
-            }
             if let Some((from, to)) = chart_interaction.remove_dependency {
-                self.undo_history.push(&self.project.tasks, &self.project.dependencies);
-                self.project.dependencies.retain(|d| {
-                    !(d.from_task == from && d.to_task == to)
-                });
+                self.push_undo("Remove dependency");
+                self.project.remove_dependencies(|d| d.from_task == from && d.to_task == to);
                 self.project.touch();
                 self.status_message = "Dependency removed".to_string();
             }
             if let Some(parent_id) = chart_interaction.toggle_collapse {
-                if let Some(task) = self.project.tasks.iter_mut().find(|t| t.id == parent_id) {
+                if let Some(task) = self.project.task_mut(parent_id) {
                     task.collapsed = !task.collapsed;
                     self.project.touch();
                 }
@@ -736,6 +2865,41 @@ impl eframe::App for GanttApp {
             if let Some(task_id) = chart_interaction.delete_task {
                 self.delete_task(task_id);
             }
+            if let Some((task_id, name)) = chart_interaction.rename {
+                self.rename_task(task_id, name);
+            }
+            if let Some(id) = chart_interaction.reveal {
+                self.reveal_task(ctx, id);
+            }
+            if let Some(drawn) = chart_interaction.create_task {
+                self.create_drawn_task(ctx, drawn);
+            }
+            if let Some((task_id, side)) = chart_interaction.insert_task {
+                self.insert_task_beside(ctx, task_id, side, ui::inline_rename::RenameSurface::Chart);
+            }
+            if let Some(task_id) = chart_interaction.toggle_lock {
+                if let Some(locked) = self.project.task(task_id).map(|t| t.locked) {
+                    self.set_task_locked(task_id, !locked);
+                }
+            }
+            if let Some(task_id) = chart_interaction.toggle_tentative {
+                if let Some(tentative) = self.project.task(task_id).map(|t| t.tentative) {
+                    self.set_task_tentative(task_id, !tentative);
+                }
+            }
+            if let Some(task_id) = chart_interaction.blocked_move {
+                self.status_message = format!(
+                    "'{}' is locked; unlock it to change its dates",
+                    self.project.task_name(task_id)
+                );
+            }
+            if self.project.tasks.is_empty() {
+                match ui::onboarding::show_empty_state(ui, chart_rect) {
+                    EmptyStateAction::AddTask => self.show_add_task = true,
+                    EmptyStateAction::LoadExample => self.load_example_project(),
+                    EmptyStateAction::None => {}
+                }
+            }
         });
 
         // Dialogs
@@ -745,5 +2909,82 @@ impl eframe::App for GanttApp {
         if self.show_about {
             ui::dialogs::show_about_dialog(self, ctx);
         }
+        if self.pending_milestone.is_some() {
+            ui::dialogs::show_milestone_dialog(self, ctx);
+        }
+        if self.pending_arrange.is_some() {
+            ui::dialogs::show_arrange_children_dialog(self, ctx);
+        }
+        if self.show_violations {
+            ui::dialogs::show_violations_dialog(self, ctx);
+        }
+        if self.show_reschedule {
+            ui::dialogs::show_reschedule_dialog(self, ctx);
+        }
+        if self.show_shift_dates {
+            ui::dialogs::show_shift_dialog(self, ctx);
+        }
+        if self.show_shortcuts {
+            ui::shortcuts_dialog::show_shortcuts_dialog(self, ctx);
+        }
+        if self.show_activity {
+            ui::activity_panel::show_activity_panel(self, ctx);
+        }
+        if self.show_dependencies {
+            ui::dependency_panel::show_dependency_panel(self, ctx);
+        }
+        if self.pending_jira_import.is_some() {
+            ui::dialogs::show_jira_import_dialog(self, ctx);
+        }
+        if self.pending_markdown_import.is_some() {
+            ui::dialogs::show_markdown_import_dialog(self, ctx);
+        }
+        if self.show_github_import {
+            ui::dialogs::show_github_import_dialog(self, ctx);
+        }
+        if self.show_save_hook {
+            ui::save_hook_panel::show_save_hook_panel(self, ctx);
+        }
+        if self.pending_hook.is_some() {
+            ui::dialogs::show_hook_confirm_dialog(self, ctx);
+        }
+        if self.comparison.is_some() {
+            ui::compare_panel::show_compare_panel(self, ctx);
+        }
+        if self.show_time_zone {
+            ui::dialogs::show_time_zone_dialog(self, ctx);
+        }
+        if self.show_target_end {
+            ui::dialogs::show_target_end_dialog(self, ctx);
+        }
+        if self.show_highlight_rules {
+            ui::dialogs::show_highlight_rules_dialog(self, ctx);
+        }
+        if self.show_progress_policy {
+            ui::dialogs::show_progress_policy_dialog(self, ctx);
+        }
+        if self.show_resources {
+            ui::dialogs::show_resources_dialog(self, ctx);
+        }
+        if self.pending_export.is_some() {
+            ui::dialogs::show_export_dialog(self, ctx);
+        }
+        if self.save_error.is_some() {
+            ui::dialogs::show_save_error_dialog(self, ctx);
+        }
+        if self.show_command_palette {
+            ui::command_palette::show_command_palette(self, ctx);
+        }
+        if let Some(dep) = ui::gantt_chart::show_link_chooser(ctx, &self.project.tasks) {
+            self.add_dependency(dep);
+        }
+        if !self.settings.tour_done && !self.project.tasks.is_empty() && ui::onboarding::show_tour(ctx) {
+            self.settings.tour_done = true;
+            self.settings.save();
+        }
+        self.toasts.show(ctx);
+        if self.project.generation() != drawn_generation {
+            ctx.request_repaint();
+        }
     }
 }
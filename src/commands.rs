@@ -0,0 +1,416 @@
+//! Named application commands.
+//!
+//! Each feature registers its commands here once, with any default key
+//! bindings, and anything that lists or runs commands (the command palette,
+//! the keymap) reads the registry instead of keeping its own hardcoded list.
+
+use crate::app::GanttApp;
+use crate::keymap::KeyBinding;
+use crate::model::task::CompletedTasks;
+use crate::model::TimelineScale;
+use crate::ui;
+use crate::ui::theme_manager::ThemePreset;
+use crate::ui::view_tabs::MainView;
+
+/// Id of the command that opens the command palette (which doesn't list it).
+pub const OPEN_PALETTE: &str = "palette.open";
+
+/// What running a command does.
+pub type CommandFn = fn(&mut GanttApp, &egui::Context);
+
+pub struct Command {
+    /// Stable identifier, e.g. `"file.save"`.
+    pub id: &'static str,
+    pub label: &'static str,
+    /// Bindings used until the user changes them.
+    pub default_keys: Vec<KeyBinding>,
+    pub run: CommandFn,
+    /// Whether the command can run right now; `None` means always.
+    pub enabled: Option<fn(&GanttApp) -> bool>,
+}
+
+impl Command {
+    pub fn new(id: &'static str, label: &'static str, run: CommandFn) -> Self {
+        Self { id, label, default_keys: Vec::new(), run, enabled: None }
+    }
+
+    /// Default bindings, written like `Ctrl+Shift+T`.
+    pub fn keys(mut self, keys: &[&str]) -> Self {
+        self.default_keys = keys
+            .iter()
+            .map(|k| KeyBinding::parse(k).unwrap_or_else(|| panic!("bad default binding {k:?}")))
+            .collect();
+        self
+    }
+
+    pub fn enabled_when(mut self, enabled: fn(&GanttApp) -> bool) -> Self {
+        self.enabled = Some(enabled);
+        self
+    }
+
+    pub fn is_enabled(&self, app: &GanttApp) -> bool {
+        self.enabled.is_none_or(|f| f(app))
+    }
+}
+
+/// Every command the app knows about, in registration order.
+#[derive(Default)]
+pub struct CommandRegistry {
+    commands: Vec<Command>,
+}
+
+impl CommandRegistry {
+    /// Registry holding the built-in commands.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::default();
+        register_builtins(&mut registry);
+        registry
+    }
+
+    /// Add a command, replacing any registered under the same id.
+    pub fn register(&mut self, command: Command) {
+        match self.commands.iter_mut().find(|c| c.id == command.id) {
+            Some(existing) => *existing = command,
+            None => self.commands.push(command),
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Command> {
+        self.commands.iter().find(|c| c.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Command> {
+        self.commands.iter()
+    }
+}
+
+fn has_selection(app: &GanttApp) -> bool {
+    app.selected_task.is_some()
+}
+
+/// The selected task has children.
+fn selected_parent(app: &GanttApp) -> bool {
+    app.selected_task.is_some_and(|id| app.project.tasks.iter().any(|t| t.parent_id == Some(id)))
+}
+
+/// Days in a week of keyboard nudges: five while they count working days.
+fn nudge_week(app: &GanttApp) -> i64 {
+    if app.settings.nudge_working_days { 5 } else { 7 }
+}
+
+/// Set the selected task's progress to `TENTHS` × 10%.
+fn set_progress<const TENTHS: u8>(app: &mut GanttApp, _: &egui::Context) {
+    if let Some(id) = app.selected_task {
+        app.set_task_progress(id, TENTHS as f32 / 10.0, false);
+    }
+}
+
+/// Insert a new task next to the selected one and rename it in the list.
+fn insert_beside(app: &mut GanttApp, ctx: &egui::Context, side: ui::gantt_chart::InsertSide) {
+    if let Some(id) = app.selected_task {
+        app.insert_task_beside(ctx, id, side, ui::inline_rename::RenameSurface::List);
+    }
+}
+
+fn register_builtins(r: &mut CommandRegistry) {
+    // Tasks
+    r.register(Command::new("task.new", "New task…", |app, _| app.show_add_task = true).keys(&["Ctrl+N"]));
+    r.register(
+        Command::new("task.add_subtask", "Add subtask to selected task", |app, _| {
+            if let Some(id) = app.selected_task {
+                app.add_subtask(id);
+            }
+        })
+        .enabled_when(has_selection),
+    );
+    r.register(
+        Command::new("task.insert_above", "Insert task above selected", |app, ctx| {
+            insert_beside(app, ctx, ui::gantt_chart::InsertSide::Above)
+        })
+        .keys(&["Alt+Insert"])
+        .enabled_when(has_selection),
+    );
+    r.register(
+        Command::new("task.insert_below", "Insert task below selected", |app, ctx| {
+            insert_beside(app, ctx, ui::gantt_chart::InsertSide::Below)
+        })
+        .keys(&["Insert"])
+        .enabled_when(has_selection),
+    );
+    r.register(
+        Command::new("task.rename", "Rename selected task", |app, ctx| {
+            if let Some(task) = app.selected_task.and_then(|id| app.project.task(id)) {
+                ui::inline_rename::begin(ctx, task.id, &task.name, ui::inline_rename::RenameSurface::List);
+            }
+        })
+        .keys(&["F2"])
+        .enabled_when(has_selection),
+    );
+    r.register(
+        Command::new("task.delete", "Delete selected task", |app, _| {
+            if let Some(id) = app.selected_task {
+                app.delete_task(id);
+            }
+        })
+        .keys(&["Delete"])
+        .enabled_when(has_selection),
+    );
+    r.register(
+        Command::new("task.link", "Link selected task to…", |app, _| {
+            app.palette_link_from = app.selected_task;
+            app.show_command_palette = true;
+        })
+        .keys(&["L"])
+        .enabled_when(has_selection),
+    );
+    r.register(
+        Command::new("task.progress_up", "Increase progress by 5%", |app, _| app.nudge_selected_progress(0.05))
+            .keys(&["Plus", "Equals"])
+            .enabled_when(has_selection),
+    );
+    r.register(
+        Command::new("task.progress_down", "Decrease progress by 5%", |app, _| app.nudge_selected_progress(-0.05))
+            .keys(&["Minus"])
+            .enabled_when(has_selection),
+    );
+    r.register(
+        Command::new("task.shift_dates", "Shift dates…", |app, _| app.show_shift_dates = true)
+            .keys(&["Ctrl+Shift+D"])
+            .enabled_when(has_selection),
+    );
+    r.register(
+        Command::new("task.nudge_later", "Move selected tasks a day later", |app, _| app.nudge_selected_dates(1, false))
+            .keys(&["Alt+Right"])
+            .enabled_when(has_selection),
+    );
+    r.register(
+        Command::new("task.nudge_earlier", "Move selected tasks a day earlier", |app, _| {
+            app.nudge_selected_dates(-1, false)
+        })
+        .keys(&["Alt+Left"])
+        .enabled_when(has_selection),
+    );
+    r.register(
+        Command::new("task.nudge_week_later", "Move selected tasks a week later", |app, _| {
+            app.nudge_selected_dates(nudge_week(app), false)
+        })
+        .keys(&["Shift+Alt+Right"])
+        .enabled_when(has_selection),
+    );
+    r.register(
+        Command::new("task.nudge_week_earlier", "Move selected tasks a week earlier", |app, _| {
+            app.nudge_selected_dates(-nudge_week(app), false)
+        })
+        .keys(&["Shift+Alt+Left"])
+        .enabled_when(has_selection),
+    );
+    r.register(
+        Command::new("task.extend_end", "Extend selected tasks by a day", |app, _| app.nudge_selected_dates(1, true))
+            .keys(&["Ctrl+Alt+Right"])
+            .enabled_when(has_selection),
+    );
+    r.register(
+        Command::new("task.shrink_end", "Shorten selected tasks by a day", |app, _| app.nudge_selected_dates(-1, true))
+            .keys(&["Ctrl+Alt+Left"])
+            .enabled_when(has_selection),
+    );
+    r.register(
+        Command::new("task.arrange_children", "Arrange children sequentially…", |app, _| {
+            app.pending_arrange = app.selected_task;
+        })
+        .enabled_when(selected_parent),
+    );
+    r.register(
+        Command::new("task.toggle_timer", "Start/stop timer on selected task", |app, _| {
+            if let Some(id) = app.selected_task {
+                app.toggle_timer(id);
+            }
+        })
+        .enabled_when(has_selection),
+    );
+    r.register(
+        Command::new("task.stop_timer", "Stop running timer", |app, _| app.stop_timer())
+            .enabled_when(|app| app.project.running_timer().is_some()),
+    );
+    let progress_levels: [(&'static str, &'static str, CommandFn, &str); 10] = [
+        ("task.progress_0", "Set progress to 0%", set_progress::<0>, "0"),
+        ("task.progress_10", "Set progress to 10%", set_progress::<1>, "1"),
+        ("task.progress_20", "Set progress to 20%", set_progress::<2>, "2"),
+        ("task.progress_30", "Set progress to 30%", set_progress::<3>, "3"),
+        ("task.progress_40", "Set progress to 40%", set_progress::<4>, "4"),
+        ("task.progress_50", "Set progress to 50%", set_progress::<5>, "5"),
+        ("task.progress_60", "Set progress to 60%", set_progress::<6>, "6"),
+        ("task.progress_70", "Set progress to 70%", set_progress::<7>, "7"),
+        ("task.progress_80", "Set progress to 80%", set_progress::<8>, "8"),
+        ("task.progress_90", "Set progress to 90%", set_progress::<9>, "9"),
+    ];
+    for (id, label, run, key) in progress_levels {
+        r.register(Command::new(id, label, run).keys(&[key]).enabled_when(has_selection));
+    }
+
+    // Edit
+    r.register(
+        Command::new("edit.undo", "Undo", |app, _| app.undo())
+            .keys(&["Ctrl+Z"])
+            .enabled_when(|app| app.undo_history.can_undo()),
+    );
+    r.register(
+        Command::new("edit.redo", "Redo", |app, _| app.redo())
+            .keys(&["Ctrl+Y", "Ctrl+Shift+Z"])
+            .enabled_when(|app| app.undo_history.can_redo()),
+    );
+
+    r.register(
+        Command::new("edit.copy_chart_image", "Copy chart as image", |app, ctx| app.copy_chart_image(ctx, false))
+            .keys(&["Ctrl+Shift+C"]),
+    );
+    r.register(
+        Command::new("edit.copy_project_image", "Copy whole project as image", |app, ctx| {
+            app.copy_chart_image(ctx, true)
+        })
+        .keys(&["Ctrl+Alt+Shift+C"]),
+    );
+
+    r.register(Command::new("schedule.violations", "Dependency violations", |app, _| app.show_violations = true));
+    r.register(Command::new("schedule.resolve_violations", "Resolve dependency violations…", |app, _| {
+        app.show_reschedule = true
+    }));
+    r.register(Command::new("project.time_zone", "Time zone…", |app, _| app.show_time_zone = true));
+    r.register(Command::new("project.target_end", "Target end date…", |app, _| app.show_target_end = true));
+    r.register(Command::new("project.highlight_rules", "Highlight rules…", |app, _| app.show_highlight_rules = true));
+    r.register(Command::new("project.progress_policy", "Progress policy…", |app, _| app.show_progress_policy = true));
+    r.register(Command::new("project.resources", "Resources and absences…", |app, _| app.show_resources = true));
+    r.register(Command::new("project.log_task_progress", "Log task progress", |app, _| {
+        app.toggle_task_progress_log()
+    }));
+
+    // File
+    r.register(Command::new("file.new", "New project", |app, _| app.new_project()));
+    r.register(Command::new("file.open", "Open project…", |app, _| app.open_project()).keys(&["Ctrl+O"]));
+    r.register(Command::new("file.save", "Save", |app, _| app.save_project()).keys(&["Ctrl+S"]));
+    r.register(Command::new("file.save_as", "Save as…", |app, _| app.save_project_as()).keys(&["Ctrl+Shift+S"]));
+    r.register(Command::new("file.compare", "Compare with…", |app, _| app.compare_with_file()));
+    r.register(Command::new("file.save_hook", "After-save command…", |app, _| app.show_save_hook = true));
+    r.register(Command::new("file.import_csv", "Import CSV…", |app, _| app.import_csv()));
+    r.register(Command::new("file.import_jira", "Import Jira CSV…", |app, _| app.import_jira_csv()));
+    r.register(Command::new("file.import_markdown", "Import Markdown outline…", |app, _| app.import_markdown()));
+    r.register(Command::new("file.import_github", "Import GitHub issues…", |app, _| {
+        app.show_github_import = true
+    }));
+    r.register(Command::new("file.export_csv", "Export CSV…", |app, _| app.begin_export("CSV")));
+    r.register(Command::new("file.export_html", "Export HTML page…", |app, _| app.begin_export("HTML")));
+    r.register(Command::new("file.export_plantuml", "Export PlantUML…", |app, _| app.begin_export("PlantUML")));
+    r.register(Command::new("file.export_org", "Export org-mode file…", |app, _| app.begin_export("Org")));
+    r.register(Command::new("file.export_time", "Export time entries (CSV)…", |app, _| {
+        app.begin_export("Time Entries (CSV)")
+    }));
+    r.register(Command::new("file.export_milestones", "Export milestones (SVG)…", |app, _| {
+        app.begin_export("Milestones (SVG)")
+    }));
+    r.register(Command::new("file.export_progress", "Export progress history (CSV)…", |app, _| {
+        app.begin_export("Progress History (CSV)")
+    }));
+
+    // View
+    r.register(
+        Command::new(OPEN_PALETTE, "Command palette", |app, _| app.show_command_palette = true)
+            .keys(&["Ctrl+P", "Ctrl+K"]),
+    );
+    r.register(
+        Command::new("view.fit", "Fit to window", |app, ctx| {
+            app.main_view = MainView::Gantt;
+            ui::gantt_chart::fit_to_window(ctx);
+        })
+        .keys(&["Ctrl+0"]),
+    );
+    r.register(
+        Command::new("view.zoom_selection", "Zoom to selection", |app, ctx| app.zoom_to_selection(ctx)).keys(&["Z"]),
+    );
+    r.register(Command::new("view.today", "Go to today", |app, ctx| app.go_to_today(ctx)).keys(&["T"]));
+    r.register(
+        Command::new("view.toggle_editor", "Toggle task editor", |app, _| {
+            app.settings.show_editor = !app.settings.show_editor;
+            app.settings.save();
+        })
+        .keys(&["Ctrl+E"]),
+    );
+    r.register(Command::new("view.zoom_in", "Zoom in", |app, _| app.viewport.zoom_in()).keys(&["Ctrl+=", "Ctrl++"]));
+    r.register(Command::new("view.zoom_out", "Zoom out", |app, _| app.viewport.zoom_out()).keys(&["Ctrl+-"]));
+    r.register(Command::new("view.scale_days", "Timeline scale: Days", |app, _| {
+        app.viewport.scale = TimelineScale::Days
+    }));
+    r.register(Command::new("view.scale_weeks", "Timeline scale: Weeks", |app, _| {
+        app.viewport.scale = TimelineScale::Weeks
+    }));
+    r.register(Command::new("view.scale_months", "Timeline scale: Months", |app, _| {
+        app.viewport.scale = TimelineScale::Months
+    }));
+    r.register(Command::new("view.gantt", "Show Gantt chart", |app, _| app.main_view = MainView::Gantt));
+    r.register(Command::new("view.calendar", "Show calendar", |app, _| app.main_view = MainView::Calendar));
+    r.register(Command::new("view.resources", "Show resources", |app, _| app.main_view = MainView::Resources));
+    r.register(Command::new("view.network", "Show network diagram", |app, _| app.main_view = MainView::Network));
+    r.register(Command::new("view.progress", "Show progress chart", |app, _| app.main_view = MainView::Progress));
+    r.register(Command::new("view.milestone_strip", "Toggle milestone strip", |app, _| {
+        app.settings.show_milestone_strip = !app.settings.show_milestone_strip;
+        app.settings.save();
+    }));
+    r.register(Command::new("view.milestone_lane", "Toggle milestone lane", |app, _| {
+        app.settings.show_milestone_lane = !app.settings.show_milestone_lane;
+        app.settings.save();
+    }));
+    r.register(Command::new("view.toggle_trace", "Toggle dependency tracing", |app, _| {
+        app.settings.trace_dependencies = !app.settings.trace_dependencies;
+        app.settings.save();
+    }));
+    r.register(Command::new("view.schedule_tint", "Toggle schedule status colours", |app, _| {
+        app.settings.schedule_tint = !app.settings.schedule_tint;
+        app.settings.save();
+    }));
+    r.register(Command::new("view.wrap_names", "Toggle wrapped task names", |app, _| {
+        app.settings.wrap_task_names = !app.settings.wrap_task_names;
+        app.settings.save();
+    }));
+    r.register(Command::new("view.color_blind_safe", "Toggle colour-blind-safe palette", |app, _| {
+        app.settings.color_blind_safe = !app.settings.color_blind_safe;
+        app.settings.save();
+    }));
+    r.register(
+        Command::new("view.hide_completed", "Toggle hiding completed tasks", |app, _| {
+            let next = if app.project.completed_tasks == CompletedTasks::Hide {
+                CompletedTasks::Show
+            } else {
+                CompletedTasks::Hide
+            };
+            app.set_completed_tasks(next);
+        })
+        .keys(&["Ctrl+Shift+H"]),
+    );
+    r.register(Command::new("view.activity", "Show activity log", |app, _| app.show_activity = true));
+    r.register(Command::new("view.dependencies", "Show dependencies", |app, _| app.show_dependencies = true));
+    r.register(Command::new("view.agenda", "Toggle agenda panel", |app, _| {
+        app.settings.show_agenda = !app.settings.show_agenda;
+        app.settings.save();
+    }));
+    r.register(Command::new("view.task_list", "Toggle task list", |app, _| {
+        app.settings.show_task_list = !app.settings.show_task_list;
+        app.settings.save();
+    }));
+    r.register(Command::new("view.filter_bar", "Toggle filter bar", |app, _| {
+        app.settings.show_filter_bar = !app.settings.show_filter_bar;
+        app.settings.save();
+    }));
+    r.register(Command::new("view.workspace_1", "Switch to the first workspace", |app, _| app.apply_workspace(0)));
+    r.register(Command::new("view.workspace_2", "Switch to the second workspace", |app, _| app.apply_workspace(1)));
+    r.register(Command::new("view.workspace_3", "Switch to the third workspace", |app, _| app.apply_workspace(2)));
+    r.register(
+        Command::new("view.toggle_appearance", "Toggle light/dark appearance", |app, _| {
+            let next = app.theme_manager.active_preset().map_or(ThemePreset::Dark, ThemePreset::next);
+            app.set_theme_preset(next);
+        })
+        .keys(&["Ctrl+Shift+T"]),
+    );
+
+    // Help
+    r.register(Command::new("help.shortcuts", "Keyboard shortcuts…", |app, _| app.show_shortcuts = true));
+    r.register(Command::new("help.about", "About", |app, _| app.show_about = true));
+}
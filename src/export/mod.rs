@@ -0,0 +1,13 @@
+//! Export formats: those in [`gantt_core::export`], plus the ones drawn with
+//! the app's theme.
+
+pub use gantt_core::export::*;
+
+use crate::io::milestone_svg;
+
+/// Registry holding every format the app offers, in menu order.
+pub fn registry() -> ExporterRegistry {
+    let mut registry = ExporterRegistry::with_builtins();
+    registry.register(milestone_svg::MilestoneSvgExporter);
+    registry
+}
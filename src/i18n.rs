@@ -0,0 +1,202 @@
+//! Translated UI strings.
+//!
+//! Strings are looked up by key (`tr("editor.priority")`) in flat JSON
+//! catalogs under `locales/`, compiled into the binary. The English catalog
+//! holds every key; other languages may leave keys out, and a missing key
+//! falls back to English, then to the key itself — never a panic.
+//!
+//! Like the theme, the language is installed once per frame with
+//! [`set_language`] before any UI code runs.
+
+use chrono::{Datelike, NaiveDateTime};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::model::grouping::{GroupBy, Status};
+use crate::model::highlight::DueState;
+use crate::model::risk::RiskLevel;
+use crate::model::task::{CompletedTasks, TaskPriority};
+
+/// Languages the UI is translated into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    /// The language's own name for itself, as offered in the menu.
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locales/en.json"),
+            Language::German => include_str!("../locales/de.json"),
+        }
+    }
+}
+
+type Catalog = HashMap<String, String>;
+
+/// Parsed catalogs, built on first use. A catalog that fails to parse is
+/// treated as empty, so everything falls back to English.
+fn catalog(language: Language) -> &'static Catalog {
+    static CATALOGS: OnceLock<HashMap<Language, Catalog>> = OnceLock::new();
+    let all = CATALOGS.get_or_init(|| {
+        Language::ALL
+            .iter()
+            .map(|&l| (l, serde_json::from_str(l.source()).unwrap_or_default()))
+            .collect()
+    });
+    &all[&language]
+}
+
+thread_local! {
+    static ACTIVE: Cell<Language> = const { Cell::new(Language::English) };
+}
+
+/// Install the language for the current frame.
+pub fn set_language(language: Language) {
+    ACTIVE.with(|l| l.set(language));
+}
+
+/// The string for `key` in the active language.
+pub fn tr(key: &str) -> &str {
+    let language = ACTIVE.with(Cell::get);
+    catalog(language)
+        .get(key)
+        .or_else(|| catalog(Language::English).get(key))
+        .map_or(key, String::as_str)
+}
+
+/// [`tr`] with `{name}` placeholders filled in from `args`.
+pub fn trf(key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    let mut text = tr(key).to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    text
+}
+
+/// [`trf`] choosing between `{key}.one` and `{key}.other` by `n`, which
+/// is also available to the text as `{n}`.
+pub fn trn(key: &str, n: usize, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    let key = format!("{}.{}", key, if n == 1 { "one" } else { "other" });
+    let mut all: Vec<(&str, &dyn std::fmt::Display)> = vec![("n", &n)];
+    all.extend_from_slice(args);
+    trf(&key, &all)
+}
+
+/// Translated name of a priority.
+pub fn priority_label(priority: TaskPriority) -> &'static str {
+    let key = match priority {
+        TaskPriority::None => "priority.none",
+        TaskPriority::Low => "priority.low",
+        TaskPriority::Medium => "priority.medium",
+        TaskPriority::High => "priority.high",
+        TaskPriority::Critical => "priority.critical",
+    };
+    tr(key)
+}
+
+/// Translated name of a risk level, or of no rating for `None`.
+pub fn risk_label(risk: Option<RiskLevel>) -> &'static str {
+    let key = match risk {
+        None => "risk.none",
+        Some(RiskLevel::Low) => "risk.low",
+        Some(RiskLevel::Medium) => "risk.medium",
+        Some(RiskLevel::High) => "risk.high",
+    };
+    tr(key)
+}
+
+/// Translated name of where a task stands against its due time.
+pub fn due_label(state: DueState) -> &'static str {
+    let key = match state {
+        DueState::Late => "due.late",
+        DueState::DueSoon => "due.soon",
+        DueState::Done => "due.done",
+    };
+    tr(key)
+}
+
+/// Translated name of a way of showing completed tasks.
+pub fn completed_tasks_label(mode: CompletedTasks) -> &'static str {
+    let key = match mode {
+        CompletedTasks::Show => "completed.show",
+        CompletedTasks::Strike => "completed.strike",
+        CompletedTasks::Hide => "completed.hide",
+    };
+    tr(key)
+}
+
+/// Translated name of a way of grouping the task list.
+pub fn group_by_label(by: GroupBy) -> &'static str {
+    let key = match by {
+        GroupBy::Hierarchy => "group_by.hierarchy",
+        GroupBy::Priority => "group_by.priority",
+        GroupBy::Assignee => "group_by.assignee",
+        GroupBy::Tag => "group_by.tag",
+        GroupBy::Status => "group_by.status",
+    };
+    tr(key)
+}
+
+/// Translated name of a task status.
+pub fn status_label(status: Status) -> &'static str {
+    let key = match status {
+        Status::NotStarted => "status.not_started",
+        Status::InProgress => "status.in_progress",
+        Status::Done => "status.done",
+    };
+    tr(key)
+}
+
+/// `pattern` with month and weekday names (`%b`, `%B`, `%a`, `%A`) spelled
+/// out in the active language, ready for chrono's `format`.
+fn localize_pattern(pattern: &str, date: &impl Datelike) -> String {
+    let month = date.month();
+    let weekday = date.weekday().num_days_from_monday() + 1;
+    let mut out = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        let name = match chars.next() {
+            Some('b') => tr(&format!("date.month_short.{}", month)).to_string(),
+            Some('B') => tr(&format!("date.month.{}", month)).to_string(),
+            Some('a') => tr(&format!("date.weekday_short.{}", weekday)).to_string(),
+            Some('A') => tr(&format!("date.weekday.{}", weekday)).to_string(),
+            Some(other) => {
+                out.push('%');
+                out.push(other);
+                continue;
+            }
+            None => {
+                out.push('%');
+                continue;
+            }
+        };
+        // Names are literal text to chrono.
+        out.push_str(&name.replace('%', "%%"));
+    }
+    out
+}
+
+/// `at` formatted with chrono's `pattern`, with month and weekday names in
+/// the active language. Pass a `date.*` key instead of a pattern to use the
+/// language's own ordering (e.g. `"date.month_day"`).
+pub fn format_datetime(at: NaiveDateTime, pattern: &str) -> String {
+    at.format(&localize_pattern(tr(pattern), &at)).to_string()
+}
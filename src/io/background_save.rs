@@ -0,0 +1,76 @@
+//! Saving on a worker thread so large projects don't stall the UI.
+//!
+//! Jobs go through a channel to a single worker, so saves run one after
+//! another in the order they were requested and never interleave on disk.
+
+use crate::model::file::SaveError;
+use crate::model::Project;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+struct SaveJob {
+    project: Project,
+    path: PathBuf,
+}
+
+/// The outcome of one background save.
+pub struct SaveOutcome {
+    pub path: PathBuf,
+    /// [`Project::generation`] of the snapshot that was written.
+    pub generation: u64,
+    pub result: Result<(), SaveError>,
+}
+
+pub struct BackgroundSaver {
+    jobs: Sender<SaveJob>,
+    outcomes: Receiver<SaveOutcome>,
+    /// Jobs sent but not yet reported back.
+    in_flight: usize,
+}
+
+impl BackgroundSaver {
+    /// Start the worker. `ctx` is asked to repaint when a save finishes so
+    /// the result shows up without waiting for input.
+    pub fn new(ctx: egui::Context) -> Self {
+        let (jobs, job_rx) = mpsc::channel::<SaveJob>();
+        let (outcome_tx, outcomes) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("project-save".into())
+            .spawn(move || {
+                for job in job_rx {
+                    let outcome = SaveOutcome {
+                        generation: job.project.generation(),
+                        result: super::save_project(&job.project, &job.path),
+                        path: job.path,
+                    };
+                    if outcome_tx.send(outcome).is_err() {
+                        break;
+                    }
+                    ctx.request_repaint();
+                }
+            })
+            .expect("failed to start save thread");
+        Self { jobs, outcomes, in_flight: 0 }
+    }
+
+    /// Queue a snapshot of `project` to be written to `path`.
+    pub fn save(&mut self, project: Project, path: PathBuf) -> Result<(), String> {
+        self.jobs
+            .send(SaveJob { project, path })
+            .map_err(|_| "save thread stopped".to_string())?;
+        self.in_flight += 1;
+        Ok(())
+    }
+
+    /// True while any queued save hasn't finished.
+    pub fn is_saving(&self) -> bool {
+        self.in_flight > 0
+    }
+
+    /// Outcomes of saves finished since the last call, oldest first.
+    pub fn poll(&mut self) -> Vec<SaveOutcome> {
+        let done: Vec<SaveOutcome> = self.outcomes.try_iter().collect();
+        self.in_flight = self.in_flight.saturating_sub(done.len());
+        done
+    }
+}
@@ -2,8 +2,12 @@ use std::path::PathBuf;
 
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 
+use crate::model::export::csv_export::EARNED_VALUE_HEADING;
 use crate::model::Task;
+use crate::model::risk::RiskLevel;
+use crate::model::slug::slugify;
 use crate::model::task::TaskPriority;
+use crate::ui::model_ext::ToRgba;
 use crate::ui::theme;
 
 /// Map a status string to a progress value (0.0 – 1.0).
@@ -19,7 +23,7 @@ fn status_to_progress(status: &str) -> f32 {
 
 /// Try parsing a datetime string with several common formats, with fallback to date-only.
 /// Returns NaiveDateTime with default time of 00:00:00 for date-only inputs.
-fn parse_datetime(s: &str, is_end_field: bool) -> Option<NaiveDateTime> {
+pub(crate) fn parse_datetime(s: &str, is_end_field: bool) -> Option<NaiveDateTime> {
     let s = s.trim();
 
     // Try datetime formats first
@@ -51,7 +55,7 @@ fn parse_datetime(s: &str, is_end_field: bool) -> Option<NaiveDateTime> {
 }
 
 /// Detect delimiter by checking the first line for common separators.
-fn detect_delimiter(first_line: &str) -> u8 {
+pub(crate) fn detect_delimiter(first_line: &str) -> u8 {
     let semicolons = first_line.matches(';').count();
     let commas = first_line.matches(',').count();
     let tabs = first_line.matches('\t').count();
@@ -66,14 +70,15 @@ fn detect_delimiter(first_line: &str) -> u8 {
 }
 
 /// Normalize a header string to a canonical column key.
-fn normalize_header(h: &str) -> String {
+pub(crate) fn normalize_header(h: &str) -> String {
     h.trim()
         .to_lowercase()
         .replace([' ', '-', '_'], "")
 }
 
 /// Map a normalized header to our column index:
-///   0 = name, 1 = start, 2 = end, 3 = status, 4 = priority, 5 = description
+///   0 = name, 1 = start, 2 = end, 3 = status, 4 = priority, 5 = description,
+///   6 = tentative, 7 = risk, 8 = slug
 fn header_to_col(normalized: &str) -> Option<usize> {
     match normalized {
         "name" | "task" | "tasklabel" | "taskname" | "label" | "title"
@@ -89,6 +94,12 @@ fn header_to_col(normalized: &str) -> Option<usize> {
 
         "description" | "notes" | "note" | "details" | "comment" | "comments" => Some(5),
 
+        "tentative" | "placeholder" => Some(6),
+
+        "risk" | "risklevel" => Some(7),
+
+        "slug" | "ref" => Some(8),
+
         _ => None,
     }
 }
@@ -150,6 +161,10 @@ pub fn import_csv(path: &PathBuf) -> Result<(Vec<Task>, usize), String> {
                 continue;
             }
         };
+        // The earned-value summary our exporter writes under the tasks.
+        if record.get(0).map(str::trim) == Some(EARNED_VALUE_HEADING) {
+            break;
+        }
 
         // Extract fields by mapped column positions
         let mut name_val = None;
@@ -158,6 +173,9 @@ pub fn import_csv(path: &PathBuf) -> Result<(Vec<Task>, usize), String> {
         let mut status_val = None;
         let mut priority_val = None;
         let mut description_val = None;
+        let mut tentative_val = None;
+        let mut risk_val = None;
+        let mut slug_val = None;
 
         for (col_idx, field) in record.iter().enumerate() {
             if col_idx < col_map.len() {
@@ -168,6 +186,9 @@ pub fn import_csv(path: &PathBuf) -> Result<(Vec<Task>, usize), String> {
                     Some(3) => status_val = Some(field.trim().to_string()),
                     Some(4) => priority_val = Some(field.trim().to_string()),
                     Some(5) => description_val = Some(field.trim().to_string()),
+                    Some(6) => tentative_val = Some(field.trim().to_lowercase()),
+                    Some(7) => risk_val = Some(field.trim().to_string()),
+                    Some(8) => slug_val = Some(field.trim().to_string()),
                     _ => {}
                 }
             }
@@ -218,7 +239,12 @@ pub fn import_csv(path: &PathBuf) -> Result<(Vec<Task>, usize), String> {
         task.progress = progress;
         task.priority = priority;
         task.description = description;
-        task.color = colors[tasks.len() % colors.len()];
+        task.tentative = matches!(tentative_val.as_deref(), Some("yes" | "y" | "true" | "1" | "x"));
+        task.risk = risk_val.as_deref().and_then(RiskLevel::parse);
+        // Slugs are cleaned up rather than dropped; clashes are suffixed
+        // when the tasks go into the project.
+        task.slug = slug_val.map(|s| slugify(&s)).filter(|s| !s.is_empty());
+        task.color = colors[tasks.len() % colors.len()].to_rgba();
         tasks.push(task);
     }
 
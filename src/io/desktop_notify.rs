@@ -0,0 +1,14 @@
+//! Native desktop notifications.
+
+/// Show a notification without blocking the UI; the platform call can be
+/// slow (D-Bus on Linux). Failures are ignored — the bell menu in the
+/// toolbar lists the same reminders.
+pub fn notify(summary: String, body: String) {
+    std::thread::spawn(move || {
+        let _ = notify_rust::Notification::new()
+            .appname("Rust Gantt App")
+            .summary(&summary)
+            .body(&body)
+            .show();
+    });
+}
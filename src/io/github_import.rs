@@ -0,0 +1,275 @@
+//! Importing the open issues of a GitHub repository through the REST API.
+//!
+//! Fetching happens on a worker thread ([`GithubFetch`]) so a slow or dead
+//! network never freezes the UI; merging the result into the project is a
+//! plain function that runs on the UI thread.
+
+use crate::model::{Project, Task};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use uuid::Uuid;
+
+const API: &str = "https://api.github.com";
+/// Issues per page; 100 is the most the API hands out at once.
+const PER_PAGE: usize = 100;
+/// Length given to imported issues that have no due date.
+const DEFAULT_DAYS: i64 = 3;
+
+#[derive(Deserialize)]
+struct ApiLabel {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct ApiUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct ApiMilestone {
+    html_url: String,
+    title: String,
+    due_on: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ApiIssue {
+    html_url: String,
+    title: String,
+    body: Option<String>,
+    #[serde(default)]
+    labels: Vec<ApiLabel>,
+    milestone: Option<ApiMilestone>,
+    assignee: Option<ApiUser>,
+    /// Present when the "issue" is really a pull request.
+    pull_request: Option<serde_json::Value>,
+}
+
+/// A GitHub milestone, imported as the parent of its issues.
+#[derive(Debug, Clone)]
+pub struct Milestone {
+    pub url: String,
+    pub title: String,
+    pub due: Option<NaiveDate>,
+}
+
+/// An open issue as far as the import cares.
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub url: String,
+    pub title: String,
+    pub body: String,
+    pub labels: Vec<String>,
+    pub milestone: Option<Milestone>,
+    pub assignee: Option<String>,
+}
+
+impl Issue {
+    /// Due date from a `due:2024-05-01` style label, else the milestone's.
+    pub fn due(&self) -> Option<NaiveDate> {
+        self.labels.iter().find_map(|l| due_label(l)).or(self.milestone.as_ref().and_then(|m| m.due))
+    }
+}
+
+/// The date in a label like `due:2024-05-01`, `due/2024-05-01` or
+/// `due 2024-05-01`.
+fn due_label(label: &str) -> Option<NaiveDate> {
+    let label = label.trim();
+    label.get(..3).filter(|p| p.eq_ignore_ascii_case("due"))?;
+    let date = label[3..].trim_start_matches([':', '/', '=', ' ']);
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+/// `owner/name` from either that form or a github.com URL.
+pub fn parse_repo(input: &str) -> Option<String> {
+    let trimmed = input.trim().trim_end_matches('/');
+    let path = trimmed
+        .strip_prefix("https://github.com/")
+        .or_else(|| trimmed.strip_prefix("http://github.com/"))
+        .or_else(|| trimmed.strip_prefix("github.com/"))
+        .unwrap_or(trimmed);
+    let mut parts = path.split('/');
+    let owner = parts.next().filter(|s| !s.is_empty())?;
+    let name = parts.next().filter(|s| !s.is_empty())?.trim_end_matches(".git");
+    if name.is_empty() {
+        return None;
+    }
+    Some(format!("{}/{}", owner, name))
+}
+
+/// The `rel="next"` URL of a `Link` response header.
+fn next_page(link: &str) -> Option<String> {
+    link.split(',').find_map(|part| {
+        let (url, rel) = part.split_once(';')?;
+        rel.contains(r#"rel="next""#)
+            .then(|| url.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+    })
+}
+
+fn convert(issue: ApiIssue) -> Issue {
+    Issue {
+        url: issue.html_url,
+        title: issue.title,
+        body: issue.body.unwrap_or_default(),
+        labels: issue.labels.into_iter().map(|l| l.name).collect(),
+        milestone: issue.milestone.map(|m| Milestone {
+            url: m.html_url,
+            title: m.title,
+            due: m.due_on.and_then(|d| NaiveDate::parse_from_str(d.get(..10)?, "%Y-%m-%d").ok()),
+        }),
+        assignee: issue.assignee.map(|u| u.login),
+    }
+}
+
+/// Every open issue of `repo` (`owner/name`), following pagination. Pull
+/// requests, which the issues endpoint also returns, are left out. Blocks
+/// until done; use [`GithubFetch`] from the UI.
+pub fn fetch_open_issues(repo: &str, token: Option<&str>) -> Result<Vec<Issue>, String> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout_connect(std::time::Duration::from_secs(10))
+        .timeout(std::time::Duration::from_secs(30))
+        .build();
+    let mut url = Some(format!("{}/repos/{}/issues?state=open&per_page={}", API, repo, PER_PAGE));
+    let mut issues = Vec::new();
+    while let Some(page) = url.take() {
+        let mut request = agent
+            .get(&page)
+            .set("Accept", "application/vnd.github+json")
+            .set("User-Agent", concat!("rust-gantt-app/", env!("CARGO_PKG_VERSION")));
+        if let Some(token) = token.filter(|t| !t.trim().is_empty()) {
+            request = request.set("Authorization", &format!("Bearer {}", token.trim()));
+        }
+        let response = request.call().map_err(|e| match e {
+            ureq::Error::Status(404, _) => format!("Repository {} not found (private repositories need a token)", repo),
+            ureq::Error::Status(401, _) => "GitHub rejected the token".to_string(),
+            ureq::Error::Status(403, _) => "GitHub refused the request (rate limit or missing access)".to_string(),
+            ureq::Error::Status(code, _) => format!("GitHub answered with HTTP {}", code),
+            ureq::Error::Transport(t) => format!("Could not reach GitHub: {}", t),
+        })?;
+        url = response.header("Link").and_then(next_page);
+        let batch: Vec<ApiIssue> = response
+            .into_json()
+            .map_err(|e| format!("Unexpected response from GitHub: {}", e))?;
+        issues.extend(batch.into_iter().filter(|i| i.pull_request.is_none()).map(convert));
+    }
+    Ok(issues)
+}
+
+/// A [`fetch_open_issues`] call running on its own thread.
+pub struct GithubFetch {
+    pub repo: String,
+    result: Receiver<Result<Vec<Issue>, String>>,
+}
+
+impl GithubFetch {
+    /// Start fetching. `ctx` is asked to repaint when the result is in.
+    pub fn start(ctx: &egui::Context, repo: String, token: Option<String>) -> Self {
+        let (tx, result) = mpsc::channel();
+        let ctx = ctx.clone();
+        let thread_repo = repo.clone();
+        std::thread::Builder::new()
+            .name("github-import".into())
+            .spawn(move || {
+                let _ = tx.send(fetch_open_issues(&thread_repo, token.as_deref()));
+                ctx.request_repaint();
+            })
+            .expect("failed to start GitHub import thread");
+        Self { repo, result }
+    }
+
+    /// The result once the fetch has finished.
+    pub fn poll(&self) -> Option<Result<Vec<Issue>, String>> {
+        match self.result.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err("GitHub import stopped unexpectedly".to_string())),
+        }
+    }
+}
+
+/// How many tasks an import created and refreshed.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ImportSummary {
+    pub added: usize,
+    pub updated: usize,
+}
+
+fn span(start: NaiveDate, end: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+    (
+        start.and_time(NaiveTime::from_hms_opt(9, 0, 0).unwrap()),
+        end.and_time(NaiveTime::from_hms_opt(17, 0, 0).unwrap()),
+    )
+}
+
+/// Add `issues` to `project`, matching earlier imports by their link.
+///
+/// New issues start `today` and last three days, or end on their due date
+/// when they have one. Issues that were imported before keep their dates;
+/// their name, description, tags, assignee and milestone are refreshed.
+/// Each milestone becomes (or updates) a parent task.
+pub fn merge_issues(project: &mut Project, issues: &[Issue], today: NaiveDate) -> ImportSummary {
+    let mut summary = ImportSummary::default();
+    let mut by_link: HashMap<String, Uuid> = project
+        .tasks
+        .iter()
+        .filter_map(|t| Some((t.link.clone()?, t.id)))
+        .collect();
+
+    for issue in issues {
+        let parent_id = issue.milestone.as_ref().map(|m| match by_link.get(&m.url) {
+            Some(&id) => {
+                if let Some(task) = project.task_mut(id) {
+                    task.name = m.title.clone();
+                }
+                id
+            }
+            None => {
+                let day = m.due.unwrap_or(today);
+                let (start, end) = span(day, day);
+                let mut task = Task::new(m.title.clone(), start, end);
+                task.link = Some(m.url.clone());
+                by_link.insert(m.url.clone(), task.id);
+                let id = task.id;
+                project.add_task(task);
+                summary.added += 1;
+                id
+            }
+        });
+
+        let tags: Vec<String> = issue.labels.iter().filter(|l| due_label(l).is_none()).cloned().collect();
+        let refresh = |task: &mut Task| {
+            task.name = issue.title.clone();
+            task.description = issue.body.clone();
+            task.tags = tags.clone();
+            task.assignee = issue.assignee.clone();
+            task.parent_id = parent_id;
+        };
+        match by_link.get(&issue.url).and_then(|&id| project.task_mut(id)) {
+            Some(task) => {
+                refresh(task);
+                summary.updated += 1;
+            }
+            None => {
+                let (start, end) = match issue.due() {
+                    Some(due) => span((due - Duration::days(DEFAULT_DAYS - 1)).min(due), due),
+                    None => span(today, today + Duration::days(DEFAULT_DAYS - 1)),
+                };
+                let mut task = Task::new(issue.title.clone(), start, end);
+                task.link = Some(issue.url.clone());
+                refresh(&mut task);
+                by_link.insert(issue.url.clone(), task.id);
+                project.add_task(task);
+                summary.added += 1;
+            }
+        }
+    }
+
+    // A milestone task moved under one of its own issues by hand would
+    // otherwise loop.
+    project.break_parent_cycles();
+    project.sort_tasks_grouped();
+    project.recalculate_parent_dates();
+    summary
+}
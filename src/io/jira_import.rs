@@ -0,0 +1,276 @@
+//! Importing a Jira board exported to CSV.
+//!
+//! Jira's export has dozens of columns; the ones listed in [`JiraColumn`]
+//! are mapped onto task fields and the rest are ignored. Epics become parent
+//! tasks and the stories linked to them their children.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, NaiveTime};
+
+use super::csv_import::{detect_delimiter, normalize_header, parse_datetime};
+use crate::model::task::TaskPriority;
+use crate::model::Task;
+use crate::ui::model_ext::ToRgba;
+use crate::ui::theme;
+
+/// Length given to issues without both a start and a due date.
+const DEFAULT_DAYS: i64 = 3;
+
+/// A Jira export column the import understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JiraColumn {
+    Summary,
+    Key,
+    Id,
+    Status,
+    Priority,
+    Assignee,
+    StartDate,
+    DueDate,
+    Description,
+    Parent,
+    ParentSummary,
+    Labels,
+}
+
+impl JiraColumn {
+    fn from_header(header: &str) -> Option<Self> {
+        Some(match normalize_header(header).as_str() {
+            "summary" => JiraColumn::Summary,
+            "issuekey" | "key" => JiraColumn::Key,
+            "issueid" => JiraColumn::Id,
+            "status" => JiraColumn::Status,
+            "priority" => JiraColumn::Priority,
+            "assignee" => JiraColumn::Assignee,
+            "startdate" | "customfield(startdate)" => JiraColumn::StartDate,
+            "duedate" => JiraColumn::DueDate,
+            "description" => JiraColumn::Description,
+            "parent" | "parentid" | "parentkey" | "parentlink" | "customfield(parentlink)" | "epiclink"
+            | "customfield(epiclink)" => JiraColumn::Parent,
+            "parentsummary" => JiraColumn::ParentSummary,
+            "labels" => JiraColumn::Labels,
+            _ => return None,
+        })
+    }
+}
+
+/// Jira's priority names, current and legacy, as a [`TaskPriority`].
+fn map_priority(priority: &str) -> TaskPriority {
+    match priority.trim().to_lowercase().as_str() {
+        "highest" | "blocker" | "critical" => TaskPriority::Critical,
+        "high" | "major" => TaskPriority::High,
+        "medium" => TaskPriority::Medium,
+        "low" | "lowest" | "minor" | "trivial" => TaskPriority::Low,
+        _ => TaskPriority::None,
+    }
+}
+
+/// Progress for a Jira status. Workflows are configurable, so unknown
+/// statuses count as not started.
+fn map_status(status: &str) -> f32 {
+    match status.trim().to_lowercase().as_str() {
+        "done" | "closed" | "resolved" | "complete" | "completed" | "released" => 1.0,
+        "in review" | "code review" | "review" | "in qa" | "qa" | "testing" | "in testing" => 0.75,
+        "in progress" | "in development" | "doing" | "started" => 0.5,
+        _ => 0.0,
+    }
+}
+
+/// Jira writes dates like `12/Mar/24 10:15 AM`; fall back to the generic
+/// CSV formats for anything else.
+fn parse_jira_date(value: &str, is_end: bool) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    for fmt in ["%d/%b/%y %I:%M %p", "%d/%b/%Y %I:%M %p", "%d/%b/%y %H:%M"] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(value, fmt) {
+            return Some(dt);
+        }
+    }
+    for fmt in ["%d/%b/%y", "%d/%b/%Y"] {
+        if let Ok(d) = NaiveDate::parse_from_str(value, fmt) {
+            return Some(d.and_time(day_time(is_end)));
+        }
+    }
+    parse_datetime(value, is_end)
+}
+
+fn day_time(is_end: bool) -> NaiveTime {
+    if is_end {
+        NaiveTime::from_hms_opt(17, 0, 0).unwrap()
+    } else {
+        NaiveTime::from_hms_opt(9, 0, 0).unwrap()
+    }
+}
+
+/// The result of reading a Jira export, held for preview before it is
+/// added to the project.
+#[derive(Debug, Clone, Default)]
+pub struct JiraImport {
+    /// Tasks in file order, with placeholder parents last. Children are
+    /// grouped under their parents when the tasks are added to a project.
+    pub tasks: Vec<Task>,
+    /// Jira key of each task, for the preview.
+    pub keys: Vec<String>,
+    /// Rows without a summary.
+    pub skipped: usize,
+    /// Headers that matched no [`JiraColumn`].
+    pub ignored_columns: Vec<String>,
+}
+
+impl JiraImport {
+    /// Name of a task's parent within the import.
+    pub fn parent_name(&self, task: &Task) -> Option<&str> {
+        let pid = task.parent_id?;
+        self.tasks.iter().find(|t| t.id == pid).map(|t| t.name.as_str())
+    }
+}
+
+struct Row {
+    task: Task,
+    key: String,
+    id: String,
+    parent: String,
+    parent_summary: String,
+}
+
+/// Read a Jira CSV export.
+pub fn import_jira_csv(path: &Path) -> Result<JiraImport, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    parse_jira_csv(&content)
+}
+
+/// Map the rows of a Jira CSV export onto tasks. Stories whose epic or
+/// parent is not in the file are grouped under a placeholder parent named
+/// after it.
+pub fn parse_jira_csv(content: &str) -> Result<JiraImport, String> {
+    let delimiter = detect_delimiter(content.lines().next().unwrap_or(""));
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Failed to read CSV headers: {}", e))?
+        .clone();
+    let columns: Vec<Option<JiraColumn>> = headers.iter().map(JiraColumn::from_header).collect();
+    if !columns.contains(&Some(JiraColumn::Summary)) {
+        return Err("Not a Jira export: there is no Summary column".to_string());
+    }
+    let mut ignored_columns: Vec<String> = Vec::new();
+    for (header, column) in headers.iter().zip(&columns) {
+        if column.is_none() && !header.is_empty() && !ignored_columns.iter().any(|h| h == header) {
+            ignored_columns.push(header.to_string());
+        }
+    }
+
+    let today = Local::now().date_naive();
+    let mut rows = Vec::new();
+    let mut skipped = 0;
+    for record in reader.records() {
+        let Ok(record) = record else {
+            skipped += 1;
+            continue;
+        };
+        let mut fields: HashMap<JiraColumn, &str> = HashMap::new();
+        let mut labels = Vec::new();
+        for (value, column) in record.iter().zip(&columns) {
+            match column {
+                Some(JiraColumn::Labels) if !value.is_empty() => labels.push(value.to_string()),
+                // Repeated columns: the first non-empty value wins.
+                Some(column) if !value.is_empty() => {
+                    fields.entry(*column).or_insert(value);
+                }
+                _ => {}
+            }
+        }
+        let get = |column| fields.get(&column).copied().unwrap_or("");
+
+        let name = get(JiraColumn::Summary);
+        if name.is_empty() {
+            skipped += 1;
+            continue;
+        }
+        let start = parse_jira_date(get(JiraColumn::StartDate), false);
+        let due = parse_jira_date(get(JiraColumn::DueDate), true);
+        let (start, end) = match (start, due) {
+            (Some(start), Some(due)) => (start, due.max(start)),
+            (Some(start), None) => (start, (start.date() + Duration::days(DEFAULT_DAYS - 1)).and_time(day_time(true))),
+            (None, Some(due)) => ((due.date() - Duration::days(DEFAULT_DAYS - 1)).and_time(day_time(false)), due),
+            (None, None) => (
+                today.and_time(day_time(false)),
+                (today + Duration::days(DEFAULT_DAYS - 1)).and_time(day_time(true)),
+            ),
+        };
+
+        let mut task = Task::new(name, start, end);
+        task.description = get(JiraColumn::Description).to_string();
+        task.priority = map_priority(get(JiraColumn::Priority));
+        task.progress = map_status(get(JiraColumn::Status));
+        task.assignee = Some(get(JiraColumn::Assignee).to_string()).filter(|a| !a.is_empty());
+        task.tags = labels;
+        rows.push(Row {
+            task,
+            key: get(JiraColumn::Key).to_string(),
+            id: get(JiraColumn::Id).to_string(),
+            parent: get(JiraColumn::Parent).to_string(),
+            parent_summary: get(JiraColumn::ParentSummary).to_string(),
+        });
+    }
+    if rows.is_empty() {
+        return Err("The Jira export has no issues".to_string());
+    }
+
+    // A story's Parent / Epic Link may hold either the key or the numeric id.
+    let mut by_ref: HashMap<String, usize> = HashMap::new();
+    for (i, row) in rows.iter().enumerate() {
+        for r in [&row.key, &row.id] {
+            if !r.is_empty() {
+                by_ref.entry(r.clone()).or_insert(i);
+            }
+        }
+    }
+    let mut placeholders: Vec<Row> = Vec::new();
+    let mut placeholder_of: HashMap<String, usize> = HashMap::new();
+    for i in 0..rows.len() {
+        let parent = rows[i].parent.clone();
+        if parent.is_empty() {
+            continue;
+        }
+        let parent_id = match by_ref.get(&parent) {
+            Some(&p) if p != i => rows[p].task.id,
+            Some(_) => continue,
+            None => {
+                let p = *placeholder_of.entry(parent.clone()).or_insert_with(|| {
+                    let name = if rows[i].parent_summary.is_empty() { &parent } else { &rows[i].parent_summary };
+                    let task = Task::new(name.clone(), rows[i].task.start, rows[i].task.end);
+                    placeholders.push(Row {
+                        task,
+                        key: parent.clone(),
+                        id: String::new(),
+                        parent: String::new(),
+                        parent_summary: String::new(),
+                    });
+                    placeholders.len() - 1
+                });
+                placeholders[p].task.id
+            }
+        };
+        rows[i].task.parent_id = Some(parent_id);
+    }
+    rows.extend(placeholders);
+
+    let colors = theme::task_palette();
+    let mut import = JiraImport { skipped, ignored_columns, ..Default::default() };
+    for (i, mut row) in rows.into_iter().enumerate() {
+        row.task.color = colors[i % colors.len()].to_rgba();
+        import.keys.push(row.key);
+        import.tasks.push(row.task);
+    }
+    Ok(import)
+}
+
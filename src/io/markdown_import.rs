@@ -0,0 +1,303 @@
+//! Importing a plan drafted as a nested Markdown list.
+//!
+//! `#` headings become phases and `-`, `*` or `+` bullets tasks; a heading
+//! holds everything up to the next heading of its level or above, and an
+//! indented bullet nests under the bullet above it. Text after a bullet
+//! can end in metadata: `(3d)` or `(2w)` for the length, `(0d)` for a
+//! milestone, `[50%]` for progress and `!high` for priority. A task-list
+//! box (`- [x] …`) marks the task done. Other lines are added to the
+//! description of the item above them.
+
+use std::path::Path;
+
+use chrono::{Duration, NaiveDate};
+use uuid::Uuid;
+
+use crate::model::project::TaskDefaults;
+use crate::model::quick_add::{parse_duration, parse_priority, MAX_DURATION_DAYS};
+use crate::model::task::TaskPriority;
+use crate::model::Task;
+use crate::ui::model_ext::ToRgba;
+use crate::ui::theme;
+
+/// Columns a tab indents a bullet by.
+const TAB_WIDTH: usize = 4;
+
+/// One heading or bullet of the outline.
+#[derive(Debug, Clone)]
+pub struct OutlineItem {
+    pub name: String,
+    /// 1-based line in the file.
+    pub line: usize,
+    /// Position of the item above it in [`MarkdownImport::items`].
+    pub parent: Option<usize>,
+    /// Length from `(3d)`; `Some(0)` for a milestone. Ignored on items with
+    /// children, whose span comes from them.
+    pub days: Option<i64>,
+    pub progress: f32,
+    pub priority: TaskPriority,
+    pub description: String,
+}
+
+/// A line the import couldn't fully use.
+#[derive(Debug, Clone)]
+pub struct OutlineProblem {
+    /// 1-based line in the file.
+    pub line: usize,
+    pub message: String,
+}
+
+/// The outline read from a Markdown file, held for preview before it is
+/// added to the project.
+#[derive(Debug, Clone)]
+pub struct MarkdownImport {
+    /// Headings and bullets in file order.
+    pub items: Vec<OutlineItem>,
+    pub problems: Vec<OutlineProblem>,
+    /// Day the first task starts; each task starts the day after the one
+    /// above it under the same parent ends.
+    pub anchor: NaiveDate,
+}
+
+impl MarkdownImport {
+    /// Nesting depth of item `i`, 0 for the top level.
+    pub fn depth(&self, i: usize) -> usize {
+        std::iter::successors(self.items[i].parent, |&p| self.items[p].parent).count()
+    }
+
+    /// The outline as tasks, in file order, laid out one after another from
+    /// `anchor` within each parent. Each top-level item and everything under
+    /// it share a colour.
+    pub fn tasks(&self, defaults: &TaskDefaults) -> Vec<Task> {
+        let ids: Vec<Uuid> = self.items.iter().map(|_| Uuid::new_v4()).collect();
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); self.items.len()];
+        for (i, item) in self.items.iter().enumerate() {
+            if let Some(p) = item.parent {
+                children[p].push(i);
+            }
+        }
+        let mut tasks: Vec<Option<Task>> = vec![None; self.items.len()];
+        let mut next_day = self.anchor;
+        for root in (0..self.items.len()).filter(|&i| self.items[i].parent.is_none()) {
+            next_day = self.place(root, next_day, defaults, &ids, &children, &mut tasks);
+        }
+
+        let palette = theme::task_palette();
+        let mut color = 0;
+        let mut tasks: Vec<Task> = tasks.into_iter().flatten().collect();
+        for (task, item) in tasks.iter_mut().zip(&self.items) {
+            if item.parent.is_none() {
+                color += 1;
+            }
+            task.color = palette[(color - 1) % palette.len().max(1)].to_rgba();
+        }
+        tasks
+    }
+
+    /// Lay out item `i` and its subtree from `first_day`. Returns the day
+    /// the next sibling starts.
+    fn place(
+        &self,
+        i: usize,
+        first_day: NaiveDate,
+        defaults: &TaskDefaults,
+        ids: &[Uuid],
+        children: &[Vec<usize>],
+        tasks: &mut [Option<Task>],
+    ) -> NaiveDate {
+        let item = &self.items[i];
+        let mut next_day = first_day;
+        let mut task = if children[i].is_empty() {
+            match item.days.unwrap_or(defaults.duration_days) {
+                0 => defaults.milestone(item.name.clone(), first_day),
+                days => {
+                    // Past the last date there is, later tasks pile up on it.
+                    next_day = first_day.checked_add_signed(Duration::days(days)).unwrap_or(NaiveDate::MAX);
+                    defaults.task(item.name.clone(), first_day, days)
+                }
+            }
+        } else {
+            for &child in &children[i] {
+                next_day = self.place(child, next_day, defaults, ids, children, tasks);
+            }
+            // Dates roll up from the children once the tasks are added.
+            defaults.task(item.name.clone(), first_day, 1)
+        };
+        task.id = ids[i];
+        task.parent_id = item.parent.map(|p| ids[p]);
+        task.progress = item.progress;
+        task.priority = item.priority;
+        task.description = item.description.clone();
+        tasks[i] = Some(task);
+        next_day
+    }
+}
+
+/// Read a Markdown outline, laid out from `anchor`.
+pub fn import_markdown(path: &Path, anchor: NaiveDate) -> Result<MarkdownImport, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    parse_markdown(&content, anchor)
+}
+
+/// Where an item sits in the outline: headings by level, then bullets by
+/// indentation, so every bullet is below every heading.
+fn rank(heading_level: Option<usize>, indent: usize) -> usize {
+    heading_level.unwrap_or(7 + indent)
+}
+
+/// Parse the headings and bullets of a Markdown document into an outline.
+pub fn parse_markdown(content: &str, anchor: NaiveDate) -> Result<MarkdownImport, String> {
+    let mut import = MarkdownImport { items: Vec::new(), problems: Vec::new(), anchor };
+    // Items that can still take children, with their rank, outermost first.
+    let mut open: Vec<(usize, usize)> = Vec::new();
+    let mut in_code = false;
+    for (n, raw) in content.lines().enumerate() {
+        let line = n + 1;
+        let trimmed = raw.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code || trimmed.is_empty() || is_rule(trimmed) {
+            continue;
+        }
+
+        let (level, text) = if let Some((level, text)) = heading(trimmed) {
+            (Some(level), text)
+        } else if let Some(text) = bullet(trimmed) {
+            (None, text)
+        } else {
+            match import.items.last_mut() {
+                Some(item) => {
+                    if !item.description.is_empty() {
+                        item.description.push('\n');
+                    }
+                    item.description.push_str(trimmed);
+                }
+                None => import.problems.push(OutlineProblem {
+                    line,
+                    message: "Text before the first heading or bullet; skipped".to_string(),
+                }),
+            }
+            continue;
+        };
+
+        let mut item = OutlineItem {
+            name: String::new(),
+            line,
+            parent: None,
+            days: None,
+            progress: 0.0,
+            priority: TaskPriority::None,
+            description: String::new(),
+        };
+        item.name = read_metadata(text, &mut item, &mut import.problems);
+        if item.name.is_empty() {
+            import.problems.push(OutlineProblem { line, message: "No task name; skipped".to_string() });
+            continue;
+        }
+        let rank = rank(level, indent_width(raw));
+        while open.last().is_some_and(|&(_, r)| r >= rank) {
+            open.pop();
+        }
+        item.parent = open.last().map(|&(i, _)| i);
+        open.push((import.items.len(), rank));
+        import.items.push(item);
+    }
+    if import.items.is_empty() {
+        return Err("No headings or list items found".to_string());
+    }
+    Ok(import)
+}
+
+/// `---`, `***` or `___` on their own.
+fn is_rule(line: &str) -> bool {
+    let mut marks = line.chars().filter(|c| *c != ' ');
+    let Some(first) = marks.next().filter(|c| matches!(c, '-' | '*' | '_')) else {
+        return false;
+    };
+    let rest: Vec<char> = marks.collect();
+    rest.len() >= 2 && rest.iter().all(|&c| c == first)
+}
+
+/// `## Build` → level 2 and `Build`.
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let rest = &line[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with(' ')) {
+        return None;
+    }
+    Some((level, rest.trim().trim_end_matches('#').trim_end()))
+}
+
+/// `- Build` → `Build`.
+fn bullet(line: &str) -> Option<&str> {
+    ["- ", "* ", "+ "].iter().find_map(|marker| line.strip_prefix(marker)).map(str::trim)
+}
+
+/// Columns of leading whitespace.
+fn indent_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+        .sum()
+}
+
+/// Take the task-list box from the front of `text` and the metadata from
+/// its end into `item`, returning the name left over.
+fn read_metadata(text: &str, item: &mut OutlineItem, problems: &mut Vec<OutlineProblem>) -> String {
+    let mut text = text;
+    for (box_, progress) in [("[ ]", 0.0), ("[x]", 1.0), ("[X]", 1.0)] {
+        if let Some(rest) = text.strip_prefix(box_) {
+            item.progress = progress;
+            text = rest.trim_start();
+        }
+    }
+    let line = item.line;
+    let mut problem = |message: String| problems.push(OutlineProblem { line, message });
+    loop {
+        text = text.trim_end();
+        if let Some((rest, inner)) = text.strip_suffix(')').and_then(|t| t.rsplit_once('(')) {
+            // Only `(<number><unit>)` is a length; `(2024)` or `(optional)` is part of the name.
+            let spec = inner.to_lowercase().replace(' ', "");
+            let digits = spec.find(|c: char| !c.is_ascii_digit()).unwrap_or(spec.len());
+            if digits == 0 || digits == spec.len() {
+                break;
+            }
+            let unit = &spec[digits..];
+            let known_unit = parse_duration(&format!("1{}", unit)).is_some();
+            if spec[..digits].parse::<i64>() == Ok(0) && known_unit {
+                item.days = Some(0);
+            } else {
+                match parse_duration(&spec) {
+                    Some(days) => item.days = Some(days),
+                    None if known_unit => {
+                        problem(format!("Length '({})' is too long; at most {} days", inner, MAX_DURATION_DAYS))
+                    }
+                    None => problem(format!("Unknown length '({})'; use days or weeks, e.g. (3d) or (2w)", inner)),
+                }
+            }
+            text = rest;
+        } else if let Some((rest, inner)) = text.strip_suffix(']').and_then(|t| t.rsplit_once('[')) {
+            let Some(percent) = inner.trim().strip_suffix('%') else {
+                break;
+            };
+            match percent.trim().parse::<f32>() {
+                Ok(p) if (0.0..=100.0).contains(&p) => item.progress = p / 100.0,
+                _ => problem(format!("Progress must be between 0% and 100%, not '[{}]'", inner)),
+            }
+            text = rest;
+        } else {
+            let (rest, word) = text.rsplit_once(' ').unwrap_or(("", text));
+            let Some(name) = word.strip_prefix('!').filter(|n| !n.is_empty()) else {
+                break;
+            };
+            match parse_priority(&name.to_lowercase()) {
+                Some(priority) => item.priority = priority,
+                None => problem(format!("Unknown priority '{}'; use !low, !medium, !high or !critical", word)),
+            }
+            text = rest;
+        }
+    }
+    text.trim().to_string()
+}
@@ -0,0 +1,116 @@
+use crate::export::{ExportExtra, ExportOptions, Exporter};
+use crate::model::{Project, Task};
+use crate::ui::model_ext::{StatusColor, ToRgba};
+use chrono::{Duration, NaiveDate};
+use std::fmt::Write as _;
+use std::io::Write;
+
+const WIDTH: f64 = 1200.0;
+const HEIGHT: f64 = 120.0;
+const MARGIN: f64 = 40.0;
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Export the milestone overview strip as a standalone SVG image.
+///
+/// Milestones are drawn as diamonds along the full project range, or the
+/// days of `crop`, coloured by status, with labels alternating above and
+/// below the axis.
+/// Returns the number of milestones written.
+pub fn export_milestones_svg(
+    tasks: &[Task],
+    crop: Option<(NaiveDate, NaiveDate)>,
+    out: &mut dyn Write,
+) -> Result<usize, String> {
+    let (start, end) = match crop {
+        Some((from, to)) => (from.and_hms_opt(0, 0, 0).unwrap(), (to + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap()),
+        None => crate::ui::milestone_strip::project_range(tasks).ok_or_else(|| "Project has no tasks".to_string())?,
+    };
+    let span = (end - start).num_minutes().max(1) as f64;
+    let x_of = |t: &Task| MARGIN + (t.start - start).num_minutes() as f64 / span * (WIDTH - 2.0 * MARGIN);
+    let axis_y = HEIGHT / 2.0;
+    let now = chrono::Local::now().naive_local();
+
+    let mut milestones: Vec<&Task> = tasks.iter().filter(|t| t.is_milestone).collect();
+    milestones.sort_by_key(|t| t.start);
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="11">"#,
+        w = WIDTH,
+        h = HEIGHT
+    );
+    let _ = writeln!(svg, r##"<rect width="100%" height="100%" fill="#FFFFFF"/>"##);
+    let _ = writeln!(
+        svg,
+        r##"<line x1="{}" y1="{y}" x2="{}" y2="{y}" stroke="#B0B4BC" stroke-width="1"/>"##,
+        MARGIN,
+        WIDTH - MARGIN,
+        y = axis_y
+    );
+    for (i, task) in milestones.iter().enumerate() {
+        let x = x_of(task);
+        let color = task.milestone_status(now).status_color().to_rgba().to_hex();
+        let _ = writeln!(
+            svg,
+            r##"<polygon points="{x},{t} {r},{y} {x},{b} {l},{y}" fill="{color}" stroke="#303030" stroke-width="0.5"><title>{name} ({date})</title></polygon>"##,
+            x = x,
+            y = axis_y,
+            t = axis_y - 7.0,
+            b = axis_y + 7.0,
+            l = x - 7.0,
+            r = x + 7.0,
+            color = color,
+            name = escape(&task.name),
+            date = task.start.format("%Y-%m-%d"),
+        );
+        let (label_y, baseline) = if i % 2 == 0 { (axis_y - 12.0, "auto") } else { (axis_y + 12.0, "hanging") };
+        let _ = writeln!(
+            svg,
+            r##"<text x="{x}" y="{y}" text-anchor="middle" dominant-baseline="{baseline}" fill="#202020">{name} <tspan fill="#707070">{date}</tspan></text>"##,
+            x = x,
+            y = label_y,
+            baseline = baseline,
+            name = escape(&task.name),
+            date = task.start.format("%b %d"),
+        );
+    }
+    svg.push_str("</svg>\n");
+
+    out.write_all(svg.as_bytes()).map_err(|e| format!("Failed to write SVG: {}", e))?;
+    Ok(milestones.len())
+}
+
+pub struct MilestoneSvgExporter;
+
+impl Exporter for MilestoneSvgExporter {
+    fn name(&self) -> &'static str {
+        "Milestones (SVG)"
+    }
+
+    fn extension(&self) -> &'static str {
+        "svg"
+    }
+
+    fn export(&self, project: &Project, options: &ExportOptions, out: &mut dyn Write) -> Result<usize, String> {
+        if !project.tasks.iter().any(|t| t.is_milestone) {
+            return Err("there are no milestones to export".to_string());
+        }
+        export_milestones_svg(&project.tasks, options.crop(), out)
+    }
+
+    fn item_label(&self) -> &'static str {
+        "milestones"
+    }
+
+    fn file_name(&self, project_name: &str) -> String {
+        format!("{} milestones.svg", project_name)
+    }
+
+    fn extras(&self) -> &'static [ExportExtra] {
+        &[ExportExtra::CropToRange]
+    }
+}
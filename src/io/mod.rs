@@ -1,5 +1,10 @@
-pub mod csv_export;
+pub mod background_save;
 pub mod csv_import;
-pub mod file;
+pub mod desktop_notify;
+pub mod github_import;
+pub mod jira_import;
+pub mod markdown_import;
+pub mod milestone_svg;
+pub mod save_hook;
 
-pub use file::{load_project, save_project};
+pub use gantt_core::file::{load_project, save_project};
@@ -0,0 +1,110 @@
+//! Running a user's shell command after each successful save, e.g. to
+//! regenerate an image and publish it somewhere.
+//!
+//! Commands run on their own thread so a slow script never stalls the UI;
+//! their output comes back through [`SaveHooks::poll`].
+
+use chrono::{DateTime, Local};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+/// Placeholder replaced by the saved file's path.
+pub const FILE_PLACEHOLDER: &str = "{file}";
+
+/// Most runs kept in the log.
+pub const LOG_LIMIT: usize = 50;
+
+/// One finished run of a post-save command.
+#[derive(Debug, Clone)]
+pub struct HookRun {
+    /// The command as run, with the path substituted.
+    pub command: String,
+    pub started: DateTime<Local>,
+    /// Exit code, or why the command could not be started or was killed.
+    pub result: Result<i32, String>,
+    /// stdout followed by stderr.
+    pub output: String,
+}
+
+impl HookRun {
+    pub fn succeeded(&self) -> bool {
+        matches!(self.result, Ok(0))
+    }
+}
+
+/// `path` quoted for the platform shell.
+fn shell_quote(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    if cfg!(windows) {
+        format!("\"{}\"", path.replace('"', "\"\""))
+    } else {
+        format!("'{}'", path.replace('\'', r"'\''"))
+    }
+}
+
+/// `template` with every `{file}` replaced by the quoted `path`.
+pub fn expand_command(template: &str, path: &Path) -> String {
+    template.replace(FILE_PLACEHOLDER, &shell_quote(path))
+}
+
+fn run(command: String) -> HookRun {
+    let started = Local::now();
+    let output = if cfg!(windows) {
+        Command::new("cmd").args(["/C", &command]).output()
+    } else {
+        Command::new("sh").args(["-c", &command]).output()
+    };
+    match output {
+        Ok(out) => {
+            let mut text = String::from_utf8_lossy(&out.stdout).into_owned();
+            text.push_str(&String::from_utf8_lossy(&out.stderr));
+            let result = out.status.code().ok_or_else(|| "terminated by a signal".to_string());
+            HookRun { command, started, result, output: text }
+        }
+        Err(e) => HookRun { command, started, result: Err(format!("could not start: {}", e)), output: String::new() },
+    }
+}
+
+/// Runs post-save commands in the background and collects their results.
+pub struct SaveHooks {
+    ctx: egui::Context,
+    done_tx: Sender<HookRun>,
+    done: Receiver<HookRun>,
+    running: usize,
+}
+
+impl SaveHooks {
+    /// `ctx` is asked to repaint when a command finishes.
+    pub fn new(ctx: egui::Context) -> Self {
+        let (done_tx, done) = mpsc::channel();
+        Self { ctx, done_tx, done, running: 0 }
+    }
+
+    /// Start `template` for the file just saved at `path`.
+    pub fn run(&mut self, template: &str, path: PathBuf) {
+        let command = expand_command(template, &path);
+        let tx = self.done_tx.clone();
+        let ctx = self.ctx.clone();
+        std::thread::Builder::new()
+            .name("post-save-hook".into())
+            .spawn(move || {
+                let _ = tx.send(run(command));
+                ctx.request_repaint();
+            })
+            .expect("failed to start post-save hook thread");
+        self.running += 1;
+    }
+
+    /// True while any command hasn't finished.
+    pub fn is_running(&self) -> bool {
+        self.running > 0
+    }
+
+    /// Runs finished since the last call, oldest first.
+    pub fn poll(&mut self) -> Vec<HookRun> {
+        let done: Vec<HookRun> = self.done.try_iter().collect();
+        self.running = self.running.saturating_sub(done.len());
+        done
+    }
+}
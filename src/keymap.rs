@@ -0,0 +1,187 @@
+//! Keyboard shortcuts for registered commands.
+//!
+//! Every command in the [`CommandRegistry`] may carry default key bindings.
+//! The [`Keymap`] starts from those defaults, applies the user's overrides
+//! from the settings file, and turns key presses into command ids each
+//! frame. Bindings are stored as text such as `Ctrl+Shift+T`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use egui::{InputState, Key, Modifiers};
+
+use crate::commands::CommandRegistry;
+
+/// A key plus the modifiers that must be held with it. `ctrl` means the
+/// platform command key (Cmd on macOS).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub key: Key,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyBinding {
+    /// Parse text such as `Ctrl+Shift+T`, `F2` or `Ctrl++`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut binding = Self { key: Key::Escape, ctrl: false, shift: false, alt: false };
+        let mut rest = text.trim();
+        loop {
+            if let Some(r) = rest.strip_prefix("Ctrl+").or_else(|| rest.strip_prefix("Cmd+")) {
+                binding.ctrl = true;
+                rest = r;
+            } else if let Some(r) = rest.strip_prefix("Shift+") {
+                binding.shift = true;
+                rest = r;
+            } else if let Some(r) = rest.strip_prefix("Alt+") {
+                binding.alt = true;
+                rest = r;
+            } else {
+                break;
+            }
+        }
+        binding.key = Key::from_name(rest)?;
+        Some(binding)
+    }
+
+    /// The binding for a key pressed with `modifiers`.
+    pub fn from_press(key: Key, modifiers: Modifiers) -> Self {
+        Self { key, ctrl: modifiers.command, shift: modifiers.shift, alt: modifiers.alt }
+    }
+
+    /// Text form used in the settings file (`Ctrl+Plus`); [`fmt::Display`]
+    /// gives the friendlier `Ctrl++`.
+    pub fn to_config(self) -> String {
+        format!("{}{}", self.modifier_prefix(), self.key.name())
+    }
+
+    /// True without Ctrl or Alt. Such bindings are ignored while a text
+    /// field has focus, so typing doesn't trigger them.
+    pub fn is_plain(self) -> bool {
+        !self.ctrl && !self.alt
+    }
+
+    /// On an arrow key. Text fields use these with every modifier, so they
+    /// are ignored while one has focus too.
+    pub fn is_arrow(self) -> bool {
+        matches!(self.key, Key::ArrowLeft | Key::ArrowRight | Key::ArrowUp | Key::ArrowDown)
+    }
+
+    fn modifier_prefix(self) -> String {
+        let mut prefix = String::new();
+        if self.ctrl {
+            prefix.push_str("Ctrl+");
+        }
+        if self.shift {
+            prefix.push_str("Shift+");
+        }
+        if self.alt {
+            prefix.push_str("Alt+");
+        }
+        prefix
+    }
+
+    fn modifiers(self) -> Modifiers {
+        let mut modifiers = Modifiers::NONE;
+        if self.ctrl {
+            modifiers = modifiers | Modifiers::COMMAND;
+        }
+        if self.shift {
+            modifiers = modifiers | Modifiers::SHIFT;
+        }
+        if self.alt {
+            modifiers = modifiers | Modifiers::ALT;
+        }
+        modifiers
+    }
+
+    fn modifier_count(self) -> usize {
+        [self.ctrl, self.shift, self.alt].iter().filter(|m| **m).count()
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.modifier_prefix(), self.key.symbol_or_name())
+    }
+}
+
+/// Current bindings for every command that has any.
+#[derive(Debug, Clone, Default)]
+pub struct Keymap {
+    bindings: HashMap<&'static str, Vec<KeyBinding>>,
+}
+
+impl Keymap {
+    /// Defaults from `registry`, with `overrides` (command id → binding
+    /// text, as saved in the settings) replacing them per command.
+    /// Unknown commands and unparseable bindings are skipped.
+    pub fn new(registry: &CommandRegistry, overrides: &BTreeMap<String, Vec<String>>) -> Self {
+        let mut bindings = HashMap::new();
+        for command in registry.iter() {
+            let keys = match overrides.get(command.id) {
+                Some(texts) => texts.iter().filter_map(|t| KeyBinding::parse(t)).collect(),
+                None => command.default_keys.clone(),
+            };
+            bindings.insert(command.id, keys);
+        }
+        Self { bindings }
+    }
+
+    pub fn bindings(&self, id: &str) -> &[KeyBinding] {
+        self.bindings.get(id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// The first binding of `id`, for menus and the command palette.
+    pub fn label(&self, id: &str) -> Option<String> {
+        self.bindings(id).first().map(|b| b.to_string())
+    }
+
+    pub fn set(&mut self, id: &'static str, keys: Vec<KeyBinding>) {
+        self.bindings.insert(id, keys);
+    }
+
+    /// The command other than `except` already using `binding`.
+    pub fn conflict(&self, binding: KeyBinding, except: &str) -> Option<&'static str> {
+        self.bindings
+            .iter()
+            .find(|(id, keys)| **id != except && keys.contains(&binding))
+            .map(|(id, _)| *id)
+    }
+
+    /// Bindings that differ from the registry defaults, for saving.
+    pub fn overrides(&self, registry: &CommandRegistry) -> BTreeMap<String, Vec<String>> {
+        registry
+            .iter()
+            .filter(|c| self.bindings(c.id) != c.default_keys.as_slice())
+            .map(|c| (c.id.to_string(), self.bindings(c.id).iter().map(|b| b.to_config()).collect()))
+            .collect()
+    }
+
+    /// Commands whose binding was pressed this frame, consuming the key
+    /// presses. More specific bindings win, so `Ctrl+Shift+Z` isn't also
+    /// read as `Ctrl+Z`. With `typing` set, plain keys and arrow keys (which
+    /// move the text cursor with any modifier) are left alone.
+    pub fn pressed(&self, input: &mut InputState, typing: bool) -> Vec<&'static str> {
+        let mut all: Vec<(KeyBinding, &'static str)> = self
+            .bindings
+            .iter()
+            .flat_map(|(id, keys)| keys.iter().map(move |b| (*b, *id)))
+            .filter(|(b, _)| !(typing && (b.is_plain() || b.is_arrow())))
+            .collect();
+        all.sort_by_key(|(b, id)| (std::cmp::Reverse(b.modifier_count()), *id));
+
+        let mut fired = Vec::new();
+        for (binding, id) in all {
+            // egui ignores an unrequested Alt; don't let Alt+0 set progress.
+            if !binding.alt && input.modifiers.alt {
+                continue;
+            }
+            if input.consume_key(binding.modifiers(), binding.key) && !fired.contains(&id) {
+                fired.push(id);
+            }
+        }
+        fired
+    }
+}
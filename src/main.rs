@@ -1,10 +1,16 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod app;
+mod commands;
+mod export;
+mod i18n;
 mod io;
-mod model;
+mod keymap;
+mod settings;
 mod ui;
 
+use gantt_core as model;
+
 fn main() -> eframe::Result<()> {
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -1,9 +0,0 @@
-pub mod history;
-pub mod project;
-pub mod task;
-pub mod timeline;
-
-pub use history::UndoHistory;
-pub use project::Project;
-pub use task::Task;
-pub use timeline::{TimelineScale, TimelineViewport};
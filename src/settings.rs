@@ -0,0 +1,350 @@
+//! Persisted user settings (lives in the OS config directory).
+//!
+//! Everything that should survive a restart but does not belong in a project
+//! file — the active theme, panel sizes, and other UI preferences — is kept in
+//! a single `settings.json` next to the user themes folder.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::export::ExportOptions;
+use crate::i18n::{tr, Language};
+use crate::model::highlight::DueState;
+use crate::model::risk::RiskLevel;
+use crate::model::task::TaskPriority;
+use crate::model::TimelineScale;
+use crate::ui::theme_manager::ThemePreset;
+use crate::ui::view_tabs::MainView;
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+/// Smallest and largest UI scale offered in the View menu.
+pub const UI_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.8..=1.6;
+
+/// Row spacing for the task list and chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum RowDensity {
+    Compact,
+    #[default]
+    Comfortable,
+}
+
+impl RowDensity {
+    pub fn label(self) -> &'static str {
+        match self {
+            RowDensity::Compact => tr("row_density.compact"),
+            RowDensity::Comfortable => tr("row_density.comfortable"),
+        }
+    }
+}
+
+/// Where task names are drawn on chart bars. Names that don't fit inside
+/// their bar move to its right either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum BarLabels {
+    #[default]
+    Inside,
+    Right,
+    Hidden,
+}
+
+impl BarLabels {
+    pub fn label(self) -> &'static str {
+        match self {
+            BarLabels::Inside => tr("bar_labels.inside"),
+            BarLabels::Right => tr("bar_labels.right"),
+            BarLabels::Hidden => tr("bar_labels.hidden"),
+        }
+    }
+}
+
+/// Which panels are open around the chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Panels {
+    pub task_list: bool,
+    /// The task editor above the list; only seen with the list open.
+    pub editor: bool,
+    pub filter_bar: bool,
+    pub agenda: bool,
+    pub milestone_strip: bool,
+    pub milestone_lane: bool,
+}
+
+impl Default for Panels {
+    fn default() -> Self {
+        Self {
+            task_list: true,
+            editor: true,
+            filter_bar: true,
+            agenda: false,
+            milestone_strip: false,
+            milestone_lane: false,
+        }
+    }
+}
+
+/// A named arrangement of panels, timeline scale and theme, switched from
+/// the toolbar.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Workspace {
+    pub name: String,
+    pub panels: Panels,
+    pub scale: TimelineScale,
+    /// Theme to switch to; `None` keeps the current one.
+    #[serde(default)]
+    pub theme: Option<String>,
+}
+
+impl Workspace {
+    /// Planning, Tracking and Presentation.
+    pub fn builtins() -> Vec<Workspace> {
+        vec![
+            Workspace {
+                name: "Planning".to_string(),
+                panels: Panels { milestone_lane: true, ..Panels::default() },
+                scale: TimelineScale::Days,
+                theme: None,
+            },
+            Workspace {
+                name: "Tracking".to_string(),
+                panels: Panels { agenda: true, milestone_strip: true, ..Panels::default() },
+                scale: TimelineScale::Weeks,
+                theme: None,
+            },
+            Workspace {
+                name: "Presentation".to_string(),
+                panels: Panels {
+                    task_list: true,
+                    editor: false,
+                    filter_bar: false,
+                    agenda: false,
+                    milestone_strip: true,
+                    milestone_lane: true,
+                },
+                scale: TimelineScale::Months,
+                theme: Some(ThemePreset::Light.theme_name().to_string()),
+            },
+        ]
+    }
+}
+
+/// Deadline reminder preferences.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    /// Send native desktop notifications (the toolbar bell lists reminders either way).
+    pub enabled: bool,
+    /// Remind this many hours before an unfinished task is due.
+    pub lead_hours: u32,
+    /// Only tasks with these priorities trigger reminders.
+    pub priorities: Vec<TaskPriority>,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            lead_hours: 24,
+            priorities: TaskPriority::all().to_vec(),
+        }
+    }
+}
+
+/// The chart's visible range and zoom, as left at exit.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SessionViewport {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    pub scale: TimelineScale,
+    pub pixels_per_day: f32,
+}
+
+/// Where the last run left off, picked up again at startup while
+/// [`AppSettings::restore_session`] is on. Panel layout is in the settings
+/// already.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Session {
+    /// Project file open at exit; `None` for an unsaved project.
+    pub file: Option<PathBuf>,
+    pub viewport: Option<SessionViewport>,
+    pub main_view: MainView,
+    pub selected_task: Option<Uuid>,
+    pub selection: Vec<Uuid>,
+    pub search_query: String,
+    pub filter_priority: Option<TaskPriority>,
+    pub filter_risk: Option<RiskLevel>,
+    pub filter_due: Option<DueState>,
+    pub hide_tentative: bool,
+}
+
+/// Persisted user settings.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    pub active_theme: String,
+    /// Width of the task list / editor panel. `None` uses the theme default.
+    pub task_panel_width: Option<f32>,
+    /// Accent colour from the in-app picker as `#RRGGBB`. `None` uses the theme's.
+    pub accent_color: Option<String>,
+    /// egui zoom factor applied on top of the OS scale, within [`UI_SCALE_RANGE`].
+    pub ui_scale: f32,
+    pub row_density: RowDensity,
+    /// Wrap long task names over two lines in the list, with the chart rows
+    /// growing to match.
+    pub wrap_task_names: bool,
+    pub bar_labels: BarLabels,
+    /// Language of the UI.
+    pub language: Language,
+    /// Show the task list panel left of the chart.
+    pub show_task_list: bool,
+    /// Show the search and priority filter above the task list.
+    pub show_filter_bar: bool,
+    /// Show the milestone overview strip above the chart.
+    pub show_milestone_strip: bool,
+    /// Pin a lane of milestones under the chart's timeline header.
+    pub show_milestone_lane: bool,
+    /// Only put milestones tagged "key" in the lane.
+    pub milestone_lane_key_only: bool,
+    /// Show the agenda panel to the right of the chart.
+    pub show_agenda: bool,
+    /// How many days ahead the agenda lists.
+    pub agenda_days: u32,
+    /// Width of the agenda panel, in points.
+    pub agenda_width: f32,
+    /// Put the agenda next to the task list instead of at the right edge.
+    pub agenda_beside_list: bool,
+    /// Show the task editor above the task list when a task is selected.
+    pub show_editor: bool,
+    /// Highlight the selected task's predecessor and successor chains.
+    pub trace_dependencies: bool,
+    /// Colour started task bars by how far behind schedule they are.
+    pub schedule_tint: bool,
+    /// Draw task and status colours from a palette that stays readable
+    /// with colour-vision deficiencies.
+    pub color_blind_safe: bool,
+    /// Name recorded on comments added from this machine.
+    pub author_name: String,
+    /// Most change events kept in a project's activity log.
+    pub activity_limit: usize,
+    /// Repository last imported from, as `owner/name`.
+    pub github_repo: String,
+    /// Write the activity log into the project file.
+    pub save_activity: bool,
+    /// Shell command run after every save of a project without its own;
+    /// `{file}` stands for the saved path. Empty runs nothing.
+    pub post_save_command: String,
+    /// Shortcuts changed from the defaults: command id → bindings such as
+    /// `Ctrl+Shift+T`. An empty list unbinds the command.
+    pub key_bindings: BTreeMap<String, Vec<String>>,
+    /// Last-used export dialog choices, by export format name.
+    pub export_options: BTreeMap<String, ExportOptions>,
+    pub notifications: NotificationSettings,
+    /// Workspaces offered in the toolbar, in order.
+    pub workspaces: Vec<Workspace>,
+    /// Name of the workspace last switched to.
+    pub active_workspace: Option<String>,
+    /// Reopen the last file, view, selection and filters at startup.
+    pub restore_session: bool,
+    pub session: Session,
+    /// Keyboard date nudges (Alt+arrows) step over weekends.
+    pub nudge_working_days: bool,
+    /// The first-run tour was finished or skipped; Help → Take the Tour
+    /// runs it again.
+    pub tour_done: bool,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            active_theme: "Default Dark".into(),
+            task_panel_width: None,
+            accent_color: None,
+            ui_scale: 1.0,
+            row_density: RowDensity::default(),
+            wrap_task_names: false,
+            bar_labels: BarLabels::default(),
+            language: Language::default(),
+            show_task_list: true,
+            show_filter_bar: true,
+            show_milestone_strip: false,
+            show_milestone_lane: false,
+            milestone_lane_key_only: false,
+            show_agenda: false,
+            agenda_days: 7,
+            agenda_width: 260.0,
+            agenda_beside_list: false,
+            show_editor: true,
+            trace_dependencies: true,
+            schedule_tint: false,
+            color_blind_safe: false,
+            author_name: std::env::var("USER").or_else(|_| std::env::var("USERNAME")).unwrap_or_default(),
+            activity_limit: 500,
+            github_repo: String::new(),
+            save_activity: true,
+            post_save_command: String::new(),
+            key_bindings: BTreeMap::new(),
+            export_options: BTreeMap::new(),
+            notifications: NotificationSettings::default(),
+            workspaces: Workspace::builtins(),
+            active_workspace: None,
+            restore_session: true,
+            session: Session::default(),
+            nudge_working_days: false,
+            tour_done: false,
+        }
+    }
+}
+
+impl AppSettings {
+    /// The panels open now.
+    pub fn panels(&self) -> Panels {
+        Panels {
+            task_list: self.show_task_list,
+            editor: self.show_editor,
+            filter_bar: self.show_filter_bar,
+            agenda: self.show_agenda,
+            milestone_strip: self.show_milestone_strip,
+            milestone_lane: self.show_milestone_lane,
+        }
+    }
+
+    pub fn set_panels(&mut self, panels: Panels) {
+        self.show_task_list = panels.task_list;
+        self.show_editor = panels.editor;
+        self.show_filter_bar = panels.filter_bar;
+        self.show_agenda = panels.agenda;
+        self.show_milestone_strip = panels.milestone_strip;
+        self.show_milestone_lane = panels.milestone_lane;
+    }
+
+    /// Load settings from disk, falling back to defaults for a missing or
+    /// unreadable file.
+    pub fn load() -> Self {
+        std::fs::read_to_string(settings_path())
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write settings to disk. Failures are ignored — settings are a convenience.
+    pub fn save(&self) {
+        let path = settings_path();
+        if let Ok(json) = serde_json::to_string_pretty(self) {
+            let _ = std::fs::create_dir_all(path.parent().unwrap_or(&path));
+            let _ = std::fs::write(&path, json);
+        }
+    }
+}
+
+/// The application's config directory (`…/RustGanttApp/config`).
+pub fn config_dir() -> PathBuf {
+    directories::ProjectDirs::from("", "", "RustGanttApp")
+        .map(|dirs| dirs.config_dir().to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+fn settings_path() -> PathBuf {
+    config_dir().join("settings.json")
+}
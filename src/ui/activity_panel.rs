@@ -0,0 +1,81 @@
+//! Project-wide activity log: every recorded change, newest first, with
+//! the settings for how much is kept and whether it is saved.
+
+use crate::app::GanttApp;
+use crate::ui::theme;
+use egui::{Context, Key, RichText, Window};
+use uuid::Uuid;
+
+/// Render the panel while `app.show_activity` is set.
+pub fn show_activity_panel(app: &mut GanttApp, ctx: &Context) {
+    let mut open = true;
+    let mut reveal: Option<Uuid> = None;
+    let mut clear = false;
+    Window::new(RichText::new("Activity").strong().size(14.0))
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_size([520.0, 420.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Keep the last");
+                let limit = ui.add(egui::DragValue::new(&mut app.settings.activity_limit).range(10..=10_000));
+                ui.label("changes");
+                if limit.changed() {
+                    app.project.activity.truncate(app.settings.activity_limit);
+                }
+                let save = ui
+                    .checkbox(&mut app.settings.save_activity, "Save with project")
+                    .on_hover_text("Write the log into the project file");
+                if limit.drag_stopped() || limit.lost_focus() || save.changed() {
+                    app.settings.save();
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.add_enabled(!app.project.activity.is_empty(), egui::Button::new("Clear")).clicked() {
+                        clear = true;
+                    }
+                });
+            });
+            ui.separator();
+
+            if app.project.activity.is_empty() {
+                ui.label(
+                    RichText::new("No changes recorded yet. Edits to names, dates, progress, priority and links show up here.")
+                        .size(11.0)
+                        .color(theme::text_dim()),
+                );
+                return;
+            }
+            egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                egui::Grid::new("activity_grid").num_columns(4).striped(true).spacing([12.0, 4.0]).show(ui, |ui| {
+                    for event in app.project.activity.events().iter().rev() {
+                        ui.label(
+                            RichText::new(event.at.format("%b %d %H:%M").to_string())
+                                .size(10.5)
+                                .color(theme::text_dim()),
+                        );
+                        let exists = app.project.task(event.task_id).is_some();
+                        let name = ui.add_enabled(exists, egui::Link::new(&event.task_name));
+                        if name.on_disabled_hover_text("Task no longer exists").clicked() {
+                            reveal = Some(event.task_id);
+                        }
+                        ui.label(RichText::new(event.field.label()).size(11.0).color(theme::text_secondary()));
+                        ui.label(RichText::new(event.summary()).size(11.0).color(theme::text_primary()));
+                        ui.end_row();
+                    }
+                });
+            });
+        });
+
+    if clear {
+        app.project.activity.clear();
+        app.status_message = "Activity log cleared".to_string();
+    }
+    if let Some(id) = reveal {
+        app.main_view = crate::ui::view_tabs::MainView::Gantt;
+        app.reveal_task(ctx, id);
+    }
+    if !open || ctx.input(|i| i.key_pressed(Key::Escape)) {
+        app.show_activity = false;
+    }
+}
@@ -0,0 +1,198 @@
+//! Day-by-day agenda of what starts, ends or is due over the next few days.
+//!
+//! Overdue work is listed first. Tasks hidden by the active search or
+//! priority filter are left out, so the agenda matches the task list.
+
+use crate::model::highlight::{due_state, due_time, short_span, DueState};
+use crate::model::task::TaskPriority;
+use crate::model::Task;
+use crate::ui::{filter_bar, theme};
+use crate::ui::theme::Severity;
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use egui::{RichText, Ui};
+use uuid::Uuid;
+
+/// Range offered by the day-count control.
+pub const AGENDA_DAYS_RANGE: std::ops::RangeInclusive<u32> = 1..=60;
+
+/// Actions the agenda can request.
+pub enum AgendaAction {
+    None,
+    Select(Uuid),
+    /// The user changed how many days ahead to list.
+    SetDays(u32),
+}
+
+/// Why a task appears on a given day.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum AgendaKind {
+    Milestone,
+    Ends,
+    Starts,
+}
+
+impl AgendaKind {
+    fn label(self) -> &'static str {
+        match self {
+            AgendaKind::Milestone => "Milestone",
+            AgendaKind::Ends => "Due",
+            AgendaKind::Starts => "Starts",
+        }
+    }
+
+    fn icon(self) -> &'static str {
+        match self {
+            AgendaKind::Milestone => egui_phosphor::regular::DIAMOND,
+            AgendaKind::Ends => egui_phosphor::regular::FLAG_CHECKERED,
+            AgendaKind::Starts => egui_phosphor::regular::PLAY,
+        }
+    }
+}
+
+/// True if the task is late at `now`, as the list's due badges count it.
+fn is_overdue(task: &Task, now: NaiveDateTime, window: Duration) -> bool {
+    due_state(task, now, window) == Some(DueState::Late)
+}
+
+/// Entries falling on `day`, milestones first.
+fn entries_on<'a>(tasks: &[&'a Task], day: NaiveDate) -> Vec<(AgendaKind, &'a Task)> {
+    let mut entries = Vec::new();
+    for &task in tasks {
+        if task.is_milestone {
+            if task.start.date() == day {
+                entries.push((AgendaKind::Milestone, task));
+            }
+            continue;
+        }
+        if task.start.date() == day {
+            entries.push((AgendaKind::Starts, task));
+        }
+        if task.end.date() == day {
+            entries.push((AgendaKind::Ends, task));
+        }
+    }
+    entries.sort_by(|(ka, a), (kb, b)| ka.cmp(kb).then(a.start.cmp(&b.start)));
+    entries
+}
+
+/// One clickable agenda line. Returns true when clicked.
+fn entry_row(ui: &mut Ui, icon: &str, task: &Task, detail: &str, selected: bool, accent: egui::Color32) -> bool {
+    let text = RichText::new(format!("{}  {}", icon, task.name)).size(11.5).color(accent);
+    ui.horizontal(|ui| {
+        let resp = ui.selectable_label(selected, text);
+        ui.label(RichText::new(detail).size(10.0).color(theme::text_dim()));
+        resp
+    })
+    .inner
+        .on_hover_text(format!(
+            "{}\n{} → {}",
+            task.name,
+            task.start.format("%Y-%m-%d %H:%M"),
+            task.end.format("%Y-%m-%d %H:%M")
+        ))
+        .clicked()
+}
+
+/// Render the agenda panel. `now` and `window` are the project's current
+/// time and due-soon window, from which overdue work is counted.
+#[allow(clippy::too_many_arguments)]
+pub fn show_agenda_panel(
+    tasks: &[Task],
+    selected_task: Option<Uuid>,
+    search_query: &str,
+    filter_priority: Option<TaskPriority>,
+    days: u32,
+    now: NaiveDateTime,
+    window: Duration,
+    ui: &mut Ui,
+) -> AgendaAction {
+    let mut action = AgendaAction::None;
+    let today = chrono::Local::now().date_naive();
+    let visible: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| filter_bar::task_matches(&t.name, &t.description, t.priority, search_query, filter_priority))
+        .collect();
+
+    ui.add_space(6.0);
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Agenda").strong().size(14.0).color(theme::text_primary()));
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            let mut value = days;
+            ui.label(RichText::new("days").size(11.0).color(theme::text_secondary()));
+            if ui
+                .add(egui::DragValue::new(&mut value).range(AGENDA_DAYS_RANGE).speed(0.2))
+                .changed()
+            {
+                action = AgendaAction::SetDays(value);
+            }
+        });
+    });
+    ui.separator();
+
+    egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+        let overdue: Vec<&Task> = {
+            let mut list: Vec<&Task> = visible.iter().copied().filter(|t| is_overdue(t, now, window)).collect();
+            list.sort_by_key(|t| due_time(t));
+            list
+        };
+        let overdue_color = theme::status_color(Severity::Bad, egui::Color32::from_rgb(220, 60, 60));
+        if !overdue.is_empty() {
+            ui.label(
+                RichText::new(format!("{} Overdue ({})", egui_phosphor::regular::WARNING, overdue.len()))
+                    .strong()
+                    .size(12.0)
+                    .color(overdue_color),
+            );
+            for task in overdue {
+                let detail = short_span(now - due_time(task))
+                    .map_or_else(|| "late".to_string(), |span| format!("{} late", span));
+                let icon = if task.is_milestone { AgendaKind::Milestone.icon() } else { AgendaKind::Ends.icon() };
+                if entry_row(ui, icon, task, &detail, selected_task == Some(task.id), overdue_color) {
+                    action = AgendaAction::Select(task.id);
+                }
+            }
+            ui.add_space(6.0);
+        }
+
+        let mut any = false;
+        for offset in 0..days as i64 {
+            let day = today + Duration::days(offset);
+            let entries = entries_on(&visible, day);
+            if entries.is_empty() {
+                continue;
+            }
+            any = true;
+            let heading = match offset {
+                0 => format!("Today · {}", day.format("%a %b %d")),
+                1 => format!("Tomorrow · {}", day.format("%a %b %d")),
+                _ => day.format("%A %b %d").to_string(),
+            };
+            ui.label(RichText::new(heading).strong().size(12.0).color(theme::text_secondary()));
+            for (kind, task) in entries {
+                let detail = match kind {
+                    AgendaKind::Starts => format!("{} {}", kind.label(), task.start.format("%H:%M")),
+                    AgendaKind::Ends => format!(
+                        "{} {} · {:.0}%",
+                        kind.label(),
+                        task.end.format("%H:%M"),
+                        task.progress * 100.0
+                    ),
+                    AgendaKind::Milestone => kind.label().to_string(),
+                };
+                if entry_row(ui, kind.icon(), task, &detail, selected_task == Some(task.id), theme::text_primary()) {
+                    action = AgendaAction::Select(task.id);
+                }
+            }
+            ui.add_space(6.0);
+        }
+        if !any {
+            ui.label(
+                RichText::new(format!("Nothing scheduled in the next {} days", days))
+                    .size(11.0)
+                    .color(theme::text_dim()),
+            );
+        }
+    });
+
+    action
+}
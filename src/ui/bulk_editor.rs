@@ -0,0 +1,278 @@
+use crate::model::Task;
+use crate::model::task::TaskPriority;
+use crate::ui::theme;
+use crate::ui::model_ext::{PriorityIcon, ToColor32};
+use egui::{Color32, Id, RichText, Ui};
+use uuid::Uuid;
+
+/// Changes to apply to every selected task. `None` fields are left alone.
+#[derive(Clone, Default)]
+pub struct BulkEdit {
+    pub priority: Option<TaskPriority>,
+    pub color: Option<Color32>,
+    /// `Some(None)` moves the tasks to the top level.
+    pub parent: Option<Option<Uuid>>,
+    pub shift_days: Option<i64>,
+    pub add_tag: Option<String>,
+    pub remove_tag: Option<String>,
+    pub progress: Option<f32>,
+}
+
+impl BulkEdit {
+    fn is_empty(&self) -> bool {
+        self.priority.is_none()
+            && self.color.is_none()
+            && self.parent.is_none()
+            && self.shift_days.is_none()
+            && self.add_tag.is_none()
+            && self.remove_tag.is_none()
+            && self.progress.is_none()
+    }
+}
+
+/// Actions that the bulk editor can request.
+pub enum BulkEditorAction {
+    None,
+    Apply(BulkEdit),
+    ClearSelection,
+}
+
+/// Per-field "apply this" toggles plus the values being edited.
+#[derive(Clone)]
+struct BulkEditState {
+    use_priority: bool,
+    priority: TaskPriority,
+    use_color: bool,
+    color: Color32,
+    use_parent: bool,
+    parent: Option<Uuid>,
+    use_shift: bool,
+    shift_days: i64,
+    use_add_tag: bool,
+    add_tag: String,
+    use_remove_tag: bool,
+    remove_tag: String,
+    use_progress: bool,
+    progress: f32,
+}
+
+impl BulkEditState {
+    fn new(selected: &[&Task]) -> Self {
+        let first = selected.first();
+        Self {
+            use_priority: false,
+            priority: first.map(|t| t.priority).unwrap_or_default(),
+            use_color: false,
+            color: first.map(|t| t.color.to_color32()).unwrap_or(Color32::GRAY),
+            use_parent: false,
+            parent: first.and_then(|t| t.parent_id),
+            use_shift: false,
+            shift_days: 0,
+            use_add_tag: false,
+            add_tag: String::new(),
+            use_remove_tag: false,
+            remove_tag: String::new(),
+            use_progress: false,
+            progress: first.map(|t| t.progress).unwrap_or(0.0),
+        }
+    }
+
+    fn to_edit(&self) -> BulkEdit {
+        let tag = |on: bool, s: &str| Some(s.trim().to_string()).filter(|t| on && !t.is_empty());
+        BulkEdit {
+            priority: self.use_priority.then_some(self.priority),
+            color: self.use_color.then_some(self.color),
+            parent: self.use_parent.then_some(self.parent),
+            shift_days: Some(self.shift_days).filter(|d| self.use_shift && *d != 0),
+            add_tag: tag(self.use_add_tag, &self.add_tag),
+            remove_tag: tag(self.use_remove_tag, &self.remove_tag),
+            progress: self.use_progress.then_some(self.progress),
+        }
+    }
+}
+
+/// The value shared by every selected task, or `None` if they differ.
+fn common<T: PartialEq>(selected: &[&Task], f: impl Fn(&Task) -> T) -> Option<T> {
+    let mut values = selected.iter().map(|t| f(t));
+    let first = values.next()?;
+    values.all(|v| v == first).then_some(first)
+}
+
+fn mixed_label(ui: &mut Ui) {
+    ui.label(RichText::new("(mixed)").size(11.0).italics().color(theme::text_dim()));
+}
+
+/// Render the bulk editor shown in place of the task editor when more than
+/// one task is selected.
+pub fn show_bulk_editor(all_tasks: &[Task], selection: &[Uuid], ui: &mut Ui) -> BulkEditorAction {
+    let mut action = BulkEditorAction::None;
+    let selected: Vec<&Task> = all_tasks.iter().filter(|t| selection.contains(&t.id)).collect();
+
+    // Keyed by the selection so changing it starts from fresh values.
+    let state_id = Id::new(("bulk-edit", selection));
+    let mut state: BulkEditState = ui
+        .ctx()
+        .data_mut(|d| d.get_temp(state_id))
+        .unwrap_or_else(|| BulkEditState::new(&selected));
+
+    ui.add_space(6.0);
+    ui.horizontal(|ui| {
+        ui.label(
+            RichText::new(format!("Edit {} Tasks", selected.len()))
+                .strong()
+                .size(13.0)
+                .color(theme::text_primary()),
+        );
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.small_button("Clear selection").clicked() {
+                action = BulkEditorAction::ClearSelection;
+            }
+        });
+    });
+    ui.add_space(4.0);
+
+    let frame = egui::Frame {
+        fill: theme::bg_dark(),
+        rounding: egui::Rounding::same(theme::widget_rounding_val()),
+        inner_margin: egui::Margin::same(theme::layout().editor_inner_margin),
+        outer_margin: egui::Margin::ZERO,
+        stroke: egui::Stroke::new(1.0, theme::border_subtle()),
+        shadow: egui::epaint::Shadow::NONE,
+    };
+
+    frame.show(ui, |ui| {
+        ui.spacing_mut().item_spacing.y = 6.0;
+        ui.visuals_mut().extreme_bg_color = theme::bg_field();
+        ui.label(
+            RichText::new("Tick a field to change it on every selected task.")
+                .size(9.5)
+                .color(theme::text_dim()),
+        );
+
+        egui::Grid::new("bulk-edit-grid")
+            .num_columns(2)
+            .spacing([8.0, 6.0])
+            .show(ui, |ui| {
+                // Priority
+                ui.checkbox(&mut state.use_priority, "Priority");
+                if state.use_priority {
+                    egui::ComboBox::from_id_salt("bulk-priority")
+                        .selected_text(format!("{} {}", state.priority.icon(), state.priority.label()))
+                        .show_ui(ui, |ui| {
+                            for p in TaskPriority::all() {
+                                ui.selectable_value(&mut state.priority, *p, format!("{} {}", p.icon(), p.label()));
+                            }
+                        });
+                } else if let Some(p) = common(&selected, |t| t.priority) {
+                    ui.label(RichText::new(format!("{} {}", p.icon(), p.label())).size(11.0).color(theme::text_dim()));
+                } else {
+                    mixed_label(ui);
+                }
+                ui.end_row();
+
+                // Color
+                ui.checkbox(&mut state.use_color, "Color");
+                if state.use_color {
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut state.color,
+                        egui::color_picker::Alpha::OnlyBlend,
+                    );
+                } else if let Some(c) = common(&selected, |t| t.color) {
+                    let (rect, _) = ui.allocate_exact_size(egui::vec2(16.0, 16.0), egui::Sense::hover());
+                    ui.painter().rect_filled(rect, egui::Rounding::same(3.0), c.to_color32());
+                } else {
+                    mixed_label(ui);
+                }
+                ui.end_row();
+
+                // Parent
+                let name_of = |id: Option<Uuid>| {
+                    id.and_then(|id| all_tasks.iter().find(|t| t.id == id))
+                        .map(|t| t.name.clone())
+                        .unwrap_or_else(|| "— None —".to_string())
+                };
+                ui.checkbox(&mut state.use_parent, "Parent");
+                if state.use_parent {
+                    egui::ComboBox::from_id_salt("bulk-parent")
+                        .selected_text(name_of(state.parent))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut state.parent, None, "— None —");
+                            // Same rule as the single editor: only top-level
+                            // tasks can be parents, and not a selected task.
+                            for t in all_tasks
+                                .iter()
+                                .filter(|t| t.parent_id.is_none() && !selection.contains(&t.id))
+                            {
+                                ui.selectable_value(&mut state.parent, Some(t.id), t.name.as_str());
+                            }
+                        });
+                } else if let Some(p) = common(&selected, |t| t.parent_id) {
+                    ui.label(RichText::new(name_of(p)).size(11.0).color(theme::text_dim()));
+                } else {
+                    mixed_label(ui);
+                }
+                ui.end_row();
+
+                // Shift dates
+                ui.checkbox(&mut state.use_shift, "Shift dates");
+                ui.add_enabled(
+                    state.use_shift,
+                    egui::DragValue::new(&mut state.shift_days)
+                        .range(-3650..=3650)
+                        .speed(0.1)
+                        .custom_formatter(|v, _| format!("{:+} d", v as i64)),
+                );
+                ui.end_row();
+
+                // Progress
+                ui.checkbox(&mut state.use_progress, "Progress");
+                if state.use_progress {
+                    ui.add(
+                        egui::Slider::new(&mut state.progress, 0.0..=1.0)
+                            .custom_formatter(|v, _| format!("{:.0}%", v * 100.0)),
+                    );
+                } else if let Some(p) = common(&selected, |t| (t.progress * 100.0).round() as i32) {
+                    ui.label(RichText::new(format!("{}%", p)).size(11.0).color(theme::text_dim()));
+                } else {
+                    mixed_label(ui);
+                }
+                ui.end_row();
+
+                // Tags
+                ui.checkbox(&mut state.use_add_tag, "Add tag");
+                ui.add_enabled(
+                    state.use_add_tag,
+                    egui::TextEdit::singleline(&mut state.add_tag).desired_width(120.0),
+                );
+                ui.end_row();
+
+                ui.checkbox(&mut state.use_remove_tag, "Remove tag");
+                ui.add_enabled(
+                    state.use_remove_tag,
+                    egui::TextEdit::singleline(&mut state.remove_tag).desired_width(120.0),
+                );
+                ui.end_row();
+            });
+
+        let edit = state.to_edit();
+        ui.add_space(4.0);
+        let btn = egui::Button::new(
+            RichText::new(format!("Apply to {} tasks", selected.len()))
+                .color(theme::text_on(theme::accent())),
+        )
+        .fill(theme::accent())
+        .rounding(egui::Rounding::same(4.0));
+        if ui
+            .add_enabled_ui(!edit.is_empty(), |ui| ui.add_sized([ui.available_width(), 26.0], btn))
+            .inner
+            .clicked()
+        {
+            action = BulkEditorAction::Apply(edit);
+            state = BulkEditState::new(&selected);
+        }
+    });
+
+    ui.ctx().data_mut(|d| d.insert_temp(state_id, state));
+    action
+}
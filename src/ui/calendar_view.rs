@@ -0,0 +1,278 @@
+//! Month grid alternative to the Gantt chart.
+//!
+//! Each week row lays its tasks out in lanes; a task spanning several weeks
+//! is drawn as one strip per week. Milestones are dots. Cells with more
+//! entries than fit show a "+N more" link that opens a list of the rest.
+
+use crate::model::Task;
+use crate::ui::theme;
+use crate::ui::model_ext::ToColor32;
+use chrono::{Datelike, Duration, NaiveDate};
+use egui::{Align2, FontId, Id, Pos2, Rect, RichText, Rounding, Sense, Stroke, Ui, Vec2};
+use uuid::Uuid;
+
+/// Actions the calendar can request.
+pub enum CalendarAction {
+    None,
+    Select(Uuid),
+    /// Select the task and switch back to the Gantt chart (double-click).
+    Open(Uuid),
+}
+
+const LANE_HEIGHT: f32 = 16.0;
+const LANE_GAP: f32 = 2.0;
+const DAY_LABEL_HEIGHT: f32 = 18.0;
+const WEEKDAY_NAMES: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// First day of the month shown, kept in temp data between frames.
+fn month_id() -> Id {
+    Id::new("calendar-month")
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+fn add_months(month: NaiveDate, delta: i32) -> NaiveDate {
+    let index = month.year() * 12 + month.month0() as i32 + delta;
+    NaiveDate::from_ymd_opt(index.div_euclid(12), index.rem_euclid(12) as u32 + 1, 1).unwrap_or(month)
+}
+
+/// A task's placement within one week row.
+struct WeekEntry<'a> {
+    task: &'a Task,
+    first_col: usize,
+    last_col: usize,
+    lane: usize,
+}
+
+/// Render the calendar month view.
+pub fn show_calendar_view(
+    tasks: &[Task],
+    selected_task: Option<Uuid>,
+    selection: &[Uuid],
+    ui: &mut Ui,
+) -> CalendarAction {
+    let mut action = CalendarAction::None;
+    let today = chrono::Local::now().date_naive();
+    let mut month: NaiveDate = ui
+        .ctx()
+        .data_mut(|d| d.get_temp(month_id()))
+        .unwrap_or_else(|| first_of_month(today));
+
+    // ── Navigation ────────────────────────────────────────────────────
+    ui.add_space(4.0);
+    ui.horizontal(|ui| {
+        ui.add_space(6.0);
+        if ui.button(egui_phosphor::regular::CARET_LEFT).on_hover_text("Previous month").clicked() {
+            month = add_months(month, -1);
+        }
+        if ui.button(egui_phosphor::regular::CARET_RIGHT).on_hover_text("Next month").clicked() {
+            month = add_months(month, 1);
+        }
+        if ui.button("Today").clicked() {
+            month = first_of_month(today);
+        }
+        ui.add_space(6.0);
+        ui.label(
+            RichText::new(month.format("%B %Y").to_string())
+                .strong()
+                .size(14.0)
+                .color(theme::text_primary()),
+        );
+    });
+    ui.ctx().data_mut(|d| d.insert_temp(month_id(), month));
+    ui.add_space(4.0);
+
+    // ── Grid geometry ─────────────────────────────────────────────────
+    let grid_start = month - Duration::days(month.weekday().num_days_from_monday() as i64);
+    let next_month = add_months(month, 1);
+    let weeks = ((next_month - grid_start).num_days() + 6) / 7;
+
+    let (rect, _) = ui.allocate_exact_size(ui.available_size(), Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, Rounding::ZERO, theme::bg_dark());
+
+    let header_h = 20.0;
+    let col_w = rect.width() / 7.0;
+    let week_h = ((rect.height() - header_h) / weeks as f32).max(DAY_LABEL_HEIGHT + LANE_HEIGHT * 2.0);
+    let col_x = |col: usize| rect.left() + col as f32 * col_w;
+
+    for (col, name) in WEEKDAY_NAMES.iter().enumerate() {
+        painter.text(
+            Pos2::new(col_x(col) + col_w / 2.0, rect.top() + header_h / 2.0),
+            Align2::CENTER_CENTER,
+            *name,
+            FontId::proportional(11.0),
+            theme::text_dim(),
+        );
+    }
+
+    // Leaf tasks and milestones; parents would only repeat their children's span.
+    let entries: Vec<&Task> = tasks.iter().filter(|t| !t.has_children(tasks)).collect();
+    // Lanes that fit below the day number, keeping one for "+N more".
+    let max_lanes = (((week_h - DAY_LABEL_HEIGHT) / (LANE_HEIGHT + LANE_GAP)).floor() as usize).saturating_sub(1).max(1);
+
+    for week in 0..weeks as usize {
+        let week_start = grid_start + Duration::days(week as i64 * 7);
+        let week_end = week_start + Duration::days(6);
+        let top = rect.top() + header_h + week as f32 * week_h;
+
+        // Day cells
+        for col in 0..7 {
+            let date = week_start + Duration::days(col as i64);
+            let cell = Rect::from_min_size(Pos2::new(col_x(col), top), Vec2::new(col_w, week_h));
+            if date.month() != month.month() {
+                painter.rect_filled(cell, Rounding::ZERO, theme::weekend_shade());
+            }
+            painter.rect_stroke(cell, Rounding::ZERO, Stroke::new(1.0, theme::grid_line()));
+            let color = if date == today {
+                theme::today_line()
+            } else if date.month() == month.month() {
+                theme::text_secondary()
+            } else {
+                theme::text_dim()
+            };
+            painter.text(
+                cell.left_top() + Vec2::new(5.0, 3.0),
+                Align2::LEFT_TOP,
+                date.day().to_string(),
+                FontId::proportional(11.0),
+                color,
+            );
+        }
+
+        // Lay out the tasks touching this week, longest first within a start day.
+        let mut in_week: Vec<&Task> = entries
+            .iter()
+            .copied()
+            .filter(|t| t.start.date() <= week_end && t.end.date() >= week_start)
+            .collect();
+        in_week.sort_by_key(|t| (t.start.date().max(week_start), std::cmp::Reverse(t.end.date())));
+
+        let mut lanes: Vec<[bool; 7]> = Vec::new();
+        let mut placed: Vec<WeekEntry> = Vec::new();
+        let mut hidden: [Vec<&Task>; 7] = Default::default();
+        for task in in_week {
+            let first_col = (task.start.date().max(week_start) - week_start).num_days() as usize;
+            let last_col = (task.end.date().min(week_end) - week_start).num_days() as usize;
+            let lane = lanes
+                .iter()
+                .position(|used| !used[first_col..=last_col].iter().any(|u| *u))
+                .unwrap_or_else(|| {
+                    lanes.push([false; 7]);
+                    lanes.len() - 1
+                });
+            lanes[lane][first_col..=last_col].iter_mut().for_each(|u| *u = true);
+            if lane < max_lanes {
+                placed.push(WeekEntry { task, first_col, last_col, lane });
+            } else {
+                for cell in &mut hidden[first_col..=last_col] {
+                    cell.push(task);
+                }
+            }
+        }
+
+        for entry in &placed {
+            let task = entry.task;
+            let y = top + DAY_LABEL_HEIGHT + entry.lane as f32 * (LANE_HEIGHT + LANE_GAP);
+            let strip = Rect::from_min_max(
+                Pos2::new(col_x(entry.first_col) + 3.0, y),
+                Pos2::new(col_x(entry.last_col + 1) - 3.0, y + LANE_HEIGHT),
+            );
+            let resp = ui.interact(strip, Id::new(("calendar-entry", task.id, week)), Sense::click());
+            let is_selected = selected_task == Some(task.id) || selection.contains(&task.id);
+
+            if task.is_milestone {
+                let center = Pos2::new(strip.left() + 6.0, strip.center().y);
+                painter.circle_filled(center, 4.5, task.color.to_color32());
+                if is_selected {
+                    painter.circle_stroke(center, 6.0, Stroke::new(1.5, theme::text_primary()));
+                }
+                painter.with_clip_rect(strip).text(
+                    Pos2::new(center.x + 9.0, center.y),
+                    Align2::LEFT_CENTER,
+                    &task.name,
+                    FontId::proportional(10.5),
+                    theme::text_primary(),
+                );
+            } else {
+                // Square off the ends that continue into the previous/next week.
+                let r = 3.0;
+                let starts_here = task.start.date() >= week_start;
+                let ends_here = task.end.date() <= week_end;
+                let rounding = Rounding {
+                    nw: if starts_here { r } else { 0.0 },
+                    sw: if starts_here { r } else { 0.0 },
+                    ne: if ends_here { r } else { 0.0 },
+                    se: if ends_here { r } else { 0.0 },
+                };
+                let color = task.color.to_color32();
+                let fill = if resp.hovered() { color.gamma_multiply(1.15) } else { color };
+                painter.rect_filled(strip, rounding, fill);
+                if is_selected {
+                    painter.rect_stroke(strip, rounding, Stroke::new(1.5, theme::text_primary()));
+                }
+                painter.with_clip_rect(strip.shrink2(Vec2::new(4.0, 0.0))).text(
+                    Pos2::new(strip.left() + 5.0, strip.center().y),
+                    Align2::LEFT_CENTER,
+                    &task.name,
+                    FontId::proportional(10.5),
+                    theme::text_on(color),
+                );
+            }
+
+            let resp = resp.on_hover_text(format!(
+                "{}\n{} → {}",
+                task.name,
+                task.start.format("%Y-%m-%d %H:%M"),
+                task.end.format("%Y-%m-%d %H:%M")
+            ));
+            if resp.double_clicked() {
+                action = CalendarAction::Open(task.id);
+            } else if resp.clicked() {
+                action = CalendarAction::Select(task.id);
+            }
+        }
+
+        // "+N more" links for cells with hidden entries
+        for (col, rest) in hidden.iter().enumerate().filter(|(_, rest)| !rest.is_empty()) {
+            let y = top + DAY_LABEL_HEIGHT + max_lanes as f32 * (LANE_HEIGHT + LANE_GAP);
+            let link_rect = Rect::from_min_size(
+                Pos2::new(col_x(col) + 3.0, y),
+                Vec2::new(col_w - 6.0, LANE_HEIGHT),
+            );
+            let link = ui.put(
+                link_rect,
+                egui::Label::new(
+                    RichText::new(format!("+{} more", rest.len()))
+                        .size(10.5)
+                        .color(theme::accent()),
+                )
+                .sense(Sense::click()),
+            );
+            let popup_id = Id::new(("calendar-more", week, col));
+            if link.clicked() {
+                ui.memory_mut(|m| m.toggle_popup(popup_id));
+            }
+            egui::popup_below_widget(ui, popup_id, &link, egui::PopupCloseBehavior::CloseOnClickOutside, |ui| {
+                ui.set_min_width(160.0);
+                let date = week_start + Duration::days(col as i64);
+                ui.label(RichText::new(date.format("%a %b %-d").to_string()).strong().size(11.0));
+                for task in rest {
+                    let selected = selected_task == Some(task.id);
+                    ui.horizontal(|ui| {
+                        let (swatch, _) = ui.allocate_exact_size(Vec2::splat(10.0), Sense::hover());
+                        ui.painter().rect_filled(swatch, Rounding::same(2.0), task.color.to_color32());
+                        if ui.selectable_label(selected, task.name.as_str()).clicked() {
+                            action = CalendarAction::Select(task.id);
+                            ui.memory_mut(|m| m.close_popup());
+                        }
+                    });
+                }
+            });
+        }
+    }
+
+    action
+}
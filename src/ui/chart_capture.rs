@@ -0,0 +1,206 @@
+//! Copying the chart to the clipboard as an image, from Edit → Copy Chart
+//! as Image.
+//!
+//! egui can't paint offscreen, so the image is cut from a screenshot of the
+//! window: the chart records where it was drawn each frame, a capture waits
+//! a couple of frames for menus to close (and, for the whole project, for
+//! the timeline to fit the window), then asks for a screenshot and crops
+//! it to the chart.
+//!
+//! A whole project taller than the window is taken a page at a time: the
+//! chart is scrolled down between screenshots and the rows each one adds
+//! below the frozen header are stacked under the last. The zoom and scroll
+//! position are put back once the copy is done.
+
+use std::borrow::Cow;
+
+use egui::{ColorImage, Context, Rect, Vec2, ViewportCommand};
+
+use crate::model::TimelineViewport;
+use crate::ui::gantt_chart::{self, ChartScroll};
+
+/// Frames to let pass before taking the screenshot, so the menu that asked
+/// for it has closed and a fit or scroll has been applied.
+const SETTLE_FRAMES: u8 = 2;
+
+/// Seconds to wait for a requested screenshot before giving up.
+const SCREENSHOT_TIMEOUT: f64 = 5.0;
+
+enum State {
+    Idle,
+    /// Repainting until the chart has settled.
+    Settling(u8),
+    /// Screenshot requested at this time on egui's clock; waiting for the
+    /// event carrying it.
+    Waiting(f64),
+}
+
+/// A whole-project capture under way.
+struct Pages {
+    /// The pages stacked so far.
+    image: Option<ColorImage>,
+    /// How far down the chart's content `image` reaches, in points.
+    covered: f32,
+    /// The zoom and scroll position to go back to.
+    viewport: TimelineViewport,
+    offset: Vec2,
+}
+
+pub struct ChartCapture {
+    state: State,
+    /// Where the chart was drawn and scrolled to in the last frame.
+    scroll: Option<ChartScroll>,
+    pages: Option<Pages>,
+    /// Kept open for the app's lifetime: on X11 and Wayland the copied image
+    /// is only served while the clipboard that set it exists.
+    clipboard: Option<arboard::Clipboard>,
+}
+
+impl Default for ChartCapture {
+    fn default() -> Self {
+        Self { state: State::Idle, scroll: None, pages: None, clipboard: None }
+    }
+}
+
+impl ChartCapture {
+    /// Start a capture of the chart as shown; the result comes from a later
+    /// [`poll`](Self::poll).
+    pub fn request(&mut self, ctx: &Context) {
+        self.pages = None;
+        self.state = State::Settling(SETTLE_FRAMES);
+        ctx.request_repaint();
+    }
+
+    /// Start a capture of the whole project, fitted to the chart's width and
+    /// as many screens tall as its rows need. `viewport` is restored after.
+    pub fn request_whole(&mut self, ctx: &Context, viewport: &TimelineViewport) {
+        let offset = self.scroll.map_or(Vec2::ZERO, |s| s.offset);
+        gantt_chart::fit_to_window(ctx);
+        gantt_chart::scroll_to(ctx, Vec2::ZERO);
+        self.request(ctx);
+        self.pages = Some(Pages { image: None, covered: 0.0, viewport: viewport.clone(), offset });
+    }
+
+    pub fn is_busy(&self) -> bool {
+        !matches!(self.state, State::Idle)
+    }
+
+    /// Record where the chart was drawn and scrolled to this frame.
+    pub fn set_chart_scroll(&mut self, scroll: ChartScroll) {
+        self.scroll = Some(scroll);
+    }
+
+    /// Advance a running capture, putting `viewport` back once a
+    /// whole-project capture ends. Returns the copied image's size in pixels
+    /// once it is on the clipboard, or why it couldn't be copied.
+    pub fn poll(&mut self, ctx: &Context, viewport: &mut TimelineViewport) -> Option<Result<[usize; 2], String>> {
+        match self.state {
+            State::Idle => None,
+            State::Settling(0) => {
+                self.state = State::Waiting(ctx.input(|i| i.time));
+                ctx.send_viewport_cmd(ViewportCommand::Screenshot);
+                ctx.request_repaint();
+                None
+            }
+            State::Settling(frames) => {
+                self.state = State::Settling(frames - 1);
+                ctx.request_repaint();
+                None
+            }
+            State::Waiting(since) => {
+                let image = ctx.input(|i| {
+                    i.raw.events.iter().find_map(|e| match e {
+                        egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                        _ => None,
+                    })
+                });
+                let Some(image) = image else {
+                    if ctx.input(|i| i.time) - since > SCREENSHOT_TIMEOUT {
+                        return Some(self.finish(ctx, viewport, Err("no screenshot came back".to_string())));
+                    }
+                    ctx.request_repaint();
+                    return None;
+                };
+                let Some(scroll) = self.scroll.filter(|s| s.rect.intersect(ctx.screen_rect()).is_positive()) else {
+                    return Some(self.finish(ctx, viewport, Err("the chart isn't on screen".to_string())));
+                };
+                let rect = scroll.rect.intersect(ctx.screen_rect());
+                let ppp = Some(ctx.pixels_per_point());
+                let Some(pages) = &mut self.pages else {
+                    let chart = image.region(&rect, ppp);
+                    return Some(self.finish(ctx, viewport, Ok(chart)));
+                };
+
+                // The first page is taken whole; after that, only rows that
+                // came into view below the frozen header are new.
+                let from = if pages.image.is_none() {
+                    0.0
+                } else {
+                    pages.covered.max(scroll.offset.y + scroll.header_height)
+                };
+                let to = (scroll.offset.y + rect.height()).min(scroll.content_height);
+                if to > from {
+                    let strip = Rect::from_x_y_ranges(
+                        rect.x_range(),
+                        rect.top() + from - scroll.offset.y..=rect.top() + to - scroll.offset.y,
+                    );
+                    let strip = image.region(&strip, ppp);
+                    pages.image = Some(match pages.image.take() {
+                        Some(above) => stack(above, &strip),
+                        None => strip,
+                    });
+                }
+                let progressed = to > pages.covered;
+                pages.covered = pages.covered.max(to);
+                if pages.covered >= scroll.content_height || !progressed {
+                    let image = pages.image.take().ok_or_else(|| "the chart isn't on screen".to_string());
+                    return Some(self.finish(ctx, viewport, image));
+                }
+                let next = Vec2::new(scroll.offset.x, pages.covered - scroll.header_height);
+                gantt_chart::scroll_to(ctx, next);
+                self.state = State::Settling(SETTLE_FRAMES);
+                ctx.request_repaint();
+                None
+            }
+        }
+    }
+
+    /// End the capture, copying `image` if there is one, and put back the
+    /// view a whole-project capture changed.
+    fn finish(
+        &mut self,
+        ctx: &Context,
+        viewport: &mut TimelineViewport,
+        image: Result<ColorImage, String>,
+    ) -> Result<[usize; 2], String> {
+        self.state = State::Idle;
+        if let Some(pages) = self.pages.take() {
+            *viewport = pages.viewport;
+            gantt_chart::scroll_to(ctx, pages.offset);
+            ctx.request_repaint();
+        }
+        let image = image?;
+        self.copy(&image).map(|()| image.size)
+    }
+
+    fn copy(&mut self, image: &ColorImage) -> Result<(), String> {
+        let clipboard = match &mut self.clipboard {
+            Some(clipboard) => clipboard,
+            None => self.clipboard.insert(arboard::Clipboard::new().map_err(|e| e.to_string())?),
+        };
+        let bytes: Vec<u8> = image.pixels.iter().flat_map(|p| p.to_srgba_unmultiplied()).collect();
+        clipboard
+            .set_image(arboard::ImageData { width: image.size[0], height: image.size[1], bytes: Cow::Owned(bytes) })
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// `below` added under `above`, both cut to the narrower one's width.
+fn stack(above: ColorImage, below: &ColorImage) -> ColorImage {
+    let width = above.size[0].min(below.size[0]);
+    let rows = |image: &ColorImage| {
+        image.pixels.chunks(image.size[0].max(1)).map(|row| row[..width].to_vec()).collect::<Vec<_>>()
+    };
+    let pixels: Vec<_> = rows(&above).into_iter().chain(rows(below)).flatten().collect();
+    ColorImage { size: [width, above.size[1] + below.size[1]], pixels }
+}
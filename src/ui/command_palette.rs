@@ -0,0 +1,299 @@
+//! Fuzzy-search overlay for jumping to tasks and running commands
+//! (Ctrl+P / Ctrl+K). A query starting with `#` searches task slugs only,
+//! so `#api-design` jumps straight to that task.
+//!
+//! Commands come from the app's [`CommandRegistry`](crate::commands::CommandRegistry);
+//! the palette only searches and runs them.
+//!
+//! Opened with `palette_link_from` set (L), the palette picks a successor
+//! for that task instead: it lists only the tasks it can be linked to, and
+//! picking one asks for the link's kind as a drop in the chart does.
+
+use crate::app::GanttApp;
+use crate::ui::theme;
+use egui::{Context, Key, Modifiers, RichText, Sense, Ui};
+use uuid::Uuid;
+
+/// Entries listed at once; refine the query to see more.
+const MAX_RESULTS: usize = 60;
+const PALETTE_WIDTH: f32 = 520.0;
+
+#[derive(Clone, Default)]
+struct PaletteState {
+    query: String,
+    selected: usize,
+    focused: bool,
+}
+
+fn state_id() -> egui::Id {
+    egui::Id::new("command-palette")
+}
+
+#[derive(Clone, Copy)]
+enum Target {
+    Command(&'static str),
+    Task(Uuid),
+}
+
+struct Entry {
+    target: Target,
+    title: String,
+    /// Parent path for tasks.
+    subtitle: Option<String>,
+    /// Shortcut for commands, dates for tasks.
+    detail: String,
+    enabled: bool,
+    score: i32,
+}
+
+/// Subsequence match of `query` in `text`, ignoring case and spaces in the
+/// query. Higher is better: consecutive letters and word starts score extra,
+/// skipped letters cost a little.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut prev: Option<usize> = None;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = (pos..text.len()).find(|&i| text[i] == q)?;
+        score += 1;
+        if found > 0 && prev == Some(found - 1) {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        score -= (found - pos).min(3) as i32;
+        pos = found + 1;
+        prev = Some(found);
+    }
+    Some(score)
+}
+
+/// Tasks whose slug matches `query` (without its `#`), an exact match
+/// first, then best first.
+fn slug_entries(app: &GanttApp, query: &str, pickable: &dyn Fn(Uuid) -> bool) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = app
+        .project
+        .tasks
+        .iter()
+        .filter(|task| pickable(task.id))
+        .filter_map(|task| {
+            let slug = task.slug.as_deref()?;
+            let score = if slug == query { i32::MAX } else { fuzzy_score(query, slug)? };
+            Some(Entry {
+                target: Target::Task(task.id),
+                title: task.name.clone(),
+                subtitle: None,
+                detail: format!("#{}", slug),
+                enabled: true,
+                score,
+            })
+        })
+        .collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.score));
+    entries.truncate(MAX_RESULTS);
+    entries
+}
+
+/// Matching commands and tasks, best first. With an empty query, commands
+/// come first in registration order, then tasks in list order. While
+/// picking a successor, only the tasks it can be: no commands, and no task
+/// already linked or that would close a cycle.
+fn collect_entries(app: &GanttApp, query: &str) -> Vec<Entry> {
+    let graph = app.palette_link_from.map(|_| app.project.dependency_graph());
+    let pickable = |id: Uuid| match (app.palette_link_from, &graph) {
+        (Some(from), Some(graph)) => !graph.has_link(from, id) && !graph.would_create_cycle(from, id),
+        _ => true,
+    };
+    if let Some(slug) = query.trim().strip_prefix('#') {
+        return slug_entries(app, slug, &pickable);
+    }
+    let mut entries = Vec::new();
+    let commands = app.commands.iter().filter(|c| c.id != crate::commands::OPEN_PALETTE);
+    for command in commands.filter(|_| app.palette_link_from.is_none()) {
+        let Some(score) = fuzzy_score(query, command.label) else {
+            continue;
+        };
+        entries.push(Entry {
+            target: Target::Command(command.id),
+            title: command.label.to_string(),
+            subtitle: None,
+            detail: app.keymap.label(command.id).unwrap_or_default(),
+            enabled: command.is_enabled(app),
+            score,
+        });
+    }
+    for task in app.project.tasks.iter().filter(|t| pickable(t.id)) {
+        let path: Vec<&str> = app
+            .project
+            .ancestor_ids(task.id)
+            .iter()
+            .rev()
+            .filter_map(|id| app.project.task(*id).map(|t| t.name.as_str()))
+            .collect();
+        let path = path.join(" › ");
+        // Matching the parent path too finds tasks by phase, ranked below name matches.
+        let score = fuzzy_score(query, &task.name)
+            .or_else(|| fuzzy_score(query, &format!("{} {}", path, task.name)).map(|s| s - 4));
+        let Some(score) = score else {
+            continue;
+        };
+        let detail = if task.is_milestone {
+            task.start.format("%b %d").to_string()
+        } else {
+            format!("{} → {}", task.start.format("%b %d"), task.end.format("%b %d"))
+        };
+        entries.push(Entry {
+            target: Target::Task(task.id),
+            title: task.name.clone(),
+            subtitle: (!path.is_empty()).then_some(path),
+            detail,
+            enabled: true,
+            score,
+        });
+    }
+    if !query.trim().is_empty() {
+        // Stable, so ties keep commands before tasks and list order.
+        entries.sort_by_key(|e| -e.score);
+    }
+    entries.truncate(MAX_RESULTS);
+    entries
+}
+
+fn entry_row(ui: &mut Ui, index: usize, entry: &Entry, selected: bool) -> egui::Response {
+    let icon = match entry.target {
+        Target::Command(_) => egui_phosphor::regular::TERMINAL_WINDOW,
+        Target::Task(_) => egui_phosphor::regular::LIST_CHECKS,
+    };
+    let title_color = if entry.enabled { theme::text_primary() } else { theme::text_dim() };
+    let fill = if selected { theme::bg_selected() } else { egui::Color32::TRANSPARENT };
+    let frame = egui::Frame::none()
+        .fill(fill)
+        .rounding(egui::Rounding::same(4.0))
+        .inner_margin(egui::Margin::symmetric(8.0, 4.0))
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(icon).color(theme::text_secondary()));
+                ui.vertical(|ui| {
+                    ui.label(RichText::new(&entry.title).size(12.5).color(title_color));
+                    if let Some(path) = &entry.subtitle {
+                        ui.label(RichText::new(path).size(10.0).color(theme::text_dim()));
+                    }
+                });
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.label(RichText::new(&entry.detail).size(10.5).color(theme::text_dim()));
+                });
+            });
+        });
+    ui.interact(frame.response.rect, ui.id().with(("palette-row", index)), Sense::click())
+}
+
+/// Render the palette while `app.show_command_palette` is set. Running an
+/// entry or pressing Escape closes it.
+pub fn show_command_palette(app: &mut GanttApp, ctx: &Context) {
+    let mut state = ctx.data(|d| d.get_temp::<PaletteState>(state_id())).unwrap_or_default();
+    let entries = collect_entries(app, &state.query);
+
+    let (up, down, enter, escape) = ctx.input_mut(|i| {
+        (
+            i.consume_key(Modifiers::NONE, Key::ArrowUp),
+            i.consume_key(Modifiers::NONE, Key::ArrowDown),
+            i.consume_key(Modifiers::NONE, Key::Enter),
+            i.consume_key(Modifiers::NONE, Key::Escape),
+        )
+    });
+    if up {
+        state.selected = state.selected.saturating_sub(1);
+    }
+    if down {
+        state.selected += 1;
+    }
+    state.selected = state.selected.min(entries.len().saturating_sub(1));
+
+    let mut chosen = enter.then(|| entries.get(state.selected)).flatten().map(|e| (e.target, e.enabled));
+    let mut close = escape;
+    let hint = match app.palette_link_from {
+        Some(from) => format!("{} Link '{}' to…", egui_phosphor::regular::LINK, app.project.task_name(from)),
+        None => format!("{} Search tasks and commands, or #slug…", egui_phosphor::regular::MAGNIFYING_GLASS),
+    };
+
+    let window = egui::Window::new("command_palette")
+        .title_bar(false)
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 60.0])
+        .fixed_size([PALETTE_WIDTH, 0.0])
+        .show(ctx, |ui| {
+            let resp = ui.add(
+                egui::TextEdit::singleline(&mut state.query)
+                    .hint_text(hint)
+                    .desired_width(f32::INFINITY)
+                    .margin(egui::vec2(8.0, 6.0)),
+            );
+            if !state.focused {
+                resp.request_focus();
+                state.focused = true;
+            }
+            if resp.changed() {
+                state.selected = 0;
+            }
+            ui.add_space(4.0);
+
+            if entries.is_empty() {
+                ui.label(RichText::new("No matches").size(11.0).color(theme::text_dim()));
+                return;
+            }
+            egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                ui.spacing_mut().item_spacing.y = 1.0;
+                for (i, entry) in entries.iter().enumerate() {
+                    let row = entry_row(ui, i, entry, i == state.selected);
+                    if i == state.selected && (up || down) {
+                        row.scroll_to_me(None);
+                    }
+                    if row.clicked() {
+                        chosen = Some((entry.target, entry.enabled));
+                    }
+                }
+            });
+        });
+
+    // Clicking outside the palette dismisses it.
+    if ctx.input(|i| i.pointer.any_click()) && !ctx.is_pointer_over_area() {
+        close = true;
+    }
+
+    match chosen {
+        Some((target, true)) => {
+            app.show_command_palette = false;
+            let link_from = app.palette_link_from.take();
+            ctx.data_mut(|d| d.remove::<PaletteState>(state_id()));
+            match (target, link_from) {
+                (Target::Task(id), Some(from)) => {
+                    let at = window.map_or(ctx.screen_rect().center(), |w| w.response.rect.left_top());
+                    let kind = app.project.task_defaults.dependency_kind;
+                    crate::ui::gantt_chart::request_link(ctx, from, id, at, kind);
+                }
+                (Target::Task(id), None) => {
+                    app.main_view = crate::ui::view_tabs::MainView::Gantt;
+                    app.reveal_task(ctx, id);
+                }
+                (Target::Command(id), _) => app.run_command(ctx, id),
+            }
+            return;
+        }
+        Some((_, false)) => {
+            app.status_message = "That command isn't available right now".to_string();
+        }
+        None => {}
+    }
+
+    if close {
+        app.show_command_palette = false;
+        app.palette_link_from = None;
+        ctx.data_mut(|d| d.remove::<PaletteState>(state_id()));
+    } else {
+        ctx.data_mut(|d| d.insert_temp(state_id(), state));
+    }
+}
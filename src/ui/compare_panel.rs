@@ -0,0 +1,134 @@
+//! Differences between the open project and another version of its file,
+//! from File → Compare With.
+
+use crate::app::GanttApp;
+use crate::model::compare::{compare, ProjectDiff, TaskDiff};
+use crate::model::Project;
+use crate::ui::theme;
+use egui::{Color32, Context, Key, RichText, Ui, Window};
+use uuid::Uuid;
+
+const ADDED: Color32 = Color32::from_rgb(80, 180, 100);
+const REMOVED: Color32 = Color32::from_rgb(220, 60, 60);
+const MODIFIED: Color32 = Color32::from_rgb(220, 160, 60);
+
+/// Another version of the project and how the open one differs from it.
+pub struct Comparison {
+    /// File name of the other version, for the window title.
+    pub label: String,
+    pub other: Project,
+    /// Changes from the open project to `other`.
+    pub diff: ProjectDiff,
+    /// [`Project::generation`] of the open project when `diff` was computed.
+    generation: u64,
+}
+
+impl Comparison {
+    pub fn new(label: String, other: Project, open: &Project) -> Self {
+        let diff = compare(open, &other);
+        Self { label, other, diff, generation: open.generation() }
+    }
+
+    /// Recompute the diff if the open project changed since.
+    fn refresh(&mut self, open: &Project) {
+        if self.generation != open.generation() {
+            self.diff = compare(open, &self.other);
+            self.generation = open.generation();
+        }
+    }
+}
+
+/// One task row: a marker, the name (a link when the task is in the open
+/// project) and, for modified tasks, a line per changed field.
+fn task_row(ui: &mut Ui, entry: &TaskDiff, marker: &str, color: Color32, in_open: bool, reveal: &mut Option<Uuid>) {
+    ui.horizontal(|ui| {
+        ui.label(RichText::new(marker).strong().color(color));
+        let name = ui.add_enabled(in_open, egui::Link::new(&entry.name));
+        if name.on_disabled_hover_text("Only in the other file").clicked() {
+            *reveal = Some(entry.id);
+        }
+    });
+    for change in &entry.changes {
+        ui.horizontal(|ui| {
+            ui.add_space(18.0);
+            ui.label(RichText::new(change.field.label()).size(11.0).color(theme::text_secondary()));
+            ui.label(RichText::new(format!("{} → {}", change.old, change.new)).size(11.0).color(theme::text_primary()));
+        });
+    }
+}
+
+/// Render the panel while `app.comparison` is set.
+pub fn show_compare_panel(app: &mut GanttApp, ctx: &Context) {
+    let Some(comparison) = app.comparison.as_mut() else {
+        return;
+    };
+    comparison.refresh(&app.project);
+    let diff = &comparison.diff;
+
+    let mut open = true;
+    let mut reveal: Option<Uuid> = None;
+    Window::new(RichText::new(format!("Compare with {}", comparison.label)).strong().size(14.0))
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_size([520.0, 420.0])
+        .show(ctx, |ui| {
+            if diff.is_empty() {
+                ui.label(
+                    RichText::new("No differences in tasks, dates, progress, hierarchy or links.")
+                        .size(11.0)
+                        .color(theme::text_dim()),
+                );
+                return;
+            }
+            ui.label(
+                RichText::new(format!(
+                    "{} added, {} removed, {} modified tasks; {} links added, {} removed",
+                    diff.added.len(),
+                    diff.removed.len(),
+                    diff.modified.len(),
+                    diff.links_added.len(),
+                    diff.links_removed.len(),
+                ))
+                .size(11.0)
+                .color(theme::text_secondary()),
+            );
+            ui.separator();
+            egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                for entry in &diff.modified {
+                    task_row(ui, entry, "~", MODIFIED, true, &mut reveal);
+                }
+                for entry in &diff.added {
+                    task_row(ui, entry, "+", ADDED, false, &mut reveal);
+                }
+                for entry in &diff.removed {
+                    task_row(ui, entry, "−", REMOVED, true, &mut reveal);
+                }
+                if !diff.links_added.is_empty() || !diff.links_removed.is_empty() {
+                    ui.add_space(6.0);
+                    ui.label(RichText::new("Dependencies").strong());
+                    let links = diff.links_added.iter().map(|l| ("+", ADDED, l));
+                    for (marker, color, link) in links.chain(diff.links_removed.iter().map(|l| ("−", REMOVED, l))) {
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(marker).strong().color(color));
+                            let in_open = app.project.task(link.to_task).is_some();
+                            let name = ui.add_enabled(in_open, egui::Link::new(&link.description));
+                            if name.on_disabled_hover_text("Only in the other file").clicked() {
+                                reveal = Some(link.to_task);
+                            }
+                        });
+                    }
+                }
+            });
+        });
+
+    if let Some(id) = reveal {
+        app.main_view = crate::ui::view_tabs::MainView::Gantt;
+        app.selected_task = Some(id);
+        app.selection = vec![id];
+        app.reveal_task(ctx, id);
+    }
+    if !open || ctx.input(|i| i.key_pressed(Key::Escape)) {
+        app.comparison = None;
+    }
+}
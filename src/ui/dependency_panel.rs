@@ -0,0 +1,317 @@
+//! Project-wide list of dependency links: every link with its kind, lag and
+//! whether the tasks' dates meet it, for editing and pruning links without
+//! going task by task.
+
+use std::collections::HashSet;
+
+use crate::app::GanttApp;
+use crate::model::task::DependencyKind;
+use crate::model::validation::DependencyViolation;
+use crate::ui::dialogs::early_label;
+use crate::ui::task_editor::dependency_picker;
+use crate::ui::theme;
+use egui::{Color32, Context, Id, Key, RichText, Ui, Window};
+use uuid::Uuid;
+
+/// Column the list is sorted by.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+enum SortBy {
+    #[default]
+    Predecessor,
+    Successor,
+    Kind,
+    Lag,
+    Status,
+}
+
+#[derive(Clone, Default)]
+struct PanelState {
+    filter: String,
+    kind: Option<DependencyKind>,
+    broken_only: bool,
+    sort: SortBy,
+    descending: bool,
+    /// Ticked links, as `(from, to)`.
+    selected: HashSet<(Uuid, Uuid)>,
+}
+
+/// One link as listed.
+struct Row {
+    from: Uuid,
+    to: Uuid,
+    kind: DependencyKind,
+    lag: i64,
+    predecessor: String,
+    successor: String,
+    violation: Option<DependencyViolation>,
+}
+
+/// A column header that sorts by `column`, or flips the order if it
+/// already does.
+fn sort_header(ui: &mut Ui, state: &mut PanelState, column: SortBy, label: &str) {
+    let arrow = match (state.sort == column, state.descending) {
+        (false, _) => "",
+        (true, false) => egui_phosphor::regular::CARET_UP,
+        (true, true) => egui_phosphor::regular::CARET_DOWN,
+    };
+    let text = RichText::new(format!("{} {}", label, arrow)).size(11.0).strong();
+    if ui.add(egui::Button::new(text).frame(false)).clicked() {
+        if state.sort == column {
+            state.descending = !state.descending;
+        } else {
+            state.sort = column;
+            state.descending = false;
+        }
+    }
+}
+
+/// Render the panel while `app.show_dependencies` is set.
+pub fn show_dependency_panel(app: &mut GanttApp, ctx: &Context) {
+    let state_id = Id::new("dependency-panel");
+    let mut state = ctx.data(|d| d.get_temp::<PanelState>(state_id)).unwrap_or_default();
+    let violations = app.project.dependency_violations();
+    let graph = app.project.dependency_graph();
+
+    let mut rows: Vec<Row> = app
+        .project
+        .dependencies
+        .iter()
+        .map(|dep| Row {
+            from: dep.from_task,
+            to: dep.to_task,
+            kind: dep.kind,
+            lag: dep.lag_days,
+            predecessor: app.project.task_name(dep.from_task),
+            successor: app.project.task_name(dep.to_task),
+            violation: violations
+                .iter()
+                .find(|v| v.dependency.from_task == dep.from_task && v.dependency.to_task == dep.to_task)
+                .cloned(),
+        })
+        .collect();
+    let total = rows.len();
+    let query = state.filter.to_lowercase();
+    rows.retain(|row| {
+        (query.is_empty()
+            || row.predecessor.to_lowercase().contains(&query)
+            || row.successor.to_lowercase().contains(&query))
+            && state.kind.is_none_or(|kind| row.kind == kind)
+            && (!state.broken_only || row.violation.is_some())
+    });
+    rows.sort_by(|a, b| {
+        let order = match state.sort {
+            SortBy::Predecessor => a.predecessor.to_lowercase().cmp(&b.predecessor.to_lowercase()),
+            SortBy::Successor => a.successor.to_lowercase().cmp(&b.successor.to_lowercase()),
+            SortBy::Kind => a.kind.short_label().cmp(b.kind.short_label()),
+            SortBy::Lag => a.lag.cmp(&b.lag),
+            // Broken links first, the furthest off at the top.
+            SortBy::Status => {
+                let early = |r: &Row| r.violation.as_ref().map(|v| v.early_by);
+                early(b).cmp(&early(a))
+            }
+        };
+        if state.descending {
+            order.reverse()
+        } else {
+            order
+        }
+    });
+    // Links deleted elsewhere (or by undo) drop out of the selection.
+    state.selected.retain(|&(from, to)| graph.has_link(from, to));
+
+    let mut open = true;
+    let mut update: Option<(Uuid, Uuid, DependencyKind, i64)> = None;
+    let mut remove: Vec<(Uuid, Uuid)> = Vec::new();
+    let mut add = None;
+    let mut reveal: Option<Uuid> = None;
+    let mut highlight = app.highlighted_link;
+    Window::new(RichText::new("Dependencies").strong().size(14.0))
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_size([640.0, 440.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut state.filter)
+                        .hint_text(format!("{} Filter by task", egui_phosphor::regular::MAGNIFYING_GLASS))
+                        .desired_width(180.0),
+                );
+                egui::ComboBox::from_id_salt("dependency-panel-kind")
+                    .selected_text(state.kind.map_or("All kinds", |k| k.short_label()))
+                    .width(80.0)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut state.kind, None, "All kinds");
+                        for k in DependencyKind::all() {
+                            ui.selectable_value(&mut state.kind, Some(*k), k.short_label())
+                                .on_hover_text(k.description());
+                        }
+                    });
+                ui.checkbox(&mut state.broken_only, "Broken only");
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let selected = state.selected.len();
+                    let delete = egui::Button::new(format!("{} Delete ({})", egui_phosphor::regular::TRASH, selected));
+                    if ui.add_enabled(selected > 0, delete).clicked() {
+                        remove = state.selected.iter().copied().collect();
+                    }
+                });
+            });
+            ui.label(
+                RichText::new(format!("{} of {} links", rows.len(), total))
+                    .size(10.5)
+                    .color(theme::text_dim()),
+            );
+            ui.separator();
+
+            if rows.is_empty() {
+                let note = if total == 0 {
+                    "No dependencies yet. Add one below, from the editor, or Shift+drag between bars."
+                } else {
+                    "No links match the filter."
+                };
+                ui.label(RichText::new(note).size(11.0).color(theme::text_dim()));
+            } else {
+                egui::ScrollArea::vertical().max_height((ui.available_height() - 90.0).max(80.0)).auto_shrink([false, true]).show(ui, |ui| {
+                    egui::Grid::new("dependency-panel-grid").num_columns(7).striped(true).spacing([10.0, 4.0]).show(ui, |ui| {
+                        let mut all = rows.iter().all(|r| state.selected.contains(&(r.from, r.to)));
+                        if ui.checkbox(&mut all, "").on_hover_text("Select all shown").changed() {
+                            for row in &rows {
+                                if all {
+                                    state.selected.insert((row.from, row.to));
+                                } else {
+                                    state.selected.remove(&(row.from, row.to));
+                                }
+                            }
+                        }
+                        sort_header(ui, &mut state, SortBy::Predecessor, "Predecessor");
+                        sort_header(ui, &mut state, SortBy::Successor, "Successor");
+                        sort_header(ui, &mut state, SortBy::Kind, "Kind");
+                        sort_header(ui, &mut state, SortBy::Lag, "Lag");
+                        sort_header(ui, &mut state, SortBy::Status, "Status");
+                        ui.label("");
+                        ui.end_row();
+
+                        for row in &rows {
+                            let link = (row.from, row.to);
+                            let mut ticked = state.selected.contains(&link);
+                            if ui.checkbox(&mut ticked, "").changed() {
+                                if ticked {
+                                    state.selected.insert(link);
+                                } else {
+                                    state.selected.remove(&link);
+                                }
+                            }
+                            if ui.link(&row.predecessor).clicked() {
+                                reveal = Some(row.from);
+                            }
+                            if ui.link(&row.successor).clicked() {
+                                reveal = Some(row.to);
+                            }
+
+                            let mut kind = row.kind;
+                            egui::ComboBox::from_id_salt(("dependency-panel-kind", row.from, row.to))
+                                .selected_text(kind.short_label())
+                                .width(46.0)
+                                .show_ui(ui, |ui| {
+                                    for k in DependencyKind::all() {
+                                        ui.selectable_value(&mut kind, *k, k.short_label()).on_hover_text(k.description());
+                                    }
+                                })
+                                .response
+                                .on_hover_text(kind.description());
+
+                            // Edit a copy while dragging / typing so the whole
+                            // edit lands as a single update (and undo step).
+                            let lag_id = Id::new(("dependency-panel-lag", row.from, row.to));
+                            let mut lag = ui.ctx().data(|d| d.get_temp::<i64>(lag_id)).unwrap_or(row.lag);
+                            let lag_resp = ui.add(egui::DragValue::new(&mut lag).range(-365..=365).suffix("d").speed(0.1));
+                            let lag_editing = lag_resp.dragged() || lag_resp.has_focus();
+                            if lag_editing {
+                                ui.ctx().data_mut(|d| d.insert_temp(lag_id, lag));
+                            } else {
+                                ui.ctx().data_mut(|d| d.remove::<i64>(lag_id));
+                            }
+                            let lag_committed = !lag_editing && lag != row.lag;
+                            if kind != row.kind || lag_committed {
+                                update = Some((row.from, row.to, kind, if lag_committed { lag } else { row.lag }));
+                            }
+
+                            match &row.violation {
+                                Some(violation) => {
+                                    ui.label(
+                                        RichText::new(format!(
+                                            "{} {} early",
+                                            egui_phosphor::regular::LINK_BREAK,
+                                            early_label(violation.early_by)
+                                        ))
+                                        .size(11.0)
+                                        .color(Color32::from_rgb(220, 60, 60)),
+                                    )
+                                    .on_hover_text(violation.describe(&row.predecessor));
+                                }
+                                None => {
+                                    ui.label(RichText::new("OK").size(11.0).color(theme::text_dim()));
+                                }
+                            }
+
+                            let shown = highlight == Some(link);
+                            let button = ui
+                                .selectable_label(shown, egui_phosphor::regular::CROSSHAIR)
+                                .on_hover_text("Highlight on chart");
+                            if button.clicked() {
+                                highlight = if shown { None } else { Some(link) };
+                                if !shown {
+                                    reveal = Some(row.to);
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+            }
+
+            ui.separator();
+            ui.label(RichText::new("Add a link").size(10.0).color(theme::text_dim()).strong());
+            match app.selected_task.filter(|id| app.project.task(*id).is_some()) {
+                Some(id) => {
+                    ui.label(
+                        RichText::new(format!("For '{}'", app.project.task_name(id)))
+                            .size(11.0)
+                            .color(theme::text_secondary()),
+                    );
+                    add = dependency_picker(ui, "dependency-panel", id, &app.project, &graph);
+                }
+                None => {
+                    ui.label(
+                        RichText::new("Select a task to link it to another.")
+                            .size(11.0)
+                            .color(theme::text_dim()),
+                    );
+                }
+            }
+        });
+
+    if let Some((from, to, kind, lag)) = update {
+        app.update_link(from, to, kind, lag);
+    }
+    if !remove.is_empty() {
+        app.remove_links(&remove);
+        if highlight.is_some_and(|link| remove.contains(&link)) {
+            highlight = None;
+        }
+        state.selected.clear();
+    }
+    if add.is_some() {
+        app.pending_add_dependency = add;
+    }
+    if let Some(id) = reveal {
+        app.main_view = crate::ui::view_tabs::MainView::Gantt;
+        app.reveal_task(ctx, id);
+    }
+    app.highlighted_link = highlight;
+    if !open || ctx.input(|i| i.key_pressed(Key::Escape)) {
+        app.show_dependencies = false;
+        app.highlighted_link = None;
+    }
+    ctx.data_mut(|d| d.insert_temp(state_id, state));
+}
@@ -1,7 +1,17 @@
 use crate::app::GanttApp;
+use crate::export::{ExportExtra, ExportOptions, ExportScope};
+use crate::model::absence::Absence;
+use crate::model::clock::{Tz, TZ_VARIANTS};
+use crate::model::highlight::HighlightRules;
+use crate::model::schedule::{chain_children, resolve_violations};
+use crate::model::task::{assignee_names, ProgressPolicy, ShiftAmount};
+use crate::model::Task;
+use crate::i18n::{format_datetime, priority_label, tr, trf, trn};
 use crate::ui::theme;
-use chrono::{NaiveTime, Timelike};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Timelike};
 use egui::{Color32, Context, RichText, Ui, Window};
+use std::collections::HashSet;
+use uuid::Uuid;
 
 /// Render a time picker with hour and minute dropdowns.
 /// Returns true if the time was changed.
@@ -50,7 +60,7 @@ fn time_picker(ui: &mut Ui, time: &mut NaiveTime, id_salt: &str) -> bool {
 pub fn show_add_task_dialog(app: &mut GanttApp, ctx: &Context) {
     let mut should_close = false;
     let layout = theme::layout();
-    let resp = Window::new(RichText::new("Add Task").strong().size(14.0))
+    let resp = Window::new(RichText::new(tr("dialog.add_task.title")).strong().size(14.0))
         .resizable(false)
         .collapsible(false)
         .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
@@ -68,16 +78,16 @@ pub fn show_add_task_dialog(app: &mut GanttApp, ctx: &Context) {
                 .striped(false)
                 .spacing([12.0, 8.0])
                 .show(ui, |ui| {
-                    ui.label(RichText::new("Name").color(theme::text_secondary()));
+                    ui.label(RichText::new(tr("editor.name")).color(theme::text_secondary()));
                     ui.add_sized(
                         [220.0, 24.0],
                         egui::TextEdit::singleline(&mut app.new_task_name)
-                            .hint_text("Task name...")
+                            .hint_text(tr("dialog.add_task.name_hint"))
                             .text_color(theme::text_primary()),
                     );
                     ui.end_row();
 
-                    ui.label(RichText::new("Start").color(theme::text_secondary()));
+                    ui.label(RichText::new(tr("editor.start")).color(theme::text_secondary()));
                     ui.vertical(|ui| {
                         let mut start_date = app.new_task_start_date.date();
                         if ui.add(
@@ -93,7 +103,7 @@ pub fn show_add_task_dialog(app: &mut GanttApp, ctx: &Context) {
                     });
                     ui.end_row();
 
-                    ui.label(RichText::new("End").color(theme::text_secondary()));
+                    ui.label(RichText::new(tr("editor.end")).color(theme::text_secondary()));
                     ui.vertical(|ui| {
                         let mut end_date = app.new_task_end_date.date();
                         if ui.add(
@@ -110,7 +120,7 @@ pub fn show_add_task_dialog(app: &mut GanttApp, ctx: &Context) {
                     ui.end_row();
 
                     ui.label("");
-                    ui.checkbox(&mut app.new_task_is_milestone, "Milestone");
+                    ui.checkbox(&mut app.new_task_is_milestone, tr("editor.milestone"));
                     ui.end_row();
                 });
 
@@ -120,7 +130,7 @@ pub fn show_add_task_dialog(app: &mut GanttApp, ctx: &Context) {
 
             ui.horizontal(|ui| {
                 let create_btn = egui::Button::new(
-                    RichText::new("Create").color(Color32::WHITE),
+                    RichText::new(tr("dialog.create")).color(theme::text_on(theme::accent())),
                 )
                 .fill(theme::accent())
                 .rounding(egui::Rounding::same(4.0));
@@ -128,7 +138,7 @@ pub fn show_add_task_dialog(app: &mut GanttApp, ctx: &Context) {
                     app.create_task_from_dialog();
                     should_close = true;
                 }
-                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                if ui.add_sized([80.0, 28.0], egui::Button::new(tr("editor.cancel"))).clicked() {
                     should_close = true;
                 }
             });
@@ -155,7 +165,7 @@ pub fn show_add_task_dialog(app: &mut GanttApp, ctx: &Context) {
 pub fn show_about_dialog(app: &mut GanttApp, ctx: &Context) {
     let mut should_close = false;
     let layout = theme::layout();
-    Window::new("About")
+    Window::new(tr("menu.help.about"))
         .resizable(false)
         .collapsible(false)
         .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
@@ -165,12 +175,12 @@ pub fn show_about_dialog(app: &mut GanttApp, ctx: &Context) {
                 ui.add_space(12.0);
                 ui.heading(RichText::new("Rust Gantt App").strong());
                 ui.add_space(2.0);
-                ui.label(RichText::new("Version 0.2.0").color(theme::text_secondary()));
+                ui.label(RichText::new(trf("dialog.about.version", &[("version", &env!("CARGO_PKG_VERSION"))])).color(theme::text_secondary()));
                 ui.add_space(10.0);
-                ui.label("A Gantt chart application");
-                ui.label("built with Rust and egui.");
+                ui.label(tr("dialog.about.line1"));
+                ui.label(tr("dialog.about.line2"));
                 ui.add_space(14.0);
-                if ui.add_sized([100.0, 28.0], egui::Button::new("Close")).clicked() {
+                if ui.add_sized([100.0, 28.0], egui::Button::new(tr("dialog.close"))).clicked() {
                     should_close = true;
                 }
             });
@@ -179,3 +189,1450 @@ pub fn show_about_dialog(app: &mut GanttApp, ctx: &Context) {
         app.show_about = false;
     }
 }
+
+/// Render the confirmation shown before turning a task into a milestone
+/// would discard its duration, children or finish-based links.
+pub fn show_milestone_dialog(app: &mut GanttApp, ctx: &Context) {
+    let Some(task_id) = app.pending_milestone else {
+        return;
+    };
+    let Some(name) = app.project.tasks.iter().find(|t| t.id == task_id).map(|t| t.name.clone()) else {
+        app.pending_milestone = None;
+        return;
+    };
+    let impact = app.project.milestone_impact(task_id);
+
+    let mut confirmed = false;
+    let mut cancelled = false;
+    let layout = theme::layout();
+    Window::new(RichText::new(tr("dialog.milestone.title")).strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label(trf("dialog.milestone.intro", &[("name", &name)]));
+            ui.add_space(4.0);
+            let bullet = |ui: &mut Ui, text: String| {
+                ui.label(RichText::new(format!("•  {}", text)).color(theme::text_secondary()));
+            };
+            if impact.duration_days > 1 {
+                bullet(ui, trf("dialog.milestone.collapse", &[("days", &impact.duration_days)]));
+            }
+            if impact.children > 0 {
+                bullet(ui, trn("dialog.milestone.children", impact.children, &[]));
+            }
+            if impact.finish_links > 0 {
+                bullet(ui, trn("dialog.milestone.links", impact.finish_links, &[]));
+            }
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new(tr("dialog.undo_hint"))
+                    .size(11.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let convert = egui::Button::new(
+                    RichText::new(tr("dialog.milestone.convert")).color(theme::text_on(theme::accent())),
+                )
+                .fill(theme::accent())
+                .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], convert).clicked() {
+                    confirmed = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new(tr("editor.cancel"))).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if confirmed {
+        app.pending_milestone = None;
+        app.set_milestone(task_id, true);
+    } else if cancelled || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.pending_milestone = None;
+    }
+}
+
+#[derive(Clone, Default)]
+struct ShiftState {
+    days: i64,
+    working_days: bool,
+    with_successors: bool,
+}
+
+impl ShiftState {
+    fn amount(&self) -> ShiftAmount {
+        if self.working_days {
+            ShiftAmount::WorkingDays(self.days)
+        } else {
+            ShiftAmount::Days(self.days)
+        }
+    }
+}
+
+/// First start and last end of the tasks in `ids`.
+fn span_of(tasks: &[Task], ids: &[Uuid]) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let mut moved = tasks.iter().filter(|t| ids.contains(&t.id));
+    let first = moved.next()?;
+    Some(moved.fold((first.start, first.end), |(s, e), t| (s.min(t.start), e.max(t.end))))
+}
+
+/// Render the "Shift Dates" dialog for the current selection while
+/// `app.show_shift_dates` is set.
+pub fn show_shift_dialog(app: &mut GanttApp, ctx: &Context) {
+    let state_id = egui::Id::new("shift-dates-dialog");
+    let mut state: ShiftState = ctx.data(|d| d.get_temp(state_id)).unwrap_or_default();
+    let targets = app.project.shift_targets(&app.selection, state.with_successors);
+    if targets.is_empty() {
+        app.show_shift_dates = false;
+        app.status_message = tr("dialog.shift.all_locked").to_string();
+        return;
+    }
+
+    let mut apply = false;
+    let mut cancelled = false;
+    let layout = theme::layout();
+    Window::new(RichText::new(tr("dialog.shift.title")).strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label(tr("dialog.shift.move_by"));
+                ui.add(
+                    egui::DragValue::new(&mut state.days)
+                        .range(-3650..=3650)
+                        .speed(0.1)
+                        .custom_formatter(|v, _| format!("{:+}", v as i64)),
+                );
+                ui.radio_value(&mut state.working_days, false, tr("dialog.shift.days"));
+                ui.radio_value(&mut state.working_days, true, tr("dialog.shift.working_days"));
+            });
+            ui.checkbox(&mut state.with_successors, tr("dialog.shift.successors"));
+            ui.add_space(4.0);
+
+            let format = |(start, end): (NaiveDateTime, NaiveDateTime)| {
+                format!("{} → {}", format_datetime(start, "date.long"), format_datetime(end, "date.long"))
+            };
+            if let Some(before) = span_of(&app.project.tasks, &targets) {
+                let amount = state.amount();
+                let after = (amount.apply(before.0), amount.apply(before.1).max(amount.apply(before.0)));
+                ui.label(
+                    RichText::new(trn("dialog.shift.span", targets.len(), &[("span", &format(before))]))
+                    .color(theme::text_secondary()),
+                );
+                ui.label(RichText::new(trf("dialog.shift.becomes", &[("span", &format(after))])).color(theme::text_primary()));
+            }
+            ui.label(
+                RichText::new(tr("dialog.shift.subtasks"))
+                    .size(11.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let shift = egui::Button::new(RichText::new(tr("dialog.shift.shift")).color(theme::text_on(theme::accent())))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_enabled(state.days != 0, shift.min_size(egui::vec2(80.0, 28.0))).clicked() {
+                    apply = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new(tr("editor.cancel"))).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    let enter = state.days != 0 && ctx.input(|i| i.key_pressed(egui::Key::Enter));
+    if apply || enter {
+        app.shift_tasks(&targets, state.amount());
+    }
+    if apply || enter || cancelled || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_shift_dates = false;
+        ctx.data_mut(|d| d.remove::<ShiftState>(state_id));
+    } else {
+        ctx.data_mut(|d| d.insert_temp(state_id, state));
+    }
+}
+
+/// `2d` or `5h`: how early a task is for a link it breaks.
+pub(crate) fn early_label(early: chrono::Duration) -> String {
+    let hours = early.num_minutes() as f64 / 60.0;
+    if hours < 24.0 {
+        format!("{}h", hours.ceil())
+    } else {
+        format!("{}d", (hours / 24.0 * 10.0).round() / 10.0)
+    }
+}
+
+/// Render the list of links the tasks' dates break while
+/// `app.show_violations` is set. Clicking a task selects it and scrolls the
+/// chart to it; Resolve hands over to the reschedule preview.
+pub fn show_violations_dialog(app: &mut GanttApp, ctx: &Context) {
+    let violations = app.project.dependency_violations();
+    let mut reveal: Option<Uuid> = None;
+    let mut resolve = false;
+    let mut close = false;
+    let layout = theme::layout();
+    Window::new(RichText::new(tr("dialog.violations.title")).strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width + 120.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            if violations.is_empty() {
+                ui.label(tr("dialog.violations.none"));
+            } else {
+                ui.label(trn("dialog.violations.intro", violations.len(), &[]));
+                ui.add_space(4.0);
+                egui::ScrollArea::vertical().max_height(280.0).show(ui, |ui| {
+                    egui::Grid::new("violations-grid").num_columns(3).spacing([10.0, 4.0]).striped(true).show(ui, |ui| {
+                        for violation in violations.iter() {
+                            let (from, to) = (violation.dependency.from_task, violation.dependency.to_task);
+                            if ui.link(app.project.task_name(to)).clicked() {
+                                reveal = Some(to);
+                            }
+                            ui.label(
+                                RichText::new(violation.describe(&app.project.task_name(from)))
+                                    .color(theme::text_secondary()),
+                            )
+                            .on_hover_text(violation.dependency.kind.description());
+                            ui.label(
+                                RichText::new(trf("dialog.violations.early", &[("time", &early_label(violation.early_by))]))
+                                    .color(Color32::from_rgb(220, 60, 60)),
+                            );
+                            ui.end_row();
+                        }
+                    });
+                });
+            }
+
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let button =
+                    egui::Button::new(RichText::new(tr("dialog.violations.resolve")).color(theme::text_on(theme::accent())))
+                        .fill(theme::accent())
+                        .rounding(egui::Rounding::same(4.0))
+                        .min_size(egui::vec2(80.0, 28.0));
+                if ui.add_enabled(!violations.is_empty(), button).clicked() {
+                    resolve = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new(tr("dialog.close"))).clicked() {
+                    close = true;
+                }
+            });
+        });
+
+    if let Some(id) = reveal {
+        app.reveal_task(ctx, id);
+    }
+    if resolve {
+        app.show_reschedule = true;
+    }
+    if resolve || close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_violations = false;
+    }
+}
+
+/// Render the "Resolve Dependency Violations" preview while
+/// `app.show_reschedule` is set. Unticking a task keeps it in place and
+/// recomputes the moves around it.
+pub fn show_reschedule_dialog(app: &mut GanttApp, ctx: &Context) {
+    let state_id = egui::Id::new("reschedule-dialog");
+    let mut skip: HashSet<Uuid> = ctx.data(|d| d.get_temp(state_id)).unwrap_or_default();
+    let plan = resolve_violations(&app.project, &skip);
+
+    let mut apply = false;
+    let mut close = false;
+    let mut toggled: Option<Uuid> = None;
+    let mut reveal: Option<Uuid> = None;
+    let layout = theme::layout();
+    Window::new(RichText::new(tr("dialog.reschedule.title")).strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width + 120.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            if plan.moves.is_empty() && skip.is_empty() {
+                ui.label(tr("dialog.reschedule.nothing"));
+            } else {
+                ui.label(trn("dialog.reschedule.intro", plan.moves.len(), &[]));
+                ui.add_space(4.0);
+                egui::ScrollArea::vertical().max_height(280.0).show(ui, |ui| {
+                    egui::Grid::new("reschedule-grid").num_columns(3).spacing([10.0, 4.0]).striped(true).show(
+                        ui,
+                        |ui| {
+                            for m in &plan.moves {
+                                let mut on = true;
+                                if ui.checkbox(&mut on, "").changed() {
+                                    toggled = Some(m.task_id);
+                                }
+                                if ui.link(app.project.task_name(m.task_id)).clicked() {
+                                    reveal = Some(m.task_id);
+                                }
+                                ui.label(
+                                    RichText::new(format!(
+                                        "{} → {}  ({:+}d)",
+                                        format_datetime(m.old_start, "date.month_day"),
+                                        format_datetime(m.new_start, "date.month_day"),
+                                        m.delta().num_days()
+                                    ))
+                                    .color(theme::text_secondary()),
+                                );
+                                ui.end_row();
+                            }
+                            for id in app.project.tasks.iter().map(|t| t.id).filter(|id| skip.contains(id)) {
+                                let mut on = false;
+                                if ui.checkbox(&mut on, "").changed() {
+                                    toggled = Some(id);
+                                }
+                                if ui.link(app.project.task_name(id)).clicked() {
+                                    reveal = Some(id);
+                                }
+                                ui.label(RichText::new(tr("dialog.reschedule.kept")).italics().color(theme::text_dim()));
+                                ui.end_row();
+                            }
+                        },
+                    );
+                });
+            }
+
+            if !plan.cycles.is_empty() || plan.unresolved > 0 {
+                ui.add_space(6.0);
+                let warn = Color32::from_rgb(220, 140, 40);
+                for cycle in &plan.cycles {
+                    let names: Vec<String> = cycle.iter().map(|id| app.project.task_name(*id)).collect();
+                    ui.label(
+                        RichText::new(trf(
+                            "dialog.reschedule.cycle",
+                            &[("icon", &egui_phosphor::regular::WARNING), ("names", &names.join(" → "))],
+                        ))
+                        .color(warn),
+                    );
+                }
+                if plan.unresolved > 0 {
+                    ui.label(
+                        RichText::new(trn(
+                            "dialog.reschedule.unresolved",
+                            plan.unresolved,
+                            &[("icon", &egui_phosphor::regular::WARNING)],
+                        ))
+                        .color(warn),
+                    );
+                }
+            }
+
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let button = egui::Button::new(RichText::new(tr("editor.apply")).color(theme::text_on(theme::accent())))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0))
+                    .min_size(egui::vec2(80.0, 28.0));
+                if ui.add_enabled(!plan.moves.is_empty(), button).clicked() {
+                    apply = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new(tr("editor.cancel"))).clicked() {
+                    close = true;
+                }
+            });
+        });
+
+    if let Some(id) = toggled {
+        if !skip.remove(&id) {
+            skip.insert(id);
+        }
+    }
+    if let Some(id) = reveal {
+        app.reveal_task(ctx, id);
+    }
+    if apply {
+        app.apply_reschedule(&plan.moves);
+    }
+    if apply || close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_reschedule = false;
+        ctx.data_mut(|d| d.remove::<HashSet<Uuid>>(state_id));
+    } else {
+        ctx.data_mut(|d| d.insert_temp(state_id, skip));
+    }
+}
+
+#[derive(Clone, Default)]
+struct ArrangeState {
+    gap_days: i64,
+    link: bool,
+}
+
+/// Render the "Arrange Children" preview for `app.pending_arrange`: the
+/// children's dates before and after laying them out back to back.
+pub fn show_arrange_children_dialog(app: &mut GanttApp, ctx: &Context) {
+    let Some(parent) = app.pending_arrange.filter(|id| app.project.task(*id).is_some()) else {
+        app.pending_arrange = None;
+        return;
+    };
+    let state_id = egui::Id::new("arrange-children-dialog");
+    let mut state: ArrangeState = ctx.data(|d| d.get_temp(state_id)).unwrap_or_default();
+    let chain = chain_children(&app.project, parent, state.gap_days);
+    let children: Vec<Uuid> = app.project.tasks.iter().filter(|t| t.parent_id == Some(parent)).map(|t| t.id).collect();
+
+    let mut apply = false;
+    let mut close = false;
+    let mut reveal: Option<Uuid> = None;
+    let layout = theme::layout();
+    Window::new(RichText::new(tr("dialog.arrange.title")).strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width + 120.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label(trf("dialog.arrange.intro", &[("name", &app.project.task_name(parent))]));
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label(tr("dialog.arrange.gap"));
+                ui.add(egui::DragValue::new(&mut state.gap_days).range(0..=365).speed(0.1));
+                ui.label(tr("dialog.shift.working_days"));
+            });
+            ui.checkbox(&mut state.link, trn("dialog.arrange.link", chain.links.len(), &[]));
+            ui.add_space(4.0);
+
+            egui::ScrollArea::vertical().max_height(280.0).show(ui, |ui| {
+                egui::Grid::new("arrange-children-grid").num_columns(2).spacing([10.0, 4.0]).striped(true).show(ui, |ui| {
+                    for id in &children {
+                        if ui.link(app.project.task_name(*id)).clicked() {
+                            reveal = Some(*id);
+                        }
+                        if let Some(m) = chain.moves.iter().find(|m| m.task_id == *id) {
+                            ui.label(
+                                RichText::new(format!(
+                                    "{} → {}  ({:+}d)",
+                                    format_datetime(m.old_start, "date.month_day"),
+                                    format_datetime(m.new_start, "date.month_day"),
+                                    m.delta().num_days()
+                                ))
+                                .color(theme::text_primary()),
+                            );
+                        } else if chain.locked.contains(id) {
+                            ui.label(RichText::new(tr("dialog.arrange.locked")).italics().color(theme::text_dim()));
+                        } else {
+                            ui.label(RichText::new(tr("dialog.arrange.unchanged")).color(theme::text_dim()));
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+            ui.add_space(4.0);
+            ui.label(RichText::new(tr("dialog.undo_hint")).size(11.0).color(theme::text_dim()));
+
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let button = egui::Button::new(RichText::new(tr("editor.apply")).color(theme::text_on(theme::accent())))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0))
+                    .min_size(egui::vec2(80.0, 28.0));
+                let changes = !chain.moves.is_empty() || (state.link && !chain.links.is_empty());
+                if ui.add_enabled(changes, button).clicked() {
+                    apply = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new(tr("editor.cancel"))).clicked() {
+                    close = true;
+                }
+            });
+        });
+
+    if let Some(id) = reveal {
+        app.reveal_task(ctx, id);
+    }
+    if apply {
+        app.arrange_children(parent, &chain, state.link);
+    }
+    if apply || close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.pending_arrange = None;
+        ctx.data_mut(|d| d.remove::<ArrangeState>(state_id));
+    } else {
+        ctx.data_mut(|d| d.insert_temp(state_id, state));
+    }
+}
+
+/// Render the "Import from GitHub" dialog while `app.show_github_import` is
+/// set. It stays open with a spinner while the issues are fetched.
+pub fn show_github_import_dialog(app: &mut GanttApp, ctx: &Context) {
+    let fetching = app.github_fetch.as_ref().map(|f| f.repo.clone());
+    let repo = crate::io::github_import::parse_repo(&app.settings.github_repo);
+    let mut import = false;
+    let mut cancelled = false;
+    let layout = theme::layout();
+    Window::new(RichText::new(tr("dialog.github.title")).strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.add_enabled_ui(fetching.is_none(), |ui| {
+                ui.label(tr("dialog.github.repo"));
+                let field = ui.add(
+                    egui::TextEdit::singleline(&mut app.settings.github_repo)
+                        .hint_text("owner/name")
+                        .desired_width(f32::INFINITY),
+                );
+                if field.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    import = repo.is_some();
+                }
+                ui.add_space(4.0);
+                ui.label(tr("dialog.github.token"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut app.github_token)
+                        .password(true)
+                        .hint_text(tr("dialog.github.optional"))
+                        .desired_width(f32::INFINITY),
+                );
+            });
+            ui.label(
+                RichText::new(tr("dialog.github.hint"))
+                    .size(11.0)
+                    .color(theme::text_dim()),
+            );
+            if let Some(repo) = &fetching {
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(RichText::new(trf("dialog.github.fetching", &[("repo", repo)])).color(theme::text_secondary()));
+                });
+            }
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let button = egui::Button::new(RichText::new(tr("dialog.import")).color(theme::text_on(theme::accent())))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                let enabled = repo.is_some() && fetching.is_none();
+                if ui.add_enabled(enabled, button.min_size(egui::vec2(80.0, 28.0))).clicked() {
+                    import = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new(tr("editor.cancel"))).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if import {
+        app.start_github_import(ctx);
+    }
+    if cancelled || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        // A fetch still running is dropped; its result is never merged.
+        app.github_fetch = None;
+        app.show_github_import = false;
+    }
+}
+
+/// Rows of a Jira import shown in its preview.
+const JIRA_PREVIEW_ROWS: usize = 20;
+
+/// Render the preview of `app.pending_jira_import`: how the first rows map
+/// onto tasks, and what was left out.
+pub fn show_jira_import_dialog(app: &mut GanttApp, ctx: &Context) {
+    let Some(import) = app.pending_jira_import.as_ref() else {
+        return;
+    };
+    let mut confirm = false;
+    let mut cancelled = false;
+    Window::new(RichText::new(tr("dialog.jira.title")).strong().size(14.0))
+        .resizable(true)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .default_size([640.0, 420.0])
+        .show(ctx, |ui| {
+            let parents = import.tasks.iter().filter(|t| t.has_children(&import.tasks)).count();
+            let skipped = if import.skipped > 0 {
+                trf("dialog.jira.skipped", &[("n", &import.skipped)])
+            } else {
+                String::new()
+            };
+            ui.label(trf(
+                "dialog.jira.summary",
+                &[("issues", &import.tasks.len()), ("parents", &parents), ("skipped", &skipped)],
+            ));
+            if !import.ignored_columns.is_empty() {
+                ui.label(
+                    RichText::new(trf("dialog.jira.ignored", &[("columns", &import.ignored_columns.join(", "))]))
+                        .size(11.0)
+                        .color(theme::text_dim()),
+                )
+                .on_hover_text(tr("dialog.jira.ignored.hint"));
+            }
+            ui.add_space(4.0);
+            egui::ScrollArea::both().max_height(300.0).auto_shrink([false, true]).show(ui, |ui| {
+                egui::Grid::new("jira-preview-grid").num_columns(8).striped(true).spacing([10.0, 4.0]).show(ui, |ui| {
+                    let headers = [
+                        "dialog.jira.key",
+                        "editor.name",
+                        "dialog.jira.parent",
+                        "editor.start",
+                        "editor.end",
+                        "editor.progress",
+                        "editor.priority",
+                        "editor.assignee",
+                    ];
+                    for header in headers {
+                        ui.label(RichText::new(tr(header)).strong().size(11.0));
+                    }
+                    ui.end_row();
+                    for (task, key) in import.tasks.iter().zip(&import.keys).take(JIRA_PREVIEW_ROWS) {
+                        ui.label(RichText::new(key).size(11.0).color(theme::text_secondary()));
+                        ui.label(RichText::new(&task.name).size(11.0));
+                        ui.label(RichText::new(import.parent_name(task).unwrap_or("")).size(11.0));
+                        ui.label(RichText::new(format_datetime(task.start, "date.long")).size(11.0));
+                        ui.label(RichText::new(format_datetime(task.end, "date.long")).size(11.0));
+                        ui.label(RichText::new(format!("{:.0}%", task.progress * 100.0)).size(11.0));
+                        ui.label(RichText::new(priority_label(task.priority)).size(11.0));
+                        ui.label(RichText::new(task.assignee.as_deref().unwrap_or("")).size(11.0));
+                        ui.end_row();
+                    }
+                });
+            });
+            if import.tasks.len() > JIRA_PREVIEW_ROWS {
+                ui.label(
+                    RichText::new(trf("dialog.jira.more", &[("n", &(import.tasks.len() - JIRA_PREVIEW_ROWS))]))
+                        .size(11.0)
+                        .color(theme::text_dim()),
+                );
+            }
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let button = egui::Button::new(RichText::new(tr("dialog.import")).color(theme::text_on(theme::accent())))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add(button.min_size(egui::vec2(80.0, 28.0))).clicked() {
+                    confirm = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new(tr("editor.cancel"))).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if confirm {
+        if let Some(import) = app.pending_jira_import.take() {
+            app.apply_jira_import(import);
+        }
+    } else if cancelled || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.pending_jira_import = None;
+    }
+}
+
+/// Render the preview of `app.pending_markdown_import`: the tasks the
+/// outline becomes, laid out from the chosen start day, and the lines that
+/// couldn't be fully read.
+pub fn show_markdown_import_dialog(app: &mut GanttApp, ctx: &Context) {
+    let defaults = app.project.task_defaults;
+    let Some(import) = app.pending_markdown_import.as_mut() else {
+        return;
+    };
+    let mut confirm = false;
+    let mut cancelled = false;
+    Window::new(RichText::new(tr("dialog.markdown.title")).strong().size(14.0))
+        .resizable(true)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .default_size([560.0, 420.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr("dialog.markdown.anchor"));
+                ui.add(egui_extras::DatePickerButton::new(&mut import.anchor).id_salt("markdown-anchor"));
+            });
+            let tasks = import.tasks(&defaults);
+            let phases = tasks.iter().filter(|t| t.has_children(&tasks)).count();
+            ui.label(trf("dialog.markdown.summary", &[("tasks", &tasks.len()), ("phases", &phases)]));
+            ui.add_space(4.0);
+            egui::ScrollArea::vertical().max_height(260.0).auto_shrink([false, true]).show(ui, |ui| {
+                egui::Grid::new("markdown-preview-grid").num_columns(4).striped(true).spacing([10.0, 4.0]).show(ui, |ui| {
+                    for (i, task) in tasks.iter().enumerate() {
+                        let indent = "    ".repeat(import.depth(i));
+                        let name = if task.is_milestone {
+                            format!("{}{} {}", indent, egui_phosphor::regular::DIAMOND, task.name)
+                        } else {
+                            format!("{}{}", indent, task.name)
+                        };
+                        let parent = task.has_children(&tasks);
+                        let name = RichText::new(name).size(11.0);
+                        ui.label(if parent { name.strong() } else { name });
+                        let dates = if parent {
+                            String::new()
+                        } else if task.is_milestone {
+                            format_datetime(task.start, "date.long")
+                        } else {
+                            format!("{} → {}", format_datetime(task.start, "date.long"), format_datetime(task.end, "date.long"))
+                        };
+                        ui.label(RichText::new(dates).size(11.0).color(theme::text_secondary()));
+                        ui.label(RichText::new(format!("{:.0}%", task.progress * 100.0)).size(11.0));
+                        ui.label(RichText::new(priority_label(task.priority)).size(11.0));
+                        ui.end_row();
+                    }
+                });
+            });
+            if !import.problems.is_empty() {
+                ui.add_space(6.0);
+                ui.label(RichText::new(trn("dialog.markdown.problems", import.problems.len(), &[])).strong());
+                egui::ScrollArea::vertical().id_salt("markdown-problems").max_height(100.0).show(ui, |ui| {
+                    for problem in &import.problems {
+                        ui.label(
+                            RichText::new(trf("dialog.markdown.line", &[("line", &problem.line), ("message", &problem.message)]))
+                                .size(11.0)
+                                .color(Color32::from_rgb(220, 140, 40)),
+                        );
+                    }
+                });
+            }
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let button = egui::Button::new(RichText::new(tr("dialog.import")).color(theme::text_on(theme::accent())))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add(button.min_size(egui::vec2(80.0, 28.0))).clicked() {
+                    confirm = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new(tr("editor.cancel"))).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if confirm {
+        if let Some(import) = app.pending_markdown_import.take() {
+            app.apply_markdown_import(import);
+        }
+    } else if cancelled || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.pending_markdown_import = None;
+    }
+}
+
+/// Ask before running a post-save command that came with an opened project
+/// file, so a downloaded project can't run commands unnoticed.
+pub fn show_hook_confirm_dialog(app: &mut GanttApp, ctx: &Context) {
+    let Some((command, path)) = app.pending_hook.clone() else {
+        return;
+    };
+    let mut run = false;
+    let mut skipped = false;
+    let layout = theme::layout();
+    Window::new(RichText::new(tr("dialog.hook.title")).strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label(tr("dialog.hook.intro"));
+            ui.add_space(4.0);
+            ui.label(RichText::new(&command).monospace().color(theme::text_primary()));
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new(trf(
+                    "dialog.hook.file",
+                    &[("file", &crate::io::save_hook::FILE_PLACEHOLDER), ("path", &path.display())],
+                ))
+                .size(11.0)
+                .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let button = egui::Button::new(RichText::new(tr("dialog.hook.run")).color(theme::text_on(theme::accent())))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], button).on_hover_text(tr("dialog.hook.run.hint")).clicked() {
+                    run = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new(tr("dialog.hook.skip"))).clicked() {
+                    skipped = true;
+                }
+            });
+        });
+
+    if run {
+        app.confirm_pending_hook();
+    } else if skipped || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        // Asked again on the next save.
+        app.pending_hook = None;
+        app.status_message = tr("dialog.hook.skipped").to_string();
+    }
+}
+
+/// Report a failed save with the system's error text and, when a complete
+/// copy was written but couldn't replace the file, where that copy is.
+pub fn show_save_error_dialog(app: &mut GanttApp, ctx: &Context) {
+    let Some((path, error)) = app.save_error.clone() else {
+        return;
+    };
+    let mut retry = false;
+    let mut closed = false;
+    let layout = theme::layout();
+    Window::new(RichText::new(tr("dialog.save_error.title")).strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label(trf("dialog.save_error.intro", &[("path", &path.display())]));
+            ui.add_space(4.0);
+            ui.label(RichText::new(&error.message).monospace().color(theme::text_primary()));
+            ui.add_space(4.0);
+            match &error.temp_file {
+                Some(tmp) => {
+                    ui.label(tr("dialog.save_error.kept"));
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(tmp.display().to_string()).monospace().size(11.0));
+                        if ui.small_button(tr("dialog.save_error.copy_path")).clicked() {
+                            ctx.copy_text(tmp.display().to_string());
+                        }
+                    });
+                }
+                None => {
+                    ui.label(
+                        RichText::new(tr("dialog.save_error.unchanged"))
+                            .size(11.0)
+                            .color(theme::text_dim()),
+                    );
+                }
+            }
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let button = egui::Button::new(RichText::new(tr("dialog.save_error.retry")).color(theme::text_on(theme::accent())))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], button).clicked() {
+                    retry = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new(tr("dialog.save_error.close"))).clicked() {
+                    closed = true;
+                }
+            });
+        });
+
+    if retry || closed || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.save_error = None;
+    }
+    if retry {
+        app.save_project();
+    }
+}
+
+#[derive(Clone)]
+struct TimeZoneState {
+    zone: Option<Tz>,
+    query: String,
+}
+
+/// Render the "Time Zone" dialog while `app.show_time_zone` is set: floating
+/// times, or a zone picked from a searchable list.
+pub fn show_time_zone_dialog(app: &mut GanttApp, ctx: &Context) {
+    let state_id = egui::Id::new("time-zone-dialog");
+    let mut state = ctx
+        .data(|d| d.get_temp::<TimeZoneState>(state_id))
+        .unwrap_or_else(|| TimeZoneState { zone: app.project.time_zone, query: String::new() });
+
+    let mut apply = false;
+    let mut cancelled = false;
+    let layout = theme::layout();
+    Window::new(RichText::new(tr("dialog.time_zone.title")).strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.radio_value(&mut state.zone, None, tr("dialog.time_zone.floating"));
+            ui.label(
+                RichText::new(tr("dialog.time_zone.floating.hint"))
+                    .size(11.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(4.0);
+            ui.add(
+                egui::TextEdit::singleline(&mut state.query)
+                    .hint_text(tr("dialog.time_zone.search"))
+                    .desired_width(f32::INFINITY),
+            );
+            let query = state.query.to_lowercase();
+            egui::ScrollArea::vertical().max_height(220.0).show(ui, |ui| {
+                for &zone in TZ_VARIANTS.iter().filter(|z| z.name().to_lowercase().contains(&query)) {
+                    ui.radio_value(&mut state.zone, Some(zone), zone.name());
+                }
+            });
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new(tr("dialog.time_zone.unchanged"))
+                    .size(11.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let button = egui::Button::new(RichText::new(tr("dialog.time_zone.apply")).color(theme::text_on(theme::accent())))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], button).clicked() {
+                    apply = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new(tr("editor.cancel"))).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if apply {
+        app.set_time_zone(state.zone);
+    }
+    if apply || cancelled || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_time_zone = false;
+        ctx.data_mut(|d| d.remove::<TimeZoneState>(state_id));
+    } else {
+        ctx.data_mut(|d| d.insert_temp(state_id, state));
+    }
+}
+
+#[derive(Clone)]
+struct TargetEndState {
+    set: bool,
+    day: NaiveDate,
+}
+
+/// Render the "Target End Date" dialog while `app.show_target_end` is set:
+/// the day the project has to be done by, checked against the forecast.
+pub fn show_target_end_dialog(app: &mut GanttApp, ctx: &Context) {
+    let state_id = egui::Id::new("target-end-dialog");
+    let forecast = app.project.forecast(app.project.clock().now());
+    let mut state = ctx.data(|d| d.get_temp::<TargetEndState>(state_id)).unwrap_or_else(|| TargetEndState {
+        set: app.project.target_end.is_some(),
+        day: app
+            .project
+            .target_end
+            .or(forecast.map(|f| f.end.date()))
+            .unwrap_or_else(|| chrono::Local::now().date_naive()),
+    });
+
+    let mut apply = false;
+    let mut cancelled = false;
+    let layout = theme::layout();
+    Window::new(RichText::new(tr("dialog.target_end.title")).strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut state.set, tr("dialog.target_end.set"));
+                ui.add_enabled(
+                    state.set,
+                    egui_extras::DatePickerButton::new(&mut state.day).id_salt("target-end"),
+                );
+            });
+            ui.add_space(4.0);
+            let note = match forecast.and_then(|f| f.finish) {
+                Some(finish) => {
+                    let finish_text = format_datetime(finish, "date.long");
+                    match forecast.and_then(|f| f.days_late(state.day)).filter(|_| state.set) {
+                        Some(days) => RichText::new(trn("dialog.target_end.late", days as usize, &[("finish", &finish_text)]))
+                            .color(Color32::from_rgb(220, 60, 60)),
+                        None => RichText::new(trf("dialog.target_end.forecast", &[("finish", &finish_text)]))
+                            .color(theme::text_dim()),
+                    }
+                }
+                None => RichText::new(tr("dialog.target_end.no_forecast")).color(theme::text_dim()),
+            };
+            ui.label(note.size(11.0));
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let button = egui::Button::new(RichText::new(tr("dialog.target_end.apply")).color(theme::text_on(theme::accent())))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], button).clicked() {
+                    apply = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new(tr("editor.cancel"))).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if apply {
+        app.set_target_end(state.set.then_some(state.day));
+    }
+    if apply || cancelled || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_target_end = false;
+        ctx.data_mut(|d| d.remove::<TargetEndState>(state_id));
+    } else {
+        ctx.data_mut(|d| d.insert_temp(state_id, state));
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ProgressPolicyState {
+    policy: ProgressPolicy,
+    /// Values the picked policy would change, with the policy and project
+    /// generation it was worked out for.
+    changes: Option<(ProgressPolicy, u64, usize)>,
+}
+
+/// Render the "Progress Policy" dialog while `app.show_progress_policy` is
+/// set: where task progress comes from, with how many values a switch
+/// would change.
+pub fn show_progress_policy_dialog(app: &mut GanttApp, ctx: &Context) {
+    let state_id = egui::Id::new("progress-policy-dialog");
+    let mut state = ctx
+        .data(|d| d.get_temp::<ProgressPolicyState>(state_id))
+        .unwrap_or(ProgressPolicyState { policy: app.project.progress_policy, changes: None });
+    let generation = app.project.generation();
+    let changes = match state.changes {
+        Some((policy, at, n)) if policy == state.policy && at == generation => n,
+        _ => {
+            let mut preview = app.project.clone();
+            preview.progress_policy = state.policy;
+            let n = preview.apply_progress_policy();
+            state.changes = Some((state.policy, generation, n));
+            n
+        }
+    };
+
+    let mut apply = false;
+    let mut cancelled = false;
+    let layout = theme::layout();
+    Window::new(RichText::new(tr("dialog.progress_policy.title")).strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            for policy in ProgressPolicy::all() {
+                let (label, hint) = match policy {
+                    ProgressPolicy::Manual => ("dialog.progress_policy.manual", "dialog.progress_policy.manual.hint"),
+                    ProgressPolicy::Checklist => ("dialog.progress_policy.checklist", "dialog.progress_policy.checklist.hint"),
+                    ProgressPolicy::Subtasks => ("dialog.progress_policy.subtasks", "dialog.progress_policy.subtasks.hint"),
+                };
+                ui.radio_value(&mut state.policy, *policy, tr(label));
+                ui.label(RichText::new(tr(hint)).size(11.0).color(theme::text_dim()));
+                ui.add_space(4.0);
+            }
+            ui.separator();
+            let summary = if state.policy == app.project.progress_policy {
+                tr("dialog.progress_policy.current").to_string()
+            } else {
+                trn("dialog.progress_policy.changes", changes, &[])
+            };
+            ui.label(RichText::new(summary).size(11.0).color(theme::text_secondary()));
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let button = egui::Button::new(RichText::new(tr("dialog.progress_policy.apply")).color(theme::text_on(theme::accent())))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], button).clicked() {
+                    apply = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new(tr("editor.cancel"))).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if apply {
+        app.set_progress_policy(state.policy);
+    }
+    if apply || cancelled || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_progress_policy = false;
+        ctx.data_mut(|d| d.remove::<ProgressPolicyState>(state_id));
+    } else {
+        ctx.data_mut(|d| d.insert_temp(state_id, state));
+    }
+}
+
+/// Render the "Highlight Rules" dialog while `app.show_highlight_rules` is
+/// set: each built-in rule's switch and thresholds.
+pub fn show_highlight_rules_dialog(app: &mut GanttApp, ctx: &Context) {
+    let state_id = egui::Id::new("highlight-rules-dialog");
+    let mut rules = ctx
+        .data(|d| d.get_temp::<HighlightRules>(state_id))
+        .unwrap_or(app.project.highlight_rules);
+
+    let mut apply = false;
+    let mut cancelled = false;
+    let layout = theme::layout();
+    Window::new(RichText::new(tr("dialog.highlight.title")).strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.checkbox(&mut rules.overdue, tr("dialog.highlight.overdue"));
+            ui.label(
+                RichText::new(tr("dialog.highlight.overdue.hint"))
+                    .size(11.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.checkbox(&mut rules.due_soon, tr("dialog.highlight.due_soon"));
+            ui.add_enabled_ui(rules.due_soon, |ui| {
+                egui::Grid::new("highlight-due-soon").num_columns(2).spacing([8.0, 4.0]).show(ui, |ui| {
+                    ui.label(tr("dialog.highlight.days"));
+                    ui.add(egui::DragValue::new(&mut rules.due_soon_days).range(0..=90).suffix(" d"));
+                    ui.end_row();
+                    ui.label(tr("dialog.highlight.progress"));
+                    let mut percent = (rules.due_soon_progress * 100.0).round();
+                    if ui.add(egui::DragValue::new(&mut percent).range(0.0..=100.0).suffix("%")).changed() {
+                        rules.due_soon_progress = percent / 100.0;
+                    }
+                    ui.end_row();
+                });
+            });
+            ui.label(
+                RichText::new(tr("dialog.highlight.due_soon.hint"))
+                    .size(11.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let button = egui::Button::new(RichText::new(tr("dialog.highlight.apply")).color(theme::text_on(theme::accent())))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], button).clicked() {
+                    apply = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new(tr("editor.cancel"))).clicked() {
+                    cancelled = true;
+                }
+                if ui.button(tr("dialog.highlight.reset")).clicked() {
+                    rules = HighlightRules::default();
+                }
+            });
+        });
+
+    if apply {
+        app.set_highlight_rules(rules);
+    }
+    if apply || cancelled || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_highlight_rules = false;
+        ctx.data_mut(|d| d.remove::<HighlightRules>(state_id));
+    } else {
+        ctx.data_mut(|d| d.insert_temp(state_id, rules));
+    }
+}
+
+#[derive(Clone)]
+struct ResourcesState {
+    absences: Vec<Absence>,
+    /// The absence being filled in below the list.
+    draft: Absence,
+}
+
+/// Render the "Resources" dialog while `app.show_resources` is set: each
+/// person's absences, with a row for adding another.
+pub fn show_resources_dialog(app: &mut GanttApp, ctx: &Context) {
+    let state_id = egui::Id::new("resources-dialog");
+    let mut state = ctx.data(|d| d.get_temp::<ResourcesState>(state_id)).unwrap_or_else(|| {
+        let today = app.project.clock().now().date();
+        ResourcesState { absences: app.project.absences.clone(), draft: Absence::new("", today, today) }
+    });
+    let people = assignee_names(&app.project.tasks);
+
+    let mut apply = false;
+    let mut cancelled = false;
+    let layout = theme::layout();
+    Window::new(RichText::new(tr("dialog.resources.title")).strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new(tr("dialog.resources.intro"))
+                    .size(11.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(4.0);
+            if state.absences.is_empty() {
+                ui.label(RichText::new(tr("dialog.resources.none")).color(theme::text_secondary()));
+            }
+            let mut remove = None;
+            egui::ScrollArea::vertical().max_height(220.0).show(ui, |ui| {
+                for (i, absence) in state.absences.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        if ui
+                            .small_button(egui_phosphor::regular::TRASH)
+                            .on_hover_text(tr("dialog.resources.remove"))
+                            .clicked()
+                        {
+                            remove = Some(i);
+                        }
+                        ui.label(RichText::new(absence.assignee.trim()).strong());
+                        ui.label(RichText::new(absence.describe()).color(theme::text_secondary()));
+                    });
+                }
+            });
+            if let Some(i) = remove {
+                state.absences.remove(i);
+            }
+
+            ui.add_space(4.0);
+            ui.separator();
+            ui.label(RichText::new(tr("dialog.resources.add")).strong());
+            egui::Grid::new("resources-draft").num_columns(2).spacing([8.0, 6.0]).show(ui, |ui| {
+                let draft = &mut state.draft;
+                ui.label(RichText::new(tr("dialog.resources.person")).color(theme::text_secondary()));
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut draft.assignee).desired_width(140.0));
+                    if !people.is_empty() {
+                        egui::ComboBox::from_id_salt("resources-person")
+                            .selected_text("")
+                            .width(24.0)
+                            .show_ui(ui, |ui| {
+                                for name in &people {
+                                    if ui.selectable_label(draft.assignee.trim() == name, name).clicked() {
+                                        draft.assignee = name.clone();
+                                    }
+                                }
+                            });
+                    }
+                });
+                ui.end_row();
+
+                ui.label(RichText::new(tr("dialog.resources.from")).color(theme::text_secondary()));
+                ui.add(egui_extras::DatePickerButton::new(&mut draft.first_day).id_salt("resources-from"));
+                ui.end_row();
+
+                ui.label(RichText::new(tr("dialog.resources.to")).color(theme::text_secondary()));
+                ui.add(egui_extras::DatePickerButton::new(&mut draft.last_day).id_salt("resources-to"));
+                ui.end_row();
+
+                ui.label(RichText::new(tr("dialog.resources.note")).color(theme::text_secondary()));
+                ui.add(
+                    egui::TextEdit::singleline(&mut draft.note)
+                        .hint_text(tr("dialog.resources.note.hint"))
+                        .desired_width(f32::INFINITY),
+                );
+                ui.end_row();
+            });
+            let ready = !state.draft.assignee.trim().is_empty();
+            if ui.add_enabled(ready, egui::Button::new(tr("dialog.resources.add_button"))).clicked() {
+                let mut absence = state.draft.clone();
+                absence.assignee = absence.assignee.trim().to_string();
+                if absence.last_day < absence.first_day {
+                    std::mem::swap(&mut absence.first_day, &mut absence.last_day);
+                }
+                state.absences.push(absence);
+                state.absences.sort_by(|a, b| {
+                    (a.assignee.to_lowercase(), a.first_day).cmp(&(b.assignee.to_lowercase(), b.first_day))
+                });
+                state.draft.note.clear();
+            }
+
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let button = egui::Button::new(RichText::new(tr("dialog.resources.apply")).color(theme::text_on(theme::accent())))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], button).clicked() {
+                    apply = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new(tr("editor.cancel"))).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if apply {
+        app.set_absences(state.absences);
+        app.show_resources = false;
+        ctx.data_mut(|d| d.remove::<ResourcesState>(state_id));
+    } else if cancelled || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_resources = false;
+        ctx.data_mut(|d| d.remove::<ResourcesState>(state_id));
+    } else {
+        ctx.data_mut(|d| d.insert_temp(state_id, state));
+    }
+}
+
+/// From and to pickers for the export dialog's date range, indented under
+/// the option they belong to.
+fn export_range_fields(ui: &mut Ui, range: &mut Option<(NaiveDate, NaiveDate)>) {
+    let Some((from, to)) = range else {
+        return;
+    };
+    ui.horizontal(|ui| {
+        ui.add_space(24.0);
+        ui.label(RichText::new(tr("dialog.export.from")).color(theme::text_secondary()));
+        ui.add(egui_extras::DatePickerButton::new(from).id_salt("export-from"));
+        ui.label(RichText::new(tr("dialog.export.to")).color(theme::text_secondary()));
+        ui.add(egui_extras::DatePickerButton::new(to).id_salt("export-to"));
+    });
+    if *to < *from {
+        std::mem::swap(from, to);
+    }
+}
+
+/// Render the export dialog while `app.pending_export` names a format: which
+/// tasks to include, plus the format's own extras. The choices are
+/// remembered per format for the next export.
+pub fn show_export_dialog(app: &mut GanttApp, ctx: &Context) {
+    let Some(exporter) = app.pending_export.and_then(|name| app.exporters.find(name)) else {
+        app.pending_export = None;
+        return;
+    };
+    let format = exporter.name();
+    let filtering = app.is_filtering();
+    let selected = app.selection.len();
+    let state_id = egui::Id::new(("export-dialog", format));
+    let mut options = ctx.data(|d| d.get_temp::<ExportOptions>(state_id)).unwrap_or_else(|| {
+        let mut options = app.settings.export_options.get(format).cloned().unwrap_or_default();
+        let unavailable = match options.scope {
+            ExportScope::Filter => !filtering,
+            ExportScope::Selection => selected == 0,
+            _ => false,
+        };
+        if unavailable {
+            options.scope = ExportScope::Project;
+        }
+        if options.date_range.is_none() {
+            let today = app.project.clock().now().date();
+            let first = app.project.tasks.iter().map(|t| t.start.date()).min().unwrap_or(today);
+            let last = app.project.tasks.iter().map(|t| t.end.date()).max().unwrap_or(today);
+            options.date_range = Some((first, last));
+        }
+        options
+    });
+
+    let mut export = false;
+    let mut cancelled = false;
+    let layout = theme::layout();
+    Window::new(RichText::new(trf("dialog.export.title", &[("format", &format)])).strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label(RichText::new(tr("dialog.export.scope")).strong());
+            ui.radio_value(&mut options.scope, ExportScope::Project, tr("dialog.export.scope.project"));
+            ui.add_enabled_ui(filtering, |ui| {
+                ui.radio_value(&mut options.scope, ExportScope::Filter, tr("dialog.export.scope.filter"))
+                    .on_disabled_hover_text(tr("dialog.export.scope.no_filter"));
+            });
+            ui.add_enabled_ui(selected > 0, |ui| {
+                ui.radio_value(
+                    &mut options.scope,
+                    ExportScope::Selection,
+                    trn("dialog.export.scope.selection", selected, &[]),
+                )
+                .on_disabled_hover_text(tr("dialog.export.scope.no_selection"));
+            });
+            ui.radio_value(&mut options.scope, ExportScope::DateRange, tr("dialog.export.scope.date_range"));
+            if options.scope == ExportScope::DateRange {
+                export_range_fields(ui, &mut options.date_range);
+            }
+            ui.label(
+                RichText::new(tr("dialog.export.scope.hint"))
+                    .size(11.0)
+                    .color(theme::text_dim()),
+            );
+
+            ui.add_space(6.0);
+            ui.checkbox(&mut options.include_notes, tr("dialog.export.include_notes"));
+            ui.checkbox(&mut options.include_completed, tr("dialog.export.include_completed"));
+
+            for extra in exporter.extras() {
+                match extra {
+                    ExportExtra::Delimiter => {
+                        let delimiters = [
+                            (';', tr("dialog.export.delimiter.semicolon")),
+                            (',', tr("dialog.export.delimiter.comma")),
+                            ('\t', tr("dialog.export.delimiter.tab")),
+                            ('|', tr("dialog.export.delimiter.pipe")),
+                        ];
+                        let current = delimiters
+                            .iter()
+                            .find(|(c, _)| *c == options.delimiter)
+                            .map_or_else(|| options.delimiter.to_string(), |(_, label)| label.to_string());
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(tr("dialog.export.delimiter")).color(theme::text_secondary()));
+                            egui::ComboBox::from_id_salt("export-delimiter").selected_text(current).show_ui(ui, |ui| {
+                                for (c, label) in delimiters {
+                                    ui.selectable_value(&mut options.delimiter, c, label);
+                                }
+                            });
+                        });
+                    }
+                    ExportExtra::CropToRange => {
+                        ui.checkbox(&mut options.crop_to_range, tr("dialog.export.crop"))
+                            .on_hover_text(tr("dialog.export.crop.hint"));
+                        // The range is shared with the date-range scope,
+                        // whose fields are already showing when it's picked.
+                        if options.crop_to_range && options.scope != ExportScope::DateRange {
+                            export_range_fields(ui, &mut options.date_range);
+                        }
+                    }
+                }
+            }
+
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let button = egui::Button::new(RichText::new(tr("dialog.export.export")).color(theme::text_on(theme::accent())))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], button).clicked() {
+                    export = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new(tr("editor.cancel"))).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if export {
+        app.pending_export = None;
+        ctx.data_mut(|d| d.remove::<ExportOptions>(state_id));
+        app.settings.export_options.insert(format.to_string(), options.clone());
+        app.settings.save();
+        app.export_as(ctx, format, &options);
+    } else if cancelled || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.pending_export = None;
+        ctx.data_mut(|d| d.remove::<ExportOptions>(state_id));
+    } else {
+        ctx.data_mut(|d| d.insert_temp(state_id, options));
+    }
+}
@@ -1,5 +1,10 @@
-use crate::model::task::TaskPriority;
+use crate::i18n::{due_label, priority_label, risk_label, tr, trf};
+use crate::model::highlight::DueState;
+use crate::model::risk::RiskLevel;
+use crate::model::task::{CompletedTasks, TaskPriority};
+pub use crate::model::task::task_matches;
 use crate::ui::theme;
+use crate::ui::model_ext::PriorityIcon;
 use egui::{RichText, Ui};
 
 /// Active filter state used to decide which tasks are visible.
@@ -8,8 +13,8 @@ use egui::{RichText, Ui};
 pub struct FilterState {
     pub search: String,
     pub priority: Option<TaskPriority>,
-    pub only_overdue: bool,
     pub only_in_progress: bool,
+    pub completed: CompletedTasks,
 }
 
 impl FilterState {
@@ -17,8 +22,8 @@ impl FilterState {
     pub fn is_active(&self) -> bool {
         !self.search.is_empty()
             || self.priority.is_some()
-            || self.only_overdue
             || self.only_in_progress
+            || self.completed == CompletedTasks::Hide
     }
 }
 
@@ -27,6 +32,9 @@ impl FilterState {
 pub fn show_filter_bar(
     search_query: &mut String,
     filter_priority: &mut Option<TaskPriority>,
+    filter_risk: &mut Option<RiskLevel>,
+    filter_due: &mut Option<DueState>,
+    hide_tentative: &mut bool,
     ui: &mut Ui,
 ) -> bool {
     let mut changed = false;
@@ -37,7 +45,11 @@ pub fn show_filter_bar(
     let combo_w = 100.0;
     let clear_w = 18.0;
     let spacing = ui.spacing().item_spacing.x * 2.0 + 6.0;
-    let has_filter = !search_query.is_empty() || filter_priority.is_some();
+    let has_filter = !search_query.is_empty()
+        || filter_priority.is_some()
+        || filter_risk.is_some()
+        || filter_due.is_some()
+        || *hide_tentative;
     let search_w = (avail - combo_w - spacing - if has_filter { clear_w + 4.0 } else { 0.0 })
         .max(40.0);
 
@@ -49,7 +61,7 @@ pub fn show_filter_bar(
         let search_resp = ui.add_sized(
             [search_w, 22.0],
             egui::TextEdit::singleline(search_query)
-                .hint_text("🔍 Search…")
+                .hint_text(tr("filter.search"))
                 .font(egui::FontId::proportional(11.0))
                 .text_color(theme::text_secondary()),
         );
@@ -58,23 +70,35 @@ pub fn show_filter_bar(
         }
 
         // Priority filter combo — fixed width
-        let pri_label = match filter_priority {
-            None => "Priority".to_string(),
-            Some(p) => format!("{} {}", p.icon(), p.label()),
+        let mut pri_label = match filter_priority {
+            None => tr("filter.priority").to_string(),
+            Some(p) => format!("{} {}", p.icon(), priority_label(*p)),
         };
+        if let Some(risk) = filter_risk {
+            pri_label.push_str(" · ");
+            pri_label.push_str(&trf("filter.risk_level", &[("level", &risk_label(Some(*risk)))]));
+        }
+        if let Some(due) = filter_due {
+            pri_label.push_str(" · ");
+            pri_label.push_str(due_label(*due));
+        }
+        if *hide_tentative {
+            pri_label.push_str(" · ");
+            pri_label.push_str(tr("filter.confirmed"));
+        }
         egui::ComboBox::from_id_salt("filter_priority_combo")
             .selected_text(RichText::new(&pri_label).size(11.0))
             .width(combo_w)
             .show_ui(ui, |ui| {
                 if ui
-                    .selectable_label(filter_priority.is_none(), "— All —")
+                    .selectable_label(filter_priority.is_none(), tr("filter.all"))
                     .clicked()
                 {
                     *filter_priority = None;
                     changed = true;
                 }
                 for p in TaskPriority::all() {
-                    let lbl = format!("{} {}", p.icon(), p.label());
+                    let lbl = format!("{} {}", p.icon(), priority_label(*p));
                     if ui
                         .selectable_label(*filter_priority == Some(*p), &lbl)
                         .clicked()
@@ -83,6 +107,34 @@ pub fn show_filter_bar(
                         changed = true;
                     }
                 }
+                ui.separator();
+                ui.label(RichText::new(tr("filter.risk")).size(10.0).color(theme::text_dim()));
+                if ui.selectable_label(filter_risk.is_none(), tr("filter.any_risk")).clicked() {
+                    *filter_risk = None;
+                    changed = true;
+                }
+                for level in RiskLevel::all() {
+                    if ui.selectable_label(*filter_risk == Some(*level), risk_label(Some(*level))).clicked() {
+                        *filter_risk = Some(*level);
+                        changed = true;
+                    }
+                }
+                ui.separator();
+                ui.label(RichText::new(tr("filter.due")).size(10.0).color(theme::text_dim()));
+                if ui.selectable_label(filter_due.is_none(), tr("filter.any_due")).clicked() {
+                    *filter_due = None;
+                    changed = true;
+                }
+                for &state in DueState::all() {
+                    if ui.selectable_label(*filter_due == Some(state), due_label(state)).clicked() {
+                        *filter_due = Some(state);
+                        changed = true;
+                    }
+                }
+                ui.separator();
+                if ui.checkbox(hide_tentative, tr("filter.hide_tentative")).changed() {
+                    changed = true;
+                }
             });
 
         // Clear button — only visible when a filter is active
@@ -92,11 +144,14 @@ pub fn show_filter_bar(
                     egui::Button::new(RichText::new(egui_phosphor::regular::X).size(10.0).color(theme::text_dim()))
                         .frame(false),
                 )
-                .on_hover_text("Clear filters")
+                .on_hover_text(tr("filter.clear"))
                 .clicked()
             {
                 search_query.clear();
                 *filter_priority = None;
+                *filter_risk = None;
+                *filter_due = None;
+                *hide_tentative = false;
                 changed = true;
             }
         }
@@ -105,28 +160,18 @@ pub fn show_filter_bar(
     changed
 }
 
-/// Returns true if a task matches the current search/filter.
-pub fn task_matches(
-    name: &str,
-    description: &str,
-    priority: TaskPriority,
-    search: &str,
-    filter_priority: Option<TaskPriority>,
-) -> bool {
-    // Priority filter
-    if let Some(fp) = filter_priority {
-        if priority != fp {
-            return false;
-        }
-    }
-
-    // Text search (case-insensitive)
-    if !search.is_empty() {
-        let query = search.to_lowercase();
-        if !name.to_lowercase().contains(&query) && !description.to_lowercase().contains(&query) {
-            return false;
-        }
-    }
-
-    true
+/// One line shown in place of the filter bar while it is hidden and a
+/// filter still applies. Returns true when the filter should be cleared.
+pub fn show_hidden_filter_note(ui: &mut Ui) -> bool {
+    let mut clear = false;
+    ui.horizontal(|ui| {
+        ui.label(
+            RichText::new(format!("{} {}", egui_phosphor::regular::FUNNEL, tr("filter.hidden_active")))
+                .size(11.0)
+                .color(theme::text_secondary()),
+        );
+        clear = ui.small_button(tr("filter.clear")).clicked();
+    });
+    ui.add_space(2.0);
+    clear
 }
@@ -1,12 +1,101 @@
 use crate::model::{Task, TimelineScale, TimelineViewport};
-use crate::model::task::{Dependency, DependencyKind};
+use crate::model::absence::{absences_overlapping, Absence};
+use crate::model::clock::ProjectClock;
+use crate::model::project::TaskDefaults;
+use crate::settings::BarLabels;
+use crate::model::graph::{DependencyGraph, DependencyTrace, TraceSide};
+use crate::model::highlight::Highlight;
+use crate::model::task::{CompletedTasks, Dependency, DependencyKind, FixedEdge};
+use crate::model::validation::DependencyViolation;
+use crate::ui::inline_rename::{self, RenameSurface};
+use crate::ui::row_metrics::RowLayout;
+use crate::ui::row_sync::{self, RowView};
+use crate::ui::rows::{group_label, Row, Rows};
+use crate::ui::scroll_anchor;
 use crate::ui::theme;
-use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+use crate::i18n::format_datetime;
+use crate::ui::model_ext::{StatusColor, ToColor32};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
 use egui::{Color32, Id, Pos2, Rect, Rounding, Sense, Stroke, Ui, Vec2};
 use uuid::Uuid;
 
 fn header_height() -> f32 { theme::header_height() }
 
+/// Minimum width of the inline rename field drawn over a bar label.
+const RENAME_FIELD_WIDTH: f32 = 180.0;
+
+/// Temp-data key for a task the chart should scroll into view.
+const REVEAL_ID: &str = "gantt-reveal-task";
+
+/// Ask the chart to scroll `id` into view on its next frame.
+pub fn reveal_task(ctx: &egui::Context, id: Uuid) {
+    ctx.data_mut(|d| d.insert_temp(Id::new(REVEAL_ID), id));
+}
+
+/// Temp-data key set when the chart should scroll to today.
+const TODAY_ID: &str = "gantt-scroll-to-today";
+
+/// Ask the chart to scroll today's date into view on its next frame.
+pub fn scroll_to_today(ctx: &egui::Context) {
+    ctx.data_mut(|d| d.insert_temp(Id::new(TODAY_ID), true));
+}
+
+/// Temp-data key of the task kept at the top of the chart across reorders.
+const ANCHOR_ID: &str = "gantt-scroll-anchor";
+
+/// Temp-data key set when the chart should fit the whole project on screen.
+const FIT_ID: &str = "gantt-fit-to-window";
+
+/// Temp-data key of a scroll offset the chart should jump to.
+const SCROLL_ID: &str = "gantt-scroll-to";
+
+/// Temp-data key for a date range (and the row to scroll to) the chart
+/// should zoom to.
+const ZOOM_ID: &str = "gantt-zoom-to-range";
+
+/// Ask the chart to frame `start..end` on its next frame and scroll `row`
+/// into view vertically.
+pub fn zoom_to_range(ctx: &egui::Context, start: NaiveDateTime, end: NaiveDateTime, row: Uuid) {
+    ctx.data_mut(|d| d.insert_temp(Id::new(ZOOM_ID), (start, end, row)));
+}
+
+/// Opacity of finished tasks' bars when completed tasks are struck through.
+const COMPLETED_OPACITY: f32 = 0.35;
+
+/// Narrowest a task bar is drawn, however far out the timeline is zoomed.
+/// The bar still starts at the task's start; only its end is stretched.
+const MIN_BAR_WIDTH: f32 = 6.0;
+
+/// Narrowest area that takes clicks and drags on a bar, so one-day tasks
+/// can still be grabbed when zoomed out to months.
+const MIN_HIT_WIDTH: f32 = 16.0;
+
+/// Extra grab room either side of a milestone's diamond.
+const MILESTONE_HIT_MARGIN: f32 = 8.0;
+
+/// Ask the chart to zoom so every task fits its width on the next frame.
+pub fn fit_to_window(ctx: &egui::Context) {
+    ctx.data_mut(|d| d.insert_temp(Id::new(FIT_ID), true));
+}
+
+/// Ask the chart to scroll to `offset` on the next frame.
+pub fn scroll_to(ctx: &egui::Context, offset: Vec2) {
+    ctx.data_mut(|d| d.insert_temp(Id::new(SCROLL_ID), offset));
+}
+
+/// Where the chart's scroll area was this frame.
+#[derive(Debug, Clone, Copy)]
+pub struct ChartScroll {
+    /// The part of the chart on screen, scroll bars left out.
+    pub rect: Rect,
+    pub offset: Vec2,
+    /// Height of everything the chart draws, from the top of its header.
+    pub content_height: f32,
+    /// Height of the header, and milestone lane if shown, frozen over the
+    /// rows as they scroll.
+    pub header_height: f32,
+}
+
 #[derive(Debug, Clone)]
 struct DragSnapshot {
     start: NaiveDateTime,
@@ -15,12 +104,39 @@ struct DragSnapshot {
     start_pointer_y: f32,
 }
 
+/// A task drawn on empty chart space: dragged out, or double-clicked for
+/// a single day (a milestone with Alt held).
+#[derive(Debug, Clone)]
+pub struct DrawnTask {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    /// The phase whose band the task was drawn in.
+    pub parent: Option<Uuid>,
+    pub milestone: bool,
+}
+
+/// A drag across empty chart space that will create a task.
+#[derive(Debug, Clone, Copy)]
+struct CreateDrag {
+    anchor_x: f32,
+    /// Visible row the drag started on; one past the last row is the free
+    /// space below the tasks.
+    row: usize,
+}
+
+/// Where a task inserted next to another goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertSide {
+    Above,
+    Below,
+}
+
 /// Result details from interactions in the Gantt chart.
 #[derive(Debug, Clone)]
 pub struct ChartInteraction {
     pub changed: bool,
-    /// A new dependency to add (created via Shift+drag).
-    pub new_dependency: Option<Dependency>,
+    /// The task whose dates were dragged, when `changed` came from a bar drag.
+    pub moved_task: Option<Uuid>,
     /// A dependency to remove (right-clicked on arrow).
     pub remove_dependency: Option<(Uuid, Uuid)>,
     /// A parent task whose collapsed state should be toggled.
@@ -29,17 +145,44 @@ pub struct ChartInteraction {
     pub add_subtask: Option<Uuid>,
     /// Request to delete this task.
     pub delete_task: Option<Uuid>,
+    /// A task renamed in place (double-click on its bar label).
+    pub rename: Option<(Uuid, String)>,
+    /// Date and time under the mouse pointer, while it is over the chart.
+    pub hover_time: Option<NaiveDateTime>,
+    /// Lock or unlock this task's dates (context menu).
+    pub toggle_lock: Option<Uuid>,
+    /// Mark this task tentative or confirmed (context menu).
+    pub toggle_tentative: Option<Uuid>,
+    /// A locked task the user tried to drag; its dates were left alone.
+    pub blocked_move: Option<Uuid>,
+    /// A new task drawn on empty space.
+    pub create_task: Option<DrawnTask>,
+    /// Insert a new sibling next to this task (context menu).
+    pub insert_task: Option<(Uuid, InsertSide)>,
+    /// A milestone clicked in the milestone lane, to select and scroll to.
+    pub reveal: Option<Uuid>,
+    /// Where the chart is scrolled to, once it has been drawn.
+    pub scroll: Option<ChartScroll>,
 }
 
 impl Default for ChartInteraction {
     fn default() -> Self {
         Self {
             changed: false,
-            new_dependency: None,
+            moved_task: None,
             remove_dependency: None,
             toggle_collapse: None,
             add_subtask: None,
             delete_task: None,
+            rename: None,
+            hover_time: None,
+            toggle_lock: None,
+            toggle_tentative: None,
+            blocked_move: None,
+            create_task: None,
+            insert_task: None,
+            reveal: None,
+            scroll: None,
         }
     }
 }
@@ -48,7 +191,96 @@ impl Default for ChartInteraction {
 #[derive(Debug, Clone)]
 struct LinkDragState {
     from_task: Uuid,
-    from_point: Pos2,
+    /// Where the source bar was when the drag started.
+    from_rect: Rect,
+}
+
+/// A link drawn onto a valid target, or picked from the keyboard, waiting
+/// for its kind and lag to be picked at `at`.
+#[derive(Debug, Clone, Copy)]
+struct PendingLink {
+    from_task: Uuid,
+    to_task: Uuid,
+    at: Pos2,
+    kind: DependencyKind,
+    lag_days: i64,
+}
+
+const PENDING_LINK_ID: &str = "gantt-pending-link";
+
+/// Ask for the kind and lag of a new link `from_task → to_task` at `at`,
+/// starting from `kind`. [`show_link_chooser`] shows the question.
+pub fn request_link(ctx: &egui::Context, from_task: Uuid, to_task: Uuid, at: Pos2, kind: DependencyKind) {
+    let pending = PendingLink { from_task, to_task, at, kind, lag_days: 0 };
+    ctx.data_mut(|d| d.insert_temp(Id::new(PENDING_LINK_ID), pending));
+}
+
+/// Tint of a link target that can't be linked to.
+const LINK_REJECT_COLOR: Color32 = Color32::from_rgb(220, 60, 60);
+
+/// Links whose dates are broken, and the warning at their successor.
+fn violation_color() -> Color32 {
+    theme::status_color(theme::Severity::Bad, Color32::from_rgb(220, 60, 60))
+}
+
+/// Chain colours while tracing a task's dependencies.
+const TRACE_UPSTREAM: Color32 = Color32::from_rgb(80, 160, 240);
+const TRACE_DOWNSTREAM: Color32 = Color32::from_rgb(235, 135, 50);
+
+fn trace_color(side: TraceSide) -> Color32 {
+    match side {
+        TraceSide::Upstream => TRACE_UPSTREAM,
+        TraceSide::Downstream => TRACE_DOWNSTREAM,
+    }
+}
+
+/// Optional overlays on top of the plain chart.
+#[derive(Clone, Copy)]
+pub struct ChartOptions<'a> {
+    /// Dim tasks off this trace's chains and draw the chains in their own colours.
+    pub trace: Option<&'a DependencyTrace>,
+    /// Colour started bars green, amber or red by how far behind schedule they are.
+    pub schedule_tint: bool,
+    /// The project's time zone; the header, weekends and today line are drawn
+    /// in the viewer's local time.
+    pub clock: ProjectClock,
+    /// Where task names go on the bars.
+    pub labels: BarLabels,
+    /// Strike through finished tasks; whether they show at all is up to `rows`.
+    pub completed: CompletedTasks,
+    /// Times of day tasks drawn at the day scales start and end.
+    pub task_defaults: TaskDefaults,
+    /// Days people are away; bars running into their assignee's absence
+    /// are marked.
+    pub absences: &'a [Absence],
+    /// Pin a lane of milestones under the header: all of them, or with
+    /// `Some(true)` only those tagged "key".
+    pub milestone_lane: Option<bool>,
+    /// Rows to show, in order, shared with the task list. Swimlanes and
+    /// drag-to-reorder are off while they are grouped.
+    pub rows: &'a Rows<'a>,
+    /// Links the tasks' dates break, from [`crate::model::Project::dependency_violations`].
+    pub violations: &'a [DependencyViolation],
+    /// Tasks the project's highlight rules mark: overdue bars are tinted
+    /// red and at-risk ones badged.
+    pub highlights: &'a std::collections::HashMap<Uuid, Highlight>,
+    /// A link picked in the Dependencies panel, drawn over the others in
+    /// the accent colour.
+    pub highlight_link: Option<(Uuid, Uuid)>,
+}
+
+/// Per-bar drawing options for [`draw_task_bar`].
+#[derive(Clone, Copy)]
+struct BarStyle {
+    selected: bool,
+    schedule_tint: bool,
+    /// The current time, as the project stores times.
+    now: NaiveDateTime,
+    labels: BarLabels,
+    /// Milestones only: put the name above the diamond's centre line instead
+    /// of below. Alternates down the chart so neighbouring names stay apart.
+    label_raised: bool,
+    highlight: Option<Highlight>,
 }
 
 /// Render the Gantt chart area (right panel).
@@ -57,81 +289,77 @@ pub fn show_gantt_chart(
     dependencies: &[Dependency],
     viewport: &mut TimelineViewport,
     selected_task: &mut Option<Uuid>,
+    selection: &[Uuid],
+    options: ChartOptions,
     ui: &mut Ui,
 ) -> ChartInteraction {
     let mut interaction = ChartInteraction::default();
+    let trace = options.trace;
     let available = ui.available_size();
+    let fit = ui.ctx().data_mut(|d| d.remove_temp::<bool>(Id::new(FIT_ID))).unwrap_or(false);
+    if fit {
+        viewport.fit_to_tasks(tasks, available.x);
+    }
+    let zoom = ui.ctx().data_mut(|d| d.remove_temp::<(NaiveDateTime, NaiveDateTime, Uuid)>(Id::new(ZOOM_ID)));
+    if let Some((start, end, _)) = zoom {
+        viewport.fit_to_range(start, end, available.x);
+    }
     let row_height = scaled_row_height(viewport);
     let row_padding = scaled_row_padding(viewport);
     let chart_width = viewport.total_width().max(available.x);
-    let hh = header_height();
-
-    // Build the list of visible task indices, skipping children of collapsed parents.
-    let visible_rows: Vec<usize> = tasks
-        .iter()
-        .enumerate()
-        .filter_map(|(i, t)| {
-            if let Some(pid) = t.parent_id {
-                let parent_collapsed = tasks.iter().find(|p| p.id == pid).map(|p| p.collapsed).unwrap_or(false);
-                if parent_collapsed { return None; }
+    let lane_height = if options.milestone_lane.is_some() { MILESTONE_LANE_HEIGHT } else { 0.0 };
+    // Rows start below the header and, when shown, the milestone lane.
+    let hh = header_height() + lane_height;
+
+    // The shared rows' tasks by their current index, in case the list
+    // changed the tasks since the rows were built. Group headers keep a row
+    // of their own so the bars stay level with the list's rows.
+    let index: std::collections::HashMap<Uuid, usize> = tasks.iter().enumerate().map(|(i, t)| (t.id, i)).collect();
+    let mut slots: Vec<Option<Uuid>> = Vec::with_capacity(options.rows.rows.len());
+    let mut group_headers = Vec::new();
+    for row in &options.rows.rows {
+        match *row {
+            Row::Group { group, shown } => {
+                slots.push(None);
+                group_headers.push((group, shown));
             }
-            Some(i)
-        })
-        .collect();
-
-    let chart_height = hh + (visible_rows.len() as f32 * (row_height + row_padding)) + 40.0;
-
-    egui::ScrollArea::both()
-        .auto_shrink([false, false])
+            Row::Task { id, .. } if index.contains_key(&id) => slots.push(Some(id)),
+            Row::Task { .. } => {}
+        }
+    }
+    let grouped = !group_headers.is_empty();
+    let row_ids: Vec<Uuid> = slots.iter().flatten().copied().collect();
+    let visible_rows: Vec<usize> = row_ids.iter().map(|id| index[id]).collect();
+    let rows = RowLayout::new(ui.ctx(), &slots, row_height, row_padding, vertical_zoom_scale(viewport));
+    let chart_height = hh + rows.total() + 40.0;
+
+    let mut scroll = egui::ScrollArea::both().auto_shrink([false, false]);
+    if fit || zoom.is_some() {
+        scroll = scroll.horizontal_scroll_offset(0.0);
+    }
+    let anchor_id = Id::new(ANCHOR_ID);
+    if let Some(offset) = ui.ctx().data_mut(|d| d.remove_temp::<Vec2>(Id::new(SCROLL_ID))) {
+        scroll = scroll.scroll_offset(offset);
+    } else if let Some(offset) = scroll_anchor::restore(ui.ctx(), anchor_id, &row_ids) {
+        scroll = scroll.vertical_scroll_offset(offset);
+    } else if let Some(offset) = row_sync::scroll_offset(ui.ctx(), RowView::Chart) {
+        scroll = scroll.vertical_scroll_offset(offset);
+    }
+    let scrolled = scroll
         .show(ui, |ui| {
+            // Dragging empty space draws a new task.
             let (response, painter) = ui.allocate_painter(
                 Vec2::new(chart_width, chart_height.max(available.y)),
-                Sense::click(),
+                Sense::click_and_drag(),
             );
 
-            // Handle Ctrl+Scroll zoom when pointer is over chart.
-            if response.hovered() && ui.input(|i| i.modifiers.ctrl) {
-                let raw_scroll_y = ui.input(|i| i.raw_scroll_delta.y);
-                let smooth_scroll_y = ui.input(|i| i.smooth_scroll_delta.y);
-                let scroll_y = if raw_scroll_y.abs() > smooth_scroll_y.abs() {
-                    raw_scroll_y
-                } else {
-                    smooth_scroll_y
-                };
-
-                // Get mouse position relative to chart area
-                if let Some(hover_pos) = response.hover_pos() {
-                    let mouse_x = hover_pos.x - response.rect.min.x;
-
-                    // Calculate the datetime at mouse position before zoom
-                    let _old_pixels_per_day = viewport.pixels_per_day;
-                    let datetime_at_mouse = viewport.x_to_datetime(mouse_x);
-
-                    // Perform zoom
-                    if scroll_y > 0.0 {
-                        viewport.zoom_in();
-                    } else if scroll_y < 0.0 {
-                        viewport.zoom_out();
-                    }
-
-                    // Adjust viewport to keep the datetime under mouse in the same position
-                    let new_x_for_datetime = viewport.datetime_to_x(datetime_at_mouse);
-                    let x_offset = new_x_for_datetime - mouse_x;
-
-                    // Shift viewport start/end to compensate
-                    let time_offset = chrono::Duration::seconds(
-                        (x_offset / viewport.pixels_per_day * 86400.0) as i64
-                    );
-                    viewport.start = viewport.start + time_offset;
-                    viewport.end = viewport.end + time_offset;
-                }
-            }
+            handle_ctrl_scroll_zoom(ui, &response, viewport);
 
             let row_height = scaled_row_height(viewport);
-            let row_padding = scaled_row_padding(viewport);
             let handle_width = scaled_handle_width(viewport);
 
             let origin = response.rect.min;
+            interaction.hover_time = response.hover_pos().map(|p| viewport.x_to_datetime(p.x - origin.x));
             let mut consumed_click = false;
             let shift_held = ui.input(|i| i.modifiers.shift);
             let mut reorder_request: Option<(usize, usize)> = None;
@@ -144,33 +372,47 @@ pub fn show_gantt_chart(
                 theme::bg_dark(),
             );
 
-            // Draw alternating row backgrounds (only for visible rows)
+            // Draw alternating row backgrounds (only for visible rows). The
+            // row hovered here or in the task list is tinted, selected rows
+            // outlined, as in the list.
+            let linked_hover = row_sync::hovered(ui.ctx());
             for (vis_i, &task_i) in visible_rows.iter().enumerate() {
-                let _task = &tasks[task_i];
-                let y = origin.y + hh + vis_i as f32 * (row_height + row_padding);
-                let row_bg = if vis_i % 2 == 0 {
+                let task = &tasks[task_i];
+                let y = origin.y + hh + rows.top(vis_i);
+                let row_bg = if linked_hover == Some(task.id) {
+                    theme::widget_bg_hovered()
+                } else if vis_i % 2 == 0 {
                     theme::bg_panel()
                 } else {
                     theme::bg_dark()
                 };
-                painter.rect_filled(
-                    Rect::from_min_size(
-                        Pos2::new(origin.x, y),
-                        Vec2::new(chart_width, row_height + row_padding),
-                    ),
-                    0.0,
-                    row_bg,
-                );
+                let row_rect = Rect::from_min_size(Pos2::new(origin.x, y), Vec2::new(chart_width, rows.span(vis_i)));
+                painter.rect_filled(row_rect, 0.0, row_bg);
+                if *selected_task == Some(task.id) || selection.contains(&task.id) {
+                    painter.rect_stroke(row_rect.shrink(0.5), 0.0, Stroke::new(1.0, theme::row_selected_stroke()));
+                }
                 // Row bottom border
                 painter.line_segment(
                     [
-                        Pos2::new(origin.x, y + row_height + row_padding),
-                        Pos2::new(origin.x + chart_width, y + row_height + row_padding),
+                        Pos2::new(origin.x, row_rect.bottom()),
+                        Pos2::new(origin.x + chart_width, row_rect.bottom()),
                     ],
                     Stroke::new(0.5, theme::border_subtle()),
                 );
             }
 
+            if !grouped {
+                draw_swimlanes(
+                    &painter,
+                    tasks,
+                    &visible_rows,
+                    Pos2::new(origin.x, origin.y + hh),
+                    &rows,
+                    chart_width,
+                    ui.clip_rect().with_min_y(ui.clip_rect().top() + hh),
+                );
+            }
+
             // Shade weekends in the gantt body so they stand out clearly.
             draw_weekend_bands(
                 &painter,
@@ -178,17 +420,42 @@ pub fn show_gantt_chart(
                 viewport,
                 chart_width,
                 origin.y + chart_height,
+                options.clock,
             );
 
-            // Draw timeline header in content space
+            // Header background and grid lines down through the rows; the
+            // frozen copy drawn last covers the header itself.
             draw_timeline_header(
                 &painter,
                 origin,
                 viewport,
                 chart_width,
                 origin.y + chart_height,
+                options.clock,
             );
 
+            // Group headers, named at the left edge of the visible chart as
+            // in the list.
+            let label_x = origin.x.max(ui.clip_rect().left()) + 8.0;
+            for (&top, &(group, shown)) in rows.headers().iter().zip(&group_headers) {
+                let slot = Rect::from_min_size(
+                    Pos2::new(origin.x, origin.y + hh + top),
+                    Vec2::new(chart_width, row_height + row_padding),
+                );
+                painter.rect_filled(slot, 0.0, theme::bg_panel());
+                painter.line_segment(
+                    [slot.left_bottom(), slot.right_bottom()],
+                    Stroke::new(0.5, theme::border_subtle()),
+                );
+                painter.text(
+                    Pos2::new(label_x, slot.center().y + row_padding / 2.0),
+                    egui::Align2::LEFT_CENTER,
+                    format!("{}  ({})", group_label(&group.key), shown),
+                    theme::font_bar(),
+                    theme::text_secondary(),
+                );
+            }
+
             // Animated row Y positions for smooth reorder transitions.
             // Only visible rows get a Y slot; collapsed children are not assigned a Y.
             let anim_dur = theme::reorder_anim_duration();
@@ -197,8 +464,7 @@ pub fn show_gantt_chart(
             let mut animating_rows = false;
             for (vis_i, &task_i) in visible_rows.iter().enumerate() {
                 let task = &tasks[task_i];
-                let target_y =
-                    origin.y + hh + vis_i as f32 * (row_height + row_padding) + row_padding;
+                let target_y = origin.y + hh + rows.bar_top(vis_i);
                 let anim_id = Id::new(("row-y", task.id));
                 let animated_y = ui.ctx().animate_value_with_time(anim_id, target_y, anim_dur);
                 if (animated_y - target_y).abs() > 0.25 {
@@ -217,9 +483,7 @@ pub fn show_gantt_chart(
                 .enumerate()
                 .map(|(vis_i, &task_i)| {
                     let task = &tasks[task_i];
-                    let y = *animated_row_y.get(&task.id).unwrap_or(
-                        &(origin.y + hh + vis_i as f32 * (row_height + row_padding) + row_padding),
-                    );
+                    let y = *animated_row_y.get(&task.id).unwrap_or(&(origin.y + hh + rows.bar_top(vis_i)));
                     let inset = theme::bar_inset();
                     if task.is_milestone {
                         let x = origin.x + viewport.date_to_x(task.start);
@@ -229,7 +493,7 @@ pub fn show_gantt_chart(
                     } else {
                         let x_start = origin.x + viewport.date_to_x(task.start);
                         let x_end = origin.x + viewport.date_to_x(task.end);
-                        let bar_width = (x_end - x_start).max(6.0);
+                        let bar_width = (x_end - x_start).max(MIN_BAR_WIDTH);
                         let bar_rect = Rect::from_min_size(
                             Pos2::new(x_start, y + inset),
                             Vec2::new(bar_width, row_height - inset * 2.0),
@@ -239,30 +503,78 @@ pub fn show_gantt_chart(
                 })
                 .collect();
 
+            if let Some(id) = row_sync::take_scroll(ui.ctx(), RowView::Chart) {
+                // Only if off-screen; leave room for the pinned header.
+                if let Some((_, rect)) = task_positions.get(&id) {
+                    ui.scroll_to_rect(rect.expand2(Vec2::new(40.0, hh)), None);
+                }
+            }
+            if let Some(id) = ui.ctx().data_mut(|d| d.remove_temp::<Uuid>(Id::new(REVEAL_ID))) {
+                if let Some((_, rect)) = task_positions.get(&id) {
+                    ui.scroll_to_rect(rect.expand(40.0), Some(egui::Align::Center));
+                }
+            }
+            if let Some((_, rect)) = zoom.and_then(|(_, _, row)| task_positions.get(&row)) {
+                // Only vertically: the zoom already framed the dates.
+                let visible = ui.clip_rect();
+                let target = Rect::from_x_y_ranges(visible.x_range(), rect.y_range());
+                ui.scroll_to_rect(target.expand2(Vec2::new(0.0, 40.0)), None);
+            }
+            if ui.ctx().data_mut(|d| d.remove_temp::<bool>(Id::new(TODAY_ID))).is_some() {
+                // Keep the vertical position: target the rows currently in view.
+                let x = origin.x + viewport.date_to_x(options.clock.now());
+                let visible = ui.clip_rect();
+                let target = Rect::from_x_y_ranges(x - 40.0..=x + 40.0, visible.y_range());
+                ui.scroll_to_rect(target, Some(egui::Align::Center));
+            }
+
             // ── Draw dependency arrows (BEHIND bars) ─────────────────
+            let violated: std::collections::HashSet<(Uuid, Uuid)> =
+                options.violations.iter().map(|v| (v.dependency.from_task, v.dependency.to_task)).collect();
             for dep in dependencies {
                 if let (Some(&(_, from_rect)), Some(&(_, to_rect))) =
                     (task_positions.get(&dep.from_task), task_positions.get(&dep.to_task))
                 {
-                    let (start_pt, end_pt) = dependency_endpoints(from_rect, to_rect, dep.kind);
-                    draw_dependency_arrow(&painter, start_pt, end_pt, dep.kind, with_alpha(theme::dep_arrow(), 180), 1.4);
+                    let route = link_route(from_rect, to_rect, dep.kind, dep.lag_days);
+                    match trace.map(|t| t.link_side(dep)) {
+                        Some(Some(side)) => draw_dependency_arrow(&painter, &route, dep.kind, trace_color(side), 2.0),
+                        Some(None) => draw_dependency_arrow(&painter, &route, dep.kind, with_alpha(theme::dep_arrow(), 50), 1.2),
+                        None if violated.contains(&(dep.from_task, dep.to_task)) => {
+                            draw_dependency_arrow(&painter, &route, dep.kind, violation_color(), 1.6)
+                        }
+                        None => draw_dependency_arrow(&painter, &route, dep.kind, with_alpha(theme::dep_arrow(), 180), 1.4),
+                    }
+                }
+            }
+            if let Some(dep) = options
+                .highlight_link
+                .and_then(|(from, to)| dependencies.iter().find(|d| d.from_task == from && d.to_task == to))
+            {
+                if let (Some(&(_, from_rect)), Some(&(_, to_rect))) =
+                    (task_positions.get(&dep.from_task), task_positions.get(&dep.to_task))
+                {
+                    let route = link_route(from_rect, to_rect, dep.kind, dep.lag_days);
+                    draw_dependency_arrow(&painter, &route, dep.kind, theme::accent(), 3.0);
                 }
             }
 
             let mut hovered_task: Option<Uuid> = None;
+            let mut raise_milestone_label = true;
 
             // Draw task bars — iterate only visible rows.
-            let vis_count = visible_rows.len();
             for (vis_i, &task_i) in visible_rows.iter().enumerate() {
                 // Safety: split borrow so we can read siblings while mutating task.
                 let task_id = tasks[task_i].id;
                 let task_parent_id = tasks[task_i].parent_id;
                 let is_parent_task = tasks[task_i].has_children(tasks);
 
-                let y = *animated_row_y.get(&task_id).unwrap_or(
-                    &(origin.y + hh + vis_i as f32 * (row_height + row_padding) + row_padding),
-                );
-                let is_selected = *selected_task == Some(task_id);
+                let y = *animated_row_y.get(&task_id).unwrap_or(&(origin.y + hh + rows.bar_top(vis_i)));
+                let is_selected = *selected_task == Some(task_id) || selection.contains(&task_id);
+
+                let mut painter = painter.clone();
+                if options.completed == CompletedTasks::Strike && tasks[task_i].is_complete() {
+                    painter.set_opacity(COMPLETED_OPACITY);
+                }
 
                 if is_parent_task {
                     // ── Summary / parent bar ─────────────────────────
@@ -299,8 +611,17 @@ pub fn show_gantt_chart(
                     );
                     if summary_resp.clicked() {
                         *selected_task = Some(task_id);
+                        row_sync::scroll_to(ui.ctx(), RowView::List, task_id);
                         consumed_click = true;
                     }
+                    if summary_resp.double_clicked() {
+                        inline_rename::begin(ui.ctx(), task_id, &task.name, RenameSurface::Chart);
+                    }
+                    let label_rect = Rect::from_min_size(
+                        Pos2::new(summary_rect.right() + 2.0, y + theme::bar_inset()),
+                        Vec2::new(RENAME_FIELD_WIDTH, row_height - theme::bar_inset() * 2.0),
+                    );
+                    show_rename_field(ui, task_id, label_rect, &mut interaction);
                     if summary_resp.secondary_clicked() {
                         let open_pos = ui.input(|i| i.pointer.interact_pos().unwrap_or(summary_rect.center()));
                         ui.ctx().data_mut(|d| d.insert_temp(Id::new(("ctx-menu", task_id)), open_pos));
@@ -318,6 +639,10 @@ pub fn show_gantt_chart(
                                         interaction.add_subtask = Some(task_id);
                                         close_menu = true;
                                     }
+                                    if let Some(side) = insert_menu_items(ui) {
+                                        interaction.insert_task = Some((task_id, side));
+                                        close_menu = true;
+                                    }
                                     if ui.button(egui_phosphor::regular::TRASH.to_string() + "  Delete Group").clicked() {
                                         interaction.delete_task = Some(task_id);
                                         close_menu = true;
@@ -340,8 +665,8 @@ pub fn show_gantt_chart(
                                 ui.strong(&task.name);
                                 ui.label(format!(
                                     "{} → {}",
-                                    task.start.format("%d/%m/%Y %H:%M"),
-                                    task.end.format("%d/%m/%Y %H:%M"),
+                                    options.clock.to_local(task.start).format("%d/%m/%Y %H:%M"),
+                                    options.clock.to_local(task.end).format("%d/%m/%Y %H:%M"),
                                 ));
                                 ui.label(format!("Progress: {}%", (task.progress * 100.0) as i32));
                                 ui.label(egui::RichText::new("Right-click for options").size(9.0).color(theme::text_dim()));
@@ -350,17 +675,36 @@ pub fn show_gantt_chart(
                     }
                 } else if tasks[task_i].is_milestone {
                     let task = &mut tasks[task_i];
-                    let task_rect = draw_milestone(&painter, origin, viewport, task, y, row_height, is_selected);
+                    let shaken = origin + Vec2::new(shake_offset(ui.ctx(), task.id), 0.0);
+                    let style = BarStyle {
+                        selected: is_selected,
+                        schedule_tint: false,
+                        now: options.clock.now(),
+                        labels: options.labels,
+                        label_raised: raise_milestone_label,
+                        highlight: None,
+                    };
+                    raise_milestone_label = !raise_milestone_label;
+                    let task_rect = draw_milestone(&painter, shaken, viewport, task, y, row_height, style);
                     let response = ui.interact(
-                        task_rect.expand(6.0),
+                        milestone_hit_rect(task_rect, y, row_height),
                         ui.make_persistent_id(("milestone", task.id)),
                         Sense::click_and_drag(),
                     );
 
                     if response.clicked() {
                         *selected_task = Some(task.id);
+                        row_sync::scroll_to(ui.ctx(), RowView::List, task.id);
                         consumed_click = true;
                     }
+                    if response.double_clicked() {
+                        inline_rename::begin(ui.ctx(), task.id, &task.name, RenameSurface::Chart);
+                    }
+                    let label_rect = Rect::from_min_size(
+                        Pos2::new(task_rect.right() + 4.0, y + theme::bar_inset()),
+                        Vec2::new(RENAME_FIELD_WIDTH, row_height - theme::bar_inset() * 2.0),
+                    );
+                    show_rename_field(ui, task.id, label_rect, &mut interaction);
                     // Right-click context menu for milestones
                     if response.secondary_clicked() {
                         let open_pos = ui.input(|i| i.pointer.interact_pos().unwrap_or(task_rect.center()));
@@ -371,6 +715,8 @@ pub fn show_gantt_chart(
                         let mut close_menu = false;
                         let tid = task.id;
                         let is_child = task_parent_id.is_some();
+                        let locked = task.locked;
+                        let tentative = task.tentative;
                         egui::Area::new(Id::new(("ctx-area", tid)))
                             .fixed_pos(open_pos)
                             .order(egui::Order::Foreground)
@@ -382,6 +728,18 @@ pub fn show_gantt_chart(
                                             close_menu = true;
                                         }
                                     }
+                                    if let Some(side) = insert_menu_items(ui) {
+                                        interaction.insert_task = Some((tid, side));
+                                        close_menu = true;
+                                    }
+                                    if lock_menu_item(ui, locked) {
+                                        interaction.toggle_lock = Some(tid);
+                                        close_menu = true;
+                                    }
+                                    if tentative_menu_item(ui, tentative) {
+                                        interaction.toggle_tentative = Some(tid);
+                                        close_menu = true;
+                                    }
                                     if ui.button(egui_phosphor::regular::TRASH.to_string() + "  Delete Task").clicked() {
                                         interaction.delete_task = Some(tid);
                                         close_menu = true;
@@ -417,28 +775,28 @@ pub fn show_gantt_chart(
                         if let Some(snapshot) = snapshot {
                             let delta_x = ptr.x - snapshot.start_pointer_x;
                             let delta_y = ptr.y - snapshot.start_pointer_y;
-                            let is_reorder_drag =
-                                delta_y.abs() > row_height * 0.45 && delta_y.abs() > delta_x.abs();
+                            // Grouped rows aren't in stored order, so they can't be reordered.
+                            let is_reorder_drag = !grouped
+                                && delta_y.abs() > row_height * 0.45
+                                && delta_y.abs() > delta_x.abs();
 
                             if is_reorder_drag {
-                                if let Some(target_vis) = row_index_from_pointer_y(
-                                    ptr.y,
-                                    origin,
-                                    row_height,
-                                    row_padding,
-                                    vis_count,
-                                ) {
+                                if let Some(target_vis) = row_index_from_pointer_y(ptr.y, origin.y + hh, &rows) {
                                     reorder_preview_target = Some(target_vis);
                                     if target_vis != vis_i {
                                         let target_task_i = visible_rows[target_vis];
                                         reorder_request = Some((task_i, target_task_i));
                                     }
                                 }
+                            } else if task.locked {
+                                if delta_x.abs() > LOCKED_DRAG_SLOP {
+                                    refuse_locked_drag(ui.ctx(), task.id, &mut interaction);
+                                }
                             } else {
                                 let duration_delta = drag_duration(delta_x, viewport);
-                                task.start = snapshot.start + duration_delta;
-                                task.end = task.start;
+                                task.set_single_date(snapshot.start + duration_delta);
                                 interaction.changed = true;
+                                interaction.moved_task = Some(task.id);
                                 *selected_task = Some(task.id);
                             }
                         }
@@ -447,6 +805,7 @@ pub fn show_gantt_chart(
                     if response.drag_stopped() {
                         ui.ctx().data_mut(|data| {
                             data.remove::<DragSnapshot>(drag_id(task.id, "milestone"));
+                            data.remove::<bool>(locked_drag_id(task.id));
                         });
                     }
 
@@ -465,20 +824,37 @@ pub fn show_gantt_chart(
                     }
                 } else {
                     let task = &mut tasks[task_i];
-                    let bar_rect = draw_task_bar(&painter, origin, viewport, task, y, row_height, is_selected);
+                    let shaken = origin + Vec2::new(shake_offset(ui.ctx(), task.id), 0.0);
+                    let style = BarStyle {
+                        selected: is_selected,
+                        schedule_tint: options.schedule_tint,
+                        now: options.clock.now(),
+                        labels: options.labels,
+                        label_raised: false,
+                        highlight: options.highlights.get(&task.id).copied(),
+                    };
+                    let bar_rect = draw_task_bar(&painter, shaken, viewport, task, y, row_height, style);
 
+                    let hit_rect = bar_hit_rect(bar_rect);
                     let bar_response = ui.interact(
-                        bar_rect,
+                        hit_rect,
                         ui.make_persistent_id(("task-bar", task.id)),
                         Sense::click_and_drag(),
                     );
+                    // A short bar's handles sit outside its grab area, or they
+                    // would cover it and leave nothing to move the bar by.
+                    let (left_x, right_x) = if bar_rect.width() < MIN_HIT_WIDTH {
+                        (hit_rect.left() - handle_width * 0.5, hit_rect.right() + handle_width * 0.5)
+                    } else {
+                        (bar_rect.left(), bar_rect.right())
+                    };
                     let left_handle_rect = Rect::from_min_max(
-                        Pos2::new(bar_rect.left() - handle_width * 0.5, bar_rect.top()),
-                        Pos2::new(bar_rect.left() + handle_width * 0.5, bar_rect.bottom()),
+                        Pos2::new(left_x - handle_width * 0.5, bar_rect.top()),
+                        Pos2::new(left_x + handle_width * 0.5, bar_rect.bottom()),
                     );
                     let right_handle_rect = Rect::from_min_max(
-                        Pos2::new(bar_rect.right() - handle_width * 0.5, bar_rect.top()),
-                        Pos2::new(bar_rect.right() + handle_width * 0.5, bar_rect.bottom()),
+                        Pos2::new(right_x - handle_width * 0.5, bar_rect.top()),
+                        Pos2::new(right_x + handle_width * 0.5, bar_rect.bottom()),
                     );
 
                     let left_response = ui.interact(
@@ -494,8 +870,17 @@ pub fn show_gantt_chart(
 
                     if bar_response.clicked() {
                         *selected_task = Some(task.id);
+                        row_sync::scroll_to(ui.ctx(), RowView::List, task.id);
                         consumed_click = true;
                     }
+                    if bar_response.double_clicked() {
+                        inline_rename::begin(ui.ctx(), task.id, &task.name, RenameSurface::Chart);
+                    }
+                    let label_rect = Rect::from_min_size(
+                        bar_rect.min,
+                        Vec2::new(bar_rect.width().max(RENAME_FIELD_WIDTH), bar_rect.height()),
+                    );
+                    show_rename_field(ui, task.id, label_rect, &mut interaction);
 
                     // Right-click context menu for regular tasks
                     if bar_response.secondary_clicked() {
@@ -507,6 +892,8 @@ pub fn show_gantt_chart(
                         let mut close_menu = false;
                         let tid = task.id;
                         let is_child = task_parent_id.is_some();
+                        let locked = task.locked;
+                        let tentative = task.tentative;
                         egui::Area::new(Id::new(("ctx-area", tid)))
                             .fixed_pos(open_pos)
                             .order(egui::Order::Foreground)
@@ -518,6 +905,18 @@ pub fn show_gantt_chart(
                                             close_menu = true;
                                         }
                                     }
+                                    if let Some(side) = insert_menu_items(ui) {
+                                        interaction.insert_task = Some((tid, side));
+                                        close_menu = true;
+                                    }
+                                    if lock_menu_item(ui, locked) {
+                                        interaction.toggle_lock = Some(tid);
+                                        close_menu = true;
+                                    }
+                                    if tentative_menu_item(ui, tentative) {
+                                        interaction.toggle_tentative = Some(tid);
+                                        close_menu = true;
+                                    }
                                     if ui.button(egui_phosphor::regular::TRASH.to_string() + "  Delete Task").clicked() {
                                         interaction.delete_task = Some(tid);
                                         close_menu = true;
@@ -583,13 +982,15 @@ pub fn show_gantt_chart(
                         let snapshot = ui
                             .ctx()
                             .data_mut(|data| data.get_persisted::<DragSnapshot>(drag_id(task.id, "left")));
-                        if let Some(snapshot) = snapshot {
+                        if task.locked {
+                            refuse_locked_drag(ui.ctx(), task.id, &mut interaction);
+                        } else if let Some(snapshot) = snapshot {
                             let total_delta_x = ptr_x - snapshot.start_pointer_x;
                             let duration_delta = drag_duration(total_delta_x, viewport);
                             let new_start = snapshot.start + duration_delta;
-                            task.start = new_start.min(snapshot.end);
-                            task.end = snapshot.end.max(task.start);
+                            task.set_dates(new_start, snapshot.end, resize_min_span(&snapshot, viewport), FixedEdge::End);
                             interaction.changed = true;
+                            interaction.moved_task = Some(task.id);
                         }
                     } else if right_response.dragged() && !shift_held {
                         ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
@@ -597,12 +998,15 @@ pub fn show_gantt_chart(
                         let snapshot = ui
                             .ctx()
                             .data_mut(|data| data.get_persisted::<DragSnapshot>(drag_id(task.id, "right")));
-                        if let Some(snapshot) = snapshot {
+                        if task.locked {
+                            refuse_locked_drag(ui.ctx(), task.id, &mut interaction);
+                        } else if let Some(snapshot) = snapshot {
                             let total_delta_x = ptr_x - snapshot.start_pointer_x;
                             let duration_delta = drag_duration(total_delta_x, viewport);
                             let new_end = snapshot.end + duration_delta;
-                            task.end = new_end.max(snapshot.start);
+                            task.set_dates(snapshot.start, new_end, resize_min_span(&snapshot, viewport), FixedEdge::Start);
                             interaction.changed = true;
+                            interaction.moved_task = Some(task.id);
                         }
                     } else if bar_response.dragged() && !shift_held {
                         ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
@@ -613,28 +1017,33 @@ pub fn show_gantt_chart(
                         if let Some(snapshot) = snapshot {
                             let delta_x = ptr.x - snapshot.start_pointer_x;
                             let delta_y = ptr.y - snapshot.start_pointer_y;
-                            let is_reorder_drag =
-                                delta_y.abs() > row_height * 0.45 && delta_y.abs() > delta_x.abs();
+                            // Grouped rows aren't in stored order, so they can't be reordered.
+                            let is_reorder_drag = !grouped
+                                && delta_y.abs() > row_height * 0.45
+                                && delta_y.abs() > delta_x.abs();
 
                             if is_reorder_drag {
-                                if let Some(target_vis) = row_index_from_pointer_y(
-                                    ptr.y,
-                                    origin,
-                                    row_height,
-                                    row_padding,
-                                    vis_count,
-                                ) {
+                                if let Some(target_vis) = row_index_from_pointer_y(ptr.y, origin.y + hh, &rows) {
                                     reorder_preview_target = Some(target_vis);
                                     if target_vis != vis_i {
                                         let target_task_i = visible_rows[target_vis];
                                         reorder_request = Some((task_i, target_task_i));
                                     }
                                 }
+                            } else if task.locked {
+                                if delta_x.abs() > LOCKED_DRAG_SLOP {
+                                    refuse_locked_drag(ui.ctx(), task.id, &mut interaction);
+                                }
                             } else {
                                 let duration_delta = drag_duration(delta_x, viewport);
-                                task.start = snapshot.start + duration_delta;
-                                task.end = snapshot.end + duration_delta;
+                                task.set_dates(
+                                    snapshot.start + duration_delta,
+                                    snapshot.end + duration_delta,
+                                    chrono::Duration::zero(),
+                                    FixedEdge::Start,
+                                );
                                 interaction.changed = true;
+                                interaction.moved_task = Some(task.id);
                             }
                         }
                     }
@@ -654,9 +1063,16 @@ pub fn show_gantt_chart(
                             data.remove::<DragSnapshot>(drag_id(task.id, "move"));
                         });
                     }
+                    if bar_response.drag_stopped() || left_response.drag_stopped() || right_response.drag_stopped() {
+                        ui.ctx().data_mut(|data| data.remove::<bool>(locked_drag_id(task.id)));
+                    }
 
                     // Handle affordances
-                    if is_selected || left_response.hovered() || right_response.hovered() {
+                    if task.locked {
+                        if left_response.hovered() || right_response.hovered() {
+                            ui.ctx().set_cursor_icon(egui::CursorIcon::NotAllowed);
+                        }
+                    } else if is_selected || left_response.hovered() || right_response.hovered() {
                         if left_response.hovered() || right_response.hovered() {
                             ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
                         } else if bar_response.hovered() {
@@ -677,6 +1093,22 @@ pub fn show_gantt_chart(
                         painter.rect_filled(rh, Rounding::same(2.0), theme::handle_color());
                     }
 
+                    // Assignee away: a thin strip along the bottom of the bar
+                    // under the days they're out.
+                    let away: Vec<&Absence> = absences_overlapping(task, options.absences).collect();
+                    for absence in &away {
+                        let (start, end) = absence.span();
+                        let x0 = (shaken.x + viewport.datetime_to_x(start)).max(bar_rect.left());
+                        let x1 = (shaken.x + viewport.datetime_to_x(end)).min(bar_rect.right());
+                        if x0 < x1 {
+                            painter.rect_filled(
+                                Rect::from_x_y_ranges(x0..=x1, bar_rect.bottom() - ABSENCE_MARKER_HEIGHT..=bar_rect.bottom()),
+                                0.0,
+                                ABSENCE_MARKER_COLOR,
+                            );
+                        }
+                    }
+
                     // Tooltip on hover
                     if bar_response.hovered() || left_response.hovered() || right_response.hovered() {
                         hovered_task = Some(task.id);
@@ -688,22 +1120,100 @@ pub fn show_gantt_chart(
                                 ui.strong(&task.name);
                                 ui.label(format!(
                                     "{} → {}",
-                                    task.start.format("%d/%m/%Y %H:%M"),
-                                    task.end.format("%d/%m/%Y %H:%M"),
+                                    options.clock.to_local(task.start).format("%d/%m/%Y %H:%M"),
+                                    options.clock.to_local(task.end).format("%d/%m/%Y %H:%M"),
                                 ));
+                                ui.label(format!("Length: {}", length_label(task)));
+                                if viewport.date_to_x(task.end) - viewport.date_to_x(task.start) < MIN_BAR_WIDTH {
+                                    ui.label(
+                                        egui::RichText::new("Drawn wider than its length at this zoom")
+                                            .size(9.0)
+                                            .color(theme::text_dim()),
+                                    );
+                                }
                                 ui.label(format!("Progress: {}%", (task.progress * 100.0) as i32));
+                                let now = options.clock.now();
+                                if task.start < now && task.progress < 1.0 {
+                                    let status = task.schedule_status(now);
+                                    ui.label(
+                                        egui::RichText::new(format!(
+                                            "Expected: {:.0}% · {}",
+                                            task.expected_progress(now) * 100.0,
+                                            status.label()
+                                        ))
+                                        .color(status.status_color()),
+                                    );
+                                }
+                                if let Some(highlight) = options.highlights.get(&task.id) {
+                                    ui.label(egui::RichText::new(highlight.label()).color(highlight.status_color()));
+                                }
+                                for absence in &away {
+                                    ui.label(
+                                        egui::RichText::new(format!(
+                                            "{} away: {}",
+                                            absence.assignee.trim(),
+                                            absence.describe()
+                                        ))
+                                        .color(ABSENCE_MARKER_COLOR),
+                                    );
+                                }
                             },
                         );
                     }
                 }
             }
 
+            // Dependency trace: fade everything off the chains, outline the chains.
+            if let Some(trace) = trace {
+                let bg = theme::bg_dark();
+                let fade = Color32::from_rgba_unmultiplied(bg.r(), bg.g(), bg.b(), 170);
+                for (&id, &(_, rect)) in &task_positions {
+                    if !trace.contains(id) {
+                        painter.rect_filled(rect.expand(2.0), Rounding::same(theme::bar_rounding()), fade);
+                    } else if let Some(side) = trace.side_of(id) {
+                        painter.rect_stroke(
+                            rect.expand(2.0),
+                            Rounding::same(theme::bar_rounding() + 2.0),
+                            Stroke::new(1.5, trace_color(side)),
+                        );
+                    }
+                }
+            }
+
+            // Broken links: a warning left of each successor, listing on hover
+            // what it breaks.
+            let mut broken: std::collections::HashMap<Uuid, Vec<&DependencyViolation>> = std::collections::HashMap::new();
+            for violation in options.violations {
+                broken.entry(violation.dependency.to_task).or_default().push(violation);
+            }
+            for (id, violations) in &broken {
+                let Some(&(_, rect)) = task_positions.get(id) else {
+                    continue;
+                };
+                // Parents have their collapse toggle there already.
+                let offset = if tasks.iter().any(|t| t.parent_id == Some(*id)) { 22.0 } else { 4.0 };
+                let glyph = painter.text(
+                    Pos2::new(rect.left() - offset, rect.center().y),
+                    egui::Align2::RIGHT_CENTER,
+                    egui_phosphor::regular::WARNING,
+                    egui::FontId::proportional(12.0),
+                    violation_color(),
+                );
+                ui.interact(glyph, Id::new(("violation-glyph", id)), Sense::hover()).on_hover_ui(|ui| {
+                    for violation in violations {
+                        let from = violation.dependency.from_task;
+                        let name = tasks.iter().find(|t| t.id == from).map_or("", |t| t.name.as_str());
+                        ui.label(egui::RichText::new(violation.describe(name)).color(violation_color()));
+                    }
+                });
+            }
+
             // Visual drop target while dragging tasks vertically to reorder.
             if let Some(target_vis) = reorder_preview_target {
-                let y = origin.y + hh + target_vis as f32 * (row_height + row_padding);
+                let y = origin.y + hh + rows.top(target_vis);
                 let row_rect = Rect::from_min_size(
                     Pos2::new(origin.x, y),
-                    Vec2::new(chart_width, row_height + row_padding),
+                    Vec2::new(chart_width, rows.span(target_vis)),
                 );
                 let ba = theme::border_accent();
                 painter.rect_filled(
@@ -726,47 +1236,30 @@ pub fn show_gantt_chart(
                 interaction.changed = true;
             }
 
-            // Draw today marker in header (no full-height line through tasks)
-            draw_today_line(&painter, origin, viewport);
-
-
-
-            // Sticky header overlay when vertically scrolled past the content header.
-            let clip_rect = ui.clip_rect();
-            if origin.y < clip_rect.top() {
-                let sticky_origin = Pos2::new(origin.x, clip_rect.top());
-                draw_timeline_header(
-                    &painter,
-                    sticky_origin,
-                    viewport,
-                    chart_width,
-                    sticky_origin.y + hh,
-                );
-                draw_today_line(&painter, sticky_origin, viewport);
-
-                // Soft shadow under pinned header for separation.
-                let r = theme::rendering();
-                let shadow_rect = Rect::from_min_size(
-                    Pos2::new(sticky_origin.x, sticky_origin.y + hh),
-                    Vec2::new(chart_width, r.sticky_shadow_height),
-                );
-                painter.rect_filled(
-                    shadow_rect,
-                    0.0,
-                    Color32::from_rgba_premultiplied(0, 0, 0, r.sticky_shadow_alpha),
-                );
+            // Share the hovered row with the task list: the bar under the
+            // pointer, or else whichever row it is over.
+            let pointer_row = response
+                .hover_pos()
+                .or_else(|| ui.input(|i| i.pointer.hover_pos()).filter(|p| ui.clip_rect().contains(*p)))
+                .filter(|p| p.y > origin.y.max(ui.clip_rect().top()) + hh)
+                .and_then(|p| rows.row_at(p.y - origin.y - hh))
+                .and_then(|row| visible_rows.get(row))
+                .map(|&i| tasks[i].id);
+            if let Some(id) = hovered_task.or(pointer_row) {
+                row_sync::hover(ui.ctx(), id);
             }
 
             // Add arrow interaction + focus mode for dependencies
-            let focus_task = hovered_task.or(*selected_task);
+            // While tracing, the trace already shows the selected task's links.
+            let focus_task = hovered_task.or(if trace.is_some() { None } else { *selected_task });
             let pointer_pos = ui.input(|i| i.pointer.hover_pos());
 
             for dep in dependencies {
                 if let (Some(&(_, from_rect)), Some(&(_, to_rect))) =
                     (task_positions.get(&dep.from_task), task_positions.get(&dep.to_task))
                 {
-                    let (start_pt, end_pt) = dependency_endpoints(from_rect, to_rect, dep.kind);
-                    let route = dependency_route_points(start_pt, end_pt, dep.kind);
+                    let link = link_route(from_rect, to_rect, dep.kind, dep.lag_days);
+                    let route = &link.points;
 
                     let is_related = focus_task
                         .map(|task_id| dep.from_task == task_id || dep.to_task == task_id)
@@ -776,7 +1269,8 @@ pub fn show_gantt_chart(
                     if focus_task.is_some() && is_related {
                         draw_dependency_polyline(
                             &painter,
-                            &route,
+                            route,
+                            dep.kind.line_style().dash_pattern(),
                             theme::dep_arrow_hover(),
                             1.8,
                             3.0,
@@ -789,13 +1283,14 @@ pub fn show_gantt_chart(
                     }
 
                     let is_hovered_arrow = pointer_pos
-                        .map(|p| is_point_near_polyline(p, &route, 6.0))
+                        .map(|p| is_point_near_polyline(p, route, 6.0))
                         .unwrap_or(false);
 
                     if is_hovered_arrow {
                         draw_dependency_polyline(
                             &painter,
-                            &route,
+                            route,
+                            dep.kind.line_style().dash_pattern(),
                             theme::dep_arrow_hover(),
                             2.2,
                             3.0,
@@ -808,7 +1303,7 @@ pub fn show_gantt_chart(
 
                         let dep_hit = ui.interact(
                             Rect::from_center_size(
-                                pointer_pos.unwrap_or(start_pt),
+                                pointer_pos.unwrap_or(route[0]),
                                 Vec2::splat(16.0),
                             ),
                             ui.make_persistent_id(("dep-arrow", dep.from_task, dep.to_task)),
@@ -834,6 +1329,19 @@ pub fn show_gantt_chart(
                                     .map(|t| t.name.as_str())
                                     .unwrap_or("?");
                                 ui.label(format!("{} → {}", from_name, to_name));
+                                ui.label(
+                                    egui::RichText::new(dep.kind.description())
+                                        .size(11.0)
+                                        .color(theme::text_secondary()),
+                                );
+                                if dep.lag_days != 0 {
+                                    let what = if dep.lag_days > 0 { "Lag" } else { "Lead" };
+                                    ui.label(
+                                        egui::RichText::new(format!("{}: {}", what, lag_label(dep.lag_days)))
+                                            .size(11.0)
+                                            .color(theme::text_secondary()),
+                                    );
+                                }
                                 ui.label(
                                     egui::RichText::new("Right-click to remove")
                                         .size(10.0)
@@ -845,11 +1353,149 @@ pub fn show_gantt_chart(
                 }
             }
 
+            // ── Milestone from the timeline header ───────────────────
+            // Alt+click a date adds a milestone there; right-click offers
+            // the phases running on that date as parents.
+            // The header (and the milestone lane under it) stays at the top
+            // of the view and takes the pointer there, so rows scrolled
+            // beneath it can't be clicked or dragged through it.
+            let clip_rect = ui.clip_rect();
+            let header_top = origin.y.max(clip_rect.top());
+            let frozen = Rect::from_min_size(Pos2::new(clip_rect.left(), header_top), Vec2::new(clip_rect.width(), hh));
+            let frozen_response = ui.interact(frozen, Id::new("chart-frozen-header"), Sense::click_and_drag());
+            handle_ctrl_scroll_zoom(ui, &frozen_response, viewport);
+            let header_x = frozen_response
+                .interact_pointer_pos()
+                .filter(|p| p.y <= header_top + header_height())
+                .map(|p| p.x - origin.x);
+            let header_menu_id = Id::new("chart-header-menu");
+            if let Some(x) = header_x {
+                if frozen_response.clicked() && ui.input(|i| i.modifiers.alt) {
+                    let (at, _) = drawn_span(x, x, viewport, options.task_defaults);
+                    interaction.create_task = Some(DrawnTask {
+                        start: at,
+                        end: at,
+                        parent: default_phase(tasks, *selected_task, at),
+                        milestone: true,
+                    });
+                } else if frozen_response.secondary_clicked() {
+                    let (at, _) = drawn_span(x, x, viewport, options.task_defaults);
+                    let open_pos = frozen_response.interact_pointer_pos().unwrap_or(origin);
+                    ui.ctx().data_mut(|d| d.insert_temp(header_menu_id, (open_pos, at)));
+                }
+            }
+            let header_menu: Option<(Pos2, NaiveDateTime)> = ui.ctx().data(|d| d.get_temp(header_menu_id));
+            if let Some((open_pos, at)) = header_menu {
+                let mut close_menu = false;
+                let phases = phases_at(tasks, at);
+                let default = default_phase(tasks, *selected_task, at);
+                egui::Area::new(Id::new("chart-header-menu-area"))
+                    .fixed_pos(open_pos)
+                    .order(egui::Order::Foreground)
+                    .show(ui.ctx(), |ui| {
+                        egui::Frame::popup(ui.style()).show(ui, |ui| {
+                            let day = format_datetime(at, "date.month_day");
+                            let mut add = |ui: &mut Ui, label: String, parent: Option<Uuid>| {
+                                if ui.button(format!("{}  {}", egui_phosphor::regular::DIAMOND, label)).clicked() {
+                                    interaction.create_task =
+                                        Some(DrawnTask { start: at, end: at, parent, milestone: true });
+                                    close_menu = true;
+                                }
+                            };
+                            add(ui, format!("Add Milestone on {}", day), default);
+                            if !phases.is_empty() {
+                                ui.separator();
+                            }
+                            for phase in &phases {
+                                if Some(phase.id) != default {
+                                    add(ui, format!("Add Milestone in '{}'", phase.name), Some(phase.id));
+                                }
+                            }
+                        });
+                    });
+                if close_menu || ui.input(|i| i.pointer.secondary_pressed() || i.key_pressed(egui::Key::Escape)) {
+                    ui.ctx().data_mut(|d| d.remove::<(Pos2, NaiveDateTime)>(header_menu_id));
+                }
+            }
+
             // Empty click on background clears selection
             if response.clicked() && !consumed_click {
                 *selected_task = None;
             }
 
+            // ── Draw a new task on empty space ───────────────────────
+            let create_id = Id::new("chart-create-drag");
+            let row_of = |y: f32| rows.row_at(y - origin.y - hh);
+            let row_parent = |row: usize| {
+                visible_rows.get(row).and_then(|&i| {
+                    let task = &tasks[i];
+                    if task.has_children(tasks) { Some(task.id) } else { task.parent_id }
+                })
+            };
+            if response.drag_started() && !shift_held {
+                if let Some(ptr) = response.interact_pointer_pos() {
+                    if let Some(row) = row_of(ptr.y) {
+                        let drag = CreateDrag { anchor_x: ptr.x - origin.x, row };
+                        ui.ctx().data_mut(|d| d.insert_temp(create_id, drag));
+                    }
+                }
+            }
+            let create_drag: Option<CreateDrag> = ui.ctx().data(|d| d.get_temp(create_id));
+            if let Some(drag) = create_drag {
+                let pointer_x = response.interact_pointer_pos().map_or(drag.anchor_x, |p| p.x - origin.x);
+                let (start, end) = drawn_span(drag.anchor_x, pointer_x, viewport, options.task_defaults);
+                if response.dragged() {
+                    let y = origin.y + hh + rows.bar_top(drag.row);
+                    let inset = theme::bar_inset();
+                    let ghost = Rect::from_min_max(
+                        Pos2::new(origin.x + viewport.date_to_x(start), y + inset),
+                        Pos2::new(origin.x + viewport.date_to_x(end), y + row_height - inset),
+                    );
+                    let accent = theme::accent();
+                    painter.rect_filled(
+                        ghost,
+                        Rounding::same(theme::bar_rounding()),
+                        Color32::from_rgba_unmultiplied(accent.r(), accent.g(), accent.b(), 90),
+                    );
+                    painter.rect_stroke(ghost, Rounding::same(theme::bar_rounding()), Stroke::new(1.5, accent));
+                    painter.text(
+                        Pos2::new(ghost.right() + 6.0, ghost.center().y),
+                        egui::Align2::LEFT_CENTER,
+                        format!(
+                            "{} → {}  ({}d)",
+                            format_datetime(start, "date.month_day"),
+                            format_datetime(end, "date.month_day"),
+                            (end.date() - start.date()).num_days() + 1
+                        ),
+                        theme::font_bar(),
+                        theme::text_secondary(),
+                    );
+                    ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
+                }
+                if response.drag_stopped() {
+                    ui.ctx().data_mut(|d| d.remove::<CreateDrag>(create_id));
+                    if (pointer_x - drag.anchor_x).abs() > 4.0 {
+                        interaction.create_task =
+                            Some(DrawnTask { start, end, parent: row_parent(drag.row), milestone: false });
+                    }
+                }
+            }
+            if response.double_clicked() && !consumed_click {
+                if let Some(ptr) = response.interact_pointer_pos() {
+                    if let Some(row) = row_of(ptr.y) {
+                        let x = ptr.x - origin.x;
+                        let milestone = ui.input(|i| i.modifiers.alt);
+                        let (start, end) = drawn_span(x, x, viewport, options.task_defaults);
+                        interaction.create_task = Some(DrawnTask {
+                            start,
+                            end: if milestone { start } else { end },
+                            parent: row_parent(row),
+                            milestone,
+                        });
+                    }
+                }
+            }
+
             // ── Shift+Drag link creation ─────────────────────────────
             let link_id = Id::new("dep-link-drag");
             let pointer_pos = ui.input(|i| i.pointer.interact_pos());
@@ -857,74 +1503,353 @@ pub fn show_gantt_chart(
             let primary_down = ui.input(|i| i.pointer.primary_down());
             let primary_released = ui.input(|i| i.pointer.primary_released());
 
+            let pointer_bar = pointer_pos.and_then(|ptr| {
+                tasks.iter().find_map(|t| {
+                    let &(_, rect) = task_positions.get(&t.id)?;
+                    rect.contains(ptr).then_some((t.id, rect))
+                })
+            });
+
             // Check if user started Shift+clicking on a bar
             if shift_held && primary_pressed {
-                if let Some(ptr) = pointer_pos {
-                    // Find which bar the pointer is over
-                    for task in tasks.iter() {
-                        if let Some(&(_, rect)) = task_positions.get(&task.id) {
-                            if rect.contains(ptr) {
-                                let state = LinkDragState {
-                                    from_task: task.id,
-                                    from_point: Pos2::new(rect.left(), rect.center().y),
-                                };
-                                ui.ctx().data_mut(|d| d.insert_temp(link_id, state));
-                                break;
-                            }
-                        }
-                    }
+                if let Some((from_task, from_rect)) = pointer_bar {
+                    ui.ctx().data_mut(|d| d.insert_temp(link_id, LinkDragState { from_task, from_rect }));
                 }
             }
 
-            // Draw the in-progress link line
+            // Draw the in-progress link, outlining the bar under the pointer
+            // and tinting it red if it can't be linked to: the source bar
+            // itself, a task already linked, or one that would close a cycle.
             let link_state: Option<LinkDragState> =
                 ui.ctx().data_mut(|d| d.get_temp(link_id));
+            let link_kind = options.task_defaults.dependency_kind;
+            let link_graph = link_state.as_ref().map(|_| DependencyGraph::new(dependencies));
+            let link_allowed = |from: Uuid, to: Uuid| {
+                link_graph.as_ref().is_some_and(|g| !g.would_create_cycle(from, to) && !g.has_link(from, to))
+            };
             if let Some(ref state) = link_state {
-                if let Some(ptr) = pointer_pos {
-                    if primary_down {
-                        draw_dependency_arrow(
-                            &painter,
-                            state.from_point,
-                            ptr,
-                            DependencyKind::FinishToStart,
-                            theme::dep_creating(),
-                            1.5,
-                        );
+                if let Some(ptr) = pointer_pos.filter(|_| primary_down) {
+                    let allowed = pointer_bar.map(|(id, _)| link_allowed(state.from_task, id));
+                    let color = if allowed == Some(false) { LINK_REJECT_COLOR } else { theme::dep_creating() };
+                    if let Some((_, rect)) = pointer_bar {
+                        let rounding = Rounding::same(theme::bar_rounding() + 2.0);
+                        if allowed == Some(false) {
+                            painter.rect_filled(rect.expand(2.0), rounding, with_alpha(LINK_REJECT_COLOR, 70));
+                        }
+                        painter.rect_stroke(rect.expand(2.0), rounding, Stroke::new(2.0, color));
                     }
+                    ui.ctx().set_cursor_icon(if allowed == Some(false) {
+                        egui::CursorIcon::NotAllowed
+                    } else {
+                        egui::CursorIcon::Alias
+                    });
+                    let to_rect = match (pointer_bar, allowed) {
+                        (Some((_, rect)), Some(true)) => rect,
+                        _ => Rect::from_min_max(ptr, ptr),
+                    };
+                    let route = link_route(state.from_rect, to_rect, link_kind, 0);
+                    draw_dependency_arrow(&painter, &route, link_kind, color, 1.5);
                 }
+            } else if shift_held && pointer_bar.is_some() {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::Alias);
             }
 
-            // On release, check if we landed on a target bar
+            // On release over a bar that can be linked to, ask for the kind
+            // and lag at the drop point.
             if primary_released {
                 if let Some(state) = link_state {
-                    if let Some(ptr) = pointer_pos {
-                        for task in tasks.iter() {
-                            if task.id != state.from_task {
-                                if let Some(&(_, rect)) = task_positions.get(&task.id) {
-                                    if rect.contains(ptr) {
-                                        interaction.new_dependency = Some(Dependency {
-                                            from_task: state.from_task,
-                                            to_task: task.id,
-                                            kind: DependencyKind::FinishToStart,
-                                        });
-                                        break;
-                                    }
-                                }
-                            }
+                    if let (Some((to_task, _)), Some(at)) = (pointer_bar, pointer_pos) {
+                        if link_allowed(state.from_task, to_task) {
+                            request_link(ui.ctx(), state.from_task, to_task, at, link_kind);
                         }
                     }
                     ui.ctx().data_mut(|d| d.remove::<LinkDragState>(link_id));
                 }
             }
+            // ── Frozen header ────────────────────────────────────────
+            // Drawn last, over whatever scrolled beneath it, at the body's
+            // horizontal offset so its dates stay over their columns.
+            let frozen_origin = Pos2::new(origin.x, header_top);
+            draw_timeline_header(&painter, frozen_origin, viewport, chart_width, frozen_origin.y + header_height(), options.clock);
+            draw_today_line(&painter, frozen_origin, viewport, options.clock);
+            if let Some(key_only) = options.milestone_lane {
+                let lane = Rect::from_min_size(
+                    Pos2::new(origin.x, header_top + header_height()),
+                    Vec2::new(chart_width, lane_height),
+                );
+                let milestones: Vec<&Task> = tasks
+                    .iter()
+                    .filter(|t| t.is_milestone && !options.rows.filtered_out.contains(&t.id) && (!key_only || t.is_key()))
+                    .collect();
+                let clicked = draw_milestone_lane(
+                    &painter,
+                    lane,
+                    origin,
+                    viewport,
+                    &milestones,
+                    *selected_task,
+                    options.clock,
+                    |rect, id| ui.interact(rect, Id::new(("milestone-lane", id)), Sense::click()),
+                );
+                if let Some(id) = clicked {
+                    interaction.reveal = Some(id);
+                }
+            }
+            if origin.y < clip_rect.top() {
+                // Soft shadow under the frozen header once rows scroll beneath it.
+                let r = theme::rendering();
+                let shadow_rect = Rect::from_min_size(
+                    Pos2::new(origin.x, header_top + hh),
+                    Vec2::new(chart_width, r.sticky_shadow_height),
+                );
+                painter.rect_filled(
+                    shadow_rect,
+                    0.0,
+                    Color32::from_rgba_premultiplied(0, 0, 0, r.sticky_shadow_alpha),
+                );
+            }
         });
+    scroll_anchor::remember(ui.ctx(), anchor_id, &row_ids, rows.pitch(), scrolled.state.offset.y);
+    // Rows scroll up under the frozen header, so the scroll offset is also
+    // how far down the rows the view starts.
+    let tops = row_ids.iter().enumerate().map(|(i, &id)| (id, rows.top(i))).collect();
+    row_sync::scrolled(ui.ctx(), RowView::Chart, tops, scrolled.state.offset.y);
+    interaction.scroll = Some(ChartScroll {
+        rect: scrolled.inner_rect,
+        offset: scrolled.state.offset,
+        content_height: scrolled.content_size.y,
+        header_height: hh,
+    });
 
     interaction
 }
 
+/// Ctrl+Scroll over `response` zooms the timeline, keeping the date under
+/// the pointer in place. Shared by every view drawn on the timeline.
+pub(crate) fn handle_ctrl_scroll_zoom(ui: &Ui, response: &egui::Response, viewport: &mut TimelineViewport) {
+    if response.hovered() && ui.input(|i| i.modifiers.ctrl) {
+        let raw_scroll_y = ui.input(|i| i.raw_scroll_delta.y);
+        let smooth_scroll_y = ui.input(|i| i.smooth_scroll_delta.y);
+        let scroll_y = if raw_scroll_y.abs() > smooth_scroll_y.abs() {
+            raw_scroll_y
+        } else {
+            smooth_scroll_y
+        };
+
+        // Get mouse position relative to chart area
+        if let Some(hover_pos) = response.hover_pos() {
+            let mouse_x = hover_pos.x - response.rect.min.x;
+
+            // Calculate the datetime at mouse position before zoom
+            let _old_pixels_per_day = viewport.pixels_per_day;
+            let datetime_at_mouse = viewport.x_to_datetime(mouse_x);
+
+            // Perform zoom
+            if scroll_y > 0.0 {
+                viewport.zoom_in();
+            } else if scroll_y < 0.0 {
+                viewport.zoom_out();
+            }
+
+            // Adjust viewport to keep the datetime under mouse in the same position
+            let new_x_for_datetime = viewport.datetime_to_x(datetime_at_mouse);
+            let x_offset = new_x_for_datetime - mouse_x;
+
+            // Shift viewport start/end to compensate
+            let time_offset = chrono::Duration::seconds(
+                (x_offset / viewport.pixels_per_day * 86400.0) as i64
+            );
+            viewport.pan(time_offset);
+        }
+    }
+}
+
 fn drag_id(task_id: Uuid, mode: &'static str) -> Id {
     Id::new(("drag", task_id, mode))
 }
 
+/// Height of the milestone lane pinned under the header.
+pub(crate) const MILESTONE_LANE_HEIGHT: f32 = 26.0;
+const LANE_DIAMOND: f32 = 5.5;
+
+/// Draw `milestones` as labelled diamonds along `lane`, on the chart's
+/// timeline. `interact` registers each diamond's hit area; returns the
+/// milestone clicked this frame.
+#[allow(clippy::too_many_arguments)]
+fn draw_milestone_lane(
+    painter: &egui::Painter,
+    lane: Rect,
+    origin: Pos2,
+    viewport: &TimelineViewport,
+    milestones: &[&Task],
+    selected: Option<Uuid>,
+    clock: ProjectClock,
+    mut interact: impl FnMut(Rect, Uuid) -> egui::Response,
+) -> Option<Uuid> {
+    painter.rect_filled(lane, 0.0, theme::bg_header());
+    painter.line_segment([lane.left_bottom(), lane.right_bottom()], Stroke::new(1.0, theme::border_subtle()));
+    let now = clock.now();
+    let today_x = origin.x + viewport.datetime_to_x(now);
+    painter.line_segment(
+        [Pos2::new(today_x, lane.top()), Pos2::new(today_x, lane.bottom())],
+        Stroke::new(1.0, theme::today_line()),
+    );
+
+    let mut sorted = milestones.to_vec();
+    sorted.sort_by_key(|t| t.start);
+    let xs: Vec<f32> = sorted.iter().map(|t| origin.x + viewport.datetime_to_x(t.start)).collect();
+    let mut clicked = None;
+    for (i, task) in sorted.iter().enumerate() {
+        let center = Pos2::new(xs[i], lane.center().y);
+        let status = task.milestone_status(now);
+        let outline = if selected == Some(task.id) {
+            Stroke::new(2.0, theme::text_primary())
+        } else {
+            Stroke::new(1.0, theme::bg_dark())
+        };
+        let points = vec![
+            center - Vec2::new(0.0, LANE_DIAMOND),
+            center + Vec2::new(LANE_DIAMOND, 0.0),
+            center + Vec2::new(0.0, LANE_DIAMOND),
+            center - Vec2::new(LANE_DIAMOND, 0.0),
+        ];
+        painter.add(egui::Shape::convex_polygon(points, status.status_color(), outline));
+
+        // Names run right up to the next diamond.
+        let label_left = center.x + LANE_DIAMOND + 4.0;
+        let label_right = xs.get(i + 1).map_or(lane.right(), |x| x - LANE_DIAMOND - 4.0);
+        if label_right - label_left >= MIN_SIDE_LABEL_WIDTH {
+            let clip = Rect::from_x_y_ranges(label_left..=label_right, lane.y_range());
+            painter.with_clip_rect(clip.intersect(painter.clip_rect())).text(
+                Pos2::new(label_left, center.y),
+                egui::Align2::LEFT_CENTER,
+                &task.name,
+                theme::font_bar(),
+                theme::text_secondary(),
+            );
+        }
+
+        let hit = Rect::from_center_size(center, Vec2::splat(LANE_DIAMOND * 2.0 + 8.0));
+        let resp = interact(hit, task.id).on_hover_text(format!(
+            "{}\n{}\n{}",
+            task.name,
+            clock.to_local(task.start).format("%d/%m/%Y %H:%M"),
+            status.label()
+        ));
+        if resp.clicked() {
+            clicked = Some(task.id);
+        }
+    }
+    clicked
+}
+
+/// Parents whose span covers `at`, in list order.
+fn phases_at(tasks: &[Task], at: NaiveDateTime) -> Vec<&Task> {
+    tasks
+        .iter()
+        .filter(|t| t.has_children(tasks) && t.start <= at && at <= t.end)
+        .collect()
+}
+
+/// The phase a milestone added from the header at `at` goes in: the selected
+/// parent, or the selected task's parent, if it runs on that date.
+fn default_phase(tasks: &[Task], selected: Option<Uuid>, at: NaiveDateTime) -> Option<Uuid> {
+    let selected = tasks.iter().find(|t| Some(t.id) == selected)?;
+    let phase = if selected.has_children(tasks) { Some(selected.id) } else { selected.parent_id }?;
+    phases_at(tasks, at).iter().any(|p| p.id == phase).then_some(phase)
+}
+
+/// Dates covered by a drag from `x0` to `x1` (chart-relative): whole hours
+/// on the hour scale, otherwise whole days at the project's default times.
+fn drawn_span(
+    x0: f32,
+    x1: f32,
+    viewport: &TimelineViewport,
+    defaults: TaskDefaults,
+) -> (NaiveDateTime, NaiveDateTime) {
+    let first = viewport.x_to_datetime(x0.min(x1));
+    let last = viewport.x_to_datetime(x0.max(x1));
+    if viewport.scale == TimelineScale::Hours {
+        let hour = |t: NaiveDateTime| t.date().and_time(NaiveTime::from_hms_opt(t.hour(), 0, 0).unwrap_or_default());
+        (hour(first), hour(last) + chrono::Duration::hours(1))
+    } else {
+        defaults.span(first.date(), (last.date() - first.date()).num_days() + 1)
+    }
+}
+
+/// Pixels a locked bar can be dragged sideways before it counts as an
+/// attempt to move it (smaller drags are clicks or row reorders).
+const LOCKED_DRAG_SLOP: f32 = 4.0;
+/// How long a locked bar shakes after a refused drag.
+const SHAKE_SECS: f64 = 0.4;
+
+/// Set while a refused drag on a locked task is in progress, so it only
+/// shakes and reports once per drag.
+fn locked_drag_id(task_id: Uuid) -> Id {
+    Id::new(("locked-drag", task_id))
+}
+
+fn shake_id(task_id: Uuid) -> Id {
+    Id::new(("locked-shake", task_id))
+}
+
+/// Leave a locked task where it is, shake it and report the attempt.
+fn refuse_locked_drag(ctx: &egui::Context, task_id: Uuid, interaction: &mut ChartInteraction) {
+    if ctx.data(|d| d.get_temp::<bool>(locked_drag_id(task_id))).is_some() {
+        return;
+    }
+    let now = ctx.input(|i| i.time);
+    ctx.data_mut(|d| {
+        d.insert_temp(locked_drag_id(task_id), true);
+        d.insert_temp(shake_id(task_id), now);
+    });
+    interaction.blocked_move = Some(task_id);
+}
+
+/// Horizontal offset of a shaking locked bar, or 0 once the shake is over.
+fn shake_offset(ctx: &egui::Context, task_id: Uuid) -> f32 {
+    let Some(started) = ctx.data(|d| d.get_temp::<f64>(shake_id(task_id))) else {
+        return 0.0;
+    };
+    let t = ctx.input(|i| i.time) - started;
+    if t >= SHAKE_SECS {
+        ctx.data_mut(|d| d.remove::<f64>(shake_id(task_id)));
+        return 0.0;
+    }
+    ctx.request_repaint();
+    ((t * 60.0).sin() * 4.0 * (1.0 - t / SHAKE_SECS)) as f32
+}
+
+/// "Insert Task Above" / "Insert Task Below" context menu entries.
+fn insert_menu_items(ui: &mut Ui) -> Option<InsertSide> {
+    if ui.button(egui_phosphor::regular::ARROW_LINE_UP.to_string() + "  Insert Task Above").clicked() {
+        return Some(InsertSide::Above);
+    }
+    if ui.button(egui_phosphor::regular::ARROW_LINE_DOWN.to_string() + "  Insert Task Below").clicked() {
+        return Some(InsertSide::Below);
+    }
+    None
+}
+
+/// "Lock dates" / "Unlock dates" entry for the bar context menus.
+fn lock_menu_item(ui: &mut Ui, locked: bool) -> bool {
+    let text = if locked {
+        format!("{}  Unlock Dates", egui_phosphor::regular::LOCK_SIMPLE_OPEN)
+    } else {
+        format!("{}  Lock Dates", egui_phosphor::regular::LOCK_SIMPLE)
+    };
+    ui.button(text).clicked()
+}
+
+fn tentative_menu_item(ui: &mut Ui, tentative: bool) -> bool {
+    let text = if tentative {
+        format!("{}  Mark Confirmed", egui_phosphor::regular::CHECK_SQUARE)
+    } else {
+        format!("{}  Mark Tentative", egui_phosphor::regular::QUESTION)
+    };
+    ui.button(text).clicked()
+}
+
 /// Calculate the duration to adjust when dragging, supporting sub-day precision.
 fn drag_duration(delta_x: f32, viewport: &TimelineViewport) -> chrono::Duration {
     match viewport.scale {
@@ -939,31 +1864,23 @@ fn drag_duration(delta_x: f32, viewport: &TimelineViewport) -> chrono::Duration
     }
 }
 
+/// Shortest a bar can be resized to: one snap unit, or its length before
+/// the drag if that was already shorter, so grabbing a handle never
+/// stretches it.
+fn resize_min_span(snapshot: &DragSnapshot, viewport: &TimelineViewport) -> chrono::Duration {
+    viewport.snap_unit().min(snapshot.end - snapshot.start)
+}
+
 /// Legacy function for compatibility - delegates to drag_duration and extracts days.
 fn drag_days(delta_x: f32, viewport: &TimelineViewport) -> i64 {
     drag_duration(delta_x, viewport).num_days()
 }
 
-fn row_index_from_pointer_y(
-    pointer_y: f32,
-    origin: Pos2,
-    row_height: f32,
-    row_padding: f32,
-    task_count: usize,
-) -> Option<usize> {
-    if task_count == 0 {
-        return None;
-    }
-
-    let row_top = origin.y + header_height() + row_padding;
-    let row_span = row_height + row_padding;
-    if row_span <= 0.0 {
-        return None;
-    }
-
-    let raw = ((pointer_y - row_top) / row_span).floor() as isize;
-    let clamped = raw.clamp(0, task_count as isize - 1) as usize;
-    Some(clamped)
+/// The visible row under `pointer_y`, for rows starting at `rows_top`;
+/// the first or last row when the pointer is above or below them all.
+fn row_index_from_pointer_y(pointer_y: f32, rows_top: f32, rows: &RowLayout) -> Option<usize> {
+    let last = rows.len().checked_sub(1)?;
+    Some(rows.row_at(pointer_y - rows_top).unwrap_or(0).min(last))
 }
 
 fn move_task_by_swapping(tasks: &mut [Task], from: usize, to: usize) {
@@ -999,12 +1916,13 @@ fn scaled_handle_width(viewport: &TimelineViewport) -> f32 {
     (theme::handle_width() * vertical_zoom_scale(viewport).sqrt()).clamp(5.0, 12.0)
 }
 
-fn draw_timeline_header(
+pub(crate) fn draw_timeline_header(
     painter: &egui::Painter,
     origin: Pos2,
     viewport: &TimelineViewport,
     width: f32,
     grid_bottom_y: f32,
+    clock: ProjectClock,
 ) {
     let hh = header_height();
     // Background for header
@@ -1024,15 +1942,18 @@ fn draw_timeline_header(
     );
 
     // Subtle weekend tint in header (especially useful in Weeks view).
-    draw_weekend_header_bands(painter, origin, viewport, width);
+    draw_weekend_header_bands(painter, origin, viewport, width, clock);
 
-    let mut date = viewport.start;
-    let end = viewport.end;
+    // Labels and grid lines follow the viewer's local time; `x_of` places a
+    // local time on the chart, which is laid out in project time.
+    let x_of = |local: NaiveDateTime| origin.x + viewport.date_to_x(clock.from_local(local));
+    let mut date = clock.to_local(viewport.start);
+    let end = clock.to_local(viewport.end);
 
     match viewport.scale {
         TimelineScale::Days => {
             while date <= end {
-                let x = origin.x + viewport.date_to_x(date);
+                let x = x_of(date);
 
                 painter.line_segment(
                     [
@@ -1062,7 +1983,7 @@ fn draw_timeline_header(
                     painter.text(
                         Pos2::new(x + 3.0, origin.y + 12.0),
                         egui::Align2::LEFT_CENTER,
-                        date.format("%b %Y").to_string(),
+                        format_datetime(date, "date.month_year"),
                         theme::font_header(),
                         theme::text_primary(),
                     );
@@ -1075,10 +1996,9 @@ fn draw_timeline_header(
             let weekday = date.weekday().num_days_from_monday();
             date -= chrono::Duration::days(weekday as i64);
             let show_weekdays = viewport.pixels_per_day >= 18.0 * 1.4;
-            let weekday_labels = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
 
             while date <= end {
-                let x = origin.x + viewport.date_to_x(date);
+                let x = x_of(date);
                 let week_band_top = origin.y + 22.0;
 
                 painter.line_segment(
@@ -1092,22 +2012,22 @@ fn draw_timeline_header(
                 painter.text(
                     Pos2::new(x + 3.0, origin.y + 26.0),
                     egui::Align2::LEFT_CENTER,
-                    date.format("W%V").to_string(),
+                    format_datetime(date, "date.week"),
                     theme::font_sub(),
                     theme::text_secondary(),
                 );
 
                 if show_weekdays {
-                    for (day_offset, label) in weekday_labels.iter().enumerate() {
-                        let day_date = date + chrono::Duration::days(day_offset as i64);
+                    for day_offset in 0..7 {
+                        let day_date = date + chrono::Duration::days(day_offset);
                         if day_date > end {
                             break;
                         }
-                        let day_x = origin.x + viewport.date_to_x(day_date) + viewport.pixels_per_day * 0.5;
+                        let day_x = x_of(day_date) + viewport.pixels_per_day * 0.5;
                         painter.text(
                             Pos2::new(day_x, origin.y + 39.0),
                             egui::Align2::CENTER_CENTER,
-                            *label,
+                            format_datetime(day_date, "%a"),
                             theme::font_small(),
                             theme::text_dim(),
                         );
@@ -1118,7 +2038,7 @@ fn draw_timeline_header(
                     painter.text(
                         Pos2::new(x + 3.0, origin.y + 12.0),
                         egui::Align2::LEFT_CENTER,
-                        date.format("%b %Y").to_string(),
+                        format_datetime(date, "date.month_year"),
                         theme::font_header(),
                         theme::text_primary(),
                     );
@@ -1133,7 +2053,7 @@ fn draw_timeline_header(
             date = first_of_month.and_time(date.time());
 
             while date <= end {
-                let x = origin.x + viewport.date_to_x(date);
+                let x = x_of(date);
 
                 painter.line_segment(
                     [
@@ -1146,7 +2066,7 @@ fn draw_timeline_header(
                 painter.text(
                     Pos2::new(x + 5.0, origin.y + 18.0),
                     egui::Align2::LEFT_CENTER,
-                    date.format("%b %Y").to_string(),
+                    format_datetime(date, "date.month_year"),
                     theme::font_header(),
                     theme::text_primary(),
                 );
@@ -1180,7 +2100,7 @@ fn draw_timeline_header(
             let mut current_day = date.date();
 
             while date <= end {
-                let x = origin.x + viewport.date_to_x(date);
+                let x = x_of(date);
 
                 // Check if we're at a new day
                 if date.date() != current_day {
@@ -1198,7 +2118,7 @@ fn draw_timeline_header(
                     painter.text(
                         Pos2::new(x + 3.0, origin.y + 12.0),
                         egui::Align2::LEFT_CENTER,
-                        date.format("%b %d").to_string(),
+                        format_datetime(date, "date.month_day"),
                         theme::font_header(),
                         theme::text_primary(),
                     );
@@ -1240,27 +2160,100 @@ fn draw_timeline_header(
     }
 }
 
-fn draw_weekend_bands(
+/// Opacity of a swimlane's tint over the row backgrounds, alternating
+/// between neighbouring lanes.
+const SWIMLANE_ALPHA: [u8; 2] = [12, 24];
+
+/// Tint the rows of each top-level parent and its descendants with the
+/// parent's colour, one band per parent. While the parent's own row is
+/// scrolled under the header, its name is ghosted at the left of the band.
+/// `top_left` is where the first row starts and rows are `pitch` apart;
+/// `visible` is the part of the view below the pinned header.
+fn draw_swimlanes(
+    painter: &egui::Painter,
+    tasks: &[Task],
+    visible_rows: &[usize],
+    top_left: Pos2,
+    rows: &RowLayout,
+    width: f32,
+    visible: Rect,
+) {
+    let index: std::collections::HashMap<Uuid, usize> = tasks.iter().enumerate().map(|(i, t)| (t.id, i)).collect();
+    let top_level = |mut i: usize| {
+        while let Some(&parent) = tasks[i].parent_id.and_then(|p| index.get(&p)) {
+            i = parent;
+        }
+        i
+    };
+
+    // Runs of consecutive rows under the same top-level task: (task, first row, row count).
+    let mut lanes: Vec<(usize, usize, usize)> = Vec::new();
+    for (row, &task_i) in visible_rows.iter().enumerate() {
+        let top = top_level(task_i);
+        match lanes.last_mut() {
+            Some((lane_top, _, count)) if *lane_top == top => *count += 1,
+            _ => lanes.push((top, row, 1)),
+        }
+    }
+
+    // Rows scrolled up are hidden under the pinned header.
+    let visible_top = visible.top().max(top_left.y);
+    let mut shade = 0;
+    for (top, first, count) in lanes {
+        let parent = &tasks[top];
+        if !parent.has_children(tasks) {
+            continue;
+        }
+        let lane = Rect::from_min_max(
+            Pos2::new(top_left.x, top_left.y + rows.top(first)),
+            Pos2::new(top_left.x + width, top_left.y + rows.top(first + count)),
+        );
+        let pitch = rows.span(first);
+        let color = parent.color.to_color32();
+        painter.rect_filled(
+            lane,
+            0.0,
+            Color32::from_rgba_unmultiplied(color.r(), color.g(), color.b(), SWIMLANE_ALPHA[shade % 2]),
+        );
+        shade += 1;
+
+        // The parent's row is the lane's first; ghost its name once that
+        // row is out of sight but the rest of the lane isn't.
+        if lane.top() + pitch <= visible_top && lane.bottom() > visible_top + pitch {
+            painter.text(
+                Pos2::new(visible.left() + 8.0, visible_top + 4.0),
+                egui::Align2::LEFT_TOP,
+                &parent.name,
+                theme::font_bar(),
+                with_alpha(theme::text_dim(), 110),
+            );
+        }
+    }
+}
+
+pub(crate) fn draw_weekend_bands(
     painter: &egui::Painter,
     origin: Pos2,
     viewport: &TimelineViewport,
     width: f32,
     bottom_y: f32,
+    clock: ProjectClock,
 ) {
     // Skip when days are too compressed to avoid noise.
     if viewport.pixels_per_day < 5.0 || viewport.scale == TimelineScale::Months {
         return;
     }
 
-    let mut date = viewport.start;
-    let end = viewport.end;
+    let x_of = |local: NaiveDateTime| origin.x + viewport.date_to_x(clock.from_local(local));
+    let mut date = clock.to_local(viewport.start);
+    let end = clock.to_local(viewport.end);
     let right = origin.x + width;
     while date <= end {
         let weekday = date.weekday().num_days_from_monday();
         if weekday >= 5 {
-            let x0 = origin.x + viewport.date_to_x(date);
+            let x0 = x_of(date);
             let next_day = date + chrono::Duration::days(1);
-            let x1 = (origin.x + viewport.date_to_x(next_day)).min(right);
+            let x1 = x_of(next_day).min(right);
             if x1 > x0 {
                 painter.rect_filled(
                     Rect::from_min_max(
@@ -1292,13 +2285,15 @@ fn draw_weekend_header_bands(
     origin: Pos2,
     viewport: &TimelineViewport,
     width: f32,
+    clock: ProjectClock,
 ) {
     if viewport.pixels_per_day < 7.0 || viewport.scale == TimelineScale::Months {
         return;
     }
 
-    let mut date = viewport.start;
-    let end = viewport.end;
+    let x_of = |local: NaiveDateTime| origin.x + viewport.date_to_x(clock.from_local(local));
+    let mut date = clock.to_local(viewport.start);
+    let end = clock.to_local(viewport.end);
     let right = origin.x + width;
     let y_min = origin.y + 22.0;
     let y_max = origin.y + header_height();
@@ -1306,9 +2301,9 @@ fn draw_weekend_header_bands(
     while date <= end {
         let weekday = date.weekday().num_days_from_monday();
         if weekday >= 5 {
-            let x0 = origin.x + viewport.date_to_x(date);
+            let x0 = x_of(date);
             let next_day = date + chrono::Duration::days(1);
-            let x1 = (origin.x + viewport.date_to_x(next_day)).min(right);
+            let x1 = x_of(next_day).min(right);
             if x1 > x0 {
                 painter.rect_filled(
                     Rect::from_min_max(Pos2::new(x0, y_min), Pos2::new(x1, y_max)),
@@ -1321,12 +2316,13 @@ fn draw_weekend_header_bands(
     }
 }
 
-fn draw_today_line(
+pub(crate) fn draw_today_line(
     painter: &egui::Painter,
     origin: Pos2,
     viewport: &TimelineViewport,
+    clock: ProjectClock,
 ) {
-    let today = chrono::Local::now().naive_local();
+    let today = clock.now();
     let x = origin.x + viewport.date_to_x(today);
 
     // Dedicated header marker: diamond + compact label.
@@ -1360,6 +2356,7 @@ fn draw_summary_bar(
     let x_start = origin.x + viewport.date_to_x(task.start);
     let x_end   = origin.x + viewport.date_to_x(task.end);
     let width   = (x_end - x_start).max(8.0);
+    let color   = task.color.to_color32();
 
     // Draw a thin horizontal bar in the middle of the row (bracket body)
     let bar_h    = (row_height * 0.35).max(5.0);
@@ -1368,12 +2365,12 @@ fn draw_summary_bar(
 
     // Muted color based on task color
     let body_color = Color32::from_rgba_premultiplied(
-        task.color.r() / 2,
-        task.color.g() / 2,
-        task.color.b() / 2,
+        color.r() / 2,
+        color.g() / 2,
+        color.b() / 2,
         200,
     );
-    let tick_color = with_alpha(task.color, 220);
+    let tick_color = with_alpha(color, 220);
 
     // Body
     painter.rect_filled(bar_rect, Rounding::same(2.0), body_color);
@@ -1384,7 +2381,7 @@ fn draw_summary_bar(
             bar_rect.min,
             Vec2::new(width * task.progress, bar_h),
         );
-        painter.rect_filled(prog_rect, Rounding::same(2.0), with_alpha(task.color, 180));
+        painter.rect_filled(prog_rect, Rounding::same(2.0), with_alpha(color, 180));
     }
 
     // Left downward tick
@@ -1404,25 +2401,63 @@ fn draw_summary_bar(
         painter.rect_stroke(
             bar_rect.expand(2.0),
             Rounding::same(3.0),
-            Stroke::new(1.5, with_alpha(task.color, 200)),
+            Stroke::new(1.5, with_alpha(color, 200)),
         );
     }
 
     // Label to the right of the bar
     let label_x = x_start + width + 6.0;
     let label_y = y + row_height / 2.0;
-    painter.text(
+    draw_side_label(
+        painter,
+        &format!("{} ({:.0}%)", task.name, task.progress * 100.0),
         Pos2::new(label_x, label_y),
-        egui::Align2::LEFT_CENTER,
-        format!("{} ({:.0}%)", task.name, task.progress * 100.0),
-        egui::FontId::proportional(11.0),
-        with_alpha(Color32::WHITE, 180),
     );
 
     // Return a slightly expanded rect so clicking near the bar registers
     bar_rect.expand(4.0)
 }
 
+/// Draw the inline rename field over a task label if that task is being
+/// renamed on the chart.
+fn show_rename_field(ui: &mut Ui, task_id: Uuid, rect: Rect, interaction: &mut ChartInteraction) {
+    if inline_rename::is_active(ui.ctx(), task_id, RenameSurface::Chart) {
+        if let Some(renamed) = inline_rename::show(ui, rect) {
+            interaction.rename = Some(renamed);
+        }
+    }
+}
+
+/// Where a bar drawn at `bar` takes clicks and drags: the bar itself, widened
+/// evenly to [`MIN_HIT_WIDTH`] when it is narrower.
+fn bar_hit_rect(bar: Rect) -> Rect {
+    let grow = ((MIN_HIT_WIDTH - bar.width()) / 2.0).max(0.0);
+    bar.expand2(Vec2::new(grow, 0.0))
+}
+
+/// Where a milestone drawn at `diamond` takes clicks and drags: the full
+/// height of its row, with [`MILESTONE_HIT_MARGIN`] either side.
+fn milestone_hit_rect(diamond: Rect, y: f32, row_height: f32) -> Rect {
+    Rect::from_x_y_ranges(
+        diamond.left() - MILESTONE_HIT_MARGIN..=diamond.right() + MILESTONE_HIT_MARGIN,
+        y..=y + row_height,
+    )
+}
+
+/// `3 days`, or `5 h` for tasks shorter than a day, so tooltips give the
+/// real length of bars drawn wider than they are.
+fn length_label(task: &Task) -> String {
+    let hours = (task.end - task.start).num_minutes() as f64 / 60.0;
+    let trim = |n: f64| format!("{:.1}", n).trim_end_matches(".0").to_string();
+    if hours < 24.0 {
+        format!("{} h", trim(hours))
+    } else if hours == 24.0 {
+        "1 day".to_string()
+    } else {
+        format!("{} days", trim(hours / 24.0))
+    }
+}
+
 fn draw_task_bar(
     painter: &egui::Painter,
     origin: Pos2,
@@ -1430,11 +2465,22 @@ fn draw_task_bar(
     task: &Task,
     y: f32,
     row_height: f32,
-    is_selected: bool,
+    style: BarStyle,
 ) -> Rect {
     let x_start = origin.x + viewport.date_to_x(task.start);
     let x_end = origin.x + viewport.date_to_x(task.end);
-    let bar_width = (x_end - x_start).max(6.0);
+    let bar_width = (x_end - x_start).max(MIN_BAR_WIDTH);
+    let now = style.now;
+    // Tinting only says something once the task should have started.
+    let fill = if style.schedule_tint && task.start < now {
+        task.schedule_status(now).status_color()
+    } else {
+        task.color.to_color32()
+    };
+    let fill = match style.highlight {
+        Some(Highlight::Overdue) => fill.lerp_to_gamma(Highlight::Overdue.status_color(), OVERDUE_TINT),
+        _ => fill,
+    };
     let inset = theme::bar_inset();
 
     let bar_rect = Rect::from_min_size(
@@ -1456,8 +2502,15 @@ fn draw_task_bar(
     }
 
     // Main bar — flat fill when darken_factor is 1.0
-    let base_color = darken_color(task.color, r.bar_darken_factor);
-    painter.rect_filled(bar_rect, rounding, base_color);
+    let base_color = darken_color(fill, r.bar_darken_factor);
+    if task.tentative {
+        painter.rect_filled(bar_rect, rounding, base_color.gamma_multiply(TENTATIVE_FILL));
+        draw_hatching(painter, bar_rect, base_color);
+        let corners = [bar_rect.left_top(), bar_rect.right_top(), bar_rect.right_bottom(), bar_rect.left_bottom()];
+        draw_dashed_outline(painter, &corners, base_color);
+    } else {
+        painter.rect_filled(bar_rect, rounding, base_color);
+    }
 
     // Mid-body glaze (skipped for flat themes)
     if r.bar_glaze_alpha > 0 {
@@ -1468,7 +2521,7 @@ fn draw_task_bar(
         painter.rect_filled(
             body_glaze,
             Rounding::same((br - 1.0).max(1.0)),
-            with_alpha(task.color, r.bar_glaze_alpha),
+            with_alpha(fill, r.bar_glaze_alpha),
         );
     }
 
@@ -1523,8 +2576,21 @@ fn draw_task_bar(
         }
     }
 
+    // Expected-progress tick: a fill short of it means behind schedule.
+    let expected = task.expected_progress(now);
+    if expected > 0.0 && expected < 1.0 && task.progress < 1.0 {
+        let tick_x = bar_rect.left() + bar_width * expected;
+        painter.line_segment(
+            [
+                Pos2::new(tick_x, bar_rect.top() - 2.0),
+                Pos2::new(tick_x, bar_rect.bottom() + 2.0),
+            ],
+            Stroke::new(1.5, theme::text_primary()),
+        );
+    }
+
     // Selection glow
-    if is_selected {
+    if style.selected {
         painter.rect_stroke(
             bar_rect.expand(r.selection_glow_outer_expand),
             Rounding::same(br + r.selection_glow_outer_expand),
@@ -1537,35 +2603,124 @@ fn draw_task_bar(
         );
     }
 
-    // Overdue indicator — red border when past due and not complete
-    let today = chrono::Local::now().naive_local();
-    if !task.is_milestone && task.end < today && task.progress < 1.0 {
-        painter.rect_stroke(
-            bar_rect.expand(1.0),
-            Rounding::same(br + 1.0),
-            Stroke::new(2.0, Color32::from_rgb(220, 60, 60)),
+    // Highlight rules: a red border on overdue bars (their fill is tinted
+    // above), an hourglass badge on the top-right corner of at-risk ones.
+    match style.highlight {
+        Some(Highlight::Overdue) => {
+            painter.rect_stroke(
+                bar_rect.expand(1.0),
+                Rounding::same(br + 1.0),
+                Stroke::new(2.0, Highlight::Overdue.status_color()),
+            );
+        }
+        Some(Highlight::AtRisk) => {
+            let center = bar_rect.right_top() + Vec2::new(-1.0, 1.0);
+            painter.circle(center, 6.0, Highlight::AtRisk.status_color(), Stroke::new(1.0, theme::bg_dark()));
+            painter.text(
+                center,
+                egui::Align2::CENTER_CENTER,
+                egui_phosphor::regular::HOURGLASS_MEDIUM,
+                egui::FontId::proportional(8.0),
+                Color32::BLACK,
+            );
+        }
+        None => {}
+    }
+
+    // Risk rating: a corner triangle on the top-left of the bar.
+    if let Some(risk) = task.risk {
+        let corner = bar_rect.left_top();
+        let size = RISK_MARKER_SIZE.min(bar_rect.height() / 2.0).min(bar_rect.width() / 2.0);
+        painter.add(egui::Shape::convex_polygon(
+            vec![corner, corner + Vec2::new(size, 0.0), corner + Vec2::new(0.0, size)],
+            risk.status_color(),
+            Stroke::new(1.0, theme::bg_dark()),
+        ));
+    }
+
+    if task.locked {
+        painter.text(
+            Pos2::new(bar_rect.right() + 4.0, bar_rect.center().y),
+            egui::Align2::LEFT_CENTER,
+            egui_phosphor::regular::LOCK_SIMPLE,
+            egui::FontId::proportional(10.0),
+            theme::text_dim(),
         );
     }
 
-    // Task name on bar (single line, clipped to bar bounds)
-    if bar_width > 30.0 {
+    // Task name: inside the bar when asked for and it fits, otherwise to the
+    // right of the bar (and its padlock).
+    if style.labels != BarLabels::Hidden {
         let galley = painter.layout_no_wrap(
             task.name.clone(),
             theme::font_bar(),
-            theme::text_on_bar(),
-        );
-        let clipped = painter.with_clip_rect(bar_rect);
-        let text_y = y + inset + (bar_rect.height() - galley.size().y) / 2.0;
-        clipped.galley(
-            Pos2::new(bar_rect.left() + 6.0, text_y),
-            galley,
-            Color32::TRANSPARENT,
+            theme::text_on(fill),
         );
+        if style.labels == BarLabels::Inside && galley.size().x + 12.0 <= bar_width {
+            let text_y = y + inset + (bar_rect.height() - galley.size().y) / 2.0;
+            painter.galley(
+                Pos2::new(bar_rect.left() + 6.0, text_y),
+                galley,
+                Color32::TRANSPARENT,
+            );
+        } else {
+            let left = bar_rect.right() + if task.locked { 18.0 } else { 6.0 };
+            draw_side_label(painter, &task.name, Pos2::new(left, bar_rect.center().y));
+        }
     }
 
     bar_rect
 }
 
+/// The strip marking where a bar runs into its assignee's absence.
+const ABSENCE_MARKER_HEIGHT: f32 = 3.0;
+const ABSENCE_MARKER_COLOR: Color32 = Color32::from_rgb(220, 140, 40);
+
+/// Length of the sides of the risk triangle on a bar's corner.
+const RISK_MARKER_SIZE: f32 = 7.0;
+
+/// How far an overdue bar's fill is pulled toward red.
+const OVERDUE_TINT: f32 = 0.45;
+
+/// How much of its colour a tentative bar or diamond keeps under the hatching.
+const TENTATIVE_FILL: f32 = 0.35;
+
+/// Diagonal stripes in `color` across `rect`, for tentative bars and absences.
+pub(crate) fn draw_hatching(painter: &egui::Painter, rect: Rect, color: Color32) {
+    const SPACING: f32 = 6.0;
+    let painter = painter.with_clip_rect(rect.intersect(painter.clip_rect()));
+    let stroke = Stroke::new(1.5, color);
+    let mut x = rect.left() - rect.height();
+    while x < rect.right() {
+        painter.line_segment([Pos2::new(x, rect.bottom()), Pos2::new(x + rect.height(), rect.top())], stroke);
+        x += SPACING;
+    }
+}
+
+/// A dashed line around the closed shape through `points`.
+fn draw_dashed_outline(painter: &egui::Painter, points: &[Pos2], color: Color32) {
+    let mut path = points.to_vec();
+    path.extend(points.first());
+    painter.extend(egui::Shape::dashed_line(&path, Stroke::new(1.0, color), 4.0, 3.0));
+}
+
+/// Narrowest room worth drawing an elided side label in.
+const MIN_SIDE_LABEL_WIDTH: f32 = 24.0;
+
+/// Draw `text` left-aligned and vertically centred at `pos`, cut short with
+/// "…" where it would run past the visible chart; the tooltip has it in full.
+fn draw_side_label(painter: &egui::Painter, text: &str, pos: Pos2) {
+    let max_width = painter.clip_rect().right() - pos.x - 4.0;
+    if max_width < MIN_SIDE_LABEL_WIDTH {
+        return;
+    }
+    let mut job = egui::text::LayoutJob::simple_singleline(text.to_string(), theme::font_bar(), theme::text_secondary());
+    job.wrap = egui::text::TextWrapping::truncate_at_width(max_width);
+    let galley = painter.layout_job(job);
+    let top = pos.y - galley.size().y / 2.0;
+    painter.galley(Pos2::new(pos.x, top), galley, Color32::TRANSPARENT);
+}
+
 fn draw_milestone(
     painter: &egui::Painter,
     origin: Pos2,
@@ -1573,8 +2728,9 @@ fn draw_milestone(
     task: &Task,
     y: f32,
     row_height: f32,
-    is_selected: bool,
+    style: BarStyle,
 ) -> Rect {
+    let is_selected = style.selected;
     let x = origin.x + viewport.date_to_x(task.start);
     let center = Pos2::new(x, y + row_height / 2.0);
     let size = (row_height / 2.0 - 3.0).max(6.0);
@@ -1600,11 +2756,17 @@ fn draw_milestone(
         Pos2::new(center.x, center.y + size),
         Pos2::new(center.x - size, center.y),
     ];
-    painter.add(egui::Shape::convex_polygon(
-        points.clone(),
-        task.color,
-        Stroke::NONE,
-    ));
+    if task.tentative {
+        let color = task.color.to_color32();
+        painter.add(egui::Shape::convex_polygon(points.clone(), color.gamma_multiply(TENTATIVE_FILL), Stroke::NONE));
+        draw_dashed_outline(painter, &points, color);
+    } else {
+        painter.add(egui::Shape::convex_polygon(
+            points.clone(),
+            task.color.to_color32(),
+            Stroke::NONE,
+        ));
+    }
 
     if is_selected {
         painter.add(egui::Shape::convex_polygon(
@@ -1614,18 +2776,81 @@ fn draw_milestone(
         ));
     }
 
-    // Label
-    painter.text(
-        Pos2::new(x + size + 6.0, y + row_height / 2.0),
-        egui::Align2::LEFT_CENTER,
-        &task.name,
-        theme::font_bar(),
-        theme::text_secondary(),
-    );
+    if task.locked {
+        painter.text(
+            Pos2::new(x - size - 4.0, center.y),
+            egui::Align2::RIGHT_CENTER,
+            egui_phosphor::regular::LOCK_SIMPLE,
+            egui::FontId::proportional(10.0),
+            theme::text_dim(),
+        );
+    }
+
+    // Label, nudged above or below the centre line
+    if style.labels != BarLabels::Hidden {
+        let nudge = if style.label_raised { -size / 2.0 } else { size / 2.0 };
+        draw_side_label(painter, &task.name, Pos2::new(x + size + 6.0, center.y + nudge));
+    }
 
     Rect::from_center_size(center, Vec2::splat(size * 2.0 + 2.0))
 }
 
+/// The kind and lag chooser for a [`request_link`]ed link: one dropped onto
+/// a bar or a list row, or picked with the keyboard. Returns the link once
+/// confirmed; Escape or a click elsewhere drops it.
+pub fn show_link_chooser(ctx: &egui::Context, tasks: &[Task]) -> Option<Dependency> {
+    let id = Id::new(PENDING_LINK_ID);
+    let mut pending = ctx.data(|d| d.get_temp::<PendingLink>(id))?;
+    let name = |id: Uuid| tasks.iter().find(|t| t.id == id).map_or("?", |t| t.name.as_str());
+    let mut confirmed = false;
+    let area = egui::Area::new(Id::new("link-kind-chooser"))
+        .fixed_pos(pending.at)
+        .order(egui::Order::Foreground)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.label(egui::RichText::new(format!("{} → {}", name(pending.from_task), name(pending.to_task))).strong());
+                ui.horizontal(|ui| {
+                    for &kind in DependencyKind::all() {
+                        ui.selectable_value(&mut pending.kind, kind, kind.short_label())
+                            .on_hover_text(kind.description());
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Lag");
+                    ui.add(egui::DragValue::new(&mut pending.lag_days).range(-365..=365).suffix(" d"))
+                        .on_hover_text("Days between the linked dates; negative for a lead");
+                });
+                ui.horizontal(|ui| {
+                    let link = egui::Button::new(egui::RichText::new("Link").color(theme::text_on(theme::accent())))
+                        .fill(theme::accent());
+                    if ui.add(link).clicked() {
+                        confirmed = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        ctx.data_mut(|d| d.remove::<PendingLink>(id));
+                    }
+                });
+            });
+        });
+    let clicked_away = ctx.input(|i| i.pointer.any_pressed())
+        && ctx.input(|i| i.pointer.interact_pos()).is_some_and(|p| !area.response.rect.contains(p));
+    if confirmed || ctx.input(|i| i.key_pressed(egui::Key::Enter)) {
+        ctx.data_mut(|d| d.remove::<PendingLink>(id));
+        return Some(Dependency {
+            from_task: pending.from_task,
+            to_task: pending.to_task,
+            kind: pending.kind,
+            lag_days: pending.lag_days,
+        });
+    }
+    if clicked_away || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        ctx.data_mut(|d| d.remove::<PendingLink>(id));
+    } else if ctx.data(|d| d.get_temp::<PendingLink>(id)).is_some() {
+        ctx.data_mut(|d| d.insert_temp(id, pending));
+    }
+    None
+}
+
 fn dependency_endpoints(from_rect: Rect, to_rect: Rect, kind: DependencyKind) -> (Pos2, Pos2) {
     // Route endpoints based on dependency type:
     // FS (Finish→Start):  exit from right of from, enter left of to
@@ -1643,6 +2868,40 @@ fn dependency_endpoints(from_rect: Rect, to_rect: Rect, kind: DependencyKind) ->
     (from, to)
 }
 
+/// Length of the straight segment a link with lag leaves its predecessor by.
+const LAG_STUB: f32 = 16.0;
+
+/// Where a link's arrow runs.
+struct LinkRoute {
+    points: Vec<Pos2>,
+    /// Middle of the lag segment and its label, for links with lag.
+    lag: Option<(Pos2, String)>,
+}
+
+/// `+2d` or `-1d`.
+fn lag_label(lag_days: i64) -> String {
+    format!("{:+}d", lag_days)
+}
+
+/// The arrow for a link of `kind` between two bars, out of and into the
+/// ends its kind joins. A link with lag first leaves its predecessor by a
+/// short straight segment, labelled with the lag, before it turns.
+fn link_route(from_rect: Rect, to_rect: Rect, kind: DependencyKind, lag_days: i64) -> LinkRoute {
+    let (from, to) = dependency_endpoints(from_rect, to_rect, kind);
+    if lag_days == 0 {
+        return LinkRoute { points: dependency_route_points(from, to, kind), lag: None };
+    }
+    // Onward from the side of the bar the link leaves by.
+    let out = match kind {
+        DependencyKind::FinishToStart | DependencyKind::FinishToFinish => 1.0,
+        DependencyKind::StartToStart | DependencyKind::StartToFinish => -1.0,
+    };
+    let lag_end = from + Vec2::new(out * LAG_STUB, 0.0);
+    let mut points = vec![from];
+    points.extend(dependency_route_points(lag_end, to, kind));
+    LinkRoute { points, lag: Some((from.lerp(lag_end, 0.5), lag_label(lag_days))) }
+}
+
 fn dependency_route_points(from: Pos2, to: Pos2, kind: DependencyKind) -> Vec<Pos2> {
     // Short fixed stub length — exits/enters each bar by this amount before turning.
     const STUB: f32 = 10.0;
@@ -1772,24 +3031,39 @@ fn dependency_route_points(from: Pos2, to: Pos2, kind: DependencyKind) -> Vec<Po
 
 fn draw_dependency_arrow(
     painter: &egui::Painter,
-    from: Pos2,
-    to: Pos2,
+    route: &LinkRoute,
     kind: DependencyKind,
     color: Color32,
     width: f32,
 ) {
-    let route = dependency_route_points(from, to, kind);
-    draw_dependency_polyline(painter, &route, color, width, 3.0);
-    if route.len() >= 2 {
-        let last_from = route[route.len() - 2];
-        let last_to = route[route.len() - 1];
+    let points = &route.points;
+    draw_dependency_polyline(painter, points, kind.line_style().dash_pattern(), color, width, 3.0);
+    if points.len() >= 2 {
+        let last_from = points[points.len() - 2];
+        let last_to = points[points.len() - 1];
         draw_arrowhead(painter, last_from, last_to, color);
     }
+    if let Some((at, label)) = &route.lag {
+        // Tick marks at both ends of the lag segment, the lag above it.
+        for x in [route.points[0].x, 2.0 * at.x - route.points[0].x] {
+            painter.line_segment([Pos2::new(x, at.y - 3.0), Pos2::new(x, at.y + 3.0)], Stroke::new(1.0, color));
+        }
+        painter.text(
+            *at - Vec2::new(0.0, 4.0),
+            egui::Align2::CENTER_BOTTOM,
+            label,
+            egui::FontId::proportional(9.0),
+            color,
+        );
+    }
 }
 
+/// Draw a link's route with rounded corners, dashed by `pattern` (see
+/// [`crate::model::task::LineStyle::dash_pattern`]).
 fn draw_dependency_polyline(
     painter: &egui::Painter,
     points: &[Pos2],
+    pattern: &[f32],
     color: Color32,
     width: f32,
     corner_radius: f32,
@@ -1840,8 +3114,14 @@ fn draw_dependency_polyline(
 
     draw_points.push(*points.last().unwrap_or(&points[0]));
 
-    for seg in draw_points.windows(2) {
-        painter.line_segment([seg[0], seg[1]], stroke);
+    if pattern.is_empty() {
+        for seg in draw_points.windows(2) {
+            painter.line_segment([seg[0], seg[1]], stroke);
+        }
+    } else {
+        let dashes: Vec<f32> = pattern.iter().step_by(2).copied().collect();
+        let gaps: Vec<f32> = pattern.iter().skip(1).step_by(2).copied().collect();
+        painter.extend(egui::Shape::dashed_line_with_offset(&draw_points, stroke, &dashes, &gaps, 0.0));
     }
 }
 
@@ -1879,6 +3159,12 @@ fn darken_color(color: Color32, factor: f32) -> Color32 {
     )
 }
 
+/// A straight arrow in `kind`'s line style, as the legend shows it.
+pub fn draw_link_sample(painter: &egui::Painter, from: Pos2, to: Pos2, kind: DependencyKind, color: Color32) {
+    draw_dependency_polyline(painter, &[from, to], kind.line_style().dash_pattern(), color, 1.5, 0.0);
+    draw_arrowhead(painter, from, to, color);
+}
+
 /// Draw a small triangular arrowhead pointing from `from` toward `to`.
 fn draw_arrowhead(painter: &egui::Painter, from: Pos2, to: Pos2, color: Color32) {
     let dir = (to - from).normalized();
@@ -0,0 +1,73 @@
+//! In-place task renaming shared by the task list and the chart.
+//!
+//! The edit buffer lives in egui temp data so either surface can start a
+//! rename without threading extra state through `GanttApp`. Only one rename
+//! is active at a time, and it is drawn by the surface that started it.
+
+use egui::Ui;
+use uuid::Uuid;
+
+/// Where the rename field is drawn.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RenameSurface {
+    List,
+    Chart,
+}
+
+#[derive(Clone)]
+struct RenameState {
+    task_id: Uuid,
+    surface: RenameSurface,
+    text: String,
+    focused: bool,
+}
+
+fn state_id() -> egui::Id {
+    egui::Id::new("inline-rename")
+}
+
+/// Start renaming `task_id` on the given surface, seeded with its current name.
+pub fn begin(ctx: &egui::Context, task_id: Uuid, name: &str, surface: RenameSurface) {
+    ctx.data_mut(|d| {
+        d.insert_temp(
+            state_id(),
+            RenameState { task_id, surface, text: name.to_string(), focused: false },
+        )
+    });
+}
+
+/// True if `task_id` is currently being renamed on `surface`.
+pub fn is_active(ctx: &egui::Context, task_id: Uuid, surface: RenameSurface) -> bool {
+    ctx.data(|d| d.get_temp::<RenameState>(state_id()))
+        .is_some_and(|s| s.task_id == task_id && s.surface == surface)
+}
+
+/// Draw the rename field inside `rect`.
+/// Returns the new name once the edit is committed (Enter or focus lost);
+/// Escape discards the edit and returns `None`.
+pub fn show(ui: &mut Ui, rect: egui::Rect) -> Option<(Uuid, String)> {
+    let mut state = ui.ctx().data(|d| d.get_temp::<RenameState>(state_id()))?;
+
+    let resp = ui.put(
+        rect,
+        egui::TextEdit::singleline(&mut state.text)
+            .font(egui::TextStyle::Body)
+            .margin(egui::vec2(4.0, 1.0)),
+    );
+    if !state.focused {
+        resp.request_focus();
+        state.focused = true;
+    }
+
+    if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+        ui.ctx().data_mut(|d| d.remove::<RenameState>(state_id()));
+        return None;
+    }
+    if resp.lost_focus() {
+        ui.ctx().data_mut(|d| d.remove::<RenameState>(state_id()));
+        return Some((state.task_id, state.text));
+    }
+
+    ui.ctx().data_mut(|d| d.insert_temp(state_id(), state));
+    None
+}
@@ -0,0 +1,67 @@
+//! Legend popover for the chart: the line style of each dependency kind
+//! and what the status colours mean, in the palette currently in use.
+
+use crate::model::highlight::Highlight;
+use crate::model::task::{DependencyKind, MilestoneStatus, ScheduleStatus};
+use crate::ui::gantt_chart::draw_link_sample;
+use crate::ui::model_ext::StatusColor;
+use crate::ui::theme;
+use egui::{Color32, Pos2, RichText, Sense, Ui, Vec2};
+
+/// Width of the line drawn for each link kind.
+const SAMPLE_WIDTH: f32 = 44.0;
+
+/// Small legend button that opens the popover.
+pub fn legend_button(ui: &mut Ui) {
+    let button = RichText::new(format!("{} Legend", egui_phosphor::regular::INFO)).size(11.0);
+    ui.menu_button(button, |ui| {
+        ui.set_min_width(300.0);
+        section(ui, "Dependency links");
+        for kind in DependencyKind::all() {
+            ui.horizontal(|ui| {
+                let (rect, _) = ui.allocate_exact_size(Vec2::new(SAMPLE_WIDTH, 14.0), Sense::hover());
+                let y = rect.center().y;
+                draw_link_sample(
+                    ui.painter(),
+                    Pos2::new(rect.left(), y),
+                    Pos2::new(rect.right(), y),
+                    *kind,
+                    theme::text_primary(),
+                );
+                ui.label(RichText::new(kind.short_label()).size(11.0).strong());
+                ui.label(RichText::new(kind.line_style().label()).size(11.0).color(theme::text_secondary()))
+                    .on_hover_text(kind.description());
+            });
+        }
+
+        ui.add_space(4.0);
+        section(ui, "Schedule (with status colours on)");
+        for status in [ScheduleStatus::OnTrack, ScheduleStatus::Slipping, ScheduleStatus::Behind] {
+            swatch(ui, status.status_color(), status.label());
+        }
+        section(ui, "Highlights");
+        for highlight in [Highlight::Overdue, Highlight::AtRisk] {
+            swatch(ui, highlight.status_color(), highlight.label());
+        }
+        section(ui, "Milestones");
+        for status in [MilestoneStatus::Hit, MilestoneStatus::Upcoming, MilestoneStatus::Overdue] {
+            swatch(ui, status.status_color(), status.label());
+        }
+        if theme::color_blind_safe() {
+            ui.add_space(2.0);
+            ui.label(RichText::new("Colour-blind-safe palette").size(10.0).color(theme::text_dim()));
+        }
+    });
+}
+
+fn section(ui: &mut Ui, title: &str) {
+    ui.label(RichText::new(title).size(10.0).color(theme::text_dim()).strong());
+}
+
+fn swatch(ui: &mut Ui, color: Color32, label: &str) {
+    ui.horizontal(|ui| {
+        let (rect, _) = ui.allocate_exact_size(Vec2::new(12.0, 12.0), Sense::hover());
+        ui.painter().rect_filled(rect, 2.0, color);
+        ui.label(RichText::new(label).size(11.0));
+    });
+}
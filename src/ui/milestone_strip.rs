@@ -0,0 +1,132 @@
+//! Compact overview of just the milestones across the whole project.
+//!
+//! Unlike the chart, the strip always spans the project's full date range
+//! regardless of zoom, so it works as an at-a-glance executive summary.
+
+use crate::model::Task;
+use crate::ui::theme;
+use crate::ui::model_ext::StatusColor;
+use chrono::{Datelike, Duration, NaiveDateTime};
+use egui::{Align2, FontId, Id, Pos2, Rect, Sense, Stroke, Ui, Vec2};
+use uuid::Uuid;
+
+pub const STRIP_HEIGHT: f32 = 64.0;
+const DIAMOND_SIZE: f32 = 6.0;
+const SIDE_MARGIN: f32 = 16.0;
+
+/// The first and last date shown: the project span, padded slightly.
+pub fn project_range(tasks: &[Task]) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let start = tasks.iter().map(|t| t.start).min()?;
+    let end = tasks.iter().map(|t| t.end).max()?;
+    let pad = ((end - start) / 50).max(Duration::days(1));
+    Some((start - pad, end + pad))
+}
+
+/// Render the strip. Returns the milestone clicked this frame, if any.
+pub fn show_milestone_strip(tasks: &[Task], selected_task: Option<Uuid>, ui: &mut Ui) -> Option<Uuid> {
+    let mut clicked = None;
+    let (rect, _) = ui.allocate_exact_size(Vec2::new(ui.available_width(), STRIP_HEIGHT), Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, theme::bg_header());
+    painter.line_segment(
+        [rect.left_bottom(), rect.right_bottom()],
+        Stroke::new(1.0, theme::border_subtle()),
+    );
+
+    let (start, end) = project_range(tasks)?;
+    let span = (end - start).num_minutes().max(1) as f32;
+    let inner = rect.shrink2(Vec2::new(SIDE_MARGIN, 0.0));
+    let x_of = |dt: NaiveDateTime| inner.left() + (dt - start).num_minutes() as f32 / span * inner.width();
+    let axis_y = rect.center().y;
+
+    painter.line_segment(
+        [Pos2::new(inner.left(), axis_y), Pos2::new(inner.right(), axis_y)],
+        Stroke::new(1.0, theme::grid_line()),
+    );
+
+    // Month ticks, thinned out so labels don't collide.
+    let months = ((end.year() - start.year()) * 12 + end.month() as i32 - start.month() as i32).max(1);
+    let step = (months as f32 / (inner.width() / 60.0)).ceil().max(1.0) as u32;
+    let mut month = start.date().with_day(1).unwrap_or(start.date());
+    while month <= end.date() {
+        let x = x_of(month.and_time(chrono::NaiveTime::MIN));
+        if x >= inner.left() {
+            painter.line_segment(
+                [Pos2::new(x, axis_y - 3.0), Pos2::new(x, axis_y + 3.0)],
+                Stroke::new(1.0, theme::grid_line()),
+            );
+            painter.text(
+                Pos2::new(x + 2.0, rect.bottom() - 2.0),
+                Align2::LEFT_BOTTOM,
+                month.format("%b %y").to_string(),
+                FontId::proportional(9.0),
+                theme::text_dim(),
+            );
+        }
+        match month.checked_add_months(chrono::Months::new(step)) {
+            Some(next) => month = next,
+            None => break,
+        }
+    }
+
+    let now = chrono::Local::now().naive_local();
+    let today_x = x_of(now);
+    if inner.x_range().contains(today_x) {
+        painter.line_segment(
+            [Pos2::new(today_x, rect.top() + 4.0), Pos2::new(today_x, rect.bottom() - 12.0)],
+            Stroke::new(1.0, theme::today_line()),
+        );
+    }
+
+    let mut milestones: Vec<&Task> = tasks.iter().filter(|t| t.is_milestone).collect();
+    milestones.sort_by_key(|t| t.start);
+    if milestones.is_empty() {
+        painter.text(
+            rect.center() - Vec2::new(0.0, 10.0),
+            Align2::CENTER_CENTER,
+            "No milestones",
+            FontId::proportional(10.5),
+            theme::text_dim(),
+        );
+    }
+
+    for (i, task) in milestones.iter().enumerate() {
+        let status = task.milestone_status(now);
+        let center = Pos2::new(x_of(task.start), axis_y);
+        let points = vec![
+            center - Vec2::new(0.0, DIAMOND_SIZE),
+            center + Vec2::new(DIAMOND_SIZE, 0.0),
+            center + Vec2::new(0.0, DIAMOND_SIZE),
+            center - Vec2::new(DIAMOND_SIZE, 0.0),
+        ];
+        let outline = if selected_task == Some(task.id) {
+            Stroke::new(2.0, theme::text_primary())
+        } else {
+            Stroke::new(1.0, theme::bg_dark())
+        };
+        painter.add(egui::Shape::convex_polygon(points, status.status_color(), outline));
+
+        // Alternate labels above and below the axis to limit overlap.
+        let (anchor, align) = if i % 2 == 0 {
+            (center - Vec2::new(0.0, DIAMOND_SIZE + 3.0), Align2::CENTER_BOTTOM)
+        } else {
+            (center + Vec2::new(0.0, DIAMOND_SIZE + 3.0), Align2::CENTER_TOP)
+        };
+        painter.text(anchor, align, &task.name, FontId::proportional(10.0), theme::text_secondary());
+
+        let hit = Rect::from_center_size(center, Vec2::splat(DIAMOND_SIZE * 2.0 + 6.0));
+        let resp = ui
+            .interact(hit, Id::new(("milestone-strip", task.id)), Sense::click())
+            .on_hover_text(format!(
+                "{}\n{}\n{}",
+                task.name,
+                task.start.format("%Y-%m-%d %H:%M"),
+                status.label()
+            ));
+        if resp.clicked() {
+            clicked = Some(task.id);
+        }
+    }
+
+    clicked
+}
@@ -1,9 +1,35 @@
+pub mod activity_panel;
+pub mod agenda_panel;
+pub mod bulk_editor;
+pub mod calendar_view;
+pub mod chart_capture;
+pub mod command_palette;
+pub mod compare_panel;
+pub mod dependency_panel;
 pub mod dialogs;
 pub mod filter_bar;
 pub mod gantt_chart;
+pub mod inline_rename;
+pub mod legend;
+pub mod milestone_strip;
+pub mod model_ext;
+pub mod network_view;
+pub mod onboarding;
+pub mod progress_chart;
+pub mod resource_view;
+pub mod row_metrics;
+pub mod row_sync;
+pub mod rows;
+pub mod save_hook_panel;
+pub mod scroll_anchor;
+pub mod shortcuts_dialog;
+pub mod status_bar;
 pub mod task_editor;
 pub mod task_table;
 pub mod theme_def;
 pub mod theme_manager;
+pub mod theme_overrides;
 pub mod theme;
+pub mod toasts;
 pub mod toolbar;
+pub mod view_tabs;
@@ -0,0 +1,108 @@
+//! UI-side additions to the model types from `gantt-core`, which knows
+//! nothing about egui: colour conversions and icons.
+
+use crate::model::highlight::{DueState, Highlight};
+use crate::model::risk::RiskLevel;
+use crate::model::task::{MilestoneStatus, ScheduleStatus, TaskPriority};
+use crate::model::Rgba;
+use crate::ui::theme::{self, Severity};
+use egui::Color32;
+
+/// A model colour as egui draws it.
+pub trait ToColor32 {
+    fn to_color32(self) -> Color32;
+}
+
+impl ToColor32 for Rgba {
+    fn to_color32(self) -> Color32 {
+        Color32::from_rgba_premultiplied(self.r, self.g, self.b, self.a)
+    }
+}
+
+/// An egui colour as the model stores it.
+pub trait ToRgba {
+    fn to_rgba(self) -> Rgba;
+}
+
+impl ToRgba for Color32 {
+    fn to_rgba(self) -> Rgba {
+        Rgba::from_rgba_premultiplied(self.r(), self.g(), self.b(), self.a())
+    }
+}
+
+/// Phosphor icon shown next to a priority.
+pub trait PriorityIcon {
+    fn icon(self) -> &'static str;
+}
+
+impl PriorityIcon for TaskPriority {
+    fn icon(self) -> &'static str {
+        match self {
+            TaskPriority::None     => "",
+            TaskPriority::Low      => egui_phosphor::regular::ARROW_DOWN,
+            TaskPriority::Medium   => egui_phosphor::regular::EQUALS,
+            TaskPriority::High     => egui_phosphor::regular::ARROW_UP,
+            TaskPriority::Critical => egui_phosphor::regular::WARNING,
+        }
+    }
+}
+
+/// A status's colour as drawn, following the colour-blind-safe option
+/// (see [`theme::status_color`]).
+pub trait StatusColor {
+    fn status_color(self) -> Color32;
+}
+
+impl StatusColor for ScheduleStatus {
+    fn status_color(self) -> Color32 {
+        let severity = match self {
+            ScheduleStatus::OnTrack  => Severity::Good,
+            ScheduleStatus::Slipping => Severity::Warning,
+            ScheduleStatus::Behind   => Severity::Bad,
+        };
+        theme::status_color(severity, self.color().to_color32())
+    }
+}
+
+impl StatusColor for MilestoneStatus {
+    fn status_color(self) -> Color32 {
+        let severity = match self {
+            MilestoneStatus::Hit      => Severity::Good,
+            MilestoneStatus::Upcoming => Severity::Warning,
+            MilestoneStatus::Overdue  => Severity::Bad,
+        };
+        theme::status_color(severity, self.color().to_color32())
+    }
+}
+
+impl StatusColor for Highlight {
+    fn status_color(self) -> Color32 {
+        let severity = match self {
+            Highlight::AtRisk  => Severity::Warning,
+            Highlight::Overdue => Severity::Bad,
+        };
+        theme::status_color(severity, self.color().to_color32())
+    }
+}
+
+impl StatusColor for DueState {
+    fn status_color(self) -> Color32 {
+        let severity = match self {
+            DueState::Done    => Severity::Good,
+            DueState::DueSoon => Severity::Warning,
+            DueState::Late    => Severity::Bad,
+        };
+        theme::status_color(severity, self.color().to_color32())
+    }
+}
+
+impl StatusColor for RiskLevel {
+    fn status_color(self) -> Color32 {
+        let severity = match self {
+            RiskLevel::Low    => Severity::Good,
+            RiskLevel::Medium => Severity::Warning,
+            RiskLevel::High   => Severity::Bad,
+        };
+        theme::status_color(severity, self.color().to_color32())
+    }
+}
@@ -0,0 +1,310 @@
+//! Dependency network diagram.
+//!
+//! Linked tasks are drawn as boxes in columns by topological rank (longest
+//! chain of predecessors), ordered within a column by the average position
+//! of their predecessors to reduce crossings. Links on a cycle are red and
+//! are ignored by the layout; critical-path links are drawn heavier.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::model::graph::DependencyGraph;
+use crate::model::schedule::critical_tasks;
+use crate::model::validation::dependency_bound;
+use crate::model::Task;
+use crate::ui::theme;
+use crate::ui::model_ext::ToColor32;
+use egui::epaint::CubicBezierShape;
+use egui::{Align2, Color32, FontId, Id, Pos2, Rect, Rounding, Sense, Stroke, Ui, Vec2};
+use uuid::Uuid;
+
+/// Actions the network view can request.
+pub enum NetworkAction {
+    None,
+    Select(Uuid),
+}
+
+const NODE_SIZE: Vec2 = Vec2::new(160.0, 44.0);
+const COLUMN_GAP: f32 = 90.0;
+const ROW_GAP: f32 = 22.0;
+const CYCLE_COLOR: Color32 = Color32::from_rgb(220, 60, 60);
+
+/// Pan offset and zoom, kept in temp data between frames.
+#[derive(Clone, Copy)]
+struct NetworkViewState {
+    offset: Vec2,
+    zoom: f32,
+}
+
+impl Default for NetworkViewState {
+    fn default() -> Self {
+        Self { offset: Vec2::new(24.0, 24.0), zoom: 1.0 }
+    }
+}
+
+/// Derived data kept between frames until the project changes.
+struct NetworkCache {
+    positions: HashMap<Uuid, Pos2>,
+    critical: HashSet<Uuid>,
+}
+
+/// Column and row of every linked task, in unzoomed layout units.
+fn layout(tasks: &[Task], graph: &DependencyGraph) -> HashMap<Uuid, Pos2> {
+    let linked: Vec<&Task> = tasks.iter().filter(|t| graph.is_linked(t.id)).collect();
+
+    // Rank = longest chain of (non-cyclic) predecessors.
+    let mut rank: HashMap<Uuid, usize> = HashMap::new();
+    for &id in graph.topo_order() {
+        if !linked.iter().any(|t| t.id == id) {
+            continue;
+        }
+        let r = graph
+            .predecessors(id)
+            .iter()
+            .filter(|d| !graph.is_cyclic_edge(d))
+            .filter_map(|d| rank.get(&d.from_task).map(|r| r + 1))
+            .max()
+            .unwrap_or(0);
+        rank.insert(id, r);
+    }
+    // Tasks only reachable through a cycle fall back to rank 0.
+    for t in &linked {
+        rank.entry(t.id).or_insert(0);
+    }
+
+    let columns = rank.values().copied().max().map_or(0, |m| m + 1);
+    let mut row_of: HashMap<Uuid, f32> = HashMap::new();
+    let mut positions = HashMap::new();
+    for col in 0..columns {
+        let mut members: Vec<(&Task, f32)> = linked
+            .iter()
+            .filter(|t| rank[&t.id] == col)
+            .map(|t| {
+                let preds: Vec<f32> = graph
+                    .predecessors(t.id)
+                    .iter()
+                    .filter_map(|d| row_of.get(&d.from_task).copied())
+                    .collect();
+                let key = if preds.is_empty() {
+                    f32::MAX
+                } else {
+                    preds.iter().sum::<f32>() / preds.len() as f32
+                };
+                (*t, key)
+            })
+            .collect();
+        // Barycentre first, then start date for tasks without placed predecessors.
+        members.sort_by(|(a, ka), (b, kb)| ka.total_cmp(kb).then(a.start.cmp(&b.start)));
+        for (row, (task, _)) in members.into_iter().enumerate() {
+            row_of.insert(task.id, row as f32);
+            positions.insert(
+                task.id,
+                Pos2::new(
+                    col as f32 * (NODE_SIZE.x + COLUMN_GAP),
+                    row as f32 * (NODE_SIZE.y + ROW_GAP),
+                ),
+            );
+        }
+    }
+    positions
+}
+
+/// Render the dependency network view.
+pub fn show_network_view(
+    tasks: &[Task],
+    graph: &DependencyGraph,
+    generation: u64,
+    selected_task: Option<Uuid>,
+    selection: &[Uuid],
+    ui: &mut Ui,
+) -> NetworkAction {
+    let mut action = NetworkAction::None;
+    let state_id = Id::new("network-view");
+    let mut state: NetworkViewState = ui.ctx().data_mut(|d| d.get_temp(state_id)).unwrap_or_default();
+
+    let (rect, response) = ui.allocate_exact_size(ui.available_size(), Sense::click_and_drag());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, theme::bg_dark());
+
+    // Pan by dragging the background, zoom with the scroll wheel around the pointer.
+    if response.dragged() {
+        state.offset += response.drag_delta();
+    }
+    if let Some(hover) = response.hover_pos() {
+        let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+        if scroll != 0.0 {
+            let old = state.zoom;
+            state.zoom = (state.zoom * (1.0 + scroll * 0.001)).clamp(0.3, 2.5);
+            let anchor = hover - rect.min - state.offset;
+            state.offset -= anchor * (state.zoom / old - 1.0);
+        }
+    }
+    if response.double_clicked() {
+        state = NetworkViewState::default();
+    }
+
+    // Layout and critical path only change with the project.
+    let cache_id = Id::new("network-view-cache");
+    let cached: Option<(u64, Arc<NetworkCache>)> = ui.ctx().data_mut(|d| d.get_temp(cache_id));
+    let cache = match cached {
+        Some((g, cache)) if g == generation => cache,
+        _ => {
+            let cache = Arc::new(NetworkCache {
+                positions: layout(tasks, graph),
+                critical: critical_tasks(tasks, graph),
+            });
+            ui.ctx().data_mut(|d| d.insert_temp(cache_id, (generation, Arc::clone(&cache))));
+            cache
+        }
+    };
+    let positions = &cache.positions;
+    if positions.is_empty() {
+        painter.text(
+            rect.center(),
+            Align2::CENTER_CENTER,
+            "No dependencies yet — Shift+drag between bars to link tasks",
+            FontId::proportional(13.0),
+            theme::text_dim(),
+        );
+        ui.ctx().data_mut(|d| d.insert_temp(state_id, state));
+        return action;
+    }
+
+    let zoom = state.zoom;
+    let to_screen = |p: Pos2| rect.min + state.offset + p.to_vec2() * zoom;
+    let node_rect = |id: &Uuid| positions.get(id).map(|p| Rect::from_min_size(to_screen(*p), NODE_SIZE * zoom));
+
+    let groups = graph.cycle_groups();
+    let critical = &cache.critical;
+    let by_id: HashMap<Uuid, &Task> = tasks.iter().map(|t| (t.id, t)).collect();
+
+    // ── Edges ─────────────────────────────────────────────────────────
+    for dep in graph.topo_order().iter().flat_map(|&id| graph.successors(id)) {
+        let (Some(from), Some(to)) = (node_rect(&dep.from_task), node_rect(&dep.to_task)) else {
+            continue;
+        };
+        let cyclic = graph.is_cyclic_edge(dep);
+        let on_critical_path = !cyclic
+            && critical.contains(&dep.from_task)
+            && critical.contains(&dep.to_task)
+            && match (by_id.get(&dep.from_task), by_id.get(&dep.to_task)) {
+                (Some(pred), Some(succ)) => {
+                    // Driving link: the successor sits right at the bound.
+                    let (constrains_start, earliest) = dependency_bound(dep, pred);
+                    let anchor = if constrains_start { succ.start } else { succ.end };
+                    anchor <= earliest
+                }
+                _ => false,
+            };
+        let stroke = if cyclic {
+            Stroke::new(2.0 * zoom.max(0.6), CYCLE_COLOR)
+        } else if on_critical_path {
+            Stroke::new(2.8 * zoom.max(0.6), theme::accent())
+        } else {
+            Stroke::new(1.2 * zoom.max(0.6), theme::dep_arrow())
+        };
+
+        let start = Pos2::new(from.right(), from.center().y);
+        let end = Pos2::new(to.left(), to.center().y);
+        let bend = ((end.x - start.x).abs() * 0.5).max(40.0 * zoom);
+        let curve = CubicBezierShape::from_points_stroke(
+            [start, start + Vec2::new(bend, 0.0), end - Vec2::new(bend, 0.0), end],
+            false,
+            Color32::TRANSPARENT,
+            stroke,
+        );
+        let mid = curve.sample(0.5);
+        painter.add(curve);
+        // Arrowhead
+        let tip = end;
+        let size = 6.0 * zoom.max(0.6);
+        painter.add(egui::Shape::convex_polygon(
+            vec![tip, tip - Vec2::new(size, size * 0.6), tip - Vec2::new(size, -size * 0.6)],
+            stroke.color,
+            Stroke::NONE,
+        ));
+
+        if zoom >= 0.6 {
+            let label = if dep.lag_days == 0 {
+                dep.kind.short_label().to_string()
+            } else {
+                format!("{} {:+}d", dep.kind.short_label(), dep.lag_days)
+            };
+            let galley = painter.layout_no_wrap(label, FontId::proportional(9.5 * zoom), theme::text_secondary());
+            let bg = Rect::from_center_size(mid, galley.size() + Vec2::new(6.0, 2.0));
+            painter.rect_filled(bg, Rounding::same(3.0), theme::bg_panel());
+            painter.galley(bg.min + Vec2::new(3.0, 1.0), galley, theme::text_secondary());
+        }
+    }
+
+    // ── Nodes ─────────────────────────────────────────────────────────
+    for task in tasks {
+        let Some(node) = node_rect(&task.id) else {
+            continue;
+        };
+        let resp = ui.interact(node, Id::new(("network-node", task.id)), Sense::click());
+        let is_selected = selected_task == Some(task.id) || selection.contains(&task.id);
+        let border = if groups.contains_key(&task.id) {
+            Stroke::new(1.5, CYCLE_COLOR)
+        } else if is_selected {
+            Stroke::new(2.0, theme::text_primary())
+        } else if critical.contains(&task.id) {
+            Stroke::new(1.5, theme::accent())
+        } else {
+            Stroke::new(1.0, theme::border_subtle())
+        };
+        let fill = if resp.hovered() { theme::widget_bg_hovered() } else { theme::bg_panel() };
+        painter.rect(node, Rounding::same(5.0 * zoom), fill, border);
+        // Colour tab on the left edge
+        painter.rect_filled(
+            Rect::from_min_size(node.min, Vec2::new(5.0 * zoom, node.height())),
+            Rounding { nw: 5.0 * zoom, sw: 5.0 * zoom, ..Rounding::ZERO },
+            task.color.to_color32(),
+        );
+
+        if zoom >= 0.45 {
+            let clip = painter.with_clip_rect(node.shrink(2.0).intersect(rect));
+            let icon = if task.is_milestone { "◆ " } else { "" };
+            clip.text(
+                node.left_top() + Vec2::new(10.0, 6.0) * zoom,
+                Align2::LEFT_TOP,
+                format!("{}{}", icon, task.name),
+                FontId::proportional(11.5 * zoom),
+                theme::text_primary(),
+            );
+            clip.text(
+                node.left_bottom() + Vec2::new(10.0, -6.0) * zoom,
+                Align2::LEFT_BOTTOM,
+                if task.is_milestone {
+                    task.start.format("%b %d").to_string()
+                } else {
+                    format!("{} – {}", task.start.format("%b %d"), task.end.format("%b %d"))
+                },
+                FontId::proportional(9.5 * zoom),
+                theme::text_dim(),
+            );
+        }
+
+        if resp.clicked() {
+            action = NetworkAction::Select(task.id);
+        }
+        resp.on_hover_text(format!(
+            "{}\n{} → {}",
+            task.name,
+            task.start.format("%Y-%m-%d %H:%M"),
+            task.end.format("%Y-%m-%d %H:%M")
+        ));
+    }
+
+    // Legend
+    let legend = rect.left_bottom() + Vec2::new(10.0, -10.0);
+    let hint = if !graph.has_cycle() {
+        "Drag to pan · scroll to zoom · double-click to reset"
+    } else {
+        "Red links form a cycle · drag to pan · scroll to zoom"
+    };
+    painter.text(legend, Align2::LEFT_BOTTOM, hint, FontId::proportional(10.0), theme::text_dim());
+
+    ui.ctx().data_mut(|d| d.insert_temp(state_id, state));
+    action
+}
@@ -0,0 +1,248 @@
+//! Help for new users: the overlay on an empty chart and the first-run tour.
+//!
+//! The tour is a sequence of coach marks, each pointing at a part of the
+//! window. Those parts are [`mark_target`]ed as they are laid out, and
+//! [`show_tour`] draws the current step beside its target at the end of the
+//! frame; a step whose target isn't on screen (its panel is hidden) is shown
+//! in the middle of the window instead. Whether the tour was finished or
+//! skipped is kept in the settings, so it only runs once.
+
+use std::collections::HashMap;
+
+use egui::{Align2, Color32, Context, Id, Order, Pos2, Rect, RichText, Sense, Shape, Stroke, Ui, Vec2};
+
+use crate::i18n::{tr, trf};
+use crate::model::task::DependencyKind;
+use crate::ui::gantt_chart::draw_link_sample;
+use crate::ui::theme;
+
+/// Parts of the window a coach mark can point at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CoachTarget {
+    FilterBar,
+    Editor,
+    Zoom,
+}
+
+/// One step of a tour: where it points, and the i18n keys of what it says.
+pub struct CoachMark {
+    pub target: CoachTarget,
+    pub title: &'static str,
+    pub body: &'static str,
+}
+
+/// The first-run tour, in order.
+pub const TOUR: &[CoachMark] = &[
+    CoachMark { target: CoachTarget::FilterBar, title: "tour.filter.title", body: "tour.filter.body" },
+    CoachMark { target: CoachTarget::Editor, title: "tour.editor.title", body: "tour.editor.body" },
+    CoachMark { target: CoachTarget::Zoom, title: "tour.zoom.title", body: "tour.zoom.body" },
+];
+
+/// Width of a coach mark's card.
+const CARD_WIDTH: f32 = 260.0;
+/// Space between a coach mark and its target, where the pointer goes.
+const CARD_GAP: f32 = 12.0;
+
+fn targets_id() -> Id {
+    Id::new("coach-mark-targets")
+}
+
+fn step_id() -> Id {
+    Id::new("coach-mark-step")
+}
+
+/// Record where `target` is this frame.
+pub fn mark_target(ctx: &Context, target: CoachTarget, rect: Rect) {
+    let frame = ctx.cumulative_pass_nr();
+    ctx.data_mut(|d| {
+        d.get_temp_mut_or_default::<HashMap<CoachTarget, (Rect, u64)>>(targets_id())
+            .insert(target, (rect, frame));
+    });
+}
+
+/// Where `target` was drawn this frame or the last, if it was.
+fn target_rect(ctx: &Context, target: CoachTarget) -> Option<Rect> {
+    let (rect, frame) = ctx.data(|d| {
+        d.get_temp::<HashMap<CoachTarget, (Rect, u64)>>(targets_id())
+            .and_then(|targets| targets.get(&target).copied())
+    })?;
+    (frame + 1 >= ctx.cumulative_pass_nr()).then_some(rect)
+}
+
+/// Start the tour again from its first step.
+pub fn restart_tour(ctx: &Context) {
+    ctx.data_mut(|d| d.insert_temp(step_id(), 0usize));
+}
+
+/// Draw the tour's current coach mark. Returns true once the user has
+/// finished or skipped it.
+pub fn show_tour(ctx: &Context) -> bool {
+    let step = ctx.data(|d| d.get_temp::<usize>(step_id())).unwrap_or(0);
+    let Some(mark) = TOUR.get(step) else {
+        return true;
+    };
+    let target = target_rect(ctx, mark.target);
+    let screen = ctx.screen_rect();
+    // Beside targets on the left of the window, above the others.
+    let (pivot, at) = match target {
+        Some(rect) if rect.center().x < screen.center().x => {
+            (Align2::LEFT_TOP, rect.right_top() + Vec2::new(CARD_GAP, 0.0))
+        }
+        Some(rect) => (Align2::RIGHT_BOTTOM, rect.right_top() - Vec2::new(0.0, CARD_GAP)),
+        None => (Align2::CENTER_CENTER, screen.center()),
+    };
+
+    let mut next = None;
+    let card = egui::Area::new(Id::new("coach-mark"))
+        .order(Order::Foreground)
+        .pivot(pivot)
+        .fixed_pos(at)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style())
+                .stroke(Stroke::new(1.5, theme::accent()))
+                .show(ui, |ui| {
+                    ui.set_width(CARD_WIDTH);
+                    let count = trf("tour.step", &[("step", &(step + 1)), ("steps", &TOUR.len())]);
+                    ui.label(RichText::new(count).size(10.0).color(theme::text_dim()));
+                    ui.label(RichText::new(tr(mark.title)).size(13.0).strong().color(theme::text_primary()));
+                    ui.label(RichText::new(tr(mark.body)).color(theme::text_secondary()));
+                    ui.add_space(6.0);
+                    ui.horizontal(|ui| {
+                        let last = step + 1 == TOUR.len();
+                        if ui.button(tr(if last { "tour.done" } else { "tour.next" })).clicked() {
+                            next = Some(step + 1);
+                        }
+                        if !last && ui.button(tr("tour.skip")).clicked() {
+                            next = Some(TOUR.len());
+                        }
+                    });
+                });
+        });
+
+    if let Some(rect) = target {
+        let painter = ctx.layer_painter(egui::LayerId::new(Order::Foreground, Id::new("coach-mark-pointer")));
+        painter.rect_stroke(rect.expand(2.0), 4.0, Stroke::new(2.0, theme::accent()));
+        let card = card.response.rect;
+        let (tip, base) = if pivot == Align2::LEFT_TOP {
+            let y = (card.top() + 18.0).clamp(rect.top(), rect.bottom());
+            (Pos2::new(rect.right() + 3.0, y), [Pos2::new(card.left(), y - 7.0), Pos2::new(card.left(), y + 7.0)])
+        } else {
+            let x = rect.center().x.clamp(card.left() + 12.0, card.right() - 12.0);
+            (Pos2::new(x, rect.top() - 3.0), [Pos2::new(x - 7.0, card.bottom()), Pos2::new(x + 7.0, card.bottom())])
+        };
+        painter.add(Shape::convex_polygon(vec![tip, base[0], base[1]], theme::accent(), Stroke::NONE));
+    }
+
+    match next {
+        Some(step) if step >= TOUR.len() => true,
+        Some(step) => {
+            ctx.data_mut(|d| d.insert_temp(step_id(), step));
+            false
+        }
+        None => false,
+    }
+}
+
+/// What the user picked on the empty-chart overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmptyStateAction {
+    None,
+    AddTask,
+    LoadExample,
+}
+
+/// Size of each hint's illustration.
+const HINT_SIZE: Vec2 = Vec2::new(120.0, 56.0);
+
+/// The overlay shown in the middle of `rect` while the project has no
+/// tasks: a button to add the first one, a link to the example project,
+/// and how to move, link and zoom.
+pub fn show_empty_state(ui: &mut Ui, rect: Rect) -> EmptyStateAction {
+    let mut action = EmptyStateAction::None;
+    let size = Vec2::new(3.0 * HINT_SIZE.x + 80.0, 250.0).min(rect.size());
+    let card = Rect::from_center_size(rect.center(), size);
+    ui.allocate_new_ui(egui::UiBuilder::new().max_rect(card), |ui| {
+        egui::Frame::popup(ui.style()).fill(theme::bg_panel()).show(ui, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(6.0);
+                ui.label(RichText::new(tr("empty.title")).size(18.0).strong().color(theme::text_primary()));
+                ui.label(RichText::new(tr("empty.subtitle")).color(theme::text_secondary()));
+                ui.add_space(10.0);
+                let add = egui::Button::new(
+                    RichText::new(format!("{}  {}", egui_phosphor::regular::PLUS, tr("empty.add_task")))
+                        .size(15.0)
+                        .color(theme::text_on(theme::accent())),
+                )
+                .fill(theme::accent())
+                .min_size(Vec2::new(200.0, 34.0));
+                if ui.add(add).clicked() {
+                    action = EmptyStateAction::AddTask;
+                }
+                if ui.link(tr("empty.load_example")).clicked() {
+                    action = EmptyStateAction::LoadExample;
+                }
+                ui.add_space(12.0);
+            });
+            ui.columns(3, |columns| {
+                hint(&mut columns[0], tr("empty.hint.move"), draw_move_hint);
+                hint(&mut columns[1], tr("empty.hint.link"), draw_link_hint);
+                hint(&mut columns[2], tr("empty.hint.zoom"), draw_zoom_hint);
+            });
+        });
+    });
+    action
+}
+
+/// An illustration drawn by `draw`, captioned `text`.
+fn hint(ui: &mut Ui, text: &str, draw: fn(&egui::Painter, Rect)) {
+    ui.vertical_centered(|ui| {
+        let (rect, _) = ui.allocate_exact_size(HINT_SIZE, Sense::hover());
+        ui.painter().rect_filled(rect, 4.0, theme::bg_dark());
+        draw(ui.painter(), rect.shrink(8.0));
+        ui.label(RichText::new(text).size(11.0).color(theme::text_secondary()));
+    });
+}
+
+fn sample_bar(painter: &egui::Painter, rect: Rect, color: Color32) {
+    painter.rect_filled(rect, 3.0, color);
+}
+
+/// A bar and a faint copy of it further along, with the arrow between.
+fn draw_move_hint(painter: &egui::Painter, rect: Rect) {
+    let bar = Rect::from_min_size(rect.left_center() - Vec2::new(0.0, 7.0), Vec2::new(rect.width() * 0.5, 14.0));
+    let moved = bar.translate(Vec2::new(rect.width() * 0.5, 0.0));
+    sample_bar(painter, bar, theme::task_color(0).gamma_multiply(0.35));
+    sample_bar(painter, moved, theme::task_color(0));
+    let y = bar.bottom() + 8.0;
+    draw_link_sample(painter, Pos2::new(bar.center().x, y), Pos2::new(moved.center().x, y), DependencyKind::FinishToStart, theme::text_dim());
+}
+
+/// Two bars, one linked to the other.
+fn draw_link_hint(painter: &egui::Painter, rect: Rect) {
+    let first = Rect::from_min_size(rect.left_top(), Vec2::new(rect.width() * 0.45, 12.0));
+    let second = Rect::from_min_size(rect.center_bottom() + Vec2::new(8.0, -12.0), Vec2::new(rect.width() * 0.45, 12.0));
+    sample_bar(painter, first, theme::task_color(1));
+    sample_bar(painter, second, theme::task_color(2));
+    let from = first.right_center();
+    draw_link_sample(painter, from, second.left_center(), DependencyKind::FinishToStart, theme::dep_arrow());
+}
+
+/// Timeline ticks spreading out under a magnifier.
+fn draw_zoom_hint(painter: &egui::Painter, rect: Rect) {
+    let stroke = Stroke::new(1.0, theme::text_dim());
+    let baseline = rect.bottom() - 4.0;
+    let mut x = rect.left();
+    let mut step = 3.0;
+    while x <= rect.right() {
+        painter.line_segment([Pos2::new(x, baseline - 8.0), Pos2::new(x, baseline)], stroke);
+        x += step;
+        step *= 1.35;
+    }
+    painter.text(
+        Pos2::new(rect.center().x, rect.top() + 10.0),
+        Align2::CENTER_CENTER,
+        egui_phosphor::regular::MAGNIFYING_GLASS_PLUS,
+        egui::FontId::proportional(20.0),
+        theme::text_primary(),
+    );
+}
@@ -0,0 +1,228 @@
+//! Planned vs actual completion over time.
+//!
+//! Planned completion comes from the task date ranges; actual completion
+//! from the dated samples the project records whenever progress changes.
+//! The header adds the earned-value indices of tasks with an estimate, as of
+//! a status date picked there, and, once tasks are rated, how the remaining
+//! work splits by risk.
+
+use crate::model::earned_value::EarnedValue;
+use crate::model::Project;
+use crate::model::risk::RiskLevel;
+use crate::ui::model_ext::StatusColor;
+use crate::ui::theme;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use egui::{RichText, Ui};
+use egui_plot::{Legend, Line, Plot, PlotPoints, VLine};
+use std::sync::Arc;
+
+fn to_x(date: NaiveDate) -> f64 {
+    date.num_days_from_ce() as f64
+}
+
+fn from_x(x: f64) -> Option<NaiveDate> {
+    NaiveDate::from_num_days_from_ce_opt(x.round() as i32)
+}
+
+/// Actual completion on `date`: the latest sample on or before it.
+fn actual_on(project: &Project, date: NaiveDate) -> Option<f32> {
+    project
+        .progress_history
+        .iter()
+        .rev()
+        .find(|s| s.date <= date)
+        .map(|s| s.completion)
+}
+
+/// Planned completion at the end of `date`.
+fn planned_on(project: &Project, date: NaiveDate) -> f32 {
+    let end_of_day = (date + Duration::days(1)).and_time(NaiveTime::MIN);
+    project.planned_completion(end_of_day)
+}
+
+/// When earned value is measured for the status date `date`: now when that
+/// is today, else the end of the day, to the minute.
+fn status_time(date: NaiveDate, now: NaiveDateTime) -> NaiveDateTime {
+    let time = if date == now.date() { now } else { (date + Duration::days(1)).and_time(NaiveTime::MIN) };
+    time.with_second(0).and_then(|t| t.with_nanosecond(0)).unwrap_or(time)
+}
+
+/// The project's earned value at `status`, worked out again only when the
+/// project or the status time changes.
+fn earned_value_at(project: &Project, status: NaiveDateTime, ui: &Ui) -> EarnedValue {
+    let cache_id = egui::Id::new("progress-chart-earned-value");
+    let key = (project.generation(), status);
+    let cached: Option<((u64, NaiveDateTime), EarnedValue)> = ui.ctx().data_mut(|d| d.get_temp(cache_id));
+    match cached {
+        Some((cached_key, ev)) if cached_key == key => ev,
+        _ => {
+            let ev = project.earned_value(status);
+            ui.ctx().data_mut(|d| d.insert_temp(cache_id, (key, ev)));
+            ev
+        }
+    }
+}
+
+/// Remaining work by risk level, highest first, as shares of all the work
+/// left. Left out until some unfinished task is rated.
+fn risk_breakdown(project: &Project, ui: &mut Ui) {
+    let risk = project.risk_breakdown();
+    if !risk.is_rated() {
+        return;
+    }
+    let mut details = format!("{:.1} h of scheduled work left", risk.total());
+    ui.horizontal(|ui| {
+        ui.add_space(8.0);
+        ui.spacing_mut().item_spacing.x = 4.0;
+        ui.label(RichText::new("Remaining work by risk:").size(11.0).color(theme::text_secondary()));
+        for &level in RiskLevel::all().iter().rev() {
+            let i = level as usize;
+            ui.label(
+                RichText::new(format!("{} {:.0}%", level.label(), risk.share(Some(level)) * 100.0))
+                    .size(11.0)
+                    .color(level.status_color()),
+            );
+            details.push_str(&format!(
+                "\n{}: {:.1} h across {} open task{}",
+                level.label(),
+                risk.remaining[i],
+                risk.open[i],
+                if risk.open[i] == 1 { "" } else { "s" }
+            ));
+        }
+        if risk.unrated > 0.0 {
+            ui.label(
+                RichText::new(format!("Not rated {:.0}%", risk.share(None) * 100.0))
+                    .size(11.0)
+                    .color(theme::text_dim()),
+            );
+            details.push_str(&format!("\nNot rated: {:.1} h", risk.unrated));
+        }
+    })
+    .response
+    .on_hover_text(details);
+}
+
+/// Render the progress-over-time chart.
+pub fn show_progress_chart(project: &Project, ui: &mut Ui) {
+    let now = project.clock().now();
+    let today = now.date();
+    let (Some(start), Some(end)) = (
+        project.tasks.iter().map(|t| t.start.date()).min(),
+        project.tasks.iter().map(|t| t.end.date()).max(),
+    ) else {
+        ui.centered_and_justified(|ui| {
+            ui.label(RichText::new("No tasks to chart yet").color(theme::text_dim()));
+        });
+        return;
+    };
+
+    let status_id = egui::Id::new("progress-chart-status-date");
+    let status_date: NaiveDate = ui.ctx().data_mut(|d| d.get_temp(status_id)).unwrap_or(today);
+
+    ui.add_space(6.0);
+    ui.horizontal(|ui| {
+        ui.add_space(8.0);
+        ui.label(RichText::new("Progress").strong().size(14.0).color(theme::text_primary()));
+        ui.label(
+            RichText::new(format!(
+                "{:.0}% done · {:.0}% planned by today",
+                project.completion() * 100.0,
+                planned_on(project, today) * 100.0
+            ))
+            .size(11.0)
+            .color(theme::text_secondary()),
+        );
+        let ev = earned_value_at(project, status_time(status_date, now), ui);
+        if ev.planned > 0.0 || ev.earned > 0.0 || ev.actual > 0.0 {
+            let mut details = format!(
+                "Planned value: {:.1} h\nEarned value: {:.1} h\nActual cost: {:.1} h tracked",
+                ev.planned, ev.earned, ev.actual
+            );
+            if ev.excluded > 0 {
+                details.push_str(&format!("\n{} tasks without an estimate left out", ev.excluded));
+            }
+            ui.label(RichText::new(format!("· {}", ev.summary())).size(11.0).color(theme::text_secondary()))
+                .on_hover_text(details);
+            ui.label(RichText::new("as of").size(11.0).color(theme::text_secondary()));
+            let mut picked = status_date;
+            let picker = ui
+                .add(egui_extras::DatePickerButton::new(&mut picked).id_salt("progress-chart-status-date"))
+                .on_hover_text("Status date the earned value is measured at");
+            if picker.changed() {
+                ui.ctx().data_mut(|d| d.insert_temp(status_id, picked));
+            }
+            if status_date != today && ui.small_button("Today").clicked() {
+                ui.ctx().data_mut(|d| d.remove::<NaiveDate>(status_id));
+            }
+        }
+    });
+    risk_breakdown(project, ui);
+
+    // The planned curve scans every task per day; rebuild it only when the
+    // project changes.
+    let cache_id = egui::Id::new("progress-chart-planned");
+    let cached: Option<(u64, Arc<Vec<[f64; 2]>>)> = ui.ctx().data_mut(|d| d.get_temp(cache_id));
+    let planned = match cached {
+        Some((generation, planned)) if generation == project.generation() => planned,
+        _ => {
+            let planned: Arc<Vec<[f64; 2]>> = Arc::new(
+                start
+                    .iter_days()
+                    .take_while(|d| *d <= end)
+                    .map(|d| [to_x(d), planned_on(project, d) as f64 * 100.0])
+                    .collect(),
+            );
+            ui.ctx().data_mut(|d| d.insert_temp(cache_id, (project.generation(), Arc::clone(&planned))));
+            planned
+        }
+    };
+
+    // Step line through the samples, carried forward to today.
+    let mut actual: Vec<[f64; 2]> = Vec::new();
+    for sample in &project.progress_history {
+        if let Some(&[_, prev]) = actual.last() {
+            actual.push([to_x(sample.date), prev]);
+        }
+        actual.push([to_x(sample.date), sample.completion as f64 * 100.0]);
+    }
+    if let Some(&[x, y]) = actual.last() {
+        if x < to_x(today) {
+            actual.push([to_x(today), y]);
+        }
+    }
+
+    let accent = theme::accent();
+    let planned_color = theme::text_dim();
+    Plot::new("progress-chart")
+        .legend(Legend::default())
+        .include_y(0.0)
+        .include_y(100.0)
+        .allow_scroll(false)
+        .y_axis_formatter(|mark, _| format!("{:.0}%", mark.value))
+        .x_axis_formatter(|mark, _| from_x(mark.value).map(|d| d.format("%b %d").to_string()).unwrap_or_default())
+        .label_formatter(|_, point| {
+            let Some(date) = from_x(point.x) else {
+                return String::new();
+            };
+            let actual = actual_on(project, date)
+                .map(|a| format!("{:.0}%", a * 100.0))
+                .unwrap_or_else(|| "—".to_string());
+            format!(
+                "{}\nPlanned: {:.0}%\nActual: {}",
+                date.format("%Y-%m-%d"),
+                planned_on(project, date) * 100.0,
+                actual
+            )
+        })
+        .show(ui, |plot_ui| {
+            plot_ui.line(Line::new(PlotPoints::from(planned.to_vec())).name("Planned").color(planned_color));
+            if !actual.is_empty() {
+                plot_ui.line(Line::new(PlotPoints::from(actual)).name("Actual").color(accent).width(2.0));
+            }
+            plot_ui.vline(VLine::new(to_x(today)).name("Today").color(theme::today_line()));
+            if status_date != today {
+                plot_ui.vline(VLine::new(to_x(status_date)).name("Status date").color(theme::text_secondary()));
+            }
+        });
+}
@@ -0,0 +1,265 @@
+//! Timeline with one row per assignee instead of per task.
+//!
+//! Shares the Gantt chart's [`TimelineViewport`], so zoom and date range stay
+//! in sync. Overlapping tasks within a person's row stack into sub-lanes and
+//! the overlapping spans are tinted as overallocation. Absences are hatched
+//! across the person's row. Dragging a bar onto another row reassigns the
+//! task.
+
+use crate::model::absence::Absence;
+use crate::model::clock::ProjectClock;
+use crate::model::task::assignee_names;
+use crate::model::{Task, TimelineViewport};
+use crate::ui::gantt_chart::{
+    draw_hatching, draw_timeline_header, draw_today_line, draw_weekend_bands, handle_ctrl_scroll_zoom,
+};
+use crate::ui::theme;
+use crate::ui::model_ext::ToColor32;
+use egui::{Align2, Color32, FontId, Id, Pos2, Rect, Rounding, Sense, Stroke, Ui, Vec2};
+use uuid::Uuid;
+
+/// Actions the resource view can request.
+pub enum ResourceAction {
+    None,
+    Select(Uuid),
+    /// Move the task to another person (`None` = unassigned).
+    Reassign(Uuid, Option<String>),
+}
+
+const LANE_GAP: f32 = 4.0;
+const ROW_PADDING: f32 = 6.0;
+/// Space at the top of each row for the person's name.
+const NAME_STRIP_HEIGHT: f32 = 18.0;
+const NAME_LABEL_WIDTH: f32 = 140.0;
+
+/// One person's row: their tasks, each with the sub-lane it occupies.
+struct ResourceRow<'a> {
+    assignee: Option<String>,
+    tasks: Vec<(&'a Task, usize)>,
+    lanes: usize,
+}
+
+/// Greedily place tasks into the first lane whose last task has ended.
+fn stack_lanes(mut tasks: Vec<&Task>) -> (Vec<(&Task, usize)>, usize) {
+    tasks.sort_by_key(|t| (t.start, t.end));
+    let mut lane_ends: Vec<chrono::NaiveDateTime> = Vec::new();
+    let mut placed = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        let lane = match lane_ends.iter().position(|end| *end <= task.start) {
+            Some(lane) => {
+                lane_ends[lane] = task.end;
+                lane
+            }
+            None => {
+                lane_ends.push(task.end);
+                lane_ends.len() - 1
+            }
+        };
+        placed.push((task, lane));
+    }
+    (placed, lane_ends.len().max(1))
+}
+
+/// Render the resource view.
+pub fn show_resource_view(
+    tasks: &[Task],
+    absences: &[Absence],
+    viewport: &mut TimelineViewport,
+    selected_task: Option<Uuid>,
+    selection: &[Uuid],
+    clock: ProjectClock,
+    ui: &mut Ui,
+) -> ResourceAction {
+    let mut action = ResourceAction::None;
+    let bar_height = theme::row_height() - 8.0;
+    let hh = theme::header_height();
+
+    // Leaf tasks only: a parent's span is already covered by its children.
+    let leaves: Vec<&Task> = tasks.iter().filter(|t| !t.has_children(tasks)).collect();
+    // Everyone with tasks, plus anyone who only has absences so far.
+    let mut names = assignee_names(tasks);
+    for absence in absences {
+        let name = absence.assignee.trim();
+        if !name.is_empty() && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    names.sort_by_key(|n| n.to_lowercase());
+    let mut people: Vec<Option<String>> = names.into_iter().map(Some).collect();
+    people.push(None);
+    let rows: Vec<ResourceRow> = people
+        .into_iter()
+        .map(|assignee| {
+            let mine = leaves
+                .iter()
+                .copied()
+                .filter(|t| t.assignee_name() == assignee.as_deref())
+                .collect();
+            let (tasks, lanes) = stack_lanes(mine);
+            ResourceRow { assignee, tasks, lanes }
+        })
+        .collect();
+    let row_height = |row: &ResourceRow| NAME_STRIP_HEIGHT + row.lanes as f32 * (bar_height + LANE_GAP) - LANE_GAP + ROW_PADDING;
+
+    let available = ui.available_size();
+    let chart_width = viewport.total_width().max(available.x);
+    let content_height = hh + rows.iter().map(row_height).sum::<f32>() + 40.0;
+
+    let drag_id = Id::new("resource-drag");
+
+    egui::ScrollArea::both()
+        .id_salt("resource-view")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            let (response, painter) = ui.allocate_painter(
+                Vec2::new(chart_width, content_height.max(available.y)),
+                Sense::hover(),
+            );
+            handle_ctrl_scroll_zoom(ui, &response, viewport);
+            let origin = response.rect.min;
+            painter.rect_filled(response.rect, 0.0, theme::bg_dark());
+
+            // Row bands, remembered for drop targeting.
+            let mut row_rects: Vec<(Rect, Option<String>)> = Vec::with_capacity(rows.len());
+            let mut y = origin.y + hh;
+            for (i, row) in rows.iter().enumerate() {
+                let rect = Rect::from_min_size(Pos2::new(origin.x, y), Vec2::new(chart_width, row_height(row)));
+                painter.rect_filled(rect, 0.0, if i % 2 == 0 { theme::bg_panel() } else { theme::bg_dark() });
+                painter.line_segment(
+                    [rect.left_bottom(), rect.right_bottom()],
+                    Stroke::new(0.5, theme::border_subtle()),
+                );
+                row_rects.push((rect, row.assignee.clone()));
+                y = rect.bottom();
+            }
+
+            draw_weekend_bands(&painter, origin, viewport, chart_width, origin.y + content_height, clock);
+
+            // Overallocation: spans where two of a person's tasks overlap.
+            let over_color = Color32::from_rgba_unmultiplied(220, 60, 60, 40);
+            for (row, (rect, _)) in rows.iter().zip(&row_rects) {
+                if row.assignee.is_none() {
+                    continue;
+                }
+                for (i, (a, _)) in row.tasks.iter().enumerate() {
+                    for (b, _) in &row.tasks[i + 1..] {
+                        let start = a.start.max(b.start);
+                        let end = a.end.min(b.end);
+                        if start < end {
+                            let x0 = origin.x + viewport.datetime_to_x(start);
+                            let x1 = origin.x + viewport.datetime_to_x(end);
+                            painter.rect_filled(
+                                Rect::from_x_y_ranges(x0..=x1, rect.y_range()),
+                                0.0,
+                                over_color,
+                            );
+                        }
+                    }
+                }
+            }
+
+            // Absences, hatched across the person's whole row.
+            let away_color = theme::text_dim().gamma_multiply(0.35);
+            for (row, (rect, _)) in rows.iter().zip(&row_rects) {
+                let Some(name) = &row.assignee else {
+                    continue;
+                };
+                for absence in absences.iter().filter(|a| a.is_for(name)) {
+                    let (start, end) = absence.span();
+                    let x0 = origin.x + viewport.datetime_to_x(start);
+                    let x1 = origin.x + viewport.datetime_to_x(end);
+                    let span = Rect::from_x_y_ranges(x0..=x1, rect.top() + NAME_STRIP_HEIGHT..=rect.bottom());
+                    draw_hatching(&painter, span, away_color);
+                    let resp = ui.interact(span, Id::new(("resource-absence", name, absence.first_day)), Sense::hover());
+                    resp.on_hover_text(format!("{} away: {}", name, absence.describe()));
+                }
+            }
+
+            // Bars
+            let dragging: Option<Uuid> = ui.ctx().data(|d| d.get_temp(drag_id));
+            let pointer = ui.input(|i| i.pointer.hover_pos());
+            for (row, (rect, _)) in rows.iter().zip(&row_rects) {
+                for (task, lane) in &row.tasks {
+                    let x0 = origin.x + viewport.datetime_to_x(task.start);
+                    let x1 = (origin.x + viewport.datetime_to_x(task.end)).max(x0 + 6.0);
+                    let top = rect.top() + NAME_STRIP_HEIGHT + *lane as f32 * (bar_height + LANE_GAP);
+                    let bar = Rect::from_min_max(Pos2::new(x0, top), Pos2::new(x1, top + bar_height));
+                    let resp = ui.interact(bar, Id::new(("resource-bar", task.id)), Sense::click_and_drag());
+                    let is_selected = selected_task == Some(task.id) || selection.contains(&task.id);
+                    let is_dragged = dragging == Some(task.id);
+
+                    let color = task.color.to_color32();
+                    let fill = if is_dragged { color.gamma_multiply(0.4) } else { color };
+                    painter.rect_filled(bar, Rounding::same(3.0), fill);
+                    if is_selected {
+                        painter.rect_stroke(bar, Rounding::same(3.0), Stroke::new(1.5, theme::text_primary()));
+                    }
+                    painter.with_clip_rect(bar.shrink2(Vec2::new(4.0, 0.0))).text(
+                        Pos2::new(bar.left() + 5.0, bar.center().y),
+                        Align2::LEFT_CENTER,
+                        &task.name,
+                        FontId::proportional(11.0),
+                        theme::text_on(color),
+                    );
+
+                    if resp.drag_started() {
+                        ui.ctx().data_mut(|d| d.insert_temp(drag_id, task.id));
+                    }
+                    if is_dragged {
+                        // Ghost bar following the pointer vertically.
+                        if let Some(p) = pointer {
+                            let ghost = bar.translate(Vec2::new(0.0, p.y - bar.center().y));
+                            painter.rect_stroke(ghost, Rounding::same(3.0), Stroke::new(1.5, color));
+                            if let Some((target, _)) = row_rects.iter().find(|(r, _)| r.y_range().contains(p.y)) {
+                                painter.rect_stroke(*target, 0.0, Stroke::new(1.0, theme::accent()));
+                            }
+                        }
+                    }
+                    if resp.drag_stopped() {
+                        ui.ctx().data_mut(|d| d.remove::<Uuid>(drag_id));
+                        let target = pointer
+                            .and_then(|p| row_rects.iter().find(|(r, _)| r.y_range().contains(p.y)));
+                        if let Some((_, assignee)) = target {
+                            if assignee.as_deref() != task.assignee_name() {
+                                action = ResourceAction::Reassign(task.id, assignee.clone());
+                            }
+                        }
+                    } else if resp.clicked() {
+                        action = ResourceAction::Select(task.id);
+                    }
+                    resp.on_hover_text(format!(
+                        "{}\n{} → {}",
+                        task.name,
+                        task.start.format("%Y-%m-%d %H:%M"),
+                        task.end.format("%Y-%m-%d %H:%M")
+                    ));
+                }
+            }
+
+            draw_timeline_header(&painter, origin, viewport, chart_width, origin.y + content_height, clock);
+            draw_today_line(&painter, origin, viewport, clock);
+
+            // Person names pinned to the left edge of the visible area.
+            let left = ui.clip_rect().left();
+            for (row, (rect, _)) in rows.iter().zip(&row_rects) {
+                let (name, color) = match &row.assignee {
+                    Some(name) => (name.as_str(), theme::text_primary()),
+                    None => ("Unassigned", theme::text_dim()),
+                };
+                let label = Rect::from_min_size(
+                    Pos2::new(left, rect.top()),
+                    Vec2::new(NAME_LABEL_WIDTH, NAME_STRIP_HEIGHT - 2.0),
+                );
+                painter.rect_filled(label, Rounding { se: 4.0, ..Rounding::ZERO }, theme::bg_header());
+                painter.with_clip_rect(label).text(
+                    label.left_center() + Vec2::new(6.0, 0.0),
+                    Align2::LEFT_CENTER,
+                    format!("{}  ({})", name, row.tasks.len()),
+                    FontId::proportional(11.0),
+                    color,
+                );
+            }
+        });
+
+    action
+}
@@ -0,0 +1,176 @@
+//! Row heights shared by the task list and the chart.
+//!
+//! With wrapped names on, a task whose name needs a second line in the list
+//! gets a taller row there and in the chart, so the two stay level. The list
+//! [`publish`]es how many lines each name took as it lays the names out;
+//! the chart reads them back through [`RowLayout`], which every row position
+//! on the chart comes from.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use egui::text::{LayoutJob, TextWrapping};
+use egui::{Context, FontId, Galley, Id, RichText, Ui};
+use uuid::Uuid;
+
+use crate::ui::theme;
+
+/// Most lines a wrapped name takes; longer names end in an ellipsis.
+pub const MAX_NAME_LINES: usize = 2;
+
+/// Size of task names in the list.
+const NAME_SIZE: f32 = 12.0;
+
+#[derive(Clone, Default)]
+struct Published {
+    /// Tasks whose name takes more than one line.
+    lines: HashMap<Uuid, usize>,
+    frame: u64,
+}
+
+fn published_id() -> Id {
+    Id::new("row-metrics-lines")
+}
+
+/// Height one more line of a name adds to its row.
+pub fn line_height(ctx: &Context) -> f32 {
+    ctx.fonts(|f| f.row_height(&FontId::proportional(NAME_SIZE)))
+}
+
+/// Height of a list row, frame included, whose name takes `lines` lines.
+pub fn row_height(ctx: &Context, lines: usize) -> f32 {
+    theme::row_height() + extra_lines(lines) as f32 * line_height(ctx)
+}
+
+fn extra_lines(lines: usize) -> usize {
+    lines.clamp(1, MAX_NAME_LINES) - 1
+}
+
+/// `name` wrapped to `width` over at most [`MAX_NAME_LINES`] lines.
+pub fn wrapped_name(ui: &Ui, name: RichText, width: f32) -> Arc<Galley> {
+    let mut job = LayoutJob::default();
+    name.size(NAME_SIZE).append_to(&mut job, ui.style(), egui::FontSelection::Default, egui::Align::Center);
+    job.wrap = TextWrapping {
+        max_width: width,
+        max_rows: MAX_NAME_LINES,
+        break_anywhere: false,
+        overflow_character: Some('…'),
+    };
+    ui.fonts(|f| f.layout_job(job))
+}
+
+/// Record how many lines each task's name took in the list this frame.
+/// Tasks left out take one.
+pub fn publish(ctx: &Context, lines: HashMap<Uuid, usize>) {
+    let frame = ctx.cumulative_pass_nr();
+    let lines = lines.into_iter().filter(|&(_, n)| n > 1).collect();
+    ctx.data_mut(|d| d.insert_temp(published_id(), Published { lines, frame }));
+}
+
+/// Lines `task`'s name took in the list this frame or the last; one when
+/// the list is hidden or doesn't wrap.
+pub fn name_lines(ctx: &Context, task: Uuid) -> usize {
+    ctx.data(|d| d.get_temp::<Published>(published_id()))
+        .filter(|p| p.frame + 1 >= ctx.cumulative_pass_nr())
+        .and_then(|p| p.lines.get(&task).copied())
+        .unwrap_or(1)
+}
+
+/// Where each chart row sits, from the top of the first row down.
+///
+/// A row's slot is its gap followed by its height; bars keep the plain row
+/// height and are centred in taller rows. Group headers take a plain row's
+/// slot between the task rows but aren't rows themselves: rows are numbered
+/// by task.
+pub struct RowLayout {
+    tops: Vec<f32>,
+    heights: Vec<f32>,
+    /// Top of each group header's slot.
+    headers: Vec<f32>,
+    bar_height: f32,
+    gap: f32,
+}
+
+impl RowLayout {
+    /// Lay out `rows` with bars `bar_height` tall and `gap` between rows,
+    /// each extra name line adding its list height times `scale`. `None`
+    /// stands for a group header.
+    pub fn new(ctx: &Context, rows: &[Option<Uuid>], bar_height: f32, gap: f32, scale: f32) -> Self {
+        let extra = line_height(ctx) * scale;
+        let mut tops = Vec::with_capacity(rows.len() + 1);
+        let mut heights = Vec::with_capacity(rows.len());
+        let mut headers = Vec::new();
+        let mut y = 0.0;
+        for row in rows {
+            match row {
+                Some(id) => {
+                    let height = bar_height + extra_lines(name_lines(ctx, *id)) as f32 * extra;
+                    tops.push(y);
+                    heights.push(height);
+                    y += height + gap;
+                }
+                None => {
+                    headers.push(y);
+                    y += bar_height + gap;
+                }
+            }
+        }
+        tops.push(y);
+        Self { tops, heights, headers, bar_height, gap }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heights.len()
+    }
+
+    /// Top of row `row`'s slot; for `len()`, the bottom of the last row.
+    pub fn top(&self, row: usize) -> f32 {
+        self.tops[row.min(self.len())]
+    }
+
+    /// Height of row `row`'s slot, gap included.
+    pub fn span(&self, row: usize) -> f32 {
+        self.heights.get(row).map_or(self.bar_height + self.gap, |h| h + self.gap)
+    }
+
+    /// Top of the bar in row `row`, centred in the row.
+    pub fn bar_top(&self, row: usize) -> f32 {
+        let height = self.heights.get(row).copied().unwrap_or(self.bar_height);
+        self.top(row) + self.gap + (height - self.bar_height) / 2.0
+    }
+
+    /// Height of every row together.
+    pub fn total(&self) -> f32 {
+        self.top(self.len())
+    }
+
+    /// Top of each group header's slot, in order; each is a plain row tall.
+    pub fn headers(&self) -> &[f32] {
+        &self.headers
+    }
+
+    /// Average slot height, for scroll anchoring.
+    pub fn pitch(&self) -> f32 {
+        let slots = self.len() + self.headers.len();
+        if slots == 0 {
+            self.bar_height + self.gap
+        } else {
+            self.total() / slots as f32
+        }
+    }
+
+    /// The row whose slot holds `y`, measured from the top of the first row;
+    /// `len()` below everything, `None` above the first row or over a group
+    /// header.
+    pub fn row_at(&self, y: f32) -> Option<usize> {
+        if y < 0.0 {
+            return None;
+        }
+        let above = self.tops[..self.len()].partition_point(|&top| top <= y);
+        match above.checked_sub(1) {
+            Some(row) if y < self.top(row) + self.span(row) => Some(row),
+            _ if y >= self.total() => Some(self.len()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//! Hover and scroll requests shared by the task list and the chart, so a
+//! task's list row and its chart row light up and come into view together.
+//!
+//! Both sides [`hover`] the task under the pointer while drawing and read
+//! [`hovered`] before drawing, which gives the task hovered on either side
+//! in this frame or the last. A click on one side asks the other to
+//! [`scroll_to`] the task; the other side [`take_scroll`]s the request when
+//! it lays out that row.
+//!
+//! The two sides also scroll vertically together. Each reports where it
+//! ended up with [`scrolled`], as the task at the top of its view and how
+//! far into that row; before showing its scroll area the other side asks
+//! [`scroll_offset`] for the offset that puts the same task at its top.
+
+use egui::{Context, Id};
+use uuid::Uuid;
+
+/// Which side a scroll request is for.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum RowView {
+    List,
+    Chart,
+}
+
+#[derive(Clone, Copy)]
+struct Hover {
+    task: Uuid,
+    frame: u64,
+}
+
+/// Where a side last scrolled itself to: `into` of the way through `task`'s
+/// row is at the top of its view.
+#[derive(Clone, Copy)]
+struct ScrollPos {
+    task: Uuid,
+    into: f32,
+    by: RowView,
+    seq: u64,
+}
+
+/// One side's rows and offset from its last frame.
+#[derive(Clone, Default)]
+struct Side {
+    /// Each row's top, measured from the top of the first row.
+    tops: Vec<(Uuid, f32)>,
+    offset: f32,
+    /// Last [`ScrollPos::seq`] this side followed.
+    seen: u64,
+    /// The offset was set from the other side, so don't report it back.
+    following: bool,
+}
+
+fn hover_id() -> Id {
+    Id::new("row-sync-hover")
+}
+
+fn scroll_id(view: RowView) -> Id {
+    Id::new(("row-sync-scroll", view))
+}
+
+fn pos_id() -> Id {
+    Id::new("row-sync-pos")
+}
+
+fn side_id(view: RowView) -> Id {
+    Id::new(("row-sync-side", view))
+}
+
+/// Record that `task`'s row is under the pointer on either side.
+pub fn hover(ctx: &Context, task: Uuid) {
+    let frame = ctx.cumulative_pass_nr();
+    ctx.data_mut(|d| d.insert_temp(hover_id(), Hover { task, frame }));
+}
+
+/// The task hovered in the list or the chart this frame or the last.
+pub fn hovered(ctx: &Context) -> Option<Uuid> {
+    let hover = ctx.data(|d| d.get_temp::<Hover>(hover_id()))?;
+    (hover.frame + 1 >= ctx.cumulative_pass_nr()).then_some(hover.task)
+}
+
+/// Ask `view` to bring `task`'s row into view on its next frame, scrolling
+/// only if it is off-screen.
+pub fn scroll_to(ctx: &Context, view: RowView, task: Uuid) {
+    ctx.data_mut(|d| d.insert_temp(scroll_id(view), task));
+}
+
+/// The task `view` was asked to bring into view, if any; the request is
+/// used up.
+pub fn take_scroll(ctx: &Context, view: RowView) -> Option<Uuid> {
+    ctx.data_mut(|d| d.remove_temp::<Uuid>(scroll_id(view)))
+}
+
+/// The vertical offset that lines `view` up with where the other side last
+/// scrolled, if it has moved since `view` last followed it.
+pub fn scroll_offset(ctx: &Context, view: RowView) -> Option<f32> {
+    let pos = ctx.data(|d| d.get_temp::<ScrollPos>(pos_id()))?;
+    let mut side = ctx.data(|d| d.get_temp::<Side>(side_id(view))).unwrap_or_default();
+    if pos.by == view || pos.seq <= side.seen {
+        return None;
+    }
+    side.seen = pos.seq;
+    let row = side.tops.iter().position(|&(id, _)| id == pos.task);
+    let offset = row.map(|i| {
+        let top = side.tops[i].1;
+        let next = side.tops.get(i + 1).map_or(top, |&(_, y)| y);
+        top + pos.into * (next - top)
+    });
+    side.following = offset.is_some();
+    ctx.data_mut(|d| d.insert_temp(side_id(view), side));
+    offset
+}
+
+/// Record `view`'s rows, each task with its top measured from the top of
+/// the first row, and the vertical `offset` it is scrolled to; if the user
+/// scrolled it, the other side follows on the next frame, which this asks
+/// for.
+pub fn scrolled(ctx: &Context, view: RowView, tops: Vec<(Uuid, f32)>, offset: f32) {
+    let mut side = ctx.data(|d| d.get_temp::<Side>(side_id(view))).unwrap_or_default();
+    let moved = (offset - side.offset).abs() > 0.5;
+    if moved && !side.following {
+        let row = tops.partition_point(|&(_, top)| top <= offset).saturating_sub(1);
+        if let Some(&(task, top)) = tops.get(row) {
+            let next = tops.get(row + 1).map_or(top, |&(_, y)| y);
+            let into = if next > top { ((offset - top) / (next - top)).clamp(0.0, 1.0) } else { 0.0 };
+            let seq = ctx.data(|d| d.get_temp::<ScrollPos>(pos_id())).map_or(0, |p| p.seq) + 1;
+            ctx.data_mut(|d| d.insert_temp(pos_id(), ScrollPos { task, into, by: view, seq }));
+            // The list draws before the chart, so one side has already laid
+            // out this frame with the old position.
+            ctx.request_repaint();
+        }
+    }
+    side.following = false;
+    side.tops = tops;
+    side.offset = offset;
+    ctx.data_mut(|d| d.insert_temp(side_id(view), side));
+}
@@ -0,0 +1,131 @@
+//! The rows the task list and the chart show.
+//!
+//! The app works these out once a frame with [`build_rows`] and hands the
+//! same list to both, so the list and the chart always show the same tasks
+//! in the same order, group headers included, whatever the filters.
+
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+
+use crate::i18n::{priority_label, status_label, tr};
+use crate::model::grouping::{GroupKey, TaskGroup};
+use crate::model::highlight::{hidden_by_due, DueState};
+use crate::model::risk::RiskLevel;
+use crate::model::task::{hidden_by_risk, hidden_completed, hidden_tentative, CompletedTasks, TaskPriority};
+use crate::model::Task;
+use crate::ui::filter_bar;
+
+/// One row of the list and the chart.
+#[derive(Clone, Copy)]
+pub enum Row<'a> {
+    /// A group's header, with how many of its tasks pass the filters.
+    Group { group: &'a TaskGroup, shown: usize },
+    /// A task and the group it is listed under. `index` is where it sat when
+    /// the rows were built; `id` still finds it if the tasks change before
+    /// the chart draws.
+    Task { index: usize, id: Uuid, group: Option<&'a GroupKey> },
+}
+
+impl Row<'_> {
+    /// The task in this row, if it isn't a group header.
+    pub fn task_id(&self) -> Option<Uuid> {
+        match *self {
+            Row::Task { id, .. } => Some(id),
+            Row::Group { .. } => None,
+        }
+    }
+}
+
+/// What the rows leave out.
+#[derive(Clone, Copy)]
+pub struct RowFilter<'a> {
+    /// Search box text; tasks matching it, or with a subtask that does, stay.
+    pub search_query: &'a str,
+    pub priority: Option<TaskPriority>,
+    pub completed: CompletedTasks,
+    /// Leave out tentative tasks.
+    pub hide_tentative: bool,
+    /// Show only tasks at this risk level.
+    pub risk: Option<RiskLevel>,
+    /// Show only tasks that are late, due soon or done, by `due_states`.
+    pub due: Option<DueState>,
+    pub due_states: &'a HashMap<Uuid, DueState>,
+}
+
+/// The rows to show this frame.
+pub struct Rows<'a> {
+    pub rows: Vec<Row<'a>>,
+    /// Tasks the filters leave out, as opposed to those tucked under a
+    /// collapsed group or parent.
+    pub filtered_out: HashSet<Uuid>,
+}
+
+/// The rows for `tasks`: `groups` with their headers when grouping, skipping
+/// tasks in `collapsed_groups`, or else the hierarchy without the children of
+/// collapsed parents. `groups` is empty for the hierarchy.
+pub fn build_rows<'a>(
+    tasks: &[Task],
+    filter: &RowFilter,
+    groups: &'a [TaskGroup],
+    collapsed_groups: &HashSet<GroupKey>,
+) -> Rows<'a> {
+    let mut hidden = hidden_completed(tasks, filter.completed);
+    hidden.extend(hidden_tentative(tasks, filter.hide_tentative));
+    hidden.extend(hidden_by_risk(tasks, filter.risk));
+    hidden.extend(hidden_by_due(tasks, filter.due, filter.due_states));
+    hidden.extend(
+        tasks.iter().filter(|t| !passes_search(t, tasks, filter.search_query, filter.priority)).map(|t| t.id),
+    );
+    let passes = |t: &Task| !hidden.contains(&t.id);
+
+    let mut rows = Vec::new();
+    if groups.is_empty() {
+        rows.extend(
+            tasks
+                .iter()
+                .enumerate()
+                .filter(|(_, t)| passes(t) && !is_collapsed_away(t, tasks))
+                .map(|(index, t)| Row::Task { index, id: t.id, group: None }),
+        );
+    }
+    for group in groups {
+        let shown: Vec<usize> = group.tasks.iter().copied().filter(|&i| passes(&tasks[i])).collect();
+        if shown.is_empty() {
+            continue;
+        }
+        rows.push(Row::Group { group, shown: shown.len() });
+        if !collapsed_groups.contains(&group.key) {
+            let group = Some(&group.key);
+            rows.extend(shown.into_iter().map(|index| Row::Task { index, id: tasks[index].id, group }));
+        }
+    }
+    Rows { rows, filtered_out: hidden }
+}
+
+/// A group's name as shown in its header.
+pub fn group_label(key: &GroupKey) -> String {
+    match key {
+        GroupKey::Priority(priority) => priority_label(*priority).to_string(),
+        GroupKey::Assignee(Some(name)) => name.clone(),
+        GroupKey::Assignee(None) => tr("list.group.unassigned").to_string(),
+        GroupKey::Tag(Some(tag)) => format!("#{}", tag),
+        GroupKey::Tag(None) => tr("list.group.no_tag").to_string(),
+        GroupKey::Status(status) => status_label(*status).to_string(),
+    }
+}
+
+/// A task is shown if it or any of its children pass the search and priority
+/// filter.
+fn passes_search(t: &Task, tasks: &[Task], search_query: &str, priority: Option<TaskPriority>) -> bool {
+    let matches = |t: &Task| filter_bar::task_matches(&t.name, &t.description, t.priority, search_query, priority);
+    matches(t) || tasks.iter().any(|child| child.parent_id == Some(t.id) && matches(child))
+}
+
+/// True if the task is hidden because its parent is collapsed.
+fn is_collapsed_away(task: &Task, tasks: &[Task]) -> bool {
+    task.parent_id
+        .and_then(|pid| tasks.iter().find(|t| t.id == pid))
+        .map(|parent| parent.collapsed)
+        .unwrap_or(false)
+}
@@ -0,0 +1,109 @@
+//! Post-save command settings and the output of recent runs.
+
+use crate::app::GanttApp;
+use crate::io::save_hook::FILE_PLACEHOLDER;
+use crate::ui::theme;
+use egui::{Color32, Context, Key, RichText, Window};
+
+/// Render the window while `app.show_save_hook` is set.
+pub fn show_save_hook_panel(app: &mut GanttApp, ctx: &Context) {
+    let mut open = true;
+    let mut clear = false;
+    Window::new(RichText::new("After-Save Command").strong().size(14.0))
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_size([560.0, 420.0])
+        .show(ctx, |ui| {
+            egui::Grid::new("save_hook_grid").num_columns(2).spacing([8.0, 6.0]).show(ui, |ui| {
+                ui.label("This project");
+                let mut own = app.project.post_save_command.clone().unwrap_or_default();
+                let field = ui.add(
+                    egui::TextEdit::singleline(&mut own)
+                        .hint_text(format!("e.g. ./publish.sh {}", FILE_PLACEHOLDER))
+                        .font(egui::TextStyle::Monospace)
+                        .desired_width(f32::INFINITY),
+                );
+                if field.changed() {
+                    let own = own.trim();
+                    app.project.post_save_command = (!own.is_empty()).then(|| own.to_string());
+                    // Typed here, so no need to confirm it before it runs.
+                    app.trusted_hook = app.project.post_save_command.clone();
+                    app.project.touch();
+                }
+                ui.end_row();
+
+                ui.label("All projects");
+                let field = ui.add(
+                    egui::TextEdit::singleline(&mut app.settings.post_save_command)
+                        .hint_text("used when the project has none")
+                        .font(egui::TextStyle::Monospace)
+                        .desired_width(f32::INFINITY),
+                );
+                if field.lost_focus() {
+                    app.settings.save();
+                }
+                ui.end_row();
+            });
+            ui.label(
+                RichText::new(format!(
+                    "Runs in the background after each successful save. {} is replaced with the saved file's path. \
+                     The project's command is saved in the file.",
+                    FILE_PLACEHOLDER
+                ))
+                .size(11.0)
+                .color(theme::text_dim()),
+            );
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Recent runs").strong());
+                if app.save_hooks.is_running() {
+                    ui.spinner();
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.add_enabled(!app.hook_log.is_empty(), egui::Button::new("Clear")).clicked() {
+                        clear = true;
+                    }
+                });
+            });
+            ui.separator();
+
+            if app.hook_log.is_empty() {
+                ui.label(RichText::new("Nothing has run yet.").size(11.0).color(theme::text_dim()));
+                return;
+            }
+            egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                for (i, run) in app.hook_log.iter().enumerate().rev() {
+                    let (icon, color) = if run.succeeded() {
+                        (egui_phosphor::regular::CHECK_CIRCLE, Color32::from_rgb(80, 180, 100))
+                    } else {
+                        (egui_phosphor::regular::X_CIRCLE, Color32::from_rgb(220, 60, 60))
+                    };
+                    let status = match &run.result {
+                        Ok(code) => format!("exit {}", code),
+                        Err(e) => e.clone(),
+                    };
+                    let title = RichText::new(format!(
+                        "{}  {}  {}  ({})",
+                        icon,
+                        run.started.format("%b %d %H:%M:%S"),
+                        run.command,
+                        status
+                    ))
+                    .size(11.0)
+                    .color(color);
+                    egui::CollapsingHeader::new(title).id_salt(("hook-run", i)).show(ui, |ui| {
+                        let output = if run.output.is_empty() { "(no output)" } else { run.output.as_str() };
+                        ui.label(RichText::new(output).monospace().size(11.0).color(theme::text_secondary()));
+                    });
+                }
+            });
+        });
+
+    if clear {
+        app.hook_log.clear();
+    }
+    if !open || ctx.input(|i| i.key_pressed(Key::Escape)) {
+        app.show_save_hook = false;
+    }
+}
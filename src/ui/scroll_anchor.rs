@@ -0,0 +1,41 @@
+//! Keeping the task at the top of a scrolled list in place when rows above
+//! it appear, disappear or move — after sorting, filtering, collapsing or
+//! undo.
+//!
+//! Each frame the list [`remember`]s which task is at the top; on the next
+//! frame [`restore`] returns the scroll offset that puts it back there if its
+//! row moved. Ordinary scrolling never moves the row, so it is left alone.
+
+use egui::{Context, Id};
+use uuid::Uuid;
+
+#[derive(Clone, Copy)]
+struct Anchor {
+    task: Uuid,
+    /// The task's row in the listed order.
+    row: usize,
+    /// Vertical scroll offset when it was at the top.
+    offset: f32,
+    /// Height of one row, including the gap to the next.
+    pitch: f32,
+}
+
+/// The offset that keeps last frame's top task at the top, if it has moved
+/// within `rows` (this frame's listed order). `None` when it hasn't moved or
+/// is no longer listed.
+pub fn restore(ctx: &Context, id: Id, rows: &[Uuid]) -> Option<f32> {
+    let anchor = ctx.data(|d| d.get_temp::<Anchor>(id))?;
+    let row = rows.iter().position(|&t| t == anchor.task)?;
+    (row != anchor.row).then(|| (anchor.offset + (row as f32 - anchor.row as f32) * anchor.pitch).max(0.0))
+}
+
+/// Record the task at the top of `rows` when scrolled to `offset`, with rows
+/// `pitch` apart.
+pub fn remember(ctx: &Context, id: Id, rows: &[Uuid], pitch: f32, offset: f32) {
+    if rows.is_empty() || pitch <= 0.0 {
+        ctx.data_mut(|d| d.remove::<Anchor>(id));
+        return;
+    }
+    let row = ((offset / pitch) as usize).min(rows.len() - 1);
+    ctx.data_mut(|d| d.insert_temp(id, Anchor { task: rows[row], row, offset, pitch }));
+}
@@ -0,0 +1,265 @@
+//! Keyboard shortcut settings: every registered command with its bindings,
+//! press-to-rebind capture and conflict detection.
+//!
+//! Changes apply immediately and are saved to the settings file as
+//! overrides of the defaults.
+
+use crate::app::GanttApp;
+use crate::keymap::KeyBinding;
+use crate::ui::theme;
+use egui::{Context, Event, Key, RichText, Window};
+
+#[derive(Clone, Default)]
+struct DialogState {
+    filter: String,
+    /// Command being rebound and which binding is replaced (`None` adds one).
+    capture: Option<(&'static str, Option<usize>)>,
+    /// A captured binding that another command already uses.
+    conflict: Option<Conflict>,
+}
+
+#[derive(Clone)]
+struct Conflict {
+    command: &'static str,
+    slot: Option<usize>,
+    binding: KeyBinding,
+    other: &'static str,
+}
+
+enum Edit {
+    StartCapture(&'static str, Option<usize>),
+    Remove(&'static str, usize),
+    Reset(&'static str),
+    ResetAll,
+}
+
+fn state_id() -> egui::Id {
+    egui::Id::new("shortcuts-dialog")
+}
+
+/// True while the dialog is waiting for a key press to bind. Shortcuts
+/// shouldn't fire then, so the key reaches the dialog instead.
+pub fn is_capturing(ctx: &Context) -> bool {
+    ctx.data(|d| d.get_temp::<DialogState>(state_id()))
+        .is_some_and(|s| s.capture.is_some())
+}
+
+fn save_bindings(app: &mut GanttApp) {
+    app.settings.key_bindings = app.keymap.overrides(&app.commands);
+    app.settings.save();
+}
+
+/// Put `binding` on `command`, replacing binding `slot` or adding it.
+fn assign(app: &mut GanttApp, command: &'static str, slot: Option<usize>, binding: KeyBinding) {
+    let mut keys = app.keymap.bindings(command).to_vec();
+    match slot {
+        Some(i) if i < keys.len() => keys[i] = binding,
+        _ if keys.contains(&binding) => {}
+        _ => keys.push(binding),
+    }
+    app.keymap.set(command, keys);
+    save_bindings(app);
+}
+
+fn command_label(app: &GanttApp, id: &str) -> &'static str {
+    app.commands.get(id).map_or("?", |c| c.label)
+}
+
+/// Render the dialog while `app.show_shortcuts` is set.
+pub fn show_shortcuts_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut state = ctx.data(|d| d.get_temp::<DialogState>(state_id())).unwrap_or_default();
+
+    // Capture the next key press, before anything else can react to it.
+    if let Some((command, slot)) = state.capture {
+        let press = ctx.input(|i| {
+            i.events.iter().find_map(|e| match e {
+                Event::Key { key, pressed: true, modifiers, .. } => Some((*key, *modifiers)),
+                _ => None,
+            })
+        });
+        if let Some((key, modifiers)) = press {
+            ctx.input_mut(|i| i.events.retain(|e| !matches!(e, Event::Key { .. })));
+            state.capture = None;
+            if key != Key::Escape {
+                let binding = KeyBinding::from_press(key, modifiers);
+                match app.keymap.conflict(binding, command) {
+                    Some(other) => state.conflict = Some(Conflict { command, slot, binding, other }),
+                    None => assign(app, command, slot, binding),
+                }
+            }
+        }
+    }
+
+    let mut edit: Option<Edit> = None;
+    let mut resolve: Option<bool> = None;
+    let mut open = true;
+    Window::new(RichText::new("Keyboard Shortcuts").strong().size(14.0))
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_size([520.0, 480.0])
+        .show(ctx, |ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut state.filter)
+                    .hint_text("🔍 Filter commands…")
+                    .desired_width(f32::INFINITY),
+            );
+            ui.add_space(4.0);
+
+            if let Some(conflict) = &state.conflict {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(
+                        RichText::new(format!(
+                            "{} {} is already used by '{}'.",
+                            egui_phosphor::regular::WARNING,
+                            conflict.binding,
+                            command_label(app, conflict.other)
+                        ))
+                        .color(egui::Color32::from_rgb(220, 140, 40)),
+                    );
+                    if ui.button(format!("Move to '{}'", command_label(app, conflict.command))).clicked() {
+                        resolve = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        resolve = Some(false);
+                    }
+                });
+                ui.add_space(4.0);
+            } else {
+                ui.label(
+                    RichText::new("Click a shortcut to change it, right-click to remove it.")
+                        .size(11.0)
+                        .color(theme::text_dim()),
+                );
+            }
+            ui.separator();
+
+            let filter = state.filter.to_lowercase();
+            egui::ScrollArea::vertical().auto_shrink([false, false]).max_height(ui.available_height() - 36.0).show(
+                ui,
+                |ui| {
+                    egui::Grid::new("shortcuts_grid").num_columns(3).striped(true).spacing([12.0, 4.0]).show(
+                        ui,
+                        |ui| {
+                            for command in app.commands.iter() {
+                                if !filter.is_empty() && !command.label.to_lowercase().contains(&filter) {
+                                    continue;
+                                }
+                                ui.label(RichText::new(command.label).color(theme::text_primary()));
+
+                                ui.horizontal_wrapped(|ui| {
+                                    let keys = app.keymap.bindings(command.id);
+                                    for (i, binding) in keys.iter().enumerate() {
+                                        let text = if state.capture == Some((command.id, Some(i))) {
+                                            RichText::new("Press a key…").italics()
+                                        } else {
+                                            RichText::new(binding.to_string()).monospace()
+                                        };
+                                        let resp = ui.button(text);
+                                        if resp.clicked() {
+                                            edit = Some(Edit::StartCapture(command.id, Some(i)));
+                                        } else if resp.secondary_clicked() {
+                                            edit = Some(Edit::Remove(command.id, i));
+                                        }
+                                    }
+                                    if state.capture == Some((command.id, None)) {
+                                        let _ = ui.button(RichText::new("Press a key…").italics());
+                                    } else if ui
+                                        .small_button(egui_phosphor::regular::PLUS)
+                                        .on_hover_text("Add a shortcut")
+                                        .clicked()
+                                    {
+                                        edit = Some(Edit::StartCapture(command.id, None));
+                                    }
+                                });
+
+                                if keys_differ(app, command.id) {
+                                    let defaults: Vec<String> =
+                                        command.default_keys.iter().map(|b| b.to_string()).collect();
+                                    let hint = if defaults.is_empty() {
+                                        "Reset to default (none)".to_string()
+                                    } else {
+                                        format!("Reset to default ({})", defaults.join(", "))
+                                    };
+                                    if ui
+                                        .small_button(egui_phosphor::regular::ARROW_COUNTER_CLOCKWISE)
+                                        .on_hover_text(hint)
+                                        .clicked()
+                                    {
+                                        edit = Some(Edit::Reset(command.id));
+                                    }
+                                } else {
+                                    ui.label("");
+                                }
+                                ui.end_row();
+                            }
+                        },
+                    );
+                },
+            );
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Reset All to Defaults").clicked() {
+                    edit = Some(Edit::ResetAll);
+                }
+            });
+        });
+
+    if let Some(confirm) = resolve {
+        if let Some(conflict) = state.conflict.take() {
+            if confirm {
+                let others: Vec<KeyBinding> = app
+                    .keymap
+                    .bindings(conflict.other)
+                    .iter()
+                    .copied()
+                    .filter(|b| *b != conflict.binding)
+                    .collect();
+                app.keymap.set(conflict.other, others);
+                assign(app, conflict.command, conflict.slot, conflict.binding);
+            }
+        }
+    }
+
+    match edit {
+        Some(Edit::StartCapture(command, slot)) => {
+            state.capture = Some((command, slot));
+            state.conflict = None;
+        }
+        Some(Edit::Remove(command, i)) => {
+            let mut keys = app.keymap.bindings(command).to_vec();
+            if i < keys.len() {
+                keys.remove(i);
+            }
+            app.keymap.set(command, keys);
+            save_bindings(app);
+        }
+        Some(Edit::Reset(command)) => {
+            if let Some(defaults) = app.commands.get(command).map(|c| c.default_keys.clone()) {
+                app.keymap.set(command, defaults);
+                save_bindings(app);
+            }
+        }
+        Some(Edit::ResetAll) => {
+            app.keymap = crate::keymap::Keymap::new(&app.commands, &Default::default());
+            save_bindings(app);
+            state.conflict = None;
+        }
+        None => {}
+    }
+
+    let escape = state.capture.is_none() && ctx.input(|i| i.key_pressed(Key::Escape));
+    if !open || escape {
+        app.show_shortcuts = false;
+        ctx.data_mut(|d| d.remove::<DialogState>(state_id()));
+    } else {
+        ctx.data_mut(|d| d.insert_temp(state_id(), state));
+    }
+}
+
+fn keys_differ(app: &GanttApp, id: &str) -> bool {
+    app.commands
+        .get(id)
+        .is_some_and(|c| app.keymap.bindings(id) != c.default_keys.as_slice())
+}
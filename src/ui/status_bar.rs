@@ -0,0 +1,214 @@
+//! Slim bar along the bottom of the window: the latest status message,
+//! what is selected, the date under the pointer, and project totals.
+
+use crate::app::GanttApp;
+use crate::model::Task;
+use crate::model::validation;
+use crate::ui::onboarding::{self, CoachTarget};
+use crate::ui::theme;
+use chrono::NaiveDateTime;
+use egui::{RichText, Ui};
+
+/// Totals that need a pass over every task, recomputed only when the
+/// project changes.
+#[derive(Clone, Copy)]
+struct Summary {
+    generation: u64,
+    completion: f32,
+    tasks_with_issues: usize,
+}
+
+fn summary(app: &GanttApp, ui: &Ui) -> Summary {
+    let id = egui::Id::new("status-bar-summary");
+    let generation = app.project.generation();
+    if let Some(cached) = ui.ctx().data(|d| d.get_temp::<Summary>(id)) {
+        if cached.generation == generation {
+            return cached;
+        }
+    }
+    let summary = Summary {
+        generation,
+        completion: app.project.completion(),
+        tasks_with_issues: validation::count_tasks_with_issues(
+            &app.project.tasks,
+            &app.project.dependency_graph(),
+            &app.project.absences,
+        ),
+    };
+    ui.ctx().data_mut(|d| d.insert_temp(id, summary));
+    summary
+}
+
+/// First and last day of `tasks`, and how many calendar days that covers.
+fn span(tasks: &[&Task]) -> Option<(NaiveDateTime, NaiveDateTime, i64)> {
+    let start = tasks.iter().map(|t| t.start).min()?;
+    let end = tasks.iter().map(|t| t.end).max()?;
+    Some((start, end, (end.date() - start.date()).num_days() + 1))
+}
+
+/// Name and dates of the selected task, or the combined span of a multi-selection.
+fn selection_text(app: &GanttApp) -> Option<String> {
+    let selected: Vec<&Task> = app.selection.iter().filter_map(|id| app.project.task(*id)).collect();
+    if selected.len() > 1 {
+        let (start, end, days) = span(&selected)?;
+        return Some(format!(
+            "{} tasks selected · {} → {} ({}d)",
+            selected.len(),
+            start.format("%b %d"),
+            end.format("%b %d"),
+            days
+        ));
+    }
+    let task = app.selected_task.and_then(|id| app.project.task(id))?;
+    if task.is_milestone {
+        return Some(format!("{} {} · {}", egui_phosphor::regular::DIAMOND, task.name, task.start.format("%b %d")));
+    }
+    let (start, end, days) = span(&[task])?;
+    Some(format!("{} · {} → {} ({}d)", task.name, start.format("%b %d"), end.format("%b %d"), days))
+}
+
+/// `1:05:09` for a running timer.
+fn format_elapsed(elapsed: chrono::Duration) -> String {
+    let secs = elapsed.num_seconds().max(0);
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+fn item(ui: &mut Ui, text: impl Into<String>) {
+    ui.label(RichText::new(text.into()).size(10.5).color(theme::text_dim()));
+}
+
+fn zoom_button(ui: &mut Ui, icon: &str, hint: &str) -> bool {
+    let button = egui::Button::new(RichText::new(icon).size(11.0).color(theme::text_dim())).frame(false);
+    ui.add(button).on_hover_text(hint).clicked()
+}
+
+fn separator(ui: &mut Ui) {
+    item(ui, " · ");
+}
+
+/// Render the status bar contents.
+pub fn show_status_bar(app: &mut GanttApp, ui: &mut Ui) {
+    let summary = summary(app, ui);
+    ui.horizontal_centered(|ui| {
+        let running = app.project.running_timer().and_then(|t| t.running_entry().map(|e| (t, e)));
+        if let Some((task, entry)) = running {
+            let now = chrono::Local::now().naive_local();
+            ui.label(
+                RichText::new(format!(
+                    "{} {} {}",
+                    egui_phosphor::regular::TIMER,
+                    task.name,
+                    format_elapsed(entry.duration(now))
+                ))
+                .font(theme::font_status())
+                .color(egui::Color32::from_rgb(220, 60, 60)),
+            )
+            .on_hover_text(format!("Timer running since {}", entry.start.format("%H:%M")));
+            separator(ui);
+        }
+        if let Some(selection) = selection_text(app) {
+            ui.label(
+                RichText::new(selection)
+                    .font(theme::font_status())
+                    .color(theme::text_primary()),
+            );
+            separator(ui);
+        }
+        ui.label(
+            RichText::new(&app.status_message)
+                .font(theme::font_status())
+                .color(theme::text_secondary()),
+        );
+
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            let default_ppd = theme::zoom().default_pixels_per_day;
+            let zoom = ui.scope(|ui| {
+                if zoom_button(ui, egui_phosphor::regular::MAGNIFYING_GLASS_PLUS, "Zoom in (Ctrl+scroll over the chart)") {
+                    app.viewport.zoom_in();
+                }
+                item(
+                    ui,
+                    format!(
+                        "{} · {:.0}%",
+                        app.viewport.scale.label(),
+                        app.viewport.pixels_per_day / default_ppd * 100.0
+                    ),
+                );
+                if zoom_button(ui, egui_phosphor::regular::MAGNIFYING_GLASS_MINUS, "Zoom out") {
+                    app.viewport.zoom_out();
+                }
+            });
+            onboarding::mark_target(ui.ctx(), CoachTarget::Zoom, zoom.response.rect);
+            separator(ui);
+            item(ui, format!("{:.0}% complete", summary.completion * 100.0));
+            separator(ui);
+            item(ui, format!("Tasks: {}", app.project.tasks.len()));
+
+            let violations = app.project.dependency_violations().len();
+            if violations > 0 {
+                separator(ui);
+                let label = egui::Label::new(
+                    RichText::new(format!(
+                        "{} {} broken {}",
+                        egui_phosphor::regular::LINK_BREAK,
+                        violations,
+                        if violations == 1 { "link" } else { "links" }
+                    ))
+                    .size(10.5)
+                    .color(theme::status_color(theme::Severity::Bad, egui::Color32::from_rgb(220, 60, 60))),
+                )
+                .sense(egui::Sense::click());
+                if ui
+                    .add(label)
+                    .on_hover_text("Dependencies the tasks' dates don't meet; click to list them")
+                    .on_hover_cursor(egui::CursorIcon::PointingHand)
+                    .clicked()
+                {
+                    app.show_violations = true;
+                }
+            }
+
+            if summary.tasks_with_issues > 0 {
+                separator(ui);
+                ui.label(
+                    RichText::new(format!(
+                        "{} {} with warnings",
+                        egui_phosphor::regular::WARNING,
+                        summary.tasks_with_issues
+                    ))
+                    .size(10.5)
+                    .color(egui::Color32::from_rgb(220, 140, 40)),
+                )
+                .on_hover_text("Tasks with schedule problems; select one to see them in the editor");
+            }
+
+            let save_state = if app.saver.is_saving() {
+                Some("Saving…")
+            } else if app.is_dirty() {
+                Some("● Unsaved")
+            } else {
+                None
+            };
+            if let Some(state) = save_state {
+                separator(ui);
+                item(ui, state);
+            }
+
+            if let Some(zone) = app.project.time_zone {
+                separator(ui);
+                item(ui, format!("{} {}", egui_phosphor::regular::GLOBE, zone.name()));
+            }
+
+            if let Some(time) = app.chart_hover_time {
+                let format = if app.viewport.scale == crate::model::TimelineScale::Hours {
+                    "%a %b %d %H:%M"
+                } else {
+                    "%a %b %d, %Y"
+                };
+                separator(ui);
+                let time = app.project.clock().to_local(time);
+                item(ui, format!("{} {}", egui_phosphor::regular::CROSSHAIR, time.format(format)));
+            }
+        });
+    });
+}
@@ -1,17 +1,56 @@
-use crate::model::Task;
-use crate::model::task::{Dependency, DependencyKind, TaskPriority};
-use crate::ui::theme;
-use chrono::{NaiveTime, Timelike};
+use crate::model::activity::ActivityLog;
+use crate::model::clock::ProjectClock;
+use crate::model::date_preset::{DatePreset, DatePresets, PresetAnchor};
+use crate::model::graph::DependencyGraph;
+use crate::model::{Project, Task};
+use crate::model::risk::RiskLevel;
+use crate::model::task_progress::TaskProgressSample;
+use crate::model::task::{
+    assignee_names, ChecklistItem, Dependency, DependencyKind, FixedEdge, ProgressSource, TaskPriority, TimeEntry,
+};
+use crate::model::validation::{self, ValidationFix};
+use crate::i18n::{format_datetime, priority_label, risk_label, tr, trf};
+use crate::ui::model_ext::{PriorityIcon, StatusColor, ToColor32};
+use crate::ui::theme::{self, Severity};
+use chrono::{NaiveDate, NaiveTime, Timelike};
 use egui::{Color32, Id, RichText, Ui};
 use uuid::Uuid;
 
+/// Shortest a task can be made by editing its dates: the finest step the
+/// chart snaps to.
+const MIN_EDIT_SPAN: chrono::Duration = chrono::Duration::hours(1);
+
 /// Actions the editor can request.
 pub enum EditorAction {
     None,
     Changed,
     RemoveDependency(Uuid, Uuid),
     AddSubtask(Uuid),
+    /// Open the preview for laying this parent's children out back to back.
+    ArrangeChildren(Uuid),
     AddDependency(Dependency),
+    /// Set progress to a fixed value as its own undo step.
+    SetProgress(f32),
+    /// Recolor the task (and its whole subtree if `include_children`).
+    /// `custom` colours are added to the project's recent colours.
+    SetColor { color: Color32, include_children: bool, custom: bool },
+    /// Convert the task to or from a milestone; the app confirms lossy
+    /// conversions first.
+    SetMilestone(bool),
+    /// Lock or unlock the task's dates as its own undo step.
+    SetLocked(bool),
+    /// Mark the task tentative or confirmed as its own undo step.
+    SetTentative(bool),
+    /// Apply the one-click fix offered for a validation warning.
+    ApplyFix(ValidationFix),
+    /// Change the kind and/or lag of an existing link.
+    UpdateDependency { from: Uuid, to: Uuid, kind: DependencyKind, lag: i64 },
+    /// Post a comment with this text.
+    AddComment(String),
+    /// Delete the comment with this id.
+    DeleteComment(Uuid),
+    /// Start or stop the timer on this task.
+    ToggleTimer,
 }
 
 /// Render a time picker with hour and minute dropdowns.
@@ -57,45 +96,753 @@ fn time_picker(ui: &mut Ui, time: &mut NaiveTime, id_salt: &str) -> bool {
     changed
 }
 
+/// [`show_local_date_fields`] with the task's times shown in the viewer's
+/// local time. Times the user didn't touch are put back exactly as stored.
+fn show_date_fields(
+    task: &mut Task,
+    clock: ProjectClock,
+    presets: &DatePresets,
+    ui: &mut Ui,
+    action: &mut EditorAction,
+) {
+    let (stored_start, stored_end) = (task.start, task.end);
+    let (local_start, local_end) = (clock.to_local(task.start), clock.to_local(task.end));
+    task.start = local_start;
+    task.end = local_end;
+    show_local_date_fields(task, presets, ui, action);
+    task.start = if task.start == local_start { stored_start } else { clock.from_local(task.start) };
+    task.end = if task.end == local_end { stored_end } else { clock.from_local(task.end) };
+}
+
+/// What a preset's button says: "Tomorrow", "Next Monday +1d", "+1w" or
+/// "Start +3d".
+fn preset_label(preset: &DatePreset) -> String {
+    let base = match (preset.anchor, preset.days) {
+        (PresetAnchor::Today, 1) => return tr("editor.preset.tomorrow").to_string(),
+        (PresetAnchor::Today, _) => tr("editor.preset.today"),
+        (PresetAnchor::NextMonday, _) => tr("editor.preset.next_monday"),
+        (PresetAnchor::Current, 0) => return "±0d".to_string(),
+        (PresetAnchor::Current, _) => return preset.offset(),
+        (PresetAnchor::Start, _) => tr("editor.start"),
+    };
+    match preset.offset() {
+        offset if offset.is_empty() => base.to_string(),
+        offset => format!("{} {}", base, offset),
+    }
+}
+
+/// A row of small buttons for `presets`, captioned `caption`, each showing
+/// the day `pick` gives it on hover; presets giving no day are left out.
+/// Returns the day of the one clicked.
+fn preset_row(
+    ui: &mut Ui,
+    caption: &str,
+    presets: &[DatePreset],
+    pick: impl Fn(&DatePreset) -> Option<NaiveDate>,
+) -> Option<NaiveDate> {
+    if presets.is_empty() {
+        return None;
+    }
+    let mut picked = None;
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing.x = 3.0;
+        ui.label(RichText::new(caption).size(10.0).color(theme::text_dim()));
+        for (preset, date) in presets.iter().filter_map(|p| Some((p, pick(p)?))) {
+            let button = egui::Button::new(RichText::new(preset_label(preset)).size(10.0)).small();
+            let day = format_datetime(date.and_time(NaiveTime::MIN), "date.preset");
+            if ui.add(button).on_hover_text(day).clicked() {
+                picked = Some(date);
+            }
+        }
+    });
+    picked
+}
+
+/// Start, duration and end fields for a task, or the single date of a
+/// milestone, each with the project's quick date presets.
+fn show_local_date_fields(task: &mut Task, presets: &DatePresets, ui: &mut Ui, action: &mut EditorAction) {
+    let today = chrono::Local::now().date_naive();
+    let pick = |current: NaiveDate, start: NaiveDate| {
+        move |preset: &DatePreset| preset.date(today, current, start, presets.skip_weekends)
+    };
+    if !task.is_milestone {
+        ui.horizontal(|ui| {
+            ui.vertical(|ui| {
+                ui.label(
+                    RichText::new(tr("editor.start"))
+                        .size(10.0)
+                        .color(theme::text_dim())
+                        .strong(),
+                );
+                let mut start_date = task.start.date();
+                let resp = ui.add(
+                    egui_extras::DatePickerButton::new(&mut start_date)
+                        .id_salt("dp_start"),
+                );
+                if resp.changed() {
+                    task.set_dates(start_date.and_time(task.start.time()), task.end, MIN_EDIT_SPAN, FixedEdge::Start);
+                    *action = EditorAction::Changed;
+                }
+
+                // Time picker for start
+                ui.add_space(4.0);
+                let mut start_time = task.start.time();
+                if time_picker(ui, &mut start_time, "start_time") {
+                    task.set_dates(task.start.date().and_time(start_time), task.end, MIN_EDIT_SPAN, FixedEdge::Start);
+                    *action = EditorAction::Changed;
+                }
+            });
+
+            ui.add_space(4.0);
+
+            // Duration — editing it moves the end date
+            ui.vertical(|ui| {
+                ui.label(
+                    RichText::new(tr("editor.duration"))
+                        .size(10.0)
+                        .color(theme::text_dim())
+                        .strong(),
+                );
+                let mut days = task.duration_days();
+                let resp = ui.add(
+                    egui::DragValue::new(&mut days)
+                        .range(1..=3650)
+                        .suffix(" d")
+                        .speed(0.1),
+                );
+                if resp.on_hover_text(tr("editor.duration.hint")).changed() {
+                    task.set_duration_days(days);
+                    *action = EditorAction::Changed;
+                }
+            });
+
+            ui.add_space(4.0);
+
+            ui.vertical(|ui| {
+                ui.label(
+                    RichText::new(tr("editor.end"))
+                        .size(10.0)
+                        .color(theme::text_dim())
+                        .strong(),
+                );
+                let mut end_date = task.end.date();
+                let resp = ui.add(
+                    egui_extras::DatePickerButton::new(&mut end_date)
+                        .id_salt("dp_end"),
+                );
+                if resp.changed() {
+                    task.set_dates(task.start, end_date.and_time(task.end.time()), MIN_EDIT_SPAN, FixedEdge::End);
+                    *action = EditorAction::Changed;
+                }
+
+                // Time picker for end
+                ui.add_space(4.0);
+                let mut end_time = task.end.time();
+                if time_picker(ui, &mut end_time, "end_time") {
+                    task.set_dates(task.start, task.end.date().and_time(end_time), MIN_EDIT_SPAN, FixedEdge::End);
+                    *action = EditorAction::Changed;
+                }
+            });
+        });
+
+        let (start, end) = (task.start.date(), task.end.date());
+        if let Some(date) = preset_row(ui, tr("editor.start"), &presets.start, pick(start, start)) {
+            task.set_dates(date.and_time(task.start.time()), task.end, MIN_EDIT_SPAN, FixedEdge::Start);
+            *action = EditorAction::Changed;
+        }
+        if let Some(date) = preset_row(ui, tr("editor.end"), &presets.end, pick(end, start)) {
+            task.set_dates(task.start, date.and_time(task.end.time()), MIN_EDIT_SPAN, FixedEdge::End);
+            *action = EditorAction::Changed;
+        }
+    } else {
+        // Milestone: single date
+        ui.label(
+            RichText::new(tr("editor.date"))
+                .size(10.0)
+                .color(theme::text_dim())
+                .strong(),
+        );
+        let mut milestone_date = task.start.date();
+        let resp = ui.add(
+            egui_extras::DatePickerButton::new(&mut milestone_date)
+                .id_salt("dp_milestone"),
+        );
+        if resp.changed() {
+            task.set_single_date(milestone_date.and_time(task.start.time()));
+            *action = EditorAction::Changed;
+        }
+
+        // Time picker for milestone
+        ui.add_space(4.0);
+        let mut milestone_time = task.start.time();
+        if time_picker(ui, &mut milestone_time, "milestone_time") {
+            task.set_single_date(milestone_date.and_time(milestone_time));
+            *action = EditorAction::Changed;
+        }
+
+        let date = task.start.date();
+        if let Some(date) = preset_row(ui, tr("editor.date"), &presets.start, pick(date, date)) {
+            task.set_single_date(date.and_time(task.start.time()));
+            *action = EditorAction::Changed;
+        }
+    }
+}
+
 /// Persistent state for the "add dependency" picker.
 #[derive(Clone)]
 struct DepPickerState {
     target_id: Option<Uuid>,
     kind: DependencyKind,
+    /// True if this task is the predecessor of the new link.
+    outgoing: bool,
+    search: String,
+}
+
+/// Picker entry for a task, prefixed with its parent to tell apart
+/// same-named tasks in different phases ("Phase 2 ▸ API design").
+fn picker_label(task: &Task, all_tasks: &[Task]) -> String {
+    match task.parent_id.and_then(|pid| all_tasks.iter().find(|t| t.id == pid)) {
+        Some(parent) => format!("{} ▸ {}", parent.name, task.name),
+        None => task.name.clone(),
+    }
+}
+
+/// Persistent state for the colour section.
+#[derive(Clone)]
+struct ColorPickerState {
+    custom_open: bool,
+    /// Colour being edited in the custom picker, applied on "Apply".
+    draft: Color32,
+    apply_to_children: bool,
+}
+
+/// A clickable colour swatch, enlarged and outlined when current.
+fn color_swatch(ui: &mut Ui, color: Color32, is_current: bool) -> egui::Response {
+    let size = if is_current { 20.0 } else { 16.0 };
+    let (rect, resp) = ui.allocate_exact_size(egui::vec2(size, size), egui::Sense::click());
+    ui.painter().rect_filled(rect, egui::Rounding::same(3.0), color);
+    if is_current {
+        ui.painter().rect_stroke(
+            rect.expand(1.0),
+            egui::Rounding::same(4.0),
+            egui::Stroke::new(2.0, theme::text_primary()),
+        );
+    }
+    resp
+}
+
+/// Split a comma-separated tag list, dropping blanks and duplicates.
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        if !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
 }
 
-/// Short label for a dependency from this task's perspective.
-fn dep_kind_label(kind: DependencyKind, is_outgoing: bool) -> String {
-    let arrow = if is_outgoing {
+/// "1 day" / "N days".
+fn format_duration(days: i64) -> String {
+    if days == 1 { tr("editor.days_one").to_string() } else { trf("editor.days", &[("n", &days)]) }
+}
+
+/// One line of values derived from the task: span, link counts and how far
+/// it is from (or past) its end date. Not editable, so never reports a change.
+fn show_info_strip(ui: &mut Ui, task: &Task, graph: &DependencyGraph, clock: ProjectClock) {
+    let chip = |ui: &mut Ui, text: String, color: Color32| ui.label(RichText::new(text).size(10.0).color(color));
+    let predecessors = graph.predecessors(task.id).len();
+    let successors = graph.successors(task.id).len();
+    let now = clock.now();
+    let today = now.date();
+    let days_left = (task.end.date() - today).num_days();
+
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing.x = 10.0;
+        if !task.is_milestone {
+            chip(
+                ui,
+                trf("editor.working", &[("duration", &format_duration(task.duration_days())), ("working", &task.working_days())]),
+                theme::text_dim(),
+            );
+        }
+        chip(
+            ui,
+            format!("{} {}  {} {}", egui_phosphor::regular::ARROW_LEFT, predecessors, successors, egui_phosphor::regular::ARROW_RIGHT),
+            theme::text_dim(),
+        )
+        .on_hover_text(trf("editor.links", &[("predecessors", &predecessors), ("successors", &successors)]));
+        if !task.is_milestone && task.start < now && task.progress < 1.0 {
+            let variance = task.progress_variance(now);
+            chip(ui, trf("editor.vs_plan", &[("points", &format!("{:+.0}", variance * 100.0))]), task.schedule_status(now).status_color()).on_hover_text(
+                trf(
+                    "editor.expected",
+                    &[
+                        ("expected", &format!("{:.0}", task.expected_progress(now) * 100.0)),
+                        ("actual", &format!("{:.0}", task.progress * 100.0)),
+                    ],
+                ),
+            );
+        }
+        if task.progress >= 1.0 {
+            chip(ui, tr("editor.complete").to_string(), theme::text_dim());
+        } else if days_left < 0 {
+            chip(ui, trf("editor.overdue", &[("duration", &format_duration(-days_left))]), theme::status_color(Severity::Bad, Color32::from_rgb(230, 100, 100)));
+        } else if days_left == 0 {
+            chip(ui, tr("editor.due_today").to_string(), theme::text_secondary());
+        } else {
+            chip(ui, trf("editor.due_in", &[("duration", &format_duration(days_left))]), theme::text_dim());
+        }
+    });
+}
+
+/// Direction arrow for a dependency from this task's perspective.
+fn dep_direction_icon(is_outgoing: bool) -> &'static str {
+    if is_outgoing {
         egui_phosphor::regular::ARROW_RIGHT
     } else {
         egui_phosphor::regular::ARROW_LEFT
+    }
+}
+
+/// Comment stream, newest first, with the box for a new comment on top.
+/// Only comments by `author` can be deleted.
+fn show_comments(ui: &mut Ui, task: &Task, author: &str, action: &mut EditorAction) {
+    ui.label(
+        RichText::new(trf("editor.comments", &[("count", &task.comments.len())]))
+            .size(10.0)
+            .color(theme::text_dim())
+            .strong(),
+    );
+
+    let draft_id = Id::new(("comment-draft", task.id));
+    let mut draft = ui.ctx().data(|d| d.get_temp::<String>(draft_id)).unwrap_or_default();
+    let resp = ui.add_sized(
+        [ui.available_width(), 36.0],
+        egui::TextEdit::multiline(&mut draft)
+            .font(egui::FontId::proportional(11.0))
+            .hint_text(trf("editor.comment.hint", &[("author", &author)])),
+    );
+    let submit = resp.has_focus() && ui.input(|i| i.modifiers.command && i.key_pressed(egui::Key::Enter));
+    let can_post = !draft.trim().is_empty();
+    let post = ui
+        .horizontal(|ui| ui.add_enabled(can_post, egui::Button::new(RichText::new(tr("editor.post")).size(11.0)).small()).clicked())
+        .inner;
+    if can_post && (post || submit) {
+        *action = EditorAction::AddComment(std::mem::take(&mut draft));
+    }
+    ui.ctx().data_mut(|d| d.insert_temp(draft_id, draft));
+
+    for comment in task.comments.iter().rev() {
+        ui.add_space(2.0);
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(&comment.author).size(10.5).strong().color(theme::text_secondary()));
+            ui.label(
+                RichText::new(format_datetime(comment.created, "date.comment"))
+                    .size(9.5)
+                    .color(theme::text_dim()),
+            );
+            if comment.author == author {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let del = ui.add(
+                        egui::Button::new(RichText::new(egui_phosphor::regular::X).size(9.0).color(theme::text_dim()))
+                            .frame(false),
+                    );
+                    if del.on_hover_text(tr("editor.comment.delete")).clicked() {
+                        *action = EditorAction::DeleteComment(comment.id);
+                    }
+                });
+            }
+        });
+        ui.add(egui::Label::new(RichText::new(&comment.text).size(11.0).color(theme::text_primary())).wrap());
+    }
+}
+
+/// The task's checklist: tick, reword or remove items, and add new ones.
+fn show_checklist(ui: &mut Ui, task: &mut Task, action: &mut EditorAction) {
+    let done = task.checklist.iter().filter(|item| item.done).count();
+    ui.label(
+        RichText::new(trf("editor.checklist", &[("done", &done), ("count", &task.checklist.len())]))
+            .size(10.0)
+            .color(theme::text_dim())
+            .strong(),
+    );
+    let mut remove = None;
+    for (i, item) in task.checklist.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            if ui.checkbox(&mut item.done, "").changed() {
+                *action = EditorAction::Changed;
+            }
+            let resp = ui.add(
+                egui::TextEdit::singleline(&mut item.text)
+                    .font(egui::FontId::proportional(11.0))
+                    .desired_width((ui.available_width() - 20.0).max(40.0)),
+            );
+            if resp.changed() {
+                *action = EditorAction::Changed;
+            }
+            let del = ui.add(
+                egui::Button::new(RichText::new(egui_phosphor::regular::X).size(9.0).color(theme::text_dim()))
+                    .frame(false),
+            );
+            if del.on_hover_text(tr("editor.checklist.delete")).clicked() {
+                remove = Some(i);
+            }
+        });
+    }
+    if let Some(i) = remove {
+        task.checklist.remove(i);
+        *action = EditorAction::Changed;
+    }
+
+    let draft_id = Id::new(("checklist-draft", task.id));
+    let mut draft = ui.ctx().data(|d| d.get_temp::<String>(draft_id)).unwrap_or_default();
+    let resp = ui.add_sized(
+        [ui.available_width(), 22.0],
+        egui::TextEdit::singleline(&mut draft)
+            .font(egui::FontId::proportional(11.0))
+            .hint_text(tr("editor.checklist.hint")),
+    );
+    if resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) && !draft.trim().is_empty() {
+        task.checklist.push(ChecklistItem::new(draft.trim()));
+        draft.clear();
+        *action = EditorAction::Changed;
+        resp.request_focus();
+    }
+    ui.ctx().data_mut(|d| d.insert_temp(draft_id, draft));
+}
+
+/// Timer button, tracked total against the estimate, and the time entries
+/// as a small editable table.
+fn show_time_tracking(ui: &mut Ui, task: &mut Task, action: &mut EditorAction) {
+    let now = chrono::Local::now().naive_local();
+    ui.label(RichText::new(tr("editor.time")).size(10.0).color(theme::text_dim()).strong());
+    ui.horizontal(|ui| {
+        let running = task.running_entry().is_some();
+        let (icon, tip) = if running {
+            (egui_phosphor::regular::STOP, tr("editor.timer.stop"))
+        } else {
+            (egui_phosphor::regular::PLAY, tr("editor.timer.start"))
+        };
+        if ui.selectable_label(running, icon).on_hover_text(tip).clicked() {
+            *action = EditorAction::ToggleTimer;
+        }
+        let tracked = task.tracked_hours(now);
+        let over = task.estimate_hours.is_some_and(|e| tracked > e);
+        let text = match task.estimate_hours {
+            Some(estimate) => trf("editor.tracked_of", &[("tracked", &format!("{:.1}", tracked)), ("estimate", &format!("{:.1}", estimate))]),
+            None => trf("editor.tracked", &[("tracked", &format!("{:.1}", tracked))]),
+        };
+        let color = if over { Color32::from_rgb(220, 60, 60) } else { theme::text_secondary() };
+        ui.label(RichText::new(text).size(11.0).color(color));
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            let mut estimate = task.estimate_hours.unwrap_or(0.0);
+            let resp = ui.add(egui::DragValue::new(&mut estimate).range(0.0..=10_000.0).speed(0.25).suffix(" h"));
+            if resp.on_hover_text(tr("editor.estimate.hint")).changed() {
+                task.estimate_hours = Some(estimate).filter(|e| *e > 0.0);
+                *action = EditorAction::Changed;
+            }
+            ui.label(RichText::new(tr("editor.estimate")).size(10.0).color(theme::text_dim()));
+        });
+    });
+
+    let mut remove = None;
+    egui::CollapsingHeader::new(RichText::new(trf("editor.entries", &[("count", &task.time_entries.len())])).size(10.5))
+        .id_salt(("time-entries", task.id))
+        .show(ui, |ui| {
+            egui::Grid::new(("time-entry-grid", task.id)).num_columns(4).spacing([6.0, 4.0]).show(ui, |ui| {
+                for (i, entry) in task.time_entries.iter_mut().enumerate() {
+                    let mut date = entry.start.date();
+                    if ui.add(egui_extras::DatePickerButton::new(&mut date).id_salt(&format!("te_date_{}", i))).changed() {
+                        let length = entry.end.map(|end| end - entry.start);
+                        entry.start = date.and_time(entry.start.time());
+                        entry.end = length.map(|l| entry.start + l);
+                        *action = EditorAction::Changed;
+                    }
+                    let mut time = entry.start.time();
+                    if time_picker(ui, &mut time, &format!("te_time_{}", i)) {
+                        let length = entry.end.map(|end| end - entry.start);
+                        entry.start = entry.start.date().and_time(time);
+                        entry.end = length.map(|l| entry.start + l);
+                        *action = EditorAction::Changed;
+                    }
+                    match entry.end {
+                        Some(end) => {
+                            let mut hours = (end - entry.start).num_minutes() as f32 / 60.0;
+                            let resp = ui.add(egui::DragValue::new(&mut hours).range(0.0..=24.0).speed(0.05).suffix(" h"));
+                            if resp.changed() {
+                                entry.end = Some(entry.start + chrono::Duration::minutes((hours * 60.0).round() as i64));
+                                *action = EditorAction::Changed;
+                            }
+                        }
+                        None => {
+                            ui.label(RichText::new(tr("editor.running")).size(10.0).color(Color32::from_rgb(220, 60, 60)));
+                        }
+                    }
+                    let del = ui.add(
+                        egui::Button::new(RichText::new(egui_phosphor::regular::X).size(9.0).color(theme::text_dim()))
+                            .frame(false),
+                    );
+                    if del.on_hover_text(tr("editor.entry.delete")).clicked() {
+                        remove = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+            if ui.small_button(format!("{} {}", egui_phosphor::regular::PLUS, tr("editor.entry.add"))).clicked() {
+                task.time_entries.push(TimeEntry { start: now - chrono::Duration::hours(1), end: Some(now) });
+                *action = EditorAction::Changed;
+            }
+        });
+    if let Some(i) = remove {
+        task.time_entries.remove(i);
+        *action = EditorAction::Changed;
+    }
+}
+
+/// The task's logged progress over time as a small step line, with the
+/// span and the change over it on hover.
+fn show_progress_sparkline(ui: &mut Ui, readings: &[TaskProgressSample]) {
+    let (Some(first), Some(last)) = (readings.first(), readings.last()) else {
+        return;
     };
-    format!("[{}] {}", kind.short_label(), arrow)
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 24.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, egui::Rounding::same(2.0), theme::bg_dark());
+    let plot = rect.shrink2(egui::vec2(2.0, 3.0));
+    let days = (last.date - first.date).num_days().max(1) as f32;
+    let x = |s: &TaskProgressSample| plot.left() + plot.width() * (s.date - first.date).num_days() as f32 / days;
+    let y = |s: &TaskProgressSample| plot.bottom() - plot.height() * s.progress;
+    let mut points: Vec<egui::Pos2> = Vec::new();
+    for reading in readings {
+        if let Some(&prev) = points.last() {
+            points.push(egui::pos2(x(reading), prev.y));
+        }
+        points.push(egui::pos2(x(reading), y(reading)));
+    }
+    painter.add(egui::Shape::line(points, egui::Stroke::new(1.5, theme::accent())));
+    response.on_hover_text(trf(
+        "editor.progress_history",
+        &[
+            ("from", &first.date.format("%Y-%m-%d")),
+            ("to", &last.date.format("%Y-%m-%d")),
+            ("start", &format!("{:.0}", first.progress * 100.0)),
+            ("end", &format!("{:.0}", last.progress * 100.0)),
+        ],
+    ));
+}
+
+/// Recorded changes to the task's fields and links, newest first.
+fn show_history(ui: &mut Ui, task_id: Uuid, activity: &ActivityLog) {
+    let mut any = false;
+    for event in activity.for_task(task_id) {
+        any = true;
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(event.field.label()).size(10.5).strong().color(theme::text_secondary()));
+            ui.label(
+                RichText::new(format_datetime(event.at, "date.comment"))
+                    .size(9.5)
+                    .color(theme::text_dim()),
+            );
+        });
+        ui.add(egui::Label::new(RichText::new(event.summary()).size(11.0).color(theme::text_primary())).wrap());
+        ui.add_space(2.0);
+    }
+    if !any {
+        ui.label(RichText::new(tr("editor.no_history")).size(10.0).color(theme::text_dim()));
+    }
+}
+
+/// The "add dependency" picker for `task_id`: which way the link runs, its
+/// kind and the task at the other end. Returns the new link when its add
+/// button is clicked. `id_salt` keeps the picker's state apart where it is
+/// shown more than once.
+pub fn dependency_picker(
+    ui: &mut Ui,
+    id_salt: &str,
+    task_id: Uuid,
+    project: &Project,
+    graph: &DependencyGraph,
+) -> Option<Dependency> {
+    let all_tasks = &project.tasks;
+    let mut added = None;
+    // Candidates: every other leaf task that isn't already linked to
+    // this one and wouldn't close a cycle in the chosen direction.
+    // Summary (parent) tasks are excluded — their dates are derived.
+    let picker_id = Id::new(("dep-picker", id_salt, task_id));
+    let mut state: DepPickerState = ui.ctx().data_mut(|d| {
+        d.get_temp(picker_id).unwrap_or(DepPickerState {
+            target_id: None,
+            kind: project.task_defaults.dependency_kind,
+            outgoing: true,
+            search: String::new(),
+        })
+    });
+
+    let already_linked: std::collections::HashSet<Uuid> = graph
+        .links_of(task_id)
+        .map(|d| if d.from_task == task_id { d.to_task } else { d.from_task })
+        .collect();
+
+    let candidates: Vec<(Uuid, String)> = all_tasks
+        .iter()
+        .filter(|t| {
+            if t.id == task_id || already_linked.contains(&t.id) {
+                return false;
+            }
+            if t.has_children(all_tasks) {
+                return false;
+            }
+            let (from, to) = if state.outgoing { (task_id, t.id) } else { (t.id, task_id) };
+            !graph.would_create_cycle(from, to)
+        })
+        .map(|t| (t.id, picker_label(t, all_tasks)))
+        .collect();
+
+    if state.target_id.is_some_and(|id| !candidates.iter().any(|(cid, _)| *cid == id)) {
+        state.target_id = None;
+    }
+    let target_label = state.target_id
+        .and_then(|id| candidates.iter().find(|(cid, _)| *cid == id))
+        .map(|(_, name)| name.clone())
+        .unwrap_or_else(|| tr("editor.pick_task").to_string());
+
+    ui.label(
+        RichText::new(tr("editor.add_dependency"))
+            .size(10.0)
+            .color(theme::text_dim())
+            .strong(),
+    );
+
+    // Direction: is this task the predecessor or the successor?
+    ui.horizontal(|ui| {
+        ui.selectable_value(&mut state.outgoing, true, RichText::new(tr("editor.blocks")).size(11.0))
+            .on_hover_text(tr("editor.blocks.hint"));
+        ui.selectable_value(&mut state.outgoing, false, RichText::new(tr("editor.depends_on")).size(11.0))
+            .on_hover_text(tr("editor.depends_on.hint"));
+    });
+
+    // Row: [kind combo] [target combo] [+ button]
+    ui.horizontal(|ui| {
+        // Kind selector (narrow)
+        let kind_resp = egui::ComboBox::from_id_salt("new-dep-kind")
+            .selected_text(RichText::new(state.kind.short_label()).size(11.0))
+            .width(46.0)
+            .show_ui(ui, |ui| {
+                for k in DependencyKind::all() {
+                    let row = ui.selectable_label(state.kind == *k, k.short_label());
+                    if row.on_hover_text(k.description()).clicked() {
+                        state.kind = *k;
+                    }
+                }
+            });
+        kind_resp.response.on_hover_text(state.kind.description());
+
+        // Target task selector — use a fixed width to avoid stretching the panel
+        let combo_w = (ui.available_width() - 30.0).clamp(60.0, 220.0);
+        egui::ComboBox::from_id_salt("new-dep-target")
+            .selected_text(RichText::new(&target_label).size(11.0))
+            .width(combo_w)
+            .height(260.0)
+            .show_ui(ui, |ui| {
+                let search = ui.add(
+                    egui::TextEdit::singleline(&mut state.search)
+                        .hint_text(format!("{} {}", egui_phosphor::regular::MAGNIFYING_GLASS, tr("editor.search_tasks")))
+                        .desired_width(f32::INFINITY),
+                );
+                if ui.memory(|m| m.focused().is_none()) {
+                    search.request_focus();
+                }
+                let query = state.search.to_lowercase();
+                let mut shown = 0;
+                for (cid, cname) in &candidates {
+                    if !query.is_empty() && !cname.to_lowercase().contains(&query) {
+                        continue;
+                    }
+                    shown += 1;
+                    if ui.selectable_label(state.target_id == Some(*cid), cname.as_str()).clicked() {
+                        state.target_id = Some(*cid);
+                        state.search.clear();
+                    }
+                }
+                if shown == 0 {
+                    ui.label(RichText::new(tr("editor.no_matches")).size(10.0).color(theme::text_dim()));
+                }
+            });
+
+        // Add button
+        let can_add = state.target_id.is_some();
+        let btn = egui::Button::new(RichText::new("＋").size(13.0).color(theme::text_on(theme::accent())))
+            .fill(if can_add { theme::accent() } else { theme::bg_field() })
+            .rounding(egui::Rounding::same(4.0));
+        let add_resp = ui.add_enabled(can_add, btn);
+        if add_resp.clicked() {
+            if let Some(target) = state.target_id {
+                let (from_task, to_task) =
+                    if state.outgoing { (task_id, target) } else { (target, task_id) };
+                added = Some(Dependency {
+                    from_task,
+                    to_task,
+                    kind: state.kind,
+                    lag_days: 0,
+                });
+                state.target_id = None; // reset after adding
+            }
+        }
+    });
+
+    ui.ctx().data_mut(|d| d.insert_temp(picker_id, state));
+    added
 }
 
-/// Render an inline task editor for the selected task.
-/// Also shows dependencies involving this task.
+/// Render an inline task editor for `task`, an edited copy of one of
+/// `project`'s tasks. Also shows dependencies involving this task and its
+/// comments, posted as `author`, and the task's entries in the project's
+/// activity log. Dates are shown in the viewer's local time.
 pub fn show_task_editor(
     task: &mut Task,
-    all_tasks: &[Task],
-    dependencies: &[Dependency],
+    project: &Project,
+    graph: &DependencyGraph,
+    author: &str,
     ui: &mut Ui,
 ) -> EditorAction {
+    let all_tasks = &project.tasks;
+    let recent_colors = &project.recent_colors;
+    let clock = project.clock();
     let mut action = EditorAction::None;
     let task_id = task.id;
+    let is_parent_task = task.has_children(all_tasks);
+    let history_id = Id::new("editor-history-tab");
+    let mut show_history_tab = ui.ctx().data(|d| d.get_temp::<bool>(history_id)).unwrap_or(false);
 
     // Section header
     ui.add_space(6.0);
     ui.horizontal(|ui| {
         ui.label(
-            RichText::new("Edit Task")
+            RichText::new(tr("editor.title"))
                 .strong()
                 .size(13.0)
                 .color(theme::text_primary()),
         );
+        ui.add_space(6.0);
+        ui.selectable_value(&mut show_history_tab, false, RichText::new(tr("editor.details")).size(11.0));
+        ui.selectable_value(&mut show_history_tab, true, RichText::new(tr("editor.history")).size(11.0));
+        // Parent dates come from their children, so there is nothing to lock.
+        if !is_parent_task {
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let (icon, tip) = if task.locked {
+                    (egui_phosphor::regular::LOCK_SIMPLE, tr("editor.locked.hint"))
+                } else {
+                    (egui_phosphor::regular::LOCK_SIMPLE_OPEN, tr("editor.lock.hint"))
+                };
+                if ui.selectable_label(task.locked, icon).on_hover_text(tip).clicked() {
+                    action = EditorAction::SetLocked(!task.locked);
+                }
+            });
+        }
     });
     ui.add_space(4.0);
+    ui.ctx().data_mut(|d| d.insert_temp(history_id, show_history_tab));
 
     let frame = egui::Frame {
         fill: theme::bg_dark(),
@@ -110,10 +857,32 @@ pub fn show_task_editor(
         ui.spacing_mut().item_spacing.y = 6.0;
         // Force dark text-field backgrounds
         ui.visuals_mut().extreme_bg_color = theme::bg_field();
+        if show_history_tab {
+            show_history(ui, task_id, &project.activity);
+            return;
+        }
+
+        // ── Warnings ──────────────────────────────────────────────────
+        let issues = validation::validate_task(task, all_tasks, graph, &project.absences);
+        if !issues.is_empty() {
+            let warn_color = Color32::from_rgb(220, 140, 40);
+            for issue in &issues {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(RichText::new(egui_phosphor::regular::WARNING).size(11.0).color(warn_color));
+                    ui.label(RichText::new(&issue.message).size(10.5).color(theme::text_secondary()));
+                    if let Some(fix) = issue.fix {
+                        if ui.small_button(fix.label()).clicked() {
+                            action = EditorAction::ApplyFix(fix);
+                        }
+                    }
+                });
+            }
+            ui.separator();
+        }
 
         // ── Task Name ──────────────────────────────────────────────────
         ui.label(
-            RichText::new("Name")
+            RichText::new(tr("editor.name"))
                 .size(10.0)
                 .color(theme::text_dim())
                 .strong(),
@@ -132,18 +901,18 @@ pub fn show_task_editor(
 
         // ── Priority ──────────────────────────────────────────────────
         ui.label(
-            RichText::new("Priority")
+            RichText::new(tr("editor.priority"))
                 .size(10.0)
                 .color(theme::text_dim())
                 .strong(),
         );
-        let pri_label = format!("{} {}", task.priority.icon(), task.priority.label());
+        let pri_label = format!("{} {}", task.priority.icon(), priority_label(task.priority));
         egui::ComboBox::from_id_salt("priority_combo")
             .selected_text(RichText::new(&pri_label).size(11.0))
             .width(ui.available_width())
             .show_ui(ui, |ui| {
                 for p in TaskPriority::all() {
-                    let lbl = format!("{} {}", p.icon(), p.label());
+                    let lbl = format!("{} {}", p.icon(), priority_label(*p));
                     if ui.selectable_value(&mut task.priority, *p, lbl).changed() {
                         action = EditorAction::Changed;
                     }
@@ -152,9 +921,155 @@ pub fn show_task_editor(
 
         ui.add_space(2.0);
 
+        // ── Risk ──────────────────────────────────────────────────────
+        ui.label(
+            RichText::new(tr("editor.risk"))
+                .size(10.0)
+                .color(theme::text_dim())
+                .strong(),
+        );
+        let risk_text = |risk: Option<RiskLevel>| match risk {
+            Some(level) => RichText::new(format!("{} {}", egui_phosphor::regular::TRIANGLE, risk_label(risk)))
+                .color(level.status_color()),
+            None => RichText::new(risk_label(None)),
+        };
+        egui::ComboBox::from_id_salt("risk_combo")
+            .selected_text(risk_text(task.risk).size(11.0))
+            .width(ui.available_width())
+            .show_ui(ui, |ui| {
+                let levels = std::iter::once(None).chain(RiskLevel::all().iter().copied().map(Some));
+                for level in levels {
+                    if ui.selectable_value(&mut task.risk, level, risk_text(level)).changed() {
+                        action = EditorAction::Changed;
+                    }
+                }
+            });
+
+        ui.add_space(2.0);
+
+        // ── Assignee ──────────────────────────────────────────────────
+        ui.label(
+            RichText::new(tr("editor.assignee"))
+                .size(10.0)
+                .color(theme::text_dim())
+                .strong(),
+        );
+        ui.horizontal(|ui| {
+            let mut assignee = task.assignee.clone().unwrap_or_default();
+            let resp = ui.add_sized(
+                [ui.available_width() - 28.0, 22.0],
+                egui::TextEdit::singleline(&mut assignee)
+                    .font(egui::FontId::proportional(11.0))
+                    .hint_text(tr("editor.unassigned")),
+            );
+            if resp.changed() {
+                task.assignee = Some(assignee).filter(|a| !a.trim().is_empty());
+                action = EditorAction::Changed;
+            }
+            let known = assignee_names(all_tasks);
+            ui.add_enabled_ui(!known.is_empty(), |ui| {
+                ui.menu_button(egui_phosphor::regular::CARET_DOWN, |ui| {
+                    for name in known {
+                        if ui.button(&name).clicked() {
+                            task.assignee = Some(name);
+                            action = EditorAction::Changed;
+                            ui.close_menu();
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(tr("editor.pick_assignee"));
+            });
+        });
+
+        ui.add_space(2.0);
+
+        // ── Link ──────────────────────────────────────────────────────
+        ui.label(
+            RichText::new(tr("editor.link"))
+                .size(10.0)
+                .color(theme::text_dim())
+                .strong(),
+        );
+        ui.horizontal(|ui| {
+            let mut link = task.link.clone().unwrap_or_default();
+            let resp = ui.add_sized(
+                [ui.available_width() - 28.0, 22.0],
+                egui::TextEdit::singleline(&mut link)
+                    .font(egui::FontId::proportional(11.0))
+                    .hint_text("https://…"),
+            );
+            if resp.changed() {
+                task.link = Some(link).filter(|l| !l.trim().is_empty());
+                action = EditorAction::Changed;
+            }
+            let open = ui
+                .add_enabled(task.link.is_some(), egui::Button::new(egui_phosphor::regular::ARROW_SQUARE_OUT))
+                .on_hover_text(tr("editor.open_link"));
+            if open.clicked() {
+                if let Some(link) = &task.link {
+                    let _ = open::that(link.trim());
+                }
+            }
+        });
+        ui.add_space(2.0);
+
+        // ── Slug ──────────────────────────────────────────────────────
+        ui.label(
+            RichText::new(tr("editor.slug"))
+                .size(10.0)
+                .color(theme::text_dim())
+                .strong(),
+        )
+        .on_hover_text(tr("editor.slug.hint"));
+        // What's typed stays in the field while it has focus, valid or not;
+        // only a valid slug reaches the task.
+        let slug_id = Id::new(("editor-slug", task_id));
+        let mut draft = ui
+            .ctx()
+            .data(|d| d.get_temp::<String>(slug_id))
+            .unwrap_or_else(|| task.slug.clone().unwrap_or_default());
+        let suggestion = project.suggest_slug(&task.name, task_id);
+        ui.horizontal(|ui| {
+            let resp = ui.add_sized(
+                [ui.available_width() - 28.0, 22.0],
+                egui::TextEdit::singleline(&mut draft)
+                    .font(egui::FontId::proportional(11.0))
+                    .hint_text(suggestion.as_deref().unwrap_or("")),
+            );
+            if resp.changed() {
+                let slug = Some(draft.trim()).filter(|s| !s.is_empty());
+                let valid = slug.is_none_or(|s| project.slug_problem(task_id, s).is_none());
+                if valid && task.slug.as_deref() != slug {
+                    task.slug = slug.map(str::to_string);
+                    action = EditorAction::Changed;
+                }
+            }
+            if resp.has_focus() {
+                ui.ctx().data_mut(|d| d.insert_temp(slug_id, draft.clone()));
+            } else {
+                ui.ctx().data_mut(|d| d.remove::<String>(slug_id));
+            }
+            let suggest = ui
+                .add_enabled(
+                    suggestion.is_some() && task.slug != suggestion,
+                    egui::Button::new(egui_phosphor::regular::MAGIC_WAND),
+                )
+                .on_hover_text(tr("editor.slug.suggest"));
+            if suggest.clicked() {
+                task.slug = suggestion.clone();
+                ui.ctx().data_mut(|d| d.remove::<String>(slug_id));
+                action = EditorAction::Changed;
+            }
+        });
+        if let Some(problem) = Some(draft.trim()).filter(|s| !s.is_empty()).and_then(|s| project.slug_problem(task_id, s)) {
+            ui.label(RichText::new(problem).size(10.5).color(Color32::from_rgb(220, 60, 60)));
+        }
+        ui.add_space(2.0);
+
         // ── Parent Task (Phase/Group) ────────────────────────────────
         ui.label(
-            RichText::new("Phase / Parent")
+            RichText::new(tr("editor.parent"))
                 .size(10.0)
                 .color(theme::text_dim())
                 .strong(),
@@ -163,7 +1078,7 @@ pub fn show_task_editor(
             .parent_id
             .and_then(|pid| all_tasks.iter().find(|t| t.id == pid))
             .map(|t| t.name.clone())
-            .unwrap_or_else(|| "— None —".to_string());
+            .unwrap_or_else(|| tr("editor.none").to_string());
 
         // Collect valid parent candidates:
         // - not self, not own children, and not already a child (one-level only)
@@ -182,7 +1097,7 @@ pub fn show_task_editor(
             .width(ui.available_width())
             .show_ui(ui, |ui| {
                 if ui
-                    .selectable_label(task.parent_id.is_none(), "— None —")
+                    .selectable_label(task.parent_id.is_none(), tr("editor.none"))
                     .clicked()
                 {
                     task.parent_id = None;
@@ -203,136 +1118,71 @@ pub fn show_task_editor(
 
         // ── Dates ───────────────────────────────────────────────────
         // For parent tasks, dates are auto-calculated from children (read-only).
-        let is_parent_task = task.has_children(all_tasks);
         if is_parent_task {
-            ui.label(RichText::new("Dates").size(10.0).color(theme::text_dim()).strong());
+            ui.label(RichText::new(tr("editor.dates")).size(10.0).color(theme::text_dim()).strong());
             ui.horizontal(|ui| {
-                ui.label(RichText::new(task.start.format("%Y-%m-%d %H:%M").to_string()).size(11.0).color(theme::text_secondary()));
+                ui.label(RichText::new(clock.to_local(task.start).format("%Y-%m-%d %H:%M").to_string()).size(11.0).color(theme::text_secondary()));
                 ui.label(RichText::new(egui_phosphor::regular::ARROW_RIGHT).size(10.0).color(theme::text_dim()));
-                ui.label(RichText::new(task.end.format("%Y-%m-%d %H:%M").to_string()).size(11.0).color(theme::text_secondary()));
-                ui.label(RichText::new("(auto)").size(9.0).color(theme::text_dim()));
+                ui.label(RichText::new(clock.to_local(task.end).format("%Y-%m-%d %H:%M").to_string()).size(11.0).color(theme::text_secondary()));
+                ui.label(RichText::new(tr("editor.auto")).size(9.0).color(theme::text_dim()));
             });
+            ui.label(
+                RichText::new(format_duration(task.duration_days()))
+                    .size(11.0)
+                    .color(theme::text_secondary()),
+            );
             ui.add_space(2.0);
             // Progress: read-only for parent
-            ui.label(RichText::new("Progress").size(10.0).color(theme::text_dim()).strong());
-            ui.label(RichText::new(format!("{:.0}%  (auto-calculated)", task.progress * 100.0)).size(11.0).color(theme::text_secondary()));
+            ui.label(RichText::new(tr("editor.progress")).size(10.0).color(theme::text_dim()).strong());
+            ui.label(RichText::new(trf("editor.progress_auto", &[("percent", &format!("{:.0}", task.progress * 100.0))])).size(11.0).color(theme::text_secondary()))
+                .on_hover_text(tr("editor.progress_auto.hint"));
             ui.add_space(4.0);
             // Add subtask button
-            let btn = egui::Button::new(RichText::new(egui_phosphor::regular::PLUS.to_string() + "  Add Subtask").color(Color32::WHITE).size(12.0))
+            let btn = egui::Button::new(RichText::new(format!("{}  {}", egui_phosphor::regular::PLUS, tr("editor.add_subtask"))).color(theme::text_on(theme::accent())).size(12.0))
                 .fill(theme::accent())
                 .rounding(egui::Rounding::same(4.0));
             if ui.add_sized([ui.available_width(), 26.0], btn).clicked() {
                 action = EditorAction::AddSubtask(task_id);
             }
+            let arrange = egui::Button::new(
+                RichText::new(format!("{}  {}", egui_phosphor::regular::ARROWS_HORIZONTAL, tr("editor.arrange_children"))).size(12.0),
+            )
+            .rounding(egui::Rounding::same(4.0));
+            if ui.add_sized([ui.available_width(), 24.0], arrange).on_hover_text(tr("editor.arrange_children.hint")).clicked() {
+                action = EditorAction::ArrangeChildren(task_id);
+            }
             ui.add_space(2.0);
-        } else if !task.is_milestone {
-            ui.horizontal(|ui| {
-                ui.vertical(|ui| {
-                    ui.label(
-                        RichText::new("Start")
-                            .size(10.0)
-                            .color(theme::text_dim())
-                            .strong(),
-                    );
-                    let mut start_date = task.start.date();
-                    let resp = ui.add(
-                        egui_extras::DatePickerButton::new(&mut start_date)
-                            .id_salt("dp_start"),
-                    );
-                    if resp.changed() {
-                        task.start = start_date.and_time(task.start.time());
-                        if task.start > task.end {
-                            task.end = task.start;
-                        }
-                        action = EditorAction::Changed;
-                    }
-
-                    // Time picker for start
-                    ui.add_space(4.0);
-                    let mut start_time = task.start.time();
-                    if time_picker(ui, &mut start_time, "start_time") {
-                        task.start = task.start.date().and_time(start_time);
-                        if task.start > task.end {
-                            task.end = task.start;
-                        }
-                        action = EditorAction::Changed;
-                    }
-                });
-
-                ui.add_space(8.0);
-
-                ui.vertical(|ui| {
-                    ui.label(
-                        RichText::new("End")
-                            .size(10.0)
-                            .color(theme::text_dim())
-                            .strong(),
-                    );
-                    let mut end_date = task.end.date();
-                    let resp = ui.add(
-                        egui_extras::DatePickerButton::new(&mut end_date)
-                            .id_salt("dp_end"),
-                    );
-                    if resp.changed() {
-                        task.end = end_date.and_time(task.end.time());
-                        if task.end < task.start {
-                            task.start = task.end;
-                        }
-                        action = EditorAction::Changed;
-                    }
-
-                    // Time picker for end
-                    ui.add_space(4.0);
-                    let mut end_time = task.end.time();
-                    if time_picker(ui, &mut end_time, "end_time") {
-                        task.end = task.end.date().and_time(end_time);
-                        if task.end < task.start {
-                            task.start = task.end;
-                        }
-                        action = EditorAction::Changed;
-                    }
-                });
-            });
         } else {
-            // Milestone: single date
-            ui.label(
-                RichText::new("Date")
-                    .size(10.0)
-                    .color(theme::text_dim())
-                    .strong(),
-            );
-            let mut milestone_date = task.start.date();
-            let resp = ui.add(
-                egui_extras::DatePickerButton::new(&mut milestone_date)
-                    .id_salt("dp_milestone"),
-            );
-            if resp.changed() {
-                task.start = milestone_date.and_time(task.start.time());
-                task.end = task.start;
-                action = EditorAction::Changed;
+            if task.locked {
+                ui.label(
+                    RichText::new(format!("{} {}", egui_phosphor::regular::LOCK_SIMPLE, tr("editor.locked")))
+                        .size(10.0)
+                        .color(theme::text_dim()),
+                );
             }
+            ui.add_enabled_ui(!task.locked, |ui| show_date_fields(task, clock, &project.date_presets, ui, &mut action));
+        }
 
-            // Time picker for milestone
-            ui.add_space(4.0);
-            let mut milestone_time = task.start.time();
-            if time_picker(ui, &mut milestone_time, "milestone_time") {
-                task.start = milestone_date.and_time(milestone_time);
-                task.end = task.start;
-                action = EditorAction::Changed;
-            }
+        // ── Info (computed, read-only) ────────────────────────────────
+        show_info_strip(ui, task, graph, clock);
+        let readings: Vec<TaskProgressSample> = project.task_progress.for_task(task_id).copied().collect();
+        if readings.len() >= 2 {
+            show_progress_sparkline(ui, &readings);
         }
 
         ui.add_space(2.0);
 
         // ── Progress ──────────────────────────────────────────────────
-        // Only show editable slider for non-parent tasks (parents auto-calculate from children)
-        if !is_parent_task {
-            ui.label(
-                RichText::new("Progress")
-                    .size(10.0)
-                    .color(theme::text_dim())
-                    .strong(),
-            );
+        // The slider is only live when the project's policy leaves progress
+        // to hand entry; otherwise it shows the worked-out value and why.
+        let source = project.progress_policy.source(task, is_parent_task);
+        ui.label(
+            RichText::new(tr("editor.progress"))
+                .size(10.0)
+                .color(theme::text_dim())
+                .strong(),
+        );
+        ui.add_enabled_ui(source == ProgressSource::Manual, |ui| {
             ui.horizontal(|ui| {
                 let slider = egui::Slider::new(&mut task.progress, 0.0..=1.0)
                     .custom_formatter(|v, _| format!("{:.0}%", v * 100.0))
@@ -345,13 +1195,44 @@ pub fn show_task_editor(
                     action = EditorAction::Changed;
                 }
             });
+            // Quick-set buttons
+            ui.horizontal(|ui| {
+                ui.spacing_mut().item_spacing.x = 3.0;
+                let w = ((ui.available_width() - 12.0) / 5.0).max(28.0);
+                for pct in [0u8, 25, 50, 75, 100] {
+                    let value = pct as f32 / 100.0;
+                    let current = (task.progress - value).abs() < 0.005;
+                    let btn = egui::Button::new(RichText::new(format!("{}%", pct)).size(10.0))
+                        .selected(current)
+                        .small();
+                    if ui.add_sized([w, 18.0], btn).clicked() && !current {
+                        action = EditorAction::SetProgress(value);
+                    }
+                }
+            });
+        });
+        let why = match source {
+            ProgressSource::Manual => None,
+            ProgressSource::Checklist => Some(tr("editor.progress.from_checklist")),
+            ProgressSource::Subtasks => Some(tr("editor.progress.from_subtasks")),
+        };
+        if let Some(why) = why {
+            ui.label(RichText::new(why).size(10.0).color(theme::text_dim()));
+        }
+
+        ui.add_space(2.0);
+        show_checklist(ui, task, &mut action);
+
+        if !is_parent_task {
+            ui.add_space(2.0);
+            show_time_tracking(ui, task, &mut action);
         }
 
         ui.add_space(2.0);
 
         // ── Notes / Description ───────────────────────────────────────
         ui.label(
-            RichText::new("Notes")
+            RichText::new(tr("editor.notes"))
                 .size(10.0)
                 .color(theme::text_dim())
                 .strong(),
@@ -361,7 +1242,7 @@ pub fn show_task_editor(
             egui::TextEdit::multiline(&mut task.description)
                 .font(egui::FontId::proportional(11.0))
                 .text_color(theme::text_secondary())
-                .hint_text("Add notes or description..."),
+                .hint_text(tr("editor.notes.hint")),
         );
         if notes_resp.changed() {
             action = EditorAction::Changed;
@@ -369,40 +1250,120 @@ pub fn show_task_editor(
 
         ui.add_space(2.0);
 
+        // ── Tags ──────────────────────────────────────────────────────
+        ui.label(
+            RichText::new(tr("editor.tags"))
+                .size(10.0)
+                .color(theme::text_dim())
+                .strong(),
+        );
+        // Keep the raw text while typing so separators aren't normalised away.
+        let tags_id = Id::new(("tags-edit", task_id));
+        let mut tags_text = ui
+            .ctx()
+            .data(|d| d.get_temp::<String>(tags_id))
+            .unwrap_or_else(|| task.tags.join(", "));
+        let tags_resp = ui.add_sized(
+            [ui.available_width(), 22.0],
+            egui::TextEdit::singleline(&mut tags_text)
+                .font(egui::FontId::proportional(11.0))
+                .hint_text(tr("editor.tags.hint")),
+        );
+        if tags_resp.changed() {
+            let tags = parse_tags(&tags_text);
+            if tags != task.tags {
+                task.tags = tags;
+                action = EditorAction::Changed;
+            }
+        }
+        if tags_resp.has_focus() {
+            ui.ctx().data_mut(|d| d.insert_temp(tags_id, tags_text));
+        } else {
+            ui.ctx().data_mut(|d| d.remove::<String>(tags_id));
+        }
+
+        ui.add_space(2.0);
+
         // ── Color ─────────────────────────────────────────────────────
         ui.label(
-            RichText::new("Color")
+            RichText::new(tr("editor.color"))
                 .size(10.0)
                 .color(theme::text_dim())
                 .strong(),
         );
+        let color_state_id = Id::new(("color-picker", task_id));
+        let mut color_state: ColorPickerState = ui.ctx().data_mut(|d| {
+            d.get_temp(color_state_id).unwrap_or(ColorPickerState {
+                custom_open: false,
+                draft: task.color.to_color32(),
+                apply_to_children: false,
+            })
+        });
+        let mut picked: Option<(Color32, bool)> = None;
+        let current = task.color.to_color32();
+
         ui.horizontal_wrapped(|ui| {
             ui.spacing_mut().item_spacing = egui::vec2(4.0, 4.0);
             let palette = theme::task_palette();
             for color in &palette {
-                let is_current = task.color == *color;
-                let size = if is_current { 20.0 } else { 16.0 };
-                let (rect, resp) =
-                    ui.allocate_exact_size(egui::vec2(size, size), egui::Sense::click());
-
-                let rounding = egui::Rounding::same(3.0);
-                ui.painter().rect_filled(rect, rounding, *color);
-
-                if is_current {
-                    ui.painter().rect_stroke(
-                        rect.expand(1.0),
-                        egui::Rounding::same(4.0),
-                        egui::Stroke::new(2.0, Color32::WHITE),
-                    );
+                if color_swatch(ui, *color, current == *color)
+                    .on_hover_text(tr("editor.color.set"))
+                    .clicked()
+                {
+                    picked = Some((*color, false));
                 }
-
-                if resp.on_hover_text("Click to set color").clicked() {
-                    task.color = *color;
-                    action = EditorAction::Changed;
+            }
+            // Recently used custom colours (per project)
+            for color in recent_colors.iter().map(|c| c.to_color32()).filter(|c| !palette.contains(c)) {
+                if color_swatch(ui, color, current == color)
+                    .on_hover_text(tr("editor.color.recent"))
+                    .clicked()
+                {
+                    picked = Some((color, true));
                 }
             }
+            let custom = ui
+                .add(egui::Button::new(RichText::new(tr("editor.color.custom")).size(10.0)).small())
+                .on_hover_text(tr("editor.color.any"));
+            if custom.clicked() {
+                color_state.custom_open = !color_state.custom_open;
+                color_state.draft = current;
+            }
         });
 
+        if color_state.custom_open {
+            egui::color_picker::color_picker_color32(
+                ui,
+                &mut color_state.draft,
+                egui::color_picker::Alpha::OnlyBlend,
+            );
+            ui.horizontal(|ui| {
+                if ui.button(tr("editor.apply")).clicked() {
+                    picked = Some((color_state.draft, true));
+                    color_state.custom_open = false;
+                }
+                if ui.button(tr("editor.cancel")).clicked() {
+                    color_state.custom_open = false;
+                }
+            });
+        }
+
+        if is_parent_task {
+            ui.checkbox(
+                &mut color_state.apply_to_children,
+                RichText::new(tr("editor.color.apply_children")).size(11.0).color(theme::text_secondary()),
+            );
+        }
+
+        if let Some((color, custom)) = picked {
+            action = EditorAction::SetColor {
+                color,
+                include_children: is_parent_task && color_state.apply_to_children,
+                custom,
+            };
+        }
+        ui.ctx().data_mut(|d| d.insert_temp(color_state_id, color_state));
+
         ui.add_space(2.0);
 
         // ── Milestone toggle ──────────────────────────────────────────
@@ -410,31 +1371,37 @@ pub fn show_task_editor(
             let mut is_milestone = task.is_milestone;
             let resp = ui.checkbox(&mut is_milestone, "");
             ui.label(
-                RichText::new("Milestone")
+                RichText::new(tr("editor.milestone"))
                     .size(11.0)
                     .color(theme::text_secondary()),
             );
             if resp.changed() {
-                task.is_milestone = is_milestone;
-                if is_milestone {
-                    task.end = task.start;
-                }
-                action = EditorAction::Changed;
+                action = EditorAction::SetMilestone(is_milestone);
+            }
+        });
+        ui.horizontal(|ui| {
+            let mut tentative = task.tentative;
+            let resp = ui.checkbox(&mut tentative, "");
+            ui.label(
+                RichText::new(tr("editor.tentative"))
+                    .size(11.0)
+                    .color(theme::text_secondary()),
+            )
+            .on_hover_text(tr("editor.tentative.hint"));
+            if resp.changed() {
+                action = EditorAction::SetTentative(tentative);
             }
         });
 
         ui.add_space(4.0);
 
         // ── Dependencies ─────────────────────────────────────────────
-        let task_deps: Vec<&Dependency> = dependencies
-            .iter()
-            .filter(|d| d.from_task == task_id || d.to_task == task_id)
-            .collect();
+        let task_deps: Vec<&Dependency> = graph.links_of(task_id).collect();
 
         ui.separator();
         ui.add_space(2.0);
         ui.label(
-            RichText::new("Dependencies")
+            RichText::new(tr("editor.dependencies"))
                 .size(10.0)
                 .color(theme::text_dim())
                 .strong(),
@@ -443,7 +1410,7 @@ pub fn show_task_editor(
 
         if task_deps.is_empty() {
             ui.label(
-                RichText::new("No dependencies yet")
+                RichText::new(tr("editor.no_dependencies"))
                     .size(9.5)
                     .color(theme::text_dim()),
             );
@@ -457,14 +1424,16 @@ pub fn show_task_editor(
                     .map(|t| t.name.clone())
                     .unwrap_or_else(|| "?".to_string());
 
-                let kind_lbl = dep_kind_label(dep.kind, is_outgoing);
-                let label = format!("{} {}", kind_lbl, other_name);
+                let label = format!("{} {}", dep_direction_icon(is_outgoing), other_name);
 
                 ui.horizontal(|ui| {
-                    ui.label(
-                        RichText::new(&label)
-                            .size(11.0)
-                            .color(theme::text_secondary()),
+                    ui.add(
+                        egui::Label::new(
+                            RichText::new(&label)
+                                .size(11.0)
+                                .color(theme::text_secondary()),
+                        )
+                        .truncate(),
                     );
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         let del = ui.add(
@@ -473,9 +1442,52 @@ pub fn show_task_editor(
                             )
                             .frame(false),
                         );
-                        if del.on_hover_text("Remove dependency").clicked() {
+                        if del.on_hover_text(tr("editor.remove_dependency")).clicked() {
                             action = EditorAction::RemoveDependency(dep.from_task, dep.to_task);
                         }
+
+                        // Edit a copy while dragging / typing so the whole
+                        // edit lands as a single update (and undo step).
+                        let lag_id = Id::new(("dep-lag-edit", dep.from_task, dep.to_task));
+                        let mut lag = ui.ctx().data(|d| d.get_temp::<i64>(lag_id)).unwrap_or(dep.lag_days);
+                        let lag_resp = ui.add(
+                            egui::DragValue::new(&mut lag)
+                                .range(-365..=365)
+                                .suffix("d")
+                                .speed(0.1),
+                        );
+                        let lag_resp = lag_resp.on_hover_text(tr("editor.lag.hint"));
+                        let lag_editing = lag_resp.dragged() || lag_resp.has_focus();
+                        if lag_editing {
+                            ui.ctx().data_mut(|d| d.insert_temp(lag_id, lag));
+                        } else {
+                            ui.ctx().data_mut(|d| d.remove::<i64>(lag_id));
+                        }
+
+                        let mut kind = dep.kind;
+                        egui::ComboBox::from_id_salt(("dep-kind", dep.from_task, dep.to_task))
+                            .selected_text(RichText::new(kind.short_label()).size(11.0))
+                            .width(46.0)
+                            .show_ui(ui, |ui| {
+                                for k in DependencyKind::all() {
+                                    let row = ui.selectable_label(kind == *k, k.short_label());
+                                    if row.on_hover_text(k.description()).clicked() {
+                                        kind = *k;
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text(kind.description());
+
+                        let lag_committed = !lag_editing && lag != dep.lag_days;
+                        if kind != dep.kind || lag_committed {
+                            action = EditorAction::UpdateDependency {
+                                from: dep.from_task,
+                                to: dep.to_task,
+                                kind,
+                                lag: if lag_committed { lag } else { dep.lag_days },
+                            };
+                        }
                     });
                 });
             }
@@ -484,121 +1496,21 @@ pub fn show_task_editor(
         ui.add_space(4.0);
 
         // ── Add dependency picker ─────────────────────────────────────
-        // Candidates: if this task is a child, only siblings (same parent);
-        // otherwise all tasks except self, parent tasks (summary bars), and
-        // tasks already linked to this one.
-        let already_linked: std::collections::HashSet<Uuid> = dependencies
-            .iter()
-            .filter(|d| d.from_task == task_id || d.to_task == task_id)
-            .map(|d| if d.from_task == task_id { d.to_task } else { d.from_task })
-            .collect();
-
-        let candidates: Vec<(Uuid, String)> = all_tasks
-            .iter()
-            .filter(|t| {
-                if t.id == task_id { return false; }
-                if already_linked.contains(&t.id) { return false; }
-                // Don't link to/from summary (parent) tasks
-                let t_is_parent = t.has_children(all_tasks);
-                if t_is_parent { return false; }
-                // If this task is a child, restrict to siblings (same parent)
-                if let Some(my_parent) = task.parent_id {
-                    return t.parent_id == Some(my_parent);
-                }
-                // Top-level task: only link to other top-level tasks
-                t.parent_id.is_none()
-            })
-            .map(|t| (t.id, t.name.clone()))
-            .collect();
-
-        if !candidates.is_empty() {
-            let picker_id = Id::new(("dep-picker", task_id));
-            let mut state: DepPickerState = ui.ctx().data_mut(|d| {
-                d.get_temp(picker_id).unwrap_or(DepPickerState {
-                    target_id: None,
-                    kind: DependencyKind::FinishToStart,
-                })
-            });
-
-            let target_label = state.target_id
-                .and_then(|id| candidates.iter().find(|(cid, _)| *cid == id))
-                .map(|(_, name)| name.clone())
-                .unwrap_or_else(|| "— pick task —".to_string());
-
-            ui.label(
-                RichText::new("Add dependency")
-                    .size(10.0)
-                    .color(theme::text_dim())
-                    .strong(),
-            );
-
-            // Row: [kind combo] [target combo] [+ button]
-            ui.horizontal(|ui| {
-                // Kind selector (narrow)
-                let kind_resp = egui::ComboBox::from_id_salt("new-dep-kind")
-                    .selected_text(RichText::new(state.kind.short_label()).size(11.0))
-                    .width(46.0)
-                    .show_ui(ui, |ui| {
-                        for k in &[
-                            DependencyKind::FinishToStart,
-                            DependencyKind::StartToStart,
-                            DependencyKind::FinishToFinish,
-                            DependencyKind::StartToFinish,
-                        ] {
-                            let row = ui.selectable_label(state.kind == *k, k.short_label());
-                            if row.on_hover_text(k.description()).clicked() {
-                                state.kind = *k;
-                            }
-                        }
-                    });
-                kind_resp.response.on_hover_text(state.kind.description());
-
-                // Target task selector — use a fixed width to avoid stretching the panel
-                let combo_w = (ui.available_width() - 30.0).clamp(60.0, 220.0);
-                egui::ComboBox::from_id_salt("new-dep-target")
-                    .selected_text(RichText::new(&target_label).size(11.0))
-                    .width(combo_w)
-                    .show_ui(ui, |ui| {
-                        for (cid, cname) in &candidates {
-                            if ui.selectable_label(state.target_id == Some(*cid), cname.as_str()).clicked() {
-                                state.target_id = Some(*cid);
-                            }
-                        }
-                    });
-
-                // Add button
-                let can_add = state.target_id.is_some();
-                let btn = egui::Button::new(RichText::new("＋").size(13.0).color(Color32::WHITE))
-                    .fill(if can_add { theme::accent() } else { theme::bg_field() })
-                    .rounding(egui::Rounding::same(4.0));
-                let add_resp = ui.add_enabled(can_add, btn);
-                if add_resp.clicked() {
-                    if let Some(target) = state.target_id {
-                        action = EditorAction::AddDependency(Dependency {
-                            from_task: task_id,
-                            to_task: target,
-                            kind: state.kind,
-                        });
-                        state.target_id = None; // reset after adding
-                    }
-                }
-            });
+        if let Some(dep) = dependency_picker(ui, "editor", task_id, project, graph) {
+            action = EditorAction::AddDependency(dep);
+        }
 
-            ui.ctx().data_mut(|d| d.insert_temp(picker_id, state));
+        ui.add_space(2.0);
+        ui.label(
+            RichText::new(tr("editor.shift_drag_hint"))
+                .size(9.0)
+                .color(theme::text_dim()),
+        );
 
-            ui.add_space(2.0);
-            ui.label(
-                RichText::new("Or Shift+drag between bars")
-                    .size(9.0)
-                    .color(theme::text_dim()),
-            );
-        } else {
-            ui.label(
-                RichText::new("Shift+drag between bars to link tasks")
-                    .size(9.5)
-                    .color(theme::text_dim()),
-            );
-        }
+        ui.add_space(4.0);
+        ui.separator();
+        ui.add_space(2.0);
+        show_comments(ui, task, author, &mut action);
     });
 
     action
@@ -1,6 +1,21 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::i18n::{group_by_label, tr};
 use crate::model::Task;
-use crate::model::task::TaskPriority;
-use crate::ui::{filter_bar, theme};
+use crate::model::graph::DependencyGraph;
+use crate::model::grouping::{GroupBy, GroupKey, TaskGroup};
+use crate::model::highlight::{due_time, short_span, DueState, Highlight};
+use crate::model::quick_add::{parse_quick_add, QuickAdd};
+use crate::model::task::{CompletedTasks, TaskPriority};
+use crate::ui::inline_rename::{self, RenameSurface};
+use crate::ui::row_metrics;
+use crate::ui::row_sync::{self, RowView};
+use crate::ui::rows::{group_label, Row, Rows};
+use crate::ui::scroll_anchor;
+use crate::ui::theme;
+use crate::ui::theme::Severity;
+use crate::ui::model_ext::{PriorityIcon, StatusColor, ToColor32};
+use chrono::NaiveDateTime;
 use egui::{Color32, RichText, Ui};
 use uuid::Uuid;
 
@@ -11,48 +26,119 @@ pub enum TaskTableAction {
     Delete(Uuid),
     Add,
     ToggleCollapse(Uuid),
+    Rename(Uuid, String),
+    /// Ctrl+click: add or remove a task from the selection.
+    ToggleSelect(Uuid),
+    /// Shift+click: select every listed row between the primary selection and
+    /// this one (inclusive); the last id becomes the primary selection.
+    SelectRange(Vec<Uuid>),
+    /// Enter pressed in the quick-add box.
+    QuickAdd(QuickAdd),
+    /// A different choice in the "Group by" dropdown.
+    SetGroupBy(GroupBy),
+    /// A group header was clicked.
+    ToggleGroup(GroupKey),
+    /// A task was dropped on a group: change its grouped field to match.
+    Regroup(Uuid, GroupKey),
+    /// A due badge was clicked: show only tasks in that state, or
+    /// everything again if that filter is on already.
+    FilterDue(DueState),
+    /// A row's link handle was dropped on another row it can be linked
+    /// to: ask for the link's kind at `at`.
+    Link { from: Uuid, to: Uuid, at: egui::Pos2 },
+}
+
+/// What a row's link handle carries while dragged: the predecessor.
+#[derive(Clone, Copy)]
+struct LinkFrom(Uuid);
+
+/// How the list is organised and what it leaves out.
+#[derive(Clone, Copy)]
+pub struct TableOptions<'a> {
+    pub completed: CompletedTasks,
+    /// Where each task stands against its due time, for the row badges.
+    pub due_states: &'a HashMap<Uuid, DueState>,
+    /// The project's current time, which the badges count from.
+    pub now: NaiveDateTime,
+    pub group_by: GroupBy,
+    /// The rows to list, shared with the chart.
+    pub rows: &'a Rows<'a>,
+    pub collapsed_groups: &'a HashSet<GroupKey>,
+    /// Tasks the project's highlight rules mark.
+    pub highlights: &'a HashMap<Uuid, Highlight>,
+    /// Wrap long names over up to two lines instead of truncating them.
+    pub wrap_names: bool,
+    /// The project's links, to refuse a dragged link that is already there
+    /// or would close a cycle.
+    pub links: &'a DependencyGraph,
+}
+
+/// The natural-language quick-add field, with a live preview of the task it
+/// would create. Returns the parsed entry when Enter is pressed.
+fn quick_add_box(tasks: &[Task], ui: &mut Ui) -> Option<QuickAdd> {
+    let id = egui::Id::new("task-table-quick-add");
+    let mut text = ui.ctx().data(|d| d.get_temp::<String>(id)).unwrap_or_default();
+    let resp = ui.add(
+        egui::TextEdit::singleline(&mut text)
+            .hint_text(format!("{} Quick add: Review tue for 3d p:high #ui /Phase", egui_phosphor::regular::LIGHTNING))
+            .desired_width(f32::INFINITY),
+    );
+    let (enter, escape) = ui.input(|i| (i.key_pressed(egui::Key::Enter), i.key_pressed(egui::Key::Escape)));
+    let mut created = None;
+
+    if resp.lost_focus() && escape {
+        text.clear();
+    } else if !text.trim().is_empty() {
+        let today = chrono::Local::now().date_naive();
+        let entry = parse_quick_add(&text, today, tasks);
+        if resp.lost_focus() && enter {
+            created = Some(entry);
+            text.clear();
+            resp.request_focus();
+        } else {
+            let (first, last) = entry.date_range(today);
+            let mut parts = vec![
+                entry.display_name().to_string(),
+                if first == last {
+                    first.format("%a %b %d").to_string()
+                } else {
+                    format!("{} → {}", first.format("%a %b %d"), last.format("%a %b %d"))
+                },
+            ];
+            if let Some(p) = entry.priority.filter(|p| *p != TaskPriority::None) {
+                parts.push(p.label().to_string());
+            }
+            parts.extend(entry.tags.iter().map(|t| format!("#{}", t)));
+            if let Some((_, parent)) = &entry.parent {
+                parts.push(format!("in {}", parent));
+            }
+            ui.label(
+                RichText::new(format!("{} {}", egui_phosphor::regular::ARROW_ELBOW_DOWN_RIGHT, parts.join(" · ")))
+                    .size(10.5)
+                    .color(theme::text_secondary()),
+            );
+        }
+    }
+
+    ui.ctx().data_mut(|d| d.insert_temp(id, text));
+    created
 }
 
 /// Render the left-side task table panel.
-/// `search_query` and `filter_priority` are used to hide non-matching tasks.
 pub fn show_task_table(
     tasks: &[Task],
     selected_task: Option<Uuid>,
-    search_query: &str,
-    filter_priority: Option<TaskPriority>,
+    selection: &[Uuid],
+    options: TableOptions,
     ui: &mut Ui,
 ) -> TaskTableAction {
     let mut action = TaskTableAction::None;
+    let completed = options.completed;
+    let grouped = options.group_by != GroupBy::Hierarchy;
 
-    // Determine which tasks are visible after filtering
-    // A parent task is shown if it or any of its children pass the filter.
-    let passes_filter = |t: &Task| -> bool {
-        if !filter_bar::task_matches(
-            &t.name,
-            &t.description,
-            t.priority,
-            search_query,
-            filter_priority,
-        ) {
-            // Check if any child passes the filter
-            let child_passes = tasks.iter().any(|child| {
-                child.parent_id == Some(t.id)
-                    && filter_bar::task_matches(
-                        &child.name,
-                        &child.description,
-                        child.priority,
-                        search_query,
-                        filter_priority,
-                    )
-            });
-            if !child_passes {
-                return false;
-            }
-        }
-        true
-    };
-
-    let visible_count = tasks.iter().filter(|t| passes_filter(t)).count();
+    let visible_count = tasks.len() - options.rows.filtered_out.len();
+    // Rows actually listed, in display order (for Shift+click ranges).
+    let listed_ids: Vec<Uuid> = options.rows.rows.iter().filter_map(Row::task_id).collect();
 
     // Header area
     ui.add_space(2.0);
@@ -74,12 +160,30 @@ pub fn show_task_table(
                 .size(11.0)
                 .color(theme::text_dim()),
         );
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            egui::ComboBox::from_id_salt("task-table-group-by")
+                .selected_text(RichText::new(group_by_label(options.group_by)).size(11.0))
+                .width(90.0)
+                .show_ui(ui, |ui| {
+                    for by in GroupBy::ALL {
+                        if ui.selectable_label(options.group_by == by, group_by_label(by)).clicked() {
+                            action = TaskTableAction::SetGroupBy(by);
+                        }
+                    }
+                });
+            ui.label(RichText::new(tr("list.group_by")).size(11.0).color(theme::text_dim()));
+        });
     });
     ui.add_space(4.0);
 
+    if let Some(entry) = quick_add_box(tasks, ui) {
+        action = TaskTableAction::QuickAdd(entry);
+    }
+    ui.add_space(4.0);
+
     // Add task button
     let btn = egui::Button::new(
-        RichText::new("＋  Add Task").color(Color32::WHITE).size(12.0),
+        RichText::new("＋  Add Task").color(theme::text_on(theme::accent())).size(12.0),
     )
     .fill(theme::accent())
     .rounding(egui::Rounding::same(5.0));
@@ -117,36 +221,49 @@ pub fn show_task_table(
 
     ui.add_space(2.0);
 
-    // Task rows
-    egui::ScrollArea::vertical()
-        .auto_shrink([false, false])
+    // Task rows, keeping the top one in place when rows above it move.
+    let anchor_id = egui::Id::new("task-table-scroll-anchor");
+    let mut scroll = egui::ScrollArea::vertical().auto_shrink([false, false]);
+    if let Some(offset) = scroll_anchor::restore(ui.ctx(), anchor_id, &listed_ids) {
+        scroll = scroll.vertical_scroll_offset(offset);
+    } else if let Some(offset) = row_sync::scroll_offset(ui.ctx(), RowView::List) {
+        scroll = scroll.vertical_scroll_offset(offset);
+    }
+    let mut row_tops: Vec<f32> = Vec::new();
+    let mut content_top = 0.0;
+    let mut name_lines: HashMap<Uuid, usize> = HashMap::new();
+    let linked_hover = row_sync::hovered(ui.ctx());
+    let scroll_to = row_sync::take_scroll(ui.ctx(), RowView::List);
+    let scrolled = scroll
         .show(ui, |ui| {
-            let today = chrono::Local::now().naive_local();
-
-            for (i, task) in tasks.iter().enumerate() {
-                // Skip if filtered out
-                if !passes_filter(task) {
-                    continue;
-                }
-
-                // Skip children of collapsed parents
-                if let Some(pid) = task.parent_id {
-                    if let Some(parent) = tasks.iter().find(|t| t.id == pid) {
-                        if parent.collapsed {
-                            continue;
+            content_top = ui.min_rect().top();
+            for row in &options.rows.rows {
+                let (i, row_group) = match *row {
+                    Row::Group { group, shown } => {
+                        let collapsed = options.collapsed_groups.contains(&group.key);
+                        if let Some(header_action) = group_header(ui, group, shown, collapsed) {
+                            action = header_action;
                         }
+                        continue;
                     }
-                }
+                    Row::Task { index, group, .. } => (index, group),
+                };
+                let task = &tasks[i];
 
-                let is_selected = selected_task == Some(task.id);
-                let is_parent = task.has_children(tasks);
-                let is_child = task.parent_id.is_some();
-                let is_overdue =
-                    !task.is_milestone && task.end < today && task.progress < 1.0;
+                let is_selected = selected_task == Some(task.id) || selection.contains(&task.id);
+                // Grouped rows are all leaves, listed flat.
+                let is_parent = !grouped && task.has_children(tasks);
+                let is_child = !grouped && task.parent_id.is_some();
+                let highlight = options.highlights.get(&task.id).copied();
+                let is_overdue = highlight == Some(Highlight::Overdue);
+                let renaming = inline_rename::is_active(ui.ctx(), task.id, RenameSurface::List);
+                let struck = completed == CompletedTasks::Strike && task.is_complete();
 
                 // Row background
                 let row_bg = if is_selected {
                     theme::bg_selected()
+                } else if linked_hover == Some(task.id) {
+                    theme::widget_bg_hovered()
                 } else if i % 2 == 0 {
                     theme::bg_panel()
                 } else {
@@ -165,15 +282,16 @@ pub fn show_task_table(
                     stroke: if is_selected {
                         egui::Stroke::new(1.0, theme::row_selected_stroke())
                     } else if is_overdue {
-                        egui::Stroke::new(1.0, egui::Color32::from_rgb(200, 60, 60))
+                        egui::Stroke::new(1.0, theme::status_color(Severity::Bad, egui::Color32::from_rgb(200, 60, 60)))
                     } else {
                         egui::Stroke::new(1.0, theme::row_unselected_stroke())
                     },
                     shadow: egui::epaint::Shadow::NONE,
                 };
 
+                let mut over_handle = false;
                 let frame_resp = frame.show(ui, |ui| {
-                    ui.set_min_height(theme::row_height() - 8.0);
+                    let mut lines = 1;
                     ui.horizontal(|ui| {
                         ui.spacing_mut().item_spacing.x = 4.0;
 
@@ -201,7 +319,7 @@ pub fn show_task_table(
                         let (dot_rect, _) =
                             ui.allocate_exact_size(egui::vec2(6.0, 6.0), egui::Sense::hover());
                         ui.painter()
-                            .circle_filled(dot_rect.center(), 3.0, task.color);
+                            .circle_filled(dot_rect.center(), 3.0, task.color.to_color32());
 
                         // Priority icon
                         let pri_icon = task.priority.icon();
@@ -217,24 +335,54 @@ pub fn show_task_table(
                         );
 
                         // Task name
-                        let name = if task.is_milestone {
-                            format!("◆ {}", task.name)
-                        } else if is_overdue {
-                            format!("⚠ {}", task.name)
-                        } else {
-                            task.name.clone()
-                        };
+                        if highlight == Some(Highlight::AtRisk) {
+                            ui.label(
+                                RichText::new(egui_phosphor::regular::HOURGLASS_MEDIUM)
+                                    .size(10.0)
+                                    .color(Highlight::AtRisk.status_color()),
+                            )
+                            .on_hover_text("At risk: ends soon with too little done");
+                        }
+                        let name = row_label(task, is_overdue);
                         let name_color = if is_selected {
-                            Color32::WHITE
+                            theme::text_primary()
                         } else if is_overdue {
-                            egui::Color32::from_rgb(230, 100, 100)
+                            theme::status_color(Severity::Bad, egui::Color32::from_rgb(230, 100, 100))
+                        } else if struck {
+                            theme::text_dim()
                         } else if is_parent {
                             theme::text_primary()
                         } else {
                             theme::text_secondary()
                         };
-                        let name_text = RichText::new(name).size(12.0).color(name_color);
-                        ui.add(egui::Label::new(name_text).truncate());
+                        if renaming {
+                            // Leave room for the dates / progress / delete cluster.
+                            let width = (ui.available_width() - 214.0).max(60.0);
+                            let (rect, _) = ui.allocate_exact_size(
+                                egui::vec2(width, 18.0),
+                                egui::Sense::hover(),
+                            );
+                            if let Some((id, new_name)) = inline_rename::show(ui, rect) {
+                                action = TaskTableAction::Rename(id, new_name);
+                            }
+                        } else {
+                            let mut name_text = RichText::new(name).size(12.0).color(name_color);
+                            if struck {
+                                name_text = name_text.strikethrough();
+                            }
+                            if task.tentative {
+                                name_text = name_text.italics();
+                            }
+                            if options.wrap_names {
+                                // Leave room for the dates / progress / delete cluster.
+                                let width = (ui.available_width() - 214.0).max(60.0);
+                                let galley = row_metrics::wrapped_name(ui, name_text, width);
+                                lines = galley.rows.len();
+                                ui.add(egui::Label::new(galley));
+                            } else {
+                                ui.add(egui::Label::new(name_text).truncate());
+                            }
+                        }
 
                         ui.with_layout(
                             egui::Layout::right_to_left(egui::Align::Center),
@@ -253,9 +401,36 @@ pub fn show_task_table(
                                     action = TaskTableAction::Delete(task.id);
                                 }
 
+                                let handle = ui.add(
+                                    egui::Button::new(
+                                        RichText::new(egui_phosphor::regular::LINK)
+                                            .size(10.0)
+                                            .color(theme::text_dim()),
+                                    )
+                                    .frame(false)
+                                    .sense(egui::Sense::drag()),
+                                );
+                                handle.dnd_set_drag_payload(LinkFrom(task.id));
+                                if let Some(ptr) = handle.interact_pointer_pos().filter(|_| handle.dragged()) {
+                                    let painter = ui.ctx().layer_painter(egui::LayerId::new(
+                                        egui::Order::Tooltip,
+                                        egui::Id::new("task-row-link"),
+                                    ));
+                                    let from = handle.rect.center();
+                                    painter.arrow(from, ptr - from, egui::Stroke::new(1.5, theme::dep_creating()));
+                                }
+                                over_handle = handle.contains_pointer();
+                                handle.on_hover_text("Drag onto another row to link this task to it");
+
+                                if let Some(&state) = options.due_states.get(&task.id) {
+                                    if due_badge(ui, task, state, options.now).clicked() {
+                                        action = TaskTableAction::FilterDue(state);
+                                    }
+                                }
+
                                 let pbar = egui::ProgressBar::new(task.progress)
                                     .desired_width(48.0)
-                                    .fill(task.color)
+                                    .fill(task.color.to_color32())
                                     .rounding(egui::Rounding::same(3.0));
                                 ui.add(pbar);
 
@@ -274,27 +449,255 @@ pub fn show_task_table(
                                         .size(10.0)
                                         .color(theme::text_secondary()),
                                 );
+
+                                if !task.comments.is_empty() {
+                                    let count = task.comments.len();
+                                    ui.label(
+                                        RichText::new(format!("{} {}", egui_phosphor::regular::CHAT_CIRCLE, count))
+                                            .size(9.5)
+                                            .color(theme::text_dim()),
+                                    )
+                                    .on_hover_text(if count == 1 {
+                                        "1 comment".to_string()
+                                    } else {
+                                        format!("{} comments", count)
+                                    });
+                                }
                             },
                         );
                     });
+                    // The chart gives the row the same height from these lines.
+                    ui.set_min_height(row_metrics::row_height(ui.ctx(), lines) - 8.0);
+                    name_lines.insert(task.id, lines);
                 });
+                row_tops.push(frame_resp.response.rect.top());
+                if ui.rect_contains_pointer(frame_resp.response.rect) {
+                    row_sync::hover(ui.ctx(), task.id);
+                }
+                if scroll_to == Some(task.id) {
+                    frame_resp.response.scroll_to_me(None);
+                }
 
-                // Make entire row clickable
-                let row_rect = frame_resp.response.rect;
-                let row_click = ui.interact(
-                    row_rect,
-                    egui::Id::new(("task-row", task.id)),
-                    egui::Sense::click(),
-                );
-                if row_click.clicked() && matches!(action, TaskTableAction::None) {
-                    action = TaskTableAction::Select(task.id);
+                // Make entire row clickable (but not over an open rename field)
+                if !renaming {
+                    let row_rect = frame_resp.response.rect;
+                    // In a grouped list, rows can be dragged onto another
+                    // group, except from the link handle.
+                    let sense = if row_group.is_some() && !over_handle {
+                        egui::Sense::click_and_drag()
+                    } else {
+                        egui::Sense::click()
+                    };
+                    let row_click = ui.interact(row_rect, egui::Id::new(("task-row", task.id)), sense);
+                    // A link handle held over the row: outline it, in red if
+                    // it can't be linked to (the row itself, a task already
+                    // linked, or one that would close a cycle).
+                    let link_allowed = |from: Uuid| {
+                        !options.links.has_link(from, task.id) && !options.links.would_create_cycle(from, task.id)
+                    };
+                    if let Some(from) = row_click.dnd_hover_payload::<LinkFrom>() {
+                        let reject = theme::status_color(Severity::Bad, Color32::from_rgb(220, 60, 60));
+                        let allowed = link_allowed(from.0);
+                        if !allowed {
+                            ui.painter().rect_filled(row_rect, 4.0, reject.gamma_multiply(0.25));
+                            ui.ctx().set_cursor_icon(egui::CursorIcon::NotAllowed);
+                        }
+                        let color = if allowed { theme::dep_creating() } else { reject };
+                        ui.painter().rect_stroke(row_rect, 4.0, egui::Stroke::new(1.5, color));
+                    }
+                    if let Some(from) = row_click.dnd_release_payload::<LinkFrom>() {
+                        if let Some(at) = ui.ctx().pointer_interact_pos().filter(|_| link_allowed(from.0)) {
+                            action = TaskTableAction::Link { from: from.0, to: task.id, at };
+                        }
+                    }
+                    if let Some(key) = row_group {
+                        row_click.dnd_set_drag_payload(task.id);
+                        if row_click.dnd_hover_payload::<Uuid>().is_some() {
+                            ui.painter().rect_stroke(row_rect, 4.0, egui::Stroke::new(1.5, theme::accent()));
+                        }
+                        if let Some(dragged) = row_click.dnd_release_payload::<Uuid>() {
+                            action = TaskTableAction::Regroup(*dragged, key.clone());
+                        }
+                    }
+                    let modifiers = ui.input(|i| i.modifiers);
+                    if row_click.double_clicked() {
+                        inline_rename::begin(ui.ctx(), task.id, &task.name, RenameSurface::List);
+                    } else if row_click.clicked() && matches!(action, TaskTableAction::None) {
+                        row_sync::scroll_to(ui.ctx(), RowView::Chart, task.id);
+                        action = if modifiers.command {
+                            TaskTableAction::ToggleSelect(task.id)
+                        } else if modifiers.shift {
+                            TaskTableAction::SelectRange(row_range(&listed_ids, selected_task, task.id))
+                        } else {
+                            TaskTableAction::Select(task.id)
+                        };
+                    }
                 }
 
                 ui.add_space(theme::row_gap());
             }
         });
+    row_metrics::publish(ui.ctx(), name_lines);
+    if let [first, .., last] = row_tops[..] {
+        let pitch = (last - first) / (row_tops.len() - 1) as f32;
+        scroll_anchor::remember(ui.ctx(), anchor_id, &listed_ids, pitch, scrolled.state.offset.y);
+    } else {
+        scroll_anchor::remember(ui.ctx(), anchor_id, &[], 0.0, 0.0);
+    }
+    let tops = listed_ids.iter().zip(&row_tops).map(|(&id, &top)| (id, top - content_top)).collect();
+    row_sync::scrolled(ui.ctx(), RowView::List, tops, scrolled.state.offset.y);
 
     action
 }
+/// Render the draggable divider on the right edge of the task panel.
+/// The caller applies drag / double-click from the returned response.
+pub fn show_panel_divider(ctx: &egui::Context, panel_rect: egui::Rect) -> egui::Response {
+    const GRIP: f32 = 6.0;
+    egui::Area::new(egui::Id::new("task-panel-divider"))
+        .fixed_pos(egui::pos2(panel_rect.right() - GRIP / 2.0, panel_rect.top()))
+        .movable(false)
+        .order(egui::Order::Middle)
+        .show(ctx, |ui| {
+            let (rect, resp) = ui.allocate_exact_size(
+                egui::vec2(GRIP, panel_rect.height()),
+                egui::Sense::click_and_drag(),
+            );
+            if resp.hovered() || resp.dragged() {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
+                ui.painter().vline(
+                    rect.center().x,
+                    rect.y_range(),
+                    egui::Stroke::new(2.0, theme::border_accent()),
+                );
+            }
+            resp.on_hover_text("Drag to resize · double-click to fit task names")
+        })
+        .inner
+}
+
+/// A group's header row: caret, name and task count. Clicking it collapses
+/// or expands the group; dropping a task on it moves the task there.
+fn group_header(ui: &mut Ui, group: &TaskGroup, shown: usize, collapsed: bool) -> Option<TaskTableAction> {
+    ui.add_space(2.0);
+    let caret = if collapsed { egui_phosphor::regular::CARET_RIGHT } else { egui_phosphor::regular::CARET_DOWN };
+    let row = ui
+        .horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 6.0;
+            ui.label(RichText::new(caret).size(9.0).color(theme::text_dim()));
+            ui.label(RichText::new(group_label(&group.key)).size(12.0).strong().color(theme::text_primary()));
+            ui.label(RichText::new(format!("({})", shown)).size(11.0).color(theme::text_dim()));
+        })
+        .response;
+    let rect = row.rect.with_max_x(ui.max_rect().right());
+    let resp = ui.interact(rect, egui::Id::new(("task-group", &group.key)), egui::Sense::click());
+    ui.add_space(2.0);
+    if resp.dnd_hover_payload::<Uuid>().is_some() {
+        ui.painter().rect_stroke(rect.expand(2.0), 4.0, egui::Stroke::new(1.5, theme::accent()));
+    }
+    if let Some(dragged) = resp.dnd_release_payload::<Uuid>() {
+        return Some(TaskTableAction::Regroup(*dragged, group.key.clone()));
+    }
+    resp.clicked().then(|| TaskTableAction::ToggleGroup(group.key.clone()))
+}
+
+/// Ids from `anchor` to `clicked` in list order, ending with `clicked`.
+/// Without a listed anchor the range is just the clicked row.
+fn row_range(listed_ids: &[Uuid], anchor: Option<Uuid>, clicked: Uuid) -> Vec<Uuid> {
+    let pos = |id: Uuid| listed_ids.iter().position(|t| *t == id);
+    match (anchor.and_then(pos), pos(clicked)) {
+        (Some(a), Some(c)) if a <= c => listed_ids[a..=c].to_vec(),
+        (Some(a), Some(c)) => listed_ids[c..=a].iter().rev().copied().collect(),
+        _ => vec![clicked],
+    }
+}
+
+/// A due badge's text: "3d late", "due in 2d", or a tick for done work.
+fn due_badge_text(task: &Task, state: DueState, now: NaiveDateTime) -> String {
+    let due = due_time(task);
+    match state {
+        DueState::Late => short_span(now - due).map_or_else(|| "late".to_string(), |span| format!("{} late", span)),
+        DueState::DueSoon => {
+            short_span(due - now).map_or_else(|| "due now".to_string(), |span| format!("due in {}", span))
+        }
+        DueState::Done => egui_phosphor::regular::CHECK.to_string(),
+    }
+}
+
+/// Width kept for a due badge when fitting the panel.
+const DUE_BADGE_WIDTH: f32 = 56.0;
+
+/// The badge saying where `task` stands against its due time.
+fn due_badge(ui: &mut Ui, task: &Task, state: DueState, now: NaiveDateTime) -> egui::Response {
+    let color = state.status_color();
+    let text = RichText::new(due_badge_text(task, state, now)).size(9.5).color(color).strong();
+    let hover = format!(
+        "{}, due {}. Click to show only {} tasks",
+        state.label(),
+        due_time(task).format("%Y-%m-%d %H:%M"),
+        state.label().to_lowercase()
+    );
+    ui.add(
+        egui::Button::new(text)
+            .small()
+            .fill(color.gamma_multiply(0.15))
+            .stroke(egui::Stroke::new(1.0, color.gamma_multiply(0.6)))
+            .rounding(egui::Rounding::same(6.0)),
+    )
+    .on_hover_text(hover)
+}
+
+/// The name as displayed in the row, including the milestone/overdue marker.
+fn row_label(task: &Task, is_overdue: bool) -> String {
+    if task.is_milestone {
+        format!("◆ {}", task.name)
+    } else if is_overdue {
+        format!("⚠ {}", task.name)
+    } else {
+        task.name.clone()
+    }
+}
+
+/// Panel width needed to show the widest listed task name in `rows` without
+/// truncation.
+pub fn fit_width(
+    tasks: &[Task],
+    rows: &[Row],
+    highlights: &HashMap<Uuid, Highlight>,
+    due_states: &HashMap<Uuid, DueState>,
+    ctx: &egui::Context,
+) -> f32 {
+    let text_width = |text: String, size: f32| {
+        ctx.fonts(|f| f.layout_no_wrap(text, egui::FontId::proportional(size), Color32::WHITE).size().x)
+    };
+
+    let name_w = rows
+        .iter()
+        .filter_map(|row| match *row {
+            Row::Task { index, group, .. } => tasks.get(index).map(|t| (t, group.is_none())),
+            Row::Group { .. } => None,
+        })
+        .map(|(t, nested)| {
+            let highlight = highlights.get(&t.id).copied();
+            let indent = if nested && t.parent_id.is_some() { 12.0 } else { 0.0 };
+            let badge = if highlight == Some(Highlight::AtRisk) { 14.0 } else { 0.0 }
+                + if due_states.contains_key(&t.id) { DUE_BADGE_WIDTH } else { 0.0 };
+            text_width(row_label(t, highlight == Some(Highlight::Overdue)), 12.0) + indent + badge
+        })
+        .fold(0.0, f32::max);
+
+    // Fixed columns around the name: caret, color dot, priority icon, the two
+    // date labels, arrow, progress bar, link handle, delete button, plus item
+    // spacing.
+    let date_w = text_width("00/00 00:00".to_string(), 10.0);
+    let columns = 12.0 + 6.0 + 10.0 + 2.0 * date_w + 10.0 + 48.0 + 10.0 + 10.0 + 9.0 * 4.0;
+    // Row frame margins, panel margins and the scroll bar.
+    let chrome = 2.0 * 6.0
+        + 2.0 * theme::layout().panel_inner_margin
+        + ctx.style().spacing.scroll.bar_width
+        + 8.0;
+
+    name_w + columns + chrome
+}
+
 #[allow(dead_code)]
 pub fn task_colors() -> Vec<Color32> { theme::task_palette() }
@@ -8,14 +8,17 @@
 //!
 //! This file replaces the original `theme.rs` that had `pub const` values.
 
+use crate::settings::RowDensity;
 use crate::ui::theme_def::ThemeDefinition;
 use egui::{Color32, FontId, Rounding, Stroke, Visuals};
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
 // ─── Thread-local active theme ──────────────────────────────────────────────
 
 thread_local! {
     static ACTIVE: RefCell<ThemeDefinition> = RefCell::new(ThemeDefinition::default());
+    static DENSITY: Cell<RowDensity> = const { Cell::new(RowDensity::Comfortable) };
+    static COLOR_BLIND_SAFE: Cell<bool> = const { Cell::new(false) };
 }
 
 /// Install a theme definition for the current frame.
@@ -26,6 +29,22 @@ pub fn set_active(def: &ThemeDefinition) {
     });
 }
 
+/// Install the row density for the current frame. Row sizing accessors
+/// ([`row_height`], [`row_gap`]) scale by it so the list and chart agree.
+pub fn set_density(density: RowDensity) {
+    DENSITY.with(|d| d.set(density));
+}
+
+/// Swap the task palette and status colours for [`COLOR_BLIND_PALETTE`]
+/// for the current frame.
+pub fn set_color_blind_safe(on: bool) {
+    COLOR_BLIND_SAFE.with(|c| c.set(on));
+}
+
+pub fn color_blind_safe() -> bool {
+    COLOR_BLIND_SAFE.with(|c| c.get())
+}
+
 /// Read the full definition (rarely needed; prefer the named accessors below).
 pub fn with_active<R>(f: impl FnOnce(&ThemeDefinition) -> R) -> R {
     ACTIVE.with(|cell| f(&cell.borrow()))
@@ -82,12 +101,31 @@ color_accessor!(row_unselected_stroke, row_unselected_stroke);
 
 // ─── Task palette ──────────────────────────────────────────────────────────
 
+/// The Okabe–Ito colours (less black), which stay apart under the common
+/// colour-vision deficiencies. Used instead of the theme's palette when the
+/// colour-blind-safe option is on.
+pub const COLOR_BLIND_PALETTE: [Color32; 7] = [
+    Color32::from_rgb(0, 114, 178),   // blue
+    Color32::from_rgb(230, 159, 0),   // orange
+    Color32::from_rgb(0, 158, 115),   // bluish green
+    Color32::from_rgb(213, 94, 0),    // vermillion
+    Color32::from_rgb(86, 180, 233),  // sky blue
+    Color32::from_rgb(204, 121, 167), // reddish purple
+    Color32::from_rgb(240, 228, 66),  // yellow
+];
+
 pub fn task_palette() -> Vec<Color32> {
+    if color_blind_safe() {
+        return COLOR_BLIND_PALETTE.to_vec();
+    }
     ACTIVE.with(|cell| cell.borrow().colors.task_palette.clone())
 }
 
 /// Convenience: get palette colour by wrapping index.
 pub fn task_color(index: usize) -> Color32 {
+    if color_blind_safe() {
+        return COLOR_BLIND_PALETTE[index % COLOR_BLIND_PALETTE.len()];
+    }
     ACTIVE.with(|cell| {
         let p = &cell.borrow().colors.task_palette;
         if p.is_empty() {
@@ -98,6 +136,30 @@ pub fn task_color(index: usize) -> Color32 {
     })
 }
 
+// ─── Status colours ────────────────────────────────────────────────────────
+
+/// How good the news a status colour carries is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Good,
+    Warning,
+    Bad,
+}
+
+/// Colour for a status: `standard`, or with the colour-blind-safe option on
+/// blue / orange / vermillion, which differ in lightness as well as hue
+/// where the usual green and red don't.
+pub fn status_color(severity: Severity, standard: Color32) -> Color32 {
+    if !color_blind_safe() {
+        return standard;
+    }
+    match severity {
+        Severity::Good => COLOR_BLIND_PALETTE[0],
+        Severity::Warning => COLOR_BLIND_PALETTE[1],
+        Severity::Bad => COLOR_BLIND_PALETTE[3],
+    }
+}
+
 // ─── Typography accessors ──────────────────────────────────────────────────
 
 pub fn font_header() -> FontId {
@@ -142,8 +204,24 @@ macro_rules! sizing_accessor {
     };
 }
 
-sizing_accessor!(row_height, row_height);
-sizing_accessor!(row_gap, row_gap);
+/// Task row height for the active theme and row density.
+pub fn row_height() -> f32 {
+    let base = ACTIVE.with(|cell| cell.borrow().sizing.row_height);
+    match DENSITY.with(Cell::get) {
+        RowDensity::Compact => (base * 0.8).round(),
+        RowDensity::Comfortable => base,
+    }
+}
+
+/// Vertical gap between task rows for the active theme and row density.
+pub fn row_gap() -> f32 {
+    let base = ACTIVE.with(|cell| cell.borrow().sizing.row_gap);
+    match DENSITY.with(Cell::get) {
+        RowDensity::Compact => base * 0.5,
+        RowDensity::Comfortable => base,
+    }
+}
+
 sizing_accessor!(header_height, header_height);
 sizing_accessor!(handle_width, handle_width);
 sizing_accessor!(bar_rounding, bar_rounding);
@@ -151,6 +229,7 @@ sizing_accessor!(bar_inset, bar_inset);
 sizing_accessor!(status_bar_height, status_bar_height);
 sizing_accessor!(side_panel_default_width, side_panel_default_width);
 sizing_accessor!(side_panel_min_width, side_panel_min_width);
+sizing_accessor!(chart_min_width, chart_min_width);
 
 // ─── Spacing accessor ──────────────────────────────────────────────────────
 
@@ -190,6 +269,39 @@ pub fn is_light() -> bool {
     ACTIVE.with(|c| c.borrow().meta.variant == "light")
 }
 
+// ─── Contrast helpers ──────────────────────────────────────────────────────
+
+/// WCAG relative luminance of an sRGB colour (0 = black, 1 = white).
+pub fn relative_luminance(c: Color32) -> f32 {
+    let lin = |v: u8| {
+        let v = v as f32 / 255.0;
+        if v <= 0.039_28 { v / 12.92 } else { ((v + 0.055) / 1.055).powf(2.4) }
+    };
+    0.2126 * lin(c.r()) + 0.7152 * lin(c.g()) + 0.0722 * lin(c.b())
+}
+
+fn contrast_ratio(a: Color32, b: Color32) -> f32 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Text colour for labels drawn on top of `bg` (task bars, accent buttons).
+/// Uses the theme's `text_on_bar` when it is legible, otherwise whichever of
+/// near-black / white contrasts more — so user-picked task colours stay
+/// readable in every theme.
+pub fn text_on(bg: Color32) -> Color32 {
+    let preferred = text_on_bar();
+    if contrast_ratio(preferred, bg) >= 4.5 {
+        return preferred;
+    }
+    let dark = Color32::from_rgb(20, 22, 28);
+    if contrast_ratio(dark, bg) > contrast_ratio(Color32::WHITE, bg) {
+        dark
+    } else {
+        Color32::WHITE
+    }
+}
+
 // ─── Apply to egui Context ─────────────────────────────────────────────────
 
 /// Applies the currently installed theme to the egui context.
@@ -230,7 +342,7 @@ pub fn apply_theme(ctx: &egui::Context) {
 
     visuals.widgets.active.bg_fill = c.widget_bg_active;
     visuals.widgets.active.bg_stroke = Stroke::new(1.0, c.accent);
-    visuals.widgets.active.fg_stroke = Stroke::new(2.0, Color32::WHITE);
+    visuals.widgets.active.fg_stroke = Stroke::new(2.0, text_on(c.widget_bg_active));
     visuals.widgets.active.rounding = wr;
 
     visuals.widgets.open.bg_fill = c.widget_bg_open;
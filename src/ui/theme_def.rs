@@ -367,6 +367,8 @@ pub struct ThemeSizing {
     pub status_bar_height: f32,
     pub side_panel_default_width: f32,
     pub side_panel_min_width: f32,
+    /// Narrowest the chart area may get when the task panel is widened.
+    pub chart_min_width: f32,
 }
 
 impl Default for ThemeSizing {
@@ -381,6 +383,7 @@ impl Default for ThemeSizing {
             status_bar_height: 24.0,
             side_panel_default_width: 340.0,
             side_panel_min_width: 240.0,
+            chart_min_width: 320.0,
         }
     }
 }
@@ -2,19 +2,53 @@
 //! Theme manager — loads, saves, switches, and enumerates themes.
 
 use crate::ui::theme_def::ThemeDefinition;
+use crate::ui::theme_overrides::{self, ThemeOverrides};
+use egui::Color32;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
-/// Persisted user settings (lives in the OS config directory).
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-#[serde(default)]
-pub struct AppSettings {
-    pub active_theme: String,
+/// How often `theme.toml` is checked for edits.
+const OVERRIDE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Quick appearance presets, each backed by a built-in theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemePreset {
+    Dark,
+    Light,
+    HighContrast,
 }
 
-impl Default for AppSettings {
-    fn default() -> Self {
-        Self {
-            active_theme: "Default Dark".into(),
+impl ThemePreset {
+    pub const ALL: [ThemePreset; 3] = [ThemePreset::Dark, ThemePreset::Light, ThemePreset::HighContrast];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ThemePreset::Dark => "Dark",
+            ThemePreset::Light => "Light",
+            ThemePreset::HighContrast => "High Contrast",
+        }
+    }
+
+    /// Name of the built-in theme this preset selects.
+    pub fn theme_name(self) -> &'static str {
+        match self {
+            ThemePreset::Dark => "Default Dark",
+            ThemePreset::Light => "Clean Light",
+            ThemePreset::HighContrast => "High Contrast",
+        }
+    }
+
+    /// The preset whose theme is `name`, if any.
+    pub fn from_theme_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.theme_name() == name)
+    }
+
+    /// Next preset in Dark → Light → High Contrast order.
+    pub fn next(self) -> Self {
+        match self {
+            ThemePreset::Dark => ThemePreset::Light,
+            ThemePreset::Light => ThemePreset::HighContrast,
+            ThemePreset::HighContrast => ThemePreset::Dark,
         }
     }
 }
@@ -27,22 +61,24 @@ pub struct ThemeManager {
     active_index: usize,
     /// Path to the user themes directory.
     themes_dir: PathBuf,
-    /// Path to the settings file.
-    settings_path: PathBuf,
+    /// Colour overrides from `theme.toml`.
+    overrides: ThemeOverrides,
+    /// Accent chosen with the in-app picker (applied after `overrides`).
+    accent: Option<Color32>,
+    /// The active theme with overrides applied — what the UI renders with.
+    effective: ThemeDefinition,
+    last_poll: Instant,
 }
 
 impl ThemeManager {
     /// Initialise the theme manager: discover config dir, load built-in +
-    /// user themes, apply persisted preference.
-    pub fn new() -> Self {
-        let (themes_dir, settings_path) = Self::config_paths();
+    /// user themes, and select the persisted `active_theme` by name.
+    pub fn new(active_theme: &str, accent: Option<Color32>) -> Self {
+        let themes_dir = crate::settings::config_dir().join("themes");
 
         // Ensure directories exist
         let _ = std::fs::create_dir_all(&themes_dir);
 
-        // Load settings
-        let settings = Self::load_settings(&settings_path);
-
         // Built-in themes
         let mut themes = builtin_themes();
 
@@ -80,22 +116,42 @@ impl ThemeManager {
         // Resolve active theme
         let active_index = themes
             .iter()
-            .position(|t| t.meta.name == settings.active_theme)
+            .position(|t| t.meta.name == active_theme)
             .unwrap_or(0);
 
-        Self {
+        let overrides = ThemeOverrides::load(&theme_overrides::default_path());
+        for warning in &overrides.warnings {
+            eprintln!("Warning: {}", warning);
+        }
+
+        let mut manager = Self {
+            effective: themes[active_index].clone(),
             themes,
             active_index,
             themes_dir,
-            settings_path,
+            overrides,
+            accent,
+            last_poll: Instant::now(),
+        };
+        manager.rebuild();
+        manager
+    }
+
+    /// Recompute the effective theme from the active theme and overrides.
+    fn rebuild(&mut self) {
+        let mut def = self.themes[self.active_index].clone();
+        self.overrides.apply(&mut def);
+        if let Some(accent) = self.accent {
+            def.colors.accent = accent;
         }
+        self.effective = def;
     }
 
     // ── Getters ─────────────────────────────────────────────────
 
-    /// The currently active theme.
+    /// The currently active theme, with user overrides applied.
     pub fn active(&self) -> &ThemeDefinition {
-        &self.themes[self.active_index]
+        &self.effective
     }
 
     /// List of (index, name) for all themes.
@@ -115,14 +171,50 @@ impl ThemeManager {
         &self.themes_dir
     }
 
+    /// Problems found in `theme.toml` on the last load.
+    pub fn override_warnings(&self) -> &[String] {
+        &self.overrides.warnings
+    }
+
+    /// The accent colour set with the in-app picker, if any.
+    pub fn accent_override(&self) -> Option<Color32> {
+        self.accent
+    }
+
     // ── Switching ───────────────────────────────────────────────
 
-    /// Switch to a theme by index.
+    /// Switch to a theme by index. The caller is responsible for persisting
+    /// the new choice in [`crate::settings::AppSettings`].
     pub fn set_active(&mut self, index: usize) {
         if index < self.themes.len() {
             self.active_index = index;
-            self.save_settings();
+            self.rebuild();
+        }
+    }
+
+    /// Set or clear the picker accent. The caller persists it.
+    pub fn set_accent(&mut self, accent: Option<Color32>) {
+        self.accent = accent;
+        self.rebuild();
+    }
+
+    /// Reload `theme.toml` if it changed on disk. Returns true if it was
+    /// reloaded, so the caller can surface any new warnings.
+    pub fn poll_overrides(&mut self) -> bool {
+        if self.last_poll.elapsed() < OVERRIDE_POLL_INTERVAL {
+            return false;
+        }
+        self.last_poll = Instant::now();
+        if !self.overrides.is_stale() {
+            return false;
         }
+        self.reload_overrides();
+        true
+    }
+
+    fn reload_overrides(&mut self) {
+        self.overrides = ThemeOverrides::load(&theme_overrides::default_path());
+        self.rebuild();
     }
 
     /// Switch to a theme by name.
@@ -132,6 +224,11 @@ impl ThemeManager {
         }
     }
 
+    /// The preset matching the active theme, or `None` for other themes.
+    pub fn active_preset(&self) -> Option<ThemePreset> {
+        ThemePreset::from_theme_name(&self.active().meta.name)
+    }
+
     /// Reload user themes from disk (e.g. after the user edits a JSON file).
     pub fn reload_user_themes(&mut self) {
         let active_name = self.themes[self.active_index].meta.name.clone();
@@ -162,40 +259,7 @@ impl ThemeManager {
             .iter()
             .position(|t| t.meta.name == active_name)
             .unwrap_or(0);
-    }
-
-    // ── Persistence helpers ─────────────────────────────────────
-
-    fn config_paths() -> (PathBuf, PathBuf) {
-        if let Some(proj_dirs) =
-            directories::ProjectDirs::from("", "", "RustGanttApp")
-        {
-            let config = proj_dirs.config_dir().to_path_buf();
-            let themes = config.join("themes");
-            let settings = config.join("settings.json");
-            (themes, settings)
-        } else {
-            // Fallback
-            let dir = PathBuf::from(".");
-            (dir.join("themes"), dir.join("settings.json"))
-        }
-    }
-
-    fn load_settings(path: &PathBuf) -> AppSettings {
-        std::fs::read_to_string(path)
-            .ok()
-            .and_then(|s| serde_json::from_str(&s).ok())
-            .unwrap_or_default()
-    }
-
-    fn save_settings(&self) {
-        let settings = AppSettings {
-            active_theme: self.themes[self.active_index].meta.name.clone(),
-        };
-        if let Ok(json) = serde_json::to_string_pretty(&settings) {
-            let _ = std::fs::create_dir_all(self.settings_path.parent().unwrap_or(&self.settings_path));
-            let _ = std::fs::write(&self.settings_path, json);
-        }
+        self.reload_overrides();
     }
 }
 
@@ -208,6 +272,7 @@ fn builtin_themes() -> Vec<ThemeDefinition> {
         warm_earth_theme(),
         dark_material_theme(),
         clean_light_theme(),
+        high_contrast_theme(),
     ]
 }
 
@@ -462,3 +527,74 @@ fn clean_light_theme() -> ThemeDefinition {
     t.rendering.sticky_shadow_alpha = 18;
     t
 }
+
+/// Maximum-contrast theme: pure black surfaces, white text, bright borders.
+fn high_contrast_theme() -> ThemeDefinition {
+    use egui::Color32;
+    let mut t = ThemeDefinition::default();
+    t.meta = crate::ui::theme_def::ThemeMeta {
+        name: "High Contrast".into(),
+        author: "Built-in".into(),
+        description: "Black background, white text and strong outlines for low vision and projectors.".into(),
+        variant: "dark".into(),
+    };
+    t.colors.bg_dark = Color32::BLACK;
+    t.colors.bg_panel = Color32::BLACK;
+    t.colors.bg_header = Color32::from_rgb(16, 16, 16);
+    t.colors.bg_row_even = Color32::from_rgba_unmultiplied(255, 255, 255, 14);
+    t.colors.bg_selected = Color32::from_rgb(0, 60, 140);
+    t.colors.bg_field = Color32::BLACK;
+
+    t.colors.border_subtle = Color32::from_rgb(160, 160, 160);
+    t.colors.border_accent = Color32::from_rgb(255, 220, 0);
+
+    t.colors.text_primary = Color32::WHITE;
+    t.colors.text_secondary = Color32::from_rgb(235, 235, 235);
+    t.colors.text_dim = Color32::from_rgb(200, 200, 200);
+    t.colors.text_on_bar = Color32::BLACK;
+
+    t.colors.accent = Color32::from_rgb(255, 220, 0);
+    t.colors.today_line = Color32::from_rgb(255, 60, 60);
+    t.colors.grid_line = Color32::from_rgb(90, 90, 90);
+    t.colors.handle_color = Color32::WHITE;
+    t.colors.weekend_shade = Color32::from_rgba_unmultiplied(255, 255, 255, 12);
+    t.colors.weekend_header_shade = Color32::from_rgba_unmultiplied(255, 255, 255, 16);
+    t.colors.progress_overlay = Color32::from_rgba_unmultiplied(0, 0, 0, 90);
+
+    t.colors.dep_arrow = Color32::from_rgb(230, 230, 230);
+    t.colors.dep_arrow_hover = Color32::from_rgb(255, 220, 0);
+    t.colors.dep_creating = Color32::from_rgb(0, 255, 255);
+
+    t.colors.widget_bg_inactive = Color32::from_rgb(24, 24, 24);
+    t.colors.widget_bg_hovered = Color32::from_rgb(48, 48, 48);
+    t.colors.widget_bg_active = Color32::from_rgb(70, 70, 70);
+    t.colors.widget_bg_open = Color32::from_rgb(40, 40, 40);
+    t.colors.faint_bg = Color32::from_rgb(10, 10, 10);
+    t.colors.extreme_bg = Color32::BLACK;
+
+    t.colors.status_bar_bg = Color32::from_rgb(16, 16, 16);
+
+    t.colors.row_selected_stroke = Color32::from_rgb(255, 220, 0);
+    t.colors.row_unselected_stroke = Color32::from_rgb(110, 110, 110);
+
+    // Bright, well-separated hues that all read against black.
+    t.colors.task_palette = vec![
+        Color32::from_rgb(80, 170, 255),
+        Color32::from_rgb(60, 220, 90),
+        Color32::from_rgb(230, 120, 255),
+        Color32::from_rgb(255, 160, 40),
+        Color32::from_rgb(0, 230, 230),
+        Color32::from_rgb(255, 90, 90),
+        Color32::from_rgb(255, 230, 60),
+        Color32::from_rgb(200, 200, 200),
+    ];
+
+    // No soft shading — solid fills with crisp edges.
+    t.rendering.bar_shadow_alpha_1 = 0;
+    t.rendering.bar_shadow_alpha_2 = 0;
+    t.rendering.bar_glaze_alpha = 0;
+    t.rendering.bar_highlight_alpha = 0;
+    t.rendering.bar_darken_factor = 1.0;
+    t.rendering.milestone_shadow_alpha = 0;
+    t
+}
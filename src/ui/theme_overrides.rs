@@ -0,0 +1,130 @@
+//! User colour overrides layered on top of the active theme.
+//!
+//! `theme.toml` in the config directory may set any [`ThemeColors`] field by
+//! name, using the same `#RRGGBB` / `#RRGGBBAA` strings as JSON themes:
+//!
+//! ```toml
+//! accent = "#E0457B"
+//! bg_dark = "#101218"
+//! task_palette = ["#3C78DC", "#2FA36B", "#C0504D"]
+//! ```
+//!
+//! Unknown keys and unparsable values are reported and skipped; everything
+//! not mentioned keeps the active theme's value.
+
+use crate::ui::theme_def::{hex_color, ThemeColors, ThemeDefinition};
+use egui::Color32;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Written when the user first opens `theme.toml` from the View menu.
+pub const TEMPLATE: &str = "\
+# Colour overrides applied on top of the active theme.
+# Any colour key from a theme JSON file may be set; changes apply on save.
+#
+# accent = \"#3C78DC\"
+# bg_dark = \"#181820\"
+# text_primary = \"#E6E8F0\"
+# task_palette = [\"#3C78DC\", \"#2FA36B\", \"#9B59B6\", \"#E67E22\"]
+";
+
+/// Location of the override file in the config directory.
+pub fn default_path() -> PathBuf {
+    crate::settings::config_dir().join("theme.toml")
+}
+
+/// Parsed contents of `theme.toml`.
+#[derive(Debug, Clone, Default)]
+pub struct ThemeOverrides {
+    path: PathBuf,
+    /// Validated `(field, value)` pairs.
+    entries: Vec<(String, serde_json::Value)>,
+    /// Problems found while reading the file, for display to the user.
+    pub warnings: Vec<String>,
+    modified: Option<SystemTime>,
+}
+
+impl ThemeOverrides {
+    /// Read and validate the override file. A missing file yields no overrides.
+    pub fn load(path: &Path) -> Self {
+        let mut overrides = Self {
+            path: path.to_path_buf(),
+            modified: modified_time(path),
+            ..Self::default()
+        };
+        let Ok(contents) = std::fs::read_to_string(path) else {
+            return overrides;
+        };
+        let table = match contents.parse::<toml::Table>() {
+            Ok(table) => table,
+            Err(e) => {
+                overrides.warnings.push(format!("theme.toml: {}", e.message()));
+                return overrides;
+            }
+        };
+
+        let known = serde_json::to_value(ThemeColors::default()).unwrap_or_default();
+        for (key, value) in table {
+            if known.get(&key).is_none() {
+                overrides.warnings.push(format!("theme.toml: unknown color '{}'", key));
+                continue;
+            }
+            match validate(&key, &value) {
+                Ok(json) => overrides.entries.push((key, json)),
+                Err(e) => overrides.warnings.push(format!("theme.toml: {}: {}", key, e)),
+            }
+        }
+        overrides
+    }
+
+    /// True if the file changed on disk since it was loaded.
+    pub fn is_stale(&self) -> bool {
+        modified_time(&self.path) != self.modified
+    }
+
+    /// Overlay the validated colours onto `def`.
+    pub fn apply(&self, def: &mut ThemeDefinition) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let Ok(serde_json::Value::Object(mut colors)) = serde_json::to_value(&def.colors) else {
+            return;
+        };
+        for (key, value) in &self.entries {
+            colors.insert(key.clone(), value.clone());
+        }
+        if let Ok(merged) = serde_json::from_value(serde_json::Value::Object(colors)) {
+            def.colors = merged;
+        }
+    }
+}
+
+/// Check a TOML value against the field's expected shape and return it in the
+/// JSON form `ThemeColors` deserialises from.
+fn validate(key: &str, value: &toml::Value) -> Result<serde_json::Value, String> {
+    let parse = |v: &toml::Value| -> Result<String, String> {
+        let s = v.as_str().ok_or_else(|| "expected a \"#RRGGBB\" string".to_string())?;
+        hex_color::parse_hex_color(s)?;
+        Ok(s.to_string())
+    };
+    if key == "task_palette" {
+        let items = value.as_array().ok_or("expected an array of colors")?;
+        if items.is_empty() {
+            return Err("palette must not be empty".into());
+        }
+        let colors = items.iter().map(parse).collect::<Result<Vec<_>, _>>()?;
+        Ok(serde_json::json!(colors))
+    } else {
+        Ok(serde_json::json!(parse(value)?))
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Format a colour as `#RRGGBB` for storage in settings.
+pub fn to_hex(color: Color32) -> String {
+    let [r, g, b, _] = color.to_array();
+    format!("#{:02X}{:02X}{:02X}", r, g, b)
+}
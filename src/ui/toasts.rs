@@ -0,0 +1,69 @@
+//! Short-lived messages stacked in the bottom-right corner of the window.
+
+use crate::ui::theme;
+use egui::{Color32, Context, RichText};
+use std::time::{Duration, Instant};
+
+/// How long a toast stays up.
+const TOAST_LIFETIME: Duration = Duration::from_secs(6);
+const ERROR_COLOR: Color32 = Color32::from_rgb(220, 60, 60);
+
+struct Toast {
+    text: String,
+    shown: Instant,
+    error: bool,
+}
+
+#[derive(Default)]
+pub struct Toasts {
+    toasts: Vec<Toast>,
+}
+
+impl Toasts {
+    /// Show `text` as an error.
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.toasts.push(Toast { text: text.into(), shown: Instant::now(), error: true });
+    }
+
+    /// Show `text` as a confirmation.
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.toasts.push(Toast { text: text.into(), shown: Instant::now(), error: false });
+    }
+
+    /// Draw the live toasts and drop expired ones. Clicking a toast
+    /// dismisses it.
+    pub fn show(&mut self, ctx: &Context) {
+        self.toasts.retain(|t| t.shown.elapsed() < TOAST_LIFETIME);
+        if self.toasts.is_empty() {
+            return;
+        }
+        let mut dismissed = None;
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-12.0, -36.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                for (i, toast) in self.toasts.iter().enumerate() {
+                    let border = if toast.error { ERROR_COLOR } else { theme::border_accent() };
+                    let frame = egui::Frame::popup(ui.style()).stroke(egui::Stroke::new(1.0, border));
+                    let resp = frame
+                        .show(ui, |ui| {
+                            ui.set_max_width(320.0);
+                            ui.label(RichText::new(&toast.text).color(theme::text_primary()));
+                        })
+                        .response
+                        .interact(egui::Sense::click());
+                    if resp.on_hover_text("Click to dismiss").clicked() {
+                        dismissed = Some(i);
+                    }
+                    ui.add_space(4.0);
+                }
+            });
+        if let Some(i) = dismissed {
+            self.toasts.remove(i);
+        }
+        // Wake up again to expire the oldest one.
+        if let Some(oldest) = self.toasts.iter().map(|t| t.shown).min() {
+            ctx.request_repaint_after(TOAST_LIFETIME.saturating_sub(oldest.elapsed()));
+        }
+    }
+}
@@ -1,75 +1,323 @@
 use crate::app::GanttApp;
+use crate::model::date_preset::{format_presets, parse_presets, DatePreset};
+use crate::model::task::{CompletedTasks, DependencyKind, TaskPriority};
+use crate::settings::{BarLabels, RowDensity, UI_SCALE_RANGE};
+use crate::i18n::{completed_tasks_label, format_datetime, priority_label, tr, trf, trn, Language};
 use crate::ui::theme;
+use crate::ui::theme_manager::ThemePreset;
+use chrono::{NaiveTime, Timelike};
 use egui::{menu, RichText, Ui};
 
+/// A menu button showing the current shortcut for `command`.
+fn menu_item(app: &GanttApp, text: &str, command: &str) -> egui::Button<'static> {
+    egui::Button::new(format!("  {}", text)).shortcut_text(app.keymap.label(command).unwrap_or_default())
+}
+
+/// Hour and minute fields for `time`. Returns true if it changed.
+fn time_fields(ui: &mut Ui, time: &mut NaiveTime) -> bool {
+    let (mut hour, mut minute) = (time.hour(), time.minute());
+    let two_digits = |n: f64, _: std::ops::RangeInclusive<usize>| format!("{:02}", n);
+    let changed = ui.add(egui::DragValue::new(&mut hour).range(0..=23).custom_formatter(two_digits)).changed()
+        | ui.add(egui::DragValue::new(&mut minute).range(0..=59).custom_formatter(two_digits)).changed();
+    if changed {
+        *time = NaiveTime::from_hms_opt(hour, minute, 0).unwrap_or(*time);
+    }
+    changed
+}
+
+/// Length and times of day of new tasks, saved with the project.
+fn task_defaults_menu(app: &mut GanttApp, ui: &mut Ui) {
+    let mut defaults = app.project.task_defaults;
+    egui::Grid::new("task-defaults").num_columns(2).show(ui, |ui| {
+        ui.label(tr("menu.edit.task_defaults.length"));
+        ui.add(egui::DragValue::new(&mut defaults.duration_days).range(1..=365).suffix(" d"));
+        ui.end_row();
+        ui.label(tr("menu.edit.task_defaults.start"));
+        ui.horizontal(|ui| time_fields(ui, &mut defaults.start_time));
+        ui.end_row();
+        ui.label(tr("menu.edit.task_defaults.end"));
+        ui.horizontal(|ui| time_fields(ui, &mut defaults.end_time));
+        ui.end_row();
+        ui.label(tr("menu.edit.task_defaults.link_kind"));
+        ui.horizontal(|ui| {
+            for &kind in DependencyKind::all() {
+                ui.selectable_value(&mut defaults.dependency_kind, kind, kind.short_label())
+                    .on_hover_text(kind.description());
+            }
+        });
+        ui.end_row();
+    });
+    if defaults != app.project.task_defaults {
+        app.project.task_defaults = defaults;
+        app.project.touch();
+    }
+}
+
+/// A comma-separated list of date presets, applied as soon as it reads as
+/// one. Returns true if `presets` changed.
+fn presets_field(ui: &mut Ui, id_salt: &str, presets: &mut Vec<DatePreset>) -> bool {
+    let id = ui.make_persistent_id(id_salt);
+    let mut text = ui.data(|d| d.get_temp::<String>(id)).unwrap_or_else(|| format_presets(presets));
+    let mut changed = false;
+    ui.vertical(|ui| {
+        let resp = ui.add(egui::TextEdit::singleline(&mut text).desired_width(220.0));
+        match parse_presets(&text) {
+            Ok(parsed) if resp.changed() && parsed != *presets => {
+                *presets = parsed;
+                changed = true;
+            }
+            Ok(_) => {}
+            Err(entry) => {
+                ui.label(
+                    RichText::new(trf("menu.edit.date_presets.invalid", &[("entry", &entry)]))
+                        .size(10.0)
+                        .color(theme::status_color(theme::Severity::Bad, egui::Color32::from_rgb(220, 60, 60))),
+                );
+            }
+        }
+        // Keep what is being typed; once the field is left, show the list
+        // as it was last applied.
+        if resp.has_focus() {
+            ui.data_mut(|d| d.insert_temp(id, text));
+        } else {
+            ui.data_mut(|d| d.remove::<String>(id));
+        }
+    });
+    changed
+}
+
+/// The quick dates the editor offers beside its date fields, saved with
+/// the project.
+fn date_presets_menu(app: &mut GanttApp, ui: &mut Ui) {
+    let mut presets = app.project.date_presets.clone();
+    let mut changed = false;
+    egui::Grid::new("date-presets").num_columns(2).show(ui, |ui| {
+        ui.label(tr("menu.edit.date_presets.start"));
+        changed |= presets_field(ui, "date-presets-start", &mut presets.start);
+        ui.end_row();
+        ui.label(tr("menu.edit.date_presets.end"));
+        changed |= presets_field(ui, "date-presets-end", &mut presets.end);
+        ui.end_row();
+    });
+    ui.label(RichText::new(tr("menu.edit.date_presets.hint")).size(10.0).color(theme::text_dim()));
+    changed |= ui.checkbox(&mut presets.skip_weekends, tr("menu.edit.date_presets.skip_weekends")).changed();
+    if ui.button(tr("menu.edit.date_presets.reset")).clicked() {
+        presets = Default::default();
+        changed = true;
+    }
+    if changed {
+        app.project.date_presets = presets;
+        app.project.touch();
+    }
+}
+
 /// Render the top toolbar / menu bar.
 pub fn show_toolbar(app: &mut GanttApp, ui: &mut Ui) {
     menu::bar(ui, |ui| {
-        ui.menu_button(RichText::new("  File  ").font(theme::font_menu()), |ui| {
-            if ui.button("  New Project").clicked() {
+        ui.menu_button(RichText::new(format!("  {}  ", tr("menu.file"))).font(theme::font_menu()), |ui| {
+            if ui.button(format!("  {}", tr("menu.file.new"))).clicked() {
                 app.new_project();
                 ui.close_menu();
             }
-            if ui.button("  Open...").clicked() {
+            if ui.add(menu_item(app, tr("menu.file.open"), "file.open")).clicked() {
                 app.open_project();
                 ui.close_menu();
             }
             ui.separator();
-            if ui.button("  Save          Ctrl+S").clicked() {
+            if ui.add(menu_item(app, tr("menu.file.save"), "file.save")).clicked() {
                 app.save_project();
                 ui.close_menu();
             }
-            if ui.button("  Save As...").clicked() {
+            if ui.add(menu_item(app, tr("menu.file.save_as"), "file.save_as")).clicked() {
                 app.save_project_as();
                 ui.close_menu();
             }
+            if ui.add(menu_item(app, tr("menu.file.compare"), "file.compare")).clicked() {
+                app.compare_with_file();
+                ui.close_menu();
+            }
+            if ui.add(menu_item(app, tr("menu.file.save_hook"), "file.save_hook")).clicked() {
+                app.show_save_hook = true;
+                ui.close_menu();
+            }
             ui.separator();
-            if ui.button("  Import CSV...").clicked() {
+            if ui.button(format!("  {}", tr("menu.file.import_csv"))).clicked() {
                 app.import_csv();
                 ui.close_menu();
             }
-            if ui.button("  Export CSV...").clicked() {
-                app.export_csv();
+            if ui.button(format!("  {}", tr("menu.file.import_jira"))).clicked() {
+                app.import_jira_csv();
+                ui.close_menu();
+            }
+            if ui.button(format!("  {}", tr("menu.file.import_markdown"))).clicked() {
+                app.import_markdown();
+                ui.close_menu();
+            }
+            if ui.button(format!("  {}", tr("menu.file.import_github"))).clicked() {
+                app.show_github_import = true;
                 ui.close_menu();
             }
+            ui.menu_button(format!("  {}", tr("menu.file.export")), |ui| {
+                let names: Vec<&'static str> = app.exporters.iter().map(|e| e.name()).collect();
+                for name in names {
+                    if ui.button(format!("{}...", name)).clicked() {
+                        app.begin_export(name);
+                        ui.close_menu();
+                    }
+                }
+            });
+            ui.separator();
+            if ui
+                .checkbox(&mut app.settings.restore_session, tr("menu.file.restore_session"))
+                .on_hover_text(tr("menu.file.restore_session.hint"))
+                .changed()
+            {
+                app.settings.save();
+            }
         });
 
-        ui.menu_button(RichText::new("  Edit  ").font(theme::font_menu()), |ui| {
+        ui.menu_button(RichText::new(format!("  {}  ", tr("menu.edit"))).font(theme::font_menu()), |ui| {
             let can_undo = app.undo_history.can_undo();
             let can_redo = app.undo_history.can_redo();
 
             if ui
-                .add_enabled(can_undo, egui::Button::new("  Undo         Ctrl+Z"))
+                .add_enabled(can_undo, menu_item(app, tr("menu.edit.undo"), "edit.undo"))
+                .on_hover_text(app.undo_history.undo_label().unwrap_or_default())
                 .clicked()
             {
                 app.undo();
                 ui.close_menu();
             }
             if ui
-                .add_enabled(can_redo, egui::Button::new("  Redo         Ctrl+Y"))
+                .add_enabled(can_redo, menu_item(app, tr("menu.edit.redo"), "edit.redo"))
+                .on_hover_text(app.undo_history.redo_label().unwrap_or_default())
                 .clicked()
             {
                 app.redo();
                 ui.close_menu();
             }
+            ui.separator();
+            if ui
+                .add_enabled(app.selected_task.is_some(), menu_item(app, tr("menu.edit.shift_dates"), "task.shift_dates"))
+                .clicked()
+            {
+                app.show_shift_dates = true;
+                ui.close_menu();
+            }
+            if ui
+                .checkbox(&mut app.settings.nudge_working_days, tr("menu.edit.nudge_working_days"))
+                .on_hover_text(tr("menu.edit.nudge_working_days.hint"))
+                .changed()
+            {
+                app.settings.save();
+            }
+            let parent = app.selected_task.filter(|id| app.project.tasks.iter().any(|t| t.parent_id == Some(*id)));
+            if ui
+                .add_enabled(parent.is_some(), menu_item(app, tr("menu.edit.arrange_children"), "task.arrange_children"))
+                .clicked()
+            {
+                app.pending_arrange = parent;
+                ui.close_menu();
+            }
+            if ui
+                .add(menu_item(app, tr("menu.edit.copy_chart_image"), "edit.copy_chart_image"))
+                .on_hover_text(tr("menu.edit.copy_chart_image.hint"))
+                .clicked()
+            {
+                let whole_project = ui.input(|i| i.modifiers.alt);
+                app.copy_chart_image(ui.ctx(), whole_project);
+                ui.close_menu();
+            }
+            if ui.add(menu_item(app, tr("menu.edit.violations"), "schedule.violations")).clicked() {
+                app.show_violations = true;
+                ui.close_menu();
+            }
+            if ui.add(menu_item(app, tr("menu.edit.resolve_violations"), "schedule.resolve_violations")).clicked() {
+                app.show_reschedule = true;
+                ui.close_menu();
+            }
+            if ui.add(menu_item(app, tr("menu.edit.time_zone"), "project.time_zone")).clicked() {
+                app.show_time_zone = true;
+                ui.close_menu();
+            }
+            if ui.add(menu_item(app, tr("menu.edit.target_end"), "project.target_end")).clicked() {
+                app.show_target_end = true;
+                ui.close_menu();
+            }
+            if ui.add(menu_item(app, tr("menu.edit.highlight_rules"), "project.highlight_rules")).clicked() {
+                app.show_highlight_rules = true;
+                ui.close_menu();
+            }
+            if ui.add(menu_item(app, tr("menu.edit.progress_policy"), "project.progress_policy")).clicked() {
+                app.show_progress_policy = true;
+                ui.close_menu();
+            }
+            if ui.add(menu_item(app, tr("menu.edit.resources"), "project.resources")).clicked() {
+                app.show_resources = true;
+                ui.close_menu();
+            }
+            let mut logging = app.project.log_task_progress;
+            if ui
+                .checkbox(&mut logging, tr("menu.edit.log_task_progress"))
+                .on_hover_text(tr("menu.edit.log_task_progress.hint"))
+                .changed()
+            {
+                app.toggle_task_progress_log();
+                ui.close_menu();
+            }
+            ui.menu_button(format!("  {}", tr("menu.edit.task_defaults")), |ui| task_defaults_menu(app, ui));
+            ui.menu_button(format!("  {}", tr("menu.edit.date_presets")), |ui| date_presets_menu(app, ui));
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label(format!("  {}", tr("menu.edit.comment_as")));
+                let resp = ui.add(
+                    egui::TextEdit::singleline(&mut app.settings.author_name)
+                        .hint_text(tr("menu.edit.anonymous"))
+                        .desired_width(120.0),
+                );
+                if resp.lost_focus() {
+                    app.settings.save();
+                }
+            });
         });
 
-        ui.menu_button(RichText::new("  View  ").font(theme::font_menu()), |ui| {
-            if ui.button("  Zoom In        Ctrl+Scroll ↑").clicked() {
+        ui.menu_button(RichText::new(format!("  {}  ", tr("menu.view"))).font(theme::font_menu()), |ui| {
+            if ui.add(menu_item(app, tr("menu.view.command_palette"), crate::commands::OPEN_PALETTE)).clicked() {
+                app.show_command_palette = true;
+                ui.close_menu();
+            }
+            if ui.add(menu_item(app, tr("menu.view.fit"), "view.fit")).clicked() {
+                app.run_command(ui.ctx(), "view.fit");
+                ui.close_menu();
+            }
+            if ui.add(menu_item(app, tr("menu.view.zoom_selection"), "view.zoom_selection")).clicked() {
+                app.run_command(ui.ctx(), "view.zoom_selection");
+                ui.close_menu();
+            }
+            if ui.add(menu_item(app, tr("menu.view.activity"), "view.activity")).clicked() {
+                app.show_activity = true;
+                ui.close_menu();
+            }
+            if ui.add(menu_item(app, tr("menu.view.dependencies"), "view.dependencies")).clicked() {
+                app.show_dependencies = true;
+                ui.close_menu();
+            }
+            ui.separator();
+            if ui.add(menu_item(app, tr("menu.view.zoom_in"), "view.zoom_in")).clicked() {
                 app.viewport.zoom_in();
                 ui.close_menu();
             }
-            if ui.button("  Zoom Out      Ctrl+Scroll ↓").clicked() {
+            if ui.add(menu_item(app, tr("menu.view.zoom_out"), "view.zoom_out")).clicked() {
                 app.viewport.zoom_out();
                 ui.close_menu();
             }
             ui.separator();
-            ui.label(RichText::new("Timeline Scale").small().weak());
+            ui.label(RichText::new(tr("menu.view.timeline_scale")).small().weak());
             if ui
                 .radio_value(
                     &mut app.viewport.scale,
                     crate::model::TimelineScale::Days,
-                    "Days",
+                    tr("menu.view.days"),
                 )
                 .clicked()
             {
@@ -79,7 +327,7 @@ pub fn show_toolbar(app: &mut GanttApp, ui: &mut Ui) {
                 .radio_value(
                     &mut app.viewport.scale,
                     crate::model::TimelineScale::Weeks,
-                    "Weeks",
+                    tr("menu.view.weeks"),
                 )
                 .clicked()
             {
@@ -89,50 +337,389 @@ pub fn show_toolbar(app: &mut GanttApp, ui: &mut Ui) {
                 .radio_value(
                     &mut app.viewport.scale,
                     crate::model::TimelineScale::Months,
-                    "Months",
+                    tr("menu.view.months"),
                 )
                 .clicked()
             {
                 ui.close_menu();
             }
             ui.separator();
-            ui.label(RichText::new("Theme").small().weak());
+            ui.label(RichText::new(tr("menu.view.display")).small().weak());
+            // Edit a copy and apply on release so the menu doesn't rescale under the pointer.
+            let scale_id = egui::Id::new("ui-scale-edit");
+            let mut scale = ui
+                .ctx()
+                .data(|d| d.get_temp::<f32>(scale_id))
+                .unwrap_or(app.settings.ui_scale);
+            let resp = ui.add(
+                egui::Slider::new(&mut scale, UI_SCALE_RANGE)
+                    .step_by(0.05)
+                    .fixed_decimals(2)
+                    .suffix("×")
+                    .text(tr("menu.view.ui_scale")),
+            );
+            if resp.dragged() {
+                ui.ctx().data_mut(|d| d.insert_temp(scale_id, scale));
+            } else if resp.drag_stopped() || resp.changed() {
+                ui.ctx().data_mut(|d| d.remove::<f32>(scale_id));
+                let ctx = ui.ctx().clone();
+                app.set_ui_scale(&ctx, scale);
+            }
+            ui.horizontal(|ui| {
+                ui.label(format!("  {}", tr("menu.view.rows")));
+                for density in [RowDensity::Compact, RowDensity::Comfortable] {
+                    if ui.radio(app.settings.row_density == density, density.label()).clicked() {
+                        app.set_row_density(density);
+                    }
+                }
+            });
+            if ui
+                .checkbox(&mut app.settings.wrap_task_names, tr("menu.view.wrap_names"))
+                .on_hover_text(tr("menu.view.wrap_names.hint"))
+                .changed()
+            {
+                app.settings.save();
+            }
+            ui.horizontal(|ui| {
+                ui.label(format!("  {}", tr("menu.view.bar_labels")));
+                for labels in [BarLabels::Inside, BarLabels::Right, BarLabels::Hidden] {
+                    if ui.radio(app.settings.bar_labels == labels, labels.label()).clicked() {
+                        app.settings.bar_labels = labels;
+                        app.settings.save();
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(format!("  {}", tr("menu.view.completed")));
+                for mode in [CompletedTasks::Show, CompletedTasks::Strike, CompletedTasks::Hide] {
+                    if ui.radio(app.project.completed_tasks == mode, completed_tasks_label(mode)).clicked() {
+                        app.set_completed_tasks(mode);
+                    }
+                }
+            });
+            ui.separator();
+            ui.label(RichText::new(tr("menu.view.panels")).small().weak());
+            if ui.checkbox(&mut app.settings.show_task_list, tr("menu.view.task_list")).changed() {
+                app.settings.save();
+            }
+            if ui.checkbox(&mut app.settings.show_filter_bar, tr("menu.view.filter_bar")).changed() {
+                app.settings.save();
+            }
+            if ui.checkbox(&mut app.settings.show_milestone_strip, tr("menu.view.milestone_strip")).changed() {
+                app.settings.save();
+            }
+            if ui.checkbox(&mut app.settings.show_milestone_lane, tr("menu.view.milestone_lane")).changed() {
+                app.settings.save();
+            }
+            if app.settings.show_milestone_lane
+                && ui
+                    .checkbox(&mut app.settings.milestone_lane_key_only, tr("menu.view.milestone_lane.key_only"))
+                    .on_hover_text(tr("menu.view.milestone_lane.key_only.hint"))
+                    .changed()
+            {
+                app.settings.save();
+            }
+            if ui.checkbox(&mut app.settings.show_agenda, tr("menu.view.agenda")).changed() {
+                app.settings.save();
+            }
+            if app.settings.show_agenda
+                && ui.checkbox(&mut app.settings.agenda_beside_list, tr("menu.view.agenda_beside_list")).changed()
+            {
+                app.settings.save();
+            }
+            if ui.checkbox(&mut app.settings.show_editor, tr("menu.view.editor")).changed() {
+                app.settings.save();
+            }
+            ui.separator();
+            if ui
+                .checkbox(&mut app.settings.trace_dependencies, tr("menu.view.trace"))
+                .on_hover_text(tr("menu.view.trace.hint"))
+                .changed()
+            {
+                app.settings.save();
+            }
+            if ui
+                .checkbox(&mut app.settings.schedule_tint, tr("menu.view.schedule_tint"))
+                .on_hover_text(tr("menu.view.schedule_tint.hint"))
+                .changed()
+            {
+                app.settings.save();
+            }
+            if ui
+                .checkbox(&mut app.settings.color_blind_safe, tr("menu.view.color_blind_safe"))
+                .on_hover_text(tr("menu.view.color_blind_safe.hint"))
+                .changed()
+            {
+                app.settings.save();
+            }
+            ui.separator();
+            ui.label(RichText::new(tr("menu.view.appearance")).small().weak());
+            let active_preset = app.theme_manager.active_preset();
+            for preset in ThemePreset::ALL {
+                if ui.radio(active_preset == Some(preset), preset.label()).clicked() {
+                    app.set_theme_preset(preset);
+                    ui.close_menu();
+                }
+            }
+            let next = active_preset.map_or(ThemePreset::Dark, ThemePreset::next);
+            if ui.add(menu_item(app, tr("menu.view.toggle_appearance"), "view.toggle_appearance")).clicked() {
+                app.set_theme_preset(next);
+                ui.close_menu();
+            }
+            ui.horizontal(|ui| {
+                ui.label(format!("  {}", tr("menu.view.accent")));
+                let mut accent = theme::accent();
+                if egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut accent,
+                    egui::color_picker::Alpha::Opaque,
+                )
+                .changed()
+                {
+                    app.set_accent_color(Some(accent));
+                }
+                if app.theme_manager.accent_override().is_some()
+                    && ui.small_button(tr("menu.view.reset")).on_hover_text(tr("menu.view.reset.hint")).clicked()
+                {
+                    app.set_accent_color(None);
+                }
+            });
+            ui.separator();
+            ui.label(RichText::new(tr("menu.view.theme")).small().weak());
             let themes = app.theme_manager.list();
             let active_idx = app.theme_manager.active_index();
             for (idx, name) in &themes {
                 let selected = *idx == active_idx;
                 if ui.radio(selected, name).clicked() {
-                    app.theme_manager.set_active(*idx);
+                    app.set_theme(*idx);
                     ui.close_menu();
                 }
             }
             ui.separator();
-            if ui.button("  Reload Themes").clicked() {
+            if ui.button(format!("  {}", tr("menu.view.reload_themes"))).clicked() {
                 app.theme_manager.reload_user_themes();
                 ui.close_menu();
             }
-            if ui.button("  Open Themes Folder").clicked() {
+            if ui.button(format!("  {}", tr("menu.view.edit_overrides"))).clicked() {
+                app.open_theme_overrides();
+                ui.close_menu();
+            }
+            if ui.button(format!("  {}", tr("menu.view.themes_folder"))).clicked() {
                 let dir = app.theme_manager.themes_dir().clone();
                 let _ = open::that(&dir);
                 ui.close_menu();
             }
+            ui.separator();
+            ui.label(RichText::new(tr("menu.view.language")).small().weak());
+            for language in Language::ALL {
+                if ui.radio(app.settings.language == language, language.label()).clicked() {
+                    app.settings.language = language;
+                    app.settings.save();
+                    ui.close_menu();
+                }
+            }
         });
 
-        ui.menu_button(RichText::new("  Help  ").font(theme::font_menu()), |ui| {
-            if ui.button("About").clicked() {
+        ui.menu_button(RichText::new(format!("  {}  ", tr("menu.help"))).font(theme::font_menu()), |ui| {
+            if ui.button(tr("menu.help.shortcuts")).clicked() {
+                app.show_shortcuts = true;
+                ui.close_menu();
+            }
+            if ui.button(tr("menu.help.tour")).clicked() {
+                app.restart_tour(ui.ctx());
+                ui.close_menu();
+            }
+            if ui.button(tr("menu.help.about")).clicked() {
                 app.show_about = true;
                 ui.close_menu();
             }
         });
 
+        ui.separator();
+        history_buttons(app, ui);
+
         // Right-aligned project name
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-            let modified = if app.file_path.is_some() { "" } else { " (unsaved)" };
+            let modified = if app.file_path.is_some() { "" } else { tr("toolbar.unsaved") };
             ui.label(
                 RichText::new(format!("{}{}", app.project.name, modified))
                     .size(11.0)
                     .weak(),
             );
+            reminders_menu(app, ui);
+            workspace_menu(app, ui);
+            let copy = egui::Button::new(RichText::new(egui_phosphor::regular::CAMERA).color(theme::text_secondary()))
+                .frame(false);
+            if ui.add(copy).on_hover_text(tr("menu.edit.copy_chart_image.hint")).clicked() {
+                let whole_project = ui.input(|i| i.modifiers.alt);
+                app.copy_chart_image(ui.ctx(), whole_project);
+            }
+        });
+    });
+}
+
+/// Entries listed under the undo and redo buttons' arrows.
+const HISTORY_MENU_STEPS: usize = 10;
+
+/// Undo and redo buttons, each with an arrow listing the steps behind it;
+/// picking an older step undoes (or redoes) everything up to it.
+fn history_buttons(app: &mut GanttApp, ui: &mut Ui) {
+    let sides = [
+        (egui_phosphor::regular::ARROW_COUNTER_CLOCKWISE, ["toolbar.undo", "toolbar.undo.none", "toolbar.undo.steps"], "edit.undo", false),
+        (egui_phosphor::regular::ARROW_CLOCKWISE, ["toolbar.redo", "toolbar.redo.none", "toolbar.redo.steps"], "edit.redo", true),
+    ];
+    for (icon, [hint_key, none_key, steps_key], command, redo) in sides {
+        let labels: Vec<String> = if redo {
+            app.undo_history.redo_labels().take(HISTORY_MENU_STEPS).map(str::to_string).collect()
+        } else {
+            app.undo_history.undo_labels().take(HISTORY_MENU_STEPS).map(str::to_string).collect()
+        };
+        let mut steps = None;
+        ui.add_enabled_ui(!labels.is_empty(), |ui| {
+            ui.spacing_mut().item_spacing.x = 0.0;
+            let button = egui::Button::new(RichText::new(icon).color(theme::text_secondary())).frame(false);
+            let shortcut = app.keymap.label(command).map(|k| format!(" ({})", k)).unwrap_or_default();
+            let hint = match labels.first() {
+                Some(label) => format!("{}{}", trf(hint_key, &[("label", label)]), shortcut),
+                None => tr(none_key).to_string(),
+            };
+            if ui.add(button).on_hover_text(&hint).on_disabled_hover_text(&hint).clicked() {
+                steps = Some(1);
+            }
+            let arrow = RichText::new(egui_phosphor::regular::CARET_DOWN).size(9.0).color(theme::text_dim());
+            ui.menu_button(arrow, |ui| {
+                ui.set_min_width(200.0);
+                for (i, label) in labels.iter().enumerate() {
+                    if ui.button(label).on_hover_text(trn(steps_key, i + 1, &[])).clicked() {
+                        steps = Some(i + 1);
+                        ui.close_menu();
+                    }
+                }
+            });
+        });
+        match steps {
+            Some(steps) if redo => app.redo_steps(steps),
+            Some(steps) => app.undo_steps(steps),
+            None => {}
+        }
+    }
+}
+
+/// Switcher between the workspaces in the settings, with the current
+/// layout saved into the active one.
+fn workspace_menu(app: &mut GanttApp, ui: &mut Ui) {
+    let active = app.settings.active_workspace.clone();
+    let label = RichText::new(format!(
+        "{} {}",
+        egui_phosphor::regular::LAYOUT,
+        active.as_deref().unwrap_or(tr("workspace.none"))
+    ))
+    .size(11.0)
+    .color(theme::text_secondary());
+    ui.menu_button(label, |ui| {
+        let names: Vec<String> = app.settings.workspaces.iter().map(|w| w.name.clone()).collect();
+        let mut active_index = None;
+        for (i, name) in names.iter().enumerate() {
+            let is_active = active.as_deref() == Some(name.as_str());
+            if is_active {
+                active_index = Some(i);
+            }
+            if ui.radio(is_active, name).clicked() {
+                app.apply_workspace(i);
+                ui.close_menu();
+            }
+        }
+        ui.separator();
+        if let Some(i) = active_index {
+            if ui
+                .button(trf("workspace.update", &[("name", &names[i])]))
+                .on_hover_text(tr("workspace.update.hint"))
+                .clicked()
+            {
+                app.update_workspace(i);
+                ui.close_menu();
+            }
+        }
+        if ui.button(tr("workspace.reset")).clicked() {
+            app.reset_workspaces();
+            ui.close_menu();
+        }
+    })
+    .response
+    .on_hover_text(tr("workspace.hint"));
+}
+
+/// Bell listing tasks coming due, with snooze and reminder preferences.
+fn reminders_menu(app: &mut GanttApp, ui: &mut Ui) {
+    let count = app.reminders.len();
+    let bell = if count > 0 {
+        RichText::new(format!("{} {}", egui_phosphor::regular::BELL_RINGING, count))
+            .color(egui::Color32::from_rgb(220, 140, 40))
+    } else {
+        RichText::new(egui_phosphor::regular::BELL).color(theme::text_secondary())
+    };
+    let ctx = ui.ctx().clone();
+    ui.menu_button(bell, |ui| {
+        ui.set_min_width(260.0);
+        let now = chrono::Local::now().naive_local();
+        if app.reminders.is_empty() {
+            ui.label(RichText::new(tr("reminders.none")).size(11.0).color(theme::text_dim()));
+        }
+        let mut reveal = None;
+        let mut snooze = None;
+        for reminder in &app.reminders {
+            ui.horizontal(|ui| {
+                if ui
+                    .button(format!("{} · {}", reminder.name, reminder.due_in(now)))
+                    .on_hover_text(trf("reminders.due", &[("when", &format_datetime(reminder.due, "date.reminder_due"))]))
+                    .clicked()
+                {
+                    reveal = Some(reminder.task_id);
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button(tr("reminders.snooze")).on_hover_text(tr("reminders.snooze.hint")).clicked() {
+                        snooze = Some(reminder.task_id);
+                    }
+                });
+            });
+        }
+        if let Some(id) = reveal {
+            app.main_view = crate::ui::view_tabs::MainView::Gantt;
+            app.reveal_task(&ctx, id);
+            ui.close_menu();
+        }
+        if let Some(id) = snooze {
+            app.snooze_reminder(id);
+        }
+
+        ui.separator();
+        let prefs = &mut app.settings.notifications;
+        let mut changed = ui.checkbox(&mut prefs.enabled, tr("reminders.desktop")).changed();
+        ui.horizontal(|ui| {
+            ui.label(tr("reminders.remind"));
+            changed |= ui
+                .add(egui::DragValue::new(&mut prefs.lead_hours).range(1..=24 * 14).suffix(" h"))
+                .changed();
+            ui.label(tr("reminders.before_due"));
+        });
+        ui.label(RichText::new(tr("reminders.priorities")).size(11.0).color(theme::text_dim()));
+        ui.horizontal_wrapped(|ui| {
+            for priority in TaskPriority::all() {
+                let mut on = prefs.priorities.contains(priority);
+                let label = priority_label(*priority);
+                if ui.checkbox(&mut on, label).changed() {
+                    if on {
+                        prefs.priorities.push(*priority);
+                    } else {
+                        prefs.priorities.retain(|p| p != priority);
+                    }
+                    changed = true;
+                }
+            }
         });
+        if changed {
+            app.settings.save();
+            app.last_reminder_check = None;
+        }
     });
 }
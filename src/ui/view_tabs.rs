@@ -0,0 +1,124 @@
+use crate::i18n::format_datetime;
+use crate::model::forecast::Forecast;
+use crate::ui::{legend, theme};
+use chrono::NaiveDate;
+use egui::{RichText, Ui};
+
+/// Which view fills the chart area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum MainView {
+    #[default]
+    Gantt,
+    Calendar,
+    Resources,
+    Network,
+    Progress,
+}
+
+impl MainView {
+    pub const ALL: [MainView; 5] = [
+        MainView::Gantt,
+        MainView::Calendar,
+        MainView::Resources,
+        MainView::Network,
+        MainView::Progress,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MainView::Gantt => "Gantt",
+            MainView::Calendar => "Calendar",
+            MainView::Resources => "Resources",
+            MainView::Network => "Network",
+            MainView::Progress => "Progress",
+        }
+    }
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            MainView::Gantt => egui_phosphor::regular::CHART_BAR_HORIZONTAL,
+            MainView::Calendar => egui_phosphor::regular::CALENDAR_BLANK,
+            MainView::Resources => egui_phosphor::regular::USERS,
+            MainView::Network => egui_phosphor::regular::GRAPH,
+            MainView::Progress => egui_phosphor::regular::CHART_LINE_UP,
+        }
+    }
+}
+
+/// Render the tab strip above the chart area, with the project's span,
+/// forecast finish and (on the Gantt view) the legend on the right.
+/// Returns true when the forecast is clicked.
+pub fn show_view_tabs(
+    current: &mut MainView,
+    forecast: Option<Forecast>,
+    target_end: Option<NaiveDate>,
+    ui: &mut Ui,
+) -> bool {
+    let mut clicked = false;
+    egui::Frame::default()
+        .fill(theme::bg_header())
+        .inner_margin(egui::Margin::symmetric(6.0, 3.0))
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.horizontal(|ui| {
+                for view in MainView::ALL {
+                    let text = RichText::new(format!("{} {}", view.icon(), view.label())).size(11.5);
+                    ui.selectable_value(current, view, text);
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if *current == MainView::Gantt {
+                        legend::legend_button(ui);
+                    }
+                    if let Some(forecast) = forecast {
+                        clicked = forecast_summary(&forecast, target_end, ui);
+                    }
+                });
+            });
+        });
+    clicked
+}
+
+/// `Mar 4 → Mar 14 · 9 working days · forecast Mar 24`, in red when the
+/// forecast runs past the target end.
+fn forecast_summary(forecast: &Forecast, target_end: Option<NaiveDate>, ui: &mut Ui) -> bool {
+    let day = |at| format_datetime(at, "date.month_day");
+    let late = target_end.and_then(|target| forecast.days_late(target));
+    let mut text = format!(
+        "{} → {} · {} working day{}",
+        day(forecast.start),
+        day(forecast.end),
+        forecast.working_days,
+        if forecast.working_days == 1 { "" } else { "s" }
+    );
+    match forecast.finish {
+        Some(finish) => text.push_str(&format!(" · forecast {}", day(finish))),
+        None => text.push_str(" · forecast —"),
+    }
+    let text = match late {
+        Some(_) => RichText::new(format!("{} {}", egui_phosphor::regular::WARNING, text)).color(egui::Color32::from_rgb(220, 60, 60)),
+        None => RichText::new(text).color(theme::text_secondary()),
+    };
+
+    let mut hover = String::from(
+        "Forecast: when the work will be done if it keeps the pace so far, \
+         from the elapsed time and the project's completion.",
+    );
+    match (target_end, late) {
+        (Some(target), Some(days)) => hover.push_str(&format!(
+            "\n{} day{} past the target end of {}.",
+            days,
+            if days == 1 { "" } else { "s" },
+            format_datetime(target.and_time(chrono::NaiveTime::MIN), "date.long")
+        )),
+        (Some(target), None) => hover.push_str(&format!(
+            "\nTarget end: {}.",
+            format_datetime(target.and_time(chrono::NaiveTime::MIN), "date.long")
+        )),
+        (None, _) => {}
+    }
+    hover.push_str("\nClick to set the target end date.");
+    ui.add(egui::Label::new(text.size(11.0)).sense(egui::Sense::click()))
+        .on_hover_text(hover)
+        .on_hover_cursor(egui::CursorIcon::PointingHand)
+        .clicked()
+}